@@ -0,0 +1,6 @@
+-- Migration: Add straddle option to tables
+-- Date: October 15, 2026
+-- Description: Persists whether the under-the-gun player can post a straddle
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS straddle_allowed BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,6 @@
+-- Migration: Add ante to tables
+-- Date: October 15, 2026
+-- Description: Persists the ante collected from every player before the blinds
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS ante BIGINT NOT NULL DEFAULT 0 CHECK (ante >= 0);
@@ -0,0 +1,6 @@
+-- Migration: Add per-player time bank to tables
+-- Date: October 15, 2026
+-- Description: Persists the time bank players can use once the action timeout runs out
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS time_bank_secs INT NOT NULL DEFAULT 0 CHECK (time_bank_secs >= 0);
@@ -0,0 +1,6 @@
+-- Migration: Add run-it-twice option to tables
+-- Date: October 15, 2026
+-- Description: Persists whether all-in pots can be run twice with every player's consent
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS run_it_twice_enabled BOOLEAN NOT NULL DEFAULT FALSE;
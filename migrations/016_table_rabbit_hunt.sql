@@ -0,0 +1,6 @@
+-- Migration: Add rabbit hunt option to tables
+-- Date: October 16, 2026
+-- Description: Persists whether players can see the undealt board after a hand ends early
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS rabbit_hunt_enabled BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,7 @@
+-- Migration: Add poker variant to tables
+-- Date: October 16, 2026
+-- Description: Persists whether a table plays Texas Hold'em or short-deck (6+) Hold'em
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS variant VARCHAR(20) NOT NULL DEFAULT 'texas_holdem'
+    CHECK (variant IN ('texas_holdem', 'short_deck'));
@@ -0,0 +1,14 @@
+-- Migration: Add hand histories for tables
+-- Date: October 16, 2026
+-- Description: Persists a structured JSON record of every completed hand for review and export
+
+CREATE TABLE IF NOT EXISTS table_hand_histories (
+    id BIGSERIAL PRIMARY KEY,
+    table_id BIGINT NOT NULL,
+    hand_number BIGINT NOT NULL,
+    history JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_table_hand_histories_table_hand
+    ON table_hand_histories(table_id, hand_number);
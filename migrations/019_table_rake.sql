@@ -0,0 +1,17 @@
+-- Migration: Add rake settings to tables
+-- Date: October 16, 2026
+-- Description: Persists the rake percentage (in basis points) and per-hand rake cap, and adds
+-- per-table rake accounts that collect the rake taken from table escrows
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS rake_bps INT NOT NULL DEFAULT 0 CHECK (rake_bps >= 0 AND rake_bps <= 10000),
+ADD COLUMN IF NOT EXISTS rake_cap_chips BIGINT NOT NULL DEFAULT 0 CHECK (rake_cap_chips >= 0);
+
+CREATE TABLE IF NOT EXISTS rake_accounts (
+    table_id BIGINT PRIMARY KEY,
+    balance BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT positive_balance CHECK (balance >= 0)
+);
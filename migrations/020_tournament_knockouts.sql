@@ -0,0 +1,20 @@
+-- Migration: Add knockout tournaments
+-- Date: October 16, 2026
+-- Description: Allows knockout tournaments, tracks each registrant's bounty, and records bounty payouts in the ledger
+
+ALTER TABLE tournaments
+DROP CONSTRAINT IF EXISTS tournaments_tournament_type_check;
+
+ALTER TABLE tournaments
+ADD CONSTRAINT tournaments_tournament_type_check
+    CHECK (tournament_type IN ('sit_and_go', 'scheduled', 'knockout'));
+
+ALTER TABLE tournament_registrations
+ADD COLUMN IF NOT EXISTS bounty BIGINT NOT NULL DEFAULT 0 CHECK (bounty >= 0);
+
+ALTER TABLE wallet_entries
+DROP CONSTRAINT IF EXISTS wallet_entries_entry_type_check;
+
+ALTER TABLE wallet_entries
+ADD CONSTRAINT wallet_entries_entry_type_check
+    CHECK (entry_type IN ('buy_in', 'cash_out', 'rake', 'bonus', 'admin_adjust', 'transfer', 'bounty'));
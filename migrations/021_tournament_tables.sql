@@ -0,0 +1,18 @@
+-- Migration: Add multi-table tournament seating
+-- Date: October 16, 2026
+-- Description: Tracks the tables of a tournament, where their buttons are, and where each player is seated so tables can be balanced
+
+CREATE TABLE IF NOT EXISTS tournament_tables (
+    tournament_id BIGINT NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    table_id BIGINT NOT NULL,
+    max_players INT NOT NULL CHECK (max_players >= 2),
+    button_seat INT NOT NULL DEFAULT 0 CHECK (button_seat >= 0),
+    PRIMARY KEY (tournament_id, table_id)
+);
+
+ALTER TABLE tournament_registrations
+ADD COLUMN IF NOT EXISTS table_id BIGINT,
+ADD COLUMN IF NOT EXISTS seat INT CHECK (seat >= 0);
+
+CREATE INDEX IF NOT EXISTS idx_tournament_registrations_table
+    ON tournament_registrations(tournament_id, table_id) WHERE finish_position IS NULL;
@@ -0,0 +1,6 @@
+-- Migration: Add sit-out limit to tables
+-- Date: October 16, 2026
+-- Description: Persists how many hands a player can sit out before they're removed from the table
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS max_sit_out_hands SMALLINT NOT NULL DEFAULT 10 CHECK (max_sit_out_hands >= 0);
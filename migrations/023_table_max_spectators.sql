@@ -0,0 +1,6 @@
+-- Migration: Add spectator cap to tables
+-- Date: October 16, 2026
+-- Description: Persists how many spectators can watch a table at once
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS max_spectators INTEGER NOT NULL DEFAULT 50 CHECK (max_spectators >= 0);
@@ -0,0 +1,10 @@
+-- Migration: Add GTO bot difficulty
+-- Date: October 16, 2026
+-- Description: Allows tables to seat bots that mix their play based on equity vs. pot odds
+
+ALTER TABLE tables
+DROP CONSTRAINT IF EXISTS tables_bot_difficulty_check;
+
+ALTER TABLE tables
+ADD CONSTRAINT tables_bot_difficulty_check
+    CHECK (bot_difficulty IN ('easy', 'standard', 'tag', 'gto'));
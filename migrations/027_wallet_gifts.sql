@@ -0,0 +1,13 @@
+-- Migration: Add chip gifting between players
+-- Date: October 16, 2026
+-- Description: Records gifts in the ledger and lets wallets be frozen so they can't send or receive chips
+
+ALTER TABLE wallets
+ADD COLUMN IF NOT EXISTS frozen BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE wallet_entries
+DROP CONSTRAINT IF EXISTS wallet_entries_entry_type_check;
+
+ALTER TABLE wallet_entries
+ADD CONSTRAINT wallet_entries_entry_type_check
+    CHECK (entry_type IN ('buy_in', 'cash_out', 'rake', 'bonus', 'admin_adjust', 'transfer', 'bounty', 'gift'));
@@ -0,0 +1,32 @@
+-- Migration: Add audit log
+-- Date: October 16, 2026
+-- Description: Append-only record of security-relevant actions, such as logins, kicks, mutes, and wallet freezes
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    -- No foreign keys, so entries outlive the users and tables they mention
+    actor_id BIGINT,
+    action VARCHAR(50) NOT NULL,
+    target_user_id BIGINT,
+    table_id BIGINT,
+    ip_address VARCHAR(45),
+    details TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor_id ON audit_log(actor_id);
+CREATE INDEX IF NOT EXISTS idx_audit_log_target_user_id ON audit_log(target_user_id);
+
+-- Entries can only be added, never changed or removed
+CREATE OR REPLACE FUNCTION reject_audit_log_changes()
+RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS audit_log_append_only ON audit_log;
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION reject_audit_log_changes();
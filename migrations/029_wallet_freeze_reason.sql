@@ -0,0 +1,7 @@
+-- Migration: Record why and when a wallet was frozen
+-- Date: October 16, 2026
+-- Description: Frozen wallets can't buy in, claim the faucet, or send gifts, but can still cash out of tables
+
+ALTER TABLE wallets
+ADD COLUMN IF NOT EXISTS frozen_reason TEXT,
+ADD COLUMN IF NOT EXISTS frozen_at TIMESTAMP;
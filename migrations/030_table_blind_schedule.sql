@@ -0,0 +1,6 @@
+-- Migration: Add blind schedules to cash tables
+-- Date: October 16, 2026
+-- Description: Persists the blind levels a table moves through over time (NULL keeps the blinds fixed)
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS blind_schedule JSONB;
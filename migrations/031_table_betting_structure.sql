@@ -0,0 +1,6 @@
+-- Migration: Add betting structures to tables
+-- Date: October 16, 2026
+-- Description: Persists whether a table plays no-limit, pot-limit, or fixed-limit (with its bet sizes)
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS betting_structure JSONB NOT NULL DEFAULT '"no_limit"'::jsonb;
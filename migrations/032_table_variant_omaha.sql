@@ -0,0 +1,10 @@
+-- Migration: Allow Omaha tables
+-- Date: October 16, 2026
+-- Description: Adds Omaha to the variants a table can play (pot-limit Omaha pairs it with a pot-limit betting structure)
+
+ALTER TABLE tables
+DROP CONSTRAINT IF EXISTS tables_variant_check;
+
+ALTER TABLE tables
+ADD CONSTRAINT tables_variant_check
+    CHECK (variant IN ('texas_holdem', 'short_deck', 'omaha'));
@@ -0,0 +1,6 @@
+-- Migration: Add biggest-stack buy-in caps to tables
+-- Date: October 16, 2026
+-- Description: Persists whether buy-ins and top-ups are capped at the deepest stack at the table
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS match_biggest_stack BOOLEAN NOT NULL DEFAULT FALSE;
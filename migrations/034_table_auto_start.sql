@@ -0,0 +1,7 @@
+-- Migration: Add auto-start to tables
+-- Date: October 16, 2026
+-- Description: Persists how many seated players start a hand automatically (0 waits for a player to start it)
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS auto_start_min_players SMALLINT NOT NULL DEFAULT 0
+    CHECK (auto_start_min_players >= 0);
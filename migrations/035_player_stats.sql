@@ -0,0 +1,17 @@
+-- Migration: Add player statistics
+-- Date: October 16, 2026
+-- Description: Per-user, per-day counters of hands played, VPIP, PFR, showdowns, wins, and
+-- winnings in hundredths of a big blind, summed over a window of days for a player's stats
+
+CREATE TABLE IF NOT EXISTS player_stats (
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    stat_date DATE NOT NULL DEFAULT CURRENT_DATE,
+    hands_played BIGINT NOT NULL DEFAULT 0 CHECK (hands_played >= 0),
+    vpip_hands BIGINT NOT NULL DEFAULT 0 CHECK (vpip_hands >= 0),
+    pfr_hands BIGINT NOT NULL DEFAULT 0 CHECK (pfr_hands >= 0),
+    showdown_hands BIGINT NOT NULL DEFAULT 0 CHECK (showdown_hands >= 0),
+    hands_won BIGINT NOT NULL DEFAULT 0 CHECK (hands_won >= 0),
+    net_centi_bb BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, stat_date)
+);
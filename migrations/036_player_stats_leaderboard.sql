@@ -0,0 +1,9 @@
+-- Migration: Add leaderboard columns to player statistics
+-- Date: October 16, 2026
+-- Description: Per-day net chips won and biggest pot won, so the leaderboard can rank players
+-- by winnings in chips across stakes
+
+ALTER TABLE player_stats ADD COLUMN IF NOT EXISTS net_chips BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE player_stats ADD COLUMN IF NOT EXISTS biggest_pot BIGINT NOT NULL DEFAULT 0 CHECK (biggest_pot >= 0);
+
+CREATE INDEX IF NOT EXISTS idx_player_stats_stat_date ON player_stats(stat_date);
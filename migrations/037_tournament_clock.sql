@@ -0,0 +1,8 @@
+-- Migration: Add tournament clock
+-- Date: October 16, 2026
+-- Description: Tournament pauses, so a tournament's clock only counts the time it's been
+-- running, and whether its tables were last put on a break
+
+ALTER TABLE tournaments ADD COLUMN IF NOT EXISTS paused_at TIMESTAMP;
+ALTER TABLE tournaments ADD COLUMN IF NOT EXISTS paused_ms BIGINT NOT NULL DEFAULT 0 CHECK (paused_ms >= 0);
+ALTER TABLE tournaments ADD COLUMN IF NOT EXISTS tables_on_break BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,6 @@
+-- Migration: Add deck styles to tables
+-- Date: October 16, 2026
+-- Description: Persists the card back style clients render at each table
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS deck_style VARCHAR(32);
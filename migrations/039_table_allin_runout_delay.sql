@@ -0,0 +1,7 @@
+-- Migration: Add all-in runout pacing to tables
+-- Date: October 16, 2026
+-- Description: Persists the delay between streets when the board is run out with every player all-in
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS allin_runout_delay_ms INTEGER NOT NULL DEFAULT 0
+    CHECK (allin_runout_delay_ms >= 0);
@@ -0,0 +1,6 @@
+-- Migration: Add persistent tables
+-- Date: October 16, 2026
+-- Description: Marks tables that stay open while empty instead of being closed once idle
+
+ALTER TABLE tables
+ADD COLUMN IF NOT EXISTS persistent BOOLEAN NOT NULL DEFAULT FALSE;
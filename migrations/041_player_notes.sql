@@ -0,0 +1,11 @@
+-- Migration: Add player notes
+-- Date: October 16, 2026
+-- Description: Private notes players keep on opponents, one per author and opponent username
+
+CREATE TABLE IF NOT EXISTS player_notes (
+    author_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    target_username VARCHAR(50) NOT NULL,
+    note TEXT NOT NULL CHECK (char_length(note) BETWEEN 1 AND 500),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (author_id, target_username)
+);
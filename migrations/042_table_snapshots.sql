@@ -0,0 +1,10 @@
+-- Migration: Add table snapshots
+-- Date: October 16, 2026
+-- Description: Latest game state of each table, so a restart resumes the hand in progress
+
+CREATE TABLE IF NOT EXISTS table_snapshots (
+    table_id BIGINT PRIMARY KEY,
+    state JSONB NOT NULL,
+    escrow_balance BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
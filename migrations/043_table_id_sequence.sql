@@ -0,0 +1,10 @@
+-- Migration: Let the database assign table IDs
+-- Date: October 16, 2026
+-- Description: Tables used to be inserted with IDs chosen by the server, which never advanced
+-- the id sequence. Move it past every existing table so new tables don't collide.
+
+SELECT setval(
+    pg_get_serial_sequence('tables', 'id'),
+    COALESCE((SELECT MAX(id) FROM tables), 0) + 1,
+    false
+);
@@ -0,0 +1,6 @@
+-- Migration: Limit guesses at email verification codes
+-- Date: October 16, 2026
+-- Description: Counts wrong codes entered against each verification so it can be locked
+
+ALTER TABLE email_verifications
+ADD COLUMN IF NOT EXISTS failed_attempts INTEGER NOT NULL DEFAULT 0;
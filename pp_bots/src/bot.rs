@@ -26,7 +26,7 @@ const ACTION_OPTIONS_ARRAY: [ActionChoice; 5] = [
     ActionChoice::Call(0),
     ActionChoice::Check,
     ActionChoice::Fold,
-    ActionChoice::Raise(0),
+    ActionChoice::Raise { min: 0, max: 0 },
 ];
 const Q_S_DEFAULT: ActionWeights = [0.2, 1.0, 1.0, 1.0, 0.2];
 
@@ -166,15 +166,11 @@ impl Bot {
                 Ok(ServerMessage::Ack(ClientMessage {
                     username,
                     command: UserCommand::CastVote(vote),
-                })) => {
-                    if username != self.client.username {
-                        match vote {
-                            Vote::Kick(user_target) | Vote::Reset(Some(user_target))
-                                if user_target == self.client.username => {}
-                            _ => self.client.cast_vote(vote)?,
-                        }
-                    }
-                }
+                })) if username != self.client.username => match vote {
+                    Vote::Kick(user_target) | Vote::Reset(Some(user_target))
+                        if user_target == self.client.username => {}
+                    _ => self.client.cast_vote(vote)?,
+                },
                 Ok(ServerMessage::GameView(view)) => {
                     self.view = view;
                     if let Some(player) = self
@@ -190,7 +186,7 @@ impl Bot {
                         self.starting_money = player.user.money;
                     }
                 }
-                Ok(ServerMessage::TurnSignal(masks)) => break masks,
+                Ok(ServerMessage::TurnSignal(turn)) => break turn.action_choices,
                 Ok(ServerMessage::ClientError(error)) => bail!(error),
                 Ok(ServerMessage::UserError(error)) => bail!(error),
                 Err(error) => bail!(error),
@@ -217,7 +213,7 @@ impl Bot {
         let bet = match action_choice {
             ActionChoice::AllIn => player.user.money,
             ActionChoice::Check | ActionChoice::Fold => 0,
-            ActionChoice::Call(amount) | ActionChoice::Raise(amount) => *amount,
+            ActionChoice::Call(amount) | ActionChoice::Raise { min: amount, .. } => *amount,
         };
         self.client.take_action(action_choice.clone().into())?;
         if action_choice == &ActionChoice::Fold {
@@ -233,10 +229,8 @@ impl Bot {
                 Ok(ServerMessage::Ack(ClientMessage {
                     username,
                     command: UserCommand::CastVote(vote),
-                })) => {
-                    if username != self.client.username {
-                        self.client.cast_vote(vote)?;
-                    }
+                })) if username != self.client.username => {
+                    self.client.cast_vote(vote)?;
                 }
                 Ok(ServerMessage::GameView(view)) => {
                     self.view = view;
@@ -264,7 +258,7 @@ impl Bot {
                         return Ok((self.hand.clone(), ActionChoices::default(), reward, true));
                     }
                 }
-                Ok(ServerMessage::TurnSignal(masks)) => break masks,
+                Ok(ServerMessage::TurnSignal(turn)) => break turn.action_choices,
                 Ok(ServerMessage::ClientError(error)) => bail!(error),
                 Ok(ServerMessage::UserError(error)) => bail!(error),
                 Err(error) => bail!(error),
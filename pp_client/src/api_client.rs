@@ -2,14 +2,55 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
 
 /// API client for communicating with poker server
 pub struct ApiClient {
     base_url: String,
     client: reqwest::Client,
     access_token: Option<String>,
-    #[allow(dead_code)]
-    refresh_token: Option<String>,
+    token_refresher: Option<Arc<TokenRefresher>>,
+}
+
+/// Gets new access tokens with the refresh token from logging in. It's
+/// shared by every table connection, since each refresh replaces the
+/// refresh token with a new one.
+pub struct TokenRefresher {
+    base_url: String,
+    client: reqwest::Client,
+    refresh_token: Mutex<String>,
+}
+
+impl TokenRefresher {
+    /// Get a new access token, e.g., after the server closed a connection
+    /// because the old one expired
+    pub async fn refresh(&self) -> Result<String> {
+        let mut refresh_token = self.refresh_token.lock().await;
+        let response = self
+            .client
+            .post(format!("{}/api/v1/auth/refresh", self.base_url))
+            .json(&*refresh_token)
+            .send()
+            .await
+            .context("Failed to send refresh request")?;
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("Failed to read error response: {}", e));
+            anyhow::bail!("Session expired, please log in again: {}", error_text);
+        }
+
+        let auth_response: AuthResponse = response
+            .json()
+            .await
+            .context("Failed to parse refresh response")?;
+
+        *refresh_token = auth_response.refresh_token;
+        Ok(auth_response.access_token)
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -48,6 +89,18 @@ pub struct TableInfo {
     pub is_private: bool,
 }
 
+/// Page of tables from the table listing endpoint
+#[derive(Debug, Deserialize)]
+struct TableList {
+    tables: Vec<TableInfo>,
+}
+
+/// The user's notes from the player notes endpoint
+#[derive(Debug, Deserialize)]
+struct NotesList {
+    notes: HashMap<String, String>,
+}
+
 impl ApiClient {
     /// Create a new API client
     pub fn new(base_url: String) -> Self {
@@ -55,10 +108,20 @@ impl ApiClient {
             base_url,
             client: reqwest::Client::new(),
             access_token: None,
-            refresh_token: None,
+            token_refresher: None,
         }
     }
 
+    /// Store the tokens from logging in or registering
+    fn set_tokens(&mut self, auth_response: AuthResponse) {
+        self.access_token = Some(auth_response.access_token);
+        self.token_refresher = Some(Arc::new(TokenRefresher {
+            base_url: self.base_url.clone(),
+            client: self.client.clone(),
+            refresh_token: Mutex::new(auth_response.refresh_token),
+        }));
+    }
+
     /// Register a new user
     pub async fn register(
         &mut self,
@@ -94,8 +157,7 @@ impl ApiClient {
             .await
             .context("Failed to parse register response")?;
 
-        self.access_token = Some(auth_response.access_token);
-        self.refresh_token = Some(auth_response.refresh_token);
+        self.set_tokens(auth_response);
 
         Ok(())
     }
@@ -116,6 +178,20 @@ impl ApiClient {
             .await
             .context("Failed to send login request")?;
 
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after_secs = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(0);
+            anyhow::bail!(
+                "Login locked for {}:{:02}",
+                retry_after_secs / 60,
+                retry_after_secs % 60
+            );
+        }
+
         if !response.status().is_success() {
             let error_text = response
                 .text()
@@ -129,8 +205,7 @@ impl ApiClient {
             .await
             .context("Failed to parse login response")?;
 
-        self.access_token = Some(auth_response.access_token);
-        self.refresh_token = Some(auth_response.refresh_token);
+        self.set_tokens(auth_response);
 
         Ok(())
     }
@@ -144,12 +219,12 @@ impl ApiClient {
             .await
             .context("Failed to list tables")?;
 
-        let tables: Vec<TableInfo> = response
+        let page: TableList = response
             .json()
             .await
             .context("Failed to parse table list")?;
 
-        Ok(tables)
+        Ok(page.tables)
     }
 
     /// Get access token for WebSocket authentication
@@ -158,6 +233,11 @@ impl ApiClient {
         self.access_token.as_deref()
     }
 
+    /// Refresher for the access token, once logged in
+    pub fn token_refresher(&self) -> Option<Arc<TokenRefresher>> {
+        self.token_refresher.clone()
+    }
+
     /// Join a table with specified buy-in amount
     pub async fn join_table(&self, table_id: i64, buy_in: i64) -> Result<()> {
         let token = self.access_token.as_ref().context("Not authenticated")?;
@@ -191,6 +271,62 @@ impl ApiClient {
         Ok(())
     }
 
+    /// Set the user's private note on an opponent. Empty text clears it.
+    pub async fn set_note(&self, username: &str, note: &str) -> Result<()> {
+        let token = self.access_token.as_ref().context("Not authenticated")?;
+
+        #[derive(Serialize)]
+        struct SetNoteRequest<'a> {
+            note: &'a str,
+        }
+
+        let response = self
+            .client
+            .put(format!("{}/api/v1/notes/{}", self.base_url, username))
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&SetNoteRequest { note })
+            .send()
+            .await
+            .context("Failed to send note request")?;
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("Failed to read error response: {}", e));
+            anyhow::bail!("Saving note failed: {}", error_text);
+        }
+
+        Ok(())
+    }
+
+    /// Get the user's notes on a set of opponents in one request, keyed
+    /// by username. Opponents without a note are left out.
+    pub async fn get_notes(&self, usernames: &[String]) -> Result<HashMap<String, String>> {
+        let token = self.access_token.as_ref().context("Not authenticated")?;
+
+        let response = self
+            .client
+            .get(format!("{}/api/v1/notes", self.base_url))
+            .header("Authorization", format!("Bearer {}", token))
+            .query(&[("usernames", usernames.join(","))])
+            .send()
+            .await
+            .context("Failed to send notes request")?;
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("Failed to read error response: {}", e));
+            anyhow::bail!("Loading notes failed: {}", error_text);
+        }
+
+        let list: NotesList = response.json().await.context("Failed to parse notes")?;
+
+        Ok(list.notes)
+    }
+
     /// Get WebSocket URL for a table
     pub fn get_websocket_url(&self, table_id: i64) -> Result<String> {
         let token = self.access_token.as_ref().context("Not authenticated")?;
@@ -45,6 +45,8 @@ check
         Check, voting to move to the next card reveal(s).                              
 fold
         Fold, forfeiting your hand.                                                    
+muck
+        Keep your hand hidden at the showdown instead of showing it.                   
 play
         Join the playing waitlist.                                                     
 raise
@@ -69,17 +71,21 @@ const MAX_LOG_RECORDS: usize = 1024;
 const POLL_TIMEOUT: Duration = Duration::from_millis(100);
 
 fn make_board_spans(view: &GameView) -> Vec<Span<'_>> {
-    (!view.board.is_empty())
-        .then(|| {
-            std::iter::once(" board: ".into()).chain(
-                view.board
-                    .iter()
-                    .flat_map(|card| vec![make_card_span(card), "  ".into()]),
-            )
-        })
-        .into_iter()
-        .flatten()
-        .collect()
+    // The second board is only dealt when running it twice.
+    [
+        (" board: ", &view.board),
+        (" 2nd board: ", &view.second_board),
+    ]
+    .into_iter()
+    .filter(|(_, board)| !board.is_empty())
+    .flat_map(|(label, board)| {
+        std::iter::once(label.into()).chain(
+            board
+                .iter()
+                .flat_map(|card| vec![make_card_span(card), "  ".into()]),
+        )
+    })
+    .collect()
 }
 
 fn make_card_span(card: &Card) -> Span<'_> {
@@ -582,17 +588,23 @@ impl App {
             _ => "",
         };
 
+        let state_repr = if player.sitting_out {
+            "away".to_string()
+        } else {
+            player.state.to_string()
+        };
+
         // Build the row cells
         let mut row = vec![
             Cell::new(Text::from(move_repr).alignment(Alignment::Center)),
             Cell::new(Text::from(button_repr).alignment(Alignment::Left)),
             Cell::new(Text::from(player.user.name.to_string()).alignment(Alignment::Left)),
             Cell::new(Text::from(format!("${}", player.user.money)).alignment(Alignment::Right)),
-            Cell::new(Text::from(player.state.to_string()).alignment(Alignment::Center)),
+            Cell::new(Text::from(state_repr).alignment(Alignment::Center)),
         ];
 
         // Add player cards
-        for card_idx in 0..2 {
+        for card_idx in 0..view.variant.num_hole_cards() {
             let card_repr = player
                 .cards
                 .get(card_idx)
@@ -602,10 +614,7 @@ impl App {
 
         // Add player's best hand
         let hand_repr = if !player.cards.is_empty() {
-            let mut cards = view.board.as_ref().clone();
-            cards.extend(player.cards.as_ref().clone());
-            functional::prepare_hand(&mut cards);
-            let hand = functional::eval(&cards);
+            let hand = functional::eval_hole_with_board(&player.cards, &view.board, view.variant);
             hand.first()
                 .map_or_else(String::new, |subhand| format!("({})", subhand.rank))
         } else {
@@ -631,10 +640,13 @@ impl App {
                 Constraint::Fill(2),
                 Constraint::Fill(2),
                 Constraint::Fill(2),
+            ]
+            .into_iter()
+            // A column per hole card, then the best hand
+            .chain(std::iter::repeat_n(
                 Constraint::Fill(1),
-                Constraint::Fill(1),
-                Constraint::Fill(1),
-            ],
+                view.variant.num_hole_cards() + 1,
+            )),
         )
         .block(
             block::Block::bordered()
@@ -1,8 +1,36 @@
 use private_poker::{
-    entities::{Action, Usd, Username, Vote},
+    entities::{Action, ActionChoice, ActionChoices, Usd, Username, Vote},
     messages::{UserCommand, UserState},
 };
-use std::fmt;
+use rustyline::{
+    Context, Editor, Helper, completion::Completer, error::ReadlineError, highlight::Highlighter,
+    hint::Hinter, history::DefaultHistory, validate::Validator,
+};
+use std::{
+    env, fmt, mem,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+};
+use tokio::sync::mpsc;
+
+/// CLI commands for game actions, only offered for completion when the
+/// action is legal.
+const ACTION_COMMANDS: [(&str, Action); 5] = [
+    ("all-in", Action::AllIn),
+    ("call", Action::Call),
+    ("check", Action::Check),
+    ("fold", Action::Fold),
+    ("raise", Action::Raise(None)),
+];
+
+/// CLI commands that can be entered at any time.
+const OTHER_COMMANDS: [&str; 7] = [
+    "help", "join", "leave", "quit", "rabbit", "spectate", "stop",
+];
+
+/// Name of the file in the home directory that keeps CLI command history.
+const HISTORY_FILE: &str = ".pp_client_history";
 
 /// Errors that can occur during command parsing.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,6 +43,12 @@ pub enum ParseError {
     InvalidVoteCommand,
     /// Unrecognized command.
     UnrecognizedCommand(String),
+    /// Command was given more arguments than it takes.
+    UnexpectedArgument { command: String, argument: String },
+    /// Action isn't one of the legal actions right now.
+    IllegalAction { command: String, available: String },
+    /// Raise amount is smaller than the min raise.
+    RaiseBelowMinimum { amount: Usd, min_raise: Usd },
 }
 
 impl fmt::Display for ParseError {
@@ -37,6 +71,22 @@ impl fmt::Display for ParseError {
                 "Unrecognized command '{}'. Type 'help' to see available commands",
                 cmd
             ),
+            Self::UnexpectedArgument { command, argument } => write!(
+                f,
+                "Unexpected argument '{}' for '{}'. Type 'help' to see how to use it",
+                argument, command
+            ),
+            Self::IllegalAction { command, available } if available.is_empty() => {
+                write!(f, "Can't {} right now. Wait for your turn", command)
+            }
+            Self::IllegalAction { command, available } => {
+                write!(f, "Can't {} right now. You can {}", command, available)
+            }
+            Self::RaiseBelowMinimum { amount, min_raise } => write!(
+                f,
+                "Can't raise ${}. The min raise is ${} (e.g., 'raise {}')",
+                amount, min_raise, min_raise
+            ),
         }
     }
 }
@@ -78,6 +128,7 @@ pub fn parse_command(input: &str) -> Result<UserCommand, ParseError> {
         "call" => return Ok(UserCommand::TakeAction(Action::Call)),
         "check" => return Ok(UserCommand::TakeAction(Action::Check)),
         "fold" => return Ok(UserCommand::TakeAction(Action::Fold)),
+        "muck" => return Ok(UserCommand::Muck),
         "play" => return Ok(UserCommand::ChangeState(UserState::Play)),
         "show" => return Ok(UserCommand::ShowHand),
         "spectate" => return Ok(UserCommand::ChangeState(UserState::Spectate)),
@@ -126,9 +177,231 @@ fn parse_vote_command(parts: &[&str]) -> Result<UserCommand, ParseError> {
     }
 }
 
+/// Parse a command, checking game actions against the `available`
+/// actions. Game actions can be shortened to `a` (all-in), `c` (call),
+/// `k` (check), `f` (fold), and `r` or `bet` (raise). Other commands are
+/// parsed by [`parse_command`].
+///
+/// # Examples
+///
+/// ```
+/// use pp_client::commands::{ParseError, parse};
+/// use private_poker::entities::{Action, ActionChoice, ActionChoices};
+/// use private_poker::messages::UserCommand;
+///
+/// let available = ActionChoices::from([
+///     ActionChoice::Fold,
+///     ActionChoice::Call(50),
+///     ActionChoice::Raise { min: 100, max: 1000 },
+/// ]);
+/// assert!(matches!(parse("r 150", &available), Ok(UserCommand::TakeAction(Action::Raise(Some(150))))));
+/// assert!(matches!(parse("check", &available), Err(ParseError::IllegalAction { .. })));
+/// ```
+pub fn parse(input: &str, available: &ActionChoices) -> Result<UserCommand, ParseError> {
+    let parts: Vec<&str> = input.split_ascii_whitespace().collect();
+    match parse_action(&parts)? {
+        Some(action) => {
+            check_action(&action, available)?;
+            Ok(UserCommand::TakeAction(action))
+        }
+        None => parse_command(input),
+    }
+}
+
+/// Parse a game action from a tokenized command, accepting the same
+/// aliases as [`parse`]. Returns nothing if the command isn't a game
+/// action.
+pub fn parse_action(parts: &[&str]) -> Result<Option<Action>, ParseError> {
+    let Some((command, args)) = parts.split_first() else {
+        return Ok(None);
+    };
+    let action = match command.to_ascii_lowercase().as_str() {
+        "all-in" | "allin" | "a" => Action::AllIn,
+        "call" | "c" => Action::Call,
+        "check" | "k" => Action::Check,
+        "fold" | "f" => Action::Fold,
+        "raise" | "bet" | "r" => match args.first() {
+            Some(value) => {
+                let amount = value
+                    .parse::<Usd>()
+                    .map_err(|_| ParseError::InvalidRaiseAmount(value.to_string()))?;
+                Action::Raise(Some(amount))
+            }
+            None => Action::Raise(None),
+        },
+        _ => return Ok(None),
+    };
+
+    // Only raises take an argument.
+    let num_args = usize::from(matches!(action, Action::Raise(Some(_))));
+    if let Some(argument) = args.get(num_args) {
+        return Err(ParseError::UnexpectedArgument {
+            command: command.to_string(),
+            argument: argument.to_string(),
+        });
+    }
+    Ok(Some(action))
+}
+
+/// Check that an action is among the `available` actions and that a
+/// raise is at least the min raise.
+pub fn check_action(action: &Action, available: &ActionChoices) -> Result<(), ParseError> {
+    if !available.contains(action) {
+        let command = ACTION_COMMANDS
+            .iter()
+            .find(|(_, command)| mem::discriminant(command) == mem::discriminant(action))
+            .map_or("do that", |(name, _)| *name);
+        return Err(ParseError::IllegalAction {
+            command: command.to_string(),
+            available: available.to_string(),
+        });
+    }
+    let min_raise = available.0.iter().find_map(|choice| match choice {
+        ActionChoice::Raise { min, .. } => Some(*min),
+        _ => None,
+    });
+    if let (Action::Raise(Some(amount)), Some(min_raise)) = (action, min_raise)
+        && *amount < min_raise
+    {
+        return Err(ParseError::RaiseBelowMinimum {
+            amount: *amount,
+            min_raise,
+        });
+    }
+    Ok(())
+}
+
+/// Actions the user can take right now, shared between the connection
+/// that learns them from turn signals and the line editor that completes
+/// commands. Nothing is available while it isn't the user's turn.
+#[derive(Clone, Debug, Default)]
+pub struct AvailableActions(Arc<Mutex<Option<ActionChoices>>>);
+
+impl AvailableActions {
+    pub fn get(&self) -> Option<ActionChoices> {
+        self.0.lock().ok().and_then(|choices| choices.clone())
+    }
+
+    pub fn set(&self, action_choices: Option<ActionChoices>) {
+        if let Ok(mut choices) = self.0.lock() {
+            *choices = action_choices;
+        }
+    }
+}
+
+/// Command names starting with `prefix` that can be entered right now,
+/// in alphabetical order. Game actions are only offered if they're among
+/// the `available` actions from the last turn signal.
+pub fn completion_candidates(prefix: &str, available: Option<&ActionChoices>) -> Vec<&'static str> {
+    let actions = ACTION_COMMANDS
+        .iter()
+        .filter(|(_, action)| available.is_some_and(|choices| choices.contains(action)))
+        .map(|(name, _)| *name);
+    let mut candidates: Vec<&str> = actions
+        .chain(OTHER_COMMANDS)
+        .filter(|name| name.starts_with(prefix))
+        .collect();
+    candidates.sort_unstable();
+    candidates
+}
+
+/// Line editor helper that tab-completes command names.
+pub struct CommandHelper {
+    available: AvailableActions,
+}
+
+impl Completer for CommandHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Self::Candidate>)> {
+        // Only the command name is completed, not its arguments.
+        let line = &line[..pos];
+        let start = line.len() - line.trim_start().len();
+        let prefix = &line[start..];
+        if prefix.contains(char::is_whitespace) {
+            return Ok((pos, Vec::new()));
+        }
+        let available = self.available.get();
+        let candidates = completion_candidates(prefix, available.as_ref())
+            .into_iter()
+            .map(String::from)
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Helper for CommandHelper {}
+
+impl Highlighter for CommandHelper {}
+
+impl Hinter for CommandHelper {
+    type Hint = String;
+}
+
+impl Validator for CommandHelper {}
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE))
+}
+
+/// Read lines of user input with history, readline-style editing, and
+/// command completion. The line editor blocks, so it runs on its own
+/// thread and sends each line through the returned channel until the
+/// input ends or the user quits. History is saved after every line so
+/// it carries over to later sessions.
+pub fn spawn_line_editor(available: AvailableActions) -> mpsc::UnboundedReceiver<String> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    thread::spawn(move || {
+        let mut editor = match Editor::<CommandHelper, DefaultHistory>::new() {
+            Ok(editor) => editor,
+            Err(e) => {
+                eprintln!("Error starting line editor: {}", e);
+                return;
+            }
+        };
+        editor.set_helper(Some(CommandHelper { available }));
+        let history_path = history_path();
+        if let Some(path) = &history_path {
+            let _ = editor.load_history(path);
+        }
+
+        loop {
+            match editor.readline("> ") {
+                Ok(line) => {
+                    let input = line.trim();
+                    if !input.is_empty() {
+                        let _ = editor.add_history_entry(input);
+                        if let Some(path) = &history_path {
+                            let _ = editor.save_history(path);
+                        }
+                    }
+                    // Stop reading before the app exits so the terminal
+                    // isn't left in raw mode.
+                    let quit = matches!(input, "quit" | "exit");
+                    if tx.send(line).is_err() || quit {
+                        break;
+                    }
+                }
+                Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+                Err(e) => {
+                    eprintln!("Error reading input: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+    rx
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use private_poker::entities::ActionChoice;
 
     // === Single-word command tests ===
 
@@ -171,6 +444,12 @@ mod tests {
         assert!(matches!(result, Ok(UserCommand::ShowHand)));
     }
 
+    #[test]
+    fn test_parse_muck() {
+        let result = parse_command("muck");
+        assert!(matches!(result, Ok(UserCommand::Muck)));
+    }
+
     #[test]
     fn test_parse_spectate() {
         let result = parse_command("spectate");
@@ -359,4 +638,202 @@ mod tests {
         assert!(msg.contains("xyz"));
         assert!(msg.contains("help"));
     }
+
+    // === Completion tests ===
+
+    #[test]
+    fn test_completion_without_turn_offers_no_actions() {
+        assert_eq!(completion_candidates("c", None), Vec::<&str>::new());
+        assert_eq!(completion_candidates("r", None), vec!["rabbit"]);
+        assert_eq!(
+            completion_candidates("", None),
+            vec![
+                "help", "join", "leave", "quit", "rabbit", "spectate", "stop"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_completion_offers_raise_only_when_legal() {
+        let choices = ActionChoices::from([
+            ActionChoice::Fold,
+            ActionChoice::Check,
+            ActionChoice::Raise { min: 20, max: 1000 },
+            ActionChoice::AllIn,
+        ]);
+        assert_eq!(
+            completion_candidates("r", Some(&choices)),
+            vec!["rabbit", "raise"]
+        );
+        assert_eq!(completion_candidates("c", Some(&choices)), vec!["check"]);
+
+        let choices = ActionChoices::from([
+            ActionChoice::Fold,
+            ActionChoice::Call(50),
+            ActionChoice::AllIn,
+        ]);
+        assert_eq!(completion_candidates("r", Some(&choices)), vec!["rabbit"]);
+        assert_eq!(completion_candidates("c", Some(&choices)), vec!["call"]);
+        assert_eq!(completion_candidates("a", Some(&choices)), vec!["all-in"]);
+    }
+
+    #[test]
+    fn test_completion_matches_full_names() {
+        let choices = ActionChoices::from([ActionChoice::Fold, ActionChoice::Check]);
+        assert_eq!(completion_candidates("fold", Some(&choices)), vec!["fold"]);
+        assert_eq!(
+            completion_candidates("folds", Some(&choices)),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_available_actions_are_shared() {
+        let available = AvailableActions::default();
+        let shared = available.clone();
+        let choices = ActionChoices::from([ActionChoice::Fold, ActionChoice::Check]);
+        available.set(Some(choices.clone()));
+        assert_eq!(shared.get(), Some(choices));
+        available.set(None);
+        assert_eq!(shared.get(), None);
+    }
+
+    // === Structured parser tests ===
+
+    fn facing_bet() -> ActionChoices {
+        ActionChoices::from([
+            ActionChoice::Fold,
+            ActionChoice::Call(50),
+            ActionChoice::Raise {
+                min: 100,
+                max: 1000,
+            },
+            ActionChoice::AllIn,
+        ])
+    }
+
+    #[test]
+    fn test_parse_aliases() {
+        let available = facing_bet();
+        assert!(matches!(
+            parse("c", &available),
+            Ok(UserCommand::TakeAction(Action::Call))
+        ));
+        assert!(matches!(
+            parse("f", &available),
+            Ok(UserCommand::TakeAction(Action::Fold))
+        ));
+        assert!(matches!(
+            parse("a", &available),
+            Ok(UserCommand::TakeAction(Action::AllIn))
+        ));
+        assert!(matches!(
+            parse("r 100", &available),
+            Ok(UserCommand::TakeAction(Action::Raise(Some(100))))
+        ));
+        assert!(matches!(
+            parse("bet 150", &available),
+            Ok(UserCommand::TakeAction(Action::Raise(Some(150))))
+        ));
+        assert!(matches!(
+            parse("  RAISE  ", &available),
+            Ok(UserCommand::TakeAction(Action::Raise(None)))
+        ));
+
+        let available = ActionChoices::from([ActionChoice::Fold, ActionChoice::Check]);
+        assert!(matches!(
+            parse("k", &available),
+            Ok(UserCommand::TakeAction(Action::Check))
+        ));
+    }
+
+    #[test]
+    fn test_parse_passes_through_other_commands() {
+        let available = ActionChoices::default();
+        assert!(matches!(
+            parse("play", &available),
+            Ok(UserCommand::ChangeState(UserState::Play))
+        ));
+        assert!(matches!(
+            parse("vote reset", &available),
+            Ok(UserCommand::CastVote(Vote::Reset(None)))
+        ));
+    }
+
+    #[test]
+    fn test_parse_unknown_command() {
+        assert_eq!(
+            parse("dance", &facing_bet()).unwrap_err(),
+            ParseError::UnrecognizedCommand("dance".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_illegal_action() {
+        let err = parse("check", &facing_bet()).unwrap_err();
+        assert!(matches!(
+            &err,
+            ParseError::IllegalAction { command, .. } if command == "check"
+        ));
+        assert!(
+            err.to_string()
+                .starts_with("Can't check right now. You can ")
+        );
+
+        let err = parse("r 100", &ActionChoices::default()).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::IllegalAction {
+                command: "raise".to_string(),
+                available: String::new(),
+            }
+        );
+        assert_eq!(err.to_string(), "Can't raise right now. Wait for your turn");
+    }
+
+    #[test]
+    fn test_parse_bad_amount() {
+        assert_eq!(
+            parse("raise abc", &facing_bet()).unwrap_err(),
+            ParseError::InvalidRaiseAmount("abc".to_string())
+        );
+        assert_eq!(
+            parse("r -5", &facing_bet()).unwrap_err(),
+            ParseError::InvalidRaiseAmount("-5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_raise_below_min_raise() {
+        let err = parse("r 50", &facing_bet()).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::RaiseBelowMinimum {
+                amount: 50,
+                min_raise: 100
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Can't raise $50. The min raise is $100 (e.g., 'raise 100')"
+        );
+    }
+
+    #[test]
+    fn test_parse_unexpected_argument() {
+        assert_eq!(
+            parse("bet 50 extra", &facing_bet()).unwrap_err(),
+            ParseError::UnexpectedArgument {
+                command: "bet".to_string(),
+                argument: "extra".to_string(),
+            }
+        );
+        assert_eq!(
+            parse("fold now", &facing_bet()).unwrap_err(),
+            ParseError::UnexpectedArgument {
+                command: "fold".to_string(),
+                argument: "now".to_string(),
+            }
+        );
+    }
 }
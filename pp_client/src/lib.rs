@@ -5,5 +5,6 @@
 
 pub mod api_client;
 pub mod commands;
+pub mod notifications;
 pub mod tui_app;
 pub mod websocket_client;
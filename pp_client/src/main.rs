@@ -1,11 +1,17 @@
 //! A poker client TUI for multi-table poker server.
 //!
 //! The client connects to an HTTP/WebSocket poker server,
-//! authenticates, browses tables, and joins a selected table.
+//! authenticates, browses tables, and joins the selected table(s).
+//! The TUI can play at multiple tables at once.
 
 use anyhow::{Context, Result};
 use pico_args::Arguments;
-use std::io::{self, Write};
+use private_poker::entities::HandHistory;
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
 #[allow(dead_code)]
 mod api_client;
@@ -14,9 +20,16 @@ mod app;
 #[allow(dead_code)]
 mod commands;
 #[allow(dead_code)]
+mod notifications;
+#[allow(dead_code)]
 mod websocket_client;
 
-use pp_client::{api_client::ApiClient, tui_app::TuiApp, websocket_client::WebSocketClient};
+use pp_client::{
+    api_client::ApiClient,
+    notifications::{NotifyMode, TurnNotifications},
+    tui_app::{HandReplay, TuiApp},
+    websocket_client::WebSocketClient,
+};
 
 const HELP: &str = "\
 Connect to a private poker server
@@ -29,6 +42,12 @@ OPTIONS:
   --username NAME       Username for login
   --password PASS       Password for login
   --tui                 Use TUI (Terminal UI) mode [default: false]
+  --auto-focus          In TUI mode, switch to a table when it's your turn there
+  --notify MODE         Notify you when it's your turn at a table you aren't
+                        looking at, with a terminal bell or a desktop
+                        notification  [possible values: bell, desktop]
+  --replay FILE         Step through a hand history saved as JSON instead of
+                        playing. Doesn't connect to the server
 
 FLAGS:
   -h, --help            Print help information
@@ -39,6 +58,9 @@ struct Args {
     username: Option<String>,
     password: Option<String>,
     use_tui: bool,
+    auto_focus: bool,
+    notify: Option<NotifyMode>,
+    replay: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -57,6 +79,11 @@ async fn main() -> Result<()> {
         username: pargs.opt_value_from_str("--username").ok().flatten(),
         password: pargs.opt_value_from_str("--password").ok().flatten(),
         use_tui: pargs.contains("--tui"),
+        auto_focus: pargs.contains("--auto-focus"),
+        notify: pargs
+            .opt_value_from_str("--notify")
+            .map_err(|e| anyhow::anyhow!("Invalid --notify: {e}"))?,
+        replay: pargs.opt_value_from_str("--replay").ok().flatten(),
     };
 
     run(args).await
@@ -65,6 +92,10 @@ async fn main() -> Result<()> {
 async fn run(args: Args) -> Result<()> {
     let mut api_client = ApiClient::new(args.server_url.clone());
 
+    if let Some(path) = &args.replay {
+        return replay(path, args.username.unwrap_or_default(), api_client);
+    }
+
     // Get credentials
     let username = match args.username {
         Some(u) => u,
@@ -126,65 +157,81 @@ async fn run(args: Args) -> Result<()> {
         );
     }
 
-    // Select table
-    print!("\nSelect table (1-{}): ", tables.len());
+    // Select tables
+    if args.use_tui {
+        print!(
+            "\nSelect table(s) (1-{}, comma-separated to open several): ",
+            tables.len()
+        );
+    } else {
+        print!("\nSelect table (1-{}): ", tables.len());
+    }
     io::stdout().flush()?;
     let mut input = String::new();
     io::stdin().read_line(&mut input)?;
-    let table_index: usize = input.trim().parse().context("Invalid table number")?;
-
-    if table_index == 0 || table_index > tables.len() {
-        anyhow::bail!("Invalid table selection");
+    let mut selected_tables = Vec::new();
+    for table_index in input.split(',') {
+        let table_index: usize = table_index.trim().parse().context("Invalid table number")?;
+        if table_index == 0 || table_index > tables.len() {
+            anyhow::bail!("Invalid table selection");
+        }
+        selected_tables.push(&tables[table_index - 1]);
     }
 
-    let selected_table = &tables[table_index - 1];
-    println!("\nConnecting to table: {}", selected_table.name);
-
-    // Get WebSocket URL
-    let ws_url = api_client.get_websocket_url(selected_table.id)?;
-
     if args.use_tui {
-        // TUI mode - create a rich terminal UI
+        // TUI mode - create a rich terminal UI with a tab per table
         println!("Starting TUI mode...");
-
-        // Create initial empty view
-        use std::collections::{HashSet, VecDeque};
-        use std::sync::Arc;
-        let initial_view = private_poker::entities::GameView {
-            blinds: Arc::new(private_poker::entities::Blinds { small: 0, big: 0 }),
-            spectators: Arc::new(HashSet::new()),
-            waitlist: Arc::new(VecDeque::new()),
-            open_seats: Arc::new(VecDeque::new()),
-            players: Vec::new(),
-            board: Arc::new(Vec::new()),
-            pot: Arc::new(private_poker::entities::PotView { size: 0 }),
-            play_positions: Arc::new(private_poker::entities::PlayPositions::default()),
-        };
+        let tables = selected_tables
+            .iter()
+            .map(|table| (table.id, table.name.clone()))
+            .collect();
 
         // Initialize terminal
         let terminal = ratatui::init();
 
         // Create and run TUI app
-        let tui_app = TuiApp::new(
-            username.clone(),
-            selected_table.name.clone(),
-            selected_table.id,
-            api_client,
-            initial_view,
-        );
-
-        let result = tui_app.run(ws_url, terminal).await;
+        let mut tui_app = TuiApp::new(username.clone(), api_client, args.auto_focus);
+        if let Some(notify) = args.notify {
+            tui_app = tui_app.with_notifications(TurnNotifications::new(notify.notifier()));
+        }
+        let result = tui_app.run(tables, terminal).await;
 
         // Restore terminal
         ratatui::restore();
 
         result?;
     } else {
-        // CLI mode - simple text-based client
-        let ws_client = WebSocketClient::new(ws_url);
+        // CLI mode - simple text-based client at a single table
+        let [selected_table] = selected_tables.as_slice() else {
+            anyhow::bail!("Playing at multiple tables requires --tui");
+        };
+        println!("\nConnecting to table: {}", selected_table.name);
+        let ws_url = api_client.get_websocket_url(selected_table.id)?;
+        let mut ws_client = WebSocketClient::new(ws_url, &username);
+        if let Some(token_refresher) = api_client.token_refresher() {
+            ws_client = ws_client.with_token_refresher(token_refresher);
+        }
+        if let Some(notify) = args.notify {
+            ws_client = ws_client.with_notifications(TurnNotifications::new(notify.notifier()));
+        }
         ws_client.connect_and_play().await?;
     }
 
     println!("\nDisconnected from table.");
     Ok(())
 }
+
+/// Replay a hand history in the TUI. The username only highlights the
+/// user's seat.
+fn replay(path: &Path, username: String, api_client: ApiClient) -> Result<()> {
+    let history =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let history: HandHistory =
+        serde_json::from_str(&history).context("Failed to parse hand history")?;
+    let replay = HandReplay::new(&history).context("Invalid card in hand history")?;
+
+    let terminal = ratatui::init();
+    let result = TuiApp::new(username, api_client, false).replay(replay, terminal);
+    ratatui::restore();
+    result
+}
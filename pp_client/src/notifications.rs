@@ -0,0 +1,203 @@
+//! Notifications for when it's the user's turn.
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Shortest time between notifications for the same table, so turn
+/// signals that are sent again don't spam the user.
+pub const NOTIFICATION_DEBOUNCE: Duration = Duration::from_secs(10);
+
+/// Gets the user's attention
+///
+/// Implementations can ring the terminal bell or show a desktop
+/// notification; tests can record the notifications instead.
+pub trait Notifier: Send {
+    /// Notify the user
+    fn notify(&mut self, title: &str, message: &str);
+}
+
+/// Notifier that rings the terminal bell.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bell;
+
+impl Notifier for Bell {
+    fn notify(&mut self, _title: &str, _message: &str) {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x07");
+        let _ = stdout.flush();
+    }
+}
+
+/// Notifier that rings the terminal bell and shows a desktop
+/// notification with `notify-send`, or `osascript` on macOS. Systems
+/// without either, like headless ones, only get the bell.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn notify(&mut self, title: &str, message: &str) {
+        Bell.notify(title, message);
+        let mut command = if cfg!(target_os = "macos") {
+            let script = format!("display notification {:?} with title {:?}", message, title);
+            let mut command = Command::new("osascript");
+            command.arg("-e").arg(script);
+            command
+        } else {
+            let mut command = Command::new("notify-send");
+            command.arg(title).arg(message);
+            command
+        };
+        // Wait for the notification in the background so it never holds
+        // up the UI.
+        if let Ok(mut child) = command.spawn() {
+            thread::spawn(move || child.wait());
+        }
+    }
+}
+
+/// How turn notifications are delivered.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NotifyMode {
+    Bell,
+    Desktop,
+}
+
+impl NotifyMode {
+    pub fn notifier(self) -> Box<dyn Notifier> {
+        match self {
+            Self::Bell => Box::new(Bell),
+            Self::Desktop => Box::new(DesktopNotifier),
+        }
+    }
+}
+
+impl FromStr for NotifyMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bell" => Ok(Self::Bell),
+            "desktop" => Ok(Self::Desktop),
+            _ => Err(format!(
+                "unknown notification mode '{s}' (expected 'bell' or 'desktop')"
+            )),
+        }
+    }
+}
+
+/// Notifies the user when it's their turn at a table they aren't looking
+/// at. Each table is notified at most once per debounce period.
+pub struct TurnNotifications {
+    notifier: Box<dyn Notifier>,
+    debounce: Duration,
+    last_notified: HashMap<i64, Instant>,
+}
+
+impl TurnNotifications {
+    pub fn new(notifier: Box<dyn Notifier>) -> Self {
+        Self {
+            notifier,
+            debounce: NOTIFICATION_DEBOUNCE,
+            last_notified: HashMap::new(),
+        }
+    }
+
+    /// Handle a turn signal for the user at a table, notifying them
+    /// unless the table is `focused` or was notified too recently.
+    /// Returns whether the user was notified.
+    pub fn turn_signal(
+        &mut self,
+        table_id: i64,
+        table_name: &str,
+        focused: bool,
+        now: Instant,
+    ) -> bool {
+        if focused {
+            return false;
+        }
+        if self
+            .last_notified
+            .get(&table_id)
+            .is_some_and(|last| now.saturating_duration_since(*last) < self.debounce)
+        {
+            return false;
+        }
+        self.last_notified.insert(table_id, now);
+        self.notifier
+            .notify("Your turn", &format!("It's your turn at {table_name}"));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use super::{NOTIFICATION_DEBOUNCE, Notifier, NotifyMode, TurnNotifications};
+
+    #[derive(Clone, Default)]
+    struct RecordingNotifier(Arc<Mutex<Vec<String>>>);
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&mut self, _title: &str, message: &str) {
+            self.0.lock().unwrap().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn debounces_and_suppresses_turn_signals() {
+        let notifier = RecordingNotifier::default();
+        let mut notifications = TurnNotifications::new(Box::new(notifier.clone()));
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        // (table, focused, seconds since start, notified)
+        let signals = [
+            // First turn at a background table.
+            (1, false, 0, true),
+            // The same turn signal sent again.
+            (1, false, 1, false),
+            // Another table has its own debounce.
+            (2, false, 2, true),
+            // Focused tables are never notified.
+            (3, true, 3, false),
+            // Being focused doesn't count as a notification.
+            (3, false, 4, true),
+            // The debounce period has passed.
+            (1, false, NOTIFICATION_DEBOUNCE.as_secs(), true),
+        ];
+        for (table_id, focused, secs, notified) in signals {
+            let name = format!("table {table_id}");
+            assert_eq!(
+                notifications.turn_signal(table_id, &name, focused, at(secs)),
+                notified,
+                "table {table_id} at {secs}s"
+            );
+        }
+        assert_eq!(
+            *notifier.0.lock().unwrap(),
+            vec![
+                "It's your turn at table 1",
+                "It's your turn at table 2",
+                "It's your turn at table 3",
+                "It's your turn at table 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_notify_mode() {
+        assert_eq!("bell".parse(), Ok(NotifyMode::Bell));
+        assert_eq!("desktop".parse(), Ok(NotifyMode::Desktop));
+        assert!("email".parse::<NotifyMode>().is_err());
+    }
+}
@@ -1,13 +1,14 @@
 //! TUI application for WebSocket-based poker client.
 //!
 //! This module provides a rich terminal UI using ratatui that connects
-//! to the poker server via WebSocket for real-time game updates.
+//! to the poker server via WebSocket for real-time game updates. Each
+//! open table gets its own tab with its own connection, so a user can
+//! play at multiple tables in one session.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use futures_util::{SinkExt, StreamExt};
 use private_poker::{
-    entities::{Card, GameView, Suit, Username},
+    entities::{Action, Blinds, GameVariant, GameView, PlayPositions, PotView, Username},
     functional,
 };
 use ratatui::{
@@ -18,42 +19,82 @@ use ratatui::{
     symbols::scrollbar,
     text::{Line, Span, Text},
     widgets::{
-        Block, Cell, Clear, List, ListDirection, ListItem, Padding, Paragraph, Row, Scrollbar,
-        ScrollbarOrientation, Table, block,
+        Block, Cell, Clear, LineGauge, List, ListDirection, ListItem, Padding, Paragraph, Row,
+        Scrollbar, ScrollbarOrientation, Table, Tabs, block,
     },
 };
 use serde::Serialize;
-use std::time::{Duration, Instant};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+mod bet_slider;
+mod hand_strength;
+mod pre_action;
+mod replay;
+mod tabs;
+mod theme;
 mod widgets;
 
-use widgets::{ScrollableList, UserInput};
+use crate::{
+    api_client::ApiClient, commands, notifications::TurnNotifications,
+    websocket_client::WebSocketClient,
+};
+pub use bet_slider::{BetPreset, BetSlider};
+pub use hand_strength::{HandInputs, HandStrength};
+pub use pre_action::PreAction;
+pub use replay::{HandReplay, ReplayFrame, ReplayStep};
+pub use tabs::{TableEvent, TableTab, TableTabs, spawn_table_connection};
+pub use theme::Theme;
+use widgets::ScrollableList;
 
 const HELP: &str = "\
-all-in
+all-in, a
         Go all-in, betting all your money on the hand.
-call
+call, c
         Match the investment required to stay in the hand.
-check
+check, k
         Check, voting to move to the next card reveal(s).
-fold
+close
+        Close the current table's tab and disconnect from it.
+fold, f
         Fold, forfeiting your hand.
 join <buy_in>
         Join the table with the specified buy-in amount.
 leave
         Leave the table.
-raise <amount>
+note <username> [text]
+        Save a private note on a player, replacing any note you already had.
+        Without text, show your note. Seated players you have a note on are
+        marked with a ✎ next to their name.
+open <table_id>
+        Open another table in a new tab. Switch tabs with Alt+1..9.
+rabbit
+        See the cards that would've completed the board after a hand ends early.
+raise <amount>, r <amount>, bet <amount>
         Raise the investment required to stay in the hand. Entering without a value
         defaults to the min raise amount. Entering AMOUNT will raise by AMOUNT, but
         AMOUNT must be >= the min raise.
+        When you can raise, a bet slider is shown instead: Shift+Left/Right moves it
+        by a big blind, F1..F4 set it to 1/2 pot, 2/3 pot, pot, or all-in, and
+        Ctrl+R raises by the slider's amount (going all-in at the top of the slider).
+F6 / F7
+        Toggle the check/fold and call any pre-actions, which are taken as soon as
+        it's your turn. Call any is cleared if someone bets or raises first.
+Ctrl+T
+        Cycle through the default, high-contrast, and colorblind-safe color themes.
+        The theme is remembered for the next session.
 show
         Show your hand. Only possible during the showdown.
 spectate
         Join as a spectator.
 stop
         Stop spectating.
+unnote <username>
+        Delete your note on a player.
 ";
 const MAX_LOG_RECORDS: usize = 1024;
 const POLL_TIMEOUT: Duration = Duration::from_millis(100);
@@ -61,18 +102,21 @@ const POLL_TIMEOUT: Duration = Duration::from_millis(100);
 /// Client command matching WebSocket protocol
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
-enum ClientCommand {
+pub enum ClientCommand {
     Join { buy_in: i64 },
     Leave,
     Action { action: ActionData },
     Spectate,
     StopSpectating,
+    RabbitHunt,
+    Resync,
+    Pong { nonce: u64 },
 }
 
 /// Action data for game moves
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
-enum ActionData {
+pub enum ActionData {
     Fold,
     Check,
     Call,
@@ -80,30 +124,34 @@ enum ActionData {
     AllIn,
 }
 
-fn make_board_spans(view: &GameView) -> Vec<Span<'_>> {
-    (!view.board.is_empty())
-        .then(|| {
-            std::iter::once(" board: ".into()).chain(
-                view.board
-                    .iter()
-                    .flat_map(|card| vec![make_card_span(card), "  ".into()]),
-            )
-        })
-        .into_iter()
-        .flatten()
-        .collect()
+impl From<Action> for ActionData {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::AllIn => Self::AllIn,
+            Action::Call => Self::Call,
+            Action::Check => Self::Check,
+            Action::Fold => Self::Fold,
+            Action::Raise(amount) => Self::Raise { amount },
+        }
+    }
 }
 
-fn make_card_span(card: &Card) -> Span<'static> {
-    let Card(.., suit) = card;
-    let repr = card.to_string();
-    match suit {
-        Suit::Club => Span::styled(repr, Style::default().light_green()),
-        Suit::Diamond => Span::styled(repr, Style::default().light_blue()),
-        Suit::Heart => Span::styled(repr, Style::default().light_red()),
-        Suit::Spade => Span::raw(repr),
-        Suit::Wild => Span::styled(repr, Style::default().light_magenta()),
-    }
+fn make_board_spans(view: &GameView, theme: Theme) -> Vec<Span<'_>> {
+    // The second board is only dealt when running it twice.
+    [
+        (" board: ", &view.board),
+        (" 2nd board: ", &view.second_board),
+    ]
+    .into_iter()
+    .filter(|(_, board)| !board.is_empty())
+    .flat_map(|(label, board)| {
+        std::iter::once(label.into()).chain(
+            board
+                .iter()
+                .flat_map(|card| vec![theme.card_span(card), "  ".into()]),
+        )
+    })
+    .collect()
 }
 
 fn make_user_row(username: &Username, user: &private_poker::entities::User) -> Row<'static> {
@@ -129,8 +177,8 @@ enum RecordKind {
     You,
 }
 
-#[derive(Clone, Copy, PartialEq)]
-enum ConnectionStatus {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConnectionStatus {
     Connected,
     Disconnected,
 }
@@ -218,36 +266,91 @@ impl TurnWarnings {
     }
 }
 
+/// Game view shown for a table until the server sends its first view.
+fn empty_view() -> GameView {
+    GameView {
+        blinds: Arc::new(Blinds {
+            small: 0,
+            big: 0,
+            ante: 0,
+        }),
+        spectators: Arc::new(HashSet::new()),
+        waitlist: Arc::new(VecDeque::new()),
+        open_seats: Arc::new(VecDeque::new()),
+        players: Vec::new(),
+        board: Arc::new(Vec::new()),
+        second_board: Arc::new(Vec::new()),
+        pot: Arc::new(PotView { size: 0 }),
+        play_positions: Arc::new(PlayPositions::default()),
+        recent_actions: Arc::new(Vec::new()),
+        sequence: 0,
+        deck_style: None,
+        variant: GameVariant::default(),
+    }
+}
+
+/// Parse and create a client command from user input
+pub fn parse_command(input: &str) -> Result<ClientCommand> {
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    if parts.is_empty() {
+        anyhow::bail!("Empty command");
+    }
+
+    // Game actions
+    if let Some(action) = commands::parse_action(&parts)? {
+        return Ok(ClientCommand::Action {
+            action: action.into(),
+        });
+    }
+
+    let command = match parts[0].to_lowercase().as_str() {
+        // Table management
+        "join" => {
+            let buy_in = if parts.len() > 1 {
+                parts[1].parse::<i64>().context("Invalid buy-in amount")?
+            } else {
+                1000
+            };
+            ClientCommand::Join { buy_in }
+        }
+        "leave" => ClientCommand::Leave,
+        "spectate" | "watch" => ClientCommand::Spectate,
+        "stop" | "unwatch" => ClientCommand::StopSpectating,
+        "rabbit" => ClientCommand::RabbitHunt,
+
+        _ => {
+            anyhow::bail!("Unknown command: '{}'. Press Tab for help.", parts[0]);
+        }
+    };
+
+    Ok(command)
+}
+
 /// TUI App state
 pub struct TuiApp {
     username: Username,
-    table_name: String,
-    table_id: i64,
-    api_client: crate::api_client::ApiClient,
+    api_client: ApiClient,
     /// Whether to display the help menu window
     show_help_menu: bool,
     /// Helps scroll through the help menu window if the terminal is small
     help_handle: ScrollableList,
-    /// History of recorded messages
-    log_handle: ScrollableList,
-    /// Current value of the input box
-    user_input: UserInput,
-    /// Connection status indicator
-    connection_status: ConnectionStatus,
-    /// Current game view
-    view: GameView,
-    /// Turn warnings
-    turn_warnings: TurnWarnings,
+    /// Open tables
+    tabs: TableTabs,
+    /// The user's private notes on other players, keyed by username
+    notes: HashMap<String, String>,
+    /// Usernames whose notes have already been looked up
+    notes_checked: HashSet<String>,
+    /// Colors for cards and highlights, saved between sessions
+    theme: Theme,
+    /// Updates from all tables' connections
+    tx_events: mpsc::UnboundedSender<TableEvent>,
+    rx_events: mpsc::UnboundedReceiver<TableEvent>,
 }
 
 impl TuiApp {
-    pub fn new(
-        username: String,
-        table_name: String,
-        table_id: i64,
-        api_client: crate::api_client::ApiClient,
-        initial_view: GameView,
-    ) -> Self {
+    /// Create the app. If `auto_focus` is set, a background table's tab
+    /// is focused when it's the user's turn there.
+    pub fn new(username: String, api_client: ApiClient, auto_focus: bool) -> Self {
         // Fill help menu with help text lines
         let mut help_handle = ScrollableList::new(MAX_LOG_RECORDS);
         help_handle.push("".into());
@@ -257,133 +360,210 @@ impl TuiApp {
         help_handle.push("".into());
         help_handle.jump_to_first();
 
+        let username = Username::new(&username);
+        let (tx_events, rx_events) = mpsc::unbounded_channel();
         Self {
-            username: Username::new(&username),
-            table_name,
-            table_id,
+            tabs: TableTabs::new(username.clone(), auto_focus),
+            username,
             api_client,
+            notes: HashMap::new(),
+            notes_checked: HashSet::new(),
             show_help_menu: false,
             help_handle,
-            log_handle: ScrollableList::new(MAX_LOG_RECORDS),
-            user_input: UserInput::new(),
-            connection_status: ConnectionStatus::Connected,
-            view: initial_view,
-            turn_warnings: TurnWarnings::new(),
+            theme: Theme::load(),
+            tx_events,
+            rx_events,
         }
     }
 
-    /// Parse and create a client command from user input
-    fn parse_command(&self, input: &str) -> Result<ClientCommand> {
-        let parts: Vec<&str> = input.split_whitespace().collect();
-        if parts.is_empty() {
-            anyhow::bail!("Empty command");
+    /// Notify the user when it's their turn at a table that isn't the
+    /// active tab.
+    pub fn with_notifications(mut self, notifications: TurnNotifications) -> Self {
+        self.tabs = self.tabs.with_notifications(notifications);
+        self
+    }
+
+    /// Connect to a table and open a tab for it.
+    async fn open_table(&mut self, table_id: i64, table_name: String) -> Result<()> {
+        if self.tabs.get(table_id).is_some() {
+            anyhow::bail!("{} is already open", table_name);
         }
+        let ws_url = self.api_client.get_websocket_url(table_id)?;
+        let mut client = WebSocketClient::new(ws_url, &self.username.to_string());
+        if let Some(token_refresher) = self.api_client.token_refresher() {
+            client = client.with_token_refresher(token_refresher);
+        }
+        let (ws_stream, _) = client.connect().await?;
+        let (commands, connection) = spawn_table_connection(
+            table_id,
+            client,
+            ws_stream,
+            self.username.clone(),
+            self.tx_events.clone(),
+        );
+        self.tabs.open(table_id, table_name, commands, connection);
+        Ok(())
+    }
 
-        let command = match parts[0].to_lowercase().as_str() {
-            // Game actions
-            "fold" => ClientCommand::Action {
-                action: ActionData::Fold,
-            },
-            "check" => ClientCommand::Action {
-                action: ActionData::Check,
-            },
-            "call" => ClientCommand::Action {
-                action: ActionData::Call,
-            },
-            "allin" | "all-in" => ClientCommand::Action {
-                action: ActionData::AllIn,
-            },
-            "raise" => {
-                let amount = if parts.len() > 1 {
-                    parts[1].parse::<u32>().ok()
-                } else {
-                    None
+    /// Add log message to the active table's history
+    fn add_log(&mut self, kind: RecordKind, content: String) {
+        if let Some(tab) = self.tabs.active_mut() {
+            tab.add_log(kind, content);
+        }
+    }
+
+    /// Handle user input and send command to the active table
+    async fn handle_command(&mut self, user_input: &str) {
+        let parts: Vec<&str> = user_input.split_whitespace().collect();
+        match parts.as_slice() {
+            ["open", table_id] => {
+                self.add_log(RecordKind::You, user_input.to_string());
+                let result = match table_id.parse::<i64>() {
+                    Ok(table_id) => {
+                        // Name the tab after the table if it's listed.
+                        let table_name = self
+                            .api_client
+                            .list_tables()
+                            .await
+                            .ok()
+                            .and_then(|tables| tables.into_iter().find(|t| t.id == table_id))
+                            .map_or_else(|| format!("table {}", table_id), |t| t.name);
+                        self.open_table(table_id, table_name).await
+                    }
+                    Err(_) => Err(anyhow::anyhow!("Invalid table ID: '{}'", table_id)),
                 };
-                ClientCommand::Action {
-                    action: ActionData::Raise { amount },
+                if let Err(e) = result {
+                    self.add_log(RecordKind::Error, format!("Failed to open table: {:#}", e));
                 }
+                return;
             }
-
-            // Table management
-            "join" => {
-                let buy_in = if parts.len() > 1 {
-                    parts[1].parse::<i64>().context("Invalid buy-in amount")?
-                } else {
-                    1000
+            ["close"] => {
+                if let Some(table_id) = self.tabs.active().map(|tab| tab.table_id) {
+                    self.tabs.close(table_id);
+                }
+                return;
+            }
+            ["note", username] => {
+                self.add_log(RecordKind::You, user_input.to_string());
+                let content = match self.notes.get(*username) {
+                    Some(note) => format!("{}: {}", username, note),
+                    None => format!("No note on {}", username),
                 };
-                ClientCommand::Join { buy_in }
+                self.add_log(RecordKind::Ack, content);
+                return;
             }
-            "leave" => ClientCommand::Leave,
-            "spectate" | "watch" => ClientCommand::Spectate,
-            "stop" | "unwatch" => ClientCommand::StopSpectating,
-
-            _ => {
-                anyhow::bail!("Unknown command: '{}'. Press Tab for help.", parts[0]);
+            ["note", username, ..] | ["unnote", username] => {
+                self.add_log(RecordKind::You, user_input.to_string());
+                let note = parts[2..].join(" ");
+                match self.api_client.set_note(username, &note).await {
+                    Ok(()) if note.is_empty() => {
+                        self.notes.remove(*username);
+                        self.add_log(RecordKind::Ack, format!("Deleted note on {}", username));
+                    }
+                    Ok(()) => {
+                        self.notes.insert(username.to_string(), note);
+                        self.add_log(RecordKind::Ack, format!("Saved note on {}", username));
+                    }
+                    Err(e) => {
+                        self.add_log(RecordKind::Error, format!("Failed to save note: {}", e))
+                    }
+                }
+                return;
             }
-        };
-
-        Ok(command)
-    }
+            _ => {}
+        }
 
-    /// Handle user input and send command
-    async fn handle_command(
-        &mut self,
-        user_input: &str,
-        tx: &mpsc::UnboundedSender<ClientCommand>,
-    ) -> Result<()> {
-        match self.parse_command(user_input) {
-            Ok(command) => {
-                // Intercept Join command to call HTTP API first
-                if let ClientCommand::Join { buy_in } = &command {
-                    let record = Record::new(RecordKind::You, format!("join {}", buy_in));
-                    self.log_handle.push(record.into());
-
-                    // Call HTTP API to join table
-                    match self.api_client.join_table(self.table_id, *buy_in).await {
-                        Ok(_) => {
-                            let record = Record::new(
-                                RecordKind::Ack,
-                                "Joined table successfully via HTTP API".to_string(),
-                            );
-                            self.log_handle.push(record.into());
-                        }
-                        Err(e) => {
-                            let record = Record::new(
-                                RecordKind::Error,
-                                format!("Failed to join table: {}", e),
-                            );
-                            self.log_handle.push(record.into());
-                        }
+        match parse_command(user_input) {
+            Ok(ClientCommand::Join { buy_in }) => {
+                // Join through the HTTP API instead of the WebSocket
+                self.add_log(RecordKind::You, format!("join {}", buy_in));
+                let Some(table_id) = self.tabs.active().map(|tab| tab.table_id) else {
+                    return;
+                };
+                match self.api_client.join_table(table_id, buy_in).await {
+                    Ok(_) => self.add_log(
+                        RecordKind::Ack,
+                        "Joined table successfully via HTTP API".to_string(),
+                    ),
+                    Err(e) => {
+                        self.add_log(RecordKind::Error, format!("Failed to join table: {}", e))
                     }
-                    // Don't send Join command via WebSocket
-                } else {
-                    tx.send(command)?;
-                    let record = Record::new(RecordKind::You, user_input.to_string());
-                    self.log_handle.push(record.into());
                 }
             }
-            Err(e) => {
-                let record = Record::new(RecordKind::Error, e.to_string());
-                self.log_handle.push(record.into());
-            }
+            Ok(command) => match self.tabs.send(command) {
+                Ok(_) => self.add_log(RecordKind::You, user_input.to_string()),
+                Err(e) => self.add_log(RecordKind::Error, e.to_string()),
+            },
+            Err(e) => self.add_log(RecordKind::Error, e.to_string()),
         }
-        Ok(())
     }
 
-    /// Update game view
-    fn update_view(&mut self, new_view: GameView) {
-        self.view = new_view;
+    /// Look up notes on players seated at any open table that haven't
+    /// been looked up yet, in a single request.
+    async fn load_notes(&mut self) {
+        let usernames: Vec<String> = self
+            .tabs
+            .tabs()
+            .iter()
+            .flat_map(|tab| tab.view.players.iter())
+            .map(|player| player.user.name.to_string())
+            .filter(|username| {
+                *username != self.username.to_string() && !self.notes_checked.contains(username)
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if usernames.is_empty() {
+            return;
+        }
+
+        // Don't retry the same usernames every frame if the lookup fails
+        self.notes_checked.extend(usernames.iter().cloned());
+        match self.api_client.get_notes(&usernames).await {
+            Ok(notes) => self.notes.extend(notes),
+            Err(e) => self.add_log(RecordKind::Error, format!("Failed to load notes: {}", e)),
+        }
     }
 
-    /// Add log message
-    fn add_log(&mut self, kind: RecordKind, content: String) {
-        let record = Record::new(kind, content);
-        self.log_handle.push(record.into());
+    /// Take the action picked with the active table's bet slider
+    fn raise_with_bet_slider(&mut self) {
+        let Some(action) = self
+            .tabs
+            .active()
+            .and_then(|tab| tab.bet_slider.as_ref())
+            .map(BetSlider::action)
+        else {
+            return;
+        };
+        let repr = match action {
+            Action::Raise(Some(amount)) => format!("raise {}", amount),
+            _ => "all-in".to_string(),
+        };
+        let command = ClientCommand::Action {
+            action: action.into(),
+        };
+        match self.tabs.send(command) {
+            Ok(_) => self.add_log(RecordKind::You, repr),
+            Err(e) => self.add_log(RecordKind::Error, e.to_string()),
+        }
+    }
+
+    /// Switch to the next theme and save it for later sessions.
+    fn cycle_theme(&mut self) {
+        self.theme = self.theme.next();
+        let content = match self.theme.save() {
+            Ok(()) => format!("Switched to the {} theme", self.theme),
+            Err(e) => format!(
+                "Switched to the {} theme, but couldn't save it: {}",
+                self.theme, e
+            ),
+        };
+        self.add_log(RecordKind::Ack, content);
     }
 
     /// Render the spectators table
-    fn draw_spectators(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        let mut spectators = Vec::from_iter(self.view.spectators.iter());
+    fn draw_spectators(&self, view: &GameView, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let mut spectators = Vec::from_iter(view.spectators.iter());
         spectators.sort_unstable();
         let spectators = Table::new(
             spectators
@@ -400,10 +580,9 @@ impl TuiApp {
     }
 
     /// Render the waitlist table
-    fn draw_waitlist(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+    fn draw_waitlist(&self, view: &GameView, frame: &mut Frame, area: ratatui::layout::Rect) {
         let waitlisters = Table::new(
-            self.view
-                .waitlist
+            view.waitlist
                 .iter()
                 .map(|user| make_user_row(&self.username, user)),
             [Constraint::Percentage(50), Constraint::Percentage(50)],
@@ -417,11 +596,11 @@ impl TuiApp {
     }
 
     /// Create a table row for a single player
-    fn make_player_row(&self, player_idx: usize) -> Row<'static> {
-        let player = &self.view.players[player_idx];
+    fn make_player_row(&self, view: &GameView, player_idx: usize) -> Row<'static> {
+        let player = &view.players[player_idx];
 
         // Indicator if it's the player's move
-        let move_repr = if self.view.play_positions.next_action_idx == Some(player_idx) {
+        let move_repr = if view.play_positions.next_action_idx == Some(player_idx) {
             "→"
         } else {
             ""
@@ -429,35 +608,45 @@ impl TuiApp {
 
         // Indicator for blind position
         let button_repr = match player_idx {
-            idx if idx == self.view.play_positions.big_blind_idx => "BB",
-            idx if idx == self.view.play_positions.small_blind_idx => "SB",
+            idx if idx == view.play_positions.big_blind_idx => "BB",
+            idx if idx == view.play_positions.small_blind_idx => "SB",
             _ => "",
         };
 
+        let state_repr = if player.sitting_out {
+            "away".to_string()
+        } else {
+            player.state.to_string()
+        };
+
+        // Indicator if the user has a note on the player
+        let name_repr = if self.notes.contains_key(&player.user.name.to_string()) {
+            format!("{} ✎", player.user.name)
+        } else {
+            player.user.name.to_string()
+        };
+
         // Build the row cells
         let mut row = vec![
             Cell::new(Text::from(move_repr).alignment(Alignment::Center)),
             Cell::new(Text::from(button_repr).alignment(Alignment::Left)),
-            Cell::new(Text::from(player.user.name.to_string()).alignment(Alignment::Left)),
+            Cell::new(Text::from(name_repr).alignment(Alignment::Left)),
             Cell::new(Text::from(format!("${}", player.user.money)).alignment(Alignment::Right)),
-            Cell::new(Text::from(player.state.to_string()).alignment(Alignment::Center)),
+            Cell::new(Text::from(state_repr).alignment(Alignment::Center)),
         ];
 
         // Add player cards
-        for card_idx in 0..2 {
+        for card_idx in 0..view.variant.num_hole_cards() {
             let card_repr = player
                 .cards
                 .get(card_idx)
-                .map_or_else(|| "".into(), make_card_span);
+                .map_or_else(|| "".into(), |card| self.theme.card_span(card));
             row.push(Cell::new(Text::from(card_repr).alignment(Alignment::Right)));
         }
 
         // Add player's best hand
         let hand_repr = if !player.cards.is_empty() {
-            let mut cards = self.view.board.as_ref().clone();
-            cards.extend(player.cards.as_ref().clone());
-            functional::prepare_hand(&mut cards);
-            let hand = functional::eval(&cards);
+            let hand = functional::eval_hole_with_board(&player.cards, &view.board, view.variant);
             hand.first()
                 .map_or_else(String::new, |subhand| format!("({})", subhand.rank))
         } else {
@@ -474,39 +663,118 @@ impl TuiApp {
     }
 
     /// Render the main game table with players
-    fn draw_table(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+    fn draw_table(&self, view: &GameView, frame: &mut Frame, area: ratatui::layout::Rect) {
         let table = Table::new(
-            (0..self.view.players.len()).map(|idx| self.make_player_row(idx)),
+            (0..view.players.len()).map(|idx| self.make_player_row(view, idx)),
             [
                 Constraint::Max(3),
                 Constraint::Fill(1),
                 Constraint::Fill(2),
                 Constraint::Fill(2),
                 Constraint::Fill(2),
+            ]
+            .into_iter()
+            // A column per hole card, then the best hand
+            .chain(std::iter::repeat_n(
                 Constraint::Fill(1),
-                Constraint::Fill(1),
-                Constraint::Fill(1),
-            ],
+                view.variant.num_hole_cards() + 1,
+            )),
         )
         .block(
             block::Block::bordered()
                 .padding(Padding::uniform(1))
-                .title_top(make_board_spans(&self.view))
-                .title_bottom(format!(
-                    " blinds: {}  pot: {}  ",
-                    self.view.blinds, self.view.pot
-                )),
+                .title_top(make_board_spans(view, self.theme))
+                .title_bottom(format!(" blinds: {}  pot: {}  ", view.blinds, view.pot)),
         );
         frame.render_widget(table, area);
     }
 
+    /// Render the tab bar with one tab per open table. Tabs of background
+    /// tables where it's the user's turn flash.
+    fn draw_tabs(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let flash_on = Utc::now().timestamp_subsec_millis() < 500;
+        let titles = self.tabs.tabs().iter().enumerate().map(|(idx, tab)| {
+            let title = Line::from(format!("{}:{}", idx + 1, tab.table_name));
+            if tab.flashing && flash_on {
+                title.black().on_light_yellow()
+            } else if tab.connection_status == ConnectionStatus::Disconnected {
+                title.red()
+            } else {
+                title
+            }
+        });
+        let tabs = Tabs::new(titles)
+            .select(self.tabs.active_idx())
+            .highlight_style(self.theme.accent().bold());
+        frame.render_widget(tabs, area);
+    }
+
+    /// Render the bet slider and the amounts of the bet presets
+    fn draw_bet_slider(&self, slider: &BetSlider, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let [gauge_area, presets_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(area);
+        let range = slider.max() - slider.min();
+        let ratio = f64::from(slider.amount() - slider.min()) / f64::from(range);
+        let label = match slider.action() {
+            Action::AllIn => format!(" all-in ${} ", slider.amount()),
+            _ => format!(" raise ${} ", slider.amount()),
+        };
+        let gauge = LineGauge::default()
+            .ratio(ratio)
+            .label(label)
+            .filled_style(self.theme.accent());
+        frame.render_widget(gauge, gauge_area);
+
+        let mut presets: Vec<Span> = vec![
+            format!(" ${}", slider.min()).into(),
+            " - ".into(),
+            format!("${}", slider.max()).into(),
+        ];
+        for (idx, preset) in BetPreset::ALL.into_iter().enumerate() {
+            presets.push("  ".into());
+            presets.push(format!("F{}", idx + 1).bold().white());
+            presets.push(format!(" {} ${}", preset, slider.preset_amount(preset)).into());
+        }
+        frame.render_widget(Paragraph::new(Line::from(presets)), presets_area);
+    }
+
+    /// Render checkboxes for the pre-actions
+    fn draw_pre_actions(tab: &TableTab, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let mut pre_actions: Vec<Span> = Vec::new();
+        for (idx, pre_action) in PreAction::ALL.into_iter().enumerate() {
+            let checkbox = if tab.pre_action == Some(pre_action) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            pre_actions.push(format!(" {} {} ", checkbox, pre_action).into());
+            pre_actions.push(format!("F{}", idx + 6).bold().white());
+            pre_actions.push(" ".into());
+        }
+        frame.render_widget(Paragraph::new(Line::from(pre_actions)), area);
+    }
+
+    /// Render the user's made hand, draws, and equity
+    fn draw_hand_strength(strength: &HandStrength, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let mut spans: Vec<Span> = vec![format!(" {}", strength.made_hand).bold()];
+        for draw in &strength.draws {
+            spans.push(format!(" · {}", draw).into());
+        }
+        if let Some(equity) = strength.equity {
+            spans.push(format!(" · {:.0}% to win", equity * 100.0).into());
+        }
+        let hand =
+            Paragraph::new(Line::from(spans)).block(block::Block::bordered().title(" your hand "));
+        frame.render_widget(hand, area);
+    }
+
     /// Render the log/history window with scrollbar
-    fn draw_log(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        let log_records = self.log_handle.list_items.clone();
+    fn draw_log(log_handle: &mut ScrollableList, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let log_records = log_handle.list_items.clone();
         let log_records = List::new(log_records)
             .direction(ListDirection::BottomToTop)
             .block(block::Block::bordered().title(" history  "));
-        frame.render_stateful_widget(log_records, area, &mut self.log_handle.list_state);
+        frame.render_stateful_widget(log_records, area, &mut log_handle.list_state);
 
         // Render log window scrollbar
         frame.render_stateful_widget(
@@ -518,30 +786,34 @@ impl TuiApp {
                 vertical: 1,
                 horizontal: 1,
             }),
-            &mut self.log_handle.scroll_state,
+            &mut log_handle.scroll_state,
         );
     }
 
     /// Render the user input area
-    fn draw_user_input(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        let user_input = Paragraph::new(self.user_input.value.as_str())
+    fn draw_user_input(&self, tab: &TableTab, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let user_input = Paragraph::new(tab.user_input.value.as_str())
             .style(Style::default())
-            .block(
-                block::Block::bordered()
-                    .title(format!(" {}@{}  ", self.username, self.table_name).light_green()),
-            );
+            .block(block::Block::bordered().title(Span::styled(
+                format!(" {}@{}  ", self.username, tab.table_name),
+                self.theme.accent(),
+            )));
         frame.render_widget(user_input, area);
         frame.set_cursor_position(Position::new(
-            area.x + self.user_input.char_idx as u16 + 1,
+            area.x + tab.user_input.char_idx as u16 + 1,
             area.y + 1,
         ));
     }
 
     /// Render the help/status bar at the bottom
-    fn draw_help_bar(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
-        let status_indicator = match self.connection_status {
-            ConnectionStatus::Connected => "● Connected".green(),
-            ConnectionStatus::Disconnected => "● Disconnected".red(),
+    fn draw_help_bar(&self, tab: &TableTab, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let status_indicator = match tab.connection_status {
+            ConnectionStatus::Connected => {
+                Span::styled("● Connected", self.theme.connection_status(true))
+            }
+            ConnectionStatus::Disconnected => {
+                Span::styled("● Disconnected", self.theme.connection_status(false))
+            }
         };
 
         let help_message = vec![
@@ -550,7 +822,9 @@ impl TuiApp {
             "Tab".bold().white(),
             " to view help, press ".into(),
             "Enter".bold().white(),
-            " to record a command, or press ".into(),
+            " to record a command, press ".into(),
+            "Alt+1..9".bold().white(),
+            " to switch tables, or press ".into(),
             "Esc".bold().white(),
             " to exit".into(),
         ];
@@ -587,15 +861,72 @@ impl TuiApp {
         );
     }
 
+    /// Render a replayed hand with the steps taken so far
+    fn draw_replay(&self, replay: &HandReplay, frame: &mut Frame) {
+        let [table_area, steps_area, help_area] = Layout::vertical([
+            Constraint::Percentage(55),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        self.draw_table(replay.view(), frame, table_area);
+
+        let steps = replay
+            .played()
+            .iter()
+            .rev()
+            .map(|step| ListItem::new(step.description.clone()));
+        let steps = List::new(steps)
+            .direction(ListDirection::BottomToTop)
+            .block(block::Block::bordered().title(Span::styled(
+                format!(
+                    " replay {}/{}  ",
+                    replay.position() + 1,
+                    replay.num_frames()
+                ),
+                self.theme.accent(),
+            )));
+        frame.render_widget(steps, steps_area);
+
+        let help_message = vec![
+            "press ".into(),
+            "Space".bold().white(),
+            " to step forward, press ".into(),
+            "Backspace".bold().white(),
+            " to step back, or press ".into(),
+            "Esc".bold().white(),
+            " to exit".into(),
+        ];
+        frame.render_widget(Paragraph::new(Line::from(help_message)), help_area);
+    }
+
     /// Main draw function - orchestrates rendering of all UI components
     fn draw(&mut self, frame: &mut Frame) {
         // Define the main layout structure
+        let bet_slider = self.tabs.active().and_then(|tab| tab.bet_slider.clone());
+        let bet_slider_height = if bet_slider.is_some() { 2 } else { 0 };
+        let has_hand = self
+            .tabs
+            .active()
+            .is_some_and(|tab| tab.hand_strength.is_some());
+        let hand_strength_height = if has_hand { 3 } else { 0 };
         let window = Layout::vertical([
-            Constraint::Min(6),    // Top area (view + log)
-            Constraint::Length(3), // User input area
-            Constraint::Length(1), // Help bar
+            Constraint::Length(1),                 // Table tabs
+            Constraint::Min(6),                    // Top area (view + log)
+            Constraint::Length(bet_slider_height), // Bet slider
+            Constraint::Length(1),                 // Pre-actions
+            Constraint::Length(3),                 // User input area
+            Constraint::Length(1),                 // Help bar
         ]);
-        let [top_area, user_input_area, help_area] = window.areas(frame.area());
+        let [
+            tabs_area,
+            top_area,
+            bet_slider_area,
+            pre_actions_area,
+            user_input_area,
+            help_area,
+        ] = window.areas(frame.area());
 
         // Split top area into view and log
         let [view_area, log_area] =
@@ -607,18 +938,35 @@ impl TuiApp {
             Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
                 .areas(view_area);
 
+        // Split table area into players and the user's hand
+        let [table_area, hand_strength_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(hand_strength_height)])
+                .areas(table_area);
+
         // Split lobby into spectators and waitlisters
         let [spectator_area, waitlister_area] =
             Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
                 .areas(lobby_area);
 
-        // Render all components
-        self.draw_spectators(frame, spectator_area);
-        self.draw_waitlist(frame, waitlister_area);
-        self.draw_table(frame, table_area);
-        self.draw_log(frame, log_area);
-        self.draw_user_input(frame, user_input_area);
-        self.draw_help_bar(frame, help_area);
+        // Render all components for the active table
+        self.draw_tabs(frame, tabs_area);
+        if let Some(tab) = self.tabs.active() {
+            self.draw_spectators(&tab.view, frame, spectator_area);
+            self.draw_waitlist(&tab.view, frame, waitlister_area);
+            self.draw_table(&tab.view, frame, table_area);
+            if let Some(strength) = &tab.hand_strength {
+                Self::draw_hand_strength(strength, frame, hand_strength_area);
+            }
+            Self::draw_pre_actions(tab, frame, pre_actions_area);
+            self.draw_user_input(tab, frame, user_input_area);
+            self.draw_help_bar(tab, frame, help_area);
+        }
+        if let Some(tab) = self.tabs.active_mut() {
+            Self::draw_log(&mut tab.log_handle, frame, log_area);
+        }
+        if let Some(bet_slider) = &bet_slider {
+            self.draw_bet_slider(bet_slider, frame, bet_slider_area);
+        }
 
         // Render help menu overlay if active
         if self.show_help_menu {
@@ -626,56 +974,48 @@ impl TuiApp {
         }
     }
 
-    /// Run the TUI application
-    pub async fn run(mut self, ws_url: String, mut terminal: DefaultTerminal) -> Result<()> {
-        // Connect to WebSocket
-        let (ws_stream, _) = connect_async(&ws_url)
-            .await
-            .context("Failed to connect to WebSocket")?;
-
-        let (mut write, mut read) = ws_stream.split();
+    /// Run the TUI application, opening a tab for each of the given
+    /// `(table_id, table_name)` tables. Every table's connection is
+    /// closed when the app exits.
+    pub async fn run(
+        mut self,
+        tables: Vec<(i64, String)>,
+        mut terminal: DefaultTerminal,
+    ) -> Result<()> {
+        for (table_id, table_name) in tables {
+            self.open_table(table_id, table_name).await?;
+        }
+        self.tabs.focus(0);
 
-        // Channel for sending commands to WebSocket
-        let (tx_command, mut rx_command) = mpsc::unbounded_channel::<ClientCommand>();
+        let result = self.run_loop(&mut terminal).await;
+        self.tabs.shutdown().await;
+        result
+    }
 
-        // Spawn task to handle outgoing messages
-        let write_handle = tokio::spawn(async move {
-            while let Some(command) = rx_command.recv().await {
-                if let Ok(json) = serde_json::to_string(&command)
-                    && write.send(Message::Text(json.into())).await.is_err()
-                {
-                    break;
-                }
-            }
-        });
+    /// Step through a recorded hand instead of playing. Nothing is sent
+    /// to the server.
+    pub fn replay(self, mut replay: HandReplay, mut terminal: DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw_replay(&replay, frame))?;
 
-        // Channel for incoming game views
-        let (tx_view, mut rx_view) = mpsc::unbounded_channel::<GameView>();
-        let (tx_error, mut rx_error) = mpsc::unbounded_channel::<String>();
-
-        // Spawn task to handle incoming messages
-        let read_handle = tokio::spawn(async move {
-            while let Some(msg) = read.next().await {
-                match msg {
-                    Ok(Message::Text(text)) => {
-                        if let Ok(view) = serde_json::from_str::<GameView>(&text) {
-                            let _ = tx_view.send(view);
-                        }
-                    }
-                    Ok(Message::Close(_)) => {
-                        let _ = tx_error.send("Server closed connection".to_string());
-                        break;
+            if let Event::Key(KeyEvent { code, kind, .. }) = event::read()?
+                && kind == KeyEventKind::Press
+            {
+                match code {
+                    KeyCode::Char(' ') | KeyCode::Right => {
+                        replay.advance();
                     }
-                    Err(e) => {
-                        let _ = tx_error.send(format!("WebSocket error: {}", e));
-                        break;
+                    KeyCode::Backspace | KeyCode::Left => {
+                        replay.rewind();
                     }
+                    KeyCode::Esc => return Ok(()),
                     _ => {}
                 }
             }
-        });
+        }
+    }
 
-        // Main UI loop
+    async fn run_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
         loop {
             terminal.draw(|frame| self.draw(frame))?;
 
@@ -690,76 +1030,96 @@ impl TuiApp {
                 && kind == KeyEventKind::Press
             {
                 match modifiers {
-                    KeyModifiers::CONTROL => match code {
-                        KeyCode::Home => self.log_handle.jump_to_first(),
-                        KeyCode::End => self.log_handle.jump_to_last(),
-                        _ => {}
-                    },
-                    KeyModifiers::NONE => match code {
-                        KeyCode::Enter => {
-                            let user_input = self.user_input.submit();
-                            self.handle_command(&user_input, &tx_command).await?;
+                    KeyModifiers::ALT => {
+                        if let KeyCode::Char(digit @ '1'..='9') = code {
+                            let idx = digit as usize - '1' as usize;
+                            self.tabs.focus(idx);
                         }
-                        KeyCode::Char(to_insert) => self.user_input.input(to_insert),
-                        KeyCode::Backspace => self.user_input.backspace(),
-                        KeyCode::Delete => self.user_input.delete(),
-                        KeyCode::Left => self.user_input.move_left(),
-                        KeyCode::Right => self.user_input.move_right(),
-                        KeyCode::Up => {
-                            if self.show_help_menu {
-                                self.help_handle.move_up();
-                            } else {
-                                self.log_handle.move_up();
+                    }
+                    KeyModifiers::CONTROL => match code {
+                        KeyCode::Char('r') => self.raise_with_bet_slider(),
+                        KeyCode::Char('t') => self.cycle_theme(),
+                        code => {
+                            if let Some(tab) = self.tabs.active_mut() {
+                                match code {
+                                    KeyCode::Home => tab.log_handle.jump_to_first(),
+                                    KeyCode::End => tab.log_handle.jump_to_last(),
+                                    _ => {}
+                                }
                             }
                         }
-                        KeyCode::Down => {
-                            if self.show_help_menu {
-                                self.help_handle.move_down();
-                            } else {
-                                self.log_handle.move_down();
+                    },
+                    KeyModifiers::SHIFT => {
+                        if let Some(slider) = self
+                            .tabs
+                            .active_mut()
+                            .and_then(|tab| tab.bet_slider.as_mut())
+                        {
+                            match code {
+                                KeyCode::Left => slider.decrease(),
+                                KeyCode::Right => slider.increase(),
+                                _ => {}
                             }
                         }
-                        KeyCode::Home => self.user_input.jump_to_first(),
-                        KeyCode::End => self.user_input.jump_to_last(),
+                    }
+                    KeyModifiers::NONE => match code {
                         KeyCode::Tab => self.show_help_menu = !self.show_help_menu,
-                        KeyCode::Esc => {
-                            write_handle.abort();
-                            read_handle.abort();
-                            return Ok(());
+                        KeyCode::Esc => return Ok(()),
+                        KeyCode::Up if self.show_help_menu => self.help_handle.move_up(),
+                        KeyCode::Down if self.show_help_menu => self.help_handle.move_down(),
+                        code => {
+                            let Some(tab) = self.tabs.active_mut() else {
+                                continue;
+                            };
+                            match code {
+                                KeyCode::Enter => {
+                                    let user_input = tab.user_input.submit();
+                                    self.handle_command(&user_input).await;
+                                }
+                                KeyCode::Char(to_insert) => tab.user_input.input(to_insert),
+                                KeyCode::Backspace => tab.user_input.backspace(),
+                                KeyCode::Delete => tab.user_input.delete(),
+                                KeyCode::Left => tab.user_input.move_left(),
+                                KeyCode::Right => tab.user_input.move_right(),
+                                KeyCode::Up => tab.log_handle.move_up(),
+                                KeyCode::Down => tab.log_handle.move_down(),
+                                KeyCode::Home => tab.user_input.jump_to_first(),
+                                KeyCode::End => tab.user_input.jump_to_last(),
+                                KeyCode::F(key @ 1..=4) => {
+                                    if let Some(slider) = &mut tab.bet_slider {
+                                        slider.select(BetPreset::ALL[usize::from(key) - 1]);
+                                    }
+                                }
+                                KeyCode::F(key @ 6..=7) => {
+                                    let pre_action = PreAction::ALL[usize::from(key) - 6];
+                                    self.tabs.toggle_pre_action(pre_action);
+                                }
+                                _ => {}
+                            }
                         }
-                        _ => {}
                     },
                     _ => {}
                 }
             }
 
-            // Check for new game views
-            if let Ok(new_view) = rx_view.try_recv() {
-                self.update_view(new_view);
-                // Check if it's our turn
-                if let Some(next_idx) = self.view.play_positions.next_action_idx
-                    && let Some(player) = self.view.players.get(next_idx)
-                    && player.user.name == self.username
-                {
-                    self.turn_warnings.reset();
-                    self.add_log(RecordKind::Alert, "It's your turn!".to_string());
-                }
+            // Route new game views and connection updates to their tables
+            while let Ok(event) = self.rx_events.try_recv() {
+                self.tabs.handle_event(event);
             }
+            self.load_notes().await;
 
-            // Check for connection errors
-            if let Ok(error_msg) = rx_error.try_recv() {
-                self.connection_status = ConnectionStatus::Disconnected;
-                self.add_log(RecordKind::Error, error_msg);
+            // Exit once every table's connection is lost for good
+            if self.tabs.is_empty() || self.tabs.all_closed() {
                 terminal.draw(|frame| self.draw(frame))?;
                 tokio::time::sleep(Duration::from_secs(2)).await;
-                write_handle.abort();
-                read_handle.abort();
                 return Ok(());
             }
 
             // Check for turn warnings
-            if let Some(warning) = self.turn_warnings.check() {
-                self.add_log(RecordKind::Alert, format!("{warning:>2} second(s) left"));
+            for tab in self.tabs.tabs_mut() {
+                if let Some(warning) = tab.turn_warnings.check() {
+                    tab.add_log(RecordKind::Alert, format!("{warning:>2} second(s) left"));
+                }
             }
         }
     }
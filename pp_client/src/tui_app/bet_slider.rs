@@ -0,0 +1,214 @@
+use private_poker::entities::{Action, TurnInfo, Usd};
+use std::fmt;
+
+/// Quick bet sizes offered next to the bet slider.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BetPreset {
+    HalfPot,
+    TwoThirdsPot,
+    Pot,
+    AllIn,
+}
+
+impl BetPreset {
+    pub const ALL: [Self; 4] = [Self::HalfPot, Self::TwoThirdsPot, Self::Pot, Self::AllIn];
+
+    /// Amount to put in for the preset, before it's clamped to the legal
+    /// raise amounts. Pot-sized raises are sized as if the call was made
+    /// first, so a pot raise is the call plus the pot after calling.
+    pub fn amount(self, pot_size: Usd, call_amount: Usd, stack: Usd) -> Usd {
+        let pot_after_call = u64::from(pot_size) + u64::from(call_amount);
+        let raise = match self {
+            Self::HalfPot => pot_after_call / 2,
+            Self::TwoThirdsPot => pot_after_call * 2 / 3,
+            Self::Pot => pot_after_call,
+            Self::AllIn => return stack,
+        };
+        let amount = u64::from(call_amount) + raise;
+        Usd::try_from(amount).unwrap_or(Usd::MAX)
+    }
+}
+
+impl fmt::Display for BetPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let repr = match self {
+            Self::HalfPot => "1/2 pot",
+            Self::TwoThirdsPot => "2/3 pot",
+            Self::Pot => "pot",
+            Self::AllIn => "all-in",
+        };
+        write!(f, "{repr}")
+    }
+}
+
+/// Picks a raise amount between the smallest legal raise and the
+/// biggest one the betting structure allows. The top of the slider is
+/// an all-in when the player can bet their whole stack.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BetSlider {
+    min: Usd,
+    max: Usd,
+    stack: Usd,
+    step: Usd,
+    amount: Usd,
+    pot_size: Usd,
+    call_amount: Usd,
+}
+
+impl BetSlider {
+    /// Create a slider for the player's turn that moves by `step`,
+    /// starting at the smallest legal raise. Returns nothing if the
+    /// player can't raise or can only raise by going all-in.
+    pub fn new(turn: &TurnInfo, stack: Usd, step: Usd) -> Option<Self> {
+        let min = turn.min_raise?;
+        if min >= stack {
+            return None;
+        }
+        let max = turn
+            .max_raise
+            .map_or(stack, |max_raise| max_raise.min(stack));
+        Some(Self {
+            min,
+            max,
+            stack,
+            step: step.max(1),
+            amount: min,
+            pot_size: turn.pot_size,
+            call_amount: turn.call_amount,
+        })
+    }
+
+    pub fn amount(&self) -> Usd {
+        self.amount
+    }
+
+    pub fn min(&self) -> Usd {
+        self.min
+    }
+
+    pub fn max(&self) -> Usd {
+        self.max
+    }
+
+    /// Set the amount, clamped to the legal raise amounts.
+    pub fn set_amount(&mut self, amount: Usd) {
+        self.amount = amount.clamp(self.min, self.max);
+    }
+
+    pub fn increase(&mut self) {
+        self.set_amount(self.amount.saturating_add(self.step));
+    }
+
+    pub fn decrease(&mut self) {
+        self.set_amount(self.amount.saturating_sub(self.step));
+    }
+
+    /// Amount the preset would set the slider to.
+    pub fn preset_amount(&self, preset: BetPreset) -> Usd {
+        preset
+            .amount(self.pot_size, self.call_amount, self.max)
+            .clamp(self.min, self.max)
+    }
+
+    pub fn select(&mut self, preset: BetPreset) {
+        self.amount = self.preset_amount(preset);
+    }
+
+    /// Action for the slider's amount. Putting in the whole stack is an
+    /// all-in rather than a raise.
+    pub fn action(&self) -> Action {
+        if self.amount >= self.stack {
+            Action::AllIn
+        } else {
+            Action::Raise(Some(self.amount))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use private_poker::entities::{Action, ActionChoice, ActionChoices, TurnInfo};
+
+    use super::{BetPreset, BetSlider};
+
+    fn turn_info(call_amount: u32, min_raise: u32, pot_size: u32) -> TurnInfo {
+        let choices = ActionChoices::from([
+            ActionChoice::Fold,
+            ActionChoice::Call(call_amount),
+            ActionChoice::Raise {
+                min: min_raise,
+                max: 1000,
+            },
+            ActionChoice::AllIn,
+        ]);
+        TurnInfo::new(choices, call_amount, pot_size)
+    }
+
+    #[test]
+    fn pot_limit_caps_slider_below_stack() {
+        let mut turn = turn_info(100, 200, 300);
+        turn.max_raise = Some(500);
+        let mut slider = BetSlider::new(&turn, 1000, 10).unwrap();
+        assert_eq!(slider.max(), 500);
+        assert_eq!(slider.preset_amount(BetPreset::AllIn), 500);
+        slider.select(BetPreset::AllIn);
+        assert_eq!(slider.action(), Action::Raise(Some(500)));
+    }
+
+    #[test]
+    fn preset_amounts() {
+        // $100 to call into a $300 pot makes a $400 pot after calling.
+        let slider = BetSlider::new(&turn_info(100, 200, 300), 1000, 10).unwrap();
+        assert_eq!(slider.preset_amount(BetPreset::HalfPot), 300);
+        assert_eq!(slider.preset_amount(BetPreset::TwoThirdsPot), 366);
+        assert_eq!(slider.preset_amount(BetPreset::Pot), 500);
+        assert_eq!(slider.preset_amount(BetPreset::AllIn), 1000);
+    }
+
+    #[test]
+    fn presets_clamp_to_legal_amounts() {
+        // Small pot presets are raised to the min raise.
+        let mut slider = BetSlider::new(&turn_info(0, 20, 30), 1000, 10).unwrap();
+        slider.select(BetPreset::HalfPot);
+        assert_eq!(slider.amount(), 20);
+        assert_eq!(slider.action(), Action::Raise(Some(20)));
+
+        // Presets bigger than the stack are all-ins.
+        let mut slider = BetSlider::new(&turn_info(100, 200, 300), 400, 10).unwrap();
+        slider.select(BetPreset::Pot);
+        assert_eq!(slider.amount(), 400);
+        assert_eq!(slider.action(), Action::AllIn);
+    }
+
+    #[test]
+    fn all_in_preset_is_an_all_in() {
+        let mut slider = BetSlider::new(&turn_info(100, 200, 300), 1000, 10).unwrap();
+        slider.select(BetPreset::AllIn);
+        assert_eq!(slider.action(), Action::AllIn);
+    }
+
+    #[test]
+    fn slider_clamps_to_legal_amounts() {
+        let mut slider = BetSlider::new(&turn_info(100, 200, 300), 215, 10).unwrap();
+        assert_eq!(slider.action(), Action::Raise(Some(200)));
+        slider.decrease();
+        assert_eq!(slider.amount(), 200);
+        slider.increase();
+        assert_eq!(slider.action(), Action::Raise(Some(210)));
+        slider.increase();
+        assert_eq!(slider.amount(), 215);
+        assert_eq!(slider.action(), Action::AllIn);
+        slider.set_amount(1);
+        assert_eq!(slider.amount(), 200);
+    }
+
+    #[test]
+    fn no_slider_without_a_raise() {
+        let choices = ActionChoices::from([ActionChoice::Fold, ActionChoice::AllIn]);
+        let turn = TurnInfo::new(choices, 500, 300);
+        assert_eq!(BetSlider::new(&turn, 400, 10), None);
+
+        // Raising the min would put the whole stack in.
+        assert_eq!(BetSlider::new(&turn_info(100, 200, 300), 200, 10), None);
+    }
+}
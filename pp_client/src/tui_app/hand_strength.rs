@@ -0,0 +1,229 @@
+use private_poker::{
+    entities::{Card, GameView, PlayerState, Rank, SubHand, Suit, Username, Value},
+    functional,
+};
+use std::collections::BTreeSet;
+
+/// Number of simulated runouts used to estimate equity.
+const EQUITY_ITERATIONS: usize = 2_000;
+
+/// What the hand strength is worked out from: the user's own hole cards,
+/// the board, and how many opponents are still in the hand.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HandInputs {
+    hole: Vec<Card>,
+    board: Vec<Card>,
+    opponents: usize,
+}
+
+impl HandInputs {
+    pub fn new(hole: Vec<Card>, board: Vec<Card>, opponents: usize) -> Self {
+        Self {
+            hole,
+            board,
+            opponents,
+        }
+    }
+
+    /// Inputs for the user's hand in the view, or nothing if they don't
+    /// have hole cards. Other players' cards are never used, even if
+    /// they're shown.
+    pub fn from_view(view: &GameView, username: &Username) -> Option<Self> {
+        let player = view
+            .players
+            .iter()
+            .find(|player| &player.user.name == username)?;
+        if player.cards.is_empty() {
+            return None;
+        }
+        let opponents = view
+            .players
+            .iter()
+            .filter(|player| &player.user.name != username && player.state != PlayerState::Fold)
+            .count();
+        Some(Self::new(
+            player.cards.to_vec(),
+            view.board.to_vec(),
+            opponents,
+        ))
+    }
+}
+
+/// How strong the user's hand is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HandStrength {
+    pub inputs: HandInputs,
+    /// Best hand made so far, e.g., "Pair of Aces"
+    pub made_hand: String,
+    /// Draws to a better hand while there are cards to come
+    pub draws: Vec<&'static str>,
+    /// Rough chance of winning or splitting the pot against the
+    /// opponents still in the hand
+    pub equity: Option<f64>,
+}
+
+impl HandStrength {
+    pub fn new(inputs: HandInputs) -> Self {
+        let mut cards = inputs.hole.clone();
+        cards.extend(&inputs.board);
+        functional::prepare_hand(&mut cards);
+        let hand = functional::eval(&cards);
+        let rank = hand.first().map_or(Rank::HighCard, |subhand| subhand.rank);
+        let made_hand = hand.first().map_or_else(String::new, SubHand::describe);
+
+        let mut draws = Vec::new();
+        if (3..5).contains(&inputs.board.len()) {
+            if rank < Rank::Flush && has_flush_draw(&cards) {
+                draws.push("Flush draw");
+            }
+            if rank < Rank::Straight && has_straight_draw(&cards) {
+                draws.push("Straight draw");
+            }
+        }
+
+        let equity = (inputs.opponents > 0).then(|| {
+            functional::equity(
+                &inputs.hole,
+                &inputs.board,
+                inputs.opponents,
+                EQUITY_ITERATIONS,
+            )
+        });
+        Self {
+            inputs,
+            made_hand,
+            draws,
+            equity,
+        }
+    }
+}
+
+/// Whether four cards share a suit. Expects prepared cards, so high aces
+/// are skipped to avoid counting aces twice.
+fn has_flush_draw(cards: &[Card]) -> bool {
+    [Suit::Club, Suit::Spade, Suit::Diamond, Suit::Heart]
+        .iter()
+        .any(|suit| {
+            cards
+                .iter()
+                .filter(|Card(value, card_suit)| card_suit == suit && *value != 14)
+                .count()
+                >= 4
+        })
+}
+
+/// Whether four of the five values of some straight are present.
+/// Expects prepared cards, so aces count as both low and high.
+fn has_straight_draw(cards: &[Card]) -> bool {
+    let values: BTreeSet<Value> = cards.iter().map(|Card(value, _)| *value).collect();
+    (1..=10).any(|low| {
+        (low..low + 5)
+            .filter(|value| values.contains(value))
+            .count()
+            >= 4
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use private_poker::entities::{Card, PlayerState, PlayerView, Suit, User, Username};
+    use std::sync::Arc;
+
+    use super::{HandInputs, HandStrength};
+    use crate::tui_app::empty_view;
+
+    fn strength(hole: &[Card], board: &[Card]) -> HandStrength {
+        HandStrength::new(HandInputs::new(hole.to_vec(), board.to_vec(), 0))
+    }
+
+    #[test]
+    fn pair_of_aces() {
+        let hole = [Card(1, Suit::Spade), Card(14, Suit::Heart)];
+        let board = [
+            Card(13, Suit::Club),
+            Card(7, Suit::Diamond),
+            Card(2, Suit::Spade),
+        ];
+        let strength = strength(&hole, &board);
+        assert_eq!(strength.made_hand, "Pair of Aces");
+        assert!(strength.draws.is_empty());
+        assert_eq!(strength.equity, None);
+    }
+
+    #[test]
+    fn full_house() {
+        let hole = [Card(12, Suit::Spade), Card(12, Suit::Heart)];
+        let board = [
+            Card(12, Suit::Club),
+            Card(5, Suit::Diamond),
+            Card(5, Suit::Spade),
+        ];
+        assert_eq!(
+            strength(&hole, &board).made_hand,
+            "Full house, Queens full of Fives"
+        );
+    }
+
+    #[test]
+    fn draws_on_the_flop() {
+        let hole = [Card(1, Suit::Heart), Card(13, Suit::Heart)];
+        let board = [
+            Card(2, Suit::Heart),
+            Card(7, Suit::Heart),
+            Card(12, Suit::Club),
+        ];
+        let flush_draw = strength(&hole, &board);
+        assert_eq!(flush_draw.made_hand, "Ace high");
+        assert_eq!(flush_draw.draws, vec!["Flush draw"]);
+
+        let hole = [Card(9, Suit::Heart), Card(10, Suit::Spade)];
+        let board = [
+            Card(11, Suit::Club),
+            Card(12, Suit::Diamond),
+            Card(3, Suit::Club),
+        ];
+        assert_eq!(strength(&hole, &board).draws, vec!["Straight draw"]);
+    }
+
+    #[test]
+    fn no_draws_on_the_river() {
+        let hole = [Card(1, Suit::Heart), Card(13, Suit::Heart)];
+        let board = [
+            Card(2, Suit::Heart),
+            Card(7, Suit::Heart),
+            Card(12, Suit::Club),
+            Card(4, Suit::Club),
+            Card(9, Suit::Spade),
+        ];
+        assert!(strength(&hole, &board).draws.is_empty());
+    }
+
+    #[test]
+    fn only_uses_the_users_cards() {
+        let player = |name: &str, cards: Vec<Card>| PlayerView {
+            user: User {
+                name: Username::new(name),
+                money: 100,
+            },
+            state: PlayerState::Wait,
+            cards: Arc::new(cards),
+            sitting_out: false,
+            seat_idx: 0,
+            committed_this_street: 0,
+            total_committed: 0,
+        };
+        let mut view = empty_view();
+        view.players = vec![
+            player("alice", vec![Card(2, Suit::Club), Card(7, Suit::Diamond)]),
+            player("bob", vec![Card(1, Suit::Spade), Card(1, Suit::Heart)]),
+        ];
+        let inputs = HandInputs::from_view(&view, &Username::new("alice")).unwrap();
+        assert_eq!(
+            inputs,
+            HandInputs::new(vec![Card(2, Suit::Club), Card(7, Suit::Diamond)], vec![], 1)
+        );
+
+        // Spectators don't have a hand.
+        assert_eq!(HandInputs::from_view(&view, &Username::new("carol")), None);
+    }
+}
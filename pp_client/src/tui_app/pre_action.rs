@@ -0,0 +1,146 @@
+use private_poker::entities::{Action, ActionChoices, RecentAction};
+use std::fmt;
+
+/// Action picked ahead of the user's turn that's taken as soon as their
+/// turn arrives.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PreAction {
+    /// Check if possible, otherwise fold
+    CheckFold,
+    /// Call whatever the bet is, checking if there's nothing to call
+    CallAny,
+}
+
+impl PreAction {
+    pub const ALL: [Self; 2] = [Self::CheckFold, Self::CallAny];
+
+    /// Action to take for the pre-action given the choices on the user's
+    /// turn, or nothing if none of the choices fit.
+    pub fn resolve(self, action_choices: &ActionChoices) -> Option<Action> {
+        let candidates: &[Action] = match self {
+            Self::CheckFold => &[Action::Check, Action::Fold],
+            // Calling a bet as big as the stack is an all-in.
+            Self::CallAny => &[Action::Check, Action::Call, Action::AllIn],
+        };
+        candidates
+            .iter()
+            .find(|action| action_choices.contains(action))
+            .cloned()
+    }
+
+    /// Whether the pre-action still holds after the given actions by
+    /// other players. A bet or raise changes what the user committed to
+    /// call, so "call any" is cleared, while "check/fold" still folds to
+    /// the new bet.
+    pub fn holds_after(self, actions: &[RecentAction]) -> bool {
+        match self {
+            Self::CheckFold => true,
+            Self::CallAny => !actions
+                .iter()
+                .any(|action| matches!(action.action, Action::Raise(_) | Action::AllIn)),
+        }
+    }
+}
+
+impl fmt::Display for PreAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let repr = match self {
+            Self::CheckFold => "check/fold",
+            Self::CallAny => "call any",
+        };
+        write!(f, "{repr}")
+    }
+}
+
+/// Actions in `new` that came after the last action in `old`. All of
+/// `new` is returned if it doesn't continue `old`, e.g., for a new hand.
+pub fn new_actions<'a>(old: &[RecentAction], new: &'a [RecentAction]) -> &'a [RecentAction] {
+    let start = old
+        .last()
+        .and_then(|last| new.iter().rposition(|action| action == last))
+        .map_or(0, |idx| idx + 1);
+    &new[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use private_poker::entities::{Action, ActionChoice, ActionChoices, RecentAction};
+
+    use super::{PreAction, new_actions};
+
+    fn recent(seat: usize, action: Action, pot_size: u32) -> RecentAction {
+        RecentAction {
+            seat,
+            action,
+            pot_size,
+        }
+    }
+
+    #[test]
+    fn check_fold_checks_when_possible() {
+        let choices = ActionChoices::from([
+            ActionChoice::Fold,
+            ActionChoice::Check,
+            ActionChoice::Raise { min: 20, max: 1000 },
+            ActionChoice::AllIn,
+        ]);
+        assert_eq!(PreAction::CheckFold.resolve(&choices), Some(Action::Check));
+    }
+
+    #[test]
+    fn check_fold_folds_to_a_bet() {
+        let choices = ActionChoices::from([
+            ActionChoice::Fold,
+            ActionChoice::Call(20),
+            ActionChoice::Raise { min: 40, max: 1000 },
+            ActionChoice::AllIn,
+        ]);
+        assert_eq!(PreAction::CheckFold.resolve(&choices), Some(Action::Fold));
+    }
+
+    #[test]
+    fn call_any_calls_or_checks() {
+        let choices = ActionChoices::from([
+            ActionChoice::Fold,
+            ActionChoice::Call(20),
+            ActionChoice::Raise { min: 40, max: 1000 },
+            ActionChoice::AllIn,
+        ]);
+        assert_eq!(PreAction::CallAny.resolve(&choices), Some(Action::Call));
+
+        let choices = ActionChoices::from([ActionChoice::Fold, ActionChoice::Check]);
+        assert_eq!(PreAction::CallAny.resolve(&choices), Some(Action::Check));
+    }
+
+    #[test]
+    fn call_any_goes_all_in_to_call_a_bigger_bet() {
+        let choices = ActionChoices::from([ActionChoice::Fold, ActionChoice::AllIn]);
+        assert_eq!(PreAction::CallAny.resolve(&choices), Some(Action::AllIn));
+    }
+
+    #[test]
+    fn raise_behind_clears_call_any() {
+        let old = vec![recent(0, Action::Raise(Some(20)), 35)];
+        let new = vec![
+            recent(0, Action::Raise(Some(20)), 35),
+            recent(1, Action::Call, 55),
+            recent(2, Action::Raise(Some(80)), 135),
+        ];
+        let actions = new_actions(&old, &new);
+        assert_eq!(actions, &new[1..]);
+        assert!(!PreAction::CallAny.holds_after(actions));
+        assert!(PreAction::CheckFold.holds_after(actions));
+
+        // Calls don't change what the user committed to.
+        let actions = new_actions(&old, &new[..2]);
+        assert!(PreAction::CallAny.holds_after(actions));
+    }
+
+    #[test]
+    fn new_hand_actions_are_all_new() {
+        let old = vec![recent(0, Action::Check, 30)];
+        let new = vec![recent(1, Action::AllIn, 130)];
+        assert_eq!(new_actions(&old, &new), &new[..]);
+        assert_eq!(new_actions(&[], &new), &new[..]);
+    }
+}
@@ -0,0 +1,430 @@
+use private_poker::entities::{
+    Action, ActionRecord, Card, CardParseError, GameView, HandHistory, HistoryAction,
+    PlayPositions, PlayerState, PlayerView, PotView, RecentAction, Street, StreetRecord, User,
+};
+use std::sync::Arc;
+
+use super::empty_view;
+
+/// What happened at a step of a replayed hand.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReplayStep {
+    /// Antes, blinds, and straddles were posted
+    ForcedBets,
+    /// A player acted
+    Action(usize),
+    /// Community cards were dealt for a street
+    Deal(Street),
+    /// Hands were shown and the pot was awarded
+    Showdown,
+}
+
+/// The table as it was right after a step of a replayed hand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayFrame {
+    pub step: ReplayStep,
+    /// What happened, e.g., "alice raises $20"
+    pub description: String,
+    pub view: GameView,
+}
+
+/// Steps through a recorded hand one action at a time. Every frame is
+/// reconstructed from the history up front, so stepping back and forth
+/// always shows the same views.
+#[derive(Clone, Debug)]
+pub struct HandReplay {
+    frames: Vec<ReplayFrame>,
+    idx: usize,
+}
+
+impl HandReplay {
+    pub fn new(history: &HandHistory) -> Result<Self, CardParseError> {
+        let mut rebuilder = Rebuilder::new(history);
+        rebuilder.forced_bets(history);
+        let mut board = history.board.iter().peekable();
+        for (action_idx, action) in history.actions.iter().enumerate() {
+            while let Some(record) = board.next_if(|record| record.street <= action.street) {
+                rebuilder.deal(record)?;
+            }
+            rebuilder.act(action_idx, action);
+        }
+        // Streets run out after everyone is all-in or the action is over.
+        for record in board {
+            rebuilder.deal(record)?;
+        }
+        rebuilder.showdown(history)?;
+        Ok(Self {
+            frames: rebuilder.finish(history),
+            idx: 0,
+        })
+    }
+
+    pub fn frame(&self) -> &ReplayFrame {
+        &self.frames[self.idx]
+    }
+
+    pub fn view(&self) -> &GameView {
+        &self.frame().view
+    }
+
+    /// Frames up to and including the current one.
+    pub fn played(&self) -> &[ReplayFrame] {
+        &self.frames[..=self.idx]
+    }
+
+    /// Index of the current frame.
+    pub fn position(&self) -> usize {
+        self.idx
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Step forward, returning whether there was a step to take.
+    pub fn advance(&mut self) -> bool {
+        if self.idx + 1 < self.frames.len() {
+            self.idx += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Step back, returning whether there was a step to undo.
+    pub fn rewind(&mut self) -> bool {
+        if self.idx > 0 {
+            self.idx -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn street_name(street: Street) -> &'static str {
+    match street {
+        Street::Preflop => "Preflop",
+        Street::Flop => "Flop",
+        Street::Turn => "Turn",
+        Street::River => "River",
+    }
+}
+
+fn parse_cards(cards: &[String]) -> Result<Vec<Card>, CardParseError> {
+    cards.iter().map(|card| card.parse()).collect()
+}
+
+/// Builds the frames of a replay by applying each step of a history to
+/// the view of the step before it.
+struct Rebuilder {
+    /// Seat of each player in the view's players list
+    seats: Vec<usize>,
+    view: GameView,
+    frames: Vec<ReplayFrame>,
+}
+
+impl Rebuilder {
+    fn new(history: &HandHistory) -> Self {
+        let mut seats: Vec<_> = history.seats.iter().collect();
+        seats.sort_by_key(|seat| seat.seat);
+        let mut view = empty_view();
+        view.blinds = Arc::new(history.blinds.clone());
+        view.players = seats
+            .iter()
+            .map(|seat| PlayerView {
+                user: User {
+                    name: seat.username.clone(),
+                    money: seat.starting_stack,
+                },
+                state: PlayerState::Wait,
+                cards: Arc::new(Vec::new()),
+                sitting_out: false,
+                seat_idx: seat.seat,
+                committed_this_street: 0,
+                total_committed: 0,
+            })
+            .collect();
+        // Blind positions aren't recorded, so no one is marked as a blind.
+        view.play_positions = Arc::new(PlayPositions {
+            small_blind_idx: seats.len(),
+            big_blind_idx: seats.len(),
+            next_action_idx: None,
+            ..PlayPositions::default()
+        });
+        Self {
+            seats: seats.iter().map(|seat| seat.seat).collect(),
+            view,
+            frames: Vec::new(),
+        }
+    }
+
+    fn player_idx(&self, seat: usize) -> Option<usize> {
+        self.seats.iter().position(|s| *s == seat)
+    }
+
+    fn add_to_pot(&mut self, player_idx: usize, amount: u32) {
+        let player = &mut self.view.players[player_idx];
+        player.user.money = player.user.money.saturating_sub(amount);
+        player.committed_this_street += amount;
+        player.total_committed += amount;
+        self.view.pot = Arc::new(PotView {
+            size: self.view.pot.size + amount,
+        });
+    }
+
+    fn push(&mut self, step: ReplayStep, description: String) {
+        self.frames.push(ReplayFrame {
+            step,
+            description,
+            view: self.view.clone(),
+        });
+    }
+
+    fn forced_bets(&mut self, history: &HandHistory) {
+        for forced_bet in &history.forced_bets {
+            if let Some(idx) = self.player_idx(forced_bet.seat) {
+                self.add_to_pot(idx, forced_bet.amount);
+            }
+        }
+        self.push(
+            ReplayStep::ForcedBets,
+            format!("Blinds {} posted", history.blinds),
+        );
+    }
+
+    fn act(&mut self, action_idx: usize, record: &ActionRecord) {
+        let Some(idx) = self.player_idx(record.seat) else {
+            return;
+        };
+        self.add_to_pot(idx, record.amount);
+        let (state, action) = match record.action {
+            HistoryAction::AllIn => (PlayerState::AllIn, Action::AllIn),
+            HistoryAction::Call => (PlayerState::Call, Action::Call),
+            HistoryAction::Check => (PlayerState::Check, Action::Check),
+            HistoryAction::Fold => (PlayerState::Fold, Action::Fold),
+            HistoryAction::Raise => (PlayerState::Raise, Action::Raise(Some(record.amount))),
+        };
+        self.view.players[idx].state = state;
+        let mut recent_actions = self.view.recent_actions.to_vec();
+        recent_actions.push(RecentAction {
+            seat: idx,
+            action: action.clone(),
+            pot_size: self.view.pot.size,
+        });
+        self.view.recent_actions = Arc::new(recent_actions);
+
+        let description = match action {
+            Action::AllIn | Action::Call => {
+                format!("{} {} (${})", record.username, action, record.amount)
+            }
+            action => format!("{} {action}", record.username),
+        };
+        self.push(ReplayStep::Action(action_idx), description);
+    }
+
+    fn deal(&mut self, record: &StreetRecord) -> Result<(), CardParseError> {
+        let cards = parse_cards(&record.cards)?;
+        let mut board = self.view.board.to_vec();
+        board.extend(cards.iter().copied());
+        self.view.board = Arc::new(board);
+        for player in &mut self.view.players {
+            player.committed_this_street = 0;
+            if !matches!(player.state, PlayerState::AllIn | PlayerState::Fold) {
+                player.state = PlayerState::Wait;
+            }
+        }
+
+        let cards: Vec<_> = cards.iter().map(|card| card.to_string()).collect();
+        self.push(
+            ReplayStep::Deal(record.street),
+            format!("{}:{}", street_name(record.street), cards.join("")),
+        );
+        Ok(())
+    }
+
+    fn showdown(&mut self, history: &HandHistory) -> Result<(), CardParseError> {
+        for shown in &history.showdown {
+            if let (Some(idx), Some(cards)) = (self.player_idx(shown.seat), &shown.cards) {
+                self.view.players[idx].cards = Arc::new(parse_cards(cards)?);
+            }
+        }
+        self.view.second_board = Arc::new(parse_cards(&history.second_board)?);
+        for award in &history.pot_awards {
+            if let Some(idx) = self.player_idx(award.seat) {
+                self.view.players[idx].user.money += award.amount;
+            }
+        }
+        self.view.pot = Arc::new(PotView { size: 0 });
+
+        let awards: Vec<_> = history
+            .pot_awards
+            .iter()
+            .map(|award| format!("{} wins ${}", award.username, award.amount))
+            .collect();
+        self.push(ReplayStep::Showdown, awards.join(", "));
+        Ok(())
+    }
+
+    /// Point each frame at the player that acts next.
+    fn finish(mut self, history: &HandHistory) -> Vec<ReplayFrame> {
+        let next_seats: Vec<_> = self
+            .frames
+            .iter()
+            .skip(1)
+            .map(|frame| match frame.step {
+                ReplayStep::Action(action_idx) => Some(history.actions[action_idx].seat),
+                _ => None,
+            })
+            .collect();
+        for (frame, next_seat) in self.frames.iter_mut().zip(next_seats) {
+            let next_action_idx =
+                next_seat.and_then(|seat| self.seats.iter().position(|s| *s == seat));
+            let mut play_positions = (*frame.view.play_positions).clone();
+            play_positions.next_action_idx = next_action_idx;
+            frame.view.play_positions = Arc::new(play_positions);
+        }
+        self.frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use private_poker::entities::{
+        ActionRecord, Blinds, ForcedBetRecord, HandHistory, HistoryAction, PotAward, SeatRecord,
+        ShowdownRecord, Street, StreetRecord, Username,
+    };
+
+    use super::{HandReplay, ReplayStep};
+
+    fn scripted_history() -> HandHistory {
+        let username = |seat: usize| Username::new(["alice", "bob", "carol"][seat]);
+        let action = |street, seat, action, amount| ActionRecord {
+            street,
+            seat,
+            username: username(seat),
+            action,
+            amount,
+        };
+        let street = |street, cards: &[&str]| StreetRecord {
+            street,
+            cards: cards.iter().map(|card| card.to_string()).collect(),
+        };
+        let seats = (0..3)
+            .map(|seat| SeatRecord {
+                seat,
+                username: username(seat),
+                starting_stack: 200,
+                hole_cards: None,
+            })
+            .collect();
+        let mut history = HandHistory::new(
+            Blinds {
+                small: 5,
+                big: 10,
+                ante: 0,
+            },
+            seats,
+        );
+        history.forced_bets = vec![
+            ForcedBetRecord {
+                seat: 1,
+                username: username(1),
+                amount: 5,
+            },
+            ForcedBetRecord {
+                seat: 2,
+                username: username(2),
+                amount: 10,
+            },
+        ];
+        history.actions = vec![
+            action(Street::Preflop, 0, HistoryAction::Call, 10),
+            action(Street::Preflop, 1, HistoryAction::Call, 5),
+            action(Street::Preflop, 2, HistoryAction::Check, 0),
+            action(Street::Flop, 1, HistoryAction::Raise, 20),
+            action(Street::Flop, 2, HistoryAction::Fold, 0),
+            action(Street::Flop, 0, HistoryAction::Call, 20),
+            action(Street::Turn, 1, HistoryAction::AllIn, 170),
+            action(Street::Turn, 0, HistoryAction::Call, 170),
+        ];
+        history.board = vec![
+            street(Street::Flop, &["As", "Kd", "2c"]),
+            street(Street::Turn, &["7h"]),
+            street(Street::River, &["9s"]),
+        ];
+        history.showdown = vec![
+            ShowdownRecord {
+                seat: 0,
+                username: username(0),
+                cards: Some(vec!["Ah".to_string(), "Qc".to_string()]),
+            },
+            ShowdownRecord {
+                seat: 1,
+                username: username(1),
+                cards: Some(vec!["Kh".to_string(), "Ks".to_string()]),
+            },
+        ];
+        history.pot_awards = vec![PotAward {
+            seat: 1,
+            username: username(1),
+            amount: 410,
+        }];
+        history
+    }
+
+    #[test]
+    fn reconstructs_pot_at_each_street() {
+        let history = scripted_history();
+        let mut replay = HandReplay::new(&history).unwrap();
+        let mut pots = vec![(replay.frame().step.clone(), replay.view().pot.size)];
+        while replay.advance() {
+            if matches!(
+                replay.frame().step,
+                ReplayStep::Deal(_) | ReplayStep::Showdown
+            ) {
+                pots.push((replay.frame().step.clone(), replay.view().pot.size));
+            }
+        }
+        assert_eq!(
+            pots,
+            vec![
+                (ReplayStep::ForcedBets, 15),
+                (ReplayStep::Deal(Street::Flop), 30),
+                (ReplayStep::Deal(Street::Turn), 70),
+                (ReplayStep::Deal(Street::River), 410),
+                (ReplayStep::Showdown, 0),
+            ]
+        );
+        let view = replay.view();
+        assert_eq!(view.board.len(), 5);
+        let stacks: Vec<_> = view.players.iter().map(|p| p.user.money).collect();
+        assert_eq!(stacks, vec![0, 410, 190]);
+        assert_eq!(view.players[0].cards.len(), 2);
+        assert!(view.players[2].cards.is_empty());
+    }
+
+    #[test]
+    fn rewinding_restores_earlier_views() {
+        let history = scripted_history();
+        let mut replay = HandReplay::new(&history).unwrap();
+        assert!(!replay.rewind());
+        let mut views = vec![replay.view().clone()];
+        while replay.advance() {
+            views.push(replay.view().clone());
+        }
+        assert_eq!(views.len(), replay.num_frames());
+        for view in views.iter().rev().skip(1) {
+            assert!(replay.rewind());
+            assert_eq!(replay.view(), view);
+        }
+        assert_eq!(replay.position(), 0);
+
+        // Nothing is left to chance, so replays are identical.
+        let mut other = HandReplay::new(&history).unwrap();
+        for view in &views {
+            assert_eq!(other.view(), view);
+            other.advance();
+        }
+    }
+}
@@ -0,0 +1,527 @@
+use anyhow::{Result, anyhow};
+use futures_util::{SinkExt, StreamExt};
+use private_poker::{
+    entities::{Action, GameView, GameViewUpdate, TurnInfo, Username},
+    net::messages::ServerMessage,
+};
+use std::time::{Duration, Instant};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tokio_tungstenite::tungstenite::Message;
+
+use super::{
+    BetSlider, ClientCommand, ConnectionStatus, MAX_LOG_RECORDS, PreAction, Record, RecordKind,
+    TurnWarnings, empty_view,
+    hand_strength::{HandInputs, HandStrength},
+    pre_action::new_actions,
+    widgets::{ScrollableList, UserInput},
+};
+use crate::{
+    notifications::TurnNotifications,
+    websocket_client::{
+        Disconnect, GameViewSync, LOST_SEAT_MESSAGE, SeatTracker, SyncStatus, WebSocketClient,
+        WsStream, message_text,
+    },
+};
+
+/// How long to wait for a table's connection to close cleanly on quit
+/// before dropping it.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Update from a table's connection, tagged with the table it's for.
+#[derive(Debug)]
+pub enum TableEvent {
+    /// New game view
+    View { table_id: i64, view: GameView },
+    /// It's the user's turn
+    TurnSignal { table_id: i64, turn: TurnInfo },
+    /// Connection status change or message for the table
+    Status {
+        table_id: i64,
+        status: ConnectionStatus,
+        message: String,
+    },
+    /// The connection couldn't be recovered
+    Closed { table_id: i64, error: String },
+}
+
+impl TableEvent {
+    /// The table the event is for.
+    pub fn table_id(&self) -> i64 {
+        match self {
+            Self::View { table_id, .. }
+            | Self::TurnSignal { table_id, .. }
+            | Self::Status { table_id, .. }
+            | Self::Closed { table_id, .. } => *table_id,
+        }
+    }
+}
+
+/// Send a command to the table, ignoring commands that can't be sent
+/// since a dropped connection is handled when reading from it.
+async fn send_command(ws_stream: &mut WsStream, command: &ClientCommand) {
+    if let Ok(json) = serde_json::to_string(command) {
+        let _ = ws_stream.send(Message::Text(json.into())).await;
+    }
+}
+
+/// Spawn a task that relays commands to a table and sends the table's
+/// updates to `events`, reconnecting at the same table if the connection
+/// drops, unless the server closed it for good. Views are tagged with
+/// `table_id`, while table messages that carry their own table ID are
+/// routed by it. The connection is closed once the returned command
+/// sender is dropped.
+pub fn spawn_table_connection(
+    table_id: i64,
+    mut client: WebSocketClient,
+    mut ws_stream: WsStream,
+    username: Username,
+    events: mpsc::UnboundedSender<TableEvent>,
+) -> (mpsc::UnboundedSender<ClientCommand>, JoinHandle<()>) {
+    let (tx_command, mut rx_command) = mpsc::unbounded_channel::<ClientCommand>();
+    let connection = tokio::spawn(async move {
+        let send_status = |status, message: String| {
+            let _ = events.send(TableEvent::Status {
+                table_id,
+                status,
+                message,
+            });
+        };
+        let mut view_sync = GameViewSync::default();
+        let mut seat_tracker = SeatTracker::default();
+        loop {
+            tokio::select! {
+                command = rx_command.recv() => match command {
+                    Some(command) => send_command(&mut ws_stream, &command).await,
+                    None => {
+                        let _ = ws_stream.close(None).await;
+                        break;
+                    }
+                },
+                msg = ws_stream.next() => match msg {
+                    Some(Ok(message @ (Message::Text(_) | Message::Binary(_)))) => {
+                        let Some(text) = message_text(&message) else {
+                            continue;
+                        };
+                        if let Ok(update) = serde_json::from_str::<GameViewUpdate>(&text) {
+                            match view_sync.apply(update) {
+                                SyncStatus::Updated(view) => {
+                                    if seat_tracker.lost_seat(view, &username) {
+                                        let message = LOST_SEAT_MESSAGE.to_string();
+                                        send_status(ConnectionStatus::Connected, message);
+                                    }
+                                    let view = view.clone();
+                                    let _ = events.send(TableEvent::View { table_id, view });
+                                }
+                                SyncStatus::ResyncNeeded => {
+                                    send_command(&mut ws_stream, &ClientCommand::Resync).await;
+                                }
+                                SyncStatus::AwaitingSnapshot => {}
+                            }
+                        } else if let Ok(message) = serde_json::from_str::<ServerMessage>(&text)
+                        {
+                            match message {
+                                ServerMessage::Ping { nonce } => {
+                                    let command = ClientCommand::Pong { nonce };
+                                    send_command(&mut ws_stream, &command).await;
+                                }
+                                ServerMessage::TableGameView { table_id, view } => {
+                                    let _ = events.send(TableEvent::View { table_id, view });
+                                }
+                                ServerMessage::TableTurnSignal { table_id, turn } => {
+                                    let _ = events.send(TableEvent::TurnSignal { table_id, turn });
+                                }
+                                ServerMessage::TableGameEvent { event, .. } => {
+                                    send_status(ConnectionStatus::Connected, event.to_string());
+                                }
+                                ServerMessage::MissedEvents {
+                                    events: missed, complete, ..
+                                } => {
+                                    if !complete {
+                                        send_status(
+                                            ConnectionStatus::Connected,
+                                            "Some events while disconnected weren't kept"
+                                                .to_string(),
+                                        );
+                                    }
+                                    for event in missed {
+                                        let message = format!("While disconnected: {}", event);
+                                        send_status(ConnectionStatus::Connected, message);
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    msg @ (Some(Ok(Message::Close(_)) | Err(_)) | None) => {
+                        let disconnect = match msg {
+                            Some(Ok(Message::Close(frame))) => Disconnect::from_frame(frame.as_ref()),
+                            _ => Disconnect::default(),
+                        };
+                        if !disconnect.is_retryable() {
+                            let error = format!("Disconnected: {}", disconnect);
+                            let _ = events.send(TableEvent::Closed { table_id, error });
+                            break;
+                        }
+                        let message = format!("Disconnected ({}), reconnecting...", disconnect);
+                        send_status(ConnectionStatus::Disconnected, message);
+                        match client.reconnect(view_sync.last_seq(), &disconnect).await {
+                            Ok(new_ws_stream) => {
+                                ws_stream = new_ws_stream;
+                                seat_tracker.resume();
+                                let message = "Reconnected".to_string();
+                                send_status(ConnectionStatus::Connected, message);
+                            }
+                            Err(e) => {
+                                let error = format!("{:#}", e);
+                                let _ = events.send(TableEvent::Closed { table_id, error });
+                                break;
+                            }
+                        }
+                    }
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+    });
+    (tx_command, connection)
+}
+
+/// A table the user is sitting at or watching. Each table keeps its own
+/// input so a half-typed command never ends up at another table.
+pub struct TableTab {
+    pub table_id: i64,
+    pub table_name: String,
+    pub view: GameView,
+    pub connection_status: ConnectionStatus,
+    /// Whether it's the user's turn at this table while it's in the
+    /// background
+    pub flashing: bool,
+    /// Picks the raise amount while it's the user's turn and they can
+    /// raise
+    pub bet_slider: Option<BetSlider>,
+    /// Action to take as soon as it's the user's turn
+    pub pre_action: Option<PreAction>,
+    /// Strength of the user's hand while they have hole cards
+    pub hand_strength: Option<HandStrength>,
+    /// History of recorded messages
+    pub(super) log_handle: ScrollableList,
+    /// Current value of the input box
+    pub(super) user_input: UserInput,
+    pub(super) turn_warnings: TurnWarnings,
+    commands: Option<mpsc::UnboundedSender<ClientCommand>>,
+    connection: JoinHandle<()>,
+}
+
+impl TableTab {
+    /// Whether the user is next to act at the table.
+    pub fn is_users_turn(&self, username: &Username) -> bool {
+        self.view
+            .play_positions
+            .next_action_idx
+            .and_then(|idx| self.view.players.get(idx))
+            .is_some_and(|player| &player.user.name == username)
+    }
+
+    pub(super) fn add_log(&mut self, kind: RecordKind, content: String) {
+        let record = Record::new(kind, content);
+        self.log_handle.push(record.into());
+    }
+}
+
+/// Tables open in a TUI session, one tab per table. Only the active tab
+/// receives user input.
+pub struct TableTabs {
+    username: Username,
+    /// Whether to switch to a background table when it's the user's turn
+    auto_focus: bool,
+    /// Notifies the user when it's their turn at a background table
+    notifications: Option<TurnNotifications>,
+    tabs: Vec<TableTab>,
+    active: usize,
+}
+
+impl TableTabs {
+    pub fn new(username: Username, auto_focus: bool) -> Self {
+        Self {
+            username,
+            auto_focus,
+            notifications: None,
+            tabs: Vec::new(),
+            active: 0,
+        }
+    }
+
+    /// Notify the user when it's their turn at a table that isn't the
+    /// active tab.
+    pub fn with_notifications(mut self, notifications: TurnNotifications) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Open a tab for a table's connection and focus it. Returns false
+    /// if the table already has a tab, in which case that tab is focused
+    /// and the new connection is dropped.
+    pub fn open(
+        &mut self,
+        table_id: i64,
+        table_name: String,
+        commands: mpsc::UnboundedSender<ClientCommand>,
+        connection: JoinHandle<()>,
+    ) -> bool {
+        if let Some(idx) = self.position(table_id) {
+            connection.abort();
+            self.focus(idx);
+            return false;
+        }
+        self.tabs.push(TableTab {
+            table_id,
+            table_name,
+            view: empty_view(),
+            connection_status: ConnectionStatus::Connected,
+            flashing: false,
+            bet_slider: None,
+            pre_action: None,
+            hand_strength: None,
+            log_handle: ScrollableList::new(MAX_LOG_RECORDS),
+            user_input: UserInput::new(),
+            turn_warnings: TurnWarnings::new(),
+            commands: Some(commands),
+            connection,
+        });
+        self.focus(self.tabs.len() - 1);
+        true
+    }
+
+    /// Close a table's tab, closing its connection in the background.
+    /// Returns false if the table doesn't have a tab.
+    pub fn close(&mut self, table_id: i64) -> bool {
+        let Some(idx) = self.position(table_id) else {
+            return false;
+        };
+        // Dropping the command sender closes the connection.
+        self.tabs.remove(idx);
+        if self.active >= self.tabs.len() {
+            self.active = self.tabs.len().saturating_sub(1);
+        } else if self.active > idx {
+            self.active -= 1;
+        }
+        true
+    }
+
+    /// Focus the tab at `idx`, clearing its flash. Returns false if
+    /// there's no such tab.
+    pub fn focus(&mut self, idx: usize) -> bool {
+        match self.tabs.get_mut(idx) {
+            Some(tab) => {
+                tab.flashing = false;
+                self.active = idx;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn active(&self) -> Option<&TableTab> {
+        self.tabs.get(self.active)
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut TableTab> {
+        self.tabs.get_mut(self.active)
+    }
+
+    pub fn active_idx(&self) -> usize {
+        self.active
+    }
+
+    pub fn get(&self, table_id: i64) -> Option<&TableTab> {
+        self.tabs.iter().find(|tab| tab.table_id == table_id)
+    }
+
+    pub fn tabs(&self) -> &[TableTab] {
+        &self.tabs
+    }
+
+    pub(super) fn tabs_mut(&mut self) -> &mut [TableTab] {
+        &mut self.tabs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// Whether every table's connection was lost for good.
+    pub fn all_closed(&self) -> bool {
+        self.tabs.iter().all(|tab| tab.commands.is_none())
+    }
+
+    /// Pick or unpick a pre-action at the active table.
+    pub fn toggle_pre_action(&mut self, pre_action: PreAction) {
+        if let Some(tab) = self.active_mut() {
+            tab.pre_action = match tab.pre_action {
+                Some(picked) if picked == pre_action => None,
+                _ => Some(pre_action),
+            };
+        }
+    }
+
+    fn position(&self, table_id: i64) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.table_id == table_id)
+    }
+
+    /// Send a command to the active table, returning the ID of the table
+    /// it was sent to. Taking an action clears the table's pre-action.
+    pub fn send(&mut self, command: ClientCommand) -> Result<i64> {
+        let tab = self
+            .active_mut()
+            .ok_or_else(|| anyhow!("No table is open"))?;
+        if matches!(command, ClientCommand::Action { .. }) {
+            tab.pre_action = None;
+        }
+        let commands = tab
+            .commands
+            .as_ref()
+            .ok_or_else(|| anyhow!("Lost connection to {}", tab.table_name))?;
+        commands
+            .send(command)
+            .map_err(|_| anyhow!("Lost connection to {}", tab.table_name))?;
+        Ok(tab.table_id)
+    }
+
+    /// Route an event to its table's tab. When it's the user's turn at a
+    /// background table, the tab flashes, and it's focused if auto-focus
+    /// is on and the user isn't in the middle of their turn at the
+    /// active table. Events for tables without a tab are ignored.
+    pub fn handle_event(&mut self, event: TableEvent) {
+        let Some(idx) = self.position(event.table_id()) else {
+            return;
+        };
+        let tab = &mut self.tabs[idx];
+        let is_turn_start = match event {
+            TableEvent::View { view, .. } => {
+                let actions = new_actions(&tab.view.recent_actions, &view.recent_actions);
+                if let Some(pre_action) = tab.pre_action
+                    && !pre_action.holds_after(actions)
+                {
+                    tab.pre_action = None;
+                    tab.add_log(
+                        RecordKind::Alert,
+                        format!("The bet changed, so {} was cleared", pre_action),
+                    );
+                }
+                let was_turn = tab.is_users_turn(&self.username);
+                tab.view = view;
+                let is_turn = tab.is_users_turn(&self.username);
+                if !is_turn {
+                    tab.bet_slider = None;
+                }
+                // Only re-evaluate when the hand changes since equity
+                // takes a while to estimate.
+                let inputs = HandInputs::from_view(&tab.view, &self.username);
+                if inputs.as_ref() != tab.hand_strength.as_ref().map(|strength| &strength.inputs) {
+                    tab.hand_strength = inputs.map(HandStrength::new);
+                }
+                !was_turn && is_turn
+            }
+            TableEvent::TurnSignal { turn, .. } => {
+                // Take the pre-action right away if it fits the turn.
+                if let Some(pre_action) = tab.pre_action.take()
+                    && let Some(action) = pre_action.resolve(&turn.action_choices)
+                {
+                    let repr = match action {
+                        Action::AllIn => "all-in",
+                        Action::Call => "call",
+                        Action::Check => "check",
+                        Action::Fold => "fold",
+                        Action::Raise(_) => "raise",
+                    };
+                    let repr = format!("{} ({})", repr, pre_action);
+                    let command = ClientCommand::Action {
+                        action: action.into(),
+                    };
+                    match tab.commands.as_ref().map(|commands| commands.send(command)) {
+                        Some(Ok(())) => tab.add_log(RecordKind::You, repr),
+                        _ => tab.add_log(RecordKind::Error, "Lost connection".to_string()),
+                    }
+                    return;
+                }
+
+                // The slider moves by big blinds up to the user's stack.
+                let stack = tab
+                    .view
+                    .players
+                    .iter()
+                    .find(|player| player.user.name == self.username)
+                    .map(|player| player.user.money);
+                let step = tab.view.blinds.big;
+                tab.bet_slider = stack.and_then(|stack| BetSlider::new(&turn, stack, step));
+                tab.add_log(
+                    RecordKind::Alert,
+                    format!("${} to call, pot is ${}", turn.call_amount, turn.pot_size),
+                );
+                true
+            }
+            TableEvent::Status {
+                status, message, ..
+            } => {
+                tab.connection_status = status;
+                tab.add_log(RecordKind::Alert, message);
+                false
+            }
+            TableEvent::Closed { error, .. } => {
+                tab.connection_status = ConnectionStatus::Disconnected;
+                tab.commands = None;
+                tab.add_log(RecordKind::Error, error);
+                false
+            }
+        };
+        if !is_turn_start {
+            return;
+        }
+
+        tab.turn_warnings.reset();
+        tab.add_log(RecordKind::Alert, "It's your turn!".to_string());
+        if let Some(notifications) = &mut self.notifications {
+            let focused = idx == self.active;
+            notifications.turn_signal(tab.table_id, &tab.table_name, focused, Instant::now());
+        }
+        if idx != self.active {
+            tab.flashing = true;
+            let active_is_turn = self
+                .active()
+                .is_some_and(|tab| tab.is_users_turn(&self.username));
+            if self.auto_focus && !active_is_turn {
+                self.focus(idx);
+            }
+        }
+    }
+
+    /// Close every table's connection, waiting a bit for each to close
+    /// cleanly before dropping it.
+    pub async fn shutdown(&mut self) {
+        let connections: Vec<JoinHandle<()>> = self
+            .tabs
+            .drain(..)
+            .map(|tab| {
+                drop(tab.commands);
+                tab.connection
+            })
+            .collect();
+        for mut connection in connections {
+            if tokio::time::timeout(CLOSE_TIMEOUT, &mut connection)
+                .await
+                .is_err()
+            {
+                connection.abort();
+            }
+        }
+        self.active = 0;
+    }
+}
+
+impl Drop for TableTabs {
+    fn drop(&mut self) {
+        for tab in &self.tabs {
+            tab.connection.abort();
+        }
+    }
+}
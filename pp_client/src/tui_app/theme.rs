@@ -0,0 +1,204 @@
+use private_poker::entities::{Card, Suit};
+use ratatui::{
+    style::{Color, Style, Stylize},
+    text::Span,
+};
+use std::{env, fmt, fs, io, path::PathBuf, str::FromStr};
+
+/// Colors used by the TUI. Themes only ever change styles, never the
+/// text that's rendered, so the layout is the same in every theme.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Theme {
+    /// Four-color deck on the terminal's own colors
+    #[default]
+    Default,
+    /// Bold cards with red suits in reverse video so they stand out in
+    /// monochrome terminals
+    HighContrast,
+    /// Four-color deck using the Okabe-Ito palette with red suits
+    /// underlined so suits never depend on color alone
+    ColorblindSafe,
+}
+
+impl Theme {
+    pub const ALL: [Self; 3] = [Self::Default, Self::HighContrast, Self::ColorblindSafe];
+
+    /// Theme after this one when cycling through themes.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL
+            .iter()
+            .position(|theme| *theme == self)
+            .unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn card_style(self, suit: Suit) -> Style {
+        match self {
+            Self::Default => match suit {
+                Suit::Club => Style::default().light_green(),
+                Suit::Diamond => Style::default().light_blue(),
+                Suit::Heart => Style::default().light_red(),
+                Suit::Spade => Style::default(),
+                Suit::Wild => Style::default().light_magenta(),
+            },
+            Self::HighContrast => match suit {
+                Suit::Club | Suit::Spade => Style::default().bold().white(),
+                Suit::Diamond | Suit::Heart => Style::default().bold().light_red().reversed(),
+                Suit::Wild => Style::default().bold().light_magenta(),
+            },
+            Self::ColorblindSafe => match suit {
+                Suit::Club => Style::default().fg(Color::Rgb(0, 158, 115)),
+                Suit::Diamond => Style::default().fg(Color::Rgb(86, 180, 233)).underlined(),
+                Suit::Heart => Style::default().fg(Color::Rgb(213, 94, 0)).underlined(),
+                Suit::Spade => Style::default(),
+                Suit::Wild => Style::default().fg(Color::Rgb(204, 121, 167)),
+            },
+        }
+    }
+
+    pub fn card_span(self, card: &Card) -> Span<'static> {
+        let Card(.., suit) = card;
+        Span::styled(card.to_string(), self.card_style(*suit))
+    }
+
+    /// Style for highlights, e.g., the active tab and the bet slider.
+    pub fn accent(self) -> Style {
+        match self {
+            Self::Default => Style::default().light_green(),
+            Self::HighContrast => Style::default().bold().white(),
+            Self::ColorblindSafe => Style::default().fg(Color::Rgb(86, 180, 233)),
+        }
+    }
+
+    /// Style for the connection status indicator.
+    pub fn connection_status(self, connected: bool) -> Style {
+        match (self, connected) {
+            (Self::Default, true) => Style::default().green(),
+            (Self::Default, false) => Style::default().red(),
+            (Self::HighContrast, true) => Style::default().bold().white(),
+            (Self::HighContrast, false) => Style::default().bold().white().reversed(),
+            (Self::ColorblindSafe, true) => Style::default().fg(Color::Rgb(86, 180, 233)),
+            (Self::ColorblindSafe, false) => Style::default().fg(Color::Rgb(230, 159, 0)),
+        }
+    }
+
+    /// File the chosen theme is saved to, under `$XDG_CONFIG_HOME` or
+    /// `~/.config`.
+    fn path() -> Option<PathBuf> {
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_dir.join("pp_client").join("theme"))
+    }
+
+    /// Load the saved theme, falling back to the default theme if none
+    /// was saved or it can't be read.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|repr| repr.trim().parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let repr = match self {
+            Self::Default => "default",
+            Self::HighContrast => "high-contrast",
+            Self::ColorblindSafe => "colorblind-safe",
+        };
+        write!(f, "{repr}")
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|theme| theme.to_string() == s)
+            .ok_or_else(|| format!("unknown theme: {s}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use private_poker::entities::{Card, Suit};
+    use ratatui::{
+        style::{Color, Modifier, Style},
+        text::Span,
+    };
+
+    use super::Theme;
+
+    const CARD: Card = Card(12, Suit::Heart);
+
+    #[test]
+    fn default_card() {
+        assert_eq!(
+            Theme::Default.card_span(&CARD),
+            Span::styled(" Q/♥", Style::default().fg(Color::LightRed))
+        );
+    }
+
+    #[test]
+    fn high_contrast_card() {
+        assert_eq!(
+            Theme::HighContrast.card_span(&CARD),
+            Span::styled(
+                " Q/♥",
+                Style::default()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            )
+        );
+    }
+
+    #[test]
+    fn colorblind_safe_card() {
+        assert_eq!(
+            Theme::ColorblindSafe.card_span(&CARD),
+            Span::styled(
+                " Q/♥",
+                Style::default()
+                    .fg(Color::Rgb(213, 94, 0))
+                    .add_modifier(Modifier::UNDERLINED)
+            )
+        );
+    }
+
+    #[test]
+    fn themes_dont_change_card_widths() {
+        for suit in [Suit::Club, Suit::Spade, Suit::Diamond, Suit::Heart] {
+            for value in [1, 10, 13] {
+                let card = Card(value, suit);
+                let width = Theme::Default.card_span(&card).width();
+                for theme in Theme::ALL {
+                    assert_eq!(theme.card_span(&card).width(), width);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn themes_cycle_and_round_trip() {
+        let mut theme = Theme::default();
+        for _ in Theme::ALL {
+            assert_eq!(theme.to_string().parse(), Ok(theme));
+            theme = theme.next();
+        }
+        assert_eq!(theme, Theme::default());
+    }
+}
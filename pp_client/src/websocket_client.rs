@@ -1,10 +1,34 @@
 //! WebSocket client for real-time poker game connection.
 
-use anyhow::{Context, Result};
-use futures_util::{SinkExt, StreamExt};
-use private_poker::entities::GameView;
+use anyhow::{Context, Result, anyhow, bail};
+use futures_util::{
+    Sink, SinkExt, Stream, StreamExt,
+    stream::{SplitSink, SplitStream},
+};
+use private_poker::{
+    entities::{Action, ActionChoices, GameView, GameViewUpdate, Username},
+    net::{
+        compression,
+        messages::{CloseReason, Hello, HelloAck, ServerMessage},
+    },
+};
 use serde::Serialize;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{net::TcpStream, sync::Mutex};
+use tokio_tungstenite::{
+    MaybeTlsStream, WebSocketStream, connect_async,
+    tungstenite::{self, Message, protocol::CloseFrame},
+};
+
+use crate::{
+    api_client::TokenRefresher,
+    commands::{AvailableActions, check_action, parse_action, spawn_line_editor},
+    notifications::TurnNotifications,
+};
 
 /// Client command to send to server
 #[allow(dead_code)]
@@ -16,6 +40,9 @@ pub enum ClientCommand {
     Action { action: ActionData },
     Spectate,
     StopSpectating,
+    RabbitHunt,
+    Resync,
+    Pong { nonce: u64 },
 }
 
 /// Action data matching server's ActionData enum
@@ -29,70 +56,363 @@ pub enum ActionData {
     AllIn,
 }
 
+impl From<Action> for ActionData {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::AllIn => Self::AllIn,
+            Action::Call => Self::Call,
+            Action::Check => Self::Check,
+            Action::Fold => Self::Fold,
+            Action::Raise(amount) => Self::Raise { amount },
+        }
+    }
+}
+
+/// Result of applying a game view update from the server
+#[derive(Debug)]
+pub enum SyncStatus<'a> {
+    /// The view is up to date
+    Updated(&'a GameView),
+    /// An update was missed and a full snapshot must be requested
+    ResyncNeeded,
+    /// The update was dropped while waiting for a requested snapshot
+    AwaitingSnapshot,
+}
+
+/// Game view rebuilt from the server's snapshots and deltas
+#[derive(Debug, Default)]
+pub struct GameViewSync {
+    view: Option<GameView>,
+    seq: u64,
+    resync_requested: bool,
+}
+
+impl GameViewSync {
+    /// Apply an update from the server. Deltas are only applied to the
+    /// view they were made from; otherwise an update was missed, and the
+    /// view is dropped until the next snapshot.
+    pub fn apply(&mut self, update: GameViewUpdate) -> SyncStatus<'_> {
+        match update {
+            GameViewUpdate::Snapshot { seq, view } => {
+                self.view = Some(view);
+                self.seq = seq;
+                self.resync_requested = false;
+            }
+            GameViewUpdate::Delta {
+                seq,
+                base_seq,
+                delta,
+            } => match &mut self.view {
+                Some(view) if base_seq == self.seq => {
+                    view.apply_delta(*delta);
+                    view.sequence = seq;
+                    self.seq = seq;
+                }
+                _ => {
+                    self.view = None;
+                    if self.resync_requested {
+                        return SyncStatus::AwaitingSnapshot;
+                    }
+                    self.resync_requested = true;
+                    return SyncStatus::ResyncNeeded;
+                }
+            },
+        }
+        match &self.view {
+            Some(view) => SyncStatus::Updated(view),
+            None => SyncStatus::AwaitingSnapshot,
+        }
+    }
+
+    /// Sequence number of the current view, if there is one, for the
+    /// server to catch up from after reconnecting
+    #[must_use]
+    pub fn last_seq(&self) -> Option<u64> {
+        self.view.as_ref().map(|_| self.seq)
+    }
+}
+
+/// Get the JSON text of a server message. Binary messages are compressed
+/// frames, which are decompressed first.
+pub fn message_text(message: &Message) -> Option<String> {
+    match message {
+        Message::Text(text) => Some(text.to_string()),
+        Message::Binary(frame) => compression::decompress(frame)
+            .ok()
+            .and_then(|payload| String::from_utf8(payload).ok()),
+        _ => None,
+    }
+}
+
+/// Why the connection to a table closed
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Disconnect {
+    /// Reason the server gave, if it closed the connection with one of
+    /// its close codes
+    pub reason: Option<CloseReason>,
+    /// Human-readable reason from the close frame
+    pub message: String,
+}
+
+impl Disconnect {
+    /// Read why the server closed the connection from its close frame.
+    /// A connection closed without a frame just dropped.
+    pub fn from_frame(frame: Option<&CloseFrame>) -> Self {
+        match frame {
+            Some(frame) => Self {
+                reason: CloseReason::from_code(u16::from(frame.code)),
+                message: frame.reason.to_string(),
+            },
+            None => Self::default(),
+        }
+    }
+
+    /// Whether reconnecting can succeed. Connections that dropped without
+    /// a reason from the server are worth retrying.
+    pub fn is_retryable(&self) -> bool {
+        self.reason.is_none_or(CloseReason::is_retryable)
+    }
+}
+
+impl fmt::Display for Disconnect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.reason {
+            _ if !self.message.is_empty() => write!(f, "{}", self.message),
+            Some(reason) => write!(f, "{}", reason),
+            None => write!(f, "connection lost"),
+        }
+    }
+}
+
+impl std::error::Error for Disconnect {}
+
+/// Replace the access token in a table's WebSocket URL
+fn url_with_token(ws_url: &str, token: &str) -> Result<String> {
+    let mut url = reqwest::Url::parse(ws_url).context("Invalid WebSocket URL")?;
+    let params: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != "token")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    url.query_pairs_mut()
+        .clear()
+        .append_pair("token", token)
+        .extend_pairs(params);
+    Ok(url.into())
+}
+
+/// Send a `Hello` and wait for the server's `HelloAck`, which has the
+/// protocol version to use. Fails with a [`Disconnect`] if the server
+/// closes the connection, e.g., because it doesn't support our protocol
+/// version or the access token expired.
+pub async fn handshake<S>(ws_stream: &mut S) -> Result<HelloAck>
+where
+    S: Stream<Item = Result<Message, tungstenite::Error>>
+        + Sink<Message, Error = tungstenite::Error>
+        + Unpin,
+{
+    let hello = Hello::new(env!("CARGO_PKG_VERSION"));
+    let json = serde_json::to_string(&hello)?;
+    ws_stream
+        .send(Message::Text(json.into()))
+        .await
+        .context("Failed to send handshake")?;
+
+    while let Some(msg) = ws_stream.next().await {
+        match msg.context("Failed to read handshake reply")? {
+            Message::Text(text) => {
+                let ack: HelloAck =
+                    serde_json::from_str(&text).context("Invalid handshake reply")?;
+                ack.verify(&hello)?;
+                return Ok(ack);
+            }
+            Message::Close(Some(frame)) => {
+                let disconnect = Disconnect::from_frame(Some(&frame));
+                return Err(anyhow::Error::new(disconnect).context("Server rejected connection"));
+            }
+            Message::Close(None) => break,
+            _ => {}
+        }
+    }
+    bail!("Server closed connection during handshake")
+}
+
+/// Notices when the player lost their seat while disconnected, e.g.,
+/// because the hand ended and they were removed from the table.
+#[derive(Debug, Default)]
+pub struct SeatTracker {
+    was_seated: bool,
+    resumed: bool,
+}
+
+impl SeatTracker {
+    /// Compare the next view against the last view before the connection
+    /// dropped.
+    pub fn resume(&mut self) {
+        self.resumed = true;
+    }
+
+    /// Update with the latest view, returning whether the player was
+    /// seated before reconnecting but no longer is.
+    pub fn lost_seat(&mut self, view: &GameView, username: &Username) -> bool {
+        let seated = view.players.iter().any(|p| &p.user.name == username);
+        let lost_seat = self.resumed && self.was_seated && !seated;
+        self.resumed = false;
+        self.was_seated = seated;
+        lost_seat
+    }
+}
+
+/// Message shown when the player lost their seat while disconnected
+pub const LOST_SEAT_MESSAGE: &str =
+    "You were removed from the table while disconnected; you're now watching";
+
+/// WebSocket connection to a table
+pub type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Number of attempts made to reconnect after the connection drops
+const RECONNECT_ATTEMPTS: u32 = 5;
+
+/// Delay before the first reconnect attempt, doubled after each failure
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
 /// WebSocket game client
 pub struct WebSocketClient {
     ws_url: String,
+    username: Username,
+    notifications: Option<Arc<Mutex<TurnNotifications>>>,
+    token_refresher: Option<Arc<TokenRefresher>>,
 }
 
 impl WebSocketClient {
-    /// Create a new WebSocket client
-    pub fn new(ws_url: String) -> Self {
-        Self { ws_url }
+    /// Create a new WebSocket client. `ws_url` includes the access token
+    /// used to authenticate, and is reused when reconnecting.
+    pub fn new(ws_url: String, username: &str) -> Self {
+        Self {
+            ws_url,
+            username: Username::new(username),
+            notifications: None,
+            token_refresher: None,
+        }
     }
 
-    /// Connect to the WebSocket and run the game session
-    pub async fn connect_and_play(self) -> Result<()> {
-        println!("Connecting to {}...", self.ws_url);
-
-        // Connect to WebSocket
-        let (ws_stream, _) = connect_async(&self.ws_url)
-            .await
-            .context("Failed to connect to WebSocket")?;
+    /// Refresh the access token when the server says it expired, instead
+    /// of giving up on the table.
+    pub fn with_token_refresher(mut self, token_refresher: Arc<TokenRefresher>) -> Self {
+        self.token_refresher = Some(token_refresher);
+        self
+    }
 
-        println!("Connected! Receiving table updates...\n");
+    /// Notify the user when it's their turn, e.g., in case they tabbed
+    /// away from the terminal.
+    pub fn with_notifications(mut self, notifications: TurnNotifications) -> Self {
+        self.notifications = Some(Arc::new(Mutex::new(notifications)));
+        self
+    }
 
-        let (mut write, mut read) = ws_stream.split();
+    /// Connect to the table and complete the handshake
+    pub async fn connect(&self) -> Result<(WsStream, HelloAck)> {
+        Self::open(&self.ws_url).await
+    }
 
-        // Spawn task to handle incoming messages
-        let read_handle = tokio::spawn(async move {
-            while let Some(msg) = read.next().await {
-                match msg {
-                    Ok(Message::Text(text)) => {
-                        // Parse and display game view
-                        match serde_json::from_str::<GameView>(&text) {
-                            Ok(view) => {
-                                display_game_view(&view);
-                            }
-                            Err(e) => {
-                                // Might be a response message, try parsing that
-                                eprintln!("Failed to parse game view: {}", e);
-                            }
+    /// Reconnect to the same table after the connection closed for
+    /// `disconnect`, re-authenticating with the stored access token, or a
+    /// refreshed one if it expired. The server catches up the game view
+    /// from `last_seq` with deltas, or sends a fresh snapshot, followed by
+    /// the game events missed while disconnected. Retries with exponential
+    /// backoff, but fails right away if the server closed the connection
+    /// for good, e.g., because the user was kicked.
+    pub async fn reconnect(
+        &mut self,
+        last_seq: Option<u64>,
+        disconnect: &Disconnect,
+    ) -> Result<WsStream> {
+        if !disconnect.is_retryable() {
+            return Err(anyhow::Error::new(disconnect.clone()).context("Can't reconnect"));
+        }
+        if disconnect.reason == Some(CloseReason::AuthExpired) {
+            self.refresh_token().await?;
+        }
+        let mut delay = RECONNECT_DELAY;
+        let mut last_error = None;
+        for _ in 0..RECONNECT_ATTEMPTS {
+            tokio::time::sleep(delay).await;
+            let url = match last_seq {
+                Some(last_seq) => format!("{}&resume=true&last_seq={}", self.ws_url, last_seq),
+                None => format!("{}&resume=true", self.ws_url),
+            };
+            match Self::open(&url).await {
+                Ok((ws_stream, _)) => return Ok(ws_stream),
+                Err(e) => {
+                    match e.downcast_ref::<Disconnect>().and_then(|d| d.reason) {
+                        Some(CloseReason::AuthExpired) => self.refresh_token().await?,
+                        Some(reason) if !reason.is_retryable() => {
+                            return Err(e.context("Failed to reconnect"));
                         }
+                        _ => {}
                     }
-                    Ok(Message::Close(_)) => {
-                        println!("Server closed connection");
-                        break;
-                    }
-                    Err(e) => {
-                        eprintln!("WebSocket error: {}", e);
-                        break;
-                    }
-                    _ => {}
+                    last_error = Some(e);
                 }
             }
-        });
+            delay *= 2;
+        }
+        let error = last_error.unwrap_or_else(|| anyhow!("no reconnect attempts made"));
+        Err(error.context("Failed to reconnect"))
+    }
+
+    /// Swap the expired access token in the URL for a fresh one
+    async fn refresh_token(&mut self) -> Result<()> {
+        let Some(token_refresher) = &self.token_refresher else {
+            bail!("Session expired, please log in again");
+        };
+        let token = token_refresher.refresh().await?;
+        self.ws_url = url_with_token(&self.ws_url, &token)?;
+        Ok(())
+    }
+
+    async fn open(url: &str) -> Result<(WsStream, HelloAck)> {
+        let (mut ws_stream, _) = connect_async(url)
+            .await
+            .context("Failed to connect to WebSocket")?;
+        let ack = handshake(&mut ws_stream).await?;
+        Ok((ws_stream, ack))
+    }
+
+    /// Connect to the WebSocket and run the game session, transparently
+    /// reconnecting if the connection drops
+    pub async fn connect_and_play(mut self) -> Result<()> {
+        println!("Connecting to {}...", self.ws_url);
+
+        let (ws_stream, ack) = self.connect().await?;
+        println!(
+            "Connected to server {} (protocol {:?})! Receiving table updates...\n",
+            ack.server_version, ack.accepted_version
+        );
+
+        let (write, read) = ws_stream.split();
+        let write = Arc::new(Mutex::new(write));
+        let available = AvailableActions::default();
+
+        // Spawn task to handle incoming messages
+        let mut read_handle = tokio::spawn(read_updates(
+            read,
+            write.clone(),
+            self.username.clone(),
+            SeatTracker::default(),
+            GameViewSync::default(),
+            available.clone(),
+            self.notifications.clone(),
+        ));
 
         // Handle user input
-        let mut stdin = tokio::io::BufReader::new(tokio::io::stdin());
-        let mut line = String::new();
+        let mut lines = spawn_line_editor(available.clone());
 
         loop {
-            use tokio::io::AsyncBufReadExt;
-
-            line.clear();
-            match stdin.read_line(&mut line).await {
-                Ok(0) => break, // EOF
-                Ok(_) => {
+            tokio::select! {
+                line = lines.recv() => {
+                    let Some(line) = line else {
+                        break; // EOF
+                    };
                     let input = line.trim();
                     if input.is_empty() {
                         continue;
@@ -104,32 +424,147 @@ impl WebSocketClient {
                         break;
                     }
 
-                    match parse_and_send_command(input, &mut write).await {
-                        Ok(_) => {
-                            // Command sent successfully
-                        }
-                        Err(e) => {
-                            eprintln!("Error: {}", e);
-                        }
+                    let mut write = write.lock().await;
+                    let available = available.get();
+                    let sent = parse_and_send_command(input, available.as_ref(), &mut *write);
+                    if let Err(e) = sent.await {
+                        eprintln!("Error: {}", e);
                     }
                 }
-                Err(e) => {
-                    eprintln!("Error reading input: {}", e);
-                    break;
+                dropped = &mut read_handle => {
+                    // The connection dropped, so resume at the same table
+                    // unless the server closed it for good
+                    let (mut seat_tracker, view_sync, disconnect) = dropped.unwrap_or_default();
+                    if !disconnect.is_retryable() {
+                        println!("Disconnected: {}", disconnect);
+                        break;
+                    }
+                    println!("Disconnected ({}), reconnecting...", disconnect);
+                    let ws_stream = self.reconnect(view_sync.last_seq(), &disconnect).await?;
+                    println!("Reconnected! Catching up on the table...\n");
+
+                    let (new_write, read) = ws_stream.split();
+                    *write.lock().await = new_write;
+                    seat_tracker.resume();
+                    read_handle = tokio::spawn(read_updates(
+                        read,
+                        write.clone(),
+                        self.username.clone(),
+                        seat_tracker,
+                        view_sync,
+                        available.clone(),
+                        self.notifications.clone(),
+                    ));
                 }
             }
         }
 
         // Clean up
-        let _ = write.close().await;
+        let _ = write.lock().await.close().await;
         read_handle.abort();
 
         Ok(())
     }
 }
 
-/// Parse user input and send command to server
-async fn parse_and_send_command<W>(input: &str, write: &mut W) -> Result<()>
+/// Display game views and events from the server until the connection
+/// drops, requesting a snapshot whenever an update is missed. The user's
+/// available actions are kept up to date for command completion, and
+/// they're notified when their turn starts. Returns why the connection
+/// closed along with the state needed to resume.
+async fn read_updates(
+    mut read: SplitStream<WsStream>,
+    write: Arc<Mutex<SplitSink<WsStream, Message>>>,
+    username: Username,
+    mut seat_tracker: SeatTracker,
+    mut view_sync: GameViewSync,
+    available: AvailableActions,
+    notifications: Option<Arc<Mutex<TurnNotifications>>>,
+) -> (SeatTracker, GameViewSync, Disconnect) {
+    let mut was_turn = false;
+    let mut disconnect = Disconnect::default();
+    while let Some(msg) = read.next().await {
+        match msg {
+            Ok(message @ (Message::Text(_) | Message::Binary(_))) => {
+                let Some(text) = message_text(&message) else {
+                    continue;
+                };
+                if let Ok(update) = serde_json::from_str::<GameViewUpdate>(&text) {
+                    match view_sync.apply(update) {
+                        SyncStatus::Updated(view) => {
+                            display_game_view(view);
+                            let is_turn = is_users_turn(view, &username);
+                            if !is_turn {
+                                available.set(None);
+                            } else if !was_turn {
+                                notify_turn(notifications.as_deref()).await;
+                            }
+                            was_turn = is_turn;
+                            if seat_tracker.lost_seat(view, &username) {
+                                println!("{}", LOST_SEAT_MESSAGE);
+                            }
+                        }
+                        SyncStatus::ResyncNeeded => {
+                            if let Ok(json) = serde_json::to_string(&ClientCommand::Resync) {
+                                let _ = write.lock().await.send(Message::Text(json.into())).await;
+                            }
+                        }
+                        SyncStatus::AwaitingSnapshot => {}
+                    }
+                } else if let Ok(message) = serde_json::from_str::<ServerMessage>(&text) {
+                    match message {
+                        // Answer heartbeats so the server keeps us connected
+                        ServerMessage::Ping { nonce } => {
+                            let pong = ClientCommand::Pong { nonce };
+                            if let Ok(json) = serde_json::to_string(&pong) {
+                                let _ = write.lock().await.send(Message::Text(json.into())).await;
+                            }
+                        }
+                        ServerMessage::MissedEvents {
+                            events, complete, ..
+                        } => {
+                            println!("While you were disconnected:");
+                            if !complete {
+                                println!("  (older events weren't kept)");
+                            }
+                            for event in events {
+                                println!("  {}", event);
+                            }
+                        }
+                        message => {
+                            if let ServerMessage::TurnSignal(turn) = &message {
+                                available.set(Some(turn.action_choices.clone()));
+                                notify_turn(notifications.as_deref()).await;
+                            }
+                            println!("{}", message);
+                        }
+                    }
+                } else {
+                    // Command responses
+                    println!("{}", text);
+                }
+            }
+            Ok(Message::Close(frame)) => {
+                disconnect = Disconnect::from_frame(frame.as_ref());
+                break;
+            }
+            Err(e) => {
+                eprintln!("WebSocket error: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+    (seat_tracker, view_sync, disconnect)
+}
+
+/// Parse user input and send command to server. Game actions are checked
+/// against the `available` actions when it's the user's turn.
+async fn parse_and_send_command<W>(
+    input: &str,
+    available: Option<&ActionChoices>,
+    write: &mut W,
+) -> Result<()>
 where
     W: SinkExt<Message> + Unpin,
     W::Error: std::error::Error + Send + Sync + 'static,
@@ -139,59 +574,48 @@ where
         return Ok(());
     }
 
-    let command = match parts[0].to_lowercase().as_str() {
+    let command = if let Some(action) = parse_action(&parts)? {
         // Game actions
-        "fold" => ClientCommand::Action {
-            action: ActionData::Fold,
-        },
-        "check" => ClientCommand::Action {
-            action: ActionData::Check,
-        },
-        "call" => ClientCommand::Action {
-            action: ActionData::Call,
-        },
-        "allin" | "all-in" => ClientCommand::Action {
-            action: ActionData::AllIn,
-        },
-        "raise" => {
-            let amount = if parts.len() > 1 {
-                parts[1].parse::<u32>().ok()
-            } else {
-                None
-            };
-            ClientCommand::Action {
-                action: ActionData::Raise { amount },
-            }
+        if let Some(available) = available {
+            check_action(&action, available)?;
         }
-
-        // Table management
-        "join" => {
-            let buy_in = if parts.len() > 1 {
-                parts[1].parse::<i64>().unwrap_or(1000)
-            } else {
-                1000
-            };
-            ClientCommand::Join { buy_in }
-        }
-        "leave" => ClientCommand::Leave,
-        "spectate" | "watch" => ClientCommand::Spectate,
-        "stop" | "unwatch" => ClientCommand::StopSpectating,
-
-        // Help
-        "help" | "?" => {
-            println!("\nAvailable commands:");
-            println!("  Game actions: fold, check, call, raise <amount>, allin");
-            println!("  Table: join <buy_in>, leave, spectate, stop");
-            println!("  Other: help, quit");
-            return Ok(());
+        ClientCommand::Action {
+            action: action.into(),
         }
+    } else {
+        match parts[0].to_lowercase().as_str() {
+            // Table management
+            "join" => {
+                let buy_in = if parts.len() > 1 {
+                    parts[1].parse::<i64>().unwrap_or(1000)
+                } else {
+                    1000
+                };
+                ClientCommand::Join { buy_in }
+            }
+            "leave" => ClientCommand::Leave,
+            "spectate" | "watch" => ClientCommand::Spectate,
+            "stop" | "unwatch" => ClientCommand::StopSpectating,
+            "rabbit" => ClientCommand::RabbitHunt,
+
+            // Help
+            "help" | "?" => {
+                println!("\nAvailable commands:");
+                println!("  Game actions: fold, check, call, raise <amount>, allin");
+                println!("  Shortcuts: f, k, c, r <amount> (or bet <amount>), a");
+                println!("  Table: join <buy_in>, leave, spectate, stop, rabbit");
+                println!("  Other: help, quit");
+                println!("  Up/Down recall earlier commands and Tab completes command names");
+                return Ok(());
+            }
 
-        _ => {
-            eprintln!(
-                "Unknown command: '{}'. Type 'help' for available commands.",
-                parts[0]
-            );
-            return Ok(());
+            _ => {
+                eprintln!(
+                    "Unknown command: '{}'. Type 'help' for available commands.",
+                    parts[0]
+                );
+                return Ok(());
+            }
         }
     };
 
@@ -205,6 +629,24 @@ where
     Ok(())
 }
 
+/// Notify the user that it's their turn. The CLI only plays at one
+/// table, so it's never treated as focused since the user may have
+/// tabbed away from the terminal.
+async fn notify_turn(notifications: Option<&Mutex<TurnNotifications>>) {
+    if let Some(notifications) = notifications {
+        let mut notifications = notifications.lock().await;
+        notifications.turn_signal(0, "the table", false, Instant::now());
+    }
+}
+
+/// Whether the user is next to act in the view.
+fn is_users_turn(view: &GameView, username: &Username) -> bool {
+    view.play_positions
+        .next_action_idx
+        .and_then(|idx| view.players.get(idx))
+        .is_some_and(|player| &player.user.name == username)
+}
+
 /// Display game view in a readable format
 fn display_game_view(view: &GameView) {
     use std::fmt::Write;
@@ -228,6 +670,15 @@ fn display_game_view(view: &GameView) {
         println!("Board: {}", board_str);
     }
 
+    // Second board (only dealt when running it twice)
+    if !view.second_board.is_empty() {
+        let mut board_str = String::new();
+        for card in view.second_board.iter() {
+            let _ = write!(&mut board_str, "{} ", format_card(card));
+        }
+        println!("Board 2: {}", board_str);
+    }
+
     // Pot
     println!("Pot: ${}", view.pot.size);
 
@@ -239,7 +690,7 @@ fn display_game_view(view: &GameView) {
         for (i, player) in view.players.iter().enumerate() {
             // Determine position markers
             let mut position_markers = Vec::new();
-            if i == view.play_positions.small_blind_idx {
+            if i == view.play_positions.small_blind_idx && !view.play_positions.dead_small_blind {
                 position_markers.push("SB");
             }
             if i == view.play_positions.big_blind_idx {
@@ -2,9 +2,36 @@
 //!
 //! Tests network error handling, connection retries, and protocol mismatch scenarios.
 
-use pp_client::api_client::ApiClient;
+use futures_util::{SinkExt, StreamExt};
+use pp_client::{
+    api_client::ApiClient,
+    tui_app::{TableTabs, parse_command, spawn_table_connection},
+    websocket_client::{
+        Disconnect, GameViewSync, SeatTracker, SyncStatus, WebSocketClient, WsStream,
+    },
+};
+use private_poker::{
+    entities::{
+        Blinds, GameVariant, GameView, GameViewUpdate, PlayPositions, PlayerState, PlayerView,
+        PotView, User, Username,
+    },
+    game::GameEvent,
+    messages::{CloseReason, Hello, ServerMessage},
+};
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
 use tokio::time::timeout;
+use tokio_tungstenite::{
+    WebSocketStream,
+    tungstenite::{
+        Message,
+        handshake::server::{ErrorResponse, Request, Response},
+        protocol::{CloseFrame, frame::coding::CloseCode},
+    },
+};
 
 /// Generate unique username for tests
 #[allow(dead_code)]
@@ -332,3 +359,376 @@ async fn test_client_state_after_failed_request() {
     let result3 = client.list_tables().await;
     assert!(result3.is_err());
 }
+
+// ============================================================================
+// Reconnect Tests
+// ============================================================================
+
+fn init_game_view(players: &[&str]) -> GameView {
+    GameView {
+        blinds: Arc::new(Blinds {
+            small: 5,
+            big: 10,
+            ante: 0,
+        }),
+        spectators: Arc::new(HashSet::new()),
+        waitlist: Arc::new(VecDeque::new()),
+        open_seats: Arc::new(VecDeque::new()),
+        players: players
+            .iter()
+            .map(|name| PlayerView {
+                user: User {
+                    name: Username::new(name),
+                    money: 600,
+                },
+                state: PlayerState::Wait,
+                cards: Arc::new(vec![]),
+                sitting_out: false,
+                seat_idx: 0,
+                committed_this_street: 0,
+                total_committed: 0,
+            })
+            .collect(),
+        board: Arc::new(vec![]),
+        second_board: Arc::new(vec![]),
+        pot: Arc::new(PotView { size: 0 }),
+        play_positions: Arc::new(PlayPositions::default()),
+        recent_actions: Arc::new(vec![]),
+        sequence: 0,
+        deck_style: None,
+        variant: GameVariant::default(),
+    }
+}
+
+/// Accept a table connection on the mock server and complete the
+/// handshake, returning the connection and the requested URI.
+async fn accept_table_connection(listener: &TcpListener) -> (WebSocketStream<TcpStream>, String) {
+    let (stream, _) = listener.accept().await.unwrap();
+    let mut uri = String::new();
+    #[allow(clippy::result_large_err)]
+    let callback = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+        uri = request.uri().to_string();
+        Ok(response)
+    };
+    let mut ws = tokio_tungstenite::accept_hdr_async(stream, callback)
+        .await
+        .unwrap();
+
+    let Some(Ok(Message::Text(text))) = ws.next().await else {
+        panic!("expected a hello");
+    };
+    let hello: Hello = serde_json::from_str(&text).unwrap();
+    let ack = hello.negotiate("mock").unwrap();
+    let json = serde_json::to_string(&ack).unwrap();
+    ws.send(Message::Text(json.into())).await.unwrap();
+    (ws, uri)
+}
+
+async fn send_json<T: serde::Serialize>(ws: &mut WebSocketStream<TcpStream>, value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    ws.send(Message::Text(json.into())).await.unwrap();
+}
+
+async fn next_text(ws_stream: &mut WsStream) -> String {
+    match ws_stream.next().await {
+        Some(Ok(Message::Text(text))) => text.to_string(),
+        other => panic!("expected a text message, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_reconnect_resyncs_table() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let username = Username::new("alice");
+    let seated_view = init_game_view(&["alice", "bob"]);
+    let booted_view = init_game_view(&["bob"]);
+    let missed_events = vec![GameEvent::Removed(username.clone())];
+
+    // Mock table that drops the first connection after sending a
+    // snapshot, then resumes the session on the next connection.
+    let server = {
+        let seated_view = seated_view.clone();
+        let booted_view = booted_view.clone();
+        let missed_events = missed_events.clone();
+        tokio::spawn(async move {
+            let (mut ws, uri) = accept_table_connection(&listener).await;
+            assert_eq!(uri, "/ws/1?token=test");
+            let snapshot = GameViewUpdate::Snapshot {
+                seq: 1,
+                view: seated_view,
+            };
+            send_json(&mut ws, &snapshot).await;
+            drop(ws);
+
+            let (mut ws, uri) = accept_table_connection(&listener).await;
+            assert_eq!(uri, "/ws/1?token=test&resume=true");
+            let snapshot = GameViewUpdate::Snapshot {
+                seq: 1,
+                view: booted_view,
+            };
+            send_json(&mut ws, &snapshot).await;
+            let missed = ServerMessage::MissedEvents {
+                table_id: 1,
+                events: missed_events,
+                complete: true,
+            };
+            send_json(&mut ws, &missed).await;
+            ws
+        })
+    };
+
+    let mut client = WebSocketClient::new(format!("ws://{addr}/ws/1?token=test"), "alice");
+    let (mut ws_stream, _) = client.connect().await.unwrap();
+    let mut view_sync = GameViewSync::default();
+    let mut seat_tracker = SeatTracker::default();
+    let update = serde_json::from_str(&next_text(&mut ws_stream).await).unwrap();
+    match view_sync.apply(update) {
+        SyncStatus::Updated(view) => {
+            assert_eq!(view, &seated_view);
+            assert!(!seat_tracker.lost_seat(view, &username));
+        }
+        other => panic!("expected a view, got {other:?}"),
+    }
+
+    // The connection drops.
+    assert!(!matches!(
+        ws_stream.next().await,
+        Some(Ok(Message::Text(_)))
+    ));
+
+    // Reconnecting resumes the session with a fresh view and replays the
+    // missed events, and the player notices they lost their seat.
+    let mut ws_stream = client
+        .reconnect(None, &Disconnect::default())
+        .await
+        .unwrap();
+    seat_tracker.resume();
+    let mut view_sync = GameViewSync::default();
+    let update = serde_json::from_str(&next_text(&mut ws_stream).await).unwrap();
+    match view_sync.apply(update) {
+        SyncStatus::Updated(view) => {
+            assert_eq!(view, &booted_view);
+            assert!(seat_tracker.lost_seat(view, &username));
+        }
+        other => panic!("expected a view, got {other:?}"),
+    }
+    match serde_json::from_str(&next_text(&mut ws_stream).await).unwrap() {
+        ServerMessage::MissedEvents {
+            events, complete, ..
+        } => {
+            assert_eq!(events, missed_events);
+            assert!(complete);
+        }
+        other => panic!("expected missed events, got {other}"),
+    }
+
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_kicked_connection_is_not_retried() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (mut ws, _) = accept_table_connection(&listener).await;
+        let frame = CloseFrame {
+            code: CloseCode::from(CloseReason::Kicked.code()),
+            reason: "removed from the table".into(),
+        };
+        ws.close(Some(frame)).await.unwrap();
+    });
+
+    let mut client = WebSocketClient::new(format!("ws://{addr}/ws/1?token=test"), "alice");
+    let (mut ws_stream, _) = client.connect().await.unwrap();
+    let frame = match ws_stream.next().await {
+        Some(Ok(Message::Close(frame))) => frame,
+        other => panic!("expected a close frame, got {other:?}"),
+    };
+    let disconnect = Disconnect::from_frame(frame.as_ref());
+    assert_eq!(disconnect.reason, Some(CloseReason::Kicked));
+    assert_eq!(disconnect.to_string(), "removed from the table");
+    assert!(!disconnect.is_retryable());
+
+    // The server isn't asked again
+    let error = client.reconnect(None, &disconnect).await.unwrap_err();
+    assert!(format!("{error:#}").contains("removed from the table"));
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_expired_token_rejection_is_retryable() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let frame = CloseFrame {
+            code: CloseCode::from(CloseReason::AuthExpired.code()),
+            reason: "session expired".into(),
+        };
+        ws.close(Some(frame)).await.unwrap();
+    });
+
+    let client = WebSocketClient::new(format!("ws://{addr}/ws/1?token=test"), "alice");
+    let error = client.connect().await.unwrap_err();
+    let disconnect = error.downcast_ref::<Disconnect>().unwrap();
+    assert_eq!(disconnect.reason, Some(CloseReason::AuthExpired));
+    assert!(disconnect.is_retryable());
+    server.await.unwrap();
+}
+
+#[test]
+fn test_sequence_gap_requests_snapshot() {
+    let mut view_sync = GameViewSync::default();
+    assert_eq!(view_sync.last_seq(), None);
+
+    let seated_view = init_game_view(&["alice", "bob"]);
+    let snapshot = GameViewUpdate::Snapshot {
+        seq: 5,
+        view: seated_view.clone(),
+    };
+    assert!(matches!(view_sync.apply(snapshot), SyncStatus::Updated(_)));
+    assert_eq!(view_sync.last_seq(), Some(5));
+
+    // A delta from the current view applies, and the sequence number
+    // can jump when the table changed more than once in between.
+    let booted_view = init_game_view(&["bob"]);
+    let delta = GameViewUpdate::Delta {
+        seq: 8,
+        base_seq: 5,
+        delta: Box::new(booted_view.diff(&seated_view)),
+    };
+    match view_sync.apply(delta) {
+        SyncStatus::Updated(view) => {
+            assert_eq!(view.players, booted_view.players);
+            assert_eq!(view.sequence, 8);
+        }
+        other => panic!("expected a view, got {other:?}"),
+    }
+
+    // A delta from a view we never got means one was missed, so a
+    // snapshot is requested once and later deltas wait for it.
+    let missed_delta = || GameViewUpdate::Delta {
+        seq: 11,
+        base_seq: 10,
+        delta: Box::new(seated_view.diff(&booted_view)),
+    };
+    assert!(matches!(
+        view_sync.apply(missed_delta()),
+        SyncStatus::ResyncNeeded
+    ));
+    assert_eq!(view_sync.last_seq(), None);
+    assert!(matches!(
+        view_sync.apply(missed_delta()),
+        SyncStatus::AwaitingSnapshot
+    ));
+
+    let snapshot = GameViewUpdate::Snapshot {
+        seq: 11,
+        view: seated_view,
+    };
+    assert!(matches!(view_sync.apply(snapshot), SyncStatus::Updated(_)));
+    assert_eq!(view_sync.last_seq(), Some(11));
+}
+
+// ============================================================================
+// Multi-Table Tests
+// ============================================================================
+
+/// Connect to a mock table, returning the client's end of the connection
+/// and the mock table's end.
+async fn connect_mock_table(
+    listener: &TcpListener,
+    table_id: i64,
+) -> (WebSocketClient, WsStream, WebSocketStream<TcpStream>) {
+    let addr = listener.local_addr().unwrap();
+    let client = WebSocketClient::new(format!("ws://{addr}/ws/{table_id}?token=test"), "alice");
+    let (connected, (table_ws, _)) =
+        tokio::join!(client.connect(), accept_table_connection(listener));
+    let (ws_stream, _) = connected.unwrap();
+    (client, ws_stream, table_ws)
+}
+
+async fn next_command(table_ws: &mut WebSocketStream<TcpStream>) -> serde_json::Value {
+    match timeout(Duration::from_secs(5), table_ws.next()).await {
+        Ok(Some(Ok(Message::Text(text)))) => serde_json::from_str(&text).unwrap(),
+        other => panic!("expected a command, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_tabs_route_input_to_active_table() {
+    let username = Username::new("alice");
+    let (tx_events, mut rx_events) = mpsc::unbounded_channel();
+    let mut tabs = TableTabs::new(username.clone(), false);
+    let mut table_wss = Vec::new();
+    for (table_id, table_name) in [(1, "one"), (2, "two")] {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (client, ws_stream, table_ws) = connect_mock_table(&listener, table_id).await;
+        let (commands, connection) = spawn_table_connection(
+            table_id,
+            client,
+            ws_stream,
+            username.clone(),
+            tx_events.clone(),
+        );
+        assert!(tabs.open(table_id, table_name.to_string(), commands, connection));
+        table_wss.push(table_ws);
+    }
+    let [table1_ws, table2_ws] = table_wss.as_mut_slice() else {
+        unreachable!();
+    };
+
+    // The last opened table is focused, so input goes to it.
+    assert_eq!(tabs.active().unwrap().table_id, 2);
+    let fold = parse_command("fold").unwrap();
+    assert_eq!(tabs.send(fold).unwrap(), 2);
+    let fold_json = serde_json::json!({"type": "action", "action": {"type": "fold"}});
+    assert_eq!(next_command(table2_ws).await, fold_json);
+
+    // Switching tabs switches where input goes.
+    assert!(tabs.focus(0));
+    let check = parse_command("check").unwrap();
+    assert_eq!(tabs.send(check).unwrap(), 1);
+    let check_json = serde_json::json!({"type": "action", "action": {"type": "check"}});
+    assert_eq!(next_command(table1_ws).await, check_json);
+
+    // It becomes the user's turn at the background table, which flashes
+    // its tab without stealing input from the active table.
+    assert!(tabs.focus(1));
+    let mut view = init_game_view(&["alice", "bob"]);
+    view.play_positions = Arc::new(PlayPositions {
+        next_action_idx: Some(0),
+        ..PlayPositions::default()
+    });
+    let snapshot = GameViewUpdate::Snapshot { seq: 1, view };
+    send_json(table1_ws, &snapshot).await;
+    let event = timeout(Duration::from_secs(5), rx_events.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.table_id(), 1);
+    tabs.handle_event(event);
+    assert!(tabs.get(1).unwrap().flashing);
+    assert!(tabs.get(1).unwrap().is_users_turn(&username));
+    assert_eq!(tabs.active().unwrap().table_id, 2);
+    let call = parse_command("call").unwrap();
+    assert_eq!(tabs.send(call).unwrap(), 2);
+    let call_json = serde_json::json!({"type": "action", "action": {"type": "call"}});
+    assert_eq!(next_command(table2_ws).await, call_json);
+
+    // Focusing the flashing tab stops the flash.
+    assert!(tabs.focus(0));
+    assert!(!tabs.get(1).unwrap().flashing);
+
+    // Quitting closes every table's connection.
+    tabs.shutdown().await;
+    assert!(tabs.is_empty());
+    for table_ws in [table1_ws, table2_ws] {
+        match timeout(Duration::from_secs(5), table_ws.next()).await {
+            Ok(Some(Ok(Message::Close(_)))) => {}
+            other => panic!("expected the connection to close, got {other:?}"),
+        }
+    }
+}
@@ -13,7 +13,8 @@
 use anyhow::Result;
 use futures_util::{SinkExt, StreamExt};
 use pp_client::api_client::ApiClient;
-use private_poker::entities::GameView;
+use pp_client::websocket_client::{GameViewSync, SyncStatus, handshake};
+use private_poker::entities::{GameView, GameViewUpdate};
 use serde::Serialize;
 use std::sync::Arc;
 use std::time::Duration;
@@ -187,7 +188,8 @@ async fn connect_and_join(
     let username = client.username.clone();
 
     // Connect to WebSocket
-    let (ws_stream, _) = connect_async(&client.ws_url).await?;
+    let (mut ws_stream, _) = connect_async(&client.ws_url).await?;
+    handshake(&mut ws_stream).await?;
     println!("✓ {} connected to WebSocket", username);
 
     let (mut write, mut read) = ws_stream.split();
@@ -200,12 +202,15 @@ async fn connect_and_join(
 
     // Spawn task to receive game updates
     tokio::spawn(async move {
+        let mut view_sync = GameViewSync::default();
         while let Some(msg) = read.next().await {
             if let Ok(Message::Text(text)) = msg {
-                if let Ok(view) = serde_json::from_str::<GameView>(&text) {
+                if let Ok(update) = serde_json::from_str::<GameViewUpdate>(&text)
+                    && let SyncStatus::Updated(view) = view_sync.apply(update)
+                {
                     // Store game view for verification
                     let mut views = game_views.lock().await;
-                    views.push(view);
+                    views.push(view.clone());
                 }
             } else if matches!(msg, Ok(Message::Close(_))) {
                 break;
@@ -359,7 +364,8 @@ async fn test_game_progresses_with_actions() -> Result<()> {
 
     // Connect all clients and manually control actions
     for client in &mut clients {
-        let (ws_stream, _) = connect_async(&client.ws_url).await?;
+        let (mut ws_stream, _) = connect_async(&client.ws_url).await?;
+        handshake(&mut ws_stream).await?;
         println!("✓ {} connected", client.username);
 
         let (mut write, _read) = ws_stream.split();
@@ -0,0 +1,465 @@
+//! Admin API handlers.
+//!
+//! This module provides HTTP REST endpoints for moderating the server:
+//! - Force-closing, pausing, and resuming tables
+//! - Kicking users from tables and muting them in table chat
+//! - Freezing and unfreezing wallets
+//! - Viewing anti-collusion flags and the audit log
+//!
+//! Every endpoint requires an admin's JWT bearer token. Non-admins get a
+//! `403 Forbidden`, and every admin action is written to the audit log.
+//!
+//! # Examples
+//!
+//! Pause a table:
+//! ```bash
+//! curl -X POST http://localhost:3000/api/v1/admin/tables/1/pause \
+//!   -H "Authorization: Bearer TOKEN"
+//! ```
+//!
+//! Freeze a wallet:
+//! ```bash
+//! curl -X POST http://localhost:3000/api/v1/admin/users/42/freeze \
+//!   -H "Authorization: Bearer TOKEN" \
+//!   -H "Content-Type: application/json" \
+//!   -d '{"reason":"Chip dumping flagged at table 7"}'
+//! ```
+//!
+//! View a user's anti-collusion flags:
+//! ```bash
+//! curl "http://localhost:3000/api/v1/admin/flags?user_id=42" \
+//!   -H "Authorization: Bearer TOKEN"
+//! ```
+//!
+//! View the latest kicks:
+//! ```bash
+//! curl "http://localhost:3000/api/v1/admin/audit?action=kick_user&limit=20" \
+//!   -H "Authorization: Bearer TOKEN"
+//! ```
+
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+};
+use private_poker::{
+    security::{AntiCollusionDetector, AuditAction, AuditEntry, AuditFilter, CollusionFlag},
+    table::messages::TableResponse,
+    wallet::WalletError,
+};
+use serde::Deserialize;
+
+use super::AppState;
+use super::client_ip::ClientIp;
+use super::tables::ErrorResponse;
+
+type AdminResult<T> = Result<T, (StatusCode, Json<ErrorResponse>)>;
+
+#[derive(Debug, Deserialize)]
+pub struct FreezeRequest {
+    /// Why the wallet is being frozen
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FlagsQuery {
+    /// Only show flags raised against this user, reviewed or not
+    pub user_id: Option<i64>,
+}
+
+/// Build an error response
+fn error(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error: message.into(),
+        }),
+    )
+}
+
+/// Turn a table manager result into a response, mapping a missing table to `404`
+fn table_result(result: Result<TableResponse, String>) -> AdminResult<StatusCode> {
+    match result {
+        Ok(response) => match response.error_message() {
+            None => Ok(StatusCode::OK),
+            Some(message) => Err(error(StatusCode::BAD_REQUEST, message)),
+        },
+        Err(e) if e == "Table not found" => Err(error(StatusCode::NOT_FOUND, e)),
+        Err(e) => Err(error(StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
+/// Turn a wallet manager result into a response, mapping a missing wallet to `404`
+fn wallet_result(result: Result<(), WalletError>) -> AdminResult<StatusCode> {
+    match result {
+        Ok(()) => Ok(StatusCode::OK),
+        Err(e @ WalletError::WalletNotFound(_)) => {
+            Err(error(StatusCode::NOT_FOUND, e.client_message()))
+        }
+        Err(e) => Err(error(StatusCode::INTERNAL_SERVER_ERROR, e.client_message())),
+    }
+}
+
+/// Force-close a table.
+///
+/// Closes the table right away and marks it inactive, so it isn't loaded
+/// again when the server restarts. A hand in progress is called off, and
+/// seated players get their chips back in their wallets.
+///
+/// # Path Parameters
+///
+/// - `table_id`: Table ID (integer)
+///
+/// # Response
+///
+/// Returns `200 OK` with empty body on success.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `404 Not Found`: Table doesn't exist
+pub async fn close_table(
+    State(state): State<AppState>,
+    Extension(admin_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Path(table_id): Path<i64>,
+) -> AdminResult<StatusCode> {
+    if state.table_manager.get_table(table_id).await.is_none() {
+        return Err(error(StatusCode::NOT_FOUND, "Table not found"));
+    }
+
+    state
+        .table_manager
+        .close_table(table_id)
+        .await
+        .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    state.audit_log.record(
+        AuditEntry::new(Some(admin_id), AuditAction::CloseTable)
+            .with_table(table_id)
+            .with_ip(ip),
+    );
+    Ok(StatusCode::OK)
+}
+
+/// Pause a table.
+///
+/// A paused table doesn't start new hands until it's resumed.
+///
+/// # Path Parameters
+///
+/// - `table_id`: Table ID (integer)
+///
+/// # Response
+///
+/// Returns `200 OK` with empty body on success.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `404 Not Found`: Table doesn't exist
+pub async fn pause_table(
+    State(state): State<AppState>,
+    Extension(admin_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Path(table_id): Path<i64>,
+) -> AdminResult<StatusCode> {
+    let status = table_result(state.table_manager.set_paused(table_id, true).await)?;
+    state.audit_log.record(
+        AuditEntry::new(Some(admin_id), AuditAction::PauseTable)
+            .with_table(table_id)
+            .with_ip(ip),
+    );
+    Ok(status)
+}
+
+/// Resume a paused table.
+///
+/// # Path Parameters
+///
+/// - `table_id`: Table ID (integer)
+///
+/// # Response
+///
+/// Returns `200 OK` with empty body on success.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `404 Not Found`: Table doesn't exist
+pub async fn resume_table(
+    State(state): State<AppState>,
+    Extension(admin_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Path(table_id): Path<i64>,
+) -> AdminResult<StatusCode> {
+    let status = table_result(state.table_manager.set_paused(table_id, false).await)?;
+    state.audit_log.record(
+        AuditEntry::new(Some(admin_id), AuditAction::ResumeTable)
+            .with_table(table_id)
+            .with_ip(ip),
+    );
+    Ok(status)
+}
+
+/// Kick a user from a table.
+///
+/// The user's chips are cashed out to their wallet, just like when they
+/// leave on their own, and their WebSocket connection to the table is
+/// closed with `CloseReason::Kicked`.
+///
+/// # Path Parameters
+///
+/// - `table_id`: Table ID (integer)
+/// - `user_id`: ID of the user to kick (integer)
+///
+/// # Response
+///
+/// Returns `200 OK` with empty body on success.
+///
+/// # Errors
+///
+/// - `400 Bad Request`: User isn't at this table
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `404 Not Found`: Table doesn't exist
+pub async fn kick_user(
+    State(state): State<AppState>,
+    Extension(admin_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Path((table_id, user_id)): Path<(i64, i64)>,
+) -> AdminResult<StatusCode> {
+    let status = table_result(state.table_manager.kick_user(table_id, user_id).await)?;
+    state.audit_log.record(
+        AuditEntry::new(Some(admin_id), AuditAction::KickUser)
+            .with_target_user(user_id)
+            .with_table(table_id)
+            .with_ip(ip),
+    );
+    Ok(status)
+}
+
+/// Mute a user in a table's chat.
+///
+/// # Path Parameters
+///
+/// - `table_id`: Table ID (integer)
+/// - `user_id`: ID of the user to mute (integer)
+///
+/// # Response
+///
+/// Returns `200 OK` with empty body on success.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `404 Not Found`: Table doesn't exist
+pub async fn mute_user(
+    State(state): State<AppState>,
+    Extension(admin_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Path((table_id, user_id)): Path<(i64, i64)>,
+) -> AdminResult<StatusCode> {
+    let result = state.table_manager.set_muted(table_id, user_id, true).await;
+    let status = table_result(result)?;
+    state.audit_log.record(
+        AuditEntry::new(Some(admin_id), AuditAction::MuteUser)
+            .with_target_user(user_id)
+            .with_table(table_id)
+            .with_ip(ip),
+    );
+    Ok(status)
+}
+
+/// Unmute a user in a table's chat.
+///
+/// # Path Parameters
+///
+/// - `table_id`: Table ID (integer)
+/// - `user_id`: ID of the user to unmute (integer)
+///
+/// # Response
+///
+/// Returns `200 OK` with empty body on success.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `404 Not Found`: Table doesn't exist
+pub async fn unmute_user(
+    State(state): State<AppState>,
+    Extension(admin_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Path((table_id, user_id)): Path<(i64, i64)>,
+) -> AdminResult<StatusCode> {
+    let result = state
+        .table_manager
+        .set_muted(table_id, user_id, false)
+        .await;
+    let status = table_result(result)?;
+    state.audit_log.record(
+        AuditEntry::new(Some(admin_id), AuditAction::UnmuteUser)
+            .with_target_user(user_id)
+            .with_table(table_id)
+            .with_ip(ip),
+    );
+    Ok(status)
+}
+
+/// Freeze a user's wallet.
+///
+/// A frozen wallet can't buy in to tables, claim the faucet, or send or
+/// receive gifts, but the user can still cash out of tables they're at.
+///
+/// # Path Parameters
+///
+/// - `user_id`: ID of the user whose wallet to freeze (integer)
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///   "reason": "Chip dumping flagged at table 7"
+/// }
+/// ```
+///
+/// # Response
+///
+/// Returns `200 OK` with empty body on success.
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Reason is empty
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `404 Not Found`: User has no wallet
+pub async fn freeze_wallet(
+    State(state): State<AppState>,
+    Extension(admin_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Path(user_id): Path<i64>,
+    Json(request): Json<FreezeRequest>,
+) -> AdminResult<StatusCode> {
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err(error(StatusCode::BAD_REQUEST, "Reason is required"));
+    }
+
+    // The wallet manager writes the audit entry in the same transaction
+    wallet_result(
+        state
+            .wallet_manager
+            .freeze(user_id, reason, Some(admin_id), ip)
+            .await,
+    )
+}
+
+/// Unfreeze a user's wallet.
+///
+/// # Path Parameters
+///
+/// - `user_id`: ID of the user whose wallet to unfreeze (integer)
+///
+/// # Response
+///
+/// Returns `200 OK` with empty body on success.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `404 Not Found`: User has no wallet
+pub async fn unfreeze_wallet(
+    State(state): State<AppState>,
+    Extension(admin_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Path(user_id): Path<i64>,
+) -> AdminResult<StatusCode> {
+    wallet_result(
+        state
+            .wallet_manager
+            .unfreeze(user_id, Some(admin_id), ip)
+            .await,
+    )
+}
+
+/// View anti-collusion flags.
+///
+/// Returns the flags nobody has reviewed yet, or every flag raised against
+/// one user with `?user_id=`.
+///
+/// # Query Parameters
+///
+/// - `user_id`: Only show this user's flags (optional)
+///
+/// # Response
+///
+/// Returns `200 OK` with a list of flags, newest first.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `500 Internal Server Error`: Database error
+pub async fn list_flags(
+    State(state): State<AppState>,
+    Extension(admin_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Query(query): Query<FlagsQuery>,
+) -> AdminResult<Json<Vec<CollusionFlag>>> {
+    let detector = AntiCollusionDetector::new(state.pool.clone());
+    let flags = match query.user_id {
+        Some(user_id) => detector.get_user_flags(user_id).await,
+        None => detector.get_unreviewed_flags().await,
+    }
+    .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut entry = AuditEntry::new(Some(admin_id), AuditAction::ViewFlags).with_ip(ip);
+    if let Some(user_id) = query.user_id {
+        entry = entry.with_target_user(user_id);
+    }
+    state.audit_log.record(entry);
+    Ok(Json(flags))
+}
+
+/// View the audit log.
+///
+/// # Query Parameters
+///
+/// All parameters are optional and mirror `AuditFilter`:
+///
+/// - `actor_id`: Only actions taken by this user
+/// - `target_user_id`: Only actions taken on this user
+/// - `table_id`: Only actions taken at this table
+/// - `action`: Only this type of action, such as `kick_user`
+/// - `from`, `to`: Only actions taken in this range (RFC 3339, inclusive)
+/// - `limit`: Maximum number of entries to return (default: 100)
+/// - `offset`: Number of matching entries to skip (default: 0)
+///
+/// # Response
+///
+/// Returns `200 OK` with a list of entries, newest first. Entries recorded
+/// in the last moment may not be written yet.
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Invalid query parameters
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `403 Forbidden`: User isn't an admin
+/// - `500 Internal Server Error`: Database error
+pub async fn list_audit_log(
+    State(state): State<AppState>,
+    Query(filter): Query<AuditFilter>,
+) -> AdminResult<Json<Vec<AuditEntry>>> {
+    state
+        .audit_log
+        .query(&filter)
+        .await
+        .map(Json)
+        .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
@@ -5,6 +5,8 @@
 //! - Login with username/password and optional 2FA
 //! - Logout to invalidate refresh tokens
 //! - Token refresh for obtaining new access tokens
+//! - API keys for bot clients, which can be created and revoked
+//! - Email verification with codes sent to the user's email
 //!
 //! All endpoints return JSON responses with either authentication tokens or error messages.
 //!
@@ -24,11 +26,18 @@
 //!   -d '{"username": "player1", "password": "Pass123!"}'
 //! ```
 
-use axum::{Json, extract::State, http::StatusCode};
-use private_poker::auth::{LoginRequest, RegisterRequest};
+use axum::{
+    Json,
+    extract::{Extension, Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use private_poker::auth::{ApiKey, AuthError, LoginRequest, RegisterRequest};
+use private_poker::security::{AuditAction, AuditEntry};
 use serde::{Deserialize, Serialize};
 
 use super::AppState;
+use super::client_ip::ClientIp;
 use super::request_id::RequestId;
 
 #[derive(Debug, Deserialize)]
@@ -46,6 +55,16 @@ pub struct RegisterPayload {
     pub email: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyPayload {
+    pub label: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailPayload {
+    pub code: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AuthResponse {
     pub access_token: String,
@@ -59,6 +78,13 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct RateLimitedResponse {
+    pub error: String,
+    pub retry_after_secs: u64,
+    pub attempts_remaining: u32,
+}
+
 /// Register a new user account and automatically log them in.
 ///
 /// Creates a new user with the provided credentials and immediately generates
@@ -188,18 +214,30 @@ pub async fn register(
 /// # Errors
 ///
 /// - `401 Unauthorized`: Invalid credentials or incorrect 2FA code
+/// - `429 Too Many Requests`: Account is locked out after too many attempts.
+///   The `Retry-After` header and body say how long until the next attempt:
+///   ```json
+///   {
+///     "error": "Too many attempts, please try again in 201 seconds",
+///     "retry_after_secs": 201,
+///     "attempts_remaining": 0
+///   }
+///   ```
 ///
 /// # Security
 ///
-/// - Failed login attempts are rate-limited
+/// - Login attempts are rate-limited per account, with exponential backoff
+///   on repeated lockouts; a successful login resets the count
 /// - Passwords are verified against hashed values
 /// - 2FA code is required if enabled for the account
 /// - Device fingerprinting is used for session tracking
+/// - Successful and failed logins are written to the audit log
 pub async fn login(
     State(state): State<AppState>,
     RequestId(request_id): RequestId,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<LoginPayload>,
-) -> Result<Json<AuthResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<AuthResponse>, Response> {
     let request = LoginRequest {
         username: payload.username.clone(),
         password: payload.password,
@@ -208,7 +246,11 @@ pub async fn login(
 
     let device_fp = "web".to_string();
 
-    match state.auth_manager.login(request, device_fp).await {
+    match state
+        .auth_manager
+        .login_from(request, device_fp, ip.as_deref())
+        .await
+    {
         Ok((user, tokens)) => {
             tracing::info!(
                 request_id = %request_id,
@@ -216,6 +258,9 @@ pub async fn login(
                 username = %user.username,
                 "User logged in successfully"
             );
+            state
+                .audit_log
+                .record(AuditEntry::new(Some(user.id), AuditAction::Login).with_ip(ip));
             Ok(Json(AuthResponse {
                 access_token: tokens.access_token,
                 refresh_token: tokens.refresh_token,
@@ -230,12 +275,34 @@ pub async fn login(
                 error = %e,
                 "Login failed"
             );
+            state.audit_log.record(
+                AuditEntry::new(None, AuditAction::LoginFailed)
+                    .with_ip(ip)
+                    .with_details(format!("username: {}", payload.username)),
+            );
+            if let AuthError::RateLimited {
+                retry_after_secs,
+                attempts_remaining,
+            } = e
+            {
+                return Err((
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                    Json(RateLimitedResponse {
+                        error: e.client_message(),
+                        retry_after_secs,
+                        attempts_remaining,
+                    }),
+                )
+                    .into_response());
+            }
             Err((
                 StatusCode::UNAUTHORIZED,
                 Json(ErrorResponse {
                     error: e.client_message(),
                 }),
-            ))
+            )
+                .into_response())
         }
     }
 }
@@ -266,10 +333,17 @@ pub async fn login(
 /// - Access tokens cannot be invalidated early
 pub async fn logout(
     State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
     Json(refresh_token): Json<String>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
     match state.auth_manager.logout(refresh_token).await {
-        Ok(_) => Ok(StatusCode::NO_CONTENT),
+        Ok(_) => {
+            state
+                .audit_log
+                .record(AuditEntry::new(Some(user_id), AuditAction::Logout).with_ip(ip));
+            Ok(StatusCode::NO_CONTENT)
+        }
         Err(e) => Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
@@ -346,3 +420,198 @@ pub async fn refresh_token(
         )),
     }
 }
+
+/// Create an API key for a bot client.
+///
+/// The key acts as the authenticated user but can only view tables, join
+/// them, and take actions. It is returned once and can't be retrieved later.
+///
+/// # Request Body
+///
+/// ```json
+/// {"label": "my-bot"}
+/// ```
+///
+/// # Response
+///
+/// On success, returns `201 Created` with the key:
+/// ```json
+/// {
+///   "id": 1,
+///   "user_id": 42,
+///   "label": "my-bot",
+///   "key": "pk_3f9a1c0b7e2d_...",
+///   "scopes": ["view_table", "join_table", "take_action"],
+///   "created_at": "2026-10-16T10:30:00Z"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Label is empty or too long
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<CreateApiKeyPayload>,
+) -> Result<(StatusCode, Json<ApiKey>), (StatusCode, Json<ErrorResponse>)> {
+    match state
+        .auth_manager
+        .create_api_key(user_id, &payload.label)
+        .await
+    {
+        Ok(api_key) => {
+            state.audit_log.record(
+                AuditEntry::new(Some(user_id), AuditAction::ApiKeyCreated)
+                    .with_ip(ip)
+                    .with_details(format!("key {} ({})", api_key.id, api_key.label)),
+            );
+            Ok((StatusCode::CREATED, Json(api_key)))
+        }
+        Err(e) => {
+            let status = match e {
+                AuthError::InvalidApiKeyLabel => StatusCode::BAD_REQUEST,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            Err((
+                status,
+                Json(ErrorResponse {
+                    error: e.client_message(),
+                }),
+            ))
+        }
+    }
+}
+
+/// Revoke one of the user's API keys.
+///
+/// # Response
+///
+/// On success, returns `204 No Content` with empty body.
+///
+/// # Errors
+///
+/// - `404 Not Found`: User has no active key with that ID
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    ClientIp(ip): ClientIp,
+    Path(key_id): Path<i64>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    match state.auth_manager.revoke_api_key(user_id, key_id).await {
+        Ok(()) => {
+            state.audit_log.record(
+                AuditEntry::new(Some(user_id), AuditAction::ApiKeyRevoked)
+                    .with_ip(ip)
+                    .with_details(format!("key {}", key_id)),
+            );
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            let status = match e {
+                AuthError::ApiKeyNotFound => StatusCode::NOT_FOUND,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            Err((
+                status,
+                Json(ErrorResponse {
+                    error: e.client_message(),
+                }),
+            ))
+        }
+    }
+}
+
+/// Email the user a code to verify their email with.
+///
+/// Any code sent earlier stops working.
+///
+/// # Response
+///
+/// On success, returns `204 No Content` with empty body.
+///
+/// # Errors
+///
+/// - `400 Bad Request`: The account has no email
+/// - `429 Too Many Requests`: Too many verification emails were sent recently
+/// - `500 Internal Server Error`: The email couldn't be sent
+pub async fn send_verification(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+) -> Result<StatusCode, Response> {
+    match state.auth_manager.send_verification(user_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(
+            e @ AuthError::RateLimited {
+                retry_after_secs,
+                attempts_remaining,
+            },
+        ) => Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after_secs.to_string())],
+            Json(RateLimitedResponse {
+                error: e.client_message(),
+                retry_after_secs,
+                attempts_remaining,
+            }),
+        )
+            .into_response()),
+        Err(e) => {
+            let status = match e {
+                AuthError::EmailNotSet => StatusCode::BAD_REQUEST,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            Err((
+                status,
+                Json(ErrorResponse {
+                    error: e.client_message(),
+                }),
+            )
+                .into_response())
+        }
+    }
+}
+
+/// Verify the user's email with a code from `send_verification`.
+///
+/// # Request Body
+///
+/// ```json
+/// {"code": "123456"}
+/// ```
+///
+/// # Response
+///
+/// On success, returns `204 No Content` with empty body.
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Code is wrong, expired, or already used
+/// - `429 Too Many Requests`: Too many wrong codes were entered, so a new
+///   one has to be sent
+pub async fn verify_email(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Json(payload): Json<VerifyEmailPayload>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    match state
+        .auth_manager
+        .verify_email(user_id, payload.code.trim())
+        .await
+    {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            let status = match e {
+                AuthError::InvalidVerificationCode => StatusCode::BAD_REQUEST,
+                AuthError::VerificationAttemptsExceeded => StatusCode::TOO_MANY_REQUESTS,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            Err((
+                status,
+                Json(ErrorResponse {
+                    error: e.client_message(),
+                }),
+            ))
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Client IP address extraction.
+//!
+//! Handlers that record who did something (such as audit log entries) use
+//! [`ClientIp`] to find out where the request came from. Behind a reverse
+//! proxy, the first address in the `X-Forwarded-For` header is used;
+//! otherwise it's the peer address of the connection, which is only known
+//! when the server is started with
+//! `into_make_service_with_connect_info::<SocketAddr>()`.
+
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use private_poker::security::normalize_ip;
+use std::{convert::Infallible, net::SocketAddr};
+
+/// Header set by reverse proxies with the original client address first
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Client IP address, or `None` if it can't be determined
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientIp(pub Option<String>);
+
+/// Axum extractor for the client IP address
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let forwarded = parts
+            .headers
+            .get(FORWARDED_FOR_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .map(str::trim)
+            .filter(|ip| !ip.is_empty())
+            .map(str::to_string);
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string());
+
+        Ok(ClientIp(forwarded.or(peer).map(|ip| normalize_ip(&ip))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    async fn extract(request: Request<()>) -> ClientIp {
+        let (mut parts, _) = request.into_parts();
+        ClientIp::from_request_parts(&mut parts, &()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_forwarded_for_uses_first_address() {
+        let request = Request::builder()
+            .header(FORWARDED_FOR_HEADER, "::ffff:203.0.113.7, 10.0.0.1")
+            .body(())
+            .unwrap();
+
+        assert_eq!(
+            extract(request).await,
+            ClientIp(Some("203.0.113.7".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_peer_address() {
+        let mut request = Request::builder().body(()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([192, 168, 1, 5], 40000))));
+
+        assert_eq!(
+            extract(request).await,
+            ClientIp(Some("192.168.1.5".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_without_header_or_peer() {
+        let request = Request::builder().body(()).unwrap();
+
+        assert_eq!(extract(request).await, ClientIp(None));
+    }
+}
@@ -0,0 +1,84 @@
+//! Leaderboard API handlers.
+//!
+//! This module provides a public HTTP REST endpoint ranking players by net
+//! chips won, biggest pot, or hands played over a period. Rankings come from
+//! the per-day player stats, and leave out bots and frozen accounts.
+//!
+//! # Examples
+//!
+//! Top 10 winners this month:
+//! ```bash
+//! curl "http://localhost:3000/api/v1/leaderboard?period=month&metric=net_chips&limit=10"
+//! ```
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use private_poker::stats::{LeaderboardPage, LeaderboardQuery, StatsRepository};
+
+use super::AppState;
+use super::tables::ErrorResponse;
+
+/// Maximum number of players returned in one page
+const MAX_LEADERBOARD_LIMIT: i64 = 100;
+
+/// Get a page of the leaderboard.
+///
+/// This endpoint does not require authentication.
+///
+/// # Query Parameters
+///
+/// All parameters are optional and mirror `LeaderboardQuery`:
+///
+/// - `period`: `day`, `week`, `month`, or `all_time` (default: `week`)
+/// - `metric`: `net_chips`, `biggest_pot`, or `hands_played` (default: `net_chips`)
+/// - `limit`: Maximum number of players to return, up to 100 (default: 25)
+/// - `offset`: Number of ranked players to skip (default: 0)
+///
+/// # Response
+///
+/// Returns `200 OK` with a page of ranked players, best first:
+/// ```json
+/// {
+///   "entries": [
+///     { "rank": 1, "user_id": 7, "username": "alice", "value": 12500 }
+///   ],
+///   "total": 1
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Invalid query parameters
+/// - `500 Internal Server Error`: Database error
+pub async fn get_leaderboard(
+    State(state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<Json<LeaderboardPage>, (StatusCode, Json<ErrorResponse>)> {
+    if !(1..=MAX_LEADERBOARD_LIMIT).contains(&query.limit) || query.offset < 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!(
+                    "limit must be between 1 and {} and offset can't be negative",
+                    MAX_LEADERBOARD_LIMIT
+                ),
+            }),
+        ));
+    }
+
+    StatsRepository::new(state.pool.clone())
+        .get_leaderboard(&query)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                }),
+            )
+        })
+}
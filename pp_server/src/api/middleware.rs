@@ -4,6 +4,10 @@
 //! The middleware extracts and validates JWT access tokens from the Authorization header,
 //! then injects the authenticated user ID into request extensions for downstream handlers.
 //!
+//! Bot clients can send an API key (`Bearer pk_...`) instead of a JWT. API keys
+//! are limited to their scopes: getting a table's state, joining a table, and
+//! taking actions. Any other protected endpoint rejects them.
+//!
 //! # Usage
 //!
 //! Apply to protected routes in the router:
@@ -35,13 +39,15 @@
 //! ```
 
 use axum::{
-    extract::{Request, State},
-    http::{StatusCode, header::AUTHORIZATION},
+    extract::{MatchedPath, Request, State},
+    http::{Method, StatusCode, header::AUTHORIZATION},
     middleware::Next,
     response::Response,
 };
+use private_poker::auth::{API_KEY_PREFIX, ApiKeyScope, AuthError};
 
 use super::AppState;
+use crate::logging::log_security_event;
 
 /// Authentication middleware that validates JWT tokens and injects user ID.
 ///
@@ -61,6 +67,8 @@ use super::AppState;
 /// - **Missing header**: Returns `401 Unauthorized`
 /// - **Invalid format**: Returns `401 Unauthorized`
 /// - **Invalid/expired token**: Returns `401 Unauthorized`
+/// - **Invalid/revoked API key**: Returns `401 Unauthorized`
+/// - **API key outside its scopes**: Returns `403 Forbidden`
 ///
 /// # Example
 ///
@@ -97,6 +105,26 @@ pub async fn auth_middleware(
         None => return Err(StatusCode::UNAUTHORIZED),
     };
 
+    if token.starts_with(API_KEY_PREFIX) {
+        let claims = state
+            .auth_manager
+            .verify_api_key(token)
+            .await
+            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|path| path.as_str());
+        match route.and_then(|route| required_scope(request.method(), route)) {
+            Some(scope) if claims.allows(scope) => {
+                request.extensions_mut().insert(claims.user_id);
+                return Ok(next.run(request).await);
+            }
+            _ => return Err(StatusCode::FORBIDDEN),
+        }
+    }
+
     // Verify token and get user ID
     match state.auth_manager.verify_access_token(token) {
         Ok(claims) => {
@@ -107,3 +135,91 @@ pub async fn auth_middleware(
         Err(_) => Err(StatusCode::UNAUTHORIZED),
     }
 }
+
+/// Admin middleware that only lets admins through.
+///
+/// Must run after [`auth_middleware`], which injects the user ID. Whether
+/// the user is an admin is read from the database on every request, so
+/// revoking admin rights takes effect right away.
+///
+/// # Behavior
+///
+/// - **Admin**: Calls next handler
+/// - **Not an admin**: Returns `403 Forbidden`
+/// - **No authenticated user**: Returns `401 Unauthorized`
+/// - **Database error**: Returns `500 Internal Server Error`
+pub async fn admin_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let user_id = *request
+        .extensions()
+        .get::<i64>()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    match state.auth_manager.is_admin(user_id).await {
+        Ok(true) => Ok(next.run(request).await),
+        Ok(false) | Err(AuthError::UserNotFound) => {
+            log_security_event(
+                "admin_access_denied",
+                Some(user_id),
+                None,
+                &format!("Non-admin tried to access {}", request.uri().path()),
+            );
+            Err(StatusCode::FORBIDDEN)
+        }
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Scope an API key needs for a route, or `None` if keys can't use it.
+fn required_scope(method: &Method, route: &str) -> Option<ApiKeyScope> {
+    if *method == Method::GET
+        && (route.ends_with("/tables/{table_id}")
+            || route.ends_with("/tables/{table_id}/legal-actions"))
+    {
+        Some(ApiKeyScope::ViewTable)
+    } else if *method == Method::POST && route.ends_with("/tables/{table_id}/join") {
+        Some(ApiKeyScope::JoinTable)
+    } else if *method == Method::POST && route.ends_with("/tables/{table_id}/action") {
+        Some(ApiKeyScope::TakeAction)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_scope_covers_only_table_play() {
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/tables/{table_id}"),
+            Some(ApiKeyScope::ViewTable)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/tables/{table_id}/legal-actions"),
+            Some(ApiKeyScope::ViewTable)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/tables/{table_id}/join"),
+            Some(ApiKeyScope::JoinTable)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/tables/{table_id}/action"),
+            Some(ApiKeyScope::TakeAction)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/tables/{table_id}/leave"),
+            None
+        );
+        assert_eq!(required_scope(&Method::POST, "/api/v1/auth/logout"), None);
+        assert_eq!(required_scope(&Method::POST, "/api/v1/auth/api-keys"), None);
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/auth/verify-email"),
+            None
+        );
+    }
+}
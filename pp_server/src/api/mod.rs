@@ -15,8 +15,11 @@
 //!
 //! - [`auth`]: User authentication (register, login, logout, token refresh)
 //! - [`tables`]: Table management (list, join, leave, take actions)
+//! - [`leaderboard`]: Player rankings by winnings, biggest pot, or hands played
+//! - [`notes`]: Private notes players keep on opponents
 //! - [`websocket`]: Real-time bidirectional communication for live game updates
-//! - [`middleware`]: Authentication middleware for protected endpoints
+//! - [`admin`]: Moderation of tables, users, wallets, and the audit log
+//! - [`middleware`]: Authentication and admin middleware for protected endpoints
 //!
 //! # Endpoints Overview
 //!
@@ -25,6 +28,10 @@
 //! - `POST /api/auth/login` - Login with credentials
 //! - `POST /api/auth/logout` - Invalidate refresh token
 //! - `POST /api/auth/refresh` - Get new access token
+//! - `POST /api/v1/auth/api-keys` - Create an API key for a bot (requires auth)
+//! - `DELETE /api/v1/auth/api-keys/:id` - Revoke an API key (requires auth)
+//! - `POST /api/v1/auth/verify-email/send` - Email a verification code (requires auth)
+//! - `POST /api/v1/auth/verify-email` - Verify email with a code (requires auth)
 //!
 //! ## Tables
 //! - `GET /api/tables` - List all tables (public)
@@ -33,6 +40,25 @@
 //! - `POST /api/tables/:id/leave` - Leave table (requires auth)
 //! - `POST /api/tables/:id/action` - Take action (requires auth)
 //!
+//! ## Leaderboard
+//! - `GET /api/v1/leaderboard?period=...&metric=...` - Ranked players (public)
+//!
+//! ## Player Notes (Requires Auth)
+//! - `GET /api/v1/notes?usernames=a,b` - Your notes on the listed opponents
+//! - `PUT /api/v1/notes/:username` - Set or clear your note on an opponent
+//!
+//! ## Admin (Requires Admin)
+//! - `POST /api/v1/admin/tables/:id/close` - Force-close table
+//! - `POST /api/v1/admin/tables/:id/pause` - Pause table
+//! - `POST /api/v1/admin/tables/:id/resume` - Resume table
+//! - `POST /api/v1/admin/tables/:id/kick/:user_id` - Kick user from table
+//! - `POST /api/v1/admin/tables/:id/mute/:user_id` - Mute user in table chat
+//! - `POST /api/v1/admin/tables/:id/unmute/:user_id` - Unmute user in table chat
+//! - `POST /api/v1/admin/users/:id/freeze` - Freeze user's wallet
+//! - `POST /api/v1/admin/users/:id/unfreeze` - Unfreeze user's wallet
+//! - `GET /api/v1/admin/flags` - View anti-collusion flags
+//! - `GET /api/v1/admin/audit` - View audit log
+//!
 //! ## WebSocket
 //! - `GET /ws/:table_id?token=<jwt>` - Establish WebSocket connection
 //!
@@ -42,9 +68,10 @@
 //! # Example Usage
 //!
 //! ```rust,no_run
-//! use pp_server::api::{create_router, AppState};
+//! use pp_server::api::{create_router, AppState, websocket::HeartbeatConfig};
 //! use std::sync::Arc;
 //! # use private_poker::auth::AuthManager;
+//! # use private_poker::security::AuditLog;
 //! # use private_poker::table::TableManager;
 //! # use private_poker::wallet::WalletManager;
 //! # use sqlx::PgPool;
@@ -53,6 +80,7 @@
 //! # let table_manager: TableManager = unimplemented!();
 //! # let wallet_manager: WalletManager = unimplemented!();
 //! # let pool: PgPool = unimplemented!();
+//! # let audit_log: AuditLog = unimplemented!();
 //!
 //! // Create application state
 //! let state = AppState {
@@ -60,6 +88,8 @@
 //!     table_manager: Arc::new(table_manager),
 //!     wallet_manager: Arc::new(wallet_manager),
 //!     pool: Arc::new(pool),
+//!     audit_log: Arc::new(audit_log),
+//!     heartbeat: HeartbeatConfig::default(),
 //! };
 //!
 //! // Create router with all endpoints
@@ -77,6 +107,9 @@
 //! - JWT access tokens expire after 15 minutes
 //! - JWT refresh tokens expire after 30 days
 //! - WebSocket connections require valid JWT in query parameter
+//! - API keys (`Bearer pk_...`) only work for getting, joining, and acting at tables
+//! - Admin endpoints return `403 Forbidden` to non-admins
+//! - Logins, logouts, API key changes, and admin actions are written to the audit log
 //! - Passwords are hashed with bcrypt before storage
 //! - Rate limiting applied to authentication endpoints
 //!
@@ -85,8 +118,12 @@
 //! CORS is configured permissively for development. In production, configure
 //! appropriate origins, methods, and headers.
 
+pub mod admin;
 pub mod auth;
+pub mod client_ip;
+pub mod leaderboard;
 pub mod middleware;
+pub mod notes;
 pub mod rate_limiter;
 pub mod request_id;
 pub mod tables;
@@ -97,9 +134,12 @@ use axum::{
     extract::State,
     http::StatusCode,
     response::{IntoResponse, Json},
-    routing::{get, post},
+    routing::{delete, get, post, put},
+};
+use private_poker::{
+    auth::AuthManager, db::CircuitState, security::AuditLog, table::TableManager,
+    wallet::WalletManager,
 };
-use private_poker::{auth::AuthManager, table::TableManager, wallet::WalletManager};
 use serde_json::json;
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -116,14 +156,18 @@ use tower_http::cors::CorsLayer;
 /// - `table_manager`: Manages poker tables and forwards commands to table actors
 /// - `wallet_manager`: Manages user balances and transactions
 /// - `pool`: Database connection pool for direct queries
+/// - `audit_log`: Records security-relevant actions in the background
+/// - `heartbeat`: How often WebSocket clients are pinged and how many
+///   missed pongs are tolerated
 #[derive(Clone)]
 pub struct AppState {
     pub auth_manager: Arc<AuthManager>,
     pub table_manager: Arc<TableManager>,
     #[allow(dead_code)]
     pub wallet_manager: Arc<WalletManager>,
-    #[allow(dead_code)]
     pub pool: Arc<PgPool>,
+    pub audit_log: Arc<AuditLog>,
+    pub heartbeat: websocket::HeartbeatConfig,
 }
 
 /// Create the complete API router with all endpoints and middleware.
@@ -147,12 +191,27 @@ pub struct AppState {
 /// POST /api/v1/auth/register           - Register user (public)
 /// POST /api/v1/auth/login              - Login (public)
 /// POST /api/v1/auth/logout             - Logout (auth required)
-/// POST /api/v1/auth/refresh            - Refresh token (auth required)
+/// POST /api/v1/auth/refresh            - Refresh token (public, takes the refresh token)
+/// POST /api/v1/auth/api-keys           - Create API key (auth required)
+/// DELETE /api/v1/auth/api-keys/:id     - Revoke API key (auth required)
 /// GET  /api/v1/tables                  - List tables (public)
 /// GET  /api/v1/tables/:id              - Get table (auth required)
 /// POST /api/v1/tables/:id/join         - Join table (auth required)
 /// POST /api/v1/tables/:id/leave        - Leave table (auth required)
 /// POST /api/v1/tables/:id/action       - Take action (auth required)
+/// GET  /api/v1/leaderboard             - Ranked players (public)
+/// GET  /api/v1/notes?usernames=a,b     - Player notes (auth required)
+/// PUT  /api/v1/notes/:username         - Set player note (auth required)
+/// POST /api/v1/admin/tables/:id/close  - Force-close table (admin required)
+/// POST /api/v1/admin/tables/:id/pause  - Pause table (admin required)
+/// POST /api/v1/admin/tables/:id/resume - Resume table (admin required)
+/// POST /api/v1/admin/tables/:id/kick/:user_id - Kick user (admin required)
+/// POST /api/v1/admin/tables/:id/mute/:user_id - Mute user (admin required)
+/// POST /api/v1/admin/tables/:id/unmute/:user_id - Unmute user (admin required)
+/// POST /api/v1/admin/users/:id/freeze  - Freeze wallet (admin required)
+/// POST /api/v1/admin/users/:id/unfreeze - Unfreeze wallet (admin required)
+/// GET  /api/v1/admin/flags             - Anti-collusion flags (admin required)
+/// GET  /api/v1/admin/audit             - Audit log (admin required)
 /// GET  /ws/:table_id?token=<jwt>       - WebSocket (auth required)
 /// ```
 ///
@@ -207,66 +266,138 @@ fn create_v1_router(state: AppState) -> Router<AppState> {
     let public_routes = Router::new()
         .route("/auth/register", post(auth::register))
         .route("/auth/login", post(auth::login))
-        .route("/tables", get(tables::list_tables));
+        // The refresh token authenticates the request, since the access
+        // token has usually expired by the time it's refreshed
+        .route("/auth/refresh", post(auth::refresh_token))
+        .route("/tables", get(tables::list_tables))
+        .route("/leaderboard", get(leaderboard::get_leaderboard));
 
     // Protected routes (require authentication middleware)
     let protected_routes = Router::new()
         .route("/auth/logout", post(auth::logout))
-        .route("/auth/refresh", post(auth::refresh_token))
+        .route("/auth/api-keys", post(auth::create_api_key))
+        .route("/auth/api-keys/{key_id}", delete(auth::revoke_api_key))
+        .route("/auth/verify-email", post(auth::verify_email))
+        .route("/auth/verify-email/send", post(auth::send_verification))
+        .route("/tables", post(tables::create_table))
         .route("/tables/{table_id}", get(tables::get_table))
         .route("/tables/{table_id}/join", post(tables::join_table))
         .route("/tables/{table_id}/leave", post(tables::leave_table))
         .route("/tables/{table_id}/action", post(tables::take_action))
+        .route(
+            "/tables/{table_id}/legal-actions",
+            get(tables::get_legal_actions),
+        )
+        .route("/notes", get(notes::get_notes))
+        .route("/notes/{username}", put(notes::set_note))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            middleware::auth_middleware,
+        ));
+
+    // Admin routes (admin middleware runs after authentication)
+    let admin_routes = Router::new()
+        .route("/admin/tables/{table_id}/close", post(admin::close_table))
+        .route("/admin/tables/{table_id}/pause", post(admin::pause_table))
+        .route("/admin/tables/{table_id}/resume", post(admin::resume_table))
+        .route(
+            "/admin/tables/{table_id}/kick/{user_id}",
+            post(admin::kick_user),
+        )
+        .route(
+            "/admin/tables/{table_id}/mute/{user_id}",
+            post(admin::mute_user),
+        )
+        .route(
+            "/admin/tables/{table_id}/unmute/{user_id}",
+            post(admin::unmute_user),
+        )
+        .route("/admin/users/{user_id}/freeze", post(admin::freeze_wallet))
+        .route(
+            "/admin/users/{user_id}/unfreeze",
+            post(admin::unfreeze_wallet),
+        )
+        .route("/admin/flags", get(admin::list_flags))
+        .route("/admin/audit", get(admin::list_audit_log))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            middleware::admin_middleware,
+        ))
         .layer(axum::middleware::from_fn_with_state(
             state.clone(),
             middleware::auth_middleware,
         ));
 
     // Combine v1 routes
-    Router::new().merge(public_routes).merge(protected_routes)
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
+        .merge(admin_routes)
 }
 
 /// Health check endpoint for monitoring and load balancers.
 ///
 /// Performs comprehensive health checks on critical system components:
-/// - Database connectivity (executes simple query)
+/// - Database connectivity (executes simple query, skipped while the wallet
+///   circuit breaker is open)
 /// - Table manager responsiveness
 ///
 /// Returns JSON with detailed health status and appropriate HTTP status code.
 ///
 /// # Response
 ///
-/// Returns `200 OK` if all components are healthy, or `503 Service Unavailable` if any component fails.
+/// Returns `200 OK` with status `healthy` if all components are healthy, or
+/// `degraded` if the database is failing or still recovering; in-progress
+/// hands keep running without it, so load balancers shouldn't pull the node.
+/// Returns `503 Service Unavailable` if the table manager fails.
 ///
 /// # Example
 ///
 /// ```bash
 /// curl http://localhost:3000/health
-/// # {"status":"healthy","database":true,"tables":true,"timestamp":"2025-11-22T10:30:00Z"}
+/// # {"status":"healthy","database":true,"circuit":"closed","tables":true,"timestamp":"2025-11-22T10:30:00Z"}
 /// ```
 async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
-    // Check database connectivity
-    let db_healthy = sqlx::query("SELECT 1")
-        .fetch_one(&*state.pool)
-        .await
-        .is_ok();
+    // Don't probe a database the circuit breaker already considers down, and
+    // feed the result back so a recovered database closes the circuit
+    let breaker = state.wallet_manager.db_breaker();
+    let db_healthy = breaker.allows_call() && {
+        let ok = sqlx::query("SELECT 1")
+            .fetch_one(&*state.pool)
+            .await
+            .is_ok();
+        if ok {
+            breaker.record_success();
+        } else {
+            breaker.record_failure();
+        }
+        ok
+    };
+    let circuit = breaker.state();
 
     // Check if table manager is responsive (has active tables count)
     let table_count = state.table_manager.table_count();
     let tables_healthy = table_count >= 0;
 
-    let overall_healthy = db_healthy && tables_healthy;
-
-    let status_code = if overall_healthy {
-        StatusCode::OK
+    let (status_code, status) = if !tables_healthy {
+        (StatusCode::SERVICE_UNAVAILABLE, "unhealthy")
+    } else if !db_healthy || circuit != CircuitState::Closed {
+        (StatusCode::OK, "degraded")
     } else {
-        StatusCode::SERVICE_UNAVAILABLE
+        (StatusCode::OK, "healthy")
+    };
+
+    let circuit = match circuit {
+        CircuitState::Closed => "closed",
+        CircuitState::Open => "open",
+        CircuitState::HalfOpen => "half_open",
     };
 
     let response = json!({
-        "status": if overall_healthy { "healthy" } else { "unhealthy" },
+        "status": status,
         "version": env!("CARGO_PKG_VERSION"),
         "database": db_healthy,
+        "circuit": circuit,
         "tables": {
             "healthy": tables_healthy,
             "active_count": table_count
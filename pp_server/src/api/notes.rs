@@ -0,0 +1,167 @@
+//! Player notes API handlers.
+//!
+//! This module provides HTTP REST endpoints for the private notes a player
+//! keeps on opponents. Every request is scoped to the authenticated user, so
+//! nobody can read or change another player's notes.
+//!
+//! # Examples
+//!
+//! Note an opponent:
+//! ```bash
+//! curl -X PUT http://localhost:3000/api/v1/notes/alice \
+//!   -H "Authorization: Bearer <token>" \
+//!   -H "Content-Type: application/json" \
+//!   -d '{"note": "Calls down light"}'
+//! ```
+//!
+//! Get notes on everyone at a table:
+//! ```bash
+//! curl "http://localhost:3000/api/v1/notes?usernames=alice,bob" \
+//!   -H "Authorization: Bearer <token>"
+//! ```
+
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+};
+use private_poker::notes::{NotesError, NotesRepository};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::AppState;
+use super::tables::ErrorResponse;
+
+/// Maximum number of usernames looked up in one request
+const MAX_NOTE_LOOKUPS: usize = 50;
+
+/// Set note request
+#[derive(Debug, Deserialize)]
+pub struct SetNoteRequest {
+    /// Note text; empty text clears the note
+    pub note: String,
+}
+
+/// Get notes query parameters
+#[derive(Debug, Deserialize)]
+pub struct GetNotesQuery {
+    /// Comma-separated usernames to look up
+    pub usernames: String,
+}
+
+/// Get notes response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotesResponse {
+    /// The caller's notes, keyed by opponent username
+    pub notes: HashMap<String, String>,
+}
+
+/// Set, replace, or clear the caller's note on an opponent.
+///
+/// # Authentication
+///
+/// Requires valid JWT token in Authorization header.
+///
+/// # Path Parameters
+///
+/// - `username`: Username of the opponent the note is about
+///
+/// # Request Body
+///
+/// ```json
+/// { "note": "Calls down light" }
+/// ```
+///
+/// # Response
+///
+/// Returns `204 No Content` once the note is saved.
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Note is longer than the limit
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `500 Internal Server Error`: Database error
+pub async fn set_note(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Path(username): Path<String>,
+    Json(request): Json<SetNoteRequest>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    NotesRepository::new(state.pool.clone())
+        .set_note(user_id, &username, &request.note)
+        .await
+        .map(|()| StatusCode::NO_CONTENT)
+        .map_err(|e| {
+            let status = match e {
+                NotesError::TooLong { .. } => StatusCode::BAD_REQUEST,
+                NotesError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                }),
+            )
+        })
+}
+
+/// Get the caller's notes on a set of opponents.
+///
+/// # Authentication
+///
+/// Requires valid JWT token in Authorization header.
+///
+/// # Query Parameters
+///
+/// - `usernames`: Comma-separated usernames, up to 50
+///
+/// # Response
+///
+/// Returns `200 OK` with notes on the opponents that have one:
+/// ```json
+/// { "notes": { "alice": "Calls down light" } }
+/// ```
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Too many usernames
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `500 Internal Server Error`: Database error
+pub async fn get_notes(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Query(query): Query<GetNotesQuery>,
+) -> Result<Json<NotesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let usernames: Vec<String> = query
+        .usernames
+        .split(',')
+        .map(str::trim)
+        .filter(|username| !username.is_empty())
+        .map(String::from)
+        .collect();
+
+    if usernames.len() > MAX_NOTE_LOOKUPS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!(
+                    "Can't look up more than {} usernames at once",
+                    MAX_NOTE_LOOKUPS
+                ),
+            }),
+        ));
+    }
+
+    NotesRepository::new(state.pool.clone())
+        .get_notes(user_id, &usernames)
+        .await
+        .map(|notes| Json(NotesResponse { notes }))
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                }),
+            )
+        })
+}
@@ -1,11 +1,14 @@
 //! Table management API handlers.
 //!
 //! This module provides HTTP REST endpoints for poker table operations including:
-//! - Listing all active tables with player counts and blind levels
+//! - Listing active tables with player counts and blind levels, filtered,
+//!   sorted, and paginated
 //! - Getting detailed state of a specific table
+//! - Creating a table and taking a seat at it in one request
 //! - Joining tables with buy-in amounts
 //! - Leaving tables and cashing out chips
 //! - Taking poker actions (fold, check, call, raise, all-in)
+//! - Checking which actions are legal on your turn before taking one
 //!
 //! Most endpoints require authentication via JWT bearer token except for listing tables.
 //!
@@ -16,6 +19,20 @@
 //! curl http://localhost:3000/api/tables
 //! ```
 //!
+//! List the 10 fullest tables with an open seat:
+//! ```bash
+//! curl "http://localhost:3000/api/tables?sort_by=players&order=desc&limit=10&has_waitlist_space=true"
+//! ```
+//!
+//! Create a table and sit down at it (`create_table.json` holds a full table
+//! config, a buy-in, and an optional passphrase):
+//! ```bash
+//! curl -X POST http://localhost:3000/api/tables \
+//!   -H "Authorization: Bearer TOKEN" \
+//!   -H "Content-Type: application/json" \
+//!   -d @create_table.json
+//! ```
+//!
 //! Join a table:
 //! ```bash
 //! curl -X POST http://localhost:3000/api/tables/1/join \
@@ -26,10 +43,14 @@
 
 use axum::{
     Json,
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
 };
-use private_poker::{game::entities::Action, table::messages::TableMessage};
+use private_poker::{
+    game::entities::{Action, TurnInfo},
+    net::messages::TableFilter,
+    table::{config::TableConfig, messages::TableMessage},
+};
 use serde::{Deserialize, Serialize};
 
 use super::AppState;
@@ -46,6 +67,12 @@ pub struct TableListItem {
     pub is_private: bool,
 }
 
+#[derive(Debug, Serialize)]
+pub struct TableListResponse {
+    pub tables: Vec<TableListItem>,
+    pub total: usize,
+}
+
 #[derive(Debug, Serialize)]
 pub struct TableStateResponse {
     pub id: i64,
@@ -55,10 +82,23 @@ pub struct TableStateResponse {
     pub phase: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CreateTableRequest {
+    pub config: TableConfig,
+    pub buy_in_amount: i64,
+    pub passphrase: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateTableResponse {
+    pub table_id: i64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct JoinTableRequest {
     pub buy_in_amount: i64,
     pub passphrase: Option<String>,
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -93,38 +133,59 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
-/// List all active poker tables.
+/// List active poker tables.
 ///
-/// Returns a list of all tables currently running on the server with basic information
-/// including name, player count, blinds, and privacy status. This endpoint does not
-/// require authentication.
+/// Returns a page of the tables currently running on the server with basic information
+/// including name, player count, blinds, and privacy status, plus the total number of
+/// matching tables. This endpoint does not require authentication.
+///
+/// # Query Parameters
+///
+/// All parameters are optional and mirror `TableFilter`:
+///
+/// - `limit`: Maximum number of tables to return (default: all)
+/// - `offset`: Number of matching tables to skip (default: 0)
+/// - `sort_by`: `players`, `stakes`, or `name` (default: newest first)
+/// - `order`: `asc` or `desc` (default: `asc`)
+/// - `stakes_tier`: `Micro`, `Low`, `Mid`, or `High`
+/// - `speed`: `normal`, `turbo`, or `hyper`
+/// - `has_waitlist_space`: Only tables with an open seat
+/// - `name_query`: Case-insensitive substring of the table name
+///
+/// Ties are broken by table ID, so pages don't overlap.
 ///
 /// # Response
 ///
-/// Returns `200 OK` with array of table summaries:
+/// Returns `200 OK` with a page of table summaries:
 /// ```json
-/// [
-///   {
-///     "id": 1,
-///     "name": "High Stakes Table",
-///     "max_players": 9,
-///     "player_count": 5,
-///     "small_blind": 10,
-///     "big_blind": 20,
-///     "is_private": false
-///   }
-/// ]
+/// {
+///   "tables": [
+///     {
+///       "id": 1,
+///       "name": "High Stakes Table",
+///       "max_players": 9,
+///       "player_count": 5,
+///       "small_blind": 10,
+///       "big_blind": 20,
+///       "is_private": false
+///     }
+///   ],
+///   "total": 1
+/// }
 /// ```
 ///
 /// # Errors
 ///
+/// - `400 Bad Request`: Invalid query parameters
 /// - `500 Internal Server Error`: Database or server error
 pub async fn list_tables(
     State(state): State<AppState>,
-) -> Result<Json<Vec<TableListItem>>, (StatusCode, Json<ErrorResponse>)> {
-    match state.table_manager.list_tables().await {
-        Ok(tables) => {
-            let items = tables
+    Query(filter): Query<TableFilter>,
+) -> Result<Json<TableListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.table_manager.list_tables_page(&filter).await {
+        Ok(page) => {
+            let tables = page
+                .tables
                 .into_iter()
                 .map(|t| TableListItem {
                     id: t.id,
@@ -136,7 +197,10 @@ pub async fn list_tables(
                     is_private: t.is_private,
                 })
                 .collect();
-            Ok(Json(items))
+            Ok(Json(TableListResponse {
+                tables,
+                total: page.total,
+            }))
         }
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -196,6 +260,91 @@ pub async fn get_table(
     }
 }
 
+/// Create a poker table and seat the caller at it.
+///
+/// The table only exists if the caller's buy-in succeeds: when the join fails
+/// (insufficient balance, buy-in outside the table's limits) the table is torn
+/// down and nothing is left in escrow.
+///
+/// # Authentication
+///
+/// Requires valid JWT bearer token in `Authorization` header.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///   "config": { "name": "Friday Game", "max_players": 6, ... },
+///   "buy_in_amount": 1000,
+///   "passphrase": null  // Makes the table private when set
+/// }
+/// ```
+///
+/// # Response
+///
+/// Returns `201 Created` with the new table's ID:
+/// ```json
+/// { "table_id": 42 }
+/// ```
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Invalid config or the creator couldn't buy in
+/// - `401 Unauthorized`: Missing or invalid authentication token
+pub async fn create_table(
+    State(state): State<AppState>,
+    RequestId(request_id): RequestId,
+    Extension(user_id): Extension<i64>,
+    Json(request): Json<CreateTableRequest>,
+) -> Result<(StatusCode, Json<CreateTableResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let start = std::time::Instant::now();
+
+    // Only the request's passphrase decides privacy, never a client-supplied hash
+    let mut config = request.config;
+    config.passphrase_hash = None;
+
+    // Get username from user_id
+    // For now, use placeholder
+    let username = format!("user_{}", user_id);
+
+    let result = state
+        .table_manager
+        .create_and_join(
+            config,
+            user_id,
+            username,
+            request.buy_in_amount,
+            request.passphrase,
+        )
+        .await;
+
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(table_id) => {
+            tracing::info!(
+                request_id = %request_id,
+                user_id = user_id,
+                table_id = table_id,
+                buy_in = request.buy_in_amount,
+                duration_ms = duration_ms,
+                "User created and joined table"
+            );
+            Ok((StatusCode::CREATED, Json(CreateTableResponse { table_id })))
+        }
+        Err(e) => {
+            tracing::warn!(
+                request_id = %request_id,
+                user_id = user_id,
+                error = %e,
+                duration_ms = duration_ms,
+                "Failed to create table"
+            );
+            Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e })))
+        }
+    }
+}
+
 /// Join a poker table with a buy-in.
 ///
 /// Seats the authenticated user at the specified table with the provided buy-in amount.
@@ -215,7 +364,8 @@ pub async fn get_table(
 /// ```json
 /// {
 ///   "buy_in_amount": 1000,
-///   "passphrase": null  // Required for private tables
+///   "passphrase": null,  // Required for private tables
+///   "idempotency_key": "9f1c..."  // Optional, reuse it when retrying
 /// }
 /// ```
 ///
@@ -233,6 +383,8 @@ pub async fn get_table(
 /// - Buy-in amount is deducted from user's wallet balance
 /// - User must have sufficient balance in their wallet
 /// - User is added to waitlist if table is full
+/// - Retrying with the same `idempotency_key` returns the original result
+///   without deducting the buy-in again
 pub async fn join_table(
     State(state): State<AppState>,
     RequestId(request_id): RequestId,
@@ -254,6 +406,7 @@ pub async fn join_table(
             username.clone(),
             request.buy_in_amount,
             request.passphrase,
+            request.idempotency_key,
         )
         .await;
 
@@ -483,6 +636,24 @@ pub async fn take_action(
                     }),
                 ))
             }
+            ref response @ private_poker::table::messages::TableResponse::RaiseBelowMinimum {
+                min_raise,
+            } => {
+                tracing::warn!(
+                    request_id = %request_id,
+                    user_id = user_id,
+                    table_id = table_id,
+                    action = %action_debug,
+                    min_raise = min_raise,
+                    "Raise below minimum"
+                );
+                Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse {
+                        error: response.error_message().unwrap_or_default(),
+                    }),
+                ))
+            }
             private_poker::table::messages::TableResponse::InvalidAction(ref e) => {
                 tracing::warn!(
                     request_id = %request_id,
@@ -527,3 +698,79 @@ pub async fn take_action(
         }
     }
 }
+
+/// List the actions the caller could take right now, without acting.
+///
+/// # Path Parameters
+///
+/// - `table_id`: Table ID (integer)
+///
+/// # Authentication
+///
+/// Requires valid JWT bearer token or API key in `Authorization` header.
+///
+/// # Response
+///
+/// Returns `200 OK` with the same turn info a turn signal carries:
+/// ```json
+/// {
+///   "action_choices": [...],
+///   "call_amount": 10,
+///   "min_raise": 20,
+///   "max_raise": 990,
+///   "pot_size": 30,
+///   "pot_odds": 0.25
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Not your turn, or not at the table
+/// - `401 Unauthorized`: Missing or invalid authentication token
+/// - `404 Not Found`: Table doesn't exist
+pub async fn get_legal_actions(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Path(table_id): Path<i64>,
+) -> Result<Json<TurnInfo>, (StatusCode, Json<ErrorResponse>)> {
+    let table_handle = match state.table_manager.get_table(table_id).await {
+        Some(handle) => handle,
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "Table not found".to_string(),
+                }),
+            ));
+        }
+    };
+
+    let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+    let message = TableMessage::GetLegalActions {
+        user_id,
+        response: response_tx,
+    };
+
+    if let Err(e) = table_handle.send(message).await {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse { error: e }),
+        ));
+    }
+
+    match response_rx.await {
+        Ok(Ok(turn)) => Ok(Json(turn)),
+        Ok(Err(response)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: response.error_message().unwrap_or_default(),
+            }),
+        )),
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Response channel closed".to_string(),
+            }),
+        )),
+    }
+}
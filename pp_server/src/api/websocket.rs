@@ -6,12 +6,34 @@
 //!
 //! # Connection Flow
 //!
-//! 1. Client connects via `GET /ws/:table_id?token=<jwt_token>`
-//! 2. Server validates JWT and establishes WebSocket
-//! 3. Server spawns two tasks:
-//!    - Send task: Pushes game view updates every 1 second
+//! 1. Client connects via `GET /ws/:table_id?token=<jwt_token>`, adding
+//!    `&resume=true` when reconnecting to replay missed game events, and
+//!    `&last_seq=<seq>` to catch up on game views with deltas from the last
+//!    one it has instead of a full snapshot
+//! 2. Server validates JWT and establishes WebSocket. A missing or invalid
+//!    token closes the connection with `CloseReason::AuthInvalid`, and an
+//!    expired one with `CloseReason::AuthExpired` so the client knows to
+//!    refresh it rather than log in again.
+//! 3. Client sends a `Hello` with its protocol version and the server replies
+//!    with a `HelloAck` containing the version it'll use, possibly lower. The
+//!    connection is closed with `CloseReason::HandshakeRequired` if the first
+//!    message isn't a `Hello`, or `CloseReason::UnsupportedVersion` if the
+//!    version isn't supported. If both sides agree to compression, server
+//!    messages are sent as binary frames with a one-byte marker,
+//!    zstd-compressed when large (see `net::compression`).
+//! 4. Server spawns two tasks:
+//!    - Send task: Pushes game view updates every 1 second and pings the
+//!      client at the heartbeat interval
 //!    - Receive task: Processes incoming client commands
-//! 4. On disconnect, both tasks are cleaned up
+//! 5. On disconnect, both tasks are cleaned up. Clients that miss too many
+//!    consecutive pongs are disconnected with `CloseReason::HeartbeatTimeout`,
+//!    so a half-open connection doesn't stay subscribed to the table. The
+//!    connection is also closed with `CloseReason::AuthExpired` once the
+//!    access token expires, `CloseReason::Kicked` when an admin kicks the
+//!    user, and `CloseReason::TableClosed` when the table closes.
+//!
+//! Close codes for each `CloseReason` are in the 4000-4999 range reserved
+//! for applications, with a human-readable reason in the close frame.
 //!
 //! # Client Messages
 //!
@@ -20,23 +42,48 @@
 //! - Leave the table
 //! - Take actions (fold, check, call, raise, all-in)
 //! - Start/stop spectating
+//! - Send chat messages
+//! - Rabbit hunt the rest of the board after a hand ends early
+//! - Start the next hand (`{"type": "start_game"}`)
+//! - Request a full game view snapshot (`{"type": "resync"}`)
+//! - Answer a heartbeat ping (`{"type": "pong", "nonce": 1}`)
 //!
 //! # Server Messages
 //!
-//! Server sends two types of messages:
-//! - **Game View Updates**: Complete game state (automatic every ~1s)
+//! Server sends three types of messages:
+//! - **Game View Updates**: A full `snapshot` on connect and resync, then a
+//!   `delta` with only the changed fields whenever the table state changes.
+//!   Each update has the table's `seq` for the view, which never decreases.
+//!   A delta applies to the view with its `base_seq`; a client whose last
+//!   `seq` differs must send `resync` and wait for the next snapshot.
 //! - **Command Responses**: Success or error responses to client commands
+//! - **Chat Messages**: `ServerMessage::ChatMessage` for each message sent to the table
+//!   (protocol version 2 and later)
+//! - **Heartbeats**: `ServerMessage::Ping` with a nonce the client must echo
+//!   back in a `pong` (protocol version 2 and later)
 //!
 //! # Example
 //!
 //! ```javascript
 //! const ws = new WebSocket('ws://localhost:3000/ws/1?token=eyJhbGc...');
 //!
+//! ws.onopen = () => {
+//!   ws.send(JSON.stringify({ protocol_version: 2, client_version: "1.0.0" }));
+//! };
+//!
+//! let lastSeq = 0;
 //! ws.onmessage = (event) => {
 //!   const data = JSON.parse(event.data);
-//!   if (data.blinds) {
+//!   if (data.Ping) {
+//!     ws.send(JSON.stringify({ type: "pong", nonce: data.Ping.nonce }));
+//!   } else if (data.type === "snapshot" || data.type === "delta") {
 //!     // Game view update
-//!     updateGameUI(data);
+//!     if (data.type === "delta" && data.base_seq !== lastSeq) {
+//!       ws.send(JSON.stringify({ type: "resync" }));
+//!     } else {
+//!       lastSeq = data.seq;
+//!       updateGameUI(data);
+//!     }
 //!   } else {
 //!     // Command response
 //!     handleResponse(data);
@@ -53,15 +100,21 @@
 use axum::{
     extract::{
         Path, Query, State,
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
-    response::{IntoResponse, Response},
+    response::Response,
+};
+use futures_util::{
+    SinkExt, StreamExt,
+    stream::{SplitSink, SplitStream},
 };
-use futures_util::{SinkExt, StreamExt};
 use tracing::{error, info, warn};
-use private_poker::entities::Action;
+use private_poker::entities::{Action, GameView, GameViewUpdate};
+use private_poker::net::compression;
+use private_poker::net::messages::{CloseReason, Hello, HelloAck, ServerMessage};
+use private_poker::table::messages::StateChangeNotification;
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 
 use super::{AppState, rate_limiter::RateLimiter};
 
@@ -69,9 +122,19 @@ use super::{AppState, rate_limiter::RateLimiter};
 /// Prevents DoS attacks via large message payloads
 const MAX_MESSAGE_SIZE: usize = 65536;
 
+/// Time a client has to send its `Hello` after connecting
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Deserialize)]
 pub struct WsQuery {
     token: String,
+    /// Replay game events missed since the last connection dropped
+    #[serde(default)]
+    resume: bool,
+    /// Sequence number of the last game view the client has, to catch up
+    /// with deltas from instead of a full snapshot
+    #[serde(default)]
+    last_seq: Option<u64>,
 }
 
 /// Client messages received via WebSocket
@@ -98,6 +161,26 @@ enum ClientMessage {
     Spectate,
     /// Stop spectating the table
     StopSpectating,
+    /// Send a chat message to the table
+    Chat { message: String },
+    /// See the rest of the board after a hand ends early
+    RabbitHunt,
+    /// Start the next hand without waiting for the table to auto-start
+    StartGame,
+    /// Sit out of hands while keeping the seat
+    SitOut,
+    /// Sit back in after sitting out
+    SitIn,
+    /// Offer the players all-in to chop the pot by equity
+    OfferEquityChop,
+    /// Accept the equity chop on offer
+    AcceptEquityChop,
+    /// Decline the equity chop on offer, running out the board as usual
+    DeclineEquityChop,
+    /// Request a full game view snapshot, e.g., after missing a delta
+    Resync,
+    /// Answer a heartbeat ping
+    Pong { nonce: u64 },
 }
 
 /// Action data from client
@@ -119,6 +202,126 @@ enum ServerResponse {
     Error { message: String },
 }
 
+/// Game views sent on a connection, used to send deltas instead of full
+/// views.
+#[derive(Debug, Default)]
+struct GameViewStream {
+    /// Last view sent to the client, or `None` if the next update must be
+    /// a full snapshot
+    last_view: Option<GameView>,
+}
+
+impl GameViewStream {
+    /// Return the next update for `view`, or `None` if the view is the
+    /// last one sent.
+    fn next_update(&mut self, view: GameView) -> Option<GameViewUpdate> {
+        let update = match &self.last_view {
+            Some(last_view) => {
+                let delta = view.diff(last_view);
+                // Deltas are still sent when only the sequence number
+                // moved, so the client's sequence number stays one the
+                // table can replay from
+                if delta.is_empty() && view.sequence == last_view.sequence {
+                    return None;
+                }
+                GameViewUpdate::Delta {
+                    seq: view.sequence,
+                    base_seq: last_view.sequence,
+                    delta: Box::new(delta),
+                }
+            }
+            None => GameViewUpdate::Snapshot {
+                seq: view.sequence,
+                view: view.clone(),
+            },
+        };
+        self.last_view = Some(view);
+        Some(update)
+    }
+
+    /// Pick up where a resuming client left off. `views` starts with the
+    /// last view the client has, followed by the ones it missed. Returns
+    /// the deltas that catch the client up; with no views, the next update
+    /// is a full snapshot.
+    fn resume(&mut self, views: Vec<GameView>) -> Vec<GameViewUpdate> {
+        let mut views = views.into_iter();
+        self.last_view = views.next();
+        views.filter_map(|view| self.next_update(view)).collect()
+    }
+}
+
+/// Heartbeat settings for WebSocket connections
+#[derive(Debug, Clone, Copy)]
+pub struct HeartbeatConfig {
+    /// Time between pings
+    pub interval: Duration,
+    /// Consecutive unanswered pings before the client is disconnected
+    pub max_missed_pongs: u32,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(15),
+            max_missed_pongs: 3,
+        }
+    }
+}
+
+/// Liveness of a connection, tracked from the client's pongs.
+#[derive(Debug)]
+struct Heartbeat {
+    /// Consecutive unanswered pings before the client is disconnected
+    max_missed_pongs: u32,
+    /// Nonce of the last ping sent
+    nonce: u64,
+    /// When the last ping was sent, used to measure round-trip time
+    last_ping_at: Option<Instant>,
+    /// Whether the last ping is still waiting for its pong
+    awaiting_pong: bool,
+    /// Consecutive pings that went unanswered
+    missed_pongs: u32,
+}
+
+impl Heartbeat {
+    fn new(max_missed_pongs: u32) -> Self {
+        Self {
+            max_missed_pongs,
+            nonce: 0,
+            last_ping_at: None,
+            awaiting_pong: false,
+            missed_pongs: 0,
+        }
+    }
+
+    /// Return the nonce for the next ping, or `None` if the client missed
+    /// too many pongs and should be disconnected.
+    fn next_ping(&mut self, now: Instant) -> Option<u64> {
+        if self.awaiting_pong {
+            self.missed_pongs += 1;
+        }
+        if self.missed_pongs >= self.max_missed_pongs {
+            return None;
+        }
+        self.nonce += 1;
+        self.last_ping_at = Some(now);
+        self.awaiting_pong = true;
+        Some(self.nonce)
+    }
+
+    /// Record a pong, returning the round-trip time if it answers the last
+    /// ping. Pongs for older pings are ignored.
+    fn pong(&mut self, nonce: u64, now: Instant) -> Option<Duration> {
+        if !self.awaiting_pong || nonce != self.nonce {
+            return None;
+        }
+        self.awaiting_pong = false;
+        self.missed_pongs = 0;
+        self.last_ping_at
+            .map(|last_ping_at| now.saturating_duration_since(last_ping_at))
+    }
+}
+
 /// Upgrade HTTP connection to WebSocket for real-time table communication.
 ///
 /// Validates the JWT access token and establishes a WebSocket connection to the specified table.
@@ -134,8 +337,10 @@ enum ServerResponse {
 ///
 /// # Response
 ///
-/// On success, upgrades connection to WebSocket protocol (101 Switching Protocols).
-/// On authentication failure, returns `401 Unauthorized`.
+/// Upgrades connection to WebSocket protocol (101 Switching Protocols). On
+/// authentication failure, the connection is closed right away with
+/// `CloseReason::AuthExpired` or `CloseReason::AuthInvalid`, since browsers
+/// don't expose the HTTP status of a failed upgrade.
 ///
 /// # WebSocket Lifecycle
 ///
@@ -156,14 +361,14 @@ pub async fn websocket_handler(
     State(state): State<AppState>,
 ) -> Response {
     // Verify token
-    let user_id = match state.auth_manager.verify_access_token(&query.token) {
+    let claims = match state.auth_manager.verify_access_token(&query.token) {
         Ok(claims) => {
             tracing::debug!(
                 user_id = claims.sub,
                 table_id = table_id,
                 "WebSocket authentication successful"
             );
-            claims.sub
+            claims
         }
         Err(e) => {
             tracing::warn!(
@@ -171,9 +376,18 @@ pub async fn websocket_handler(
                 error = %e,
                 "WebSocket authentication failed: invalid token"
             );
-            return (StatusCode::UNAUTHORIZED, "Invalid token").into_response();
+            let reason = if e.is_token_expired() {
+                CloseReason::AuthExpired
+            } else {
+                CloseReason::AuthInvalid
+            };
+            return ws.on_upgrade(move |socket| async move {
+                let (mut sender, _) = socket.split();
+                close_with(&mut sender, reason, None).await;
+            });
         }
     };
+    let user_id = claims.sub;
 
     tracing::info!(
         user_id = user_id,
@@ -181,7 +395,13 @@ pub async fn websocket_handler(
         "WebSocket connection established"
     );
 
-    ws.on_upgrade(move |socket| handle_socket(socket, table_id, user_id, state))
+    let resume = query.resume;
+    let last_seq = query.last_seq;
+    ws.on_upgrade(move |socket| {
+        handle_socket(
+            socket, table_id, user_id, claims.exp, resume, last_seq, state,
+        )
+    })
 }
 
 /// Handle an established WebSocket connection.
@@ -200,12 +420,30 @@ pub async fn websocket_handler(
 /// - `socket`: The WebSocket connection
 /// - `table_id`: Table the user is connected to
 /// - `user_id`: Authenticated user ID
+/// - `token_expires_at`: When the access token expires, as a Unix timestamp
+/// - `resume`: Whether to replay game events missed since the last connection
+/// - `last_seq`: Sequence number of the last game view the client has, if any
 /// - `state`: Shared application state (table manager, auth manager, etc.)
-async fn handle_socket(socket: WebSocket, table_id: i64, user_id: i64, state: AppState) {
+async fn handle_socket(
+    socket: WebSocket,
+    table_id: i64,
+    user_id: i64,
+    token_expires_at: i64,
+    resume: bool,
+    last_seq: Option<u64>,
+    state: AppState,
+) {
     let (mut sender, mut receiver) = socket.split();
 
     info!("WebSocket connected: table={}, user={}", table_id, user_id);
 
+    // Agree on a protocol version before any game messages flow
+    let Some(ack) = perform_handshake(&mut sender, &mut receiver).await else {
+        return;
+    };
+    let protocol_version = ack.accepted_version;
+    let compression = ack.compression;
+
     // Create rate limiters for DoS protection
     let mut burst_limiter = RateLimiter::burst(); // 10 messages per second
     let mut sustained_limiter = RateLimiter::sustained(); // 100 messages per minute
@@ -213,6 +451,12 @@ async fn handle_socket(socket: WebSocket, table_id: i64, user_id: i64, state: Ap
     // Create channel for sending responses from message handler
     let (response_tx, mut response_rx) = tokio::sync::mpsc::channel::<String>(32);
 
+    // Create channel for resync requests, answered by the send task with a full snapshot
+    let (resync_tx, mut resync_rx) = tokio::sync::mpsc::channel::<()>(4);
+
+    // Create channel for pong nonces, checked by the send task against its pings
+    let (pong_tx, mut pong_rx) = tokio::sync::mpsc::channel::<u64>(4);
+
     // Subscribe to table state change notifications
     let (notification_tx, mut notification_rx) =
         tokio::sync::mpsc::channel::<private_poker::table::messages::StateChangeNotification>(32);
@@ -222,6 +466,12 @@ async fn handle_socket(socket: WebSocket, table_id: i64, user_id: i64, state: Ap
         Some(h) => h,
         None => {
             error!("Table {} not found", table_id);
+            close_with(
+                &mut sender,
+                CloseReason::TableClosed,
+                Some("table not found"),
+            )
+            .await;
             return;
         }
     };
@@ -230,78 +480,342 @@ async fn handle_socket(socket: WebSocket, table_id: i64, user_id: i64, state: Ap
         .send(private_poker::table::messages::TableMessage::Subscribe {
             user_id,
             sender: notification_tx,
+            resume,
+            last_seq,
         })
         .await
         .is_err()
     {
         error!("Failed to subscribe to table {} notifications", table_id);
+        close_with(&mut sender, CloseReason::TableClosed, None).await;
         return;
     }
 
     // Spawn task to send table updates and responses (event-driven)
     let send_state = state.clone();
-    let send_task = tokio::spawn(async move {
+    let heartbeat_config = state.heartbeat;
+    let mut send_task = tokio::spawn(async move {
+        let mut view_stream = GameViewStream::default();
+        let mut heartbeat = Heartbeat::new(heartbeat_config.max_missed_pongs);
+        let mut ping_interval = tokio::time::interval_at(
+            tokio::time::Instant::now() + heartbeat_config.interval,
+            heartbeat_config.interval,
+        );
+        // Older clients don't understand pings, so they aren't checked
+        let heartbeat_supported =
+            ServerMessage::Ping { nonce: 0 }.protocol_version() <= protocol_version;
+        let token_expiry = tokio::time::sleep(time_until(token_expires_at));
+        tokio::pin!(token_expiry);
+
+        // Send a full snapshot on connect so the client has a view to apply
+        // deltas to. A client with a view of its own is caught up once the
+        // table says which views it missed.
+        if last_seq.is_none()
+            && !send_game_view(
+                &mut sender,
+                &send_state,
+                table_id,
+                user_id,
+                &mut view_stream,
+                compression,
+            )
+            .await
+        {
+            return;
+        }
+
         loop {
             tokio::select! {
                 // Receive state change notification from table actor
-                Some(_notification) = notification_rx.recv() => {
-                    // Get updated game view for this user
-                    let table_handle = match send_state.table_manager.get_table(table_id).await {
-                        Some(h) => h,
-                        None => {
-                            error!("Table {} not found", table_id);
-                            break;
-                        }
+                notification = notification_rx.recv() => {
+                    // The table actor dropped its subscribers, so it's gone
+                    let Some(notification) = notification else {
+                        info!("Table {} closed, disconnecting user {}", table_id, user_id);
+                        close_with(&mut sender, CloseReason::TableClosed, None).await;
+                        break;
                     };
 
-                    let (tx, rx) = tokio::sync::oneshot::channel();
-                    if table_handle
-                        .send(private_poker::table::messages::TableMessage::GetGameView {
-                            user_id,
-                            response: tx,
-                        })
-                        .await
-                        .is_err()
-                    {
-                        error!("Failed to send GetGameView message");
+                    if matches!(notification, StateChangeNotification::Kicked) {
+                        info!("User {} kicked from table {}", user_id, table_id);
+                        close_with(&mut sender, CloseReason::Kicked, None).await;
                         break;
                     }
 
-                    match rx.await {
-                        Ok(Some(game_view)) => {
-                            let json = match serde_json::to_string(&game_view) {
+                    // Chat messages are forwarded as-is rather than triggering a view refresh
+                    if let StateChangeNotification::ChatMessage(chat) = notification {
+                        let chat_message = ServerMessage::ChatMessage {
+                            table_id: chat.table_id,
+                            user_id: chat.user_id,
+                            username: chat.username,
+                            message: chat.message,
+                            timestamp: chat.timestamp,
+                        };
+                        // Older clients don't understand chat messages
+                        if chat_message.protocol_version() > protocol_version {
+                            continue;
+                        }
+                        let json = match serde_json::to_string(&chat_message) {
+                            Ok(j) => j,
+                            Err(e) => {
+                                error!("Failed to serialize chat message: {}", e);
+                                continue;
+                            }
+                        };
+
+                        if sender.send(outgoing_message(json, compression)).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // Game events narrate the hand, arriving before the view
+                    // update that shows them
+                    if let StateChangeNotification::GameEvent(event) = notification {
+                        let game_event = ServerMessage::TableGameEvent { table_id, event };
+                        if game_event.protocol_version() > protocol_version {
+                            continue;
+                        }
+                        let json = match serde_json::to_string(&game_event) {
+                            Ok(j) => j,
+                            Err(e) => {
+                                error!("Failed to serialize game event: {}", e);
+                                continue;
+                            }
+                        };
+
+                        if sender.send(outgoing_message(json, compression)).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // Views missed while reconnecting are sent as deltas
+                    // from the client's last view, falling back to a
+                    // snapshot if the table no longer has it
+                    if let StateChangeNotification::MissedViews { views } = notification {
+                        let mut sent = true;
+                        for update in view_stream.resume(views) {
+                            let json = match serde_json::to_string(&update) {
                                 Ok(j) => j,
                                 Err(e) => {
                                     error!("Failed to serialize game view: {}", e);
                                     continue;
                                 }
                             };
-
-                            if sender.send(Message::Text(json.into())).await.is_err() {
+                            if sender.send(outgoing_message(json, compression)).await.is_err() {
+                                sent = false;
                                 break;
                             }
                         }
-                        Ok(None) => {
-                            // User doesn't have a view yet (not joined)
+                        if !sent
+                            || !send_game_view(
+                                &mut sender,
+                                &send_state,
+                                table_id,
+                                user_id,
+                                &mut view_stream,
+                                compression,
+                            )
+                            .await
+                        {
+                            break;
                         }
-                        Err(e) => {
-                            error!("Failed to receive game view: {}", e);
+                        continue;
+                    }
+
+                    // Events missed while reconnecting are replayed after the snapshot
+                    if let StateChangeNotification::MissedEvents { events, complete } =
+                        notification
+                    {
+                        let missed_events = ServerMessage::MissedEvents {
+                            table_id,
+                            events,
+                            complete,
+                        };
+                        if missed_events.protocol_version() > protocol_version {
+                            continue;
+                        }
+                        let json = match serde_json::to_string(&missed_events) {
+                            Ok(j) => j,
+                            Err(e) => {
+                                error!("Failed to serialize missed events: {}", e);
+                                continue;
+                            }
+                        };
+
+                        if sender.send(outgoing_message(json, compression)).await.is_err() {
                             break;
                         }
+                        continue;
+                    }
+
+                    // Let clients know the table is waiting for players or
+                    // counting down to start a hand
+                    let status_message = match &notification {
+                        StateChangeNotification::WaitingForPlayers { waiting: true } => {
+                            Some("waiting for players".to_string())
+                        }
+                        StateChangeNotification::WaitingForPlayers { waiting: false } => {
+                            Some("dealing resumed".to_string())
+                        }
+                        StateChangeNotification::AutoStartCountdown {
+                            starts_in_secs: Some(secs),
+                        } => Some(format!("starting in {secs}s")),
+                        StateChangeNotification::AutoStartCountdown {
+                            starts_in_secs: None,
+                        } => Some("start cancelled, waiting for players".to_string()),
+                        _ => None,
+                    };
+                    if let Some(message) = status_message {
+                        let status = ServerMessage::TableStatus { table_id, message };
+                        if status.protocol_version() > protocol_version {
+                            continue;
+                        }
+                        let json = match serde_json::to_string(&status) {
+                            Ok(j) => j,
+                            Err(e) => {
+                                error!("Failed to serialize table status: {}", e);
+                                continue;
+                            }
+                        };
+
+                        if sender.send(outgoing_message(json, compression)).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // Equity chop offers are forwarded as-is so players can
+                    // answer them
+                    if let StateChangeNotification::EquityChopOffered { equities } = notification {
+                        let offer = ServerMessage::EquityChopOffer { table_id, equities };
+                        if offer.protocol_version() > protocol_version {
+                            continue;
+                        }
+                        let json = match serde_json::to_string(&offer) {
+                            Ok(j) => j,
+                            Err(e) => {
+                                error!("Failed to serialize equity chop offer: {}", e);
+                                continue;
+                            }
+                        };
+
+                        if sender.send(outgoing_message(json, compression)).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    // Let a waitlisted user know they've been seated before
+                    // sending the view that includes them
+                    if let StateChangeNotification::SeatedFromWaitlist { user_id: seated } =
+                        notification
+                        && seated == user_id
+                    {
+                        let joined = ServerMessage::JoinedTable { table_id };
+                        let json = match serde_json::to_string(&joined) {
+                            Ok(j) => j,
+                            Err(e) => {
+                                error!("Failed to serialize joined table message: {}", e);
+                                continue;
+                            }
+                        };
+
+                        if sender.send(outgoing_message(json, compression)).await.is_err() {
+                            break;
+                        }
+                    }
+
+                    // Notifications were dropped while the client was
+                    // behind, so it gets a full snapshot instead of a delta
+                    if matches!(notification, StateChangeNotification::Resync) {
+                        view_stream.last_view = None;
+                    }
+
+                    if !send_game_view(
+                        &mut sender,
+                        &send_state,
+                        table_id,
+                        user_id,
+                        &mut view_stream,
+                        compression,
+                    )
+                    .await
+                    {
+                        break;
+                    }
+                }
+                Some(()) = resync_rx.recv() => {
+                    // Forget the last view so the next update is a full snapshot
+                    view_stream.last_view = None;
+                    if !send_game_view(
+                        &mut sender,
+                        &send_state,
+                        table_id,
+                        user_id,
+                        &mut view_stream,
+                        compression,
+                    )
+                    .await
+                    {
+                        break;
                     }
                 }
                 Some(response_json) = response_rx.recv() => {
                     // Send response from message handler
-                    if sender.send(Message::Text(response_json.into())).await.is_err() {
+                    let message = outgoing_message(response_json, compression);
+                    if sender.send(message).await.is_err() {
                         break;
                     }
                 }
+                _ = ping_interval.tick(), if heartbeat_supported => {
+                    let Some(nonce) = heartbeat.next_ping(Instant::now()) else {
+                        warn!(
+                            "WebSocket heartbeat timed out: table={}, user={}",
+                            table_id, user_id
+                        );
+                        close_with(&mut sender, CloseReason::HeartbeatTimeout, None).await;
+                        break;
+                    };
+                    let Ok(json) = serde_json::to_string(&ServerMessage::Ping { nonce }) else {
+                        continue;
+                    };
+                    if sender.send(outgoing_message(json, compression)).await.is_err() {
+                        break;
+                    }
+                }
+                () = &mut token_expiry => {
+                    info!(
+                        "WebSocket access token expired: table={}, user={}",
+                        table_id, user_id
+                    );
+                    close_with(&mut sender, CloseReason::AuthExpired, None).await;
+                    break;
+                }
+                Some(nonce) = pong_rx.recv() => {
+                    if let Some(rtt) = heartbeat.pong(nonce, Instant::now()) {
+                        tracing::debug!(
+                            user_id = user_id,
+                            table_id = table_id,
+                            rtt_ms = rtt.as_millis() as u64,
+                            "WebSocket heartbeat pong received"
+                        );
+                    }
+                }
             }
         }
     });
 
-    // Receive messages from client
-    while let Some(msg) = receiver.next().await {
+    // Receive messages from client until the connection closes or the send
+    // task stops, e.g., after a heartbeat timeout
+    loop {
+        let msg = tokio::select! {
+            msg = receiver.next() => msg,
+            _ = &mut send_task => break,
+        };
+        let Some(msg) = msg else {
+            break;
+        };
         match msg {
             Ok(Message::Text(text)) => {
                 // Check rate limits FIRST (before any processing)
@@ -371,6 +885,20 @@ async fn handle_socket(socket: WebSocket, table_id: i64, user_id: i64, state: Ap
 
                 // Now safe to parse message
                 let response = match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(ClientMessage::Resync) => {
+                        // The snapshot is the reply, so there's no response to send
+                        if resync_tx.send(()).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                    Ok(ClientMessage::Pong { nonce }) => {
+                        // Pongs are checked by the send task, which sent the pings
+                        if pong_tx.send(nonce).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
                     Ok(client_msg) => {
                         handle_client_message(client_msg, table_id, user_id, &state).await
                     }
@@ -457,6 +985,143 @@ async fn handle_socket(socket: WebSocket, table_id: i64, user_id: i64, state: Ap
     );
 }
 
+/// Wait for the client's `Hello` and reply with a `HelloAck`.
+///
+/// Returns the accepted `HelloAck`, or `None` after closing the connection
+/// with a close code explaining why the handshake failed.
+async fn perform_handshake(
+    sender: &mut SplitSink<WebSocket, Message>,
+    receiver: &mut SplitStream<WebSocket>,
+) -> Option<HelloAck> {
+    let hello = match tokio::time::timeout(HANDSHAKE_TIMEOUT, receiver.next()).await {
+        Ok(Some(Ok(Message::Text(text)))) => serde_json::from_str::<Hello>(&text).ok(),
+        _ => None,
+    };
+    let Some(hello) = hello else {
+        warn!("WebSocket client didn't start with a hello handshake");
+        close_with(sender, CloseReason::HandshakeRequired, None).await;
+        return None;
+    };
+
+    match hello.negotiate(env!("CARGO_PKG_VERSION")) {
+        Ok(ack) => {
+            info!(
+                "WebSocket handshake complete: client={}, protocol={:?}",
+                hello.client_version, ack.accepted_version
+            );
+            let json = serde_json::to_string(&ack).ok()?;
+            sender.send(Message::Text(json.into())).await.ok()?;
+            Some(ack)
+        }
+        Err(e) => {
+            warn!("WebSocket handshake failed: {}", e);
+            let detail = e.to_string();
+            close_with(sender, CloseReason::UnsupportedVersion, Some(&detail)).await;
+            None
+        }
+    }
+}
+
+/// Wrap a serialized message for sending. With compression negotiated,
+/// messages are sent as binary frames from `net::compression` so large
+/// ones can be compressed.
+fn outgoing_message(json: String, compression: bool) -> Message {
+    if compression {
+        match compression::compress(json.as_bytes()) {
+            Ok(frame) => return Message::Binary(frame.into()),
+            Err(e) => warn!("Failed to compress message: {}", e),
+        }
+    }
+    Message::Text(json.into())
+}
+
+/// Build the close frame for a close reason. The frame's reason is the
+/// `detail` if there is one, or else the reason's description.
+fn close_frame(reason: CloseReason, detail: Option<&str>) -> CloseFrame {
+    let text = detail.map_or_else(|| reason.to_string(), str::to_string);
+    CloseFrame {
+        code: reason.code(),
+        reason: text.into(),
+    }
+}
+
+/// Close the connection with the close code and reason for `reason`.
+async fn close_with(
+    sender: &mut SplitSink<WebSocket, Message>,
+    reason: CloseReason,
+    detail: Option<&str>,
+) {
+    let frame = close_frame(reason, detail);
+    let _ = sender.send(Message::Close(Some(frame))).await;
+}
+
+/// Time left until a Unix timestamp, or zero if it's passed.
+fn time_until(timestamp: i64) -> Duration {
+    let secs = timestamp.saturating_sub(chrono::Utc::now().timestamp());
+    Duration::from_secs(u64::try_from(secs).unwrap_or(0))
+}
+
+/// Fetch the user's game view and send it as a snapshot or delta.
+///
+/// Returns `false` if the connection should be closed.
+async fn send_game_view(
+    sender: &mut SplitSink<WebSocket, Message>,
+    state: &AppState,
+    table_id: i64,
+    user_id: i64,
+    view_stream: &mut GameViewStream,
+    compression: bool,
+) -> bool {
+    let table_handle = match state.table_manager.get_table(table_id).await {
+        Some(h) => h,
+        None => {
+            error!("Table {} not found", table_id);
+            return false;
+        }
+    };
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    if table_handle
+        .send(private_poker::table::messages::TableMessage::GetGameView {
+            user_id,
+            response: tx,
+        })
+        .await
+        .is_err()
+    {
+        error!("Failed to send GetGameView message");
+        return false;
+    }
+
+    match rx.await {
+        Ok(Some(game_view)) => {
+            let Some(update) = view_stream.next_update(game_view) else {
+                return true;
+            };
+            let json = match serde_json::to_string(&update) {
+                Ok(j) => j,
+                Err(e) => {
+                    error!("Failed to serialize game view: {}", e);
+                    return true;
+                }
+            };
+
+            sender
+                .send(outgoing_message(json, compression))
+                .await
+                .is_ok()
+        }
+        Ok(None) => {
+            // User doesn't have a view yet (not joined)
+            true
+        }
+        Err(e) => {
+            error!("Failed to receive game view: {}", e);
+            false
+        }
+    }
+}
+
 /// Process a client command message and return a response.
 ///
 /// Parses the client's command and forwards it to the appropriate table actor via
@@ -465,7 +1130,7 @@ async fn handle_socket(socket: WebSocket, table_id: i64, user_id: i64, state: Ap
 ///
 /// # Arguments
 ///
-/// - `msg`: Parsed client message (Join, Leave, Action, Spectate, StopSpectating)
+/// - `msg`: Parsed client message (Join, Leave, Action, Spectate, StopSpectating, Chat)
 /// - `table_id`: Table ID the command applies to
 /// - `user_id`: User ID making the request
 /// - `state`: Application state with table manager
@@ -482,6 +1147,12 @@ async fn handle_socket(socket: WebSocket, table_id: i64, user_id: i64, state: Ap
 /// - **Action**: Take poker action (fold, check, call, raise, all-in)
 /// - **Spectate**: Start spectating the table
 /// - **StopSpectating**: Stop spectating
+/// - **Chat**: Send a chat message to the table
+/// - **SitOut**/**SitIn**: Stop or resume being dealt into hands
+/// - **Resync**: Handled by the receive loop, which has the send task
+///   reply with a full game view snapshot
+/// - **Pong**: Handled by the receive loop, which passes it to the send
+///   task's heartbeat
 async fn handle_client_message(
     msg: ClientMessage,
     table_id: i64,
@@ -564,6 +1235,9 @@ async fn handle_client_message(
                     message: "Action processed successfully".to_string(),
                 },
                 Ok(TableResponse::Error(e)) => ServerResponse::Error { message: e },
+                Ok(response @ TableResponse::RaiseBelowMinimum { .. }) => ServerResponse::Error {
+                    message: response.error_message().unwrap_or_default(),
+                },
                 _ => ServerResponse::Error {
                     message: "Unexpected response".to_string(),
                 },
@@ -593,6 +1267,9 @@ async fn handle_client_message(
                     message: "Now spectating".to_string(),
                 },
                 Ok(TableResponse::Error(e)) => ServerResponse::Error { message: e },
+                Ok(TableResponse::SpectatorLimitReached) => ServerResponse::Error {
+                    message: "Spectator limit reached".to_string(),
+                },
                 _ => ServerResponse::Error {
                     message: "Unexpected response".to_string(),
                 },
@@ -625,5 +1302,352 @@ async fn handle_client_message(
                 },
             }
         }
+
+        ClientMessage::Chat { message } => {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+
+            if table_handle
+                .send(TableMessage::SendChat {
+                    user_id,
+                    message,
+                    response: tx,
+                })
+                .await
+                .is_err()
+            {
+                return ServerResponse::Error {
+                    message: "Failed to send chat message".to_string(),
+                };
+            }
+
+            match rx.await {
+                Ok(TableResponse::Success) => ServerResponse::Success {
+                    message: "Chat message sent".to_string(),
+                },
+                Ok(response) => ServerResponse::Error {
+                    message: response
+                        .error_message()
+                        .unwrap_or_else(|| "Unexpected response".to_string()),
+                },
+                Err(_) => ServerResponse::Error {
+                    message: "Unexpected response".to_string(),
+                },
+            }
+        }
+
+        ClientMessage::RabbitHunt => {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+
+            if table_handle
+                .send(TableMessage::RabbitHunt {
+                    user_id,
+                    response: tx,
+                })
+                .await
+                .is_err()
+            {
+                return ServerResponse::Error {
+                    message: "Failed to rabbit hunt".to_string(),
+                };
+            }
+
+            match rx.await {
+                Ok(Ok(cards)) => ServerResponse::Success {
+                    message: format!(
+                        "Rabbit hunt: {}",
+                        cards
+                            .iter()
+                            .map(|card| card.to_string())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    ),
+                },
+                Ok(Err(response)) => ServerResponse::Error {
+                    message: response
+                        .error_message()
+                        .unwrap_or_else(|| "Unexpected response".to_string()),
+                },
+                Err(_) => ServerResponse::Error {
+                    message: "Unexpected response".to_string(),
+                },
+            }
+        }
+
+        ClientMessage::Resync => {
+            // Resync requests are answered by the send task with a snapshot
+            ServerResponse::Success {
+                message: "Resync requested".to_string(),
+            }
+        }
+
+        ClientMessage::Pong { .. } => {
+            // Pongs are checked by the send task's heartbeat
+            ServerResponse::Success {
+                message: "Pong received".to_string(),
+            }
+        }
+
+        ClientMessage::OfferEquityChop => {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+
+            if table_handle
+                .send(TableMessage::OfferEquityChop { response: tx })
+                .await
+                .is_err()
+            {
+                return ServerResponse::Error {
+                    message: "Failed to offer equity chop".to_string(),
+                };
+            }
+
+            match rx.await {
+                Ok(Ok(equities)) => ServerResponse::Success {
+                    message: format!(
+                        "Equity chop offered: {}",
+                        equities
+                            .iter()
+                            .map(|(username, equity)| {
+                                format!("{} {:.1}%", username, 100.0 * equity)
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                },
+                Ok(Err(response)) => ServerResponse::Error {
+                    message: response
+                        .error_message()
+                        .unwrap_or_else(|| "Unexpected response".to_string()),
+                },
+                Err(_) => ServerResponse::Error {
+                    message: "Unexpected response".to_string(),
+                },
+            }
+        }
+
+        ClientMessage::AcceptEquityChop | ClientMessage::DeclineEquityChop => {
+            let accept = matches!(msg, ClientMessage::AcceptEquityChop);
+            let (tx, rx) = tokio::sync::oneshot::channel();
+
+            if table_handle
+                .send(TableMessage::RespondToEquityChop {
+                    user_id,
+                    accept,
+                    response: tx,
+                })
+                .await
+                .is_err()
+            {
+                return ServerResponse::Error {
+                    message: "Failed to answer equity chop".to_string(),
+                };
+            }
+
+            match rx.await {
+                Ok(TableResponse::Success) => ServerResponse::Success {
+                    message: if accept {
+                        "Equity chop accepted".to_string()
+                    } else {
+                        "Equity chop declined".to_string()
+                    },
+                },
+                Ok(response) => ServerResponse::Error {
+                    message: response
+                        .error_message()
+                        .unwrap_or_else(|| "Unexpected response".to_string()),
+                },
+                Err(_) => ServerResponse::Error {
+                    message: "Unexpected response".to_string(),
+                },
+            }
+        }
+
+        ClientMessage::StartGame => {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+
+            if table_handle
+                .send(TableMessage::StartGame {
+                    user_id,
+                    response: tx,
+                })
+                .await
+                .is_err()
+            {
+                return ServerResponse::Error {
+                    message: "Failed to start game".to_string(),
+                };
+            }
+
+            match rx.await {
+                Ok(TableResponse::Success) => ServerResponse::Success {
+                    message: "Game starting".to_string(),
+                },
+                Ok(response) => ServerResponse::Error {
+                    message: response
+                        .error_message()
+                        .unwrap_or_else(|| "Unexpected response".to_string()),
+                },
+                Err(_) => ServerResponse::Error {
+                    message: "Unexpected response".to_string(),
+                },
+            }
+        }
+
+        ClientMessage::SitOut | ClientMessage::SitIn => {
+            let sitting_out = matches!(msg, ClientMessage::SitOut);
+            let (tx, rx) = tokio::sync::oneshot::channel();
+
+            if table_handle
+                .send(TableMessage::SetSittingOut {
+                    user_id,
+                    sitting_out,
+                    response: tx,
+                })
+                .await
+                .is_err()
+            {
+                return ServerResponse::Error {
+                    message: "Failed to change sit out status".to_string(),
+                };
+            }
+
+            match rx.await {
+                Ok(TableResponse::SuccessWithMessage(message)) => {
+                    ServerResponse::Success { message }
+                }
+                Ok(response) => ServerResponse::Error {
+                    message: response
+                        .error_message()
+                        .unwrap_or_else(|| "Unexpected response".to_string()),
+                },
+                Err(_) => ServerResponse::Error {
+                    message: "Unexpected response".to_string(),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use private_poker::PokerState;
+    use private_poker::entities::Username;
+    use private_poker::game::{GameStateManagement, PhaseIndependentUserManagement};
+
+    fn game_view(players: &[&str], sequence: u64) -> GameView {
+        let mut state = PokerState::new();
+        for player in players {
+            state.new_user(&Username::new(player)).unwrap();
+        }
+        GameView {
+            sequence,
+            ..state.get_views().remove(&Username::new("alice")).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_view_stream_sends_table_sequence_numbers() {
+        let mut view_stream = GameViewStream::default();
+        let view = game_view(&["alice"], 3);
+        assert!(matches!(
+            view_stream.next_update(view.clone()),
+            Some(GameViewUpdate::Snapshot { seq: 3, .. })
+        ));
+        assert_eq!(view_stream.next_update(view.clone()), None);
+
+        // The sequence number moved without the view changing, so the
+        // client is still told about it
+        match view_stream.next_update(GameView {
+            sequence: 5,
+            ..view
+        }) {
+            Some(GameViewUpdate::Delta {
+                seq: 5,
+                base_seq: 3,
+                delta,
+            }) => assert!(delta.is_empty()),
+            other => panic!("expected a delta, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_view_stream_resumes_from_missed_views() {
+        let mut view_stream = GameViewStream::default();
+        let views = vec![
+            game_view(&["alice"], 3),
+            game_view(&["alice", "bob"], 4),
+            game_view(&["alice", "bob"], 6),
+        ];
+        let updates = view_stream.resume(views.clone());
+        let seqs: Vec<(u64, u64)> = updates
+            .iter()
+            .map(|update| match update {
+                GameViewUpdate::Delta { seq, base_seq, .. } => (*seq, *base_seq),
+                GameViewUpdate::Snapshot { .. } => panic!("expected deltas"),
+            })
+            .collect();
+        assert_eq!(seqs, vec![(4, 3), (6, 4)]);
+        assert_eq!(view_stream.last_view.as_ref(), views.last());
+
+        // Without the client's last view, the next update is a snapshot
+        assert!(view_stream.resume(Vec::new()).is_empty());
+        assert!(matches!(
+            view_stream.next_update(game_view(&["alice"], 7)),
+            Some(GameViewUpdate::Snapshot { seq: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn test_heartbeat_drops_client_that_stops_ponging() {
+        let mut heartbeat = Heartbeat::new(3);
+        let mut now = Instant::now();
+
+        // The client answers the first few pings
+        for _ in 0..3 {
+            let nonce = heartbeat.next_ping(now).unwrap();
+            now += Duration::from_millis(20);
+            assert!(heartbeat.pong(nonce, now).is_some());
+            now += Duration::from_secs(15);
+        }
+
+        // Then it stops ponging; it's dropped after 3 unanswered pings
+        for _ in 0..3 {
+            assert!(heartbeat.next_ping(now).is_some());
+            now += Duration::from_secs(15);
+        }
+        assert_eq!(heartbeat.next_ping(now), None);
+    }
+
+    #[test]
+    fn test_heartbeat_measures_rtt() {
+        let mut heartbeat = Heartbeat::new(3);
+        let sent_at = Instant::now();
+        let nonce = heartbeat.next_ping(sent_at).unwrap();
+        assert_eq!(heartbeat.last_ping_at, Some(sent_at));
+
+        // Pongs for the wrong ping don't count
+        let received_at = sent_at + Duration::from_millis(42);
+        assert_eq!(heartbeat.pong(nonce + 1, received_at), None);
+        assert_eq!(
+            heartbeat.pong(nonce, received_at),
+            Some(Duration::from_millis(42))
+        );
+        // Duplicate pongs are ignored
+        assert_eq!(heartbeat.pong(nonce, received_at), None);
+    }
+
+    #[test]
+    fn test_heartbeat_pong_resets_missed_count() {
+        let mut heartbeat = Heartbeat::new(2);
+        let now = Instant::now();
+        heartbeat.next_ping(now).unwrap();
+        let nonce = heartbeat.next_ping(now).unwrap();
+        assert_eq!(heartbeat.missed_pongs, 1);
+
+        // A late pong for an older ping doesn't count
+        assert_eq!(heartbeat.pong(nonce - 1, now), None);
+        assert!(heartbeat.pong(nonce, now).is_some());
+        assert_eq!(heartbeat.missed_pongs, 0);
+        assert!(heartbeat.next_ping(now).is_some());
     }
 }
@@ -16,8 +16,15 @@ pub struct ServerConfig {
     pub security: SecurityConfig,
     /// Table defaults configuration
     pub table_defaults: TableDefaultsConfig,
+    /// WebSocket configuration
+    pub websocket: WebSocketConfig,
     /// Number of tables to create on startup
     pub num_tables: usize,
+    /// Most tables that can be open at once before users can't create more
+    /// (0 for no limit)
+    pub max_open_tables: usize,
+    /// Seconds a table can sit empty before it's closed (0 never closes)
+    pub idle_table_timeout_secs: u64,
 }
 
 /// Security-related configuration
@@ -54,6 +61,15 @@ pub struct TableDefaultsConfig {
     pub bot_difficulty: BotDifficulty,
 }
 
+/// WebSocket connection configuration
+#[derive(Debug, Clone)]
+pub struct WebSocketConfig {
+    /// Seconds between heartbeat pings
+    pub ping_interval_secs: u64,
+    /// Consecutive unanswered pings before a client is disconnected
+    pub max_missed_pongs: u32,
+}
+
 impl ServerConfig {
     /// Load configuration from environment variables
     ///
@@ -144,6 +160,7 @@ impl ServerConfig {
                 "easy" => Some(BotDifficulty::Easy),
                 "standard" => Some(BotDifficulty::Standard),
                 "tag" => Some(BotDifficulty::Tag),
+                "gto" => Some(BotDifficulty::Gto),
                 _ => None,
             })
             .unwrap_or(BotDifficulty::Standard);
@@ -161,15 +178,26 @@ impl ServerConfig {
             bot_difficulty,
         };
 
+        // WebSocket heartbeat
+        let websocket = WebSocketConfig {
+            ping_interval_secs: parse_env_or("WS_PING_INTERVAL_SECS", 15),
+            max_missed_pongs: parse_env_or("WS_MAX_MISSED_PONGS", 3),
+        };
+
         // Number of tables
         let num_tables = num_tables_override.unwrap_or_else(|| parse_env_or("MAX_TABLES", 1));
+        let max_open_tables = parse_env_or("MAX_OPEN_TABLES", 100);
+        let idle_table_timeout_secs = parse_env_or("IDLE_TABLE_TIMEOUT_SECS", 1800);
 
         Ok(ServerConfig {
             bind,
             database,
             security,
             table_defaults,
+            websocket,
             num_tables,
+            max_open_tables,
+            idle_table_timeout_secs,
         })
     }
 
@@ -241,6 +269,21 @@ impl ServerConfig {
             });
         }
 
+        // Validate heartbeat
+        if self.websocket.ping_interval_secs == 0 {
+            return Err(ConfigError::Invalid {
+                var: "WS_PING_INTERVAL_SECS".to_string(),
+                reason: "Must be greater than 0".to_string(),
+            });
+        }
+
+        if self.websocket.max_missed_pongs == 0 {
+            return Err(ConfigError::Invalid {
+                var: "WS_MAX_MISSED_PONGS".to_string(),
+                reason: "Must be greater than 0".to_string(),
+            });
+        }
+
         Ok(())
     }
 }
@@ -309,7 +352,13 @@ mod tests {
                 target_bot_count: 6,
                 bot_difficulty: BotDifficulty::Standard,
             },
+            websocket: WebSocketConfig {
+                ping_interval_secs: 15,
+                max_missed_pongs: 3,
+            },
             num_tables: 1,
+            max_open_tables: 100,
+            idle_table_timeout_secs: 1800,
         };
 
         let err = config.validate().unwrap_err();
@@ -344,7 +393,13 @@ mod tests {
                 target_bot_count: 6,
                 bot_difficulty: BotDifficulty::Standard,
             },
+            websocket: WebSocketConfig {
+                ping_interval_secs: 15,
+                max_missed_pongs: 3,
+            },
             num_tables: 1,
+            max_open_tables: 100,
+            idle_table_timeout_secs: 1800,
         };
 
         let err = config.validate().unwrap_err();
@@ -10,14 +10,17 @@ mod metrics;
 
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Error;
-use ctrlc::set_handler;
 use pico_args::Arguments;
 use private_poker::{
     auth::AuthManager,
     db::Database,
+    entities::{BettingStructure, GameVariant},
+    security::AuditLog,
     table::{TableConfig, TableManager, TableSpeed},
+    tournament::TournamentManager,
     wallet::WalletManager,
 };
 
@@ -45,6 +48,10 @@ ENVIRONMENT:
   (See .env file for all configuration options)
 ";
 
+/// Longest time shutdown waits for hands in progress to finish before
+/// force-settling their tables.
+const TABLE_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
 struct Args {
     bind: SocketAddr,
     database_url: String,
@@ -85,9 +92,6 @@ async fn main() -> Result<(), Error> {
         }),
     };
 
-    // Catching signals for exit.
-    set_handler(|| std::process::exit(0))?;
-
     // Initialize structured logging
     logging::init();
 
@@ -136,7 +140,11 @@ async fn main() -> Result<(), Error> {
     // Create managers
     let pool = Arc::new(db.pool().clone());
     let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
-    let table_manager = Arc::new(TableManager::new(pool.clone(), wallet_manager.clone()));
+    let mut table_manager = TableManager::new(pool.clone(), wallet_manager.clone());
+    if config.max_open_tables > 0 {
+        table_manager = table_manager.with_max_tables(config.max_open_tables);
+    }
+    let table_manager = Arc::new(table_manager);
 
     let auth_manager = Arc::new(AuthManager::new(
         pool.clone(),
@@ -162,16 +170,34 @@ async fn main() -> Result<(), Error> {
         let table_config = TableConfig {
             name: format!("Table {}", i + 1),
             max_players: config.table_defaults.max_players,
+            max_spectators: 50,
             small_blind: config.table_defaults.small_blind,
             big_blind: config.table_defaults.big_blind,
+            ante: 0,
             min_buy_in_bb: config.table_defaults.min_buy_in_bb,
             max_buy_in_bb: config.table_defaults.max_buy_in_bb,
             absolute_chip_cap: config.table_defaults.absolute_chip_cap,
             top_up_cooldown_hands: config.table_defaults.top_up_cooldown_hands,
+            match_biggest_stack: false,
+            max_sit_out_hands: 10,
+            auto_start_min_players: 0,
             speed: TableSpeed::Normal,
+            time_bank_secs: 0,
+            allin_runout_delay_ms: 0,
             bots_enabled: config.table_defaults.bots_enabled,
             target_bot_count: config.table_defaults.target_bot_count,
             bot_difficulty: config.table_defaults.bot_difficulty,
+            straddle_allowed: false,
+            run_it_twice_enabled: false,
+            rabbit_hunt_enabled: false,
+            variant: GameVariant::TexasHoldem,
+            betting_structure: BettingStructure::NoLimit,
+            rake_bps: 0,
+            rake_cap_chips: 0,
+            require_verified_email: false,
+            blind_schedule: None,
+            deck_style: None,
+            persistent: true,
             is_private: false,
             passphrase_hash: None,
             invite_token: None,
@@ -234,12 +260,86 @@ async fn main() -> Result<(), Error> {
 
     tracing::info!("Background session cleanup task started (runs every hour)");
 
+    // Spawn background task for escrow reconciliation
+    // Only reports stranded chips; returning them is left to an operator
+    let reconcile_table_manager = table_manager.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600)); // 1 hour
+        loop {
+            interval.tick().await;
+            match reconcile_table_manager.reconcile_escrow(false).await {
+                Ok(report) => {
+                    for discrepancy in &report.discrepancies {
+                        match discrepancy.table_chips {
+                            Some(chips) => tracing::warn!(
+                                "Table {}: escrow holds {} chips but {} are seated",
+                                discrepancy.table_id,
+                                discrepancy.escrow_balance,
+                                chips
+                            ),
+                            None => tracing::warn!(
+                                "Table {}: {} chips stranded in escrow with no active table",
+                                discrepancy.table_id,
+                                discrepancy.escrow_balance
+                            ),
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Escrow reconciliation failed: {}", e);
+                }
+            }
+        }
+    });
+
+    tracing::info!("Background escrow reconciliation task started (runs every hour)");
+
+    // Spawn background task for tournament clocks
+    // Moves running tournaments' tables up their blind levels together
+    let tournament_manager = TournamentManager::new(pool.clone());
+    let clock_table_manager = table_manager.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            if let Err(e) = tournament_manager.update_clocks(&clock_table_manager).await {
+                tracing::error!("Tournament clock update failed: {}", e);
+            }
+        }
+    });
+
+    tracing::info!("Background tournament clock task started (runs every second)");
+
+    // Spawn background task for idle table shutdown
+    // Empty tables that weren't created as persistent are closed
+    if config.idle_table_timeout_secs > 0 {
+        let idle_timeout = Duration::from_secs(config.idle_table_timeout_secs);
+        let idle_table_manager = table_manager.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                let closed = idle_table_manager.close_idle_tables(idle_timeout).await;
+                if !closed.is_empty() {
+                    tracing::info!("Closed {} idle table(s)", closed.len());
+                }
+            }
+        });
+
+        tracing::info!("Background idle table task started (runs every minute)");
+    }
+
     // Create API state
     let api_state = api::AppState {
         auth_manager,
-        table_manager,
+        table_manager: table_manager.clone(),
         wallet_manager,
         pool: pool.clone(),
+        audit_log: Arc::new(AuditLog::new(pool.clone())),
+        heartbeat: api::websocket::HeartbeatConfig {
+            interval: std::time::Duration::from_secs(config.websocket.ping_interval_secs),
+            max_missed_pongs: config.websocket.max_missed_pongs,
+        },
     };
 
     // Create router
@@ -256,19 +356,48 @@ async fn main() -> Result<(), Error> {
         config.bind
     );
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .map_err(|e| anyhow::anyhow!("Server error: {}", e))?;
+    // Connection info lets handlers see client IP addresses for the audit log
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await
+    .map_err(|e| anyhow::anyhow!("Server error: {}", e))?;
 
     tracing::info!("Shutting down server...");
 
+    // Let hands in progress finish so no chips are left in escrow
+    tracing::info!(
+        "Draining tables (waiting up to {:?} for hands in progress)...",
+        TABLE_DRAIN_TIMEOUT
+    );
+    table_manager.drain_all(TABLE_DRAIN_TIMEOUT).await;
+    tracing::info!("All tables drained");
+
     Ok(())
 }
 
-/// Graceful shutdown signal
+/// Graceful shutdown signal (Ctrl+C, or SIGTERM on Unix)
 async fn shutdown_signal() {
-    tokio::signal::ctrl_c()
-        .await
-        .expect("Failed to install CTRL+C signal handler");
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install CTRL+C signal handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM signal handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        _ = terminate => {}
+    }
 }
@@ -5,9 +5,11 @@
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use http_body_util::BodyExt;
-use private_poker::auth::{AuthManager, RegisterRequest};
+use private_poker::auth::{AuthManager, LoginRequest, RegisterRequest};
 use private_poker::db::{Database, DatabaseConfig};
-use private_poker::table::TableManager;
+use private_poker::security::{AuditAction, AuditFilter, AuditLog};
+use private_poker::stats::{StatsCounters, StatsRepository};
+use private_poker::table::{TableConfig, TableManager};
 use private_poker::wallet::WalletManager;
 use std::sync::Arc;
 use std::time::Duration;
@@ -37,7 +39,19 @@ async fn setup_test_db() -> Arc<sqlx::PgPool> {
 
 /// Helper to create test server with managers
 async fn create_test_server() -> (axum::Router, Arc<AuthManager>, Arc<TableManager>) {
+    let (app, auth_manager, table_manager, _) = create_test_server_with_audit_log().await;
+    (app, auth_manager, table_manager)
+}
+
+/// Helper to create test server with managers and its audit log
+async fn create_test_server_with_audit_log() -> (
+    axum::Router,
+    Arc<AuthManager>,
+    Arc<TableManager>,
+    Arc<AuditLog>,
+) {
     let pool = setup_test_db().await;
+    let audit_log = Arc::new(AuditLog::new(pool.clone()));
 
     let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
     let table_manager = Arc::new(TableManager::new(pool.clone(), wallet_manager.clone()));
@@ -55,11 +69,13 @@ async fn create_test_server() -> (axum::Router, Arc<AuthManager>, Arc<TableManag
         table_manager: table_manager.clone(),
         wallet_manager,
         pool: pool.clone(),
+        audit_log: audit_log.clone(),
+        heartbeat: pp_server::api::websocket::HeartbeatConfig::default(),
     };
 
     let app = pp_server::api::create_router(state);
 
-    (app, auth_manager, table_manager)
+    (app, auth_manager, table_manager, audit_log)
 }
 
 /// Generate unique username for tests
@@ -90,6 +106,7 @@ async fn test_health_check_endpoint() {
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
     assert_eq!(json["status"], "healthy");
     assert_eq!(json["database"], true);
+    assert_eq!(json["circuit"], "closed");
     assert_eq!(json["tables"]["healthy"], true);
     assert!(json["tables"]["active_count"].is_number());
     assert!(json["version"].is_string());
@@ -249,6 +266,405 @@ async fn test_list_tables_endpoint() {
     assert_eq!(response.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn test_list_tables_pagination() {
+    let (app, _, _) = create_test_server().await;
+
+    let request = Request::builder()
+        .uri("/api/tables?sort_by=players&order=desc&limit=1&offset=0")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json["tables"].as_array().unwrap().len() <= 1);
+    assert!(json["total"].is_number());
+
+    // Unknown sort fields are rejected
+    let request = Request::builder()
+        .uri("/api/tables?sort_by=color")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// API Key Tests
+// ============================================================================
+
+/// Register a user and create an API key for them
+async fn register_with_api_key(auth_manager: &AuthManager, prefix: &str) -> (i64, String) {
+    let username = unique_username(prefix);
+    let user = auth_manager
+        .register(RegisterRequest {
+            username: username.clone(),
+            password: "TestPass123!".to_string(),
+            display_name: "Bot Owner".to_string(),
+            email: Some(format!("{}@test.com", username)),
+        })
+        .await
+        .unwrap();
+
+    let api_key = auth_manager
+        .create_api_key(user.id, "test-bot")
+        .await
+        .unwrap();
+    assert!(api_key.key.starts_with("pk_"));
+
+    (user.id, api_key.key)
+}
+
+#[tokio::test]
+async fn test_api_key_can_get_table_state() {
+    let (app, auth_manager, table_manager) = create_test_server().await;
+    let (_, key) = register_with_api_key(&auth_manager, "apikey").await;
+
+    let config = TableConfig {
+        name: format!("API Key Table {}", unique_username("t")),
+        ..Default::default()
+    };
+    let table_id = table_manager.create_table(config, None).await.unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/api/v1/tables/{}", table_id))
+        .header("authorization", format!("Bearer {}", key))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_revoked_api_key_is_rejected() {
+    let (app, auth_manager, _) = create_test_server().await;
+    let (user_id, key) = register_with_api_key(&auth_manager, "revoked").await;
+
+    let claims = auth_manager.verify_api_key(&key).await.unwrap();
+    auth_manager
+        .revoke_api_key(user_id, claims.key_id)
+        .await
+        .unwrap();
+
+    let request = Request::builder()
+        .uri("/api/v1/tables/1")
+        .header("authorization", format!("Bearer {}", key))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_api_key_cannot_use_account_endpoints() {
+    let (app, auth_manager, _) = create_test_server().await;
+    let (_, key) = register_with_api_key(&auth_manager, "scoped").await;
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/auth/api-keys")
+        .header("authorization", format!("Bearer {}", key))
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"label": "another-bot"}"#))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+// ============================================================================
+// Admin Endpoint Tests
+// ============================================================================
+
+/// Register and log in a user, making them an admin if asked, and return
+/// their user ID, username, and access token
+async fn login_user(
+    auth_manager: &AuthManager,
+    prefix: &str,
+    is_admin: bool,
+) -> (i64, String, String) {
+    let username = unique_username(prefix);
+    let user = auth_manager
+        .register(RegisterRequest {
+            username: username.clone(),
+            password: "TestPass123!".to_string(),
+            display_name: "Test User".to_string(),
+            email: Some(format!("{}@test.com", username)),
+        })
+        .await
+        .unwrap();
+
+    if is_admin {
+        let pool = setup_test_db().await;
+        sqlx::query("UPDATE users SET is_admin = true WHERE id = $1")
+            .bind(user.id)
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+    }
+
+    let login_req = LoginRequest {
+        username: username.clone(),
+        password: "TestPass123!".to_string(),
+        totp_code: None,
+    };
+    let (_user, tokens) = auth_manager
+        .login(login_req, "test_device".to_string())
+        .await
+        .unwrap();
+
+    (user.id, username, tokens.access_token)
+}
+
+/// Build an admin request to pause or resume a table
+fn pause_request(table_id: i64, action: &str, token: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(format!("/api/v1/admin/tables/{}/{}", table_id, action))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_admin_can_pause_table() {
+    let (app, auth_manager, table_manager) = create_test_server().await;
+    let (_, _, token) = login_user(&auth_manager, "admin", true).await;
+
+    let config = TableConfig {
+        name: format!("Admin Table {}", unique_username("t")),
+        ..Default::default()
+    };
+    let table_id = table_manager.create_table(config, None).await.unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(pause_request(table_id, "pause", &token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let state = table_manager.get_table_state(table_id, None).await.unwrap();
+    assert!(!state.is_active);
+
+    let response = app
+        .oneshot(pause_request(table_id, "resume", &token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let state = table_manager.get_table_state(table_id, None).await.unwrap();
+    assert!(state.is_active);
+}
+
+#[tokio::test]
+async fn test_non_admin_is_forbidden() {
+    let (app, auth_manager, table_manager) = create_test_server().await;
+    let (_, _, token) = login_user(&auth_manager, "notadmin", false).await;
+
+    let config = TableConfig {
+        name: format!("Admin Table {}", unique_username("t")),
+        ..Default::default()
+    };
+    let table_id = table_manager.create_table(config, None).await.unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(pause_request(table_id, "pause", &token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let state = table_manager.get_table_state(table_id, None).await.unwrap();
+    assert!(state.is_active);
+
+    let request = Request::builder()
+        .uri("/api/v1/admin/flags")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Without a token, authentication fails before the admin check
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/v1/admin/tables/{}/pause", table_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_kick_is_audited() {
+    let (app, auth_manager, table_manager, audit_log) = create_test_server_with_audit_log().await;
+    let (admin_id, _, token) = login_user(&auth_manager, "admin", true).await;
+    let (user_id, username, _) = login_user(&auth_manager, "kicked", false).await;
+
+    let config = TableConfig {
+        name: format!("Kick Table {}", unique_username("t")),
+        ..Default::default()
+    };
+    let table_id = table_manager.create_table(config, None).await.unwrap();
+    let response = table_manager
+        .join_table(table_id, user_id, username, 2000, None, None)
+        .await
+        .unwrap();
+    assert!(response.is_success());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/api/v1/admin/tables/{}/kick/{}",
+            table_id, user_id
+        ))
+        .header("authorization", format!("Bearer {}", token))
+        .header("x-forwarded-for", "203.0.113.7")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    audit_log.flush().await;
+    let entries = audit_log
+        .query(&AuditFilter {
+            target_user_id: Some(user_id),
+            action: Some(AuditAction::KickUser),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert!(entry.id.is_some());
+    assert_eq!(entry.actor_id, Some(admin_id));
+    assert_eq!(entry.table_id, Some(table_id));
+    assert_eq!(entry.ip_address.as_deref(), Some("203.0.113.7"));
+
+    // The log is append-only
+    let result = sqlx::query("DELETE FROM audit_log WHERE id = $1")
+        .bind(entry.id)
+        .execute(setup_test_db().await.as_ref())
+        .await;
+    assert!(result.is_err());
+}
+
+// ============================================================================
+// Leaderboard Tests
+// ============================================================================
+
+/// Get a leaderboard page's usernames and values
+async fn get_leaderboard(app: &axum::Router, query: &str) -> Vec<(String, i64)> {
+    let request = Request::builder()
+        .uri(format!("/api/v1/leaderboard?{}", query))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry["username"].as_str().unwrap().to_string(),
+                entry["value"].as_i64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_leaderboard_ranks_fixture_players() {
+    let (app, auth_manager, _) = create_test_server().await;
+    let pool = setup_test_db().await;
+    let stats = StatsRepository::new(pool.clone());
+
+    // Results are far beyond what other tests record, so the fixture
+    // players top every leaderboard. Earlier runs' fixtures are cleared.
+    sqlx::query(
+        "DELETE FROM player_stats
+         WHERE user_id IN (SELECT id FROM users WHERE username LIKE 'lbfix%')",
+    )
+    .execute(pool.as_ref())
+    .await
+    .unwrap();
+
+    let (alice, alice_name, _) = login_user(&auth_manager, "lbfix_alice", false).await;
+    let (bob, bob_name, _) = login_user(&auth_manager, "lbfix_bob", false).await;
+    let (frozen, _, _) = login_user(&auth_manager, "lbfix_frozen", false).await;
+    let (bot, _) = register_with_api_key(&auth_manager, "lbfix_bot").await;
+
+    const T: i64 = 1_000_000_000_000;
+    let results = |net_chips: i64, biggest_pot: i64, hands_played: i64| StatsCounters {
+        hands_played,
+        net_chips,
+        biggest_pot,
+        ..Default::default()
+    };
+    stats
+        .record_hand(&[
+            (alice, results(3 * T, T, 2 * T)),
+            (bob, results(2 * T, 3 * T, T)),
+            (frozen, results(5 * T, 5 * T, 5 * T)),
+            (bot, results(4 * T, 4 * T, 4 * T)),
+        ])
+        .await
+        .unwrap();
+    WalletManager::new(pool.clone())
+        .freeze(frozen, "test", None, None)
+        .await
+        .unwrap();
+
+    let top = get_leaderboard(&app, "period=day&metric=net_chips&limit=2").await;
+    assert_eq!(
+        top,
+        vec![(alice_name.clone(), 3 * T), (bob_name.clone(), 2 * T)]
+    );
+
+    let top = get_leaderboard(&app, "period=week&metric=biggest_pot&limit=2").await;
+    assert_eq!(
+        top,
+        vec![(bob_name.clone(), 3 * T), (alice_name.clone(), T)]
+    );
+
+    let top = get_leaderboard(&app, "period=all_time&metric=hands_played&limit=2").await;
+    assert_eq!(
+        top,
+        vec![(alice_name.clone(), 2 * T), (bob_name.clone(), T)]
+    );
+
+    // The second page starts where the first ended
+    let second = get_leaderboard(&app, "metric=net_chips&limit=1&offset=1").await;
+    assert_eq!(second, vec![(bob_name, 2 * T)]);
+}
+
+#[tokio::test]
+async fn test_leaderboard_rejects_bad_limit() {
+    let (app, _, _) = create_test_server().await;
+
+    let request = Request::builder()
+        .uri("/api/v1/leaderboard?limit=0")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 // ============================================================================
 // Error Handling Tests
 // ============================================================================
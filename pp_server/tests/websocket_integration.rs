@@ -4,14 +4,24 @@
 
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
+use futures_util::{SinkExt, StreamExt};
+use pp_server::api::websocket::HeartbeatConfig;
 use private_poker::auth::{AuthManager, LoginRequest, RegisterRequest};
 use private_poker::db::{Database, DatabaseConfig};
+use private_poker::net::messages::{CloseReason, Hello};
+use private_poker::security::AuditLog;
 use private_poker::table::{TableConfig, TableManager};
 use private_poker::wallet::WalletManager;
 use serde_json::json;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tower::ServiceExt;
 
+/// Secret the test server signs access tokens with
+const TEST_JWT_SECRET: &str = "test_secret_key_for_testing_only";
+
 /// Helper to create test database pool
 async fn setup_test_db() -> Arc<sqlx::PgPool> {
     let database_url = std::env::var("DATABASE_URL")
@@ -35,13 +45,20 @@ async fn setup_test_db() -> Arc<sqlx::PgPool> {
 
 /// Helper to create test server
 async fn create_test_server() -> (axum::Router, Arc<AuthManager>, Arc<TableManager>) {
+    create_test_server_with_heartbeat(HeartbeatConfig::default()).await
+}
+
+/// Helper to create test server with custom heartbeat settings
+async fn create_test_server_with_heartbeat(
+    heartbeat: HeartbeatConfig,
+) -> (axum::Router, Arc<AuthManager>, Arc<TableManager>) {
     let pool = setup_test_db().await;
 
     let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
     let table_manager = Arc::new(TableManager::new(pool.clone(), wallet_manager.clone()));
 
     let pepper = "test_pepper_for_testing_only";
-    let jwt_secret = "test_secret_key_for_testing_only";
+    let jwt_secret = TEST_JWT_SECRET;
     let auth_manager = Arc::new(AuthManager::new(
         pool.clone(),
         pepper.to_string(),
@@ -53,6 +70,8 @@ async fn create_test_server() -> (axum::Router, Arc<AuthManager>, Arc<TableManag
         table_manager: table_manager.clone(),
         wallet_manager,
         pool: pool.clone(),
+        audit_log: Arc::new(AuditLog::new(pool.clone())),
+        heartbeat,
     };
 
     let app = pp_server::api::create_router(state);
@@ -484,3 +503,188 @@ async fn test_websocket_message_size_limit() {
     // This should exceed any reasonable WebSocket message size limit
     assert!(huge_message.len() > 1_000_000);
 }
+
+// ============================================================================
+// Close Code Tests
+// ============================================================================
+
+/// Serve the test app on a local port for real WebSocket clients
+async fn spawn_test_server(
+    heartbeat: HeartbeatConfig,
+) -> (SocketAddr, Arc<AuthManager>, Arc<TableManager>) {
+    let (app, auth_manager, table_manager) = create_test_server_with_heartbeat(heartbeat).await;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let _ = axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await;
+    });
+    (addr, auth_manager, table_manager)
+}
+
+/// Create a table for close code tests, skipping IDs already taken by
+/// tables from other tests
+async fn create_close_test_table(table_manager: &TableManager) -> i64 {
+    let config = TableConfig {
+        name: format!("Close Test {}", rand::random::<u32>()),
+        ..Default::default()
+    };
+    for _ in 0..10_000 {
+        if let Ok(table_id) = table_manager.create_table(config.clone(), None).await {
+            return table_id;
+        }
+    }
+    panic!("Failed to create test table");
+}
+
+/// Sign an access token for user 1 that expires `expires_in` seconds from now
+fn access_token_expiring_in(expires_in: i64) -> String {
+    let now = chrono::Utc::now().timestamp();
+    let claims = json!({
+        "sub": 1,
+        "username": "close_test",
+        "is_admin": false,
+        "exp": now + expires_in,
+        "iat": now,
+    });
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes()),
+    )
+    .unwrap()
+}
+
+/// Connect to a table, optionally sending a first message, and return the
+/// close code and reason the server closes the connection with.
+async fn close_frame_for(
+    addr: SocketAddr,
+    table_id: i64,
+    token: &str,
+    first_message: Option<String>,
+) -> (u16, String) {
+    let url = format!("ws://{}/ws/{}?token={}", addr, table_id, token);
+    let (mut ws_stream, _) = connect_async(url).await.unwrap();
+    if let Some(message) = first_message {
+        ws_stream.send(Message::Text(message.into())).await.unwrap();
+    }
+    let close = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(msg) = ws_stream.next().await {
+            if let Message::Close(frame) = msg.unwrap() {
+                return frame;
+            }
+        }
+        None
+    })
+    .await
+    .expect("server didn't close the connection")
+    .expect("close frame had no code");
+    (u16::from(close.code), close.reason.to_string())
+}
+
+/// A `Hello` the server accepts
+fn hello() -> Option<String> {
+    Some(serde_json::to_string(&Hello::new("test")).unwrap())
+}
+
+#[tokio::test]
+async fn test_websocket_close_codes() {
+    let (addr, auth_manager, table_manager) = spawn_test_server(HeartbeatConfig::default()).await;
+    let table_id = create_close_test_table(&table_manager).await;
+    let token = create_test_user(&auth_manager, "ws_close").await;
+
+    let (code, reason) = close_frame_for(addr, table_id, "invalid_token_123", None).await;
+    assert_eq!(code, CloseReason::AuthInvalid.code());
+    assert_eq!(reason, CloseReason::AuthInvalid.to_string());
+
+    // Tokens are accepted for a minute past expiry, so this one is well past it
+    let expired = access_token_expiring_in(-3600);
+    let (code, reason) = close_frame_for(addr, table_id, &expired, None).await;
+    assert_eq!(code, CloseReason::AuthExpired.code());
+    assert_eq!(reason, CloseReason::AuthExpired.to_string());
+    assert!(CloseReason::from_code(code).unwrap().is_retryable());
+
+    let not_hello = Some("not a hello".to_string());
+    let (code, reason) = close_frame_for(addr, table_id, &token, not_hello).await;
+    assert_eq!(code, CloseReason::HandshakeRequired.code());
+    assert_eq!(reason, CloseReason::HandshakeRequired.to_string());
+
+    let unsupported = Some(json!({ "protocol_version": 0, "client_version": "test" }).to_string());
+    let (code, reason) = close_frame_for(addr, table_id, &token, unsupported).await;
+    assert_eq!(code, CloseReason::UnsupportedVersion.code());
+    assert_eq!(reason, "protocol version 0 is not supported");
+
+    let (code, reason) = close_frame_for(addr, i64::MAX, &token, hello()).await;
+    assert_eq!(code, CloseReason::TableClosed.code());
+    assert_eq!(reason, "table not found");
+}
+
+#[tokio::test]
+async fn test_websocket_closes_when_token_expires() {
+    let (addr, _, table_manager) = spawn_test_server(HeartbeatConfig::default()).await;
+    let table_id = create_close_test_table(&table_manager).await;
+
+    let token = access_token_expiring_in(2);
+    let (code, reason) = close_frame_for(addr, table_id, &token, hello()).await;
+    assert_eq!(code, CloseReason::AuthExpired.code());
+    assert_eq!(reason, CloseReason::AuthExpired.to_string());
+}
+
+#[tokio::test]
+async fn test_websocket_closes_on_heartbeat_timeout() {
+    let heartbeat = HeartbeatConfig {
+        interval: Duration::from_millis(100),
+        max_missed_pongs: 1,
+    };
+    let (addr, auth_manager, table_manager) = spawn_test_server(heartbeat).await;
+    let table_id = create_close_test_table(&table_manager).await;
+    let token = create_test_user(&auth_manager, "ws_heartbeat").await;
+
+    let (code, reason) = close_frame_for(addr, table_id, &token, hello()).await;
+    assert_eq!(code, CloseReason::HeartbeatTimeout.code());
+    assert_eq!(reason, CloseReason::HeartbeatTimeout.to_string());
+}
+
+#[tokio::test]
+async fn test_websocket_closes_when_table_closes() {
+    let (addr, auth_manager, table_manager) = spawn_test_server(HeartbeatConfig::default()).await;
+    let table_id = create_close_test_table(&table_manager).await;
+    let token = create_test_user(&auth_manager, "ws_table_close").await;
+
+    let closer = table_manager.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        closer.close_table(table_id).await.unwrap();
+    });
+
+    let (code, reason) = close_frame_for(addr, table_id, &token, hello()).await;
+    assert_eq!(code, CloseReason::TableClosed.code());
+    assert_eq!(reason, CloseReason::TableClosed.to_string());
+}
+
+#[tokio::test]
+async fn test_websocket_closes_when_user_is_kicked() {
+    let (addr, auth_manager, table_manager) = spawn_test_server(HeartbeatConfig::default()).await;
+    let table_id = create_close_test_table(&table_manager).await;
+    let token = create_test_user(&auth_manager, "ws_kicked").await;
+    let claims = auth_manager.verify_access_token(&token).unwrap();
+    let response = table_manager
+        .join_table(table_id, claims.sub, claims.username, 2000, None, None)
+        .await
+        .unwrap();
+    assert!(response.is_success());
+
+    let kicker = table_manager.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let response = kicker.kick_user(table_id, claims.sub).await.unwrap();
+        assert!(response.is_success());
+    });
+
+    let (code, reason) = close_frame_for(addr, table_id, &token, hello()).await;
+    assert_eq!(code, CloseReason::Kicked.code());
+    assert_eq!(reason, CloseReason::Kicked.to_string());
+}
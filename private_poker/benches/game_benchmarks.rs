@@ -1,10 +1,14 @@
+use chrono::Utc;
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
 use private_poker::{
-    GameSettings, PokerState,
+    EntryDirection, EntryType, GameSettings, PokerState, WalletEntry,
     entities::{Card, Suit, Username},
-    functional::{argmax, eval},
+    functional::{argmax, eval, eval_fast, prepare_hand},
     game::{GameStateManagement, PhaseIndependentUserManagement},
+    messages::ServerMessage,
+    net::compression::{compress, decompress},
 };
+use rand::{rng, seq::SliceRandom};
 
 /// Helper to create a game state with N players ready to play
 fn setup_game_with_players(n_players: usize) -> PokerState {
@@ -61,6 +65,55 @@ fn bench_hand_eval_7_cards(c: &mut Criterion) {
     });
 }
 
+/// Benchmark lookup table hand evaluation with the same 7 cards
+fn bench_hand_eval_fast_7_cards(c: &mut Criterion) {
+    let cards = vec![
+        Card(14, Suit::Spade),
+        Card(13, Suit::Spade),
+        Card(12, Suit::Spade),
+        Card(11, Suit::Spade),
+        Card(10, Suit::Spade),
+        Card(2, Suit::Heart),
+        Card(3, Suit::Diamond),
+    ];
+
+    c.bench_function("hand_eval_fast_7_cards", |b| {
+        b.iter(|| eval_fast(&cards));
+    });
+}
+
+/// Compare both evaluators on the same random 7-card hands
+fn bench_eval_vs_eval_fast(c: &mut Criterion) {
+    let mut deck: Vec<Card> = (1u8..14u8)
+        .flat_map(|value| {
+            [Suit::Club, Suit::Spade, Suit::Diamond, Suit::Heart].map(|suit| Card(value, suit))
+        })
+        .collect();
+    let mut rng = rng();
+    let hands: Vec<Vec<Card>> = (0..2_000)
+        .map(|_| {
+            deck.shuffle(&mut rng);
+            let mut cards = deck[..7].to_vec();
+            prepare_hand(&mut cards);
+            cards
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("eval_vs_eval_fast_2000_hands");
+    group.bench_function("eval", |b| {
+        b.iter(|| hands.iter().map(|cards| eval(cards)).collect::<Vec<_>>());
+    });
+    group.bench_function("eval_fast", |b| {
+        b.iter(|| {
+            hands
+                .iter()
+                .map(|cards| eval_fast(cards))
+                .collect::<Vec<_>>()
+        });
+    });
+    group.finish();
+}
+
 /// Benchmark hand evaluation 100 times with random-ish hands
 fn bench_hand_eval_100_iterations(c: &mut Criterion) {
     // Create 100 different 7-card hands
@@ -180,10 +233,41 @@ fn bench_drain_events(c: &mut Criterion) {
     });
 }
 
+/// Benchmark compressing and decompressing a large transaction history
+fn bench_transaction_history_compression(c: &mut Criterion) {
+    let entries: Vec<WalletEntry> = (0..500)
+        .map(|id| WalletEntry {
+            id,
+            user_id: 42,
+            table_id: Some(id % 4),
+            amount: 100 + id,
+            balance_after: 10_000 - id,
+            direction: if id % 2 == 0 {
+                EntryDirection::Debit
+            } else {
+                EntryDirection::Credit
+            },
+            entry_type: EntryType::BuyIn,
+            idempotency_key: format!("buy_in_42_{id}"),
+            description: Some("Buy-in at table".to_string()),
+            created_at: Utc::now(),
+        })
+        .collect();
+    let payload = serde_json::to_vec(&ServerMessage::TransactionHistory { entries }).unwrap();
+    let frame = compress(&payload).unwrap();
+
+    let mut group = c.benchmark_group("transaction_history_compression");
+    group.bench_function("compress", |b| b.iter(|| compress(&payload).unwrap()));
+    group.bench_function("decompress", |b| b.iter(|| decompress(&frame).unwrap()));
+    group.finish();
+}
+
 criterion_group!(
     hand_evaluation,
     bench_hand_eval_2_cards,
     bench_hand_eval_7_cards,
+    bench_hand_eval_fast_7_cards,
+    bench_eval_vs_eval_fast,
     bench_hand_eval_100_iterations,
     bench_hand_comparison,
 );
@@ -195,4 +279,6 @@ criterion_group!(
     bench_drain_events,
 );
 
-criterion_main!(hand_evaluation, game_operations);
+criterion_group!(networking, bench_transaction_history_compression);
+
+criterion_main!(hand_evaluation, game_operations, networking);
@@ -1,5 +1,6 @@
 //! Authentication error types.
 
+use crate::security::RateLimitError;
 use thiserror::Error;
 
 /// Authentication errors
@@ -66,12 +67,49 @@ pub enum AuthError {
     TwoFactorNotEnabled,
 
     /// Rate limited
-    #[error("Too many attempts, please try again later")]
-    RateLimited,
+    #[error("Too many attempts, please try again in {retry_after_secs} seconds")]
+    RateLimited {
+        /// Seconds until another attempt is allowed
+        retry_after_secs: u64,
+        /// Attempts left before being locked out
+        attempts_remaining: u32,
+    },
+
+    /// Rate limiter error
+    #[error("Rate limiter error: {0}")]
+    RateLimiter(#[from] RateLimitError),
 
     /// Invalid reset code
     #[error("Invalid or expired reset code")]
     InvalidResetCode,
+
+    /// No email on the account to verify
+    #[error("No email address on the account")]
+    EmailNotSet,
+
+    /// Invalid verification code
+    #[error("Invalid or expired verification code")]
+    InvalidVerificationCode,
+
+    /// Too many wrong codes were entered for the latest verification email
+    #[error("Too many wrong codes, please request a new verification email")]
+    VerificationAttemptsExceeded,
+
+    /// Email delivery failed
+    #[error("Failed to send email: {0}")]
+    MailerFailed(String),
+
+    /// API key is malformed, unknown, or revoked
+    #[error("Invalid or revoked API key")]
+    InvalidApiKey,
+
+    /// API key not found for the user
+    #[error("API key not found")]
+    ApiKeyNotFound,
+
+    /// API key label is empty or too long
+    #[error("API key label must be 1-50 characters")]
+    InvalidApiKeyLabel,
 }
 
 impl AuthError {
@@ -85,10 +123,22 @@ impl AuthError {
             AuthError::Database(_) => "Internal server error".to_string(),
             // Sanitize JWT errors - don't expose token structure
             AuthError::JwtError(_) => "Authentication failed".to_string(),
+            // Sanitize rate limiter errors - they wrap database errors
+            AuthError::RateLimiter(_) => "Internal server error".to_string(),
             // All other errors are safe to expose
             _ => self.to_string(),
         }
     }
+
+    /// Whether the error is from an access token that has expired, as
+    /// opposed to one that's malformed or forged
+    pub fn is_token_expired(&self) -> bool {
+        matches!(
+            self,
+            AuthError::JwtError(e)
+                if *e.kind() == jsonwebtoken::errors::ErrorKind::ExpiredSignature
+        )
+    }
 }
 
 /// Result type for authentication operations
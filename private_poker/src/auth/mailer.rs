@@ -0,0 +1,35 @@
+//! Email delivery for account notifications.
+
+use super::errors::AuthResult;
+use async_trait::async_trait;
+
+/// Delivers emails to users
+///
+/// Implementations can send through SMTP or an email API; tests can capture
+/// the emails instead of sending them.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Send an email
+    ///
+    /// # Arguments
+    ///
+    /// * `to` - Recipient email address
+    /// * `subject` - Email subject
+    /// * `body` - Plain text email body
+    async fn send(&self, to: &str, subject: &str, body: &str) -> AuthResult<()>;
+}
+
+/// Mailer that writes emails to the log instead of sending them
+///
+/// Used until a real email provider is configured.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> AuthResult<()> {
+        log::info!("Email to {}: {}", to, subject);
+        log::debug!("{}", body);
+        Ok(())
+    }
+}
@@ -4,19 +4,41 @@
 
 use super::{
     errors::{AuthError, AuthResult},
-    models::{AccessTokenClaims, LoginRequest, RegisterRequest, SessionTokens, User, UserId},
+    mailer::{LogMailer, Mailer},
+    models::{
+        AccessTokenClaims, ApiKey, ApiKeyClaims, ApiKeyScope, LoginRequest, RegisterRequest,
+        SessionTokens, User, UserId,
+    },
 };
+use crate::security::{RateLimitResult, RateLimiter};
 use argon2::{
     Argon2,
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
 };
 use chrono::{Duration, Utc};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use rand::Rng;
+use sha2::{Digest, Sha256};
 use sqlx::{PgPool, Row};
 use std::sync::Arc;
 use totp_rs::{Algorithm, Secret, TOTP};
 use uuid::Uuid;
 
+/// Rate limiter endpoint that login attempts are counted against
+const LOGIN_ENDPOINT: &str = "login";
+
+/// Rate limiter endpoint that verification emails are counted against
+const EMAIL_VERIFICATION_ENDPOINT: &str = "email_verification";
+
+/// Wrong codes allowed per verification email before it's locked
+const MAX_VERIFICATION_ATTEMPTS: i32 = 5;
+
+/// Prefix that tells API keys apart from JWTs
+pub const API_KEY_PREFIX: &str = "pk_";
+
+/// Longest label an API key can have
+const MAX_API_KEY_LABEL_LEN: usize = 50;
+
 /// Authentication manager
 #[derive(Clone)]
 pub struct AuthManager {
@@ -25,6 +47,9 @@ pub struct AuthManager {
     jwt_secret: String,
     access_token_duration: Duration,
     refresh_token_duration: Duration,
+    email_verification_duration: Duration,
+    rate_limiter: Arc<RateLimiter>,
+    mailer: Arc<dyn Mailer>,
 }
 
 impl AuthManager {
@@ -50,15 +75,37 @@ impl AuthManager {
             .and_then(|v| v.parse::<i64>().ok())
             .unwrap_or(604_800); // 7 days
 
+        let email_verification_secs = std::env::var("EMAIL_VERIFICATION_EXPIRY")
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(3600); // 1 hour
+
         Self {
+            rate_limiter: Arc::new(RateLimiter::new(pool.clone())),
             pool,
             pepper,
             jwt_secret,
             access_token_duration: Duration::seconds(access_token_secs),
             refresh_token_duration: Duration::seconds(refresh_token_secs),
+            email_verification_duration: Duration::seconds(email_verification_secs),
+            mailer: Arc::new(LogMailer),
         }
     }
 
+    /// Use a different mailer to deliver emails
+    ///
+    /// # Arguments
+    ///
+    /// * `mailer` - Mailer that delivers verification emails
+    ///
+    /// # Returns
+    ///
+    /// * `AuthManager` - Authentication manager using the mailer
+    pub fn with_mailer(mut self, mailer: Arc<dyn Mailer>) -> Self {
+        self.mailer = mailer;
+        self
+    }
+
     /// Register a new user
     ///
     /// # Arguments
@@ -112,8 +159,9 @@ impl AuthManager {
             r#"
             INSERT INTO users (username, password_hash, display_name, email)
             VALUES ($1, $2, $3, $4)
-            RETURNING id, username, display_name, avatar_url, email, country, timezone,
-                      tos_version, privacy_version, is_active, is_admin, created_at, last_login
+            RETURNING id, username, display_name, avatar_url, email, email_verified, country,
+                      timezone, tos_version, privacy_version, is_active, is_admin,
+                      created_at, last_login
             "#,
         )
         .bind(&request.username)
@@ -129,6 +177,7 @@ impl AuthManager {
             display_name: row.get("display_name"),
             avatar_url: row.get("avatar_url"),
             email: row.get("email"),
+            email_verified: row.get("email_verified"),
             country: row.get("country"),
             timezone: row.get("timezone"),
             tos_version: row.get("tos_version"),
@@ -173,16 +222,70 @@ impl AuthManager {
     /// * `AuthError::InvalidPassword` - Incorrect password
     /// * `AuthError::TwoFactorRequired` - 2FA code required but not provided
     /// * `AuthError::InvalidTwoFactorCode` - Invalid 2FA code
+    /// * `AuthError::RateLimited` - Too many attempts, account is locked out
     pub async fn login(
         &self,
         request: LoginRequest,
         device_fingerprint: String,
     ) -> AuthResult<(User, SessionTokens)> {
+        self.login_from(request, device_fingerprint, None).await
+    }
+
+    /// Login a user, also limiting attempts from the client's IP address
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Login request with username and password
+    /// * `device_fingerprint` - Device fingerprint (User-Agent + IP hash)
+    /// * `ip_address` - Client IP address, if known
+    ///
+    /// # Returns
+    ///
+    /// * `AuthResult<(User, SessionTokens)>` - User and session tokens or error
+    ///
+    /// # Errors
+    ///
+    /// Same as [`AuthManager::login`], with `AuthError::RateLimited` also
+    /// returned when the IP address is locked out.
+    pub async fn login_from(
+        &self,
+        request: LoginRequest,
+        device_fingerprint: String,
+        ip_address: Option<&str>,
+    ) -> AuthResult<(User, SessionTokens)> {
+        // Count the attempt against the account, locking it out with
+        // exponential backoff after too many attempts
+        let limit = self
+            .rate_limiter
+            .check_and_record(LOGIN_ENDPOINT, &request.username)
+            .await?;
+        if let RateLimitResult::Locked { retry_after } = limit {
+            return Err(AuthError::RateLimited {
+                retry_after_secs: retry_after,
+                attempts_remaining: 0,
+            });
+        }
+
+        // Login is pre-auth, so it's also limited by the client's IP address
+        if ip_address.is_some() {
+            let limit = self
+                .rate_limiter
+                .check_rate_limit(LOGIN_ENDPOINT, ip_address, None)
+                .await?;
+            if let RateLimitResult::Locked { retry_after } = limit {
+                return Err(AuthError::RateLimited {
+                    retry_after_secs: retry_after,
+                    attempts_remaining: 0,
+                });
+            }
+        }
+
         // Fetch user with password hash
         let user_row = sqlx::query(
             r#"
-            SELECT id, username, password_hash, display_name, avatar_url, email, country, timezone,
-                   tos_version, privacy_version, is_active, is_admin, created_at, last_login
+            SELECT id, username, password_hash, display_name, avatar_url, email, email_verified,
+                   country, timezone, tos_version, privacy_version, is_active, is_admin,
+                   created_at, last_login
             FROM users
             WHERE username = $1
             "#,
@@ -220,6 +323,7 @@ impl AuthManager {
             display_name: user_row.get("display_name"),
             avatar_url: user_row.get("avatar_url"),
             email: user_row.get("email"),
+            email_verified: user_row.get("email_verified"),
             country: user_row.get("country"),
             timezone: user_row.get("timezone"),
             tos_version: user_row.get("tos_version"),
@@ -245,6 +349,16 @@ impl AuthManager {
             .create_session(user.id, &user.username, user.is_admin, device_fingerprint)
             .await?;
 
+        // Successful login clears the failed attempts
+        self.rate_limiter
+            .reset(LOGIN_ENDPOINT, &request.username)
+            .await?;
+        if ip_address.is_some() {
+            self.rate_limiter
+                .reset_rate_limit(LOGIN_ENDPOINT, ip_address, None)
+                .await?;
+        }
+
         Ok((user, tokens))
     }
 
@@ -400,6 +514,335 @@ impl AuthManager {
         Ok(())
     }
 
+    /// Email a time-limited code the user can verify their email with
+    ///
+    /// Sending a new code invalidates any the user was sent before.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User whose email to verify
+    ///
+    /// # Returns
+    ///
+    /// * `AuthResult<()>` - Success or error
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::UserNotFound` - User doesn't exist
+    /// * `AuthError::EmailNotSet` - User hasn't given an email
+    /// * `AuthError::RateLimited` - Too many emails were sent recently
+    /// * `AuthError::MailerFailed` - The email couldn't be sent
+    pub async fn send_verification(&self, user_id: UserId) -> AuthResult<()> {
+        let email: Option<String> = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(self.pool.as_ref())
+            .await?
+            .ok_or(AuthError::UserNotFound)?;
+        let email = email.ok_or(AuthError::EmailNotSet)?;
+
+        // Each email brings fresh guesses at a code, so sending is limited too
+        let limit = self
+            .rate_limiter
+            .check_rate_limit(EMAIL_VERIFICATION_ENDPOINT, None, Some(user_id))
+            .await?;
+        if let RateLimitResult::Locked { retry_after } = limit {
+            return Err(AuthError::RateLimited {
+                retry_after_secs: retry_after,
+                attempts_remaining: 0,
+            });
+        }
+
+        let code = format!("{:06}", rand::rng().random_range(0..1_000_000));
+        let expires_at = Utc::now() + self.email_verification_duration;
+
+        let mut tx = self.pool.begin().await?;
+
+        // Only the latest code works, so retire any sent before it
+        sqlx::query(
+            "UPDATE email_verifications SET used = TRUE WHERE user_id = $1 AND used = FALSE",
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO email_verifications (user_id, email, code, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(user_id)
+        .bind(&email)
+        .bind(&code)
+        .bind(expires_at.naive_utc())
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        let body = format!(
+            "Your verification code is {}. It expires in {} minutes.",
+            code,
+            self.email_verification_duration.num_minutes()
+        );
+        self.mailer.send(&email, "Verify your email", &body).await
+    }
+
+    /// Mark the user's email as verified with a code from `send_verification`
+    ///
+    /// Codes can only be used once, and only until they expire. Only the
+    /// latest code sent works, and it's locked after too many wrong guesses.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User whose email to verify
+    /// * `code` - Verification code that was emailed to the user
+    ///
+    /// # Returns
+    ///
+    /// * `AuthResult<()>` - Success or error
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::InvalidVerificationCode` - Code is wrong, expired, or used
+    /// * `AuthError::VerificationAttemptsExceeded` - Too many wrong codes were
+    ///   entered, so a new code has to be sent
+    pub async fn verify_email(&self, user_id: UserId, code: &str) -> AuthResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let verification = sqlx::query(
+            r#"
+            SELECT id, email, code, failed_attempts
+            FROM email_verifications
+            WHERE user_id = $1 AND used = FALSE AND expires_at > NOW()
+            ORDER BY created_at DESC, id DESC
+            LIMIT 1
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AuthError::InvalidVerificationCode)?;
+
+        let verification_id: i64 = verification.get("id");
+        let failed_attempts: i32 = verification.get("failed_attempts");
+        if failed_attempts >= MAX_VERIFICATION_ATTEMPTS {
+            return Err(AuthError::VerificationAttemptsExceeded);
+        }
+
+        if verification.get::<String, _>("code") != code {
+            sqlx::query(
+                "UPDATE email_verifications SET failed_attempts = failed_attempts + 1 WHERE id = $1",
+            )
+            .bind(verification_id)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            return Err(AuthError::InvalidVerificationCode);
+        }
+
+        // Claim the code so it can't be used again
+        sqlx::query("UPDATE email_verifications SET used = TRUE WHERE id = $1")
+            .bind(verification_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // The code only verifies the email it was sent to
+        let email: String = verification.get("email");
+        let result =
+            sqlx::query("UPDATE users SET email_verified = TRUE WHERE id = $1 AND email = $2")
+                .bind(user_id)
+                .bind(&email)
+                .execute(&mut *tx)
+                .await?;
+        if result.rows_affected() == 0 {
+            return Err(AuthError::InvalidVerificationCode);
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Create an API key a bot client can authenticate with
+    ///
+    /// The key is only returned here; just a hash of its secret is stored,
+    /// so it can't be shown again.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User the key acts as
+    /// * `label` - Name to tell the user's keys apart
+    ///
+    /// # Returns
+    ///
+    /// * `AuthResult<ApiKey>` - Created key or error
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::InvalidApiKeyLabel` - Label is empty or too long
+    pub async fn create_api_key(&self, user_id: UserId, label: &str) -> AuthResult<ApiKey> {
+        let label = label.trim();
+        if label.is_empty() || label.chars().count() > MAX_API_KEY_LABEL_LEN {
+            return Err(AuthError::InvalidApiKeyLabel);
+        }
+
+        // Public prefix to look the key up by, and the secret that proves it
+        let (key_prefix, secret) = {
+            let mut rng = rand::rng();
+            (
+                to_hex(&rng.random::<[u8; 6]>()),
+                to_hex(&rng.random::<[u8; 32]>()),
+            )
+        };
+        let key_hash = self.hash_api_key_secret(&secret);
+        let scopes = ApiKeyScope::DEFAULT.to_vec();
+        let scope_names: Vec<&str> = scopes.iter().map(ApiKeyScope::as_str).collect();
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO api_keys (user_id, label, key_prefix, key_hash, scopes)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(label)
+        .bind(&key_prefix)
+        .bind(&key_hash)
+        .bind(&scope_names)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        Ok(ApiKey {
+            id: row.get("id"),
+            user_id,
+            label: label.to_string(),
+            key: format!("{}{}_{}", API_KEY_PREFIX, key_prefix, secret),
+            scopes,
+            created_at: row.get::<chrono::NaiveDateTime, _>("created_at").and_utc(),
+        })
+    }
+
+    /// Authenticate with an API key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - API key from `create_api_key`
+    ///
+    /// # Returns
+    ///
+    /// * `AuthResult<ApiKeyClaims>` - User and scopes of the key or error
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::InvalidApiKey` - Key is malformed, unknown, or revoked
+    pub async fn verify_api_key(&self, key: &str) -> AuthResult<ApiKeyClaims> {
+        let (key_prefix, secret) = key
+            .strip_prefix(API_KEY_PREFIX)
+            .and_then(|key| key.split_once('_'))
+            .ok_or(AuthError::InvalidApiKey)?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT id, user_id, key_hash, scopes
+            FROM api_keys
+            WHERE key_prefix = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(key_prefix)
+        .fetch_optional(self.pool.as_ref())
+        .await?
+        .ok_or(AuthError::InvalidApiKey)?;
+
+        // Compare hashes in constant time to prevent timing attacks
+        use subtle::ConstantTimeEq;
+        let stored_hash: String = row.get("key_hash");
+        let provided_hash = self.hash_api_key_secret(secret);
+        let hashes_match: bool = stored_hash
+            .as_bytes()
+            .ct_eq(provided_hash.as_bytes())
+            .into();
+        if !hashes_match {
+            return Err(AuthError::InvalidApiKey);
+        }
+
+        let key_id: i64 = row.get("id");
+        sqlx::query("UPDATE api_keys SET last_used_at = NOW() WHERE id = $1")
+            .bind(key_id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        Ok(ApiKeyClaims {
+            key_id,
+            user_id: row.get("user_id"),
+            scopes: row
+                .get::<Vec<String>, _>("scopes")
+                .iter()
+                .filter_map(|scope| ApiKeyScope::parse(scope))
+                .collect(),
+        })
+    }
+
+    /// Revoke one of a user's API keys so it can't be used again
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User who owns the key
+    /// * `key_id` - ID of the key to revoke
+    ///
+    /// # Returns
+    ///
+    /// * `AuthResult<()>` - Success or error
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::ApiKeyNotFound` - User has no active key with that ID
+    pub async fn revoke_api_key(&self, user_id: UserId, key_id: i64) -> AuthResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = NOW()
+            WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(key_id)
+        .bind(user_id)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AuthError::ApiKeyNotFound);
+        }
+        Ok(())
+    }
+
+    /// Check whether a user is an admin
+    ///
+    /// Reads the flag from the database rather than the token, so revoking
+    /// admin rights takes effect right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User to check
+    ///
+    /// # Returns
+    ///
+    /// * `AuthResult<bool>` - Whether the user is an active admin
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::UserNotFound` - User doesn't exist
+    pub async fn is_admin(&self, user_id: UserId) -> AuthResult<bool> {
+        let row = sqlx::query("SELECT is_admin, is_active FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(self.pool.as_ref())
+            .await?
+            .ok_or(AuthError::UserNotFound)?;
+
+        Ok(row.get::<bool, _>("is_admin") && row.get::<bool, _>("is_active"))
+    }
+
     /// Verify an access token
     ///
     /// # Arguments
@@ -432,6 +875,15 @@ impl AuthManager {
             .to_string())
     }
 
+    /// Hash an API key secret with the pepper
+    fn hash_api_key_secret(&self, secret: &str) -> String {
+        let digest = Sha256::new()
+            .chain_update(self.pepper.as_bytes())
+            .chain_update(secret.as_bytes())
+            .finalize();
+        to_hex(&digest)
+    }
+
     /// Verify password against hash
     fn verify_password(&self, password: &str, hash: &str) -> AuthResult<()> {
         let peppered = format!("{}{}", password, self.pepper);
@@ -561,3 +1013,8 @@ impl AuthManager {
         Ok(result.rows_affected() as usize)
     }
 }
+
+/// Encode bytes as lowercase hex
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -6,6 +6,8 @@
 //! - Rotating refresh tokens (7-day expiry)
 //! - Two-factor authentication (TOTP)
 //! - Device fingerprinting for session security
+//! - Email verification through a pluggable mailer
+//! - Revocable, scoped API keys for bot clients
 //!
 //! ## Example
 //!
@@ -37,12 +39,14 @@
 //! ```
 
 pub mod errors;
+pub mod mailer;
 pub mod manager;
 pub mod models;
 
 pub use errors::{AuthError, AuthResult};
-pub use manager::AuthManager;
+pub use mailer::{LogMailer, Mailer};
+pub use manager::{API_KEY_PREFIX, AuthManager};
 pub use models::{
-    AccessTokenClaims, LoginRequest, PasswordResetConfirm, PasswordResetRequest, RegisterRequest,
-    Session, SessionTokens, TwoFactorSetup, User, UserId,
+    AccessTokenClaims, ApiKey, ApiKeyClaims, ApiKeyScope, LoginRequest, PasswordResetConfirm,
+    PasswordResetRequest, RegisterRequest, Session, SessionTokens, TwoFactorSetup, User, UserId,
 };
@@ -14,6 +14,7 @@ pub struct User {
     pub display_name: String,
     pub avatar_url: Option<String>,
     pub email: Option<String>,
+    pub email_verified: bool,
     pub country: Option<String>,
     pub timezone: Option<String>,
     pub tos_version: i16,
@@ -89,3 +90,70 @@ pub struct TwoFactorSetup {
     pub qr_code_url: String,
     pub backup_codes: Vec<String>,
 }
+
+/// Actions an API key can be used for
+///
+/// API keys are meant for bot clients, so they only cover playing at a
+/// table and never account or wallet operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Get a table's state
+    ViewTable,
+    /// Join a table
+    JoinTable,
+    /// Take actions at a table
+    TakeAction,
+}
+
+impl ApiKeyScope {
+    /// Scopes given to new API keys
+    pub const DEFAULT: [ApiKeyScope; 3] = [Self::ViewTable, Self::JoinTable, Self::TakeAction];
+
+    /// Name the scope is stored under
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ViewTable => "view_table",
+            Self::JoinTable => "join_table",
+            Self::TakeAction => "take_action",
+        }
+    }
+
+    /// Parse a stored scope name
+    pub fn parse(scope: &str) -> Option<Self> {
+        match scope {
+            "view_table" => Some(Self::ViewTable),
+            "join_table" => Some(Self::JoinTable),
+            "take_action" => Some(Self::TakeAction),
+            _ => None,
+        }
+    }
+}
+
+/// Newly created API key
+///
+/// The key itself is only available here; just its hash is stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: i64,
+    pub user_id: UserId,
+    pub label: String,
+    pub key: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Identity and permissions of an authenticated API key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyClaims {
+    pub key_id: i64,
+    pub user_id: UserId,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+impl ApiKeyClaims {
+    /// Whether the key can be used for an action
+    pub fn allows(&self, scope: ApiKeyScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
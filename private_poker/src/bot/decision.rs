@@ -1,7 +1,10 @@
 //! Bot decision-making logic with difficulty-based behavior.
 
-use super::models::{BotPlayer, DifficultyParams};
-use crate::game::entities::{Action, Card};
+use super::models::{BotPlayer, DifficultyParams, OpponentProfile, PlanIntent};
+use crate::game::{
+    entities::{Action, Card, Street},
+    functional::equity,
+};
 use rand::Rng;
 
 // === Hand Strength Base Values ===
@@ -34,6 +37,13 @@ const STRENGTH_FOUR_OF_A_KIND: f32 = 0.95;
 /// Hand strength for straight flush (99% = nearly unbeatable)
 const STRENGTH_STRAIGHT_FLUSH: f32 = 0.99;
 
+/// Most strength card values can add on top of the hand rank
+const MAX_KICKER_BONUS: f32 = 0.1;
+
+/// Base the best five card values are encoded in for the kicker bonus.
+/// Values never exceed 14, so each card outweighs all the ones after it.
+const KICKER_BASE: f32 = 15.0;
+
 /// Configuration for bot decision-making thresholds and multipliers.
 ///
 /// All threshold values are hand strength floats in range [0.0, 1.0].
@@ -197,6 +207,85 @@ pub struct BotDecisionConfig {
     /// **Effect**: Reduces hand strength by 5% from UTG
     /// **More negative** = plays very tight from earliest position
     pub utg_position_penalty: f32,
+
+    /// Number of simulated runouts used to estimate equity (mixed-strategy bots).
+    ///
+    /// **Range**: 100-1000 (typical: 200)
+    /// **Effect**: 200 = equity is accurate to within a few percent
+    /// **Higher** = more accurate, but slower decisions
+    pub equity_iterations: usize,
+
+    /// Equity margin over the pot odds price at which a mixed-strategy bot
+    /// always continues (mixed-strategy bots).
+    ///
+    /// **Range**: 0.05-0.2 (typical: 0.1)
+    /// **Effect**: Calls 50% of the time at exactly the right price, ramping to
+    /// 100% at 10% more equity than the price and 0% at 10% less
+    /// **Lower** = closer to a pure strategy (more predictable)
+    pub mixed_call_band: f32,
+
+    /// Equity above which a mixed-strategy bot bets or raises for value.
+    ///
+    /// **Range**: 0.5-0.7 (typical: 0.6)
+    /// **Effect**: Hands with 60%+ equity are bet for value, weaker hands are
+    /// only bet as bluffs
+    pub mixed_value_equity: f32,
+
+    /// Pot fractions a mixed-strategy bot picks its bet sizes from.
+    ///
+    /// **Effect**: Sizes are picked at random regardless of hand strength, so
+    /// bet size doesn't give away whether the bot is bluffing
+    pub pot_fraction_sizes: [f32; 4],
+
+    /// Variance range around each pot fraction bet size (±percentage).
+    ///
+    /// **Range**: 0.05-0.2 (typical: 0.1)
+    /// **Effect**: A half-pot bet varies between 45% and 55% of the pot
+    pub pot_fraction_variance: f32,
+
+    /// Opponent VPIP at or above which they're treated as a calling station.
+    ///
+    /// **Range**: 0.35-0.6 (typical: 0.45)
+    /// **Effect**: Opponents playing 45%+ of hands are bluffed less
+    pub loose_opponent_vpip: f32,
+
+    /// Opponent VPIP at or below which they're treated as a nit.
+    ///
+    /// **Range**: 0.1-0.25 (typical: 0.18)
+    /// **Effect**: Opponents playing 18% or fewer of hands are bluffed more
+    /// and value bet thinner
+    pub tight_opponent_vpip: f32,
+
+    /// Bluff frequency multiplier against calling stations.
+    ///
+    /// **Range**: 0.0-0.5 (typical: 0.25)
+    /// **Effect**: 0.25 = bluffs a quarter as often as against unknown opponents
+    pub loose_opponent_bluff_multiplier: f32,
+
+    /// Bluff frequency multiplier against nits.
+    ///
+    /// **Range**: 1.0-2.0 (typical: 1.5)
+    /// **Effect**: 1.5 = bluffs 50% more often than against unknown opponents
+    pub tight_opponent_bluff_multiplier: f32,
+
+    /// Reduction of the raise threshold against nits.
+    ///
+    /// **Range**: 0.0-0.1 (typical: 0.05)
+    /// **Effect**: Raises for value with hands 5% weaker than usual
+    pub tight_opponent_value_adjustment: f32,
+
+    /// Bluff frequency multiplier for betting a draw on the flop.
+    ///
+    /// **Range**: 1.0-3.0 (typical: 2.0)
+    /// **Effect**: 2.0 = semi-bluffs draws twice as often as pure bluffs
+    pub semi_bluff_multiplier: f32,
+
+    /// Hand strength at or above which a bet starts a plan to keep betting
+    /// for value on later streets.
+    ///
+    /// **Range**: 0.25-0.55 (typical: 0.40)
+    /// **Effect**: 0.40 = barrels two pair or better
+    pub barrel_strength: f32,
 }
 
 impl Default for BotDecisionConfig {
@@ -230,6 +319,21 @@ impl Default for BotDecisionConfig {
             middle_position_bonus: 0.04,
             early_middle_position_penalty: -0.03,
             utg_position_penalty: -0.05,
+            // Mixed strategies
+            equity_iterations: 200,
+            mixed_call_band: 0.1,
+            mixed_value_equity: 0.6,
+            pot_fraction_sizes: [1.0 / 3.0, 0.5, 2.0 / 3.0, 1.0],
+            pot_fraction_variance: 0.1,
+            // Opponent modeling
+            loose_opponent_vpip: 0.45,
+            tight_opponent_vpip: 0.18,
+            loose_opponent_bluff_multiplier: 0.25,
+            tight_opponent_bluff_multiplier: 1.5,
+            tight_opponent_value_adjustment: 0.05,
+            // Multi-street planning
+            semi_bluff_multiplier: 2.0,
+            barrel_strength: 0.40,
         }
     }
 }
@@ -263,6 +367,104 @@ pub struct BotDecisionContext<'a> {
 
     /// Number of players still in the hand
     pub players_remaining: usize,
+
+    /// Observed tendencies of the opponents still in the hand, if they've
+    /// been seen in enough hands
+    pub opponent: Option<OpponentProfile>,
+
+    /// Current betting round
+    pub street: Street,
+
+    /// Bot's last action this hand
+    pub prior_action: Option<Action>,
+}
+
+/// Estimates how strong a hand is for threshold-based bot decisions
+pub trait HandStrengthEstimator {
+    /// Estimate hand strength from 0.0 (weakest) to 1.0 (strongest)
+    ///
+    /// # Arguments
+    ///
+    /// * `hole_cards` - Bot's hole cards
+    /// * `board_cards` - Community cards
+    /// * `opponents` - Number of opponents still in the hand
+    ///
+    /// # Returns
+    ///
+    /// * `f32` - Estimated strength (0.0 to 1.0)
+    fn estimate(&self, hole_cards: &[Card], board_cards: &[Card], opponents: usize) -> f32;
+}
+
+/// Cheap estimator that scores the made hand's rank, plus a small kicker
+/// bonus. Ignores draws and the number of opponents.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RankHeuristic;
+
+impl HandStrengthEstimator for RankHeuristic {
+    fn estimate(&self, hole_cards: &[Card], board_cards: &[Card], _opponents: usize) -> f32 {
+        use crate::game::{entities::Rank, functional::eval};
+
+        // Combine hole cards and board cards
+        let mut all_cards = Vec::with_capacity(hole_cards.len() + board_cards.len());
+        all_cards.extend_from_slice(hole_cards);
+        all_cards.extend_from_slice(board_cards);
+
+        // Need at least 2 cards to evaluate
+        if all_cards.len() < 2 {
+            return 0.0;
+        }
+
+        // Evaluate hand
+        let hand = eval(&all_cards);
+
+        if hand.is_empty() {
+            return 0.0;
+        }
+
+        // Base strength on hand rank
+        let base_strength = match hand[0].rank {
+            Rank::HighCard => STRENGTH_HIGH_CARD,
+            Rank::OnePair => STRENGTH_ONE_PAIR,
+            Rank::TwoPair => STRENGTH_TWO_PAIR,
+            Rank::ThreeOfAKind => STRENGTH_THREE_OF_A_KIND,
+            Rank::Straight => STRENGTH_STRAIGHT,
+            Rank::Flush => STRENGTH_FLUSH,
+            Rank::FullHouse => STRENGTH_FULL_HOUSE,
+            Rank::FourOfAKind => STRENGTH_FOUR_OF_A_KIND,
+            Rank::StraightFlush => STRENGTH_STRAIGHT_FLUSH,
+        };
+
+        // Adjust for kickers (higher values = stronger hand within same rank).
+        // Every value of the best five cards counts, in order, so second
+        // pairs and kickers break ties between hands with the same top card.
+        // Encoding them as digits keeps the order lexicographic.
+        let mut values = hand
+            .iter()
+            .flat_map(|subhand| subhand.values.iter().copied());
+        let encoded = (0..5).fold(0.0, |encoded, _| {
+            encoded * KICKER_BASE + values.next().unwrap_or(0) as f32
+        });
+        // Normalize to 0.0-1.0
+        let kicker_bonus = encoded / KICKER_BASE.powi(5) * MAX_KICKER_BONUS;
+
+        // Clamp final strength to 0.0-1.0
+        (base_strength + kicker_bonus).min(1.0)
+    }
+}
+
+/// Estimator that uses the hand's Monte Carlo equity against the
+/// opponents still in the hand. Accounts for draws and multiway pots, at
+/// the cost of simulating `iterations` runouts per decision.
+#[derive(Debug, Clone, Copy)]
+pub struct EquityEstimator {
+    /// Runouts simulated per estimate
+    pub iterations: usize,
+}
+
+impl HandStrengthEstimator for EquityEstimator {
+    fn estimate(&self, hole_cards: &[Card], board_cards: &[Card], opponents: usize) -> f32 {
+        equity(hole_cards, board_cards, opponents.max(1), self.iterations) as f32
+    }
 }
 
 /// Bot decision maker
@@ -271,15 +473,14 @@ pub struct BotDecisionMaker {
     rng: rand::rngs::ThreadRng,
     /// Configuration for decision-making
     config: BotDecisionConfig,
+    /// Hand strength estimator for threshold-based decisions
+    estimator: Box<dyn HandStrengthEstimator>,
 }
 
 impl BotDecisionMaker {
     /// Create a new decision maker with default config
     pub fn new() -> Self {
-        Self {
-            rng: rand::rng(),
-            config: BotDecisionConfig::default(),
-        }
+        Self::with_config(BotDecisionConfig::default())
     }
 
     /// Create a new decision maker with custom config
@@ -287,30 +488,145 @@ impl BotDecisionMaker {
         Self {
             rng: rand::rng(),
             config,
+            estimator: Box::new(RankHeuristic),
         }
     }
 
+    /// Use a different hand strength estimator
+    ///
+    /// # Arguments
+    ///
+    /// * `estimator` - Estimator used in place of [`RankHeuristic`]
+    ///
+    /// # Returns
+    ///
+    /// * `BotDecisionMaker` - Decision maker using the estimator
+    pub fn with_estimator(mut self, estimator: Box<dyn HandStrengthEstimator>) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
     /// Decide bot action based on difficulty and game state
     ///
+    /// The bot's plan for the hand is followed first, so a bot that started
+    /// barrelling or semi-bluffing on an earlier street keeps doing so, and
+    /// the plan is updated with the chosen action.
+    ///
     /// # Arguments
     ///
     /// * `bot` - Bot player
-    /// * `hole_cards` - Bot's hole cards
-    /// * `board_cards` - Community cards
-    /// * `pot_size` - Current pot size
-    /// * `current_bet` - Current bet to call
-    /// * `bot_chips` - Bot's current chip count
-    /// * `can_check` - Whether bot can check
     /// * `ctx` - Decision context with game state
     ///
     /// # Returns
     ///
     /// * `Action` - Bot's chosen action
-    pub fn decide_action(&mut self, bot: &BotPlayer, ctx: &BotDecisionContext) -> Action {
+    pub fn decide_action(&mut self, bot: &mut BotPlayer, ctx: &BotDecisionContext) -> Action {
+        let action = match self.planned_action(bot, ctx) {
+            Some(action) => action,
+            None if bot.params.mixed_strategy => self.decide_mixed_action(&bot.params, ctx),
+            None => self.decide_threshold_action(&bot.params, ctx),
+        };
+        self.update_plan(bot, ctx, &action);
+        action
+    }
+
+    /// Continue the bot's plan from an earlier street, or start a
+    /// semi-bluff with a draw on the flop
+    ///
+    /// # Arguments
+    ///
+    /// * `bot` - Bot player
+    /// * `ctx` - Decision context with game state
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Action>` - Planned action, or nothing if the plan doesn't
+    ///   cover this decision
+    fn planned_action(&mut self, bot: &BotPlayer, ctx: &BotDecisionContext) -> Option<Action> {
+        // Plans only cover betting when checked to, facing a bet is played
+        // on its own merits
+        if !ctx.can_check || ctx.bot_chips <= ctx.current_bet {
+            return None;
+        }
+
         let params = &bot.params;
+        if ctx.street == Street::Flop
+            && bot.plan.street != Some(Street::Flop)
+            && params.bluffs
+            && has_draw(ctx.hole_cards, ctx.board_cards)
+        {
+            let (bluff_multiplier, _) = self.opponent_adjustments(ctx.opponent);
+            let semi_bluff_frequency =
+                params.bluff_frequency * bluff_multiplier * self.config.semi_bluff_multiplier;
+            if self.rng.random_bool(semi_bluff_frequency.min(1.0) as f64) {
+                return Some(self.pot_fraction_bet(ctx));
+            }
+            return None;
+        }
+
+        // Only continue a plan the bot acted on by betting last street
+        let planned_earlier = bot.plan.street.is_some_and(|street| street < ctx.street);
+        if !planned_earlier || !matches!(ctx.prior_action, Some(Action::Raise(_) | Action::AllIn)) {
+            return None;
+        }
+
+        let continues = match bot.plan.intent {
+            PlanIntent::None => false,
+            PlanIntent::Barrel => true,
+            PlanIntent::SemiBluff => {
+                let strength = self.context_hand_strength(ctx);
+                strength >= STRENGTH_STRAIGHT || is_scare_card(ctx.board_cards)
+            }
+        };
+        continues.then(|| self.pot_fraction_bet(ctx))
+    }
+
+    /// Record the bot's action and decide what it intends to do on later
+    /// streets
+    ///
+    /// Betting a strong hand plans to keep betting for value, and betting a
+    /// draw plans to keep semi-bluffing. Checking or calling gives up on
+    /// the plan.
+    fn update_plan(&self, bot: &mut BotPlayer, ctx: &BotDecisionContext, action: &Action) {
+        let intent = match action {
+            Action::Raise(_) | Action::AllIn if ctx.street != Street::Preflop => {
+                let strength = self.context_hand_strength(ctx);
+                if strength >= self.config.barrel_strength {
+                    PlanIntent::Barrel
+                } else if has_draw(ctx.hole_cards, ctx.board_cards) {
+                    PlanIntent::SemiBluff
+                } else {
+                    PlanIntent::None
+                }
+            }
+            _ => PlanIntent::None,
+        };
+        bot.plan.intent = intent;
+        bot.plan.street = Some(ctx.street);
+        bot.plan.prior_action = Some(action.clone());
+    }
+
+    /// Decide an action with fixed hand-strength thresholds for the bot's
+    /// difficulty
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Difficulty parameters
+    /// * `ctx` - Decision context with game state
+    ///
+    /// # Returns
+    ///
+    /// * `Action` - Bot's chosen action
+    fn decide_threshold_action(
+        &mut self,
+        params: &DifficultyParams,
+        ctx: &BotDecisionContext,
+    ) -> Action {
+        let (bluff_multiplier, value_adjustment) = self.opponent_adjustments(ctx.opponent);
+        let bluff_frequency = (params.bluff_frequency * bluff_multiplier).min(1.0);
 
         // Estimate hand strength
-        let mut hand_strength = self.estimate_hand_strength(ctx.hole_cards, ctx.board_cards);
+        let mut hand_strength = self.context_hand_strength(ctx);
 
         // Apply position modifier (late position can play slightly weaker hands)
         let position_modifier =
@@ -349,6 +665,7 @@ impl BotDecisionMaker {
                 self.config.tag_raise_threshold,
             ),
         };
+        let raise_threshold = raise_threshold - value_adjustment;
 
         // Fold weak hands unless can check for free
         if hand_strength < fold_threshold {
@@ -356,7 +673,7 @@ impl BotDecisionMaker {
                 return Action::Check;
             }
             // Sometimes bluff with weak hands
-            if params.bluffs && self.rng.random_bool(params.bluff_frequency as f64) {
+            if params.bluffs && self.rng.random_bool(bluff_frequency as f64) {
                 let bluff_size = (ctx.pot_size as f32 * self.config.bluff_size_multiplier) as u32;
                 return if ctx.bot_chips <= bluff_size {
                     Action::AllIn
@@ -410,6 +727,120 @@ impl BotDecisionMaker {
         }
     }
 
+    /// Decide an action with a mixed strategy, weighing equity against the
+    /// price of calling rather than using fixed hand-strength thresholds
+    ///
+    /// Hands close to the price are called some of the time instead of
+    /// always or never, and bets are made with the same randomized sizes
+    /// whether the bot is betting for value or bluffing.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Difficulty parameters
+    /// * `ctx` - Decision context with game state
+    ///
+    /// # Returns
+    ///
+    /// * `Action` - Bot's chosen action
+    fn decide_mixed_action(
+        &mut self,
+        params: &DifficultyParams,
+        ctx: &BotDecisionContext,
+    ) -> Action {
+        // All-in if critically short-stacked
+        if ctx.bot_chips <= ctx.current_bet {
+            return Action::AllIn;
+        }
+
+        let (bluff_multiplier, value_adjustment) = self.opponent_adjustments(ctx.opponent);
+        let value_equity = self.config.mixed_value_equity - value_adjustment;
+
+        let opponents = ctx.players_remaining.saturating_sub(1).max(1);
+        let hand_equity = equity(
+            ctx.hole_cards,
+            ctx.board_cards,
+            opponents,
+            self.config.equity_iterations,
+        ) as f32;
+
+        // Value bets get more frequent with more equity, while weaker hands
+        // are bet as bluffs just often enough to keep bets balanced
+        let bet_frequency = if hand_equity >= value_equity {
+            let aggression = params.aggression_factor / (1.0 + params.aggression_factor);
+            let strength = (hand_equity - value_equity) / (1.0 - value_equity);
+            (aggression * (0.5 + strength)).min(1.0)
+        } else if params.bluffs {
+            (params.bluff_frequency * bluff_multiplier * (1.0 - hand_equity)).min(1.0)
+        } else {
+            0.0
+        };
+
+        if ctx.can_check {
+            return if self.rng.random_bool(bet_frequency as f64) {
+                self.pot_fraction_bet(ctx)
+            } else {
+                Action::Check
+            };
+        }
+
+        // Price of calling is the share of the final pot the call makes up
+        let price = 1.0 - self.calculate_pot_odds(ctx.pot_size, ctx.current_bet);
+        let call_frequency =
+            (0.5 + (hand_equity - price) / (2.0 * self.config.mixed_call_band)).clamp(0.0, 1.0);
+        if !self.rng.random_bool(call_frequency as f64) {
+            return Action::Fold;
+        }
+        if self.rng.random_bool(bet_frequency as f64) {
+            self.pot_fraction_bet(ctx)
+        } else {
+            Action::Call
+        }
+    }
+
+    /// Adjust bluffing and value betting to the opponents in the hand
+    ///
+    /// Calling stations are bluffed less, while nits are bluffed more and
+    /// value bet thinner. Unknown opponents get the difficulty defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `opponent` - Observed tendencies of the opponents, if known
+    ///
+    /// # Returns
+    ///
+    /// * `(f32, f32)` - Bluff frequency multiplier and how much to lower
+    ///   the threshold for value bets
+    fn opponent_adjustments(&self, opponent: Option<OpponentProfile>) -> (f32, f32) {
+        match opponent {
+            Some(profile) if profile.vpip >= self.config.loose_opponent_vpip => {
+                (self.config.loose_opponent_bluff_multiplier, 0.0)
+            }
+            Some(profile) if profile.vpip <= self.config.tight_opponent_vpip => (
+                self.config.tight_opponent_bluff_multiplier,
+                self.config.tight_opponent_value_adjustment,
+            ),
+            _ => (1.0, 0.0),
+        }
+    }
+
+    /// Bet or raise by a pot fraction picked at random, so bet sizes don't
+    /// depend on hand strength
+    fn pot_fraction_bet(&mut self, ctx: &BotDecisionContext) -> Action {
+        let sizes = &self.config.pot_fraction_sizes;
+        let fraction = sizes[self.rng.random_range(0..sizes.len())];
+        let variance = self
+            .rng
+            .random_range(-self.config.pot_fraction_variance..=self.config.pot_fraction_variance);
+        let bet_size =
+            ((ctx.pot_size + ctx.current_bet) as f32 * fraction * (1.0 + variance)) as u32;
+        let raise_amount = ctx.current_bet + bet_size.max(1);
+        if ctx.bot_chips <= raise_amount {
+            Action::AllIn
+        } else {
+            Action::Raise(Some(raise_amount))
+        }
+    }
+
     /// Decide whether to play a hand pre-flop based on VPIP
     ///
     /// # Arguments
@@ -558,54 +989,15 @@ impl BotDecisionMaker {
     /// # Returns
     ///
     /// * `f32` - Estimated strength (0.0 to 1.0)
-    pub fn estimate_hand_strength(
-        &self,
-        hole_cards: &[crate::game::entities::Card],
-        board_cards: &[crate::game::entities::Card],
-    ) -> f32 {
-        use crate::game::{entities::Rank, functional::eval};
-
-        // Combine hole cards and board cards
-        let mut all_cards = Vec::with_capacity(hole_cards.len() + board_cards.len());
-        all_cards.extend_from_slice(hole_cards);
-        all_cards.extend_from_slice(board_cards);
-
-        // Need at least 2 cards to evaluate
-        if all_cards.len() < 2 {
-            return 0.0;
-        }
-
-        // Evaluate hand
-        let hand = eval(&all_cards);
-
-        if hand.is_empty() {
-            return 0.0;
-        }
-
-        // Base strength on hand rank
-        let base_strength = match hand[0].rank {
-            Rank::HighCard => STRENGTH_HIGH_CARD,
-            Rank::OnePair => STRENGTH_ONE_PAIR,
-            Rank::TwoPair => STRENGTH_TWO_PAIR,
-            Rank::ThreeOfAKind => STRENGTH_THREE_OF_A_KIND,
-            Rank::Straight => STRENGTH_STRAIGHT,
-            Rank::Flush => STRENGTH_FLUSH,
-            Rank::FullHouse => STRENGTH_FULL_HOUSE,
-            Rank::FourOfAKind => STRENGTH_FOUR_OF_A_KIND,
-            Rank::StraightFlush => STRENGTH_STRAIGHT_FLUSH,
-        };
-
-        // Adjust for kickers (higher values = stronger hand within same rank)
-        let kicker_bonus = if !hand[0].values.is_empty() {
-            let max_value = *hand[0].values.iter().max().unwrap_or(&0);
-            // Normalize value (1-14) to 0.0-0.1 range for kicker bonus
-            (max_value as f32 / 14.0) * 0.1
-        } else {
-            0.0
-        };
+    pub fn estimate_hand_strength(&self, hole_cards: &[Card], board_cards: &[Card]) -> f32 {
+        self.estimator.estimate(hole_cards, board_cards, 1)
+    }
 
-        // Clamp final strength to 0.0-1.0
-        (base_strength + kicker_bonus).min(1.0)
+    /// Estimate hand strength against the opponents still in the hand
+    fn context_hand_strength(&self, ctx: &BotDecisionContext) -> f32 {
+        let opponents = ctx.players_remaining.saturating_sub(1).max(1);
+        self.estimator
+            .estimate(ctx.hole_cards, ctx.board_cards, opponents)
     }
 }
 
@@ -615,10 +1007,52 @@ impl Default for BotDecisionMaker {
     }
 }
 
+/// Whether the cards hold a flush draw or an open-ended straight draw that
+/// uses at least one hole card
+fn has_draw(hole_cards: &[Card], board_cards: &[Card]) -> bool {
+    let all_cards: Vec<Card> = hole_cards.iter().chain(board_cards).copied().collect();
+
+    let flush_draw = hole_cards
+        .iter()
+        .any(|hole| all_cards.iter().filter(|card| card.1 == hole.1).count() == 4);
+    if flush_draw {
+        return true;
+    }
+
+    // Aces play both high and low
+    let has_value = |cards: &[Card], value: u8| {
+        cards
+            .iter()
+            .any(|card| card.0 == value || (value == 1 && card.0 == 14))
+    };
+    (2..=10).any(|low| {
+        let run = low..low + 4;
+        run.clone().all(|value| has_value(&all_cards, value))
+            && run.clone().any(|value| has_value(hole_cards, value))
+            && !has_value(&all_cards, low - 1)
+            && !has_value(&all_cards, low + 4)
+    })
+}
+
+/// Whether the last card dealt to the board makes opponents' bets more
+/// believable: an ace or king overcard, or a third card of a suit
+fn is_scare_card(board_cards: &[Card]) -> bool {
+    let Some((last, earlier)) = board_cards.split_last() else {
+        return false;
+    };
+    if earlier.len() < 3 {
+        return false;
+    }
+
+    let overcard = last.0 >= 13 && earlier.iter().all(|card| card.0 < last.0);
+    let flush_card = board_cards.iter().filter(|card| card.1 == last.1).count() == 3;
+    overcard || flush_card
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bot::models::BotConfig;
+    use crate::bot::models::{BotConfig, HandPlan};
     use crate::game::entities::{Card, Suit};
     use crate::table::config::BotDifficulty;
 
@@ -643,6 +1077,9 @@ mod tests {
             can_check,
             position,
             players_remaining,
+            opponent: None,
+            street: Street::from_num_community_cards(board_cards.len()),
+            prior_action: None,
         }
     }
 
@@ -659,7 +1096,8 @@ mod tests {
         let trials = 100;
         for _ in 0..trials {
             let ctx = make_ctx(&hole_cards, &board_cards, 100, 10, 1000, false, Some(0), 6);
-            let action = decision_maker.decide_action(&create_test_bot(BotDifficulty::Easy), &ctx);
+            let action =
+                decision_maker.decide_action(&mut create_test_bot(BotDifficulty::Easy), &ctx);
             if matches!(action, Action::Raise(_) | Action::AllIn) {
                 raise_count += 1;
             }
@@ -689,7 +1127,8 @@ mod tests {
         let trials = 100;
         for _ in 0..trials {
             let ctx = make_ctx(&hole_cards, &board_cards, 100, 10, 1000, false, Some(0), 6);
-            let action = decision_maker.decide_action(&create_test_bot(BotDifficulty::Tag), &ctx);
+            let action =
+                decision_maker.decide_action(&mut create_test_bot(BotDifficulty::Tag), &ctx);
             if matches!(action, Action::Raise(_) | Action::AllIn) {
                 raise_count += 1;
             }
@@ -717,7 +1156,8 @@ mod tests {
         let trials = 500; // Increased for statistical reliability
         for _ in 0..trials {
             let ctx = make_ctx(&hole_cards, &board_cards, 100, 10, 1000, false, Some(5), 6);
-            let action = decision_maker.decide_action(&create_test_bot(BotDifficulty::Tag), &ctx);
+            let action =
+                decision_maker.decide_action(&mut create_test_bot(BotDifficulty::Tag), &ctx);
             if matches!(action, Action::Fold) {
                 fold_count += 1;
             }
@@ -746,7 +1186,8 @@ mod tests {
         let trials = 100;
         for _ in 0..trials {
             let ctx = make_ctx(&hole_cards, &board_cards, 100, 10, 1000, false, Some(0), 6);
-            let action = decision_maker.decide_action(&create_test_bot(BotDifficulty::Easy), &ctx);
+            let action =
+                decision_maker.decide_action(&mut create_test_bot(BotDifficulty::Easy), &ctx);
             if matches!(action, Action::Fold) {
                 fold_count += 1;
             }
@@ -777,7 +1218,7 @@ mod tests {
         for _ in 0..1000 {
             let ctx = make_ctx(&hole_cards, &board_cards, 100, 20, 1000, false, Some(0), 6);
             let action =
-                decision_maker.decide_action(&create_test_bot(BotDifficulty::Standard), &ctx);
+                decision_maker.decide_action(&mut create_test_bot(BotDifficulty::Standard), &ctx);
             if !matches!(action, Action::Fold) {
                 plays_button += 1;
             }
@@ -788,7 +1229,7 @@ mod tests {
         for _ in 0..1000 {
             let ctx = make_ctx(&hole_cards, &board_cards, 100, 20, 1000, false, Some(5), 6);
             let action =
-                decision_maker.decide_action(&create_test_bot(BotDifficulty::Standard), &ctx);
+                decision_maker.decide_action(&mut create_test_bot(BotDifficulty::Standard), &ctx);
             if !matches!(action, Action::Fold) {
                 plays_utg += 1;
             }
@@ -840,6 +1281,273 @@ mod tests {
         assert!(odds2 > odds3, "Medium odds should be > bad odds");
     }
 
+    #[test]
+    fn test_gto_bot_calls_with_pot_odds_more_than_tag() {
+        let mut decision_maker = BotDecisionMaker::new();
+
+        // Flush draw with overcards: only jack high, but getting a good
+        // enough price on a small bet to continue
+        let hole_cards = vec![Card(11, Suit::Heart), Card(10, Suit::Heart)];
+        let board_cards = vec![
+            Card(9, Suit::Heart),
+            Card(2, Suit::Heart),
+            Card(5, Suit::Club),
+        ];
+
+        let trials = 200;
+        let mut count_calls = |difficulty| {
+            let mut bot = create_test_bot(difficulty);
+            (0..trials)
+                .filter(|_| {
+                    let ctx = make_ctx(&hole_cards, &board_cards, 100, 25, 1000, false, None, 2);
+                    matches!(decision_maker.decide_action(&mut bot, &ctx), Action::Call)
+                })
+                .count()
+        };
+        let tag_calls = count_calls(BotDifficulty::Tag);
+        let gto_calls = count_calls(BotDifficulty::Gto);
+
+        assert!(
+            gto_calls > tag_calls + trials / 4,
+            "GTO bot called {} times vs TAG {} times out of {} (GTO should call the right price more)",
+            gto_calls,
+            tag_calls,
+            trials
+        );
+    }
+
+    #[test]
+    fn test_gto_bot_randomizes_bet_sizes() {
+        let mut decision_maker = BotDecisionMaker::new();
+        let mut bot = create_test_bot(BotDifficulty::Gto);
+        let board_cards = vec![
+            Card(12, Suit::Spade),
+            Card(11, Suit::Spade),
+            Card(10, Suit::Spade),
+        ];
+
+        // Bet sizes for the nuts and for air should both be spread across
+        // pot fractions, so size doesn't give away the bot's hand
+        let nuts = vec![Card(14, Suit::Spade), Card(13, Suit::Spade)];
+        let air = vec![Card(7, Suit::Club), Card(2, Suit::Diamond)];
+        for hole_cards in [&nuts, &air] {
+            let bets: Vec<u32> = (0..500)
+                .filter_map(|_| {
+                    let ctx = make_ctx(hole_cards, &board_cards, 100, 0, 10_000, true, None, 2);
+                    match decision_maker.decide_action(&mut bot, &ctx) {
+                        Action::Raise(Some(amount)) => Some(amount),
+                        _ => None,
+                    }
+                })
+                .collect();
+            assert!(bets.len() > 20, "Only {} bets were made", bets.len());
+            let min = *bets.iter().min().unwrap();
+            let max = *bets.iter().max().unwrap();
+            assert!(min < 40, "Smallest bet was {}", min);
+            assert!((91..=110).contains(&max), "Largest bet was {}", max);
+        }
+    }
+
+    #[test]
+    fn test_bluffs_less_into_calling_station() {
+        let mut decision_maker = BotDecisionMaker::new();
+        let mut bot = create_test_bot(BotDifficulty::Tag);
+
+        // Weak hand TAG only ever plays as a bluff
+        let hole_cards = vec![Card(7, Suit::Club), Card(2, Suit::Diamond)];
+        let board_cards = vec![];
+        let calling_station = OpponentProfile {
+            vpip: 0.7,
+            aggression_factor: 0.3,
+            hands_observed: 50,
+        };
+
+        let trials = 1000;
+        let mut count_bluffs = |opponent| {
+            (0..trials)
+                .filter(|_| {
+                    let mut ctx =
+                        make_ctx(&hole_cards, &board_cards, 100, 10, 1000, false, None, 2);
+                    ctx.opponent = opponent;
+                    matches!(
+                        decision_maker.decide_action(&mut bot, &ctx),
+                        Action::Raise(_) | Action::AllIn
+                    )
+                })
+                .count()
+        };
+        let default_bluffs = count_bluffs(None);
+        let station_bluffs = count_bluffs(Some(calling_station));
+
+        assert!(
+            station_bluffs * 2 < default_bluffs,
+            "Bluffed a calling station {} times vs {} times by default (should be far fewer)",
+            station_bluffs,
+            default_bluffs
+        );
+    }
+
+    #[test]
+    fn test_bot_continues_planned_barrel_on_turn() {
+        let mut decision_maker = BotDecisionMaker::new();
+
+        // Flush draw on the flop, then an ace lands on the turn
+        let hole_cards = vec![Card(11, Suit::Heart), Card(10, Suit::Heart)];
+        let flop = vec![
+            Card(9, Suit::Heart),
+            Card(2, Suit::Heart),
+            Card(5, Suit::Club),
+        ];
+        let mut turn = flop.clone();
+        turn.push(Card(14, Suit::Spade));
+
+        let mut flop_bets = 0;
+        for _ in 0..200 {
+            let mut bot = create_test_bot(BotDifficulty::Tag);
+            let ctx = make_ctx(&hole_cards, &flop, 100, 0, 1000, true, None, 2);
+            let flop_action = decision_maker.decide_action(&mut bot, &ctx);
+            if !matches!(flop_action, Action::Raise(_) | Action::AllIn) {
+                continue;
+            }
+            flop_bets += 1;
+            assert_eq!(bot.plan.intent, PlanIntent::SemiBluff);
+            assert_eq!(bot.plan.prior_action, Some(flop_action));
+
+            let mut ctx = make_ctx(&hole_cards, &turn, 300, 0, 900, true, None, 2);
+            ctx.prior_action = bot.plan.prior_action.clone();
+            let turn_action = decision_maker.decide_action(&mut bot, &ctx);
+            assert!(
+                matches!(turn_action, Action::Raise(_) | Action::AllIn),
+                "Bot gave up its semi-bluff on a scare card with {:?}",
+                turn_action
+            );
+
+            bot.reset_plan();
+            assert_eq!(bot.plan, HandPlan::default());
+        }
+
+        assert!(flop_bets > 0, "Bot never semi-bluffed the flop");
+    }
+
+    #[test]
+    fn test_second_pair_adds_strength() {
+        let decision_maker = BotDecisionMaker::new();
+        let board = [
+            Card(14, Suit::Club),
+            Card(13, Suit::Heart),
+            Card(2, Suit::Heart),
+        ];
+        let aces_over_kings = [Card(14, Suit::Spade), Card(13, Suit::Diamond)];
+        let aces_over_twos = [Card(14, Suit::Spade), Card(2, Suit::Diamond)];
+
+        let kings = decision_maker.estimate_hand_strength(&aces_over_kings, &board);
+        let twos = decision_maker.estimate_hand_strength(&aces_over_twos, &board);
+        assert!(
+            kings > twos,
+            "aces over kings {kings} <= aces over twos {twos}"
+        );
+        assert!((STRENGTH_TWO_PAIR..=1.0).contains(&twos));
+    }
+
+    #[test]
+    fn test_kickers_add_strength() {
+        let decision_maker = BotDecisionMaker::new();
+        let board = [
+            Card(9, Suit::Club),
+            Card(7, Suit::Heart),
+            Card(4, Suit::Spade),
+        ];
+        let ace_king = [Card(14, Suit::Spade), Card(13, Suit::Diamond)];
+        let ace_deuce = [Card(14, Suit::Spade), Card(2, Suit::Diamond)];
+
+        let king_kicker = decision_maker.estimate_hand_strength(&ace_king, &board);
+        let deuce_kicker = decision_maker.estimate_hand_strength(&ace_deuce, &board);
+        assert!(king_kicker > deuce_kicker);
+
+        // Raising any kicker never lowers strength, and it stays in range
+        let mut previous = 0.0;
+        for kicker in 2..=13 {
+            let hole = [Card(14, Suit::Spade), Card(kicker, Suit::Diamond)];
+            let strength = decision_maker.estimate_hand_strength(&hole, &[]);
+            assert!(strength >= previous, "{kicker} kicker lowered strength");
+            assert!((0.0..=1.0).contains(&strength));
+            previous = strength;
+        }
+
+        // The top card outweighs every kicker after it
+        let ace_high = decision_maker.estimate_hand_strength(
+            &[Card(14, Suit::Spade), Card(6, Suit::Diamond)],
+            &[
+                Card(4, Suit::Club),
+                Card(3, Suit::Heart),
+                Card(2, Suit::Spade),
+            ],
+        );
+        let king_high = decision_maker.estimate_hand_strength(
+            &[Card(13, Suit::Spade), Card(12, Suit::Diamond)],
+            &[
+                Card(11, Suit::Club),
+                Card(10, Suit::Heart),
+                Card(8, Suit::Spade),
+            ],
+        );
+        assert!(ace_high > king_high, "{ace_high} <= {king_high}");
+        let aces = decision_maker.estimate_hand_strength(
+            &[Card(14, Suit::Spade), Card(14, Suit::Diamond)],
+            &[
+                Card(4, Suit::Club),
+                Card(3, Suit::Heart),
+                Card(2, Suit::Spade),
+            ],
+        );
+        let kings = decision_maker.estimate_hand_strength(
+            &[Card(13, Suit::Spade), Card(13, Suit::Diamond)],
+            &[
+                Card(12, Suit::Club),
+                Card(11, Suit::Heart),
+                Card(10, Suit::Spade),
+            ],
+        );
+        assert!(aces > kings, "{aces} <= {kings}");
+
+        let royal_flush = [Card(14, Suit::Spade), Card(13, Suit::Spade)];
+        let board = [
+            Card(12, Suit::Spade),
+            Card(11, Suit::Spade),
+            Card(10, Suit::Spade),
+        ];
+        assert!(decision_maker.estimate_hand_strength(&royal_flush, &board) <= 1.0);
+    }
+
+    /// Estimator that rates every hand the same
+    struct FixedStrength(f32);
+
+    impl HandStrengthEstimator for FixedStrength {
+        fn estimate(&self, _hole_cards: &[Card], _board_cards: &[Card], _opponents: usize) -> f32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_decide_action_uses_swapped_estimator() {
+        let aces = vec![Card(14, Suit::Spade), Card(14, Suit::Heart)];
+        let seven_deuce = vec![Card(7, Suit::Club), Card(2, Suit::Diamond)];
+        let board_cards = vec![];
+
+        // Easy bots never bluff, so a hopeless rating always folds to a bet
+        let mut hopeless = BotDecisionMaker::new().with_estimator(Box::new(FixedStrength(0.0)));
+        let mut nuts = BotDecisionMaker::new().with_estimator(Box::new(FixedStrength(1.0)));
+        for _ in 0..100 {
+            let ctx = make_ctx(&aces, &board_cards, 100, 10, 1000, false, None, 2);
+            let action = hopeless.decide_action(&mut create_test_bot(BotDifficulty::Easy), &ctx);
+            assert_eq!(action, Action::Fold);
+
+            let ctx = make_ctx(&seven_deuce, &board_cards, 100, 10, 1000, false, None, 2);
+            let action = nuts.decide_action(&mut create_test_bot(BotDifficulty::Easy), &ctx);
+            assert_ne!(action, Action::Fold);
+        }
+    }
+
     fn create_test_bot(difficulty: BotDifficulty) -> BotPlayer {
         let config = BotConfig {
             id: 1,
@@ -847,6 +1555,7 @@ mod tests {
             difficulty,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
         BotPlayer::new(config)
     }
@@ -3,10 +3,13 @@
 //! This module implements:
 //! - BotPlayer: Individual bot with statistics tracking
 //! - BotManager: Auto-spawn/despawn bots to reach target players
-//! - Difficulty presets (Easy, Standard, TAG) with distinct play styles
+//! - Difficulty presets (Easy, Standard, TAG, GTO) with distinct play styles
 //! - Human-like pacing with randomized delays
 //! - Telemetry tracking and anomaly detection
 //! - Bot ratio caps for high-stakes tables
+//! - Opponent modeling from the hands played at the table
+//! - Per-hand plans carried across streets (barrels and semi-bluffs)
+//! - Unique, human-like bot names with avatar seeds
 //!
 //! ## Difficulty Presets
 //!
@@ -28,6 +31,12 @@
 //! - Aggression: 2.5 (very aggressive)
 //! - Bluffs 25% of time
 //!
+//! ### GTO (Mixed Strategy)
+//! - VPIP: 25%
+//! - Calls at a frequency set by equity vs. pot odds
+//! - Bets from randomized pot fractions (1/3, 1/2, 2/3, pot)
+//! - Bluffs to balance value bets
+//!
 //! ## Example
 //!
 //! ```no_run
@@ -66,6 +75,9 @@ pub mod decision;
 pub mod manager;
 pub mod models;
 
-pub use decision::BotDecisionMaker;
+pub use decision::{BotDecisionMaker, EquityEstimator, HandStrengthEstimator, RankHeuristic};
 pub use manager::BotManager;
-pub use models::{BotConfig, BotId, BotPlayer, BotStats, BotTelemetry, DifficultyParams};
+pub use models::{
+    BotConfig, BotId, BotPlayer, BotStats, BotTelemetry, DifficultyParams, HandPlan, OpponentHand,
+    OpponentProfile, OpponentStats, PlanIntent,
+};
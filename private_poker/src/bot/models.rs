@@ -1,12 +1,22 @@
 //! Bot player models and configuration.
 
-use crate::table::config::BotDifficulty;
+use crate::{
+    game::entities::{Action, Street},
+    table::config::BotDifficulty,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 /// Bot player identifier
 pub type BotId = i32;
 
+/// Number of recent hands remembered per opponent
+const OPPONENT_HISTORY_SIZE: usize = 100;
+
+/// Hands an opponent must be seen in before their tendencies are used
+const MIN_OPPONENT_HANDS: usize = 10;
+
 /// Bot player configuration
 #[derive(Debug, Clone)]
 pub struct BotConfig {
@@ -24,6 +34,9 @@ pub struct BotConfig {
 
     /// Initial chip count
     pub starting_chips: i64,
+
+    /// Seed the client can use to render a consistent avatar for the bot
+    pub avatar_seed: Option<u64>,
 }
 
 /// Bot difficulty parameters
@@ -55,6 +68,10 @@ pub struct DifficultyParams {
 
     /// Bluff frequency (0.0 to 1.0)
     pub bluff_frequency: f32,
+
+    /// Whether bot mixes its actions based on equity vs. pot odds rather
+    /// than fixed hand-strength thresholds
+    pub mixed_strategy: bool,
 }
 
 impl DifficultyParams {
@@ -71,6 +88,7 @@ impl DifficultyParams {
             think_time_variance_ms: 1000, // ±1s variance
             bluffs: false,                // Never bluffs
             bluff_frequency: 0.0,
+            mixed_strategy: false,
         }
     }
 
@@ -87,6 +105,7 @@ impl DifficultyParams {
             think_time_variance_ms: 1500, // ±1.5s variance
             bluffs: true,                 // Bluffs occasionally
             bluff_frequency: 0.15,        // Bluffs 15% of time
+            mixed_strategy: false,
         }
     }
 
@@ -103,6 +122,24 @@ impl DifficultyParams {
             think_time_variance_ms: 2000, // ±2s variance
             bluffs: true,                 // Bluffs strategically
             bluff_frequency: 0.25,        // Bluffs 25% of time
+            mixed_strategy: false,
+        }
+    }
+
+    /// Get parameters for GTO difficulty
+    /// Mixes calls and bets by equity vs. pot odds, with randomized sizing
+    pub fn gto() -> Self {
+        Self {
+            vpip: 0.25,                   // Plays 25% of hands
+            pfr: 0.20,                    // Raises pre-flop 20%
+            aggression_factor: 2.0,       // Aggressive
+            fold_to_3bet: 0.45,           // Folds to re-raises 45%
+            cbet_frequency: 0.60,         // Continuation bets 60%
+            base_think_time_ms: 2500,     // Thinks ~2.5s base
+            think_time_variance_ms: 1500, // ±1.5s variance
+            bluffs: true,                 // Bluffs to balance value bets
+            bluff_frequency: 0.20,        // Bluffs 20% of time
+            mixed_strategy: true,
         }
     }
 
@@ -112,6 +149,7 @@ impl DifficultyParams {
             BotDifficulty::Easy => Self::easy(),
             BotDifficulty::Standard => Self::standard(),
             BotDifficulty::Tag => Self::tag(),
+            BotDifficulty::Gto => Self::gto(),
         }
     }
 }
@@ -211,6 +249,130 @@ impl BotStats {
     }
 }
 
+/// How an opponent played a single hand
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpponentHand {
+    /// Whether they voluntarily put money in the pot pre-flop
+    pub vpip: bool,
+
+    /// Bets/raises made
+    pub aggressive_actions: u32,
+
+    /// Calls made
+    pub passive_actions: u32,
+}
+
+/// Rolling record of an opponent's most recent hands at a table
+#[derive(Debug, Clone, Default)]
+pub struct OpponentStats {
+    /// Recent hands (oldest first)
+    hands: VecDeque<OpponentHand>,
+}
+
+impl OpponentStats {
+    /// Record a hand the opponent played, forgetting the oldest hand
+    /// once enough have been recorded
+    pub fn record_hand(&mut self, hand: OpponentHand) {
+        if self.hands.len() == OPPONENT_HISTORY_SIZE {
+            self.hands.pop_front();
+        }
+        self.hands.push_back(hand);
+    }
+
+    /// Summarize the opponent's tendencies, or nothing if they haven't
+    /// been seen in enough hands to say
+    pub fn profile(&self) -> Option<OpponentProfile> {
+        let hands_observed = self.hands.len();
+        if hands_observed < MIN_OPPONENT_HANDS {
+            return None;
+        }
+
+        let vpip_count = self.hands.iter().filter(|h| h.vpip).count();
+        let aggressive: u32 = self.hands.iter().map(|h| h.aggressive_actions).sum();
+        let passive: u32 = self.hands.iter().map(|h| h.passive_actions).sum();
+        let aggression_factor = if passive == 0 {
+            aggressive as f32
+        } else {
+            aggressive as f32 / passive as f32
+        };
+
+        Some(OpponentProfile {
+            vpip: vpip_count as f32 / hands_observed as f32,
+            aggression_factor,
+            hands_observed,
+        })
+    }
+}
+
+/// Summary of an opponent's observed tendencies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpponentProfile {
+    /// VPIP (Voluntarily Put $ In Pot) percentage
+    pub vpip: f32,
+
+    /// Aggression factor (ratio of bets/raises to calls)
+    pub aggression_factor: f32,
+
+    /// Number of hands the profile is based on
+    pub hands_observed: usize,
+}
+
+impl OpponentProfile {
+    /// Combine the profiles of several opponents, weighted by how many
+    /// hands each was observed in
+    ///
+    /// # Returns
+    ///
+    /// * `Option<OpponentProfile>` - Combined profile, or nothing if there
+    ///   are no profiles to combine
+    pub fn combine(profiles: &[OpponentProfile]) -> Option<Self> {
+        let hands_observed: usize = profiles.iter().map(|p| p.hands_observed).sum();
+        if hands_observed == 0 {
+            return None;
+        }
+
+        let weighted = |stat: fn(&OpponentProfile) -> f32| {
+            profiles
+                .iter()
+                .map(|p| stat(p) * p.hands_observed as f32)
+                .sum::<f32>()
+                / hands_observed as f32
+        };
+        Some(Self {
+            vpip: weighted(|p| p.vpip),
+            aggression_factor: weighted(|p| p.aggression_factor),
+            hands_observed,
+        })
+    }
+}
+
+/// What a bot intends to do on the later streets of a hand
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlanIntent {
+    /// No plan, each street is played on its own merits
+    #[default]
+    None,
+
+    /// Keep betting a strong hand for value
+    Barrel,
+
+    /// Keep betting a draw if it improves or a scare card lands
+    SemiBluff,
+}
+
+/// A bot's plan for the hand it's playing, carried across streets
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HandPlan {
+    /// What the bot intends to do on later streets
+    pub intent: PlanIntent,
+
+    /// Street the plan was made on
+    pub street: Option<Street>,
+
+    /// Bot's last action this hand
+    pub prior_action: Option<Action>,
+}
+
 /// Bot player state
 #[derive(Debug, Clone)]
 pub struct BotPlayer {
@@ -225,6 +387,9 @@ pub struct BotPlayer {
 
     /// Last action timestamp (for pacing)
     pub last_action_time: Option<DateTime<Utc>>,
+
+    /// Plan for the current hand
+    pub plan: HandPlan,
 }
 
 impl BotPlayer {
@@ -242,9 +407,15 @@ impl BotPlayer {
             params,
             stats,
             last_action_time: None,
+            plan: HandPlan::default(),
         }
     }
 
+    /// Forget the plan for the last hand before a new hand starts
+    pub fn reset_plan(&mut self) {
+        self.plan = HandPlan::default();
+    }
+
     /// Get thinking delay in milliseconds (with randomization)
     pub fn get_think_delay_ms(&self) -> u64 {
         use rand::Rng;
@@ -325,6 +496,15 @@ mod tests {
         assert_eq!(params.bluff_frequency, 0.25);
     }
 
+    #[test]
+    fn test_difficulty_params_gto() {
+        let params = DifficultyParams::gto();
+        assert_eq!(params.vpip, 0.25);
+        assert_eq!(params.aggression_factor, 2.0);
+        assert!(params.bluffs);
+        assert!(params.mixed_strategy);
+    }
+
     #[test]
     fn test_difficulty_params_from_difficulty() {
         let easy = DifficultyParams::from_difficulty(BotDifficulty::Easy);
@@ -335,6 +515,9 @@ mod tests {
 
         let tag = DifficultyParams::from_difficulty(BotDifficulty::Tag);
         assert_eq!(tag.vpip, 0.20);
+
+        let gto = DifficultyParams::from_difficulty(BotDifficulty::Gto);
+        assert!(gto.mixed_strategy);
     }
 
     #[test]
@@ -433,6 +616,66 @@ mod tests {
         assert_eq!(stats.win_rate(), -4.0); // -200 chips / 50 hands = -4.0
     }
 
+    #[test]
+    fn test_opponent_stats_cold_start() {
+        let mut stats = OpponentStats::default();
+        for _ in 0..MIN_OPPONENT_HANDS - 1 {
+            stats.record_hand(OpponentHand::default());
+        }
+        assert!(stats.profile().is_none());
+
+        stats.record_hand(OpponentHand::default());
+        assert!(stats.profile().is_some());
+    }
+
+    #[test]
+    fn test_opponent_stats_rolling_profile() {
+        let mut stats = OpponentStats::default();
+        let station = OpponentHand {
+            vpip: true,
+            aggressive_actions: 0,
+            passive_actions: 2,
+        };
+        let nit = OpponentHand {
+            vpip: false,
+            aggressive_actions: 1,
+            passive_actions: 0,
+        };
+        for _ in 0..OPPONENT_HISTORY_SIZE {
+            stats.record_hand(station);
+        }
+        let profile = stats.profile().unwrap();
+        assert_eq!(profile.vpip, 1.0);
+        assert_eq!(profile.aggression_factor, 0.0);
+
+        // Only the most recent hands count
+        for _ in 0..OPPONENT_HISTORY_SIZE / 2 {
+            stats.record_hand(nit);
+        }
+        let profile = stats.profile().unwrap();
+        assert_eq!(profile.vpip, 0.5);
+        assert_eq!(profile.hands_observed, OPPONENT_HISTORY_SIZE);
+    }
+
+    #[test]
+    fn test_opponent_profile_combine() {
+        let loose = OpponentProfile {
+            vpip: 0.6,
+            aggression_factor: 0.5,
+            hands_observed: 30,
+        };
+        let tight = OpponentProfile {
+            vpip: 0.2,
+            aggression_factor: 2.5,
+            hands_observed: 10,
+        };
+        let combined = OpponentProfile::combine(&[loose, tight]).unwrap();
+        assert!((combined.vpip - 0.5).abs() < 1e-6);
+        assert!((combined.aggression_factor - 1.0).abs() < 1e-6);
+        assert_eq!(combined.hands_observed, 40);
+        assert!(OpponentProfile::combine(&[]).is_none());
+    }
+
     #[test]
     fn test_bot_player_new() {
         let config = BotConfig {
@@ -441,6 +684,7 @@ mod tests {
             difficulty: BotDifficulty::Standard,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let bot = BotPlayer::new(config.clone());
@@ -459,6 +703,7 @@ mod tests {
             difficulty: BotDifficulty::Standard,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let bot = BotPlayer::new(config);
@@ -480,6 +725,7 @@ mod tests {
             difficulty: BotDifficulty::Easy,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let mut bot = BotPlayer::new(config);
@@ -498,6 +744,7 @@ mod tests {
             difficulty: BotDifficulty::Easy,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let mut bot = BotPlayer::new(config);
@@ -516,6 +763,7 @@ mod tests {
             difficulty: BotDifficulty::Tag,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let mut bot = BotPlayer::new(config);
@@ -533,6 +781,7 @@ mod tests {
             difficulty: BotDifficulty::Tag,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let mut bot = BotPlayer::new(config);
@@ -549,6 +798,7 @@ mod tests {
             difficulty: BotDifficulty::Standard,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let mut bot = BotPlayer::new(config);
@@ -567,6 +817,7 @@ mod tests {
             difficulty: BotDifficulty::Easy,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let mut bot = BotPlayer::new(config);
@@ -584,6 +835,7 @@ mod tests {
             difficulty: BotDifficulty::Standard,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let mut bot = BotPlayer::new(config);
@@ -600,6 +852,7 @@ mod tests {
             difficulty: BotDifficulty::Standard,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let mut bot = BotPlayer::new(config);
@@ -635,6 +888,7 @@ mod tests {
             difficulty: BotDifficulty::Easy,
             table_id: 1,
             starting_chips: 1000,
+            avatar_seed: None,
         };
 
         let cloned = config.clone();
@@ -126,8 +126,9 @@ impl UserRepository for PgUserRepository {
 
     async fn find_by_username(&self, username: &str) -> AuthResult<Option<User>> {
         let row = sqlx::query(
-            "SELECT id, username, display_name, avatar_url, email, country, timezone,
-                    tos_version, privacy_version, is_active, is_admin, created_at, last_login
+            "SELECT id, username, display_name, avatar_url, email, email_verified, country,
+                    timezone, tos_version, privacy_version, is_active, is_admin,
+                    created_at, last_login
              FROM users WHERE username = $1",
         )
         .bind(username)
@@ -140,6 +141,7 @@ impl UserRepository for PgUserRepository {
             display_name: r.get("display_name"),
             avatar_url: r.get("avatar_url"),
             email: r.get("email"),
+            email_verified: r.get("email_verified"),
             country: r.get("country"),
             timezone: r.get("timezone"),
             tos_version: r.get("tos_version"),
@@ -155,8 +157,9 @@ impl UserRepository for PgUserRepository {
 
     async fn find_by_id(&self, user_id: i64) -> AuthResult<Option<User>> {
         let row = sqlx::query(
-            "SELECT id, username, display_name, avatar_url, email, country, timezone,
-                    tos_version, privacy_version, is_active, is_admin, created_at, last_login
+            "SELECT id, username, display_name, avatar_url, email, email_verified, country,
+                    timezone, tos_version, privacy_version, is_active, is_admin,
+                    created_at, last_login
              FROM users WHERE id = $1",
         )
         .bind(user_id)
@@ -169,6 +172,7 @@ impl UserRepository for PgUserRepository {
             display_name: r.get("display_name"),
             avatar_url: r.get("avatar_url"),
             email: r.get("email"),
+            email_verified: r.get("email_verified"),
             country: r.get("country"),
             timezone: r.get("timezone"),
             tos_version: r.get("tos_version"),
@@ -249,6 +253,7 @@ pub mod mock {
                 display_name: display_name.to_string(),
                 avatar_url: None,
                 email: None,
+                email_verified: false,
                 country: None,
                 timezone: None,
                 tos_version: 1,
@@ -407,6 +412,7 @@ pub mod mock {
                 display_name: "Preloaded User".to_string(),
                 avatar_url: None,
                 email: Some("test@example.com".to_string()),
+                email_verified: false,
                 country: Some("US".to_string()),
                 timezone: Some("UTC".to_string()),
                 tos_version: 1,
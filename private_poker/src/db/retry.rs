@@ -0,0 +1,415 @@
+//! Bounded retries and a circuit breaker for database calls.
+//!
+//! Transient failures (pool timeouts, dropped connections, serialization
+//! conflicts) are retried a few times with exponential backoff. Repeated
+//! failures trip a shared circuit breaker so callers fail fast instead of
+//! piling onto a database that's already struggling; after a cooldown a
+//! single trial call is let through to probe whether it has recovered.
+//!
+//! Retrying is only safe for idempotent operations. Wallet transfers qualify
+//! because every transfer carries an idempotency key that's reused across
+//! attempts.
+
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Default number of attempts, including the first one
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry (doubled on each later retry)
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(50);
+
+/// Default upper bound on the delay between retries
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Default number of consecutive failures that opens the circuit
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Default time the circuit stays open before a trial call is allowed
+pub const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// Errors that can be retried and reported by the circuit breaker
+pub trait RetryableError {
+    /// Whether the failed call might succeed if it's tried again
+    fn is_transient(&self) -> bool;
+
+    /// The error returned without touching the database while the circuit is open
+    fn circuit_open() -> Self;
+}
+
+/// Whether a database error is worth retrying
+///
+/// Connection-level failures and Postgres serialization failures or
+/// deadlocks are transient; constraint violations and bad queries aren't.
+pub fn is_transient_db_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::PoolTimedOut
+        | sqlx::Error::Io(_)
+        | sqlx::Error::Tls(_)
+        | sqlx::Error::Protocol(_)
+        | sqlx::Error::WorkerCrashed => true,
+        sqlx::Error::Database(db_err) => {
+            matches!(db_err.code().as_deref(), Some("40001" | "40P01"))
+        }
+        _ => false,
+    }
+}
+
+/// How many times to try a call and how long to wait in between
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between retries
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay to wait after the given (1-based) failed attempt
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Observable state of a circuit breaker
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through normally
+    Closed,
+    /// Calls fail fast without touching the database
+    Open,
+    /// The cooldown has passed; one trial call at a time may go through
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+struct BreakerInner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// When the in-flight trial call started, while half-open
+    probe_started_at: Option<Instant>,
+}
+
+/// Circuit breaker tracking consecutive database failures
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    inner: Mutex<BreakerInner>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD, DEFAULT_OPEN_DURATION)
+    }
+}
+
+impl CircuitBreaker {
+    /// Create a breaker that opens after `failure_threshold` consecutive
+    /// failures and stays open for `open_duration`
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            inner: Mutex::new(BreakerInner::default()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Current state of the breaker
+    pub fn state(&self) -> CircuitState {
+        match self.lock().opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.open_duration => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Whether a call may go through right now
+    ///
+    /// While half-open only one trial call is allowed at a time; a `true`
+    /// claims the trial, and the caller must report how it went with
+    /// [`record_success`](Self::record_success),
+    /// [`record_failure`](Self::record_failure) or
+    /// [`release_probe`](Self::release_probe). A trial that never reports
+    /// back (e.g. its future was dropped) is given up on after another
+    /// cooldown.
+    pub fn allows_call(&self) -> bool {
+        let mut inner = self.lock();
+        match inner.opened_at {
+            None => true,
+            Some(opened_at) if opened_at.elapsed() < self.open_duration => false,
+            Some(_) => match inner.probe_started_at {
+                Some(started_at) if started_at.elapsed() < self.open_duration => false,
+                _ => {
+                    inner.probe_started_at = Some(Instant::now());
+                    true
+                }
+            },
+        }
+    }
+
+    /// Record a successful call, closing the circuit
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_started_at = None;
+    }
+
+    /// End a trial call that says nothing about the database's health
+    ///
+    /// The circuit stays half-open and the next call becomes the trial.
+    pub fn release_probe(&self) {
+        self.lock().probe_started_at = None;
+    }
+
+    /// Record a failed call, opening the circuit once the threshold is hit
+    ///
+    /// A failed trial call while half-open reopens the circuit for another
+    /// full cooldown.
+    pub fn record_failure(&self) {
+        let mut inner = self.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        if inner.opened_at.is_some() || inner.consecutive_failures >= self.failure_threshold {
+            inner.opened_at = Some(Instant::now());
+        }
+        inner.probe_started_at = None;
+    }
+}
+
+/// Retry policy paired with a shared circuit breaker
+///
+/// Cloning shares the breaker, so every clone sees the same database health.
+#[derive(Clone, Debug, Default)]
+pub struct DbRetry {
+    policy: RetryPolicy,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl DbRetry {
+    /// Create a retry wrapper from a policy and breaker
+    pub fn new(policy: RetryPolicy, breaker: Arc<CircuitBreaker>) -> Self {
+        Self { policy, breaker }
+    }
+
+    /// The shared circuit breaker
+    pub fn breaker(&self) -> &CircuitBreaker {
+        &self.breaker
+    }
+
+    /// Run `op`, retrying transient failures with backoff
+    ///
+    /// `op` receives the 1-based attempt number so callers can tell a first
+    /// try from a retry. Non-transient errors are returned immediately and
+    /// don't count against the breaker. While the circuit is open the call
+    /// fails fast with [`RetryableError::circuit_open`].
+    pub async fn run<T, E, F, Fut>(&self, mut op: F) -> Result<T, E>
+    where
+        E: RetryableError,
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let max_attempts = self.policy.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            if !self.breaker.allows_call() {
+                return Err(E::circuit_open());
+            }
+
+            match op(attempt).await {
+                Ok(value) => {
+                    self.breaker.record_success();
+                    return Ok(value);
+                }
+                Err(err) if err.is_transient() => {
+                    self.breaker.record_failure();
+                    if attempt >= max_attempts {
+                        return Err(err);
+                    }
+                    log::warn!(
+                        "Transient database error (attempt {}/{}), retrying",
+                        attempt,
+                        max_attempts
+                    );
+                    tokio::time::sleep(self.policy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                Err(err) => {
+                    self.breaker.release_probe();
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, PartialEq)]
+    enum TestError {
+        Flaky,
+        Fatal,
+        CircuitOpen,
+    }
+
+    impl RetryableError for TestError {
+        fn is_transient(&self) -> bool {
+            matches!(self, TestError::Flaky)
+        }
+
+        fn circuit_open() -> Self {
+            TestError::CircuitOpen
+        }
+    }
+
+    fn fast_retry(max_attempts: u32, breaker: CircuitBreaker) -> DbRetry {
+        DbRetry::new(
+            RetryPolicy {
+                max_attempts,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(2),
+            },
+            Arc::new(breaker),
+        )
+    }
+
+    /// Stand-in for a pool that drops the first `failures` calls.
+    struct FlakyPool {
+        failures: u32,
+        calls: AtomicU32,
+    }
+
+    impl FlakyPool {
+        async fn query(&self) -> Result<i64, TestError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call <= self.failures {
+                Err(TestError::Flaky)
+            } else {
+                Ok(42)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn flaky_pool_succeeds_on_second_attempt() {
+        let pool = FlakyPool {
+            failures: 1,
+            calls: AtomicU32::new(0),
+        };
+        let retry = fast_retry(3, CircuitBreaker::default());
+        let result = retry.run(|_| pool.query()).await;
+        assert_eq!(result, Ok(42));
+        assert_eq!(pool.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(retry.breaker().state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn retries_are_bounded() {
+        let pool = FlakyPool {
+            failures: u32::MAX,
+            calls: AtomicU32::new(0),
+        };
+        let retry = fast_retry(3, CircuitBreaker::default());
+        assert_eq!(retry.run(|_| pool.query()).await, Err(TestError::Flaky));
+        assert_eq!(pool.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn non_transient_errors_are_not_retried() {
+        let calls = AtomicU32::new(0);
+        let retry = fast_retry(3, CircuitBreaker::default());
+        let result: Result<(), _> = retry
+            .run(|_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Err(TestError::Fatal) }
+            })
+            .await;
+        assert_eq!(result, Err(TestError::Fatal));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn open_circuit_fails_fast_until_cooldown() {
+        let pool = FlakyPool {
+            failures: 2,
+            calls: AtomicU32::new(0),
+        };
+        let retry = fast_retry(2, CircuitBreaker::new(2, Duration::from_millis(20)));
+        assert_eq!(retry.run(|_| pool.query()).await, Err(TestError::Flaky));
+        assert_eq!(retry.breaker().state(), CircuitState::Open);
+
+        assert_eq!(
+            retry.run(|_| pool.query()).await,
+            Err(TestError::CircuitOpen)
+        );
+        assert_eq!(pool.calls.load(Ordering::SeqCst), 2);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(retry.breaker().state(), CircuitState::HalfOpen);
+        assert_eq!(retry.run(|_| pool.query()).await, Ok(42));
+        assert_eq!(retry.breaker().state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn half_open_allows_a_single_probe() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+        breaker.record_failure();
+        assert!(!breaker.allows_call());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.allows_call());
+        assert!(!breaker.allows_call());
+
+        // A failed probe reopens the circuit for another cooldown
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allows_call());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.allows_call());
+        breaker.release_probe();
+        assert!(breaker.allows_call());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allows_call());
+        assert!(breaker.allows_call());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_millis(150),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(50));
+        assert_eq!(policy.backoff(2), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3), Duration::from_millis(150));
+    }
+}
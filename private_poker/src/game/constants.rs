@@ -2,3 +2,9 @@
 pub const MAX_PLAYERS: usize = 10;
 pub const DEFAULT_MAX_USERS: usize = MAX_PLAYERS + 6;
 pub const MAX_USER_INPUT_LENGTH: usize = 32;
+// Players sitting out longer than this are removed to free up their seats.
+pub const DEFAULT_MAX_SIT_OUT_HANDS: usize = 10;
+// Runouts sampled to work out equities for an equity chop before the turn.
+pub const EQUITY_CHOP_ITERATIONS: usize = 10_000;
+// Bets allowed on each street of a fixed-limit game: a bet and three raises.
+pub const FIXED_LIMIT_MAX_BETS_PER_STREET: usize = 4;
@@ -1,11 +1,12 @@
-use rand::{rng, seq::SliceRandom};
+use rand::{Rng, rng, seq::SliceRandom};
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     borrow::Borrow,
-    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     fmt::{self},
     hash::{Hash, Hasher},
     mem::discriminant,
+    str::FromStr,
     sync::Arc,
 };
 
@@ -57,6 +58,72 @@ impl fmt::Display for Card {
     }
 }
 
+impl Card {
+    /// Return the card's two-character shorthand (e.g., "As", "Td", "2c"),
+    /// the inverse of parsing a card with `str::parse`.
+    #[must_use]
+    pub fn to_shorthand(&self) -> String {
+        let value = match self.0 {
+            1 | 14 => 'A',
+            10 => 'T',
+            11 => 'J',
+            12 => 'Q',
+            13 => 'K',
+            v @ 2..=9 => char::from(b'0' + v),
+            _ => '?',
+        };
+        let suit = match self.1 {
+            Suit::Club => 'c',
+            Suit::Spade => 's',
+            Suit::Diamond => 'd',
+            Suit::Heart => 'h',
+            Suit::Wild => 'w',
+        };
+        format!("{value}{suit}")
+    }
+}
+
+/// Errors from parsing a card from its shorthand.
+#[derive(Debug, Eq, thiserror::Error, PartialEq)]
+pub enum CardParseError {
+    #[error("card shorthand must be exactly 2 characters, got {0:?}")]
+    InvalidLength(String),
+    #[error("invalid card rank {0:?}, expected one of 23456789TJQKA")]
+    InvalidRank(char),
+    #[error("invalid card suit {0:?}, expected one of cdhs")]
+    InvalidSuit(char),
+}
+
+impl FromStr for Card {
+    type Err = CardParseError;
+
+    /// Parse a card from its shorthand (e.g., "As", "Td", "2c"). Aces
+    /// are parsed as high aces (14).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (Some(value), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(CardParseError::InvalidLength(s.to_string()));
+        };
+        let value = match value.to_ascii_uppercase() {
+            v @ '2'..='9' => v as Value - b'0',
+            'T' => 10,
+            'J' => 11,
+            'Q' => 12,
+            'K' => 13,
+            'A' => 14,
+            _ => return Err(CardParseError::InvalidRank(value)),
+        };
+        let suit = match suit.to_ascii_lowercase() {
+            'c' => Suit::Club,
+            's' => Suit::Spade,
+            'd' => Suit::Diamond,
+            'h' => Suit::Heart,
+            _ => return Err(CardParseError::InvalidSuit(suit)),
+        };
+        Ok(Self(value, suit))
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Rank {
     HighCard,
@@ -87,19 +154,188 @@ impl fmt::Display for Rank {
     }
 }
 
+/// Strength of a hand as a single number from `functional::eval_fast`.
+/// Stronger hands have higher ranks, and hands that tie have the same
+/// rank.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct HandRank(pub u32);
+
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct SubHand {
     pub rank: Rank,
     pub values: Vec<Value>,
 }
 
-#[derive(Debug)]
+impl SubHand {
+    /// Describe the subhand, e.g., "Full house, Queens full of Fives".
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let values = &self.values;
+        match self.rank {
+            Rank::HighCard => format!("{} high", value_name(values[0])),
+            Rank::OnePair => format!("Pair of {}", plural_value_name(values[0])),
+            Rank::TwoPair => format!(
+                "Two pair, {} and {}",
+                plural_value_name(values[0]),
+                plural_value_name(values[2])
+            ),
+            Rank::ThreeOfAKind => format!("Three of a kind, {}", plural_value_name(values[0])),
+            Rank::Straight => format!("Straight, {} high", value_name(values[0])),
+            Rank::Flush => format!("Flush, {} high", value_name(values[0])),
+            Rank::FullHouse => format!(
+                "Full house, {} full of {}",
+                plural_value_name(values[0]),
+                plural_value_name(values[3])
+            ),
+            Rank::FourOfAKind => format!("Four of a kind, {}", plural_value_name(values[0])),
+            Rank::StraightFlush if values[0] == 14 => "Royal flush".to_string(),
+            Rank::StraightFlush => format!("Straight flush, {} high", value_name(values[0])),
+        }
+    }
+}
+
+fn value_name(value: Value) -> &'static str {
+    match value {
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        _ => "Ace",
+    }
+}
+
+fn plural_value_name(value: Value) -> String {
+    match value {
+        6 => "Sixes".to_string(),
+        value => format!("{}s", value_name(value)),
+    }
+}
+
+/// Poker variants, changing which cards are in the deck, how many hole
+/// cards are dealt, and how hands are ranked.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameVariant {
+    #[default]
+    TexasHoldem,
+    /// Six-plus Hold'em, played without the 2s through 5s. Flushes are
+    /// harder to make than full houses so they beat full houses, and
+    /// A-6-7-8-9 is the lowest straight.
+    ShortDeck,
+    /// Omaha Hold'em, where players are dealt four hole cards and their
+    /// hand has to use exactly two of them with exactly three board
+    /// cards. Played pot-limit, it's Pot-Limit Omaha (PLO).
+    Omaha,
+}
+
+impl GameVariant {
+    /// Return the lowest card value in the deck (besides aces).
+    fn min_value(self) -> Value {
+        match self {
+            Self::TexasHoldem | Self::Omaha => 2,
+            Self::ShortDeck => 6,
+        }
+    }
+
+    /// Return the number of hole cards each player is dealt.
+    #[must_use]
+    pub fn num_hole_cards(self) -> usize {
+        match self {
+            Self::TexasHoldem | Self::ShortDeck => 2,
+            Self::Omaha => 4,
+        }
+    }
+
+    /// Return the value aces take when they're played low. Low aces
+    /// sit right below the lowest card so they make the lowest straight.
+    #[must_use]
+    pub fn low_ace_value(self) -> Value {
+        self.min_value() - 1
+    }
+
+    /// Return the number of cards in the deck.
+    #[must_use]
+    pub fn deck_size(self) -> usize {
+        4 * usize::from(15 - self.min_value())
+    }
+
+    /// Return how strong a hand rank is compared to other ranks.
+    #[must_use]
+    pub fn rank_strength(self, rank: Rank) -> u8 {
+        match (self, rank) {
+            (Self::ShortDeck, Rank::Flush) => Rank::FullHouse as u8,
+            (Self::ShortDeck, Rank::FullHouse) => Rank::Flush as u8,
+            (_, rank) => rank as u8,
+        }
+    }
+}
+
+/// Limits on how much players can bet and raise.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BettingStructure {
+    /// Players can bet anything from the minimum raise up to their
+    /// whole stack.
+    #[default]
+    NoLimit,
+    /// Raises are capped at the size of the pot after calling.
+    PotLimit,
+    /// Bets and raises are the small bet preflop and on the flop, and
+    /// the big bet on the turn and river. Only so many bets are allowed
+    /// on each street.
+    FixedLimit { small_bet: Usd, big_bet: Usd },
+}
+
+impl fmt::Display for BettingStructure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoLimit => write!(f, "no_limit"),
+            Self::PotLimit => write!(f, "pot_limit"),
+            Self::FixedLimit { small_bet, big_bet } => {
+                write!(f, "fixed_limit ${small_bet}/${big_bet}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for GameVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let repr = match self {
+            Self::TexasHoldem => "texas_holdem",
+            Self::ShortDeck => "short_deck",
+            Self::Omaha => "omaha",
+        };
+        write!(f, "{repr}")
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Deck {
-    cards: [Card; 52],
+    cards: Vec<Card>,
     pub deck_idx: usize,
 }
 
 impl Deck {
+    /// Create an unshuffled deck with the cards used by the variant.
+    #[must_use]
+    pub fn new(variant: GameVariant) -> Self {
+        let mut cards = Vec::with_capacity(variant.deck_size());
+        for value in std::iter::once(1).chain(variant.min_value()..14) {
+            for suit in [Suit::Club, Suit::Spade, Suit::Diamond, Suit::Heart] {
+                cards.push(Card(value, suit));
+            }
+        }
+        Self { cards, deck_idx: 0 }
+    }
+
     pub fn deal_card(&mut self) -> Card {
         // Bounds check to prevent deck exhaustion
         // This should NEVER happen in valid gameplay:
@@ -119,24 +355,33 @@ impl Deck {
         card
     }
 
+    /// Number of cards that haven't been dealt yet.
+    #[must_use]
+    pub fn cards_left(&self) -> usize {
+        self.cards.len() - self.deck_idx
+    }
+
+    /// Return the next cards that would be dealt without dealing them.
+    pub fn peek_cards(&self, num_cards: usize) -> Vec<Card> {
+        let end = (self.deck_idx + num_cards).min(self.cards.len());
+        self.cards[self.deck_idx..end].to_vec()
+    }
+
     pub fn shuffle(&mut self) {
-        self.cards.shuffle(&mut rng());
+        self.shuffle_with(&mut rng());
+    }
+
+    /// Shuffle the deck using the given random number generator. Useful
+    /// for reproducing a deal from a seeded RNG.
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
         self.deck_idx = 0;
     }
 }
 
 impl Default for Deck {
     fn default() -> Self {
-        let mut cards: [Card; 52] = [Card(0, Suit::Wild); 52];
-        for (i, value) in (1u8..14u8).enumerate() {
-            for (j, suit) in [Suit::Club, Suit::Spade, Suit::Diamond, Suit::Heart]
-                .into_iter()
-                .enumerate()
-            {
-                cards[4 * i + j] = Card(value, suit);
-            }
-        }
-        Self { cards, deck_idx: 0 }
+        Self::new(GameVariant::default())
     }
 }
 
@@ -193,12 +438,35 @@ pub type SeatIndex = usize;
 
 /// Play positions used for tracking who is paying what blinds and whose
 /// turn is next.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct PlayPositions {
     pub small_blind_idx: SeatIndex,
     pub big_blind_idx: SeatIndex,
     pub starting_action_idx: SeatIndex,
     pub next_action_idx: Option<SeatIndex>,
+    /// The player that posted a straddle this hand, if any. The straddler
+    /// acts last preflop, so the preflop action starts to their left.
+    pub straddle_idx: Option<SeatIndex>,
+    /// Seat the button is on. It can be an empty seat (a dead button) if
+    /// the player that posted the small blind last hand left.
+    #[serde(default)]
+    pub button_seat: Option<SeatIndex>,
+    /// Seat the small blind belongs to this hand, even if it's dead.
+    #[serde(default)]
+    pub small_blind_seat: Option<SeatIndex>,
+    /// Seat that posted the big blind this hand. The blinds move from here
+    /// next hand, so players leaving between hands don't shift them.
+    #[serde(default)]
+    pub big_blind_seat: Option<SeatIndex>,
+    /// Whether no one posts the small blind this hand because the player
+    /// that owed it left or is sitting out. `small_blind_idx` is the big
+    /// blind when it's dead.
+    #[serde(default)]
+    pub dead_small_blind: bool,
+    /// Whether only two players were dealt in. The button posts the small
+    /// blind and acts first preflop, but acts last on every later street.
+    #[serde(default)]
+    pub heads_up: bool,
 }
 
 impl Default for PlayPositions {
@@ -208,11 +476,17 @@ impl Default for PlayPositions {
             big_blind_idx: 1,
             starting_action_idx: 2,
             next_action_idx: None,
+            straddle_idx: None,
+            button_seat: None,
+            small_blind_seat: None,
+            big_blind_seat: None,
+            dead_small_blind: false,
+            heads_up: false,
         }
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct PlayerCounts {
     /// Count of the number of players active in a hand.
     /// All-in and folding are considered INACTIVE since they
@@ -227,7 +501,7 @@ pub struct PlayerCounts {
     pub num_called: usize,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct PlayerQueues {
     /// Queue of users that've been voted to be kicked. We can't
     /// safely remove them from the game mid gameplay, so we instead queue
@@ -244,6 +518,13 @@ pub struct PlayerQueues {
     /// Queue of users whose money we'll reset. We can't safely
     /// reset them mid gameplay, so we instead queue them for reset.
     pub to_reset: BTreeSet<Username>,
+    /// Queue of users that want to straddle the next time they're
+    /// under the gun.
+    pub to_straddle: BTreeSet<Username>,
+    /// Queue of users that want to move to a different seat. We can't
+    /// safely move them mid gameplay, so we instead queue them to move
+    /// at the start of the next game.
+    pub to_move: BTreeMap<Username, SeatIndex>,
 }
 
 // By default, a player will be cleaned if they fold 60 rounds with the big
@@ -252,15 +533,21 @@ pub const DEFAULT_BUY_IN: Usd = 600;
 pub const DEFAULT_MIN_BIG_BLIND: Usd = DEFAULT_BUY_IN / 60;
 pub const DEFAULT_MIN_SMALL_BLIND: Usd = DEFAULT_MIN_BIG_BLIND / 2;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Blinds {
     pub small: Usd,
     pub big: Usd,
+    /// Forced bet collected from every player before the blinds.
+    pub ante: Usd,
 }
 
 impl fmt::Display for Blinds {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let repr = format!("${}/{}", self.small, self.big);
+        let repr = if self.ante > 0 {
+            format!("${}/{} (${} ante)", self.small, self.big, self.ante)
+        } else {
+            format!("${}/{}", self.small, self.big)
+        };
         write!(f, "{repr}")
     }
 }
@@ -322,7 +609,12 @@ pub enum ActionChoice {
     Call(Usd),
     Check,
     Fold,
-    Raise(Usd),
+    /// Raise by betting at least `min` and at most `max`, which is
+    /// capped at the player's stack.
+    Raise {
+        min: Usd,
+        max: Usd,
+    },
 }
 
 // Can't really convert a usize into an ActionChoice safely, and it doesn't
@@ -336,7 +628,7 @@ impl Into<usize> for ActionChoice {
             Self::Call(_) => 1,
             Self::Check => 2,
             Self::Fold => 3,
-            Self::Raise(_) => 4,
+            Self::Raise { .. } => 4,
         }
     }
 }
@@ -348,7 +640,7 @@ impl fmt::Display for ActionChoice {
             Self::Call(amount) => format!("call (== ${amount})"),
             Self::Check => "check".to_string(),
             Self::Fold => "fold".to_string(),
-            Self::Raise(amount) => format!("raise (>= ${amount})"),
+            Self::Raise { min, max } => format!("raise (>= ${min}, <= ${max})"),
         };
         write!(f, "{repr}")
     }
@@ -380,7 +672,7 @@ impl From<ActionChoice> for Action {
             ActionChoice::Call(_) => Self::Call,
             ActionChoice::Check => Self::Check,
             ActionChoice::Fold => Self::Fold,
-            ActionChoice::Raise(amount) => Self::Raise(Some(amount)),
+            ActionChoice::Raise { min, .. } => Self::Raise(Some(min)),
         }
     }
 }
@@ -398,7 +690,7 @@ impl ActionChoices {
             Action::Call => ActionChoice::Call(0),
             Action::Check => ActionChoice::Check,
             Action::Fold => ActionChoice::Fold,
-            Action::Raise(_) => ActionChoice::Raise(0),
+            Action::Raise(_) => ActionChoice::Raise { min: 0, max: 0 },
         };
         self.0.contains(&action_choice)
     }
@@ -435,6 +727,63 @@ where
     }
 }
 
+/// What a player can do on their turn, along with the amounts they
+/// need to make a decision.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TurnInfo {
+    pub action_choices: ActionChoices,
+    /// Money the player has to put in to call, capped at their stack.
+    pub call_amount: Usd,
+    /// Smallest raise the player can make, or nothing if they can't
+    /// raise (e.g., an all-in for less didn't reopen the betting).
+    pub min_raise: Option<Usd>,
+    /// Biggest raise the player can make, capped at their stack, or
+    /// nothing if they can't raise.
+    #[serde(default)]
+    pub max_raise: Option<Usd>,
+    pub pot_size: Usd,
+    /// Share of the pot after calling that the call makes up. Calling
+    /// is profitable if the player's equity is higher than this.
+    pub pot_odds: f64,
+}
+
+impl TurnInfo {
+    #[must_use]
+    pub fn new(action_choices: ActionChoices, call_amount: Usd, pot_size: Usd) -> Self {
+        let (min_raise, max_raise) = action_choices
+            .0
+            .iter()
+            .find_map(|choice| match choice {
+                ActionChoice::Raise { min, max } => Some((Some(*min), Some(*max))),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let pot_odds = if call_amount == 0 {
+            0.0
+        } else {
+            f64::from(call_amount) / (f64::from(pot_size) + f64::from(call_amount))
+        };
+        Self {
+            action_choices,
+            call_amount,
+            min_raise,
+            max_raise,
+            pot_size,
+            pot_odds,
+        }
+    }
+
+    /// Return the legal raise closest to `amount`, or nothing if the
+    /// player can't raise.
+    #[must_use]
+    pub fn clamp_raise(&self, amount: Usd) -> Option<Usd> {
+        let min_raise = self.min_raise?;
+        Some(self.max_raise.map_or(amount.max(min_raise), |max_raise| {
+            amount.clamp(min_raise, max_raise)
+        }))
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum BetAction {
     AllIn,
@@ -491,12 +840,14 @@ impl fmt::Display for PlayerState {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Player {
     pub user: User,
     pub state: PlayerState,
     pub cards: Vec<Card>,
     pub showing: bool,
+    /// Whether the player declined to show their hand at showdown.
+    pub mucked: bool,
     pub seat_idx: usize,
 }
 
@@ -508,6 +859,7 @@ impl Player {
             state: PlayerState::Wait,
             cards: Vec::with_capacity(2),
             showing: false,
+            mucked: false,
             seat_idx,
         }
     }
@@ -516,13 +868,16 @@ impl Player {
         self.state = PlayerState::Wait;
         self.cards.clear();
         self.showing = false;
+        self.mucked = false;
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Pot {
     // Map seat indices (players) to their investment in the pot.
     pub investments: HashMap<usize, Usd>,
+    // Each player's investment when the current street started.
+    pub street_start_investments: HashMap<usize, Usd>,
 }
 
 impl Default for Pot {
@@ -556,6 +911,21 @@ impl Pot {
         *self.investments.get(&player_idx).unwrap_or(&0)
     }
 
+    /// Return the amount the player has put into the pot on the current
+    /// street.
+    #[must_use]
+    pub fn get_street_investment_by_player_idx(&self, player_idx: usize) -> Usd {
+        let street_start = *self.street_start_investments.get(&player_idx).unwrap_or(&0);
+        self.get_investment_by_player_idx(player_idx)
+            .saturating_sub(street_start)
+    }
+
+    /// Start a new street, so bets made before it no longer count as
+    /// committed on the current street.
+    pub fn start_street(&mut self) {
+        self.street_start_investments.clone_from(&self.investments);
+    }
+
     /// Return the minimum amount a player has to bet in order for their
     /// raise to be considered a valid raise.
     #[must_use]
@@ -563,6 +933,14 @@ impl Pot {
         2 * self.get_call() - self.get_investment_by_player_idx(player_idx)
     }
 
+    /// Return the most a player can bet with a pot-limit raise: a call,
+    /// plus a raise the size of the pot after that call.
+    #[must_use]
+    pub fn get_max_pot_limit_raise_by_player_idx(&self, player_idx: usize) -> Usd {
+        let call = self.get_call_by_player_idx(player_idx);
+        call + self.get_size() + call
+    }
+
     #[must_use]
     pub fn get_size(&self) -> Usd {
         self.investments.values().sum()
@@ -577,10 +955,80 @@ impl Pot {
     pub fn new(max_players: usize) -> Self {
         Self {
             investments: HashMap::with_capacity(max_players),
+            street_start_investments: HashMap::with_capacity(max_players),
+        }
+    }
+
+    /// Split the pot into a main pot and side pots, ordered from the main
+    /// pot (contested by everyone still in the hand) to the last side pot.
+    ///
+    /// Each pot is capped at an investment level of a player that hasn't
+    /// folded. Folded players' investments are dead money that still count
+    /// towards each pot up to their investment, but they're never eligible
+    /// to win. Dead money above the largest contested investment is added
+    /// to the last side pot.
+    #[must_use]
+    pub fn split_into_side_pots(&self, folded: &HashSet<usize>) -> Vec<SidePot> {
+        let mut levels: Vec<Usd> = self
+            .investments
+            .iter()
+            .filter(|(player_idx, investment)| !folded.contains(player_idx) && **investment > 0)
+            .map(|(_, investment)| *investment)
+            .collect();
+        levels.sort_unstable();
+        levels.dedup();
+
+        let mut side_pots: Vec<SidePot> = Vec::with_capacity(levels.len());
+        let mut prev_level: Usd = 0;
+        for level in levels {
+            let amount = self
+                .investments
+                .values()
+                .map(|investment| (*investment).min(level).saturating_sub(prev_level))
+                .sum();
+            let mut eligible: Vec<usize> = self
+                .investments
+                .iter()
+                .filter(|(player_idx, investment)| {
+                    !folded.contains(player_idx) && **investment >= level
+                })
+                .map(|(player_idx, _)| *player_idx)
+                .collect();
+            eligible.sort_unstable();
+            side_pots.push(SidePot {
+                amount,
+                cap: level,
+                eligible,
+            });
+            prev_level = level;
+        }
+
+        let dead_money: Usd = self
+            .investments
+            .values()
+            .map(|investment| investment.saturating_sub(prev_level))
+            .sum();
+        if dead_money > 0
+            && let Some(last_pot) = side_pots.last_mut()
+        {
+            last_pot.amount += dead_money;
+            last_pot.cap = self.get_call();
         }
+        side_pots
     }
 }
 
+/// A main pot or side pot that can be won by a subset of players.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SidePot {
+    /// Total amount of money in the pot.
+    pub amount: Usd,
+    /// Largest investment that contributes to this pot.
+    pub cap: Usd,
+    /// Sorted player indices that're eligible to win the pot.
+    pub eligible: Vec<usize>,
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum Vote {
     // Vote to kick another user.
@@ -600,14 +1048,25 @@ impl fmt::Display for Vote {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct PlayerView {
     pub user: User,
     pub state: PlayerState,
     pub cards: std::sync::Arc<Vec<Card>>,
+    /// Whether the player is sitting out and being dealt out of hands.
+    pub sitting_out: bool,
+    /// Seat the player is in.
+    #[serde(default)]
+    pub seat_idx: SeatIndex,
+    /// Chips the player has put in the pot on the current street.
+    #[serde(default)]
+    pub committed_this_street: Usd,
+    /// Chips the player has put in the pot this hand.
+    #[serde(default)]
+    pub total_committed: Usd,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct PotView {
     pub size: Usd,
 }
@@ -618,6 +1077,19 @@ impl fmt::Display for PotView {
     }
 }
 
+/// Most actions kept per street in a game view's action history. Older
+/// actions on a street are dropped first.
+pub const MAX_RECENT_ACTIONS_PER_STREET: usize = 20;
+
+/// An action taken during the current hand, as shown in game views.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RecentAction {
+    pub seat: SeatIndex,
+    pub action: Action,
+    /// Size of the pot after the action.
+    pub pot_size: Usd,
+}
+
 // Helper module for Arc serialization
 mod arc_serde {
     use super::*;
@@ -640,7 +1112,7 @@ mod arc_serde {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GameView {
     #[serde(with = "arc_serde")]
     pub blinds: Arc<Blinds>,
@@ -653,14 +1125,487 @@ pub struct GameView {
     pub players: Vec<PlayerView>,
     #[serde(with = "arc_serde")]
     pub board: Arc<Vec<Card>>,
+    /// Second runout of the board when the pot is run twice. Empty
+    /// otherwise.
+    #[serde(default, with = "arc_serde")]
+    pub second_board: Arc<Vec<Card>>,
     #[serde(with = "arc_serde")]
     pub pot: Arc<PotView>,
     #[serde(with = "arc_serde")]
     pub play_positions: Arc<PlayPositions>,
+    /// Actions taken so far this hand, oldest first.
+    #[serde(default, with = "arc_serde")]
+    pub recent_actions: Arc<Vec<RecentAction>>,
+    /// Table-wide sequence number of the state this view shows. It goes
+    /// up with every state change and is never reset, not even between
+    /// hands.
+    #[serde(default)]
+    pub sequence: u64,
+    /// Card back style to render the table's cards with (`None` for the
+    /// default). It's fixed for the table, so deltas leave it out.
+    #[serde(default)]
+    pub deck_style: Option<String>,
+    /// Variant being played, which decides how many hole cards players
+    /// hold and how their hands are made. Fixed for the table, so deltas
+    /// leave it out.
+    #[serde(default)]
+    pub variant: GameVariant,
+}
+
+impl GameView {
+    /// Return the changes from the previous view to this one. Applying
+    /// the changes to the previous view with `apply_delta` reproduces
+    /// this view.
+    #[must_use]
+    pub fn diff(&self, previous: &GameView) -> GameViewDelta {
+        let (board, new_board_cards) = if self.board.starts_with(&previous.board) {
+            (None, self.board[previous.board.len()..].to_vec())
+        } else {
+            (Some(self.board.to_vec()), Vec::new())
+        };
+        GameViewDelta {
+            blinds: (self.blinds != previous.blinds).then(|| self.blinds.as_ref().clone()),
+            spectators: (self.spectators != previous.spectators)
+                .then(|| self.spectators.as_ref().clone()),
+            waitlist: (self.waitlist != previous.waitlist).then(|| self.waitlist.as_ref().clone()),
+            open_seats: (self.open_seats != previous.open_seats)
+                .then(|| self.open_seats.as_ref().clone()),
+            players: self
+                .players
+                .iter()
+                .enumerate()
+                .filter(|(player_idx, player)| previous.players.get(*player_idx) != Some(*player))
+                .map(|(player_idx, player)| (player_idx, player.clone()))
+                .collect(),
+            num_players: (self.players.len() != previous.players.len())
+                .then_some(self.players.len()),
+            board,
+            new_board_cards,
+            second_board: (self.second_board != previous.second_board)
+                .then(|| self.second_board.to_vec()),
+            pot: (self.pot.size != previous.pot.size).then_some(self.pot.size),
+            play_positions: (self.play_positions != previous.play_positions)
+                .then(|| self.play_positions.as_ref().clone()),
+            recent_actions: (self.recent_actions != previous.recent_actions)
+                .then(|| self.recent_actions.to_vec()),
+        }
+    }
+
+    /// Update the view with the changes from `diff`.
+    pub fn apply_delta(&mut self, delta: GameViewDelta) {
+        if let Some(blinds) = delta.blinds {
+            self.blinds = Arc::new(blinds);
+        }
+        if let Some(spectators) = delta.spectators {
+            self.spectators = Arc::new(spectators);
+        }
+        if let Some(waitlist) = delta.waitlist {
+            self.waitlist = Arc::new(waitlist);
+        }
+        if let Some(open_seats) = delta.open_seats {
+            self.open_seats = Arc::new(open_seats);
+        }
+        if let Some(num_players) = delta.num_players {
+            self.players.truncate(num_players);
+        }
+        for (player_idx, player) in delta.players {
+            match self.players.get_mut(player_idx) {
+                Some(old_player) => *old_player = player,
+                None => self.players.push(player),
+            }
+        }
+        if let Some(board) = delta.board {
+            self.board = Arc::new(board);
+        }
+        if !delta.new_board_cards.is_empty() {
+            Arc::make_mut(&mut self.board).extend(delta.new_board_cards);
+        }
+        if let Some(second_board) = delta.second_board {
+            self.second_board = Arc::new(second_board);
+        }
+        if let Some(size) = delta.pot {
+            self.pot = Arc::new(PotView { size });
+        }
+        if let Some(play_positions) = delta.play_positions {
+            self.play_positions = Arc::new(play_positions);
+        }
+        if let Some(recent_actions) = delta.recent_actions {
+            self.recent_actions = Arc::new(recent_actions);
+        }
+    }
+
+    /// Return every seat at the table in order, with who's in it and
+    /// where the button and blinds are, so clients can lay out the table
+    /// without working it out themselves.
+    #[must_use]
+    pub fn seat_map(&self) -> Vec<SeatSlot> {
+        let positions = &self.play_positions;
+        let num_seats = self
+            .players
+            .iter()
+            .map(|player| player.seat_idx + 1)
+            .chain(self.open_seats.iter().map(|seat_idx| seat_idx + 1))
+            .max()
+            .unwrap_or(0)
+            .max(self.players.len() + self.open_seats.len());
+        (0..num_seats)
+            .map(|seat_idx| SeatSlot {
+                seat_idx,
+                is_open: self.open_seats.contains(&seat_idx),
+                player: self
+                    .players
+                    .iter()
+                    .find(|player| player.seat_idx == seat_idx)
+                    .cloned(),
+                is_button: positions.button_seat == Some(seat_idx),
+                // Heads-up, the button posts the small blind.
+                is_small_blind: !positions.dead_small_blind
+                    && positions.small_blind_seat == Some(seat_idx),
+                is_big_blind: positions.big_blind_seat == Some(seat_idx),
+            })
+            .collect()
+    }
+}
+
+/// A seat at the table, as laid out by `GameView::seat_map`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SeatSlot {
+    pub seat_idx: SeatIndex,
+    /// Whether a player can take the seat.
+    pub is_open: bool,
+    /// Player in the seat, if there's one.
+    pub player: Option<PlayerView>,
+    /// Whether the button is on the seat. It can be on an empty seat (a
+    /// dead button).
+    pub is_button: bool,
+    /// Whether the seat posts the small blind this hand. A dead small
+    /// blind isn't posted by anyone.
+    pub is_small_blind: bool,
+    /// Whether the seat posts the big blind this hand.
+    pub is_big_blind: bool,
+}
+
+/// Changes between two game views. Fields that didn't change are left
+/// out, so sending a delta is much cheaper than sending the whole view.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct GameViewDelta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blinds: Option<Blinds>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spectators: Option<HashSet<User>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waitlist: Option<VecDeque<User>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_seats: Option<VecDeque<usize>>,
+    /// Players whose view changed, paired with their index in the
+    /// players list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub players: Vec<(usize, PlayerView)>,
+    /// Number of players, if players joined or left.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_players: Option<usize>,
+    /// Whole board, if the previous board was cleared for a new hand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board: Option<Vec<Card>>,
+    /// Cards dealt to the board since the previous view.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub new_board_cards: Vec<Card>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub second_board: Option<Vec<Card>>,
+    /// Pot size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pot: Option<Usd>,
+    /// Positions, including whose turn it is next.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub play_positions: Option<PlayPositions>,
+    /// Whole action history, if it changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recent_actions: Option<Vec<RecentAction>>,
+}
+
+impl GameViewDelta {
+    /// Return whether nothing changed between the two views.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Game view update sent to a client, tagged with the view's sequence
+/// number. A delta only applies to the view with sequence number
+/// `base_seq`, so a client whose last update has a different sequence
+/// number missed an update and has to ask for a new snapshot.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameViewUpdate {
+    /// The whole view, sent when a client connects or resyncs.
+    Snapshot { seq: u64, view: GameView },
+    /// Changes since the update with sequence number `base_seq`.
+    Delta {
+        seq: u64,
+        base_seq: u64,
+        delta: Box<GameViewDelta>,
+    },
+}
+
+impl GameViewUpdate {
+    /// Return the update's sequence number.
+    #[must_use]
+    pub fn seq(&self) -> u64 {
+        match self {
+            Self::Snapshot { seq, .. } | Self::Delta { seq, .. } => *seq,
+        }
+    }
 }
 
 pub type GameViews = HashMap<Username, GameView>;
 
+/// Betting rounds of a hand.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    /// Return the betting round for the number of cards on the board.
+    #[must_use]
+    pub fn from_num_community_cards(num_cards: usize) -> Self {
+        match num_cards {
+            0..=2 => Self::Preflop,
+            3 => Self::Flop,
+            4 => Self::Turn,
+            _ => Self::River,
+        }
+    }
+}
+
+impl fmt::Display for Street {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let repr = match self {
+            Self::Preflop => "preflop",
+            Self::Flop => "flop",
+            Self::Turn => "turn",
+            Self::River => "river",
+        };
+        write!(f, "{repr}")
+    }
+}
+
+/// Kinds of actions recorded in a hand history.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryAction {
+    AllIn,
+    Call,
+    Check,
+    Fold,
+    Raise,
+}
+
+impl From<&Action> for HistoryAction {
+    fn from(value: &Action) -> Self {
+        match value {
+            Action::AllIn => Self::AllIn,
+            Action::Call => Self::Call,
+            Action::Check => Self::Check,
+            Action::Fold => Self::Fold,
+            Action::Raise(_) => Self::Raise,
+        }
+    }
+}
+
+/// A player seated at the start of a hand.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SeatRecord {
+    pub seat: SeatIndex,
+    pub username: Username,
+    /// Money the player had before the antes and blinds.
+    pub starting_stack: Usd,
+    /// Only known if the player showed their hand.
+    pub hole_cards: Option<Vec<String>>,
+}
+
+/// Antes, blinds, and straddles a player posted before the cards were
+/// dealt.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ForcedBetRecord {
+    pub seat: SeatIndex,
+    pub username: Username,
+    pub amount: Usd,
+}
+
+/// An action taken by a player during a hand.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ActionRecord {
+    pub street: Street,
+    pub seat: SeatIndex,
+    pub username: Username,
+    pub action: HistoryAction,
+    /// Money the player put into the pot with the action.
+    pub amount: Usd,
+}
+
+/// Community cards dealt on a street.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StreetRecord {
+    pub street: Street,
+    pub cards: Vec<String>,
+}
+
+/// A player that made it to the showdown.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ShowdownRecord {
+    pub seat: SeatIndex,
+    pub username: Username,
+    /// The player's cards, or nothing if they didn't show them.
+    pub cards: Option<Vec<String>>,
+}
+
+/// Money won from the pot by a player. Players can win several
+/// awards in a hand (e.g., from side pots or when running it twice).
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PotAward {
+    pub seat: SeatIndex,
+    pub username: Username,
+    pub amount: Usd,
+}
+
+/// Who won a pot on one of the boards it was run on.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BoardResult {
+    /// Seats of the players that won the board, splitting its share of
+    /// the pot.
+    pub winners: Vec<SeatIndex>,
+    /// The winning hand, or nothing if the pot was uncontested and the
+    /// winner didn't have to show.
+    pub hand: Option<String>,
+}
+
+/// How a main pot or side pot was paid out, so players can follow the
+/// payout math.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PotBreakdown {
+    /// Zero for the main pot, counting up through the side pots.
+    pub pot_idx: usize,
+    /// Money in the pot after the rake.
+    pub amount: Usd,
+    /// Seats of the players that were eligible to win the pot.
+    pub eligible: Vec<SeatIndex>,
+    /// Results for each board the pot was run on, with two boards when
+    /// running it twice. Empty when the pot was chopped by equity.
+    pub boards: Vec<BoardResult>,
+    /// Chips left over from splitting the pot evenly, and the seats
+    /// they went to.
+    pub odd_chips: Vec<(SeatIndex, Usd)>,
+}
+
+impl fmt::Display for PotBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn fmt_seats(seats: &[SeatIndex]) -> String {
+            let plural = if seats.len() == 1 { "" } else { "s" };
+            let seats = seats
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("seat{plural} {seats}")
+        }
+
+        let pot = match self.pot_idx {
+            0 => "main pot".to_string(),
+            pot_idx => format!("side pot {pot_idx}"),
+        };
+        let mut parts = Vec::new();
+        if self.boards.is_empty() {
+            parts.push("chopped by equity".to_string());
+        }
+        for (board_idx, result) in self.boards.iter().enumerate() {
+            let board = match self.boards.len() {
+                1 => String::new(),
+                _ => format!("board {}: ", board_idx + 1),
+            };
+            let verb = match result.winners.len() {
+                1 => "wins",
+                _ => "split",
+            };
+            let hand = result
+                .hand
+                .as_ref()
+                .map_or_else(String::new, |hand| format!(" with {hand}"));
+            parts.push(format!(
+                "{board}{} {verb}{hand}",
+                fmt_seats(&result.winners)
+            ));
+        }
+        for (seat, chips) in &self.odd_chips {
+            match chips {
+                1 => parts.push(format!("odd chip to seat {seat}")),
+                chips => parts.push(format!("{chips} odd chips to seat {seat}")),
+            }
+        }
+        write!(
+            f,
+            "{pot} ${} ({}): {}",
+            self.amount,
+            fmt_seats(&self.eligible),
+            parts.join("; ")
+        )
+    }
+}
+
+/// Structured record of everything that happened in a hand. Cards are
+/// given in their shorthand (e.g., "As", "Td").
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HandHistory {
+    pub blinds: Blinds,
+    pub seats: Vec<SeatRecord>,
+    /// Only players that posted something are included.
+    #[serde(default)]
+    pub forced_bets: Vec<ForcedBetRecord>,
+    pub actions: Vec<ActionRecord>,
+    pub board: Vec<StreetRecord>,
+    /// The second board when the pot was run twice.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub second_board: Vec<String>,
+    pub showdown: Vec<ShowdownRecord>,
+    pub pot_awards: Vec<PotAward>,
+    /// Money taken from the pot by the house.
+    #[serde(default)]
+    pub rake: Usd,
+}
+
+impl HandHistory {
+    #[must_use]
+    pub fn new(blinds: Blinds, seats: Vec<SeatRecord>) -> Self {
+        Self {
+            blinds,
+            seats,
+            forced_bets: Vec::new(),
+            actions: Vec::new(),
+            board: Vec::new(),
+            second_board: Vec::new(),
+            showdown: Vec::new(),
+            pot_awards: Vec::new(),
+            rake: 0,
+        }
+    }
+
+    /// Record cards a player showed.
+    pub fn show_cards(&mut self, username: &Username, cards: &[Card]) {
+        let cards: Vec<String> = cards.iter().map(Card::to_shorthand).collect();
+        if let Some(seat) = self.seats.iter_mut().find(|s| &s.username == username) {
+            seat.hole_cards = Some(cards.clone());
+        }
+        if let Some(record) = self.showdown.iter_mut().find(|s| &s.username == username) {
+            record.cards = Some(cards);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -705,6 +1650,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_card_shorthand_round_trip() {
+        let deck = Deck::default();
+        for card in deck.cards {
+            let shorthand = card.to_shorthand();
+            let parsed: Card = shorthand.parse().unwrap();
+            assert_eq!(parsed.to_shorthand(), shorthand);
+            assert_eq!(parsed.1, card.1);
+            if card.0 == 1 {
+                assert_eq!(parsed.0, 14);
+            } else {
+                assert_eq!(parsed.0, card.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_card_from_str() {
+        assert_eq!("As".parse(), Ok(Card(14, Suit::Spade)));
+        assert_eq!("Td".parse(), Ok(Card(10, Suit::Diamond)));
+        assert_eq!("2c".parse(), Ok(Card(2, Suit::Club)));
+        assert_eq!("kh".parse(), Ok(Card(13, Suit::Heart)));
+    }
+
+    #[test]
+    fn test_card_from_str_rejects_invalid_input() {
+        assert_eq!("Xx".parse::<Card>(), Err(CardParseError::InvalidRank('X')));
+        assert_eq!(
+            "".parse::<Card>(),
+            Err(CardParseError::InvalidLength(String::new()))
+        );
+        assert_eq!(
+            "Asd".parse::<Card>(),
+            Err(CardParseError::InvalidLength("Asd".to_string()))
+        );
+        assert_eq!("1s".parse::<Card>(), Err(CardParseError::InvalidRank('1')));
+        assert_eq!("Ax".parse::<Card>(), Err(CardParseError::InvalidSuit('x')));
+    }
+
     // === Deck Tests ===
 
     #[test]
@@ -713,6 +1697,16 @@ mod tests {
         assert_eq!(deck.cards.len(), 52);
     }
 
+    #[test]
+    fn test_short_deck_initialization() {
+        let deck = Deck::new(GameVariant::ShortDeck);
+        assert_eq!(deck.cards.len(), 36);
+        assert_eq!(deck.cards.len(), GameVariant::ShortDeck.deck_size());
+        assert!(deck.cards.iter().all(|card| card.0 == 1 || card.0 >= 6));
+        let unique: BTreeSet<_> = deck.cards.iter().collect();
+        assert_eq!(unique.len(), 36);
+    }
+
     #[test]
     fn test_deck_shuffle() {
         let mut deck = Deck::default();
@@ -760,7 +1754,11 @@ mod tests {
 
     #[test]
     fn test_blinds_creation() {
-        let blinds = Blinds { small: 10, big: 20 };
+        let blinds = Blinds {
+            small: 10,
+            big: 20,
+            ante: 0,
+        };
         assert_eq!(blinds.small, 10);
         assert_eq!(blinds.big, 20);
     }
@@ -770,6 +1768,7 @@ mod tests {
         let blinds = Blinds {
             small: 50,
             big: 100,
+            ante: 0,
         };
         assert_eq!(blinds.big, blinds.small * 2);
     }
@@ -851,6 +1850,87 @@ mod tests {
         assert!(pot.investments.is_empty());
     }
 
+    #[test]
+    fn test_side_pots_3_way_all_in_distinct_stacks() {
+        let mut pot = Pot::default();
+        pot.investments.insert(0, 100);
+        pot.investments.insert(1, 300);
+        pot.investments.insert(2, 600);
+        let side_pots = pot.split_into_side_pots(&HashSet::new());
+        assert_eq!(
+            side_pots,
+            vec![
+                SidePot {
+                    amount: 300,
+                    cap: 100,
+                    eligible: vec![0, 1, 2],
+                },
+                SidePot {
+                    amount: 400,
+                    cap: 300,
+                    eligible: vec![1, 2],
+                },
+                SidePot {
+                    amount: 300,
+                    cap: 600,
+                    eligible: vec![2],
+                },
+            ]
+        );
+        let total: Usd = side_pots.iter().map(|side_pot| side_pot.amount).sum();
+        assert_eq!(total, pot.get_size());
+    }
+
+    #[test]
+    fn test_side_pots_equal_all_ins_share_pot() {
+        let mut pot = Pot::default();
+        pot.investments.insert(0, 200);
+        pot.investments.insert(1, 200);
+        pot.investments.insert(2, 500);
+        let side_pots = pot.split_into_side_pots(&HashSet::new());
+        assert_eq!(side_pots.len(), 2);
+        assert_eq!(side_pots[0].amount, 600);
+        assert_eq!(side_pots[0].eligible, vec![0, 1, 2]);
+        assert_eq!(side_pots[1].amount, 300);
+        assert_eq!(side_pots[1].eligible, vec![2]);
+    }
+
+    #[test]
+    fn test_side_pots_short_all_in_only_wins_matched_portion() {
+        let mut pot = Pot::default();
+        pot.investments.insert(0, 50);
+        pot.investments.insert(1, 400);
+        pot.investments.insert(2, 400);
+        let side_pots = pot.split_into_side_pots(&HashSet::new());
+        assert_eq!(side_pots.len(), 2);
+        assert_eq!(side_pots[0].amount, 150);
+        assert_eq!(side_pots[0].eligible, vec![0, 1, 2]);
+        assert_eq!(side_pots[1].amount, 700);
+        assert_eq!(side_pots[1].eligible, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_side_pots_folded_dead_money_in_main_pot() {
+        let mut pot = Pot::default();
+        pot.investments.insert(0, 100);
+        pot.investments.insert(1, 50);
+        pot.investments.insert(2, 300);
+        pot.investments.insert(3, 300);
+        let folded = HashSet::from([1]);
+        let side_pots = pot.split_into_side_pots(&folded);
+        assert_eq!(side_pots.len(), 2);
+        assert_eq!(side_pots[0].amount, 350);
+        assert_eq!(side_pots[0].eligible, vec![0, 2, 3]);
+        assert_eq!(side_pots[1].amount, 400);
+        assert_eq!(side_pots[1].eligible, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_side_pots_empty_pot() {
+        let pot = Pot::default();
+        assert!(pot.split_into_side_pots(&HashSet::new()).is_empty());
+    }
+
     // === Rank Tests ===
 
     #[test]
@@ -960,8 +2040,17 @@ mod tests {
 
     #[test]
     fn test_action_choice_raise_with_amount() {
-        let choice = ActionChoice::Raise(200);
-        assert!(matches!(choice, ActionChoice::Raise(200)));
+        let choice = ActionChoice::Raise {
+            min: 200,
+            max: 1000,
+        };
+        assert!(matches!(
+            choice,
+            ActionChoice::Raise {
+                min: 200,
+                max: 1000
+            }
+        ));
     }
 
     #[test]
@@ -1077,6 +2166,12 @@ mod tests {
             big_blind_idx: 1,
             starting_action_idx: 2,
             next_action_idx: Some(0),
+            straddle_idx: None,
+            button_seat: Some(3),
+            small_blind_seat: Some(0),
+            big_blind_seat: Some(1),
+            dead_small_blind: false,
+            heads_up: false,
         };
         assert_eq!(positions.small_blind_idx, 0);
         assert_eq!(positions.big_blind_idx, 1);
@@ -1162,7 +2257,11 @@ mod tests {
 
     #[test]
     fn test_blinds_zero() {
-        let blinds = Blinds { small: 0, big: 0 };
+        let blinds = Blinds {
+            small: 0,
+            big: 0,
+            ante: 0,
+        };
         assert_eq!(blinds.small, 0);
         assert_eq!(blinds.big, 0);
     }
@@ -1172,6 +2271,7 @@ mod tests {
         let blinds = Blinds {
             small: 1000,
             big: 2000,
+            ante: 0,
         };
         assert_eq!(blinds.small, 1000);
         assert_eq!(blinds.big, 2000);
@@ -1270,10 +2370,24 @@ mod tests {
 
     #[test]
     fn test_blinds_display() {
-        let blinds = Blinds { small: 5, big: 10 };
+        let blinds = Blinds {
+            small: 5,
+            big: 10,
+            ante: 0,
+        };
         assert_eq!(format!("{}", blinds), "$5/10");
     }
 
+    #[test]
+    fn test_blinds_display_with_ante() {
+        let blinds = Blinds {
+            small: 5,
+            big: 10,
+            ante: 1,
+        };
+        assert_eq!(format!("{}", blinds), "$5/10 ($1 ante)");
+    }
+
     #[test]
     fn test_action_display_all_in() {
         let action = Action::AllIn;
@@ -1316,13 +2430,13 @@ mod tests {
         let call = ActionChoice::Call(50);
         let check = ActionChoice::Check;
         let fold = ActionChoice::Fold;
-        let raise = ActionChoice::Raise(100);
+        let raise = ActionChoice::Raise { min: 100, max: 500 };
 
         assert_eq!(format!("{}", all_in), "all-in");
         assert_eq!(format!("{}", call), "call (== $50)");
         assert_eq!(format!("{}", check), "check");
         assert_eq!(format!("{}", fold), "fold");
-        assert_eq!(format!("{}", raise), "raise (>= $100)");
+        assert_eq!(format!("{}", raise), "raise (>= $100, <= $500)");
     }
 
     #[test]
@@ -1331,7 +2445,7 @@ mod tests {
         let call: usize = ActionChoice::Call(50).into();
         let check: usize = ActionChoice::Check.into();
         let fold: usize = ActionChoice::Fold.into();
-        let raise: usize = ActionChoice::Raise(100).into();
+        let raise: usize = ActionChoice::Raise { min: 100, max: 500 }.into();
 
         assert_eq!(all_in, 0);
         assert_eq!(call, 1);
@@ -1354,7 +2468,7 @@ mod tests {
         let action: Action = ActionChoice::Fold.into();
         assert!(matches!(action, Action::Fold));
 
-        let action: Action = ActionChoice::Raise(100).into();
+        let action: Action = ActionChoice::Raise { min: 100, max: 500 }.into();
         assert!(matches!(action, Action::Raise(Some(100))));
     }
 
@@ -1481,6 +2595,7 @@ mod tests {
         player.state = PlayerState::Fold;
         player.cards = vec![Card(14, Suit::Spade), Card(13, Suit::Heart)];
         player.showing = true;
+        player.mucked = true;
 
         // Reset player
         player.reset();
@@ -1488,6 +2603,7 @@ mod tests {
         assert!(matches!(player.state, PlayerState::Wait));
         assert!(player.cards.is_empty());
         assert!(!player.showing);
+        assert!(!player.mucked);
     }
 
     #[test]
@@ -1556,6 +2672,18 @@ mod tests {
         assert_eq!(pot.get_min_raise_by_player_idx(1), 100);
     }
 
+    #[test]
+    fn test_pot_get_max_pot_limit_raise_by_player_idx() {
+        let mut pot = Pot::default();
+        pot.investments.insert(0, 50);
+        pot.investments.insert(1, 100);
+
+        // Player 0 calls 50 (pot becomes 200), then raises by the pot
+        assert_eq!(pot.get_max_pot_limit_raise_by_player_idx(0), 250);
+        // Player 1 has nothing to call, so can bet the pot
+        assert_eq!(pot.get_max_pot_limit_raise_by_player_idx(1), 150);
+    }
+
     #[test]
     fn test_pot_get_size() {
         let mut pot = Pot::default();
@@ -1641,6 +2769,10 @@ mod tests {
             user: user.clone(),
             state: PlayerState::Wait,
             cards: cards_arc.clone(),
+            sitting_out: false,
+            seat_idx: 0,
+            committed_this_street: 0,
+            total_committed: 0,
         };
 
         assert_eq!(player_view.user, user);
@@ -1652,7 +2784,10 @@ mod tests {
     fn test_action_choice_equality() {
         // ActionChoice uses discriminant for equality, so amounts don't matter
         assert_eq!(ActionChoice::Call(50), ActionChoice::Call(100));
-        assert_eq!(ActionChoice::Raise(50), ActionChoice::Raise(200));
+        assert_eq!(
+            ActionChoice::Raise { min: 50, max: 100 },
+            ActionChoice::Raise { min: 200, max: 400 }
+        );
         assert_ne!(ActionChoice::Call(50), ActionChoice::Fold);
     }
 
@@ -1853,18 +2988,21 @@ mod tests {
 
     #[test]
     fn test_action_choice_raise_zero() {
-        let choice = ActionChoice::Raise(0);
+        let choice = ActionChoice::Raise { min: 0, max: 0 };
         match choice {
-            ActionChoice::Raise(amt) => assert_eq!(amt, 0),
+            ActionChoice::Raise { min, max } => assert_eq!((min, max), (0, 0)),
             _ => panic!("Expected Raise action"),
         }
     }
 
     #[test]
     fn test_action_choice_raise_max_u32() {
-        let choice = ActionChoice::Raise(u32::MAX);
+        let choice = ActionChoice::Raise {
+            min: u32::MAX,
+            max: u32::MAX,
+        };
         match choice {
-            ActionChoice::Raise(amt) => assert_eq!(amt, u32::MAX),
+            ActionChoice::Raise { min, max } => assert_eq!((min, max), (u32::MAX, u32::MAX)),
             _ => panic!("Expected Raise action"),
         }
     }
@@ -1947,7 +3085,11 @@ mod tests {
 
     #[test]
     fn test_blinds_zero_values() {
-        let blinds = Blinds { small: 0, big: 0 };
+        let blinds = Blinds {
+            small: 0,
+            big: 0,
+            ante: 0,
+        };
         assert_eq!(blinds.small, 0);
         assert_eq!(blinds.big, 0);
     }
@@ -1957,6 +3099,7 @@ mod tests {
         let blinds = Blinds {
             small: u32::MAX,
             big: u32::MAX,
+            ante: 0,
         };
         assert_eq!(blinds.small, u32::MAX);
         assert_eq!(blinds.big, u32::MAX);
@@ -1967,6 +3110,7 @@ mod tests {
         let blinds = Blinds {
             small: 100,
             big: 50, // Invalid game state but type allows it
+            ante: 0,
         };
         assert_eq!(blinds.small, 100);
         assert_eq!(blinds.big, 50);
@@ -1977,6 +3121,7 @@ mod tests {
         let blinds = Blinds {
             small: 12345,
             big: 67890,
+            ante: 0,
         };
         let serialized = serialize_value(&blinds);
         let deserialized: Blinds = deserialize_value(&serialized);
@@ -2005,6 +3150,7 @@ mod tests {
     fn test_pot_empty_investments() {
         let pot = Pot {
             investments: HashMap::new(),
+            street_start_investments: HashMap::new(),
         };
         assert_eq!(pot.investments.len(), 0);
     }
@@ -2014,7 +3160,10 @@ mod tests {
         let mut investments = HashMap::new();
         investments.insert(0, 1000);
         investments.insert(1, 500);
-        let pot = Pot { investments };
+        let pot = Pot {
+            investments,
+            street_start_investments: HashMap::new(),
+        };
         assert_eq!(pot.investments.len(), 2);
         assert_eq!(*pot.investments.get(&0).unwrap(), 1000);
         assert_eq!(*pot.investments.get(&1).unwrap(), 500);
@@ -2079,6 +3228,10 @@ mod tests {
             user: user.clone(),
             state: PlayerState::Fold,
             cards: Arc::new(vec![]),
+            sitting_out: false,
+            seat_idx: 0,
+            committed_this_street: 0,
+            total_committed: 0,
         };
         let serialized = serialize_value(&player_view);
         let deserialized: PlayerView = deserialize_value(&serialized);
@@ -2095,6 +3248,10 @@ mod tests {
             user: user.clone(),
             state: PlayerState::Call,
             cards: Arc::new(vec![Card(14, Suit::Spade), Card(13, Suit::Heart)]),
+            sitting_out: false,
+            seat_idx: 0,
+            committed_this_street: 0,
+            total_committed: 0,
         };
 
         let serialized = serialize_value(&player_view);
@@ -2181,4 +3338,242 @@ mod tests {
         assert_eq!(format!("{}", Rank::FourOfAKind), "4k");
         assert_eq!(format!("{}", Rank::StraightFlush), "sf");
     }
+
+    // === Game View Delta Tests ===
+
+    fn init_player_view(name: &str, money: Usd, state: PlayerState) -> PlayerView {
+        PlayerView {
+            user: User {
+                name: Username::new(name),
+                money,
+            },
+            state,
+            cards: Arc::new(vec![]),
+            sitting_out: false,
+            seat_idx: 0,
+            committed_this_street: 0,
+            total_committed: 0,
+        }
+    }
+
+    fn init_game_view() -> GameView {
+        GameView {
+            blinds: Arc::new(Blinds {
+                small: 5,
+                big: 10,
+                ante: 0,
+            }),
+            spectators: Arc::new(HashSet::new()),
+            waitlist: Arc::new(VecDeque::new()),
+            open_seats: Arc::new(VecDeque::from([3, 4, 5])),
+            players: vec![
+                init_player_view("alice", 595, PlayerState::Wait),
+                init_player_view("bob", 590, PlayerState::Wait),
+                init_player_view("carol", 600, PlayerState::Wait),
+            ],
+            board: Arc::new(vec![]),
+            second_board: Arc::new(vec![]),
+            pot: Arc::new(PotView { size: 15 }),
+            play_positions: Arc::new(PlayPositions {
+                next_action_idx: Some(2),
+                ..Default::default()
+            }),
+            recent_actions: Arc::new(vec![]),
+            sequence: 0,
+            deck_style: None,
+            variant: GameVariant::TexasHoldem,
+        }
+    }
+
+    #[test]
+    fn test_seat_map_lays_out_six_seat_table() {
+        let seated = |name: &str, seat_idx: SeatIndex| PlayerView {
+            seat_idx,
+            ..init_player_view(name, 600, PlayerState::Wait)
+        };
+        let mut view = init_game_view();
+        view.players = vec![seated("alice", 0), seated("bob", 2), seated("carol", 4)];
+        view.open_seats = Arc::new(VecDeque::from([1, 3, 5]));
+        view.play_positions = Arc::new(PlayPositions {
+            button_seat: Some(0),
+            small_blind_seat: Some(2),
+            big_blind_seat: Some(4),
+            ..Default::default()
+        });
+
+        let seats = view.seat_map();
+        assert_eq!(seats.len(), 6);
+        let occupants: Vec<_> = seats
+            .iter()
+            .map(|seat| {
+                seat.player
+                    .as_ref()
+                    .map(|player| player.user.name.to_string())
+            })
+            .collect();
+        assert_eq!(
+            occupants,
+            vec![
+                Some("alice".to_string()),
+                None,
+                Some("bob".to_string()),
+                None,
+                Some("carol".to_string()),
+                None,
+            ]
+        );
+        let open: Vec<_> = seats.iter().map(|seat| seat.is_open).collect();
+        assert_eq!(open, vec![false, true, false, true, false, true]);
+        assert!(seats[0].is_button && !seats[0].is_small_blind);
+        assert!(seats[2].is_small_blind && !seats[2].is_big_blind);
+        assert!(seats[4].is_big_blind);
+
+        // Heads-up, the button posts the small blind and the other player
+        // posts the big blind
+        view.players = vec![seated("alice", 1), seated("bob", 4)];
+        view.open_seats = Arc::new(VecDeque::from([0, 2, 3, 5]));
+        view.play_positions = Arc::new(PlayPositions {
+            button_seat: Some(4),
+            small_blind_seat: Some(4),
+            big_blind_seat: Some(1),
+            heads_up: true,
+            ..Default::default()
+        });
+        let seats = view.seat_map();
+        assert_eq!(seats.len(), 6);
+        assert!(seats[4].is_button && seats[4].is_small_blind && !seats[4].is_big_blind);
+        assert!(seats[1].is_big_blind && !seats[1].is_button && !seats[1].is_small_blind);
+        let annotated = seats
+            .iter()
+            .filter(|seat| seat.is_button || seat.is_small_blind || seat.is_big_blind)
+            .count();
+        assert_eq!(annotated, 2);
+    }
+
+    #[test]
+    fn test_game_view_delta_reconstructs_view() {
+        let previous = init_game_view();
+        let mut current = init_game_view();
+        current.players[2] = init_player_view("carol", 590, PlayerState::Call);
+        current.board = Arc::new(vec![
+            Card(2, Suit::Club),
+            Card(9, Suit::Heart),
+            Card(14, Suit::Spade),
+        ]);
+        current.pot = Arc::new(PotView { size: 30 });
+        current.play_positions = Arc::new(PlayPositions {
+            next_action_idx: Some(0),
+            ..Default::default()
+        });
+
+        let delta = current.diff(&previous);
+        assert_eq!(delta.blinds, None);
+        assert_eq!(delta.spectators, None);
+        assert_eq!(delta.players.len(), 1);
+        assert_eq!(delta.players[0].0, 2);
+        assert_eq!(delta.num_players, None);
+        assert_eq!(delta.board, None);
+        assert_eq!(delta.new_board_cards, current.board.to_vec());
+        assert_eq!(delta.pot, Some(30));
+
+        let mut view = previous.clone();
+        view.apply_delta(delta);
+        assert_eq!(view, current);
+    }
+
+    #[test]
+    fn test_game_view_delta_new_hand_replaces_board() {
+        let mut previous = init_game_view();
+        previous.board = Arc::new(vec![
+            Card(2, Suit::Club),
+            Card(9, Suit::Heart),
+            Card(14, Suit::Spade),
+            Card(5, Suit::Diamond),
+            Card(11, Suit::Club),
+        ]);
+        let mut current = init_game_view();
+        current.players.pop();
+        current.open_seats = Arc::new(VecDeque::from([2, 3, 4, 5]));
+
+        let delta = current.diff(&previous);
+        assert_eq!(delta.board, Some(vec![]));
+        assert!(delta.new_board_cards.is_empty());
+        assert_eq!(delta.num_players, Some(2));
+        assert!(delta.players.is_empty());
+
+        let mut view = previous.clone();
+        view.apply_delta(delta);
+        assert_eq!(view, current);
+
+        // A player joining is sent as a new player at the end of the list.
+        let delta = previous.diff(&current);
+        assert_eq!(delta.num_players, Some(3));
+        assert_eq!(delta.players.len(), 1);
+        let mut view = current.clone();
+        view.apply_delta(delta);
+        assert_eq!(view, previous);
+    }
+
+    #[test]
+    fn test_game_view_delta_unchanged_view_is_empty() {
+        let view = init_game_view();
+        let delta = view.diff(&view.clone());
+        assert!(delta.is_empty());
+        assert_eq!(serde_json::to_string(&delta).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_game_view_delta_serialization_roundtrip() {
+        let previous = init_game_view();
+        let mut current = init_game_view();
+        current.players[0] = init_player_view("alice", 0, PlayerState::AllIn);
+        current.board = Arc::new(vec![Card(7, Suit::Diamond)]);
+        let delta = current.diff(&previous);
+
+        let json = serde_json::to_string(&delta).unwrap();
+        let deserialized: GameViewDelta = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, delta);
+    }
+
+    #[test]
+    fn test_game_view_with_deck_style_serialization_roundtrip() {
+        let mut view = init_game_view();
+        view.deck_style = Some("midnight".to_string());
+
+        let json = serde_json::to_string(&view).unwrap();
+        assert!(json.contains(r#""deck_style":"midnight""#));
+        let deserialized: GameView = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, view);
+
+        // The style is fixed for the table, so it survives deltas
+        let mut current = view.clone();
+        current.pot = Arc::new(PotView { size: 40 });
+        view.apply_delta(current.diff(&view));
+        assert_eq!(view, current);
+    }
+
+    #[test]
+    fn test_game_view_update_serialization_roundtrip() {
+        let previous = init_game_view();
+        let mut current = init_game_view();
+        current.pot = Arc::new(PotView { size: 40 });
+
+        let updates = [
+            GameViewUpdate::Snapshot {
+                seq: 1,
+                view: previous.clone(),
+            },
+            GameViewUpdate::Delta {
+                seq: 2,
+                base_seq: 1,
+                delta: Box::new(current.diff(&previous)),
+            },
+        ];
+        for update in updates {
+            let json = serde_json::to_string(&update).unwrap();
+            let deserialized: GameViewUpdate = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized.seq(), update.seq());
+            assert_eq!(deserialized, update);
+        }
+    }
 }
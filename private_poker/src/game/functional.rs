@@ -1,9 +1,11 @@
+use rand::{rng, seq::SliceRandom};
 use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet},
+    sync::LazyLock,
 };
 
-use super::entities::{Card, Rank, SubHand, Suit, Value};
+use super::entities::{Card, GameVariant, HandRank, Rank, SubHand, Suit, Value};
 
 /// Get the indices corresponding to the winning hands from an array
 /// of hands that were each created from `eval`.
@@ -21,11 +23,18 @@ use super::entities::{Card, Rank, SubHand, Suit, Value};
 /// ```
 #[must_use]
 pub fn argmax(hands: &[Vec<SubHand>]) -> Vec<usize> {
+    argmax_with(hands, GameVariant::TexasHoldem)
+}
+
+/// Get the indices corresponding to the winning hands like `argmax`,
+/// but using the hand rankings of the given game variant.
+#[must_use]
+pub fn argmax_with(hands: &[Vec<SubHand>], variant: GameVariant) -> Vec<usize> {
     let mut argmaxes: Vec<usize> = Vec::new();
     let mut best_hand: Option<&[SubHand]> = None;
     for (i, hand) in hands.iter().enumerate() {
         if let Some(current_best_hand) = best_hand {
-            match hand.as_slice().cmp(current_best_hand) {
+            match cmp_hands(hand, current_best_hand, variant) {
                 Ordering::Equal => argmaxes.push(i),
                 Ordering::Greater => {
                     argmaxes.clear();
@@ -42,6 +51,424 @@ pub fn argmax(hands: &[Vec<SubHand>]) -> Vec<usize> {
     argmaxes
 }
 
+/// Compare two hands created from `eval_with` using the hand rankings
+/// of the given game variant.
+#[must_use]
+pub fn cmp_hands(hand1: &[SubHand], hand2: &[SubHand], variant: GameVariant) -> Ordering {
+    for (subhand1, subhand2) in hand1.iter().zip(hand2) {
+        let ordering = variant
+            .rank_strength(subhand1.rank)
+            .cmp(&variant.rank_strength(subhand2.rank))
+            .then_with(|| subhand1.values.cmp(&subhand2.values));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    hand1.len().cmp(&hand2.len())
+}
+
+/// Estimate the probability that a hand wins or splits the pot against
+/// some number of opponents with a Monte Carlo simulation.
+///
+/// Each iteration deals random hole cards to the opponents and completes
+/// the board from the cards that aren't already known (the hole cards
+/// and the board). Works for any board size from preflop (an empty board)
+/// to the river. Aces can be given either as 1s or 14s.
+///
+/// # Examples
+///
+/// ```
+/// use private_poker::{entities::{Card, Suit}, functional::equity};
+///
+/// let hole = [Card(1, Suit::Club), Card(1, Suit::Spade)];
+/// let board = [Card(1, Suit::Heart), Card(1, Suit::Diamond), Card(13, Suit::Club)];
+/// assert!(equity(&hole, &board, 1, 100) > 0.9)
+/// ```
+#[must_use]
+pub fn equity(hole: &[Card], board: &[Card], opponents: usize, iterations: usize) -> f64 {
+    // The deck represents aces as 1s, so high aces are converted
+    // to match the cards that'll be dealt from the remaining deck.
+    let to_deck_card = |card: &Card| match card {
+        Card(14, suit) => Card(1, *suit),
+        card => *card,
+    };
+    let hole: Vec<Card> = hole.iter().map(to_deck_card).collect();
+    let board: Vec<Card> = board.iter().map(to_deck_card).collect();
+
+    let mut remaining: Vec<Card> = Vec::with_capacity(52);
+    for value in 1u8..14u8 {
+        for suit in [Suit::Club, Suit::Spade, Suit::Diamond, Suit::Heart] {
+            let card = Card(value, suit);
+            if !hole.contains(&card) && !board.contains(&card) {
+                remaining.push(card);
+            }
+        }
+    }
+
+    let num_board_cards_to_deal = 5usize.saturating_sub(board.len());
+    let num_cards_to_deal = num_board_cards_to_deal + 2 * opponents;
+    if iterations == 0 || num_cards_to_deal > remaining.len() {
+        return 0.0;
+    }
+
+    // Buffers are reused across iterations to avoid reallocating them.
+    let mut rng = rng();
+    let mut runout: Vec<Card> = Vec::with_capacity(5);
+    let mut cards: Vec<Card> = Vec::with_capacity(11);
+    let mut num_wins_or_splits: usize = 0;
+    for _ in 0..iterations {
+        let (dealt, _) = remaining.partial_shuffle(&mut rng, num_cards_to_deal);
+        let (board_cards, opponent_cards) = dealt.split_at(num_board_cards_to_deal);
+        runout.clear();
+        runout.extend(&board);
+        runout.extend(board_cards.iter());
+
+        cards.clear();
+        cards.extend(&hole);
+        cards.extend(&runout);
+        let hand = eval_fast(&cards);
+
+        let mut is_beaten = false;
+        for opponent_hole in opponent_cards.chunks_exact(2) {
+            cards.clear();
+            cards.extend(opponent_hole.iter());
+            cards.extend(&runout);
+            if eval_fast(&cards) > hand {
+                is_beaten = true;
+                break;
+            }
+        }
+        if !is_beaten {
+            num_wins_or_splits += 1;
+        }
+    }
+    num_wins_or_splits as f64 / iterations as f64
+}
+
+/// Most community cards left to come for `showdown_equities` to go
+/// through every possible runout instead of sampling them.
+const MAX_ENUMERATED_RUNOUT_CARDS: usize = 2;
+
+/// Compute each hand's share of the pot when the given hands are all-in
+/// against each other, using the hand rankings of the given variant.
+///
+/// Unlike `equity`, every opponent's hole cards are known. Splits count
+/// as a fraction of a win, so the shares add up to 1. Every possible
+/// runout is gone through on the turn and the river, and on earlier
+/// streets `iterations` random runouts are sampled instead. Aces can be
+/// given either as 1s or 14s.
+///
+/// # Examples
+///
+/// ```
+/// use private_poker::{
+///     entities::{Card, GameVariant, Suit},
+///     functional::showdown_equities,
+/// };
+///
+/// let holes = [
+///     vec![Card(13, Suit::Club), Card(13, Suit::Spade)],
+///     vec![Card(13, Suit::Heart), Card(13, Suit::Diamond)],
+/// ];
+/// let board = [Card(2, Suit::Club), Card(7, Suit::Spade), Card(9, Suit::Heart)];
+/// let shares = showdown_equities(&holes, &board, GameVariant::TexasHoldem, 0);
+/// assert_eq!(shares, vec![0.5, 0.5])
+/// ```
+#[must_use]
+pub fn showdown_equities(
+    holes: &[Vec<Card>],
+    board: &[Card],
+    variant: GameVariant,
+    iterations: usize,
+) -> Vec<f64> {
+    let holes: Vec<Vec<Card>> = holes
+        .iter()
+        .map(|hole| hole.iter().map(to_deck_card).collect())
+        .collect();
+    let board: Vec<Card> = board.iter().map(to_deck_card).collect();
+    let remaining: Vec<Card> = std::iter::once(1)
+        .chain(variant.low_ace_value() + 1..14)
+        .flat_map(|value| {
+            [Suit::Club, Suit::Spade, Suit::Diamond, Suit::Heart].map(|suit| Card(value, suit))
+        })
+        .filter(|card| !board.contains(card) && !holes.iter().any(|hole| hole.contains(card)))
+        .collect();
+
+    let mut shares = vec![0.0; holes.len()];
+    let num_cards_to_deal = 5usize.saturating_sub(board.len());
+    if holes.is_empty() || num_cards_to_deal > remaining.len() {
+        return shares;
+    }
+
+    let mut num_runouts: usize = 0;
+    let mut settle = |runout: &[Card]| {
+        let hands: Vec<_> = holes
+            .iter()
+            .map(|hole| {
+                let mut cards = board.clone();
+                cards.extend_from_slice(runout);
+                eval_hole_with_board(hole, &cards, variant)
+            })
+            .collect();
+        let winners = argmax_with(&hands, variant);
+        for winner in &winners {
+            shares[*winner] += 1.0 / winners.len() as f64;
+        }
+        num_runouts += 1;
+    };
+    if num_cards_to_deal <= MAX_ENUMERATED_RUNOUT_CARDS {
+        let mut runout = Vec::with_capacity(num_cards_to_deal);
+        for_each_combination(&remaining, num_cards_to_deal, &mut runout, &mut settle);
+    } else {
+        let mut remaining = remaining;
+        let mut rng = rng();
+        for _ in 0..iterations {
+            let (runout, _) = remaining.partial_shuffle(&mut rng, num_cards_to_deal);
+            settle(runout);
+        }
+    }
+
+    if num_runouts > 0 {
+        for share in &mut shares {
+            *share /= num_runouts as f64;
+        }
+    }
+    shares
+}
+
+/// Convert high aces to the low aces the deck uses.
+fn to_deck_card(card: &Card) -> Card {
+    match card {
+        Card(14, suit) => Card(1, *suit),
+        card => *card,
+    }
+}
+
+/// Call `f` with every way of choosing `k` of the cards, appending the
+/// choices to `chosen`.
+fn for_each_combination(
+    cards: &[Card],
+    k: usize,
+    chosen: &mut Vec<Card>,
+    f: &mut impl FnMut(&[Card]),
+) {
+    if k == 0 {
+        f(chosen);
+        return;
+    }
+    for (card_idx, card) in cards.iter().enumerate() {
+        if cards.len() - card_idx < k {
+            break;
+        }
+        chosen.push(*card);
+        for_each_combination(&cards[card_idx + 1..], k - 1, chosen, f);
+        chosen.pop();
+    }
+}
+
+/// Most cards `eval_fast` can evaluate.
+const MAX_FAST_EVAL_CARDS: usize = 7;
+
+/// Number of card values, from deuces to aces.
+const NUM_VALUES: usize = 13;
+
+/// Lookup tables for `eval_fast`, built from `eval` the first time
+/// they're used so both evaluators always agree.
+struct LookupTables {
+    /// `num_ways[value_idx][num_cards]` is the number of ways to hold
+    /// `num_cards` cards using only values from `value_idx` up, with at
+    /// most 4 cards of each value. Used to perfectly hash a hand's
+    /// value counts.
+    num_ways: [[usize; MAX_FAST_EVAL_CARDS + 1]; NUM_VALUES + 1],
+    /// Ranks of hands without a flush, indexed by the number of cards
+    /// and then by the hash of the hand's value counts.
+    unsuited: Vec<Vec<u32>>,
+    /// Ranks of flushes, indexed by the bitmask of values within the
+    /// flush's suit. Zero for bitmasks that don't make a flush.
+    flushes: Vec<u32>,
+}
+
+static LOOKUP_TABLES: LazyLock<LookupTables> = LazyLock::new(LookupTables::new);
+
+impl LookupTables {
+    fn new() -> Self {
+        let mut num_ways = [[0; MAX_FAST_EVAL_CARDS + 1]; NUM_VALUES + 1];
+        num_ways[NUM_VALUES][0] = 1;
+        for value_idx in (0..NUM_VALUES).rev() {
+            for num_cards in 0..=MAX_FAST_EVAL_CARDS {
+                num_ways[value_idx][num_cards] = (0..=num_cards.min(4))
+                    .map(|count| num_ways[value_idx + 1][num_cards - count])
+                    .sum();
+            }
+        }
+
+        // Evaluate every possible hand with the reference evaluator.
+        // Suits are dealt round-robin so unsuited hands never make a
+        // flush, which is fine since a hand with a flush can't also
+        // make a full house or quads.
+        let suits = [Suit::Club, Suit::Spade, Suit::Diamond, Suit::Heart];
+        let mut unsuited_hands: Vec<Vec<Vec<SubHand>>> = Vec::new();
+        for num_cards in 0..=MAX_FAST_EVAL_CARDS {
+            let mut hands = vec![Vec::new(); num_ways[0][num_cards]];
+            for_each_value_counts(&mut [0; NUM_VALUES], 0, num_cards, &mut |counts| {
+                let mut cards: Vec<Card> = counts
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(value_idx, count)| (0..*count).map(move |_| value_idx as Value + 2))
+                    .zip(suits.iter().cycle())
+                    .map(|(value, suit)| Card(value, *suit))
+                    .collect();
+                prepare_hand(&mut cards);
+                hands[hash_value_counts(&num_ways, counts, num_cards)] = eval(&cards);
+            });
+            unsuited_hands.push(hands);
+        }
+        let flush_hands: Vec<Option<Vec<SubHand>>> = (0..1u16 << NUM_VALUES)
+            .map(|mask| {
+                if mask.count_ones() < 5 {
+                    return None;
+                }
+                let mut cards: Vec<Card> = (0..NUM_VALUES)
+                    .filter(|value_idx| mask & (1 << value_idx) != 0)
+                    .map(|value_idx| Card(value_idx as Value + 2, Suit::Club))
+                    .collect();
+                prepare_hand(&mut cards);
+                Some(eval(&cards))
+            })
+            .collect();
+
+        // Number hands by their order so comparing ranks is the same as
+        // comparing evaluated hands. Zero is left for "no hand".
+        let mut ordered_hands: Vec<&Vec<SubHand>> = unsuited_hands
+            .iter()
+            .flatten()
+            .chain(flush_hands.iter().flatten())
+            .collect();
+        ordered_hands.sort_unstable();
+        ordered_hands.dedup();
+        let rank_of = |hand: &Vec<SubHand>| {
+            let idx = ordered_hands
+                .binary_search(&hand)
+                .expect("every evaluated hand is ordered");
+            idx as u32 + 1
+        };
+
+        let unsuited = unsuited_hands
+            .iter()
+            .map(|hands| hands.iter().map(rank_of).collect())
+            .collect();
+        let flushes = flush_hands
+            .iter()
+            .map(|hand| hand.as_ref().map_or(0, rank_of))
+            .collect();
+        Self {
+            num_ways,
+            unsuited,
+            flushes,
+        }
+    }
+}
+
+/// Call `f` with every way of holding `num_cards` cards of the values
+/// from `value_idx` up, with at most 4 cards of each value.
+fn for_each_value_counts(
+    counts: &mut [u8; NUM_VALUES],
+    value_idx: usize,
+    num_cards: usize,
+    f: &mut impl FnMut(&[u8; NUM_VALUES]),
+) {
+    if value_idx == NUM_VALUES {
+        if num_cards == 0 {
+            f(counts);
+        }
+        return;
+    }
+    for count in 0..=num_cards.min(4) {
+        counts[value_idx] = count as u8;
+        for_each_value_counts(counts, value_idx + 1, num_cards - count, f);
+    }
+    counts[value_idx] = 0;
+}
+
+/// Perfect hash of a hand's value counts among all hands with the same
+/// number of cards.
+fn hash_value_counts(
+    num_ways: &[[usize; MAX_FAST_EVAL_CARDS + 1]; NUM_VALUES + 1],
+    counts: &[u8; NUM_VALUES],
+    num_cards: usize,
+) -> usize {
+    let mut hash = 0;
+    let mut remaining = num_cards;
+    for (value_idx, count) in counts.iter().enumerate() {
+        let count = *count as usize;
+        hash += (0..count)
+            .map(|smaller_count| num_ways[value_idx + 1][remaining - smaller_count])
+            .sum::<usize>();
+        remaining -= count;
+    }
+    hash
+}
+
+/// Evaluate up to 7 cards with precomputed lookup tables, returning the
+/// strength of the best (up to) 5-card hand as a single number. Ranks
+/// compare the same way `eval` hands do, but are much cheaper to
+/// compute and compare, so this is preferred for simulations like
+/// `equity`. The tables are built on first use.
+///
+/// Cards don't need to be prepared, and aces can be given either as 1s
+/// or 14s.
+///
+/// # Panics
+///
+/// Panics if given more than 7 distinct cards.
+///
+/// # Examples
+///
+/// ```
+/// use private_poker::{entities::{Card, Suit}, functional::eval_fast};
+///
+/// let trips = [Card(4, Suit::Club), Card(4, Suit::Heart), Card(4, Suit::Spade)];
+/// let pair = [Card(14, Suit::Club), Card(1, Suit::Heart), Card(11, Suit::Spade)];
+/// assert!(eval_fast(&trips) > eval_fast(&pair))
+/// ```
+#[must_use]
+pub fn eval_fast(cards: &[Card]) -> HandRank {
+    let tables = &*LOOKUP_TABLES;
+
+    // Bitmasks of values per suit. Using bitmasks also removes the
+    // duplicate aces of prepared hands.
+    let mut masks = [0u16; 5];
+    for Card(value, suit) in cards {
+        let value_idx = match value {
+            1 | 14 => 12,
+            value => *value as usize - 2,
+        };
+        masks[*suit as usize] |= 1 << value_idx;
+    }
+
+    let mut counts = [0u8; NUM_VALUES];
+    for (value_idx, count) in counts.iter_mut().enumerate() {
+        *count = masks
+            .iter()
+            .filter(|mask| *mask & (1 << value_idx) != 0)
+            .count() as u8;
+    }
+    let num_cards: usize = counts.iter().map(|count| *count as usize).sum();
+    assert!(
+        num_cards <= MAX_FAST_EVAL_CARDS,
+        "can't evaluate more than {MAX_FAST_EVAL_CARDS} cards"
+    );
+
+    let hash = hash_value_counts(&tables.num_ways, &counts, num_cards);
+    let mut rank = tables.unsuited[num_cards][hash];
+    for mask in masks {
+        if mask.count_ones() >= 5 {
+            rank = rank.max(tables.flushes[mask as usize]);
+        }
+    }
+    HandRank(rank)
+}
+
 /// Evaluate any number of cards, returning the best (up to) 5-card hand.
 ///
 /// This function assumes the cards are already sorted in increasing order.
@@ -61,6 +488,121 @@ pub fn argmax(hands: &[Vec<SubHand>]) -> Vec<usize> {
 /// ```
 #[must_use]
 pub fn eval(cards: &[Card]) -> Vec<SubHand> {
+    eval_with(cards, GameVariant::TexasHoldem)
+}
+
+/// Evaluate cards like `eval`, but for the given game variant. Low aces
+/// are played right below the variant's lowest card, so A-6-7-8-9 is a
+/// straight in short-deck. Hands evaluated for a variant should be
+/// compared with `argmax_with` for the same variant.
+///
+/// # Examples
+///
+/// ```
+/// use private_poker::{entities::{Card, GameVariant, Rank, Suit}, functional::{eval_with, prepare_hand}};
+///
+/// let mut cards = vec![
+///     Card(1, Suit::Club),
+///     Card(6, Suit::Heart),
+///     Card(7, Suit::Spade),
+///     Card(8, Suit::Club),
+///     Card(9, Suit::Diamond),
+/// ];
+/// prepare_hand(&mut cards);
+/// let subhands = eval_with(&cards, GameVariant::ShortDeck);
+/// assert_eq!(subhands[0].rank, Rank::Straight)
+/// ```
+#[must_use]
+pub fn eval_with(cards: &[Card], variant: GameVariant) -> Vec<SubHand> {
+    let low_ace = variant.low_ace_value();
+    if low_ace == 1 {
+        return eval_hand(cards, low_ace);
+    }
+    // Low aces are still the lowest cards in the hand after this,
+    // so the cards stay sorted.
+    let cards: Vec<Card> = cards
+        .iter()
+        .map(|card| match card {
+            Card(1, suit) => Card(low_ace, *suit),
+            card => *card,
+        })
+        .collect();
+    eval_hand(&cards, low_ace)
+}
+
+/// Evaluate a player's best hand from their hole cards and the board
+/// for the given game variant. Unlike `eval_with`, the cards don't need
+/// to be prepared with `prepare_hand` first.
+///
+/// In Omaha, the best hand has to use exactly two hole cards and
+/// exactly three board cards, so four cards of a suit in the hole and
+/// one on the board isn't a flush. Other variants can use any of the
+/// cards.
+///
+/// # Examples
+///
+/// ```
+/// use private_poker::{entities::{Card, GameVariant, Rank, Suit}, functional::eval_hole_with_board};
+///
+/// let hole = [
+///     Card(2, Suit::Heart),
+///     Card(5, Suit::Heart),
+///     Card(9, Suit::Heart),
+///     Card(12, Suit::Heart),
+/// ];
+/// let board = [
+///     Card(3, Suit::Heart),
+///     Card(7, Suit::Club),
+///     Card(8, Suit::Spade),
+///     Card(11, Suit::Diamond),
+///     Card(13, Suit::Club),
+/// ];
+/// let subhands = eval_hole_with_board(&hole, &board, GameVariant::Omaha);
+/// assert_eq!(subhands[0].rank, Rank::HighCard)
+/// ```
+#[must_use]
+pub fn eval_hole_with_board(hole: &[Card], board: &[Card], variant: GameVariant) -> Vec<SubHand> {
+    if variant != GameVariant::Omaha {
+        let mut cards = hole.to_vec();
+        cards.extend_from_slice(board);
+        prepare_hand(&mut cards);
+        return eval_with(&cards, variant);
+    }
+
+    let num_hole_cards = hole.len().min(OMAHA_HOLE_CARDS_USED);
+    let num_board_cards = board.len().min(OMAHA_BOARD_CARDS_USED);
+    let mut board_choices: Vec<Vec<Card>> = Vec::new();
+    let mut chosen = Vec::with_capacity(num_board_cards);
+    for_each_combination(board, num_board_cards, &mut chosen, &mut |board_cards| {
+        board_choices.push(board_cards.to_vec());
+    });
+    let mut best_hand: Option<Vec<SubHand>> = None;
+    let mut chosen = Vec::with_capacity(num_hole_cards);
+    for_each_combination(hole, num_hole_cards, &mut chosen, &mut |hole_cards| {
+        for board_cards in &board_choices {
+            let mut cards = hole_cards.to_vec();
+            cards.extend_from_slice(board_cards);
+            prepare_hand(&mut cards);
+            let hand = eval_with(&cards, variant);
+            if best_hand
+                .as_ref()
+                .is_none_or(|best_hand| cmp_hands(&hand, best_hand, variant).is_gt())
+            {
+                best_hand = Some(hand);
+            }
+        }
+    });
+    best_hand.unwrap_or_default()
+}
+
+/// Number of hole cards an Omaha hand has to use.
+const OMAHA_HOLE_CARDS_USED: usize = 2;
+
+/// Number of board cards an Omaha hand has to use.
+const OMAHA_BOARD_CARDS_USED: usize = 3;
+
+/// Evaluate sorted cards where low aces have the value `low_ace`.
+fn eval_hand(cards: &[Card], low_ace: Value) -> Vec<SubHand> {
     // Mapping of suit to (sorted) cards within that suit.
     // Used for tracking whether there's a flush or straight flush.
     let mut values_per_suit: HashMap<Suit, Vec<Value>> = HashMap::new();
@@ -78,8 +620,8 @@ pub fn eval(cards: &[Card]) -> Vec<SubHand> {
 
     // Loop through cards in hand assuming the hand is sorted
     // and that each ace appears in the hand twice (at the low
-    // end with a value of 1 and at the high end with a value
-    // of 14). We push hands into a binary heap so we can
+    // end with a value of `low_ace` and at the high end with a
+    // value of 14). We push hands into a binary heap so we can
     // easily get the best hand at the end.
     let mut hands: BinaryHeap<SubHand> = BinaryHeap::new();
     for Card(value, suit) in cards {
@@ -184,7 +726,7 @@ pub fn eval(cards: &[Card]) -> Vec<SubHand> {
                 // Ignore the case where a high ace and low ace get counted
                 // together as a two pair.
                 if let Some(next_best_one_pair) = one_pairs.iter().nth_back(1)
-                    && (*value != 14 || next_best_one_pair.values != vec![1, 1])
+                    && (*value != 14 || next_best_one_pair.values != vec![low_ace; 2])
                 {
                     let mut two_pair_cards = vec![*value; 2];
                     two_pair_cards.extend(next_best_one_pair.values.clone());
@@ -203,7 +745,7 @@ pub fn eval(cards: &[Card]) -> Vec<SubHand> {
                 // a high ace and low ace get counted together as a full house.
                 if let Some(three_of_a_kinds) = subhands_per_rank.get(&Rank::ThreeOfAKind)
                     && let Some(best_three_of_a_kind) = three_of_a_kinds.iter().next()
-                    && (*value != 14 || best_three_of_a_kind.values != vec![1, 1, 1])
+                    && (*value != 14 || best_three_of_a_kind.values != vec![low_ace; 3])
                 {
                     let mut full_house_cards = best_three_of_a_kind.values.clone();
                     full_house_cards.extend(vec![*value; 2]);
@@ -241,7 +783,7 @@ pub fn eval(cards: &[Card]) -> Vec<SubHand> {
                 // ace and low ace get counted together as a full house.
                 if let Some(one_pairs) = subhands_per_rank.get(&Rank::OnePair)
                     && let Some(best_one_pair) = one_pairs.iter().next_back()
-                    && (*value != 14 || best_one_pair.values != vec![1, 1])
+                    && (*value != 14 || best_one_pair.values != vec![low_ace; 2])
                 {
                     let mut full_house_cards = vec![*value; 3];
                     full_house_cards.extend(best_one_pair.values.clone());
@@ -260,7 +802,7 @@ pub fn eval(cards: &[Card]) -> Vec<SubHand> {
                 // and low ace get counted together as a full house.
                 if let Some(three_of_a_kinds) = subhands_per_rank.get(&Rank::ThreeOfAKind)
                     && let Some(next_best_three_of_a_kind) = three_of_a_kinds.iter().nth_back(1)
-                    && (*value != 14 || next_best_three_of_a_kind.values != vec![1, 1, 1])
+                    && (*value != 14 || next_best_three_of_a_kind.values != vec![low_ace; 3])
                 {
                     let mut full_house_cards = vec![*value; 3];
                     full_house_cards.extend(vec![next_best_three_of_a_kind.values[0]; 2]);
@@ -344,6 +886,7 @@ pub fn eval(cards: &[Card]) -> Vec<SubHand> {
 
 /// Prepare a hand for evaluation by sorting it and adding high
 /// aces to it so aces can be treated as 1s in addition to 14s.
+/// Aces can be given either as 1s or 14s.
 ///
 /// # Examples
 ///
@@ -355,7 +898,17 @@ pub fn eval(cards: &[Card]) -> Vec<SubHand> {
 /// assert_eq!(cards, vec![Card(1, Suit::Heart), Card(10, Suit::Spade), Card(11, Suit::Club), Card(14, Suit::Heart)])
 /// ```
 pub fn prepare_hand(cards: &mut Vec<Card>) {
+    // Aces are only ever kept as low aces until now so they're always
+    // at the start of the sorted hand, which makes sure wheels (A-2-3-4-5)
+    // are found regardless of how the aces were given. Duplicates are
+    // removed in case the hand was already prepared.
+    for card in cards.iter_mut() {
+        if card.0 == 14 {
+            card.0 = 1;
+        }
+    }
     cards.sort_unstable();
+    cards.dedup();
     // Add ace highs to the hand for evaluation.
     for card_idx in 0..4 {
         if let Some(Card(1, suit)) = cards.get(card_idx) {
@@ -366,8 +919,12 @@ pub fn prepare_hand(cards: &mut Vec<Card>) {
 
 #[cfg(test)]
 mod tests {
-    use super::{argmax, eval};
-    use crate::game::entities::{Card, Rank, SubHand, Suit};
+    use rand::{rng, seq::SliceRandom};
+
+    use super::{
+        argmax, argmax_with, equity, eval, eval_fast, eval_hole_with_board, eval_with, prepare_hand,
+    };
+    use crate::game::entities::{Card, GameVariant, Rank, SubHand, Suit};
 
     struct TestHand {
         expected_best_subhand: SubHand,
@@ -940,5 +1497,351 @@ mod tests {
                 ]
             }, vec![0, 1]
         ),
+        wheel_loses_to_six_high_straight: (
+            TestHand{
+                expected_best_subhand: SubHand {
+                    rank: Rank::Straight,
+                    values: vec![5, 4, 3, 2, 1]
+                },
+                cards: vec![
+                    Card(1, Suit::Club),
+                    Card(2, Suit::Diamond),
+                    Card(3, Suit::Heart),
+                    Card(4, Suit::Spade),
+                    Card(5, Suit::Club),
+                    Card(9, Suit::Diamond),
+                    Card(14, Suit::Club),
+                ]
+            },
+            TestHand{
+                expected_best_subhand: SubHand {
+                    rank: Rank::Straight,
+                    values: vec![6, 5, 4, 3, 2]
+                },
+                cards: vec![
+                    Card(2, Suit::Diamond),
+                    Card(3, Suit::Heart),
+                    Card(4, Suit::Spade),
+                    Card(5, Suit::Club),
+                    Card(6, Suit::Heart),
+                    Card(9, Suit::Diamond),
+                    Card(11, Suit::Club),
+                ]
+            }, vec![1]
+        ),
+        wheel_wins_to_pair_of_kings: (
+            TestHand{
+                expected_best_subhand: SubHand {
+                    rank: Rank::Straight,
+                    values: vec![5, 4, 3, 2, 1]
+                },
+                cards: vec![
+                    Card(1, Suit::Club),
+                    Card(2, Suit::Diamond),
+                    Card(3, Suit::Heart),
+                    Card(4, Suit::Spade),
+                    Card(5, Suit::Club),
+                    Card(9, Suit::Diamond),
+                    Card(14, Suit::Club),
+                ]
+            },
+            TestHand{
+                expected_best_subhand: SubHand {
+                    rank: Rank::OnePair,
+                    values: vec![13, 13]
+                },
+                cards: vec![
+                    Card(2, Suit::Diamond),
+                    Card(4, Suit::Spade),
+                    Card(5, Suit::Club),
+                    Card(9, Suit::Diamond),
+                    Card(11, Suit::Club),
+                    Card(13, Suit::Heart),
+                    Card(13, Suit::Spade),
+                ]
+            }, vec![0]
+        ),
+        steel_wheel_wins_to_four_of_a_kind: (
+            TestHand{
+                expected_best_subhand: SubHand {
+                    rank: Rank::StraightFlush,
+                    values: vec![5, 4, 3, 2, 1]
+                },
+                cards: vec![
+                    Card(1, Suit::Heart),
+                    Card(2, Suit::Heart),
+                    Card(3, Suit::Heart),
+                    Card(4, Suit::Heart),
+                    Card(5, Suit::Heart),
+                    Card(9, Suit::Diamond),
+                    Card(14, Suit::Heart),
+                ]
+            },
+            TestHand{
+                expected_best_subhand: SubHand {
+                    rank: Rank::FourOfAKind,
+                    values: vec![9, 9, 9, 9]
+                },
+                cards: vec![
+                    Card(2, Suit::Diamond),
+                    Card(5, Suit::Club),
+                    Card(9, Suit::Club),
+                    Card(9, Suit::Diamond),
+                    Card(9, Suit::Heart),
+                    Card(9, Suit::Spade),
+                    Card(11, Suit::Club),
+                ]
+            }, vec![0]
+        ),
+    }
+
+    #[test]
+    fn wheel_with_high_ace_is_a_straight() {
+        let mut cards = vec![
+            Card(14, Suit::Club),
+            Card(2, Suit::Diamond),
+            Card(3, Suit::Heart),
+            Card(4, Suit::Spade),
+            Card(5, Suit::Club),
+            Card(13, Suit::Diamond),
+            Card(13, Suit::Heart),
+        ];
+        prepare_hand(&mut cards);
+        let hand = eval(&cards);
+        assert_eq!(
+            hand[0],
+            SubHand {
+                rank: Rank::Straight,
+                values: vec![5, 4, 3, 2, 1]
+            }
+        );
+    }
+
+    #[test]
+    fn short_deck_flush_beats_full_house() {
+        let mut flush = vec![
+            Card(6, Suit::Heart),
+            Card(8, Suit::Heart),
+            Card(10, Suit::Heart),
+            Card(12, Suit::Heart),
+            Card(13, Suit::Heart),
+            Card(7, Suit::Club),
+            Card(9, Suit::Spade),
+        ];
+        let mut full_house = vec![
+            Card(7, Suit::Diamond),
+            Card(7, Suit::Spade),
+            Card(7, Suit::Heart),
+            Card(11, Suit::Club),
+            Card(11, Suit::Diamond),
+            Card(6, Suit::Club),
+            Card(9, Suit::Club),
+        ];
+        prepare_hand(&mut flush);
+        prepare_hand(&mut full_house);
+
+        let hands = [
+            eval_with(&flush, GameVariant::ShortDeck),
+            eval_with(&full_house, GameVariant::ShortDeck),
+        ];
+        assert_eq!(hands[0][0].rank, Rank::Flush);
+        assert_eq!(hands[1][0].rank, Rank::FullHouse);
+        assert_eq!(argmax_with(&hands, GameVariant::ShortDeck), vec![0]);
+
+        // The usual rankings still apply to Texas Hold'em.
+        let hands = [eval(&flush), eval(&full_house)];
+        assert_eq!(argmax(&hands), vec![1]);
+    }
+
+    #[test]
+    fn short_deck_ace_to_nine_is_the_lowest_straight() {
+        let mut lowest_straight = vec![
+            Card(14, Suit::Club),
+            Card(6, Suit::Diamond),
+            Card(7, Suit::Heart),
+            Card(8, Suit::Spade),
+            Card(9, Suit::Club),
+            Card(13, Suit::Diamond),
+            Card(13, Suit::Heart),
+        ];
+        let mut higher_straight = vec![
+            Card(6, Suit::Club),
+            Card(7, Suit::Diamond),
+            Card(8, Suit::Heart),
+            Card(9, Suit::Spade),
+            Card(10, Suit::Club),
+            Card(12, Suit::Diamond),
+            Card(12, Suit::Heart),
+        ];
+        let mut three_of_a_kind = vec![
+            Card(14, Suit::Diamond),
+            Card(14, Suit::Heart),
+            Card(14, Suit::Spade),
+            Card(6, Suit::Heart),
+            Card(8, Suit::Club),
+            Card(10, Suit::Diamond),
+            Card(12, Suit::Spade),
+        ];
+        prepare_hand(&mut lowest_straight);
+        prepare_hand(&mut higher_straight);
+        prepare_hand(&mut three_of_a_kind);
+
+        let hand = eval_with(&lowest_straight, GameVariant::ShortDeck);
+        assert_eq!(
+            hand[0],
+            SubHand {
+                rank: Rank::Straight,
+                values: vec![9, 8, 7, 6, 5]
+            }
+        );
+        assert_eq!(eval(&lowest_straight)[0].rank, Rank::OnePair);
+
+        let hands = [
+            hand.clone(),
+            eval_with(&higher_straight, GameVariant::ShortDeck),
+        ];
+        assert_eq!(argmax_with(&hands, GameVariant::ShortDeck), vec![1]);
+        let hands = [hand, eval_with(&three_of_a_kind, GameVariant::ShortDeck)];
+        assert_eq!(hands[1][0].rank, Rank::ThreeOfAKind);
+        assert_eq!(argmax_with(&hands, GameVariant::ShortDeck), vec![0]);
+    }
+
+    #[test]
+    fn omaha_must_use_two_hole_cards() {
+        let hole = [
+            Card(14, Suit::Heart),
+            Card(13, Suit::Heart),
+            Card(2, Suit::Heart),
+            Card(3, Suit::Heart),
+        ];
+        let board = [
+            Card(7, Suit::Heart),
+            Card(8, Suit::Club),
+            Card(9, Suit::Diamond),
+            Card(11, Suit::Spade),
+            Card(12, Suit::Club),
+        ];
+
+        // The naive best five is a flush with four hearts from the hole.
+        let naive = eval_hole_with_board(&hole, &board, GameVariant::TexasHoldem);
+        assert_eq!(naive[0].rank, Rank::Flush);
+
+        // Only two hole cards can play, so the best Omaha hand is ace-king
+        // with the three biggest board cards.
+        let hand = eval_hole_with_board(&hole, &board, GameVariant::Omaha);
+        let mut best_five = vec![
+            Card(14, Suit::Heart),
+            Card(13, Suit::Heart),
+            Card(9, Suit::Diamond),
+            Card(11, Suit::Spade),
+            Card(12, Suit::Club),
+        ];
+        prepare_hand(&mut best_five);
+        assert_eq!(hand, eval(&best_five));
+        assert_eq!(hand[0].rank, Rank::HighCard);
+    }
+
+    #[test]
+    fn omaha_must_use_three_board_cards() {
+        // Quads on the board only play with one of them in Omaha.
+        let hole = [
+            Card(5, Suit::Club),
+            Card(5, Suit::Diamond),
+            Card(6, Suit::Heart),
+            Card(7, Suit::Spade),
+        ];
+        let board = [
+            Card(10, Suit::Club),
+            Card(10, Suit::Diamond),
+            Card(10, Suit::Heart),
+            Card(10, Suit::Spade),
+            Card(2, Suit::Club),
+        ];
+        assert_eq!(
+            eval_hole_with_board(&hole, &board, GameVariant::TexasHoldem)[0].rank,
+            Rank::FourOfAKind
+        );
+        assert_eq!(
+            eval_hole_with_board(&hole, &board, GameVariant::Omaha)[0].rank,
+            Rank::FullHouse
+        );
+    }
+
+    #[test]
+    fn prepare_hand_is_idempotent() {
+        let mut cards = vec![Card(1, Suit::Club), Card(10, Suit::Spade)];
+        prepare_hand(&mut cards);
+        let prepared = cards.clone();
+        prepare_hand(&mut cards);
+        assert_eq!(cards, prepared);
+    }
+
+    #[test]
+    fn pocket_aces_preflop_heads_up_equity() {
+        let hole = [Card(1, Suit::Club), Card(1, Suit::Spade)];
+        let equity = equity(&hole, &[], 1, 10_000);
+        assert!(equity > 0.8, "pocket aces equity was {equity}");
+    }
+
+    #[test]
+    fn equity_on_the_flop_excludes_known_cards() {
+        // Quad aces on the flop can only lose to a straight flush.
+        let hole = [Card(14, Suit::Club), Card(14, Suit::Spade)];
+        let board = [
+            Card(1, Suit::Heart),
+            Card(1, Suit::Diamond),
+            Card(2, Suit::Club),
+        ];
+        let equity = equity(&hole, &board, 3, 1_000);
+        assert!(equity > 0.95, "quad aces equity was {equity}");
+    }
+
+    #[test]
+    fn equity_with_too_many_opponents() {
+        let hole = [Card(1, Suit::Club), Card(1, Suit::Spade)];
+        assert_eq!(equity(&hole, &[], 30, 100), 0.0);
+    }
+
+    #[test]
+    fn eval_fast_accepts_prepared_and_unprepared_hands() {
+        let mut cards = vec![
+            Card(1, Suit::Club),
+            Card(2, Suit::Club),
+            Card(3, Suit::Club),
+            Card(4, Suit::Club),
+            Card(5, Suit::Club),
+            Card(14, Suit::Heart),
+        ];
+        let rank = eval_fast(&cards);
+        prepare_hand(&mut cards);
+        assert_eq!(eval_fast(&cards), rank);
+    }
+
+    #[test]
+    fn eval_fast_orders_hands_like_eval() {
+        let mut deck: Vec<Card> = (1u8..14u8)
+            .flat_map(|value| {
+                [Suit::Club, Suit::Spade, Suit::Diamond, Suit::Heart].map(|suit| Card(value, suit))
+            })
+            .collect();
+        let mut rng = rng();
+        let hands: Vec<Vec<Card>> = (0..2_000)
+            .map(|_| {
+                deck.shuffle(&mut rng);
+                let mut cards = deck[..7].to_vec();
+                prepare_hand(&mut cards);
+                cards
+            })
+            .collect();
+
+        for pair in hands.windows(2) {
+            assert_eq!(
+                eval_fast(&pair[0]).cmp(&eval_fast(&pair[1])),
+                eval(&pair[0]).cmp(&eval(&pair[1])),
+                "{:?} vs {:?}",
+                pair[0],
+                pair[1]
+            );
+        }
     }
 }
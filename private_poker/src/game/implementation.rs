@@ -43,16 +43,21 @@
 
 use enum_dispatch::enum_dispatch;
 use log::error;
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::{Ordering, max, min},
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     fmt,
     sync::Arc,
 };
 
+use super::constants::{EQUITY_CHOP_ITERATIONS, FIXED_LIMIT_MAX_BETS_PER_STREET};
 use super::entities::{
-    Action, ActionChoice, ActionChoices, Bet, BetAction, GameView, GameViews, Player, PlayerState,
-    PlayerView, Pot, PotView, SeatIndex, Usd, User, Username, Vote,
+    Action, ActionChoice, ActionChoices, ActionRecord, Bet, BetAction, BettingStructure, Blinds,
+    BoardResult, Card, ForcedBetRecord, GameView, GameViews, HandHistory,
+    MAX_RECENT_ACTIONS_PER_STREET, Player, PlayerState, PlayerView, Pot, PotAward, PotBreakdown,
+    PotView, RecentAction, SeatIndex, SeatRecord, ShowdownRecord, SidePot, Street, StreetRecord,
+    SubHand, TurnInfo, Usd, User, Username, Vote,
 };
 use super::functional;
 
@@ -69,7 +74,7 @@ pub use super::state_machine::{
 
 /// Lobby state - waiting for players to join before starting the game.
 /// Min 2 players required to start.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Lobby {
     start_game: bool,
 }
@@ -89,69 +94,70 @@ impl Lobby {
 
 /// SeatPlayers state - assigns seats to players from the waitlist.
 /// Uses cryptographic randomization for fair seating.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SeatPlayers {}
 
 /// MoveButton state - advances the dealer button to the next player.
 /// Also sets small blind and big blind positions.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct MoveButton {}
 
 /// CollectBlinds state - collects small and big blind bets.
 /// Handles cases where players don't have enough chips for full blind.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CollectBlinds {}
 
 /// Deal state - deals hole cards to all active players.
 /// Each player receives 2 private cards face down.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Deal {}
 
 /// TakeAction state - players make betting decisions (fold, check, call, raise, all-in).
 /// Tracks action choices available to the current player.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TakeAction {
     pub action_choices: Option<ActionChoices>,
 }
 
 /// Flop state - deals 3 community cards to the board.
 /// Transitions to TakeAction for next betting round.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Flop {}
 
 /// Turn state - deals the 4th community card to the board.
 /// Transitions to TakeAction for next betting round.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Turn {}
 
 /// River state - deals the 5th and final community card to the board.
 /// Transitions to TakeAction for final betting round.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct River {}
 
 /// ShowHands state - reveals player hands for showdown evaluation.
-/// Players can voluntarily show their cards or hide them if they folded.
-#[derive(Clone, Debug)]
+/// Hands are revealed starting from the last aggressor, and players
+/// whose hands are already beaten muck unless they choose to show.
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ShowHands {}
 
 /// DistributePot state - awards pot(s) to winning player(s).
 /// Handles side pots for all-in situations.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct DistributePot {}
 
 /// RemovePlayers state - removes players who left or were kicked.
 /// Processes the removal queue built during gameplay.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct RemovePlayers {}
 
-/// UpdateBlinds state - increases blinds according to tournament schedule (if applicable).
-/// For cash games, blinds remain constant.
-#[derive(Debug)]
+/// UpdateBlinds state - increases blinds according to the table's blind schedule
+/// (if it has one) and the stacks at the table.
+#[derive(Debug, Deserialize, Serialize)]
 pub struct UpdateBlinds {}
 
 /// BootPlayers state - removes players with insufficient chips.
 /// Players with <= 0 chips are moved to spectators.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct BootPlayers {}
 
 /// A poker game with data and logic for running a poker game end-to-end.
@@ -160,7 +166,7 @@ pub struct BootPlayers {}
 ///
 /// The `Game<T>` struct uses generics to encode state in the type system,
 /// making invalid state transitions impossible at compile time.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Game<T> {
     pub data: GameData,
     pub state: T,
@@ -183,7 +189,8 @@ impl<T> Game<T> {
             .data
             .players
             .iter()
-            .map(|player| {
+            .enumerate()
+            .map(|(player_idx, player)| {
                 let cards = if &player.user.name == username || player.showing {
                     Arc::new(player.cards.clone())  // Clone once, wrap in Arc
                 } else {
@@ -193,6 +200,13 @@ impl<T> Game<T> {
                     user: player.user.clone(),
                     state: player.state.clone(),
                     cards,
+                    sitting_out: self.data.sitting_out.contains_key(&player.user.name),
+                    seat_idx: player.seat_idx,
+                    committed_this_street: self
+                        .data
+                        .pot
+                        .get_street_investment_by_player_idx(player_idx),
+                    total_committed: self.data.pot.get_investment_by_player_idx(player_idx),
                 }
             })
             .collect();
@@ -204,8 +218,13 @@ impl<T> Game<T> {
             open_seats: Arc::clone(&shared.open_seats),
             players,
             board: Arc::clone(&shared.board),
+            second_board: Arc::clone(&shared.second_board),
             pot: Arc::clone(&shared.pot),
             play_positions: Arc::clone(&shared.play_positions),
+            recent_actions: Arc::clone(&shared.recent_actions),
+            sequence: 0,
+            deck_style: None,
+            variant: self.data.settings.variant,
         }
     }
 
@@ -218,6 +237,14 @@ impl<T> Game<T> {
         for votes in self.data.votes.values_mut() {
             votes.remove(&name);
         }
+        self.data.player_queues.to_straddle.remove(&name);
+        self.data.player_queues.to_move.remove(&name);
+        self.data.run_it_twice_consent.remove(&name);
+        if self.data.equity_chop.contains_key(&name) {
+            self.data.equity_chop.clear();
+            self.data.equity_chop_accepted.clear();
+        }
+        self.data.sitting_out.remove(&name);
         self.data.ledger.insert(name, money);
     }
 
@@ -225,6 +252,12 @@ impl<T> Game<T> {
         self.data.players.iter().any(|p| &p.user.name == username)
     }
 
+    /// Return whether every player in an equity chop offer accepted it.
+    fn is_equity_chop_accepted(&self) -> bool {
+        !self.data.equity_chop.is_empty()
+            && self.data.equity_chop_accepted.len() == self.data.equity_chop.len()
+    }
+
     fn contains_user(&self, username: &Username) -> bool {
         self.data.spectators.contains(username)
             || self
@@ -264,9 +297,11 @@ impl<T> Game<T> {
         self.data.play_positions.next_action_idx.map(|action_idx| {
             let mut action_choices = HashSet::from([ActionChoice::Fold]);
             let user = &self.data.players[action_idx].user;
-            let raise = self.data.pot.get_min_raise_by_player_idx(action_idx);
+            let (min_raise, max_raise) = self.get_raise_bounds(action_idx);
             let call = self.data.pot.get_call_by_player_idx(action_idx);
-            if self.data.player_counts.num_active > 1 || call >= user.money {
+            let can_raise = self.data.player_counts.num_active > 1
+                && !self.data.raise_closed.contains(&action_idx);
+            if (can_raise && user.money <= max_raise) || call >= user.money {
                 action_choices.insert(ActionChoice::AllIn);
             }
             if call > 0 && call < user.money {
@@ -274,13 +309,46 @@ impl<T> Game<T> {
             } else if call == 0 {
                 action_choices.insert(ActionChoice::Check);
             }
-            if self.data.player_counts.num_active > 1 && user.money > raise {
-                action_choices.insert(ActionChoice::Raise(raise));
+            if can_raise && user.money > min_raise && max_raise > call {
+                action_choices.insert(ActionChoice::Raise {
+                    min: min_raise,
+                    max: max_raise.min(user.money),
+                });
             }
             ActionChoices(action_choices)
         })
     }
 
+    /// Return the least and most a player can bet to raise under the
+    /// game's betting structure. The most is just a call if the player
+    /// can't raise at all, e.g., once a fixed-limit street is capped.
+    /// All-ins that were too small to be full raises raise the bet to
+    /// call, but not the size of the next raise.
+    fn get_raise_bounds(&self, player_idx: usize) -> (Usd, Usd) {
+        let pot = &self.data.pot;
+        let min_raise = pot.get_min_raise_by_player_idx(player_idx) - self.data.under_raise;
+        match self.data.settings.betting_structure {
+            BettingStructure::NoLimit => (min_raise, Usd::MAX),
+            BettingStructure::PotLimit => (
+                min_raise,
+                pot.get_max_pot_limit_raise_by_player_idx(player_idx),
+            ),
+            BettingStructure::FixedLimit { small_bet, big_bet } => {
+                let call = pot.get_call_by_player_idx(player_idx);
+                if self.data.num_street_bets >= FIXED_LIMIT_MAX_BETS_PER_STREET {
+                    return (call, call);
+                }
+                // The big bet is used from the turn on.
+                let bet = if self.data.board.len() < 4 {
+                    small_bet
+                } else {
+                    big_bet
+                };
+                (call + bet, call + bet)
+            }
+        }
+    }
+
     /// Return the username of the user that has the next turn (or nothing
     /// if there is no turn next). Helps determine whether to notify the
     /// player that their turn has come.
@@ -298,6 +366,18 @@ impl<T> Game<T> {
         self.data.board.len()
     }
 
+    /// Return whether the rest of the board is left to deal with no more
+    /// betting, because at most one player in the hand isn't all-in.
+    fn is_running_out_board(&self) -> bool {
+        let num_players_remaining = self
+            .data
+            .players
+            .iter()
+            .filter(|p| p.state != PlayerState::Fold)
+            .count();
+        num_players_remaining >= 2 && self.is_ready_for_showdown() && self.data.board.len() < 5
+    }
+
     fn get_num_players(&self) -> usize {
         self.data.players.len()
     }
@@ -305,30 +385,142 @@ impl<T> Game<T> {
     /// Return the number of players plus the number of players in
     /// the waitlist. This is equal to the number of players that
     /// could play the game if the game started. This helps determine
-    /// whether the game can actually start.
+    /// whether the game can actually start. Players that are sitting
+    /// out aren't counted, but they still hold onto their seats.
     pub fn get_num_potential_players(&self) -> usize {
+        let num_sitting_out = self.get_num_sitting_out();
         min(
-            self.data.players.len() + self.data.waitlist.len(),
-            self.data.settings.max_players,
+            self.data.players.len() - num_sitting_out + self.data.waitlist.len(),
+            self.data.settings.max_players - num_sitting_out,
         )
     }
 
+    /// Return the number of users watching the game without a seat or
+    /// a spot on the waitlist.
+    pub fn get_num_spectators(&self) -> usize {
+        self.data.spectators.len()
+    }
+
+    /// Return the number of seated players that are sitting out.
+    fn get_num_sitting_out(&self) -> usize {
+        self.data
+            .players
+            .iter()
+            .filter(|p| self.data.sitting_out.contains_key(&p.user.name))
+            .count()
+    }
+
     /// Return the number of pots, indicating whether to continue
     /// showing player hands and distributing the pots, or whether
     /// to move on to other post-game phases.
     pub fn get_num_pots(&self) -> usize {
-        let unique_investments: HashSet<_> = self
+        let folded = self.get_folded_player_indices();
+        self.data.pot.split_into_side_pots(&folded).len()
+    }
+
+    /// Return the indices of players that have folded and can't win
+    /// any part of the pot.
+    fn get_folded_player_indices(&self) -> HashSet<usize> {
+        self.data
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(_, player)| player.state == PlayerState::Fold)
+            .map(|(player_idx, _)| player_idx)
+            .collect()
+    }
+
+    /// Deal community cards to the board, and to the second board too
+    /// if the pot is being run twice.
+    fn deal_board_cards(&mut self, num_cards: usize) {
+        // The rest of the board isn't dealt once everyone else folds. It
+        // stays in the deck so it can be rabbit hunted after the hand.
+        let num_players_remaining = self
             .data
-            .pot
-            .investments
+            .players
             .iter()
-            .filter(|(player_idx, _)| {
-                let player = &self.data.players[**player_idx];
-                player.state != PlayerState::Fold
-            })
-            .map(|(_, investment)| *investment)
+            .filter(|p| p.state != PlayerState::Fold)
+            .count();
+        if num_players_remaining <= 1 {
+            return;
+        }
+        self.data.pot.start_street();
+        for _ in 0..num_cards {
+            let card = self.data.deck.deal_card();
+            self.data.board.push(card);
+        }
+        let num_dealt = self.data.board.len();
+        self.data.events.push_back(GameEvent::DealtBoard(
+            Street::from_num_community_cards(num_dealt),
+            self.data.board[num_dealt - num_cards..].to_vec(),
+        ));
+        if let Some(history) = &mut self.data.hand_history {
+            history.board.push(StreetRecord {
+                street: Street::from_num_community_cards(num_dealt),
+                cards: self.data.board[num_dealt - num_cards..]
+                    .iter()
+                    .map(Card::to_shorthand)
+                    .collect(),
+            });
+        }
+        if self.data.run_it_twice {
+            for _ in 0..num_cards {
+                let card = self.data.deck.deal_card();
+                self.data.second_board.push(card);
+            }
+        }
+    }
+
+    /// Decide the order the players still in the hand reveal their hands
+    /// at showdown. The last player to bet or raise in the final betting
+    /// round shows first, or the first player to act if it was checked
+    /// through, and the rest follow clockwise. There's no order if the pot
+    /// is uncontested or chopped by equity, since no one can be beaten.
+    fn queue_showdown_order(&mut self) {
+        self.data.showdown_order.clear();
+        if self.is_equity_chop_accepted() {
+            return;
+        }
+        let num_players = self.data.players.len();
+        let first_idx = self
+            .data
+            .last_aggressor
+            .unwrap_or(self.data.play_positions.starting_action_idx);
+        let order: Vec<usize> = (0..num_players)
+            .map(|offset| (first_idx + offset) % num_players)
+            .filter(|&player_idx| self.data.players[player_idx].state != PlayerState::Fold)
+            .collect();
+        if order.len() < 2 {
+            return;
+        }
+        let usernames = order
+            .iter()
+            .map(|&player_idx| self.data.players[player_idx].user.name.clone())
             .collect();
-        unique_investments.len()
+        self.data
+            .events
+            .push_back(GameEvent::ShowdownOrder(usernames));
+        self.data.showdown_order = order;
+    }
+
+    /// Work out how much rake the house takes from the pot. Only pots
+    /// that saw a flop are raked, and the rake is rounded down to the
+    /// nearest chip before being capped.
+    fn compute_rake(&mut self) {
+        let pot_size = self.data.pot.get_size();
+        let mut rake = if self.data.board.len() < 3 {
+            0
+        } else {
+            let rake = u64::from(pot_size) * u64::from(self.data.settings.rake_bps) / 10_000;
+            rake.min(u64::from(pot_size)) as Usd
+        };
+        if self.data.settings.rake_cap > 0 {
+            rake = rake.min(self.data.settings.rake_cap);
+        }
+        self.data.rake = rake;
+        if let Some(history) = &mut self.data.hand_history {
+            history.rake = rake;
+        }
     }
 
     fn get_num_users(&self) -> usize {
@@ -391,6 +583,11 @@ impl<T> Game<T> {
     /// in preparation for a new round of betting.
     fn prepare_for_next_phase(&mut self) -> Option<ActionChoices> {
         self.data.player_counts.num_called = 0;
+        // The big blind is the first bet preflop.
+        self.data.num_street_bets = usize::from(self.data.board.is_empty());
+        self.data.last_aggressor = None;
+        self.data.under_raise = 0;
+        self.data.raise_closed.clear();
         // Reset player states for players that are still in the hand.
         for player in self.data.players.iter_mut().filter(|player| {
             matches!(
@@ -403,9 +600,24 @@ impl<T> Game<T> {
         self.data.play_positions.next_action_idx =
             Some(self.data.play_positions.starting_action_idx);
         self.data.play_positions.next_action_idx = self.get_next_action_idx(true);
+        self.data.street_actions_start = self.data.recent_actions.len();
         self.get_next_action_choices()
     }
 
+    /// Add an action to the hand's action history, dropping the oldest
+    /// action on the current street if the street has too many.
+    fn record_recent_action(&mut self, seat: SeatIndex, action: Action) {
+        let street_start = self.data.street_actions_start;
+        if self.data.recent_actions.len() - street_start >= MAX_RECENT_ACTIONS_PER_STREET {
+            self.data.recent_actions.remove(street_start);
+        }
+        self.data.recent_actions.push(RecentAction {
+            seat,
+            action,
+            pot_size: self.data.pot.get_size(),
+        });
+    }
+
     fn queue_player_for_kick_with_event(&mut self, username: &Username) {
         self.data
             .events
@@ -440,6 +652,34 @@ impl<T> Game<T> {
         self.data.player_queues.to_spectate.insert(username.clone());
     }
 
+    fn queue_seat_change_with_event(
+        &mut self,
+        username: &Username,
+        seat_idx: SeatIndex,
+    ) -> Result<(), UserError> {
+        if !self.contains_player(username) && !self.contains_waitlister(username) {
+            return Err(UserError::UserNotPlaying);
+        }
+        // Only one user can claim an open seat at a time.
+        let is_claimed = self
+            .data
+            .player_queues
+            .to_move
+            .iter()
+            .any(|(other, &other_seat_idx)| other != username && other_seat_idx == seat_idx);
+        if !self.data.open_seats.contains(&seat_idx) || is_claimed {
+            return Err(UserError::SeatUnavailable);
+        }
+        self.data
+            .events
+            .push_back(GameEvent::SeatChangeQueue(username.clone(), seat_idx));
+        self.data
+            .player_queues
+            .to_move
+            .insert(username.clone(), seat_idx);
+        Ok(())
+    }
+
     fn seat_player_with_event(&mut self, player: Player) {
         self.data
             .events
@@ -477,10 +717,12 @@ impl<T> GameStateManagement for Game<T> {
             waitlist: Arc::new(self.data.waitlist.clone()),
             open_seats: Arc::new(self.data.open_seats.clone()),
             board: Arc::new(self.data.board.clone()),
+            second_board: Arc::new(self.data.second_board.clone()),
             pot: Arc::new(PotView {
                 size: self.data.pot.get_size(),
             }),
             play_positions: Arc::new(self.data.play_positions.clone()),
+            recent_actions: Arc::new(self.data.recent_actions.clone()),
         };
 
         let mut views = HashMap::with_capacity(self.data.settings.max_users);
@@ -889,8 +1131,95 @@ impl From<Game<SeatPlayers>> for Game<Lobby> {
     }
 }
 
+impl Game<SeatPlayers> {
+    /// Move users that queued a seat change into their requested seats.
+    /// Players give up their old seat, and waitlisted users are seated
+    /// ahead of the rest of the waitlist.
+    fn change_seats(&mut self) {
+        while let Some((username, seat_idx)) = self.data.player_queues.to_move.pop_first() {
+            // It's possible the user left or moved to spectate since they
+            // queued the seat change, so the request is just dropped.
+            let Some(open_seat_pos) = self.data.open_seats.iter().position(|&idx| idx == seat_idx)
+            else {
+                continue;
+            };
+            if let Some(player) = self
+                .data
+                .players
+                .iter_mut()
+                .find(|p| p.user.name == username)
+            {
+                self.data.open_seats.remove(open_seat_pos);
+                self.data.open_seats.push_back(player.seat_idx);
+                player.seat_idx = seat_idx;
+                self.data
+                    .events
+                    .push_back(GameEvent::ChangedSeat(username, seat_idx));
+            } else if let Some(waitlist_idx) =
+                self.data.waitlist.iter().position(|u| u.name == username)
+                && let Some(user) = self.data.waitlist.remove(waitlist_idx)
+            {
+                if user.money < self.data.blinds.big {
+                    self.spectate_user_with_event(user);
+                } else {
+                    self.data.open_seats.remove(open_seat_pos);
+                    let player = Player::new(user, seat_idx);
+                    self.seat_player_with_event(player);
+                }
+            }
+        }
+    }
+
+    /// Waitlisted users that will take the open seats, in the order
+    /// they'll be seated.
+    fn waitlisters_to_seat(&self) -> Vec<Username> {
+        self.data
+            .waitlist
+            .iter()
+            .take(self.data.open_seats.len())
+            .map(|u| u.name.clone())
+            .collect()
+    }
+
+    fn set_waitlister_money(&mut self, username: &Username, money: Usd) -> Result<(), UserError> {
+        let user = self
+            .data
+            .waitlist
+            .iter_mut()
+            .find(|u| &u.name == username)
+            .ok_or(UserError::UserDoesNotExist)?;
+        user.money = money;
+        Ok(())
+    }
+
+    /// Count another hand for each player that's sitting out, kicking
+    /// players that've sat out for too many hands to free up their seats.
+    fn kick_idle_players(&mut self) {
+        // Players that moved to spectate or left while sitting out
+        // no longer need to be tracked.
+        let players = &self.data.players;
+        self.data
+            .sitting_out
+            .retain(|username, _| players.iter().any(|p| &p.user.name == username));
+        let max_sit_out_hands = self.data.settings.max_sit_out_hands;
+        let mut to_kick = Vec::new();
+        for (username, num_hands) in &mut self.data.sitting_out {
+            if max_sit_out_hands > 0 && *num_hands >= max_sit_out_hands {
+                to_kick.push(username.clone());
+            } else {
+                *num_hands += 1;
+            }
+        }
+        for username in to_kick {
+            let _ = self.kick_user(&username);
+        }
+    }
+}
+
 impl From<Game<SeatPlayers>> for Game<MoveButton> {
     fn from(mut value: Game<SeatPlayers>) -> Self {
+        value.kick_idle_players();
+        value.change_seats();
         while !value.data.open_seats.is_empty() && !value.data.waitlist.is_empty() {
             // Safety: while loop condition guarantees both collections are non-empty
             if let (Some(open_seat_idx), Some(user)) = (
@@ -909,7 +1238,14 @@ impl From<Game<SeatPlayers>> for Game<MoveButton> {
             }
         }
         value.data.players.sort_by_key(|p| p.seat_idx);
-        value.data.player_counts.num_active = value.get_num_players();
+        // Players that are sitting out are dealt out of the game as if
+        // they'd already folded.
+        for player in &mut value.data.players {
+            if value.data.sitting_out.contains_key(&player.user.name) {
+                player.state = PlayerState::Fold;
+            }
+        }
+        value.data.player_counts.num_active = value.get_num_players() - value.get_num_sitting_out();
         Self {
             data: value.data,
             state: MoveButton {},
@@ -919,38 +1255,96 @@ impl From<Game<SeatPlayers>> for Game<MoveButton> {
 
 /// Move the blinds and next action indices, preparing the next game
 /// by determining who will be paying blinds and who will be making
-/// the first action.
+/// the first action. Players that are sitting out are skipped.
+///
+/// Once blinds have been posted, they follow the dead button rules so
+/// players leaving between hands never cause a blind to be skipped or
+/// posted twice in a row. The big blind moves to the next player after
+/// last hand's big blind seat, the small blind goes to that seat, and the
+/// button goes to last hand's small blind seat. If the player in the
+/// small blind seat left or is sitting out, the small blind is dead and
+/// the button may be left on an empty seat. Heads-up, the button posts
+/// the small blind instead, so the first action preflop is the button's.
 impl From<Game<MoveButton>> for Game<CollectBlinds> {
     fn from(mut value: Game<MoveButton>) -> Self {
-        let num_players = value.get_num_players();
-        let player_indices = value
-            .data
-            .players
-            .iter()
-            .enumerate()
-            .map(|(player_idx, _)| player_idx);
-        // Search for the big blind and starting positions.
-        let mut seats = player_indices
-            .clone()
-            .cycle()
-            .skip(value.data.play_positions.big_blind_idx + 1);
-        // Safety: .cycle() creates infinite iterator, .next() always returns Some
-        value.data.play_positions.big_blind_idx =
-            seats.next().unwrap_or(0);
-        value.data.play_positions.starting_action_idx =
-            seats.next().unwrap_or(0);
-        value.data.play_positions.next_action_idx =
-            Some(value.data.play_positions.starting_action_idx);
-        // Reverse the table search to find the small blind position relative
-        // to the big blind position since the small blind must always trail the big
-        // blind.
-        let mut seats = player_indices
-            .rev()
-            .cycle()
-            .skip(num_players - value.data.play_positions.big_blind_idx);
-        // Safety: .cycle() creates infinite iterator, .next() always returns Some
-        value.data.play_positions.small_blind_idx =
-            seats.next().unwrap_or(0);
+        let players = &value.data.players;
+        let positions = &mut value.data.play_positions;
+        let dealt_in: Vec<usize> = (0..players.len())
+            .filter(|&player_idx| players[player_idx].state != PlayerState::Fold)
+            .collect();
+        let seat_of = |player_idx: usize| players[player_idx].seat_idx;
+        // Searches cover every player once, wrapping around the table, so
+        // they only come up empty if no one is dealt in.
+        let next_after = |player_idx: usize| {
+            dealt_in
+                .iter()
+                .copied()
+                .find(|&idx| idx > player_idx)
+                .or_else(|| dealt_in.first().copied())
+        };
+        let prev_before = |player_idx: usize| {
+            dealt_in
+                .iter()
+                .copied()
+                .rev()
+                .find(|&idx| idx < player_idx)
+                .or_else(|| dealt_in.last().copied())
+        };
+
+        let last_big_blind_seat = positions.big_blind_seat;
+        let big_blind_idx = match last_big_blind_seat {
+            // Blinds move by seat once they've been posted since player
+            // indices shift when players leave.
+            Some(seat_idx) => dealt_in
+                .iter()
+                .copied()
+                .find(|&idx| seat_of(idx) > seat_idx)
+                .or_else(|| dealt_in.first().copied()),
+            None => next_after(positions.big_blind_idx),
+        }
+        .unwrap_or(0);
+        let heads_up = dealt_in.len() == 2;
+        let (small_blind_idx, button_seat) = match last_big_blind_seat {
+            // Heads-up, the button posts the small blind.
+            _ if heads_up => {
+                let small_blind_idx = prev_before(big_blind_idx);
+                (small_blind_idx, small_blind_idx.map(seat_of))
+            }
+            // Whoever posted the big blind last hand posts the small blind,
+            // and the button moves to where the small blind was, even if
+            // those seats are empty now.
+            Some(seat_idx) => (
+                dealt_in
+                    .iter()
+                    .copied()
+                    .find(|&idx| seat_of(idx) == seat_idx),
+                positions.small_blind_seat,
+            ),
+            None => {
+                let small_blind_idx = prev_before(big_blind_idx);
+                let button_idx = small_blind_idx.and_then(prev_before);
+                (small_blind_idx, button_idx.map(seat_of))
+            }
+        };
+
+        positions.dead_small_blind = small_blind_idx.is_none();
+        positions.small_blind_seat = small_blind_idx.map(seat_of).or(last_big_blind_seat);
+        // A dead small blind leaves the big blind as the first player in
+        // the deal order.
+        positions.small_blind_idx = small_blind_idx.unwrap_or(big_blind_idx);
+        positions.big_blind_idx = big_blind_idx;
+        positions.big_blind_seat = (!dealt_in.is_empty()).then(|| seat_of(big_blind_idx));
+        positions.button_seat = button_seat;
+        positions.heads_up = heads_up;
+        positions.starting_action_idx = next_after(big_blind_idx).unwrap_or(big_blind_idx);
+        positions.next_action_idx = Some(positions.starting_action_idx);
+        positions.straddle_idx = None;
+        if let Some(seat_idx) = button_seat {
+            value
+                .data
+                .events
+                .push_back(GameEvent::ButtonMoved(seat_idx));
+        }
         Self {
             data: value.data,
             state: CollectBlinds {},
@@ -958,10 +1352,133 @@ impl From<Game<MoveButton>> for Game<CollectBlinds> {
     }
 }
 
-/// Collect blinds, initializing the main pot.
+impl Game<CollectBlinds> {
+    /// Collect the ante from every player that's dealt in. Players that
+    /// can't cover the ante go all-in for whatever they have left.
+    fn collect_antes(&mut self) {
+        let ante = self.data.blinds.ante;
+        if ante == 0 {
+            return;
+        }
+        for (player_idx, player) in self.data.players.iter_mut().enumerate() {
+            // Players that are sitting out don't pay antes.
+            if player.state == PlayerState::Fold {
+                continue;
+            }
+            let bet = if player.user.money > ante {
+                Bet {
+                    action: BetAction::Call,
+                    amount: ante,
+                }
+            } else {
+                player.state = PlayerState::AllIn;
+                self.data.player_counts.num_active -= 1;
+                Bet {
+                    action: BetAction::AllIn,
+                    amount: player.user.money,
+                }
+            };
+            self.data.pot.bet(player_idx, &bet);
+            player.user.money -= bet.amount;
+        }
+    }
+
+    /// Collect a straddle of twice the big blind from the player under
+    /// the gun if they've opted into straddling and can afford it. The
+    /// preflop action then starts to the left of the straddler so they
+    /// act last, just like the big blind would without a straddle.
+    fn collect_straddle(&mut self) {
+        let num_players = self.get_num_players();
+        let num_dealt_in = self
+            .data
+            .players
+            .iter()
+            .filter(|p| p.state != PlayerState::Fold)
+            .count();
+        if !self.data.settings.straddle_allowed || num_dealt_in < 3 {
+            return;
+        }
+        let straddle_idx = self.data.play_positions.starting_action_idx;
+        let straddle = 2 * self.data.blinds.big;
+        let player = &mut self.data.players[straddle_idx];
+        if !self
+            .data
+            .player_queues
+            .to_straddle
+            .remove(&player.user.name)
+            || player.user.money <= straddle
+        {
+            return;
+        }
+        let bet = Bet {
+            action: BetAction::Raise,
+            amount: straddle,
+        };
+        player.state = PlayerState::Wait;
+        player.user.money -= bet.amount;
+        self.data
+            .events
+            .push_back(GameEvent::PostedBlind(player.user.name.clone(), bet.amount));
+        self.data.pot.bet(straddle_idx, &bet);
+        self.data.play_positions.straddle_idx = Some(straddle_idx);
+        self.data.play_positions.starting_action_idx = (straddle_idx + 1) % num_players;
+        self.data.play_positions.next_action_idx =
+            Some(self.data.play_positions.starting_action_idx);
+    }
+
+    /// Record how much each player was forced to put in the pot before
+    /// the cards are dealt so the hand can be replayed from its history.
+    fn record_forced_bets(&mut self) {
+        let Some(history) = &mut self.data.hand_history else {
+            return;
+        };
+        history.forced_bets = history
+            .seats
+            .iter()
+            .filter_map(|seat| {
+                let player = self
+                    .data
+                    .players
+                    .iter()
+                    .find(|p| p.user.name == seat.username)?;
+                let amount = seat.starting_stack - player.user.money;
+                (amount > 0).then(|| ForcedBetRecord {
+                    seat: seat.seat,
+                    username: seat.username.clone(),
+                    amount,
+                })
+            })
+            .collect();
+    }
+}
+
+/// Collect antes, blinds, and the straddle (if any), initializing
+/// the main pot.
 impl From<Game<CollectBlinds>> for Game<Deal> {
     fn from(mut value: Game<CollectBlinds>) -> Self {
         value.data.pot = Pot::new(value.data.settings.max_players);
+        value.data.rake = 0;
+        value.data.rabbit_hunt_cards.clear();
+        value.data.recent_actions.clear();
+        value.data.street_actions_start = 0;
+        value.data.last_aggressor = None;
+        value.data.showdown_order.clear();
+        let seats = value
+            .data
+            .players
+            .iter()
+            .filter(|p| p.state != PlayerState::Fold)
+            .map(|p| SeatRecord {
+                seat: p.seat_idx,
+                username: p.user.name.clone(),
+                starting_stack: p.user.money,
+                hole_cards: None,
+            })
+            .collect();
+        value.data.hand_history = Some(HandHistory::new(value.data.blinds.clone(), seats));
+        value.collect_antes();
+        // No one posts a dead small blind.
+        let num_dead_blinds = usize::from(value.data.play_positions.dead_small_blind);
         for (player_idx, blind) in [
             (
                 value.data.play_positions.small_blind_idx,
@@ -971,8 +1488,15 @@ impl From<Game<CollectBlinds>> for Game<Deal> {
                 value.data.play_positions.big_blind_idx,
                 value.data.blinds.big,
             ),
-        ] {
+        ]
+        .into_iter()
+        .skip(num_dead_blinds)
+        {
             let player = &mut value.data.players[player_idx];
+            // The player may have already gone all-in for the ante.
+            if player.state == PlayerState::AllIn {
+                continue;
+            }
             let bet = match player.user.money.cmp(&blind) {
                 Ordering::Equal => {
                     player.state = PlayerState::AllIn;
@@ -1008,7 +1532,13 @@ impl From<Game<CollectBlinds>> for Game<Deal> {
             // we don't even need to check.
             value.data.pot.bet(player_idx, &bet);
             player.user.money -= bet.amount;
+            value
+                .data
+                .events
+                .push_back(GameEvent::PostedBlind(player.user.name.clone(), bet.amount));
         }
+        value.collect_straddle();
+        value.record_forced_bets();
         value.data.player_counts.num_called = 0;
         Self {
             data: value.data,
@@ -1017,23 +1547,31 @@ impl From<Game<CollectBlinds>> for Game<Deal> {
     }
 }
 
-/// Shuffle the game's deck and deal 2 cards to each player.
+/// Shuffle the game's deck and deal hole cards to each player that's
+/// dealt in (2 each, or 4 in Omaha).
 impl From<Game<Deal>> for Game<TakeAction> {
     fn from(mut value: Game<Deal>) -> Self {
         value.data.deck.shuffle();
 
         let num_players = value.get_num_players();
-        let mut seats = (0..num_players)
-            .cycle()
-            .skip(value.data.play_positions.small_blind_idx);
-        // Deal 2 cards per player, looping over players and dealing them 1 card
-        // at a time.
-        for _ in 0..(2 * num_players) {
-            // Safety: .cycle() creates infinite iterator, .next() always returns Some
-            let deal_idx = seats.next().unwrap_or(0);
-            let player = &mut value.data.players[deal_idx];
-            let card = value.data.deck.deal_card();
-            player.cards.push(card);
+        let deal_order: Vec<usize> = (0..num_players)
+            .map(|offset| (value.data.play_positions.small_blind_idx + offset) % num_players)
+            .filter(|&player_idx| value.data.players[player_idx].state != PlayerState::Fold)
+            .collect();
+        // Deal hole cards per player, looping over players and dealing them
+        // 1 card at a time.
+        let num_hole_cards = value.data.settings.variant.num_hole_cards();
+        for &deal_idx in &deal_order {
+            value.data.players[deal_idx]
+                .cards
+                .reserve_exact(num_hole_cards);
+        }
+        for _ in 0..num_hole_cards {
+            for &deal_idx in &deal_order {
+                let player = &mut value.data.players[deal_idx];
+                let card = value.data.deck.deal_card();
+                player.cards.push(card);
+            }
         }
         let action_choices = value.prepare_for_next_phase();
         Self {
@@ -1047,7 +1585,29 @@ impl From<Game<Deal>> for Game<TakeAction> {
 
 impl Game<TakeAction> {
     pub fn act(&mut self, action: Action) -> Result<Action, UserError> {
+        let player_idx = self.data.play_positions.next_action_idx;
+        let money_before = player_idx.map(|idx| self.data.players[idx].user.money);
         let sanitized_action = self.affect(action)?;
+        if let Some(player_idx) = player_idx {
+            self.data.raise_closed.insert(player_idx);
+            let player = &self.data.players[player_idx];
+            let event = GameEvent::Acted(player.user.name.clone(), sanitized_action.clone());
+            self.data.events.push_back(event);
+            let seat = self.data.players[player_idx].seat_idx;
+            self.record_recent_action(seat, sanitized_action.clone());
+        }
+        if let (Some(player_idx), Some(money_before), Some(history)) =
+            (player_idx, money_before, &mut self.data.hand_history)
+        {
+            let player = &self.data.players[player_idx];
+            history.actions.push(ActionRecord {
+                street: Street::from_num_community_cards(self.data.board.len()),
+                seat: player.seat_idx,
+                username: player.user.name.clone(),
+                action: (&sanitized_action).into(),
+                amount: money_before - player.user.money,
+            });
+        }
         self.data.play_positions.next_action_idx = self.get_next_action_idx(false);
         if self.is_ready_for_next_phase() {
             self.data.play_positions.next_action_idx = None;
@@ -1118,15 +1678,20 @@ impl Game<TakeAction> {
         player_idx: SeatIndex,
         player_investment: Usd,
         pot_call: Usd,
+        (min_raise, max_raise): (Usd, Usd),
     ) -> Result<(), Bet> {
         let new_player_investment = player_investment + bet.amount;
+        let is_raise = new_player_investment > pot_call;
         let player = &mut self.data.players[player_idx];
 
         // Update player counts and state based on bet action
         match bet.action {
             BetAction::AllIn => {
+                if is_raise && bet.amount > max_raise {
+                    return Err(bet.clone());
+                }
                 self.data.player_counts.num_active -= 1;
-                if new_player_investment > pot_call {
+                if is_raise {
                     self.data.player_counts.num_called = 0;
                 }
                 player.state = PlayerState::AllIn;
@@ -1136,13 +1701,24 @@ impl Game<TakeAction> {
                 player.state = PlayerState::Call;
             }
             BetAction::Raise => {
-                if new_player_investment < (2 * pot_call) {
+                if bet.amount < min_raise || bet.amount > max_raise {
                     return Err(bet.clone());
                 }
                 self.data.player_counts.num_called = 1;
                 player.state = PlayerState::Raise;
             }
         }
+        if is_raise {
+            self.data.num_street_bets += 1;
+            self.data.last_aggressor = Some(player_idx);
+            if bet.amount >= min_raise {
+                // A full raise reopens the betting for everyone else.
+                self.data.under_raise = 0;
+                self.data.raise_closed.clear();
+            } else {
+                self.data.under_raise += new_player_investment - pot_call;
+            }
+        }
 
         // Deduct bet from player's money and add to pot
         player.user.money -= bet.amount;
@@ -1185,7 +1761,8 @@ impl Game<TakeAction> {
                 let pot_call = self.data.pot.get_call();
                 let player_investment = self.data.pot.get_investment_by_player_idx(player_idx);
                 let player_call = pot_call - player_investment;
-                let player_raise = 2 * pot_call - player_investment;
+                let raise_bounds = self.get_raise_bounds(player_idx);
+                let player_raise = raise_bounds.0;
 
                 // Convert action to bet (or handle check/fold immediately)
                 let bet = match self.convert_action_to_bet(
@@ -1199,9 +1776,17 @@ impl Game<TakeAction> {
                     Err(handled_action) => return Ok(handled_action), // Check or Fold handled
                 };
 
+                // Only an all-in can raise by less than the minimum, and a
+                // raise the player can't cover was already made an all-in
+                if bet.action == BetAction::Raise && bet.amount < player_raise {
+                    return Err(UserError::RaiseBelowMinimum {
+                        min_raise: player_raise,
+                    });
+                }
+
                 // Apply the bet and update game state
                 if let Err(invalid_bet) =
-                    self.apply_bet(&bet, player_idx, player_investment, pot_call)
+                    self.apply_bet(&bet, player_idx, player_investment, pot_call, raise_bounds)
                 {
                     return Err(UserError::InvalidBet { bet: invalid_bet });
                 }
@@ -1220,10 +1805,139 @@ impl Game<TakeAction> {
     pub fn get_action_choices(&self) -> Option<ActionChoices> {
         self.state.action_choices.clone()
     }
+
+    /// Return the action choices of the player whose turn it is, along
+    /// with the call amount, minimum raise, and pot odds they face.
+    #[must_use]
+    pub fn get_turn_info(&self) -> Option<TurnInfo> {
+        let action_idx = self.data.play_positions.next_action_idx?;
+        let action_choices = self.state.action_choices.clone()?;
+        let call_amount = self
+            .data
+            .pot
+            .get_call_by_player_idx(action_idx)
+            .min(self.data.players[action_idx].user.money);
+        Some(TurnInfo::new(
+            action_choices,
+            call_amount,
+            self.data.pot.get_size(),
+        ))
+    }
+}
+
+impl Game<TakeAction> {
+    /// Check whether to run the rest of the board twice now that the
+    /// betting is over. This requires running it twice to be allowed,
+    /// cards left to come, enough cards in the deck for both runs, and
+    /// every player left in the pot agreeing to it.
+    fn prepare_run_it_twice(&mut self) {
+        if !self.data.settings.run_it_twice_allowed
+            || self.data.run_it_twice
+            || self.data.board.len() >= 5
+            || !self.is_ready_for_showdown()
+        {
+            return;
+        }
+        let remaining: Vec<_> = self
+            .data
+            .players
+            .iter()
+            .filter(|p| p.state != PlayerState::Fold)
+            .collect();
+        // Both runs deal the rest of the board from the same deck, so a
+        // full table may not have enough cards left for a second run.
+        let num_board_cards_left = 5 - self.data.board.len();
+        if remaining.len() >= 2
+            && remaining
+                .iter()
+                .all(|p| self.data.run_it_twice_consent.contains(&p.user.name))
+            && self.data.deck.cards_left() >= 2 * num_board_cards_left
+        {
+            self.data.run_it_twice = true;
+            self.data.second_board = self.data.board.clone();
+            self.data.events.push_back(GameEvent::RunItTwice);
+        }
+    }
+}
+
+impl Game<TakeAction> {
+    /// Offer the two players left in an all-in pot to chop it by their
+    /// current equity instead of dealing the rest of the board. The
+    /// equities are snapshotted now, so they don't change by the time
+    /// the offer is accepted.
+    fn offer_equity_chop(&mut self) -> Result<Vec<(Username, f64)>, UserError> {
+        let remaining: Vec<_> = self
+            .data
+            .players
+            .iter()
+            .filter(|p| p.state != PlayerState::Fold)
+            .collect();
+        if !self.is_ready_for_showdown()
+            || self.data.board.len() >= 5
+            || remaining.len() != 2
+            || !self.data.equity_chop.is_empty()
+        {
+            return Err(UserError::CannotOfferEquityChop);
+        }
+        let holes: Vec<_> = remaining.iter().map(|p| p.cards.clone()).collect();
+        let shares = functional::showdown_equities(
+            &holes,
+            &self.data.board,
+            self.data.settings.variant,
+            EQUITY_CHOP_ITERATIONS,
+        );
+        let offer: Vec<_> = remaining
+            .iter()
+            .map(|p| p.user.name.clone())
+            .zip(shares)
+            .collect();
+        self.data.equity_chop = offer.iter().cloned().collect();
+        self.data.equity_chop_accepted.clear();
+        Ok(offer)
+    }
+
+    /// Accept or decline the equity chop that's on offer. Declining
+    /// withdraws the offer so the rest of the board is dealt as usual.
+    fn respond_to_equity_chop(
+        &mut self,
+        username: &Username,
+        accept: bool,
+    ) -> Result<(), UserError> {
+        if !self.data.equity_chop.contains_key(username) {
+            return Err(UserError::NoEquityChopOffer);
+        }
+        if accept {
+            self.data.equity_chop_accepted.insert(username.clone());
+            if self.is_equity_chop_accepted() {
+                self.data.events.push_back(GameEvent::EquityChop);
+            }
+        } else {
+            self.data.equity_chop.clear();
+            self.data.equity_chop_accepted.clear();
+        }
+        Ok(())
+    }
+
+    /// Return whether an equity chop is waiting on players to answer it.
+    /// The board isn't dealt until everyone answers.
+    fn is_equity_chop_pending(&self) -> bool {
+        !self.data.equity_chop.is_empty() && !self.is_equity_chop_accepted()
+    }
 }
 
 impl From<Game<TakeAction>> for Game<Flop> {
-    fn from(value: Game<TakeAction>) -> Self {
+    fn from(mut value: Game<TakeAction>) -> Self {
+        value.prepare_run_it_twice();
+        let positions = &mut value.data.play_positions;
+        if positions.heads_up {
+            // The button acts first preflop heads-up, but last on every
+            // street after.
+            positions.starting_action_idx = positions.big_blind_idx;
+        } else if let Some(straddle_idx) = positions.straddle_idx {
+            // The straddler only acts last preflop. Postflop action starts
+            // from the usual position again.
+            positions.starting_action_idx = straddle_idx;
+        }
         Self {
             data: value.data,
             state: Flop {},
@@ -1232,7 +1946,8 @@ impl From<Game<TakeAction>> for Game<Flop> {
 }
 
 impl From<Game<TakeAction>> for Game<Turn> {
-    fn from(value: Game<TakeAction>) -> Self {
+    fn from(mut value: Game<TakeAction>) -> Self {
+        value.prepare_run_it_twice();
         Self {
             data: value.data,
             state: Turn {},
@@ -1241,7 +1956,8 @@ impl From<Game<TakeAction>> for Game<Turn> {
 }
 
 impl From<Game<TakeAction>> for Game<River> {
-    fn from(value: Game<TakeAction>) -> Self {
+    fn from(mut value: Game<TakeAction>) -> Self {
+        value.prepare_run_it_twice();
         Self {
             data: value.data,
             state: River {},
@@ -1250,7 +1966,9 @@ impl From<Game<TakeAction>> for Game<River> {
 }
 
 impl From<Game<TakeAction>> for Game<ShowHands> {
-    fn from(value: Game<TakeAction>) -> Self {
+    fn from(mut value: Game<TakeAction>) -> Self {
+        value.compute_rake();
+        value.queue_showdown_order();
         Self {
             data: value.data,
             state: ShowHands {},
@@ -1262,10 +1980,7 @@ impl From<Game<TakeAction>> for Game<ShowHands> {
 
 impl Game<Flop> {
     fn step(&mut self) {
-        for _ in 0..3 {
-            let card = self.data.deck.deal_card();
-            self.data.board.push(card);
-        }
+        self.deal_board_cards(3);
     }
 }
 
@@ -1297,8 +2012,7 @@ impl From<Game<Flop>> for Game<Turn> {
 
 impl Game<Turn> {
     fn step(&mut self) {
-        let card = self.data.deck.deal_card();
-        self.data.board.push(card);
+        self.deal_board_cards(1);
     }
 }
 
@@ -1330,8 +2044,7 @@ impl From<Game<Turn>> for Game<River> {
 
 impl Game<River> {
     fn step(&mut self) {
-        let card = self.data.deck.deal_card();
-        self.data.board.push(card);
+        self.deal_board_cards(1);
     }
 }
 
@@ -1352,6 +2065,8 @@ impl From<Game<River>> for Game<TakeAction> {
 impl From<Game<River>> for Game<ShowHands> {
     fn from(mut value: Game<River>) -> Self {
         value.step();
+        value.compute_rake();
+        value.queue_showdown_order();
         Self {
             data: value.data,
             state: ShowHands {},
@@ -1370,119 +2085,364 @@ macro_rules! impl_show_hands {
                     .find(|p| &p.user.name == username)
                 {
                     Some(player) => {
-                        if !player.showing {
+                        if player.showing {
+                            Err(UserError::UserAlreadyShowingHand)
+                        } else if player.mucked {
+                            Err(UserError::UserAlreadyMuckedHand)
+                        } else {
                             player.showing = true;
+                            self.data
+                                .events
+                                .push_back(GameEvent::ShowedHand(username.clone(), player.cards.clone()));
+                            if let Some(history) = &mut self.data.hand_history {
+                                history.show_cards(&player.user.name, &player.cards);
+                            }
                             Ok(())
-                        } else {
-                            Err(UserError::UserAlreadyShowingHand)
                         }
                     }
                     None => Err(UserError::UserNotPlaying),
                 }
             }
-        })*
-    }
-}
 
-impl_show_hands!(
-    Game<ShowHands>,
-    Game<DistributePot>,
-    Game<RemovePlayers>,
+            /// Decline to show a hand at showdown. The hand stays hidden
+            /// from other users unless it wins a contested pot.
+            pub fn muck_hand(&mut self, username: &Username) -> Result<(), UserError> {
+                match self
+                    .data
+                    .players
+                    .iter_mut()
+                    .find(|p| &p.user.name == username)
+                {
+                    Some(player) => {
+                        if player.state == PlayerState::Fold {
+                            Err(UserError::CannotMuckHand)
+                        } else if player.showing {
+                            Err(UserError::UserAlreadyShowingHand)
+                        } else if player.mucked {
+                            Err(UserError::UserAlreadyMuckedHand)
+                        } else {
+                            player.mucked = true;
+                            Ok(())
+                        }
+                    }
+                    None => Err(UserError::UserNotPlaying),
+                }
+            }
+        })*
+    }
+}
+
+impl_show_hands!(
+    Game<ShowHands>,
+    Game<DistributePot>,
+    Game<RemovePlayers>,
     Game<UpdateBlinds>
 );
 
 impl From<Game<ShowHands>> for Game<DistributePot> {
-    fn from(mut value: Game<ShowHands>) -> Self {
-        let num_players_remaining: usize = value
-            .data
-            .players
-            .iter()
-            .map(|p| usize::from(p.state != PlayerState::Fold))
-            .sum();
-        if num_players_remaining > 1 {
-            for player_idx in value.data.pot.investments.keys() {
-                let player = &mut value.data.players[*player_idx];
-                if player.state != PlayerState::Fold {
-                    player.showing = true;
-                }
-            }
-        }
-        Self {
+    fn from(value: Game<ShowHands>) -> Self {
+        let mut game = Self {
             data: value.data,
             state: DistributePot {},
-        }
+        };
+        game.reveal_in_showdown_order();
+        game.show_winning_hands();
+        game
     }
 }
 
 // --- DistributePot State ---
 
 impl Game<DistributePot> {
-    /// Get all players in the pot that haven't folded and compare their
-    /// hands to one another. Get the winning indices and distribute
-    /// the pot accordingly.
-    fn distribute(&mut self) {
-        let mut investments = Vec::from_iter(self.data.pot.investments.iter_mut());
-        investments
-            .sort_unstable_by(|(_, investment1), (_, investment2)| investment1.cmp(investment2));
-        if let Some((_, largest_call)) = investments.last() {
-            // Get the pot size and the player indices in the pot.
-            let mut pot_idx = investments.len() - 1;
-            let mut pot_call = **largest_call;
-            for (idx, (player_idx, investment)) in investments.iter().enumerate().rev() {
-                let player = &self.data.players[**player_idx];
-                // If the player's investment doesn't match the pot's call, then
-                // that means they and everyone with smaller investements aren't
-                // included in the pot.
-                if player.state != PlayerState::Fold && investment < largest_call {
-                    pot_call = **largest_call - **investment;
-                    break;
+    /// Return the players that win a pot on the given board by comparing
+    /// the hands of the players that're eligible for it, along with a
+    /// description of the winning hand. The hand isn't described when the
+    /// pot is uncontested since the winner never has to show.
+    fn get_board_winners(
+        &self,
+        eligible: &[usize],
+        board: &[Card],
+    ) -> (Vec<usize>, Option<String>) {
+        let hands_in_pot: Vec<_> = eligible
+            .iter()
+            .map(|player_idx| {
+                let player = &self.data.players[*player_idx];
+                functional::eval_hole_with_board(&player.cards, board, self.data.settings.variant)
+            })
+            .collect();
+        let winner_indices = functional::argmax_with(&hands_in_pot, self.data.settings.variant);
+        let hand = winner_indices
+            .first()
+            .and_then(|winner_idx| hands_in_pot[*winner_idx].first())
+            .filter(|_| eligible.len() >= 2)
+            .map(SubHand::describe);
+        let winners = winner_indices
+            .into_iter()
+            .map(|winner_idx| eligible[winner_idx])
+            .collect();
+        (winners, hand)
+    }
+
+    /// Split a pot amongst its winners. Remainder chips are awarded to
+    /// winner(s) in earliest position (standard poker rule), and are
+    /// tallied in `odd_chips`.
+    fn split_pot(
+        winners: &[usize],
+        amount: Usd,
+        odd_chips: &mut BTreeMap<usize, Usd>,
+    ) -> Vec<(usize, Usd)> {
+        let num_winners = winners.len().max(1);
+        let pot_split = amount / num_winners as Usd;
+        let pot_remainder = amount % num_winners as Usd;
+        winners
+            .iter()
+            .enumerate()
+            .map(|(i, player_idx)| {
+                let odd_chip = Usd::from((i as Usd) < pot_remainder);
+                if odd_chip > 0 {
+                    *odd_chips.entry(*player_idx).or_default() += odd_chip;
                 }
-                pot_idx = idx;
-            }
-
-            // Evaluate the hands in the pot and get the winners.
-            let mut pot_size: Usd = 0;
-            let mut seats_in_pot = Vec::with_capacity(self.data.settings.max_players);
-            let mut hands_in_pot = Vec::with_capacity(self.data.settings.max_players);
-            for (player_idx, investment) in investments[pot_idx..].as_mut() {
-                let pot_investment = min(pot_call, **investment);
-                pot_size += pot_investment;
-                **investment -= pot_investment;
-                let player = &mut self.data.players[**player_idx];
-                if player.state != PlayerState::Fold {
-                    seats_in_pot.push(*player_idx);
-                    let mut cards = player.cards.clone();
-                    cards.extend(self.data.board.clone());
-                    functional::prepare_hand(&mut cards);
-                    let hand = functional::eval(&cards);
-                    hands_in_pot.push(hand);
+                (*player_idx, pot_split + odd_chip)
+            })
+            .collect()
+    }
+
+    /// Return how much each player in a side pot gets when the pot is
+    /// chopped by the equities from when the chop was offered.
+    fn get_equity_chop_awards(
+        &self,
+        side_pot: &SidePot,
+        odd_chips: &mut BTreeMap<usize, Usd>,
+    ) -> Vec<(usize, Usd)> {
+        let shares: Vec<_> = side_pot
+            .eligible
+            .iter()
+            .map(|player_idx| {
+                let username = &self.data.players[*player_idx].user.name;
+                self.data.equity_chop.get(username).copied().unwrap_or(0.0)
+            })
+            .collect();
+        let total_shares: f64 = shares.iter().sum();
+        let mut awards: BTreeMap<usize, Usd> = BTreeMap::new();
+        for (player_idx, share) in side_pot.eligible.iter().zip(shares) {
+            let award = if total_shares > 0.0 {
+                (f64::from(side_pot.amount) * share / total_shares).floor() as Usd
+            } else {
+                0
+            };
+            awards.insert(*player_idx, award);
+        }
+        // Chips lost to rounding go to the player in the earliest seat.
+        let remainder = side_pot.amount - awards.values().sum::<Usd>();
+        if let Some((player_idx, award)) = awards.iter_mut().next() {
+            *award += remainder;
+            if remainder > 0 {
+                odd_chips.insert(*player_idx, remainder);
+            }
+        }
+        awards.into_iter().filter(|(_, award)| *award > 0).collect()
+    }
+
+    /// Return how much each winner of a side pot gets, along with a
+    /// breakdown explaining the payout. When running it twice, each board
+    /// is worth half of the pot.
+    fn get_side_pot_awards(
+        &self,
+        pot_idx: usize,
+        side_pot: &SidePot,
+    ) -> (Vec<(usize, Usd)>, PotBreakdown) {
+        let seats = |player_indices: &[usize]| -> Vec<SeatIndex> {
+            player_indices
+                .iter()
+                .map(|player_idx| self.data.players[*player_idx].seat_idx)
+                .collect()
+        };
+        let mut odd_chips = BTreeMap::new();
+        let mut boards = Vec::new();
+        let awards = if self.is_equity_chop_accepted() && side_pot.eligible.len() >= 2 {
+            self.get_equity_chop_awards(side_pot, &mut odd_chips)
+        } else if self.data.run_it_twice {
+            let half = side_pot.amount / 2;
+            let mut awards: BTreeMap<usize, Usd> = BTreeMap::new();
+            for board in [&self.data.board, &self.data.second_board] {
+                let (winners, hand) = self.get_board_winners(&side_pot.eligible, board);
+                for (player_idx, award) in Self::split_pot(&winners, half, &mut odd_chips) {
+                    *awards.entry(player_idx).or_default() += award;
                 }
+                boards.push(BoardResult {
+                    winners: seats(&winners),
+                    hand,
+                });
+            }
+            // The odd chip goes to the winner in the earliest seat.
+            if let Some((player_idx, award)) = awards.iter_mut().next()
+                && !side_pot.amount.is_multiple_of(2)
+            {
+                *award += 1;
+                *odd_chips.entry(*player_idx).or_default() += 1;
             }
-            let winner_indices = functional::argmax(&hands_in_pot);
+            awards.into_iter().collect()
+        } else {
+            let (winners, hand) = self.get_board_winners(&side_pot.eligible, &self.data.board);
+            boards.push(BoardResult {
+                winners: seats(&winners),
+                hand,
+            });
+            Self::split_pot(&winners, side_pot.amount, &mut odd_chips)
+        };
+        let breakdown = PotBreakdown {
+            pot_idx,
+            amount: side_pot.amount,
+            eligible: seats(&side_pot.eligible),
+            boards,
+            odd_chips: odd_chips
+                .into_iter()
+                .map(|(player_idx, chips)| (self.data.players[player_idx].seat_idx, chips))
+                .collect(),
+        };
+        (awards, breakdown)
+    }
 
-            // Split the pot amongst all the winners.
-            // Remainder chips are awarded to winner(s) in earliest position (standard poker rule).
-            let num_winners = winner_indices.len();
-            let pot_split = pot_size / num_winners as Usd;
-            let pot_remainder = pot_size % num_winners as Usd;
+    /// Return whether a player's hand loses to one of the hands that were
+    /// already shown on every board.
+    fn is_beaten_by(&self, player_idx: usize, shown: &[usize]) -> bool {
+        let mut eligible = shown.to_vec();
+        eligible.push(player_idx);
+        let boards = if self.data.run_it_twice {
+            vec![&self.data.board, &self.data.second_board]
+        } else {
+            vec![&self.data.board]
+        };
+        boards.into_iter().all(|board| {
+            let (winners, _) = self.get_board_winners(&eligible, board);
+            !winners.contains(&player_idx)
+        })
+    }
 
-            for (i, winner_idx) in winner_indices.iter().enumerate() {
-                let winner_player_idx = seats_in_pot[*winner_idx];
-                let player = &mut self.data.players[*winner_player_idx];
+    /// Reveal hands in showdown order. The first player in the order
+    /// shows, and each player after them shows if their hand beats or
+    /// ties every hand revealed so far. Beaten hands are mucked unless
+    /// the player already chose to show, though they can still show
+    /// them once the hand is over.
+    fn reveal_in_showdown_order(&mut self) {
+        let order = std::mem::take(&mut self.data.showdown_order);
+        let mut shown = Vec::with_capacity(order.len());
+        for player_idx in order {
+            let beaten = !shown.is_empty() && self.is_beaten_by(player_idx, &shown);
+            let player = &mut self.data.players[player_idx];
+            if player.showing {
+                shown.push(player_idx);
+            } else if !beaten && !player.mucked {
+                player.showing = true;
+                shown.push(player_idx);
+                let event = GameEvent::ShowedHand(player.user.name.clone(), player.cards.clone());
+                self.data.events.push_back(event);
+            } else {
+                let event = GameEvent::MuckedHand(player.user.name.clone());
+                self.data.events.push_back(event);
+            }
+        }
+    }
 
-                // Award base pot split to all winners
-                let mut award = pot_split;
+    /// Reveal the hands that win the highest remaining side pot. Everyone
+    /// else in the pot keeps their hand hidden unless they choose to show,
+    /// and a player that wins a pot uncontested never has to show.
+    fn show_winning_hands(&mut self) {
+        let folded = self.get_folded_player_indices();
+        let mut side_pots = self.data.pot.split_into_side_pots(&folded);
+        let Some(side_pot) = side_pots.pop() else {
+            return;
+        };
+        if side_pot.eligible.len() < 2 {
+            return;
+        }
+        let (awards, _) = self.get_side_pot_awards(side_pots.len(), &side_pot);
+        for (player_idx, _) in awards {
+            let player = &mut self.data.players[player_idx];
+            if !player.showing {
+                player.showing = true;
+                let event = GameEvent::ShowedHand(player.user.name.clone(), player.cards.clone());
+                self.data.events.push_back(event);
+            }
+        }
+    }
 
-                // Award remainder chips to first winner(s) in position
-                if (i as Usd) < pot_remainder {
-                    award += 1;
-                }
+    /// Record the players that made it to the showdown and the hands
+    /// that were shown. Folded hands stay hidden unless they were shown.
+    fn record_showdown(&mut self) {
+        let Some(history) = &mut self.data.hand_history else {
+            return;
+        };
+        let remaining: Vec<_> = self
+            .data
+            .players
+            .iter()
+            .filter(|p| p.state != PlayerState::Fold)
+            .collect();
+        if remaining.len() >= 2 {
+            history.showdown = remaining
+                .iter()
+                .map(|p| ShowdownRecord {
+                    seat: p.seat_idx,
+                    username: p.user.name.clone(),
+                    cards: None,
+                })
+                .collect();
+        }
+        if self.data.run_it_twice {
+            history.second_board = self
+                .data
+                .second_board
+                .iter()
+                .map(Card::to_shorthand)
+                .collect();
+        }
+        for player in self.data.players.iter().filter(|p| p.showing) {
+            history.show_cards(&player.user.name, &player.cards);
+        }
+    }
 
+    /// Get the highest remaining side pot, compare the hands of the
+    /// players that're eligible for it, and distribute it amongst the
+    /// winners. The investments contributing to the side pot are then
+    /// removed so the next call distributes the next lower pot.
+    fn distribute(&mut self) {
+        let folded = self.get_folded_player_indices();
+        let mut side_pots = self.data.pot.split_into_side_pots(&folded);
+        if let Some(mut side_pot) = side_pots.pop() {
+            // The rake comes out of the main pot first, and only spills
+            // over into the side pots if the main pot can't cover it.
+            let lower_pots: Usd = side_pots.iter().map(|side_pot| side_pot.amount).sum();
+            side_pot.amount -= self
+                .data
+                .rake
+                .saturating_sub(lower_pots)
+                .min(side_pot.amount);
+            let (awards, breakdown) = self.get_side_pot_awards(side_pots.len(), &side_pot);
+            self.data
+                .events
+                .push_back(GameEvent::PotBreakdown(breakdown));
+            for (player_idx, award) in awards {
+                let player = &mut self.data.players[player_idx];
                 player.user.money += award;
+                if let Some(history) = &mut self.data.hand_history {
+                    history.pot_awards.push(PotAward {
+                        seat: player.seat_idx,
+                        username: player.user.name.clone(),
+                        amount: award,
+                    });
+                }
                 self.data
                     .events
                     .push_back(GameEvent::SplitPot(player.user.name.clone(), award));
             }
+
+            // Everything above the next lower pot's cap belonged to the
+            // pot that was just distributed.
+            let prev_cap = side_pots.last().map_or(0, |side_pot| side_pot.cap);
+            for investment in self.data.pot.investments.values_mut() {
+                *investment = min(*investment, prev_cap);
+            }
         }
 
         // Remove null investments.
@@ -1507,6 +2467,9 @@ impl From<Game<DistributePot>> for Game<RemovePlayers> {
     fn from(mut value: Game<DistributePot>) -> Self {
         value.distribute();
         value.data.player_counts.num_active = 0;
+        let num_undealt = 5usize.saturating_sub(value.data.board.len());
+        value.data.rabbit_hunt_cards = value.data.deck.peek_cards(num_undealt);
+        value.record_showdown();
         Self {
             data: value.data,
             state: RemovePlayers {},
@@ -1540,8 +2503,17 @@ impl From<Game<RemovePlayers>> for Game<UpdateBlinds> {
 /// each player must make in each hand, preventing games where a handful of
 /// players have large stacks and can afford to fold many times without
 /// any other action.
+///
+/// Minimum blinds queued by a blind schedule take effect here, so they
+/// only ever change between hands.
 impl From<Game<UpdateBlinds>> for Game<BootPlayers> {
     fn from(mut value: Game<UpdateBlinds>) -> Self {
+        let next_blinds = value.data.next_blinds.take();
+        if let Some(blinds) = &next_blinds {
+            value.data.settings.min_small_blind = blinds.small;
+            value.data.settings.min_big_blind = blinds.big;
+            value.data.settings.min_ante = blinds.ante;
+        }
         if value.data.reset_all_money_after_game {
             value.data.player_queues.to_reset.clear();
             value.reset_all_money();
@@ -1560,8 +2532,15 @@ impl From<Game<UpdateBlinds>> for Game<BootPlayers> {
             .min()
             .unwrap_or(value.data.settings.min_big_blind);
         let multiple = max(1, min_playable_money / value.data.settings.buy_in);
+        let old_blinds = value.data.blinds.clone();
         value.data.blinds.small = multiple * value.data.settings.min_small_blind;
         value.data.blinds.big = multiple * value.data.settings.min_big_blind;
+        value.data.blinds.ante = multiple * value.data.settings.min_ante;
+        // Queuing the blinds already in play doesn't announce them again
+        if next_blinds.is_some() && value.data.blinds != old_blinds {
+            let event = GameEvent::BlindsIncreased(value.data.blinds.clone());
+            value.data.events.push_back(event);
+        }
         Self {
             data: value.data,
             state: BootPlayers {},
@@ -1575,6 +2554,11 @@ impl From<Game<UpdateBlinds>> for Game<BootPlayers> {
 impl From<Game<BootPlayers>> for Game<Lobby> {
     fn from(mut value: Game<BootPlayers>) -> Self {
         value.data.board.clear();
+        value.data.second_board.clear();
+        value.data.run_it_twice = false;
+        value.data.equity_chop.clear();
+        value.data.equity_chop_accepted.clear();
+        value.data.completed_hand_history = value.data.hand_history.take();
         for player in &mut value.data.players {
             if player.user.money < value.data.blinds.big {
                 value.data.open_seats.push_back(player.seat_idx);
@@ -1610,7 +2594,7 @@ impl From<Game<BootPlayers>> for Game<Lobby> {
 
 /// A poker finite state machine. Wrapper around all possible game states,
 /// managing the transition from one state to the next.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 #[enum_dispatch(
     GameStateManagement,
     PhaseDependentUserManagement,
@@ -1655,9 +2639,15 @@ impl fmt::Display for PokerState {
                     [game.data.play_positions.small_blind_idx]
                     .user
                     .name;
-                &format!(
-                    "collecting ${small_blind} from {small_blind_username} and ${big_blind} from {big_blind_username}"
-                )
+                if game.data.play_positions.dead_small_blind {
+                    &format!(
+                        "collecting ${big_blind} from {big_blind_username} with a dead small blind"
+                    )
+                } else {
+                    &format!(
+                        "collecting ${small_blind} from {small_blind_username} and ${big_blind} from {big_blind_username}"
+                    )
+                }
             }
             Self::Deal(_) => "dealing cards",
             Self::TakeAction(game) => {
@@ -1701,6 +2691,14 @@ impl PokerState {
         }
     }
 
+    #[must_use]
+    pub fn get_turn_info(&self) -> Option<TurnInfo> {
+        match self {
+            Self::TakeAction(game) => game.get_turn_info(),
+            _ => None,
+        }
+    }
+
     #[must_use]
     pub fn get_next_action_username(&self) -> Option<Username> {
         match self {
@@ -1709,6 +2707,19 @@ impl PokerState {
         }
     }
 
+    /// Return whether the players left in the hand are all-in and the rest
+    /// of the board is being dealt, a street per step.
+    #[must_use]
+    pub fn is_running_out_board(&self) -> bool {
+        match self {
+            Self::TakeAction(game) => game.is_running_out_board(),
+            Self::Flop(game) => game.is_running_out_board(),
+            Self::Turn(game) => game.is_running_out_board(),
+            Self::River(game) => game.is_running_out_board(),
+            _ => false,
+        }
+    }
+
     /// Get the amount a player needs to call to stay in the hand
     #[must_use]
     pub fn get_call_amount_for_player(&self, username: &Username) -> Option<Usd> {
@@ -1748,6 +2759,69 @@ impl PokerState {
         }
     }
 
+    /// Check if a username is a spectator (not seated or waitlisted)
+    #[must_use]
+    pub fn contains_spectator(&self, username: &Username) -> bool {
+        match self {
+            Self::Lobby(game) => game.contains_spectator(username),
+            Self::SeatPlayers(game) => game.contains_spectator(username),
+            Self::MoveButton(game) => game.contains_spectator(username),
+            Self::CollectBlinds(game) => game.contains_spectator(username),
+            Self::Deal(game) => game.contains_spectator(username),
+            Self::TakeAction(game) => game.contains_spectator(username),
+            Self::Flop(game) => game.contains_spectator(username),
+            Self::Turn(game) => game.contains_spectator(username),
+            Self::River(game) => game.contains_spectator(username),
+            Self::ShowHands(game) => game.contains_spectator(username),
+            Self::DistributePot(game) => game.contains_spectator(username),
+            Self::RemovePlayers(game) => game.contains_spectator(username),
+            Self::UpdateBlinds(game) => game.contains_spectator(username),
+            Self::BootPlayers(game) => game.contains_spectator(username),
+        }
+    }
+
+    /// Get the number of players that could play if a hand started now
+    #[must_use]
+    pub fn get_num_potential_players(&self) -> usize {
+        match self {
+            Self::Lobby(game) => game.get_num_potential_players(),
+            Self::SeatPlayers(game) => game.get_num_potential_players(),
+            Self::MoveButton(game) => game.get_num_potential_players(),
+            Self::CollectBlinds(game) => game.get_num_potential_players(),
+            Self::Deal(game) => game.get_num_potential_players(),
+            Self::TakeAction(game) => game.get_num_potential_players(),
+            Self::Flop(game) => game.get_num_potential_players(),
+            Self::Turn(game) => game.get_num_potential_players(),
+            Self::River(game) => game.get_num_potential_players(),
+            Self::ShowHands(game) => game.get_num_potential_players(),
+            Self::DistributePot(game) => game.get_num_potential_players(),
+            Self::RemovePlayers(game) => game.get_num_potential_players(),
+            Self::UpdateBlinds(game) => game.get_num_potential_players(),
+            Self::BootPlayers(game) => game.get_num_potential_players(),
+        }
+    }
+
+    /// Get the number of spectators watching the game
+    #[must_use]
+    pub fn get_num_spectators(&self) -> usize {
+        match self {
+            Self::Lobby(game) => game.get_num_spectators(),
+            Self::SeatPlayers(game) => game.get_num_spectators(),
+            Self::MoveButton(game) => game.get_num_spectators(),
+            Self::CollectBlinds(game) => game.get_num_spectators(),
+            Self::Deal(game) => game.get_num_spectators(),
+            Self::TakeAction(game) => game.get_num_spectators(),
+            Self::Flop(game) => game.get_num_spectators(),
+            Self::Turn(game) => game.get_num_spectators(),
+            Self::River(game) => game.get_num_spectators(),
+            Self::ShowHands(game) => game.get_num_spectators(),
+            Self::DistributePot(game) => game.get_num_spectators(),
+            Self::RemovePlayers(game) => game.get_num_spectators(),
+            Self::UpdateBlinds(game) => game.get_num_spectators(),
+            Self::BootPlayers(game) => game.get_num_spectators(),
+        }
+    }
+
     pub fn init_start(&mut self, username: &Username) -> Result<(), UserError> {
         match self {
             Self::Lobby(game) => {
@@ -1763,6 +2837,23 @@ impl PokerState {
         }
     }
 
+    /// Start the game without a user asking to, e.g., when a table
+    /// starts automatically once enough players are seated.
+    pub fn auto_start(&mut self) -> Result<(), UserError> {
+        match self {
+            Self::Lobby(game) => game.init_start(),
+            Self::SeatPlayers(_) => Err(UserError::GameAlreadyStarting),
+            _ => Err(UserError::GameAlreadyInProgress),
+        }
+    }
+
+    /// Return whether the game is in the lobby and will start on the next
+    /// step.
+    #[must_use]
+    pub fn is_starting(&self) -> bool {
+        matches!(self, Self::Lobby(game) if game.is_ready_to_start())
+    }
+
     #[must_use]
     pub fn new() -> Self {
         let game = Game::<Lobby>::new();
@@ -1770,6 +2861,11 @@ impl PokerState {
     }
 
     fn phase_transition(game: Game<TakeAction>) -> Self {
+        if game.is_equity_chop_accepted() {
+            // The pot is settled by equity without dealing the rest of
+            // the board.
+            return Self::ShowHands(game.into());
+        }
         match game.get_num_community_cards() {
             0 => Self::Flop(game.into()),
             3 => Self::Turn(game.into()),
@@ -1800,6 +2896,290 @@ impl PokerState {
         Ok(())
     }
 
+    pub fn muck_hand(&mut self, username: &Username) -> Result<(), UserError> {
+        match self {
+            Self::ShowHands(game) => {
+                game.muck_hand(username)?;
+            }
+            Self::DistributePot(game) => {
+                game.muck_hand(username)?;
+            }
+            Self::RemovePlayers(game) => {
+                game.muck_hand(username)?;
+            }
+            Self::UpdateBlinds(game) => {
+                game.muck_hand(username)?;
+            }
+            _ => return Err(UserError::CannotMuckHand),
+        }
+        Ok(())
+    }
+
+    /// Queue a user to straddle the next time they're under the gun.
+    pub fn straddle(&mut self, username: &Username) -> Result<(), UserError> {
+        fn queue_straddle<T>(game: &mut Game<T>, username: &Username) -> Result<(), UserError> {
+            if !game.data.settings.straddle_allowed {
+                return Err(UserError::StraddleNotAllowed);
+            }
+            if !game.contains_player(username) && !game.contains_waitlister(username) {
+                return Err(UserError::UserNotPlaying);
+            }
+            game.data.player_queues.to_straddle.insert(username.clone());
+            Ok(())
+        }
+
+        match self {
+            Self::Lobby(game) => queue_straddle(game, username),
+            Self::SeatPlayers(game) => queue_straddle(game, username),
+            Self::MoveButton(game) => queue_straddle(game, username),
+            Self::CollectBlinds(game) => queue_straddle(game, username),
+            Self::Deal(game) => queue_straddle(game, username),
+            Self::TakeAction(game) => queue_straddle(game, username),
+            Self::Flop(game) => queue_straddle(game, username),
+            Self::Turn(game) => queue_straddle(game, username),
+            Self::River(game) => queue_straddle(game, username),
+            Self::ShowHands(game) => queue_straddle(game, username),
+            Self::DistributePot(game) => queue_straddle(game, username),
+            Self::RemovePlayers(game) => queue_straddle(game, username),
+            Self::UpdateBlinds(game) => queue_straddle(game, username),
+            Self::BootPlayers(game) => queue_straddle(game, username),
+        }
+    }
+
+    /// Queue a user to move to an open seat at the start of the next game.
+    pub fn request_seat(
+        &mut self,
+        username: &Username,
+        seat_idx: SeatIndex,
+    ) -> Result<(), UserError> {
+        match self {
+            Self::Lobby(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::SeatPlayers(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::MoveButton(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::CollectBlinds(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::Deal(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::TakeAction(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::Flop(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::Turn(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::River(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::ShowHands(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::DistributePot(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::RemovePlayers(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::UpdateBlinds(game) => game.queue_seat_change_with_event(username, seat_idx),
+            Self::BootPlayers(game) => game.queue_seat_change_with_event(username, seat_idx),
+        }
+    }
+
+    /// Offer the two players left in an all-in pot to chop it by their
+    /// current equity, returning each player's share of the pot.
+    pub fn offer_equity_chop(&mut self) -> Result<Vec<(Username, f64)>, UserError> {
+        match self {
+            Self::TakeAction(game) => game.offer_equity_chop(),
+            _ => Err(UserError::CannotOfferEquityChop),
+        }
+    }
+
+    /// Accept or decline the equity chop that's on offer. The pot is
+    /// chopped once both players accept, and the rest of the board is
+    /// dealt as usual if either declines.
+    pub fn respond_to_equity_chop(
+        &mut self,
+        username: &Username,
+        accept: bool,
+    ) -> Result<(), UserError> {
+        match self {
+            Self::TakeAction(game) => game.respond_to_equity_chop(username, accept),
+            _ => Err(UserError::NoEquityChopOffer),
+        }
+    }
+
+    /// Return whether an equity chop is waiting on players to answer it.
+    #[must_use]
+    pub fn is_equity_chop_pending(&self) -> bool {
+        match self {
+            Self::TakeAction(game) => game.is_equity_chop_pending(),
+            _ => false,
+        }
+    }
+
+    /// Withdraw the equity chop that's on offer, e.g., when players take
+    /// too long to answer it, so the rest of the board is dealt as usual.
+    pub fn withdraw_equity_chop(&mut self) {
+        if let Self::TakeAction(game) = self {
+            game.data.equity_chop.clear();
+            game.data.equity_chop_accepted.clear();
+        }
+    }
+
+    /// Set whether a user agrees to run it twice whenever they're all-in.
+    /// The board is only run twice if every player left in the pot agrees.
+    pub fn set_run_it_twice(
+        &mut self,
+        username: &Username,
+        consent: bool,
+    ) -> Result<(), UserError> {
+        fn set_consent<T>(
+            game: &mut Game<T>,
+            username: &Username,
+            consent: bool,
+        ) -> Result<(), UserError> {
+            if !game.data.settings.run_it_twice_allowed {
+                return Err(UserError::RunItTwiceNotAllowed);
+            }
+            if !game.contains_player(username) && !game.contains_waitlister(username) {
+                return Err(UserError::UserNotPlaying);
+            }
+            if consent {
+                game.data.run_it_twice_consent.insert(username.clone());
+            } else {
+                game.data.run_it_twice_consent.remove(username);
+            }
+            Ok(())
+        }
+
+        match self {
+            Self::Lobby(game) => set_consent(game, username, consent),
+            Self::SeatPlayers(game) => set_consent(game, username, consent),
+            Self::MoveButton(game) => set_consent(game, username, consent),
+            Self::CollectBlinds(game) => set_consent(game, username, consent),
+            Self::Deal(game) => set_consent(game, username, consent),
+            Self::TakeAction(game) => set_consent(game, username, consent),
+            Self::Flop(game) => set_consent(game, username, consent),
+            Self::Turn(game) => set_consent(game, username, consent),
+            Self::River(game) => set_consent(game, username, consent),
+            Self::ShowHands(game) => set_consent(game, username, consent),
+            Self::DistributePot(game) => set_consent(game, username, consent),
+            Self::RemovePlayers(game) => set_consent(game, username, consent),
+            Self::UpdateBlinds(game) => set_consent(game, username, consent),
+            Self::BootPlayers(game) => set_consent(game, username, consent),
+        }
+    }
+
+    /// Set whether a player is sitting out. Players that are sitting out
+    /// keep their seat and money, but are dealt out of games starting
+    /// with the next one, and are kicked if they sit out for too long.
+    pub fn set_sitting_out(
+        &mut self,
+        username: &Username,
+        sitting_out: bool,
+    ) -> Result<(), UserError> {
+        fn set_sitting_out<T>(
+            game: &mut Game<T>,
+            username: &Username,
+            sitting_out: bool,
+        ) -> Result<(), UserError> {
+            if !game.contains_player(username) {
+                return Err(UserError::UserNotPlaying);
+            }
+            if sitting_out {
+                if game.data.sitting_out.contains_key(username) {
+                    return Err(UserError::UserAlreadySittingOut);
+                }
+                game.data.sitting_out.insert(username.clone(), 0);
+                game.data
+                    .events
+                    .push_back(GameEvent::SatOut(username.clone()));
+            } else {
+                if game.data.sitting_out.remove(username).is_none() {
+                    return Err(UserError::UserNotSittingOut);
+                }
+                game.data
+                    .events
+                    .push_back(GameEvent::SatIn(username.clone()));
+            }
+            Ok(())
+        }
+
+        match self {
+            Self::Lobby(game) => set_sitting_out(game, username, sitting_out),
+            Self::SeatPlayers(game) => set_sitting_out(game, username, sitting_out),
+            Self::MoveButton(game) => set_sitting_out(game, username, sitting_out),
+            Self::CollectBlinds(game) => set_sitting_out(game, username, sitting_out),
+            Self::Deal(game) => set_sitting_out(game, username, sitting_out),
+            Self::TakeAction(game) => set_sitting_out(game, username, sitting_out),
+            Self::Flop(game) => set_sitting_out(game, username, sitting_out),
+            Self::Turn(game) => set_sitting_out(game, username, sitting_out),
+            Self::River(game) => set_sitting_out(game, username, sitting_out),
+            Self::ShowHands(game) => set_sitting_out(game, username, sitting_out),
+            Self::DistributePot(game) => set_sitting_out(game, username, sitting_out),
+            Self::RemovePlayers(game) => set_sitting_out(game, username, sitting_out),
+            Self::UpdateBlinds(game) => set_sitting_out(game, username, sitting_out),
+            Self::BootPlayers(game) => set_sitting_out(game, username, sitting_out),
+        }
+    }
+
+    /// Return the community cards that would've completed the board if
+    /// the last hand hadn't ended early. Only available between the pot
+    /// being distributed and the next hand being dealt. The cards are
+    /// only peeked at, so the deck is left as is.
+    pub fn rabbit_hunt(&self) -> Result<Vec<Card>, UserError> {
+        let cards = match self {
+            Self::Lobby(game) => &game.data.rabbit_hunt_cards,
+            Self::SeatPlayers(game) => &game.data.rabbit_hunt_cards,
+            Self::MoveButton(game) => &game.data.rabbit_hunt_cards,
+            Self::CollectBlinds(game) => &game.data.rabbit_hunt_cards,
+            Self::RemovePlayers(game) => &game.data.rabbit_hunt_cards,
+            Self::UpdateBlinds(game) => &game.data.rabbit_hunt_cards,
+            Self::BootPlayers(game) => &game.data.rabbit_hunt_cards,
+            _ => return Err(UserError::CannotRabbitHunt),
+        };
+        if cards.is_empty() {
+            return Err(UserError::CannotRabbitHunt);
+        }
+        Ok(cards.clone())
+    }
+
+    /// Take the history of the last hand that was played. Only available
+    /// once the game is back in the lobby, and only returned once.
+    pub fn take_hand_history(&mut self) -> Option<HandHistory> {
+        match self {
+            Self::Lobby(game) => game.data.completed_hand_history.take(),
+            _ => None,
+        }
+    }
+
+    /// Money each user would leave the table with if the hand in
+    /// progress were called off. Seated players get back what they've
+    /// put into the pot this hand on top of their stack, and waitlisted
+    /// users keep their stack. Helps settle a table that closes mid-hand.
+    #[must_use]
+    pub fn get_settled_stacks(&self) -> HashMap<Username, Usd> {
+        fn settled_stacks<T>(game: &Game<T>) -> HashMap<Username, Usd> {
+            let mut stacks = HashMap::new();
+            for (player_idx, player) in game.data.players.iter().enumerate() {
+                let investment = game
+                    .data
+                    .pot
+                    .investments
+                    .get(&player_idx)
+                    .copied()
+                    .unwrap_or(0);
+                stacks.insert(player.user.name.clone(), player.user.money + investment);
+            }
+            for user in &game.data.waitlist {
+                stacks.entry(user.name.clone()).or_insert(user.money);
+            }
+            stacks
+        }
+
+        match self {
+            Self::Lobby(game) => settled_stacks(game),
+            Self::SeatPlayers(game) => settled_stacks(game),
+            Self::MoveButton(game) => settled_stacks(game),
+            Self::CollectBlinds(game) => settled_stacks(game),
+            Self::Deal(game) => settled_stacks(game),
+            Self::TakeAction(game) => settled_stacks(game),
+            Self::Flop(game) => settled_stacks(game),
+            Self::Turn(game) => settled_stacks(game),
+            Self::River(game) => settled_stacks(game),
+            Self::ShowHands(game) => settled_stacks(game),
+            Self::DistributePot(game) => settled_stacks(game),
+            Self::RemovePlayers(game) => settled_stacks(game),
+            Self::UpdateBlinds(game) => settled_stacks(game),
+            Self::BootPlayers(game) => settled_stacks(game),
+        }
+    }
+
     /// Main state transitions.
     #[must_use]
     pub fn step(self) -> Self {
@@ -1822,7 +3202,9 @@ impl PokerState {
             Self::CollectBlinds(game) => Self::Deal(game.into()),
             Self::Deal(game) => Self::TakeAction(game.into()),
             Self::TakeAction(mut game) => {
-                if game.is_ready_for_next_phase() {
+                if game.is_equity_chop_pending() {
+                    Self::TakeAction(game)
+                } else if game.is_ready_for_next_phase() {
                     Self::phase_transition(game)
                 } else {
                     game.act(Action::Fold).expect("force folding should be OK");
@@ -1882,12 +3264,36 @@ impl PokerState {
         }
     }
 
-    /// Add chips to a player's stack
-    ///
-    /// # Arguments
-    ///
-    /// * `username` - Username of the player
-    /// * `amount` - Amount of chips to add
+    /// Act on behalf of a player that ran out of time, checking if
+    /// possible and folding otherwise.
+    pub fn take_timeout_action(&mut self, username: &Username) -> Result<Action, UserError> {
+        match self {
+            Self::TakeAction(game) if !game.is_ready_for_next_phase() && game.is_turn(username) => {
+                let can_check = game
+                    .get_action_choices()
+                    .is_some_and(|choices| choices.contains(&Action::Check));
+                let action = if can_check {
+                    Action::Check
+                } else {
+                    Action::Fold
+                };
+                let sanitized_action = game.act(action)?;
+                game.data.events.push_back(GameEvent::TimedOut(
+                    username.clone(),
+                    sanitized_action.clone(),
+                ));
+                Ok(sanitized_action)
+            }
+            _ => Err(UserError::OutOfTurnAction),
+        }
+    }
+
+    /// Add chips to a player's stack
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - Username of the player
+    /// * `amount` - Amount of chips to add
     ///
     /// # Returns
     ///
@@ -1935,6 +3341,66 @@ impl PokerState {
             Self::BootPlayers(game) => add_chips(game, username, amount),
         }
     }
+
+    /// Queue new minimum blinds that take effect once the hand in
+    /// progress is over, or after the next hand if none is in progress.
+    /// Queuing again before then replaces the blinds that were queued.
+    ///
+    /// # Arguments
+    ///
+    /// * `blinds` - New minimum small blind, big blind, and ante
+    pub fn queue_blinds(&mut self, blinds: Blinds) {
+        match self {
+            Self::Lobby(game) => game.data.next_blinds = Some(blinds),
+            Self::SeatPlayers(game) => game.data.next_blinds = Some(blinds),
+            Self::MoveButton(game) => game.data.next_blinds = Some(blinds),
+            Self::CollectBlinds(game) => game.data.next_blinds = Some(blinds),
+            Self::Deal(game) => game.data.next_blinds = Some(blinds),
+            Self::TakeAction(game) => game.data.next_blinds = Some(blinds),
+            Self::Flop(game) => game.data.next_blinds = Some(blinds),
+            Self::Turn(game) => game.data.next_blinds = Some(blinds),
+            Self::River(game) => game.data.next_blinds = Some(blinds),
+            Self::ShowHands(game) => game.data.next_blinds = Some(blinds),
+            Self::DistributePot(game) => game.data.next_blinds = Some(blinds),
+            Self::RemovePlayers(game) => game.data.next_blinds = Some(blinds),
+            Self::UpdateBlinds(game) => game.data.next_blinds = Some(blinds),
+            Self::BootPlayers(game) => game.data.next_blinds = Some(blinds),
+        }
+    }
+
+    /// Waitlisted users that will take the open seats once players are
+    /// seated, in the order they'll be seated. Only known while seating
+    /// players.
+    #[must_use]
+    pub fn waitlisters_to_seat(&self) -> Vec<Username> {
+        match self {
+            Self::SeatPlayers(game) => game.waitlisters_to_seat(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Set the stack a waitlisted user brings to the table once they're
+    /// seated.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - Username of the waitlisted user
+    /// * `money` - The user's stack
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Stack set successfully
+    /// * `Err(UserError)` - Not seating players, or the user isn't waitlisted
+    pub fn set_waitlister_money(
+        &mut self,
+        username: &Username,
+        money: Usd,
+    ) -> Result<(), UserError> {
+        match self {
+            Self::SeatPlayers(game) => game.set_waitlister_money(username, money),
+            _ => Err(UserError::GameAlreadyInProgress),
+        }
+    }
 }
 
 impl From<GameSettings> for PokerState {
@@ -1946,11 +3412,20 @@ impl From<GameSettings> for PokerState {
 
 #[cfg(test)]
 mod game_tests {
-    use super::super::entities::{Action, ActionChoice, Card, PlayerState, Suit, Username};
+    use std::collections::{BTreeMap, HashSet};
+
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::super::entities::{
+        Action, ActionChoice, BoardResult, Card, GameVariant, GameView, PlayerState, PotBreakdown,
+        Suit, Usd, Username,
+    };
+    use super::super::functional;
     use super::{
-        BootPlayers, CollectBlinds, Deal, DistributePot, Flop, Game, Lobby, MoveButton,
-        PhaseDependentUserManagement, PhaseIndependentUserManagement, RemovePlayers, River,
-        SeatPlayers, ShowHands, TakeAction, Turn, UpdateBlinds, UserError,
+        BootPlayers, CollectBlinds, Deal, DistributePot, Flop, Game, GameEvent, GameSettings,
+        GameStateManagement, Lobby, MoveButton, PhaseDependentUserManagement,
+        PhaseIndependentUserManagement, RemovePlayers, River, SeatPlayers, ShowHands, TakeAction,
+        Turn, UpdateBlinds, UserError,
     };
 
     fn init_2_player_game() -> Game<SeatPlayers> {
@@ -2069,6 +3544,76 @@ mod game_tests {
         }
     }
 
+    #[test]
+    fn omaha_deals_four_hole_cards() {
+        let settings = GameSettings {
+            variant: GameVariant::Omaha,
+            ..Default::default()
+        };
+        let game: Game<Lobby> = settings.into();
+        let mut game: Game<SeatPlayers> = game.into();
+        for i in 0..3 {
+            let username = i.to_string().into();
+            game.new_user(&username).unwrap();
+            game.waitlist_user(&username).unwrap();
+        }
+        let game: Game<MoveButton> = game.into();
+        let game: Game<CollectBlinds> = game.into();
+        let game: Game<Deal> = game.into();
+        let game: Game<TakeAction> = game.into();
+        assert_eq!(game.data.deck.deck_idx, 4 * game.get_num_users());
+        for player in &game.data.players {
+            assert_eq!(player.cards.len(), 4);
+        }
+    }
+
+    #[test]
+    fn hole_cards_follow_variant_into_views() {
+        for (variant, num_hole_cards) in [
+            (GameVariant::TexasHoldem, 2),
+            (GameVariant::ShortDeck, 2),
+            (GameVariant::Omaha, 4),
+        ] {
+            let settings = GameSettings {
+                variant,
+                ..Default::default()
+            };
+            let game: Game<Lobby> = settings.into();
+            let mut game: Game<SeatPlayers> = game.into();
+            for i in 0..3 {
+                let username = i.to_string().into();
+                game.new_user(&username).unwrap();
+                game.waitlist_user(&username).unwrap();
+            }
+            let game: Game<MoveButton> = game.into();
+            let game: Game<CollectBlinds> = game.into();
+            let game: Game<Deal> = game.into();
+            let game: Game<TakeAction> = game.into();
+
+            let username: Username = "0".to_string().into();
+            let view = game.get_views().remove(&username).unwrap();
+            assert_eq!(view.variant, variant);
+            for player in &view.players {
+                let expected = if player.user.name == username {
+                    num_hole_cards
+                } else {
+                    0
+                };
+                assert_eq!(player.cards.len(), expected, "{variant:?}");
+            }
+
+            // Players keep every card they were dealt across the wire
+            let json = serde_json::to_string(&view).unwrap();
+            let view: GameView = serde_json::from_str(&json).unwrap();
+            let own = view
+                .players
+                .iter()
+                .find(|p| p.user.name == username)
+                .unwrap();
+            assert_eq!(own.cards.len(), num_hole_cards, "{variant:?}");
+        }
+    }
+
     #[test]
     fn early_showdown() {
         let mut game = init_game_at_deal();
@@ -2194,6 +3739,154 @@ mod game_tests {
         }
     }
 
+    #[test]
+    fn rake_is_capped() {
+        let mut game = init_game_at_deal();
+        game.data.settings.rake_bps = 500;
+        game.data.settings.rake_cap = 25;
+        game.act(Action::Fold).unwrap();
+        game.act(Action::AllIn).unwrap();
+        game.act(Action::AllIn).unwrap();
+        let game: Game<Flop> = game.into();
+        let game: Game<Turn> = game.into();
+        let game: Game<River> = game.into();
+        let mut game: Game<ShowHands> = game.into();
+        // 5% of the pot would be 60 chips, but the cap kicks in.
+        assert_eq!(game.data.rake, 25);
+        game.data.board = vec![
+            Card(4, Suit::Diamond),
+            Card(5, Suit::Diamond),
+            Card(6, Suit::Diamond),
+            Card(7, Suit::Diamond),
+            Card(13, Suit::Club),
+        ];
+        game.data.players[1].cards = vec![Card(1, Suit::Diamond), Card(7, Suit::Heart)];
+        game.data.players[2].cards = vec![Card(2, Suit::Diamond), Card(5, Suit::Heart)];
+        let game: Game<DistributePot> = game.into();
+        let game: Game<ShowHands> = game.into();
+        assert!(game.is_pot_empty());
+        for (i, money) in [
+            game.data.settings.buy_in,
+            2 * game.data.settings.buy_in - 25,
+            0,
+        ]
+        .iter()
+        .enumerate()
+        {
+            assert_eq!(game.data.players[i].user.money, *money);
+        }
+    }
+
+    #[test]
+    fn rake_rounds_down() {
+        let mut game = init_game_at_deal();
+        game.data.settings.rake_bps = 7;
+        game.act(Action::Fold).unwrap();
+        game.act(Action::AllIn).unwrap();
+        game.act(Action::AllIn).unwrap();
+        let game: Game<Flop> = game.into();
+        let game: Game<Turn> = game.into();
+        let game: Game<River> = game.into();
+        let game: Game<ShowHands> = game.into();
+        // 0.07% of 1200 chips is 0.84 chips.
+        assert_eq!(game.data.rake, 0);
+    }
+
+    #[test]
+    fn no_rake_without_flop() {
+        let mut game = init_game_at_deal();
+        game.data.settings.rake_bps = 500;
+        game.act(Action::Fold).unwrap();
+        game.act(Action::Fold).unwrap();
+        let game: Game<Flop> = game.into();
+        let game: Game<Turn> = game.into();
+        let game: Game<River> = game.into();
+        let game: Game<ShowHands> = game.into();
+        assert!(game.data.board.is_empty());
+        assert_eq!(game.data.rake, 0);
+        let game: Game<DistributePot> = game.into();
+        let game: Game<ShowHands> = game.into();
+        assert!(game.is_pot_empty());
+        assert_eq!(
+            game.data.players[2].user.money,
+            game.data.settings.buy_in + game.data.settings.min_small_blind
+        );
+    }
+
+    #[test]
+    fn showdown_loser_mucks() {
+        let mut game = init_game_at_showdown_with_2_all_ins();
+        game.data.board = vec![
+            Card(4, Suit::Diamond),
+            Card(5, Suit::Diamond),
+            Card(6, Suit::Diamond),
+            Card(7, Suit::Diamond),
+            Card(13, Suit::Club),
+        ];
+        game.data.players[1].cards = vec![Card(1, Suit::Diamond), Card(7, Suit::Heart)];
+        game.data.players[2].cards = vec![Card(2, Suit::Diamond), Card(5, Suit::Heart)];
+        let username1 = game.data.players[1].user.name.clone();
+        let username2 = game.data.players[2].user.name.clone();
+        assert_eq!(game.muck_hand(&username2), Ok(()));
+        assert_eq!(
+            game.muck_hand(&username2),
+            Err(UserError::UserAlreadyMuckedHand)
+        );
+        assert_eq!(
+            game.show_hand(&username2),
+            Err(UserError::UserAlreadyMuckedHand)
+        );
+
+        // Only the winner is revealed to everyone else.
+        let mut game: Game<DistributePot> = game.into();
+        let views = game.get_views();
+        let view = &views[&game.data.players[0].user.name];
+        assert_eq!(*view.players[1].cards, game.data.players[1].cards);
+        assert!(view.players[2].cards.is_empty());
+        assert_eq!(
+            *views[&username2].players[2].cards,
+            game.data.players[2].cards
+        );
+        assert_eq!(
+            game.show_hand(&username1),
+            Err(UserError::UserAlreadyShowingHand)
+        );
+    }
+
+    #[test]
+    fn showdown_only_reveals_winner() {
+        let mut game = init_game_at_showdown_with_2_all_ins();
+        game.data.board = vec![
+            Card(4, Suit::Diamond),
+            Card(5, Suit::Diamond),
+            Card(6, Suit::Diamond),
+            Card(7, Suit::Diamond),
+            Card(13, Suit::Club),
+        ];
+        game.data.players[1].cards = vec![Card(1, Suit::Diamond), Card(7, Suit::Heart)];
+        game.data.players[2].cards = vec![Card(2, Suit::Diamond), Card(5, Suit::Heart)];
+        let mut game: Game<DistributePot> = game.into();
+        assert!(game.data.players[1].showing);
+        assert!(!game.data.players[2].showing);
+
+        // The loser can still choose to show.
+        let username2 = game.data.players[2].user.name.clone();
+        assert_eq!(game.show_hand(&username2), Ok(()));
+        assert_eq!(
+            game.muck_hand(&username2),
+            Err(UserError::UserAlreadyShowingHand)
+        );
+    }
+
+    #[test]
+    fn uncontested_winner_doesnt_show() {
+        let game = init_game_at_showdown_with_1_all_in();
+        let mut game: Game<DistributePot> = game.into();
+        assert!(game.data.players.iter().all(|p| !p.showing));
+        let username1 = game.data.players[1].user.name.clone();
+        assert_eq!(game.muck_hand(&username1), Err(UserError::CannotMuckHand));
+    }
+
     #[test]
     fn early_showdown_3_decreasing_all_ins() {
         let game = init_3_player_game();
@@ -2242,33 +3935,18 @@ mod game_tests {
     }
 
     #[test]
-    fn early_showdown_3_increasing_all_ins() {
+    fn early_showdown_3_decreasing_all_ins_short_stack_wins() {
         let game = init_3_player_game();
         let mut game: Game<MoveButton> = game.into();
         for i in 0..3 {
-            game.data.players[i].user.money = game.data.settings.buy_in * (i as u32 + 1);
+            game.data.players[i].user.money = game.data.settings.buy_in * (3 - i as u32);
         }
         let game: Game<CollectBlinds> = game.into();
         let game: Game<Deal> = game.into();
         let mut game: Game<TakeAction> = game.into();
-        assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
-        assert_eq!(
-            game.get_next_action_choices(),
-            Some(
-                [
-                    ActionChoice::AllIn,
-                    ActionChoice::Call(195),
-                    ActionChoice::Fold,
-                ]
-                .into()
-            )
-        );
-        assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
-        assert_eq!(
-            game.get_next_action_choices(),
-            Some([ActionChoice::Call(390), ActionChoice::Fold,].into())
-        );
-        assert_eq!(game.act(Action::Call), Ok(Action::Call));
+        for _ in 0..3 {
+            assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
+        }
         let game: Game<Flop> = game.into();
         let game: Game<Turn> = game.into();
         let game: Game<River> = game.into();
@@ -2280,20 +3958,23 @@ mod game_tests {
             Card(6, Suit::Diamond),
             Card(7, Suit::Diamond),
         ];
-        game.data.players[0].cards = vec![Card(3, Suit::Heart), Card(1, Suit::Diamond)];
+        game.data.players[0].cards = vec![Card(2, Suit::Heart), Card(9, Suit::Diamond)];
         game.data.players[1].cards = vec![Card(1, Suit::Heart), Card(10, Suit::Diamond)];
-        game.data.players[2].cards = vec![Card(2, Suit::Heart), Card(9, Suit::Diamond)];
+        game.data.players[2].cards = vec![Card(3, Suit::Heart), Card(1, Suit::Diamond)];
         let game: Game<DistributePot> = game.into();
+        assert_eq!(game.get_num_pots(), 3);
         let game: Game<ShowHands> = game.into();
         let game: Game<DistributePot> = game.into();
+        assert_eq!(game.get_num_pots(), 2);
         let game: Game<ShowHands> = game.into();
         let game: Game<DistributePot> = game.into();
+        assert_eq!(game.get_num_pots(), 1);
         let game: Game<ShowHands> = game.into();
         assert!(game.is_pot_empty());
         for (i, money) in [
-            3 * game.data.settings.buy_in,
-            2 * game.data.settings.buy_in,
             game.data.settings.buy_in,
+            2 * game.data.settings.buy_in,
+            3 * game.data.settings.buy_in,
         ]
         .iter()
         .enumerate()
@@ -2303,34 +3984,217 @@ mod game_tests {
     }
 
     #[test]
-    fn manipulating_user_in_lobby() {
-        let mut game = Game::<SeatPlayers>::new();
-        let username = Username::new("ognf");
-
-        assert_eq!(game.new_user(&username), Ok(true));
-        assert!(game.contains_spectator(&username));
-
-        assert_eq!(game.new_user(&username), Err(UserError::UserAlreadyExists));
-
-        assert_eq!(game.waitlist_user(&username), Ok(Some(true)));
-        assert!(game.contains_waitlister(&username));
-
-        assert_eq!(game.spectate_user(&username), Ok(Some(true)));
-        assert!(game.contains_spectator(&username));
-
-        assert_eq!(game.remove_user(&username), Ok(Some(true)));
-        assert!(!game.contains_user(&username));
+    fn pot_breakdown_explains_side_pots() {
+        let game = init_3_player_game();
+        let mut game: Game<MoveButton> = game.into();
+        for i in 0..3 {
+            game.data.players[i].user.money = game.data.settings.buy_in * (3 - i as u32);
+        }
+        let game: Game<CollectBlinds> = game.into();
+        let game: Game<Deal> = game.into();
+        let mut game: Game<TakeAction> = game.into();
+        for _ in 0..3 {
+            assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
+        }
+        let game: Game<Flop> = game.into();
+        let game: Game<Turn> = game.into();
+        let game: Game<River> = game.into();
+        let mut game: Game<ShowHands> = game.into();
+        game.data.board = vec![
+            Card(1, Suit::Spade),
+            Card(4, Suit::Diamond),
+            Card(5, Suit::Diamond),
+            Card(6, Suit::Diamond),
+            Card(7, Suit::Diamond),
+        ];
+        game.data.players[0].cards = vec![Card(2, Suit::Heart), Card(9, Suit::Diamond)];
+        game.data.players[1].cards = vec![Card(1, Suit::Heart), Card(10, Suit::Diamond)];
+        game.data.players[2].cards = vec![Card(3, Suit::Heart), Card(1, Suit::Diamond)];
+        game.data.events.clear();
+        let game: Game<DistributePot> = game.into();
+        let game: Game<ShowHands> = game.into();
+        let game: Game<DistributePot> = game.into();
+        let game: Game<ShowHands> = game.into();
+        let game: Game<DistributePot> = game.into();
+        let mut game: Game<ShowHands> = game.into();
+        assert!(game.is_pot_empty());
 
+        // Pots are paid out from the highest side pot down, and the hand
+        // that's only in the top side pot never has to show.
+        let buy_in = game.data.settings.buy_in;
+        let breakdowns: Vec<_> = game
+            .drain_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                GameEvent::PotBreakdown(breakdown) => Some(breakdown),
+                _ => None,
+            })
+            .collect();
         assert_eq!(
-            game.remove_user(&username),
-            Err(UserError::UserDoesNotExist)
+            breakdowns,
+            [
+                PotBreakdown {
+                    pot_idx: 2,
+                    amount: buy_in,
+                    eligible: vec![0],
+                    boards: vec![BoardResult {
+                        winners: vec![0],
+                        hand: None,
+                    }],
+                    odd_chips: vec![],
+                },
+                PotBreakdown {
+                    pot_idx: 1,
+                    amount: 2 * buy_in,
+                    eligible: vec![0, 1],
+                    boards: vec![BoardResult {
+                        winners: vec![1],
+                        hand: Some("Flush, Ten high".to_string()),
+                    }],
+                    odd_chips: vec![],
+                },
+                PotBreakdown {
+                    pot_idx: 0,
+                    amount: 3 * buy_in,
+                    eligible: vec![0, 1, 2],
+                    boards: vec![BoardResult {
+                        winners: vec![2],
+                        hand: Some("Flush, Ace high".to_string()),
+                    }],
+                    odd_chips: vec![],
+                },
+            ]
         );
         assert_eq!(
-            game.waitlist_user(&username),
-            Err(UserError::UserDoesNotExist)
+            breakdowns[0].to_string(),
+            format!("side pot 2 ${buy_in} (seat 0): seat 0 wins")
         );
         assert_eq!(
-            game.spectate_user(&username),
+            breakdowns[2].to_string(),
+            format!(
+                "main pot ${} (seats 0, 1, 2): seat 2 wins with Flush, Ace high",
+                3 * buy_in
+            )
+        );
+    }
+
+    #[test]
+    fn pot_breakdown_explains_odd_chip() {
+        // The odd chip from a tie goes to the winner in the earliest seat
+        let mut odd_chips = BTreeMap::new();
+        assert_eq!(
+            Game::<DistributePot>::split_pot(&[1, 2], 301, &mut odd_chips),
+            [(1, 151), (2, 150)]
+        );
+        assert_eq!(odd_chips, BTreeMap::from([(1, 1)]));
+
+        let breakdown = PotBreakdown {
+            pot_idx: 0,
+            amount: 301,
+            eligible: vec![0, 1, 2],
+            boards: vec![BoardResult {
+                winners: vec![1, 2],
+                hand: Some("Straight, Seven high".to_string()),
+            }],
+            odd_chips: vec![(1, 1)],
+        };
+        assert_eq!(
+            breakdown.to_string(),
+            "main pot $301 (seats 0, 1, 2): seats 1, 2 split with Straight, Seven high; \
+             odd chip to seat 1"
+        );
+    }
+
+    #[test]
+    fn early_showdown_3_increasing_all_ins() {
+        let game = init_3_player_game();
+        let mut game: Game<MoveButton> = game.into();
+        for i in 0..3 {
+            game.data.players[i].user.money = game.data.settings.buy_in * (i as u32 + 1);
+        }
+        let game: Game<CollectBlinds> = game.into();
+        let game: Game<Deal> = game.into();
+        let mut game: Game<TakeAction> = game.into();
+        assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
+        assert_eq!(
+            game.get_next_action_choices(),
+            Some(
+                [
+                    ActionChoice::AllIn,
+                    ActionChoice::Call(195),
+                    ActionChoice::Fold,
+                ]
+                .into()
+            )
+        );
+        assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
+        assert_eq!(
+            game.get_next_action_choices(),
+            Some([ActionChoice::Call(390), ActionChoice::Fold,].into())
+        );
+        assert_eq!(game.act(Action::Call), Ok(Action::Call));
+        let game: Game<Flop> = game.into();
+        let game: Game<Turn> = game.into();
+        let game: Game<River> = game.into();
+        let mut game: Game<ShowHands> = game.into();
+        game.data.board = vec![
+            Card(1, Suit::Spade),
+            Card(4, Suit::Diamond),
+            Card(5, Suit::Diamond),
+            Card(6, Suit::Diamond),
+            Card(7, Suit::Diamond),
+        ];
+        game.data.players[0].cards = vec![Card(3, Suit::Heart), Card(1, Suit::Diamond)];
+        game.data.players[1].cards = vec![Card(1, Suit::Heart), Card(10, Suit::Diamond)];
+        game.data.players[2].cards = vec![Card(2, Suit::Heart), Card(9, Suit::Diamond)];
+        let game: Game<DistributePot> = game.into();
+        let game: Game<ShowHands> = game.into();
+        let game: Game<DistributePot> = game.into();
+        let game: Game<ShowHands> = game.into();
+        let game: Game<DistributePot> = game.into();
+        let game: Game<ShowHands> = game.into();
+        assert!(game.is_pot_empty());
+        for (i, money) in [
+            3 * game.data.settings.buy_in,
+            2 * game.data.settings.buy_in,
+            game.data.settings.buy_in,
+        ]
+        .iter()
+        .enumerate()
+        {
+            assert_eq!(game.data.players[i].user.money, *money);
+        }
+    }
+
+    #[test]
+    fn manipulating_user_in_lobby() {
+        let mut game = Game::<SeatPlayers>::new();
+        let username = Username::new("ognf");
+
+        assert_eq!(game.new_user(&username), Ok(true));
+        assert!(game.contains_spectator(&username));
+
+        assert_eq!(game.new_user(&username), Err(UserError::UserAlreadyExists));
+
+        assert_eq!(game.waitlist_user(&username), Ok(Some(true)));
+        assert!(game.contains_waitlister(&username));
+
+        assert_eq!(game.spectate_user(&username), Ok(Some(true)));
+        assert!(game.contains_spectator(&username));
+
+        assert_eq!(game.remove_user(&username), Ok(Some(true)));
+        assert!(!game.contains_user(&username));
+
+        assert_eq!(
+            game.remove_user(&username),
+            Err(UserError::UserDoesNotExist)
+        );
+        assert_eq!(
+            game.waitlist_user(&username),
+            Err(UserError::UserDoesNotExist)
+        );
+        assert_eq!(
+            game.spectate_user(&username),
             Err(UserError::UserDoesNotExist)
         );
 
@@ -2361,6 +4225,333 @@ mod game_tests {
         );
     }
 
+    #[test]
+    fn collect_antes_from_full_table() {
+        let mut game = init_game_at_move_button();
+        game.data.blinds.ante = 2;
+        let game: Game<Deal> = game.into();
+        let blinds = game.data.blinds.clone();
+        assert_eq!(game.data.pot.get_investment_by_player_idx(0), 2);
+        assert_eq!(
+            game.data.pot.get_investment_by_player_idx(1),
+            blinds.small + 2
+        );
+        assert_eq!(
+            game.data.pot.get_investment_by_player_idx(2),
+            blinds.big + 2
+        );
+        assert_eq!(game.data.pot.get_size(), blinds.small + blinds.big + 6);
+        assert_eq!(
+            game.data.players[0].user.money,
+            game.data.settings.buy_in - 2
+        );
+        assert_eq!(game.data.player_counts.num_active, 3);
+
+        // Everyone still owes the big blind, but the antes are already in.
+        let game: Game<TakeAction> = game.into();
+        assert_eq!(game.data.pot.get_call_by_player_idx(0), blinds.big);
+    }
+
+    #[test]
+    fn collect_antes_with_short_stack_all_in() {
+        let mut game = init_game_at_move_button();
+        game.data.blinds.ante = 2;
+        game.data.players[0].user.money = 1;
+        let game: Game<Deal> = game.into();
+        assert_eq!(game.data.pot.get_investment_by_player_idx(0), 1);
+        assert_eq!(game.data.players[0].user.money, 0);
+        assert_eq!(game.data.players[0].state, PlayerState::AllIn);
+        assert_eq!(game.data.player_counts.num_active, 2);
+
+        // The short stack can only win what they matched.
+        let side_pots = game.data.pot.split_into_side_pots(&HashSet::new());
+        assert_eq!(side_pots[0].amount, 3);
+        assert_eq!(side_pots[0].eligible, vec![0, 1, 2]);
+
+        // The short stack is skipped for action.
+        let game: Game<TakeAction> = game.into();
+        assert_eq!(game.data.play_positions.next_action_idx, Some(1));
+    }
+
+    #[test]
+    fn straddle_3_players() {
+        let mut game = init_game_at_move_button();
+        game.data.settings.straddle_allowed = true;
+        game.data
+            .player_queues
+            .to_straddle
+            .insert(Username::new("0"));
+        let game: Game<Deal> = game.into();
+        let straddle = 2 * game.data.blinds.big;
+        assert_eq!(game.data.play_positions.straddle_idx, Some(0));
+        assert_eq!(game.data.play_positions.starting_action_idx, 1);
+        assert_eq!(game.data.pot.get_investment_by_player_idx(0), straddle);
+        assert!(game.data.player_queues.to_straddle.is_empty());
+
+        // Action starts left of the straddler, and the straddle is the
+        // effective current bet.
+        let mut game: Game<TakeAction> = game.into();
+        assert_eq!(game.data.play_positions.next_action_idx, Some(1));
+        assert_eq!(game.data.pot.get_min_raise_by_player_idx(1), 35);
+        assert_eq!(
+            game.get_next_action_choices(),
+            Some(
+                [
+                    ActionChoice::AllIn,
+                    ActionChoice::Call(15),
+                    ActionChoice::Fold,
+                    ActionChoice::Raise { min: 35, max: 595 },
+                ]
+                .into()
+            )
+        );
+        assert_eq!(game.act(Action::Call), Ok(Action::Call));
+        assert_eq!(game.data.play_positions.next_action_idx, Some(2));
+        assert_eq!(game.act(Action::Call), Ok(Action::Call));
+
+        // The straddler acts last and has the option to check.
+        assert_eq!(game.data.play_positions.next_action_idx, Some(0));
+        assert_eq!(
+            game.get_next_action_choices(),
+            Some(
+                [
+                    ActionChoice::AllIn,
+                    ActionChoice::Check,
+                    ActionChoice::Fold,
+                    ActionChoice::Raise {
+                        min: straddle,
+                        max: 580,
+                    },
+                ]
+                .into()
+            )
+        );
+        assert_eq!(game.act(Action::Check), Ok(Action::Check));
+        assert!(game.is_ready_for_next_phase());
+        assert_eq!(game.data.pot.get_size(), 3 * straddle);
+
+        // Postflop action starts from the usual position again.
+        let game: Game<Flop> = game.into();
+        assert_eq!(game.data.play_positions.starting_action_idx, 0);
+    }
+
+    #[test]
+    fn straddle_not_allowed() {
+        let mut game = init_game_at_move_button();
+        game.data
+            .player_queues
+            .to_straddle
+            .insert(Username::new("0"));
+        let game: Game<Deal> = game.into();
+        assert_eq!(game.data.play_positions.straddle_idx, None);
+        assert_eq!(game.data.play_positions.starting_action_idx, 0);
+        assert_eq!(game.data.pot.get_investment_by_player_idx(0), 0);
+    }
+
+    #[test]
+    fn run_it_twice_2_all_ins() {
+        let game = init_2_player_game();
+        let mut game: Game<MoveButton> = game.into();
+        game.data.settings.run_it_twice_allowed = true;
+        for i in 0..2 {
+            game.data.run_it_twice_consent.insert(i.to_string().into());
+        }
+        let game: Game<CollectBlinds> = game.into();
+        let game: Game<Deal> = game.into();
+        let mut game: Game<TakeAction> = game.into();
+
+        // Redeal from a seeded deck so the runouts are reproducible.
+        game.data.deck.shuffle_with(&mut StdRng::seed_from_u64(42));
+        for player in &mut game.data.players {
+            player.cards = vec![game.data.deck.deal_card(), game.data.deck.deal_card()];
+        }
+        for _ in 0..2 {
+            assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
+        }
+        let game: Game<Flop> = game.into();
+        assert!(game.data.run_it_twice);
+        let game: Game<Turn> = game.into();
+        let game: Game<River> = game.into();
+        let game: Game<ShowHands> = game.into();
+        assert_eq!(game.data.board.len(), 5);
+        assert_eq!(game.data.second_board.len(), 5);
+
+        // Each run gets its own cards from the same deck.
+        let mut cards: Vec<_> = game
+            .data
+            .board
+            .iter()
+            .chain(game.data.second_board.iter())
+            .chain(game.data.players.iter().flat_map(|p| p.cards.iter()))
+            .copied()
+            .collect();
+        let num_cards = cards.len();
+        cards.sort_unstable();
+        cards.dedup();
+        assert_eq!(cards.len(), num_cards);
+
+        // Each run is worth half the pot.
+        let pot_size = game.data.pot.get_size();
+        let mut expected_money = [0; 2];
+        for board in [&game.data.board, &game.data.second_board] {
+            let hands: Vec<_> = game
+                .data
+                .players
+                .iter()
+                .map(|player| {
+                    let mut cards = player.cards.clone();
+                    cards.extend_from_slice(board);
+                    functional::prepare_hand(&mut cards);
+                    functional::eval(&cards)
+                })
+                .collect();
+            let winner_indices = functional::argmax(&hands);
+            for winner_idx in &winner_indices {
+                expected_money[*winner_idx] += pot_size / 2 / winner_indices.len() as Usd;
+            }
+        }
+        let game: Game<DistributePot> = game.into();
+        let game: Game<RemovePlayers> = game.into();
+        assert!(game.is_pot_empty());
+        for (i, money) in expected_money.iter().enumerate() {
+            assert_eq!(game.data.players[i].user.money, *money);
+        }
+        assert_eq!(expected_money.iter().sum::<Usd>(), pot_size);
+    }
+
+    #[test]
+    fn run_it_twice_odd_chip_goes_to_earliest_seat() {
+        let mut game = init_game_at_move_button();
+        game.data.settings.run_it_twice_allowed = true;
+        // The folding player doesn't need to agree to run it twice.
+        for username in ["0", "2"] {
+            game.data
+                .run_it_twice_consent
+                .insert(Username::new(username));
+        }
+        let game: Game<Deal> = game.into();
+        let mut game: Game<TakeAction> = game.into();
+        assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
+        assert_eq!(game.act(Action::Fold), Ok(Action::Fold));
+        assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
+        let game: Game<Flop> = game.into();
+        assert!(game.data.run_it_twice);
+        let game: Game<Turn> = game.into();
+        let game: Game<River> = game.into();
+        let mut game: Game<ShowHands> = game.into();
+
+        // The small blind's dead money makes the pot odd.
+        let pot_size = game.data.pot.get_size();
+        assert_eq!(pot_size, 1205);
+        game.data.board = vec![
+            Card(2, Suit::Spade),
+            Card(5, Suit::Diamond),
+            Card(8, Suit::Club),
+            Card(11, Suit::Heart),
+            Card(3, Suit::Club),
+        ];
+        game.data.second_board = vec![
+            Card(13, Suit::Spade),
+            Card(6, Suit::Diamond),
+            Card(9, Suit::Club),
+            Card(12, Suit::Heart),
+            Card(4, Suit::Club),
+        ];
+        game.data.players[0].cards = vec![Card(1, Suit::Heart), Card(1, Suit::Diamond)];
+        game.data.players[2].cards = vec![Card(13, Suit::Heart), Card(13, Suit::Diamond)];
+        let mut game: Game<DistributePot> = game.into();
+        assert_eq!(game.get_num_pots(), 1);
+        game.data.events.clear();
+        let mut game: Game<RemovePlayers> = game.into();
+        assert!(game.is_pot_empty());
+        assert_eq!(game.data.players[0].user.money, 603);
+        assert_eq!(game.data.players[1].user.money, 595);
+        assert_eq!(game.data.players[2].user.money, 602);
+        assert_eq!(
+            game.drain_events(),
+            [
+                GameEvent::PotBreakdown(PotBreakdown {
+                    pot_idx: 0,
+                    amount: 1205,
+                    eligible: vec![0, 2],
+                    boards: vec![
+                        BoardResult {
+                            winners: vec![0],
+                            hand: Some("Pair of Aces".to_string()),
+                        },
+                        BoardResult {
+                            winners: vec![2],
+                            hand: Some("Three of a kind, Kings".to_string()),
+                        },
+                    ],
+                    odd_chips: vec![(0, 1)],
+                }),
+                GameEvent::SplitPot(Username::new("0"), 603),
+                GameEvent::SplitPot(Username::new("2"), 602),
+            ]
+        );
+    }
+
+    #[test]
+    fn run_it_twice_needs_consent() {
+        let mut game = init_game_at_move_button();
+        game.data.settings.run_it_twice_allowed = true;
+        game.data.run_it_twice_consent.insert(Username::new("0"));
+        let game: Game<Deal> = game.into();
+        let mut game: Game<TakeAction> = game.into();
+        for _ in 0..3 {
+            assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
+        }
+        let game: Game<Flop> = game.into();
+        assert!(!game.data.run_it_twice);
+        let game: Game<Turn> = game.into();
+        let game: Game<River> = game.into();
+        let game: Game<ShowHands> = game.into();
+        assert!(game.data.second_board.is_empty());
+    }
+
+    #[test]
+    fn full_omaha_table_runs_it_twice_only_if_the_deck_has_room() {
+        // 10 players leave 12 cards for two boards, but 11 leave only 8.
+        for (num_players, runs_twice) in [(10, true), (11, false)] {
+            let settings = GameSettings {
+                variant: GameVariant::Omaha,
+                max_users: num_players,
+                max_players: num_players,
+                run_it_twice_allowed: true,
+                ..Default::default()
+            };
+            let game: Game<Lobby> = settings.into();
+            let mut game: Game<SeatPlayers> = game.into();
+            for i in 0..num_players {
+                let username = i.to_string().into();
+                game.new_user(&username).unwrap();
+                game.waitlist_user(&username).unwrap();
+            }
+            let mut game: Game<MoveButton> = game.into();
+            for i in 0..num_players {
+                game.data.run_it_twice_consent.insert(i.to_string().into());
+            }
+            let game: Game<CollectBlinds> = game.into();
+            let game: Game<Deal> = game.into();
+            let mut game: Game<TakeAction> = game.into();
+            assert_eq!(game.get_num_users(), num_players);
+            for _ in 0..num_players {
+                assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
+            }
+            let game: Game<Flop> = game.into();
+            assert_eq!(game.data.run_it_twice, runs_twice);
+            let game: Game<Turn> = game.into();
+            let game: Game<River> = game.into();
+            let game: Game<ShowHands> = game.into();
+            assert_eq!(game.data.board.len(), 5);
+            assert_eq!(game.data.second_board.len(), if runs_twice { 5 } else { 0 });
+            let game: Game<DistributePot> = game.into();
+            let game: Game<RemovePlayers> = game.into();
+            assert!(game.is_pot_empty());
+        }
+    }
+
     // Fill a game to capacity and then move the action index around.
     // Every player should get their turn.
     #[test]
@@ -2534,30 +4725,67 @@ mod game_tests {
     }
 
     #[test]
-    fn seat_players() {
-        let game = init_game_at_seat_players();
-        let username0 = Username::new("0");
-        let username1 = Username::new("1");
-        let username2 = Username::new("2");
-        assert_eq!(game.data.player_counts.num_active, game.get_num_players());
-        assert!(game.contains_player(&username0));
-        assert!(game.contains_player(&username1));
-        assert!(game.contains_player(&username2));
-    }
+    fn player_views_show_committed_chips_per_street() {
+        fn committed<T>(game: &Game<T>) -> Vec<(Usd, Usd)> {
+            let view = game.get_views().remove(&Username::new("0")).unwrap();
+            view.players
+                .iter()
+                .map(|player| (player.committed_this_street, player.total_committed))
+                .collect()
+        }
 
-    #[test]
-    fn take_action_2_all_ins() {
         let mut game = init_game_at_deal();
+        assert_eq!(committed(&game), [(0, 0), (5, 5), (10, 10)]);
         assert_eq!(
-            game.get_next_action_choices(),
-            Some(
-                [
-                    ActionChoice::AllIn,
-                    ActionChoice::Call(10),
-                    ActionChoice::Fold,
-                    ActionChoice::Raise(20)
-                ]
-                .into()
+            game.act(Action::Raise(Some(30))),
+            Ok(Action::Raise(Some(30)))
+        );
+        assert_eq!(game.act(Action::Call), Ok(Action::Call));
+        assert_eq!(game.act(Action::Call), Ok(Action::Call));
+        assert_eq!(committed(&game), [(30, 30), (30, 30), (30, 30)]);
+
+        // Bets from earlier streets only count towards the hand's total
+        let game: Game<Flop> = game.into();
+        let mut game: Game<TakeAction> = game.into();
+        assert_eq!(committed(&game), [(0, 30), (0, 30), (0, 30)]);
+        assert_eq!(
+            game.act(Action::Raise(Some(40))),
+            Ok(Action::Raise(Some(40)))
+        );
+        assert_eq!(game.act(Action::Call), Ok(Action::Call));
+        assert_eq!(game.act(Action::Fold), Ok(Action::Fold));
+        assert_eq!(committed(&game), [(40, 70), (40, 70), (0, 30)]);
+
+        let game: Game<Turn> = game.into();
+        let game: Game<TakeAction> = game.into();
+        assert_eq!(committed(&game), [(0, 70), (0, 70), (0, 30)]);
+    }
+
+    #[test]
+    fn seat_players() {
+        let game = init_game_at_seat_players();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        assert_eq!(game.data.player_counts.num_active, game.get_num_players());
+        assert!(game.contains_player(&username0));
+        assert!(game.contains_player(&username1));
+        assert!(game.contains_player(&username2));
+    }
+
+    #[test]
+    fn take_action_2_all_ins() {
+        let mut game = init_game_at_deal();
+        assert_eq!(
+            game.get_next_action_choices(),
+            Some(
+                [
+                    ActionChoice::AllIn,
+                    ActionChoice::Call(10),
+                    ActionChoice::Fold,
+                    ActionChoice::Raise { min: 20, max: 600 }
+                ]
+                .into()
             )
         );
         assert_eq!(game.act(Action::AllIn), Ok(Action::AllIn));
@@ -2584,7 +4812,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Call(10),
                     ActionChoice::Fold,
-                    ActionChoice::Raise(20)
+                    ActionChoice::Raise { min: 20, max: 600 }
                 ]
                 .into()
             )
@@ -2597,7 +4825,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Call(5),
                     ActionChoice::Fold,
-                    ActionChoice::Raise(15)
+                    ActionChoice::Raise { min: 15, max: 595 }
                 ]
                 .into()
             )
@@ -2610,7 +4838,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Check,
                     ActionChoice::Fold,
-                    ActionChoice::Raise(20)
+                    ActionChoice::Raise { min: 10, max: 590 }
                 ]
                 .into()
             )
@@ -2629,7 +4857,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Call(10),
                     ActionChoice::Fold,
-                    ActionChoice::Raise(20)
+                    ActionChoice::Raise { min: 20, max: 600 }
                 ]
                 .into()
             )
@@ -2642,7 +4870,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Call(5),
                     ActionChoice::Fold,
-                    ActionChoice::Raise(15)
+                    ActionChoice::Raise { min: 15, max: 595 }
                 ]
                 .into()
             )
@@ -2661,7 +4889,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Call(10),
                     ActionChoice::Fold,
-                    ActionChoice::Raise(20)
+                    ActionChoice::Raise { min: 20, max: 600 }
                 ]
                 .into()
             )
@@ -2674,7 +4902,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Call(5),
                     ActionChoice::Fold,
-                    ActionChoice::Raise(15)
+                    ActionChoice::Raise { min: 15, max: 595 }
                 ]
                 .into()
             )
@@ -2691,7 +4919,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Call(10),
                     ActionChoice::Fold,
-                    ActionChoice::Raise(30)
+                    ActionChoice::Raise { min: 30, max: 590 }
                 ]
                 .into()
             )
@@ -2708,7 +4936,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Call(20),
                     ActionChoice::Fold,
-                    ActionChoice::Raise(60)
+                    ActionChoice::Raise { min: 60, max: 580 }
                 ]
                 .into()
             )
@@ -2725,7 +4953,7 @@ mod game_tests {
                     ActionChoice::AllIn,
                     ActionChoice::Call(40),
                     ActionChoice::Fold,
-                    ActionChoice::Raise(120)
+                    ActionChoice::Raise { min: 120, max: 560 }
                 ]
                 .into()
             )
@@ -2737,10 +4965,15 @@ mod game_tests {
 
 #[cfg(test)]
 mod state_tests {
-    use super::super::entities::{Action, Username};
+    use std::sync::Arc;
+
+    use super::super::entities::{
+        Action, ActionChoice, BettingStructure, Blinds, Card, DEFAULT_BUY_IN,
+        MAX_RECENT_ACTIONS_PER_STREET, PotBreakdown, SeatIndex, Suit, Usd, Username,
+    };
     use super::{
-        GameSettings, PhaseDependentUserManagement, PhaseIndependentUserManagement, PokerState,
-        UserError,
+        GameEvent, GameSettings, GameStateManagement, PhaseDependentUserManagement,
+        PhaseIndependentUserManagement, PokerState, UserError,
     };
 
     fn init_state() -> PokerState {
@@ -2771,9 +5004,682 @@ mod state_tests {
         // Should be back at Lobby.
         state = state.step();
         assert_eq!(
-            state.init_start(&username0),
-            Err(UserError::NotEnoughPlayers)
+            state.init_start(&username0),
+            Err(UserError::NotEnoughPlayers)
+        );
+    }
+
+    /// Put players 0 and 2 all-in against each other with aces against
+    /// kings on the turn, leaving the kings two outs.
+    fn init_all_in_on_the_turn() -> PokerState {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(state.take_action(&username0, Action::AllIn).is_ok());
+        assert!(state.take_action(&username1, Action::Fold).is_ok());
+        assert!(state.take_action(&username2, Action::AllIn).is_ok());
+        let PokerState::TakeAction(game) = &mut state else {
+            unreachable!()
+        };
+        game.data.board = vec![
+            Card(2, Suit::Spade),
+            Card(5, Suit::Diamond),
+            Card(8, Suit::Club),
+            Card(11, Suit::Heart),
+        ];
+        for player in &mut game.data.players {
+            if player.user.name == username0 {
+                player.cards = vec![Card(1, Suit::Heart), Card(1, Suit::Diamond)];
+            } else if player.user.name == username2 {
+                player.cards = vec![Card(13, Suit::Heart), Card(13, Suit::Diamond)];
+            }
+        }
+        state
+    }
+
+    #[test]
+    fn equity_chop_splits_pot_by_equity_at_offer_time() {
+        let mut state = init_all_in_on_the_turn();
+        let username0 = Username::new("0");
+        let username2 = Username::new("2");
+        let offer = state.offer_equity_chop().unwrap();
+        assert_eq!(
+            offer,
+            vec![
+                (username0.clone(), 42.0 / 44.0),
+                (username2.clone(), 2.0 / 44.0)
+            ]
+        );
+        assert_eq!(
+            state.offer_equity_chop(),
+            Err(UserError::CannotOfferEquityChop)
+        );
+
+        // Nothing is dealt while the offer is waiting on an answer.
+        assert_eq!(state.respond_to_equity_chop(&username0, true), Ok(()));
+        state = state.step();
+        assert!(state.is_equity_chop_pending());
+
+        // A king on the river after the offer doesn't change the split.
+        let PokerState::TakeAction(game) = &mut state else {
+            unreachable!()
+        };
+        game.data.board.push(Card(13, Suit::Club));
+        assert_eq!(state.respond_to_equity_chop(&username2, true), Ok(()));
+        assert!(!state.is_equity_chop_pending());
+        state.drain_events();
+        state = state.step();
+        assert!(matches!(state, PokerState::ShowHands(_)));
+        state = state.step();
+        state = state.step();
+        assert!(matches!(state, PokerState::RemovePlayers(_)));
+
+        // Both players get a share of the pot, so both hands are shown.
+        // 1205 * 42 / 44 rounds down to 1150 and 1205 * 2 / 44 to 54, and
+        // the chip lost to rounding goes to the earliest seat.
+        assert_eq!(
+            state.drain_events(),
+            [
+                GameEvent::ShowedHand(
+                    username0.clone(),
+                    vec![Card(1, Suit::Heart), Card(1, Suit::Diamond)]
+                ),
+                GameEvent::ShowedHand(
+                    username2.clone(),
+                    vec![Card(13, Suit::Heart), Card(13, Suit::Diamond)]
+                ),
+                GameEvent::PotBreakdown(PotBreakdown {
+                    pot_idx: 0,
+                    amount: 1205,
+                    eligible: vec![0, 2],
+                    boards: vec![],
+                    odd_chips: vec![(0, 1)],
+                }),
+                GameEvent::SplitPot(username0, 1151),
+                GameEvent::SplitPot(username2, 54),
+            ]
+        );
+    }
+
+    #[test]
+    fn declined_equity_chop_runs_out_the_board() {
+        let mut state = init_all_in_on_the_turn();
+        let username0 = Username::new("0");
+        let username2 = Username::new("2");
+        assert!(state.offer_equity_chop().is_ok());
+        assert_eq!(state.respond_to_equity_chop(&username0, true), Ok(()));
+        assert_eq!(state.respond_to_equity_chop(&username2, false), Ok(()));
+        assert!(!state.is_equity_chop_pending());
+        assert_eq!(
+            state.respond_to_equity_chop(&username0, true),
+            Err(UserError::NoEquityChopOffer)
+        );
+        state = state.step();
+        assert!(matches!(state, PokerState::River(_)));
+        state = state.step();
+        assert!(matches!(state, PokerState::ShowHands(_)));
+        let PokerState::ShowHands(game) = &state else {
+            unreachable!()
+        };
+        assert_eq!(game.data.board.len(), 5);
+    }
+
+    #[test]
+    fn timeout_action_checks_or_folds() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        assert_eq!(
+            state.take_timeout_action(&username1),
+            Err(UserError::OutOfTurnAction)
+        );
+
+        // Facing the big blind, so the timeout action is a fold.
+        assert_eq!(state.take_timeout_action(&username0), Ok(Action::Fold));
+        assert_eq!(
+            state.take_action(&username1, Action::Call),
+            Ok(Action::Call)
+        );
+
+        // The big blind can check their option.
+        assert_eq!(state.take_timeout_action(&username2), Ok(Action::Check));
+        let events = state.drain_events();
+        assert!(events.contains(&GameEvent::TimedOut(username0, Action::Fold)));
+        assert!(events.contains(&GameEvent::TimedOut(username2, Action::Check)));
+    }
+
+    #[test]
+    fn settled_stacks_call_off_the_hand() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+
+        // The blinds are in the pot, but they're refunded.
+        let stacks = state.get_settled_stacks();
+        assert_eq!(stacks.len(), 3);
+        assert!(stacks.values().all(|money| *money == DEFAULT_BUY_IN));
+
+        assert_eq!(
+            state.take_action(&username0, Action::Call),
+            Ok(Action::Call)
+        );
+        assert_eq!(
+            state.take_action(&username1, Action::Call),
+            Ok(Action::Call)
+        );
+        assert_eq!(state.get_settled_stacks(), stacks);
+    }
+
+    #[test]
+    fn seat_change_applies_next_game() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+
+        // Can't request a taken seat or a seat someone else already claimed.
+        assert_eq!(
+            state.request_seat(&username0, 2),
+            Err(UserError::SeatUnavailable)
+        );
+        assert_eq!(state.request_seat(&username0, 5), Ok(()));
+        assert_eq!(
+            state.request_seat(&username1, 5),
+            Err(UserError::SeatUnavailable)
+        );
+        assert!(
+            state
+                .drain_events()
+                .contains(&GameEvent::SeatChangeQueue(username0.clone(), 5))
+        );
+
+        // The move doesn't take effect mid-hand.
+        let views = state.get_views();
+        assert!(views[&username0].open_seats.contains(&5));
+        assert!(!views[&username0].open_seats.contains(&0));
+
+        while !matches!(state, PokerState::Lobby(_)) {
+            state = state.step();
+        }
+        assert!(state.get_views()[&username0].open_seats.contains(&5));
+        assert_eq!(state.init_start(&username0), Ok(()));
+        state = state.step();
+        state = state.step();
+        assert!(matches!(state, PokerState::MoveButton(_)));
+
+        let PokerState::MoveButton(ref game) = state else {
+            unreachable!()
+        };
+        let seats: Vec<_> = game
+            .data
+            .players
+            .iter()
+            .map(|p| (p.user.name.clone(), p.seat_idx))
+            .collect();
+        assert_eq!(
+            seats,
+            vec![(username1, 1), (username2, 2), (username0.clone(), 5)]
+        );
+        let views = state.get_views();
+        assert!(views[&username0].open_seats.contains(&0));
+        assert!(!views[&username0].open_seats.contains(&5));
+        assert!(
+            state
+                .drain_events()
+                .contains(&GameEvent::ChangedSeat(username0, 5))
+        );
+    }
+
+    #[test]
+    fn rabbit_hunt_after_flop_fold_out() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        assert_eq!(
+            state.take_action(&username0, Action::Call),
+            Ok(Action::Call)
+        );
+        assert_eq!(
+            state.take_action(&username1, Action::Call),
+            Ok(Action::Call)
+        );
+        assert_eq!(
+            state.take_action(&username2, Action::Check),
+            Ok(Action::Check)
+        );
+        state = state.step();
+        assert!(matches!(state, PokerState::Flop(_)));
+        state = state.step();
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        assert_eq!(state.rabbit_hunt(), Err(UserError::CannotRabbitHunt));
+
+        // Fold out the hand on the flop.
+        assert_eq!(
+            state.take_action(&username0, Action::Fold),
+            Ok(Action::Fold)
+        );
+        assert_eq!(
+            state.take_action(&username1, Action::Fold),
+            Ok(Action::Fold)
+        );
+        while !matches!(state, PokerState::RemovePlayers(_)) {
+            state = state.step();
+        }
+        let board = state.get_views()[&username0].board.clone();
+        assert_eq!(board.len(), 3);
+
+        // The turn and river are returned without being dealt.
+        let cards = state.rabbit_hunt().unwrap();
+        assert_eq!(cards.len(), 2);
+        assert!(cards.iter().all(|card| !board.contains(card)));
+        assert_eq!(state.rabbit_hunt(), Ok(cards.clone()));
+
+        while !matches!(state, PokerState::Lobby(_)) {
+            state = state.step();
+        }
+        assert_eq!(state.rabbit_hunt(), Ok(cards));
+
+        // Gone once the next hand is dealt.
+        assert_eq!(state.init_start(&username0), Ok(()));
+        while !matches!(state, PokerState::Deal(_)) {
+            state = state.step();
+        }
+        assert_eq!(state.rabbit_hunt(), Err(UserError::CannotRabbitHunt));
+    }
+
+    #[test]
+    fn turn_info_matches_pot() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+
+        let turn = state.get_turn_info().unwrap();
+        let PokerState::TakeAction(game) = &state else {
+            unreachable!()
+        };
+        let action_idx = game.data.play_positions.next_action_idx.unwrap();
+        let call = game.data.pot.get_call_by_player_idx(action_idx);
+        let pot_size = game.data.pot.get_size();
+        assert!(call > 0);
+        assert_eq!(turn.call_amount, call);
+        assert_eq!(
+            turn.min_raise,
+            Some(game.data.pot.get_min_raise_by_player_idx(action_idx))
+        );
+        assert_eq!(turn.pot_size, pot_size);
+        assert_eq!(turn.pot_odds, f64::from(call) / f64::from(pot_size + call));
+        assert_eq!(Some(turn.action_choices), state.get_action_choices());
+
+        // Nothing to call means no price to pay.
+        assert!(state.take_action(&username0, Action::Call).is_ok());
+        let username1 = Username::new("1");
+        assert!(state.take_action(&username1, Action::Call).is_ok());
+        let turn = state.get_turn_info().unwrap();
+        assert_eq!(turn.call_amount, 0);
+        assert_eq!(turn.pot_odds, 0.0);
+    }
+
+    #[test]
+    fn hand_emits_narrative_events_in_order() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        state.drain_events();
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(state.take_action(&username0, Action::Fold).is_ok());
+        assert!(state.take_action(&username1, Action::Call).is_ok());
+        assert!(state.take_action(&username2, Action::Check).is_ok());
+        for _ in 0..3 {
+            state = state.step();
+            state = state.step();
+            assert!(state.take_action(&username1, Action::Check).is_ok());
+            assert!(state.take_action(&username2, Action::Check).is_ok());
+        }
+
+        // Give player 1 the winning hand.
+        let PokerState::TakeAction(game) = &mut state else {
+            unreachable!()
+        };
+        game.data.board = vec![
+            Card(2, Suit::Spade),
+            Card(5, Suit::Diamond),
+            Card(8, Suit::Club),
+            Card(11, Suit::Heart),
+            Card(12, Suit::Spade),
+        ];
+        for player in &mut game.data.players {
+            if player.user.name == username1 {
+                player.cards = vec![Card(1, Suit::Heart), Card(1, Suit::Diamond)];
+            } else if player.user.name == username2 {
+                player.cards = vec![Card(13, Suit::Heart), Card(13, Suit::Diamond)];
+            }
+        }
+        while !matches!(state, PokerState::Lobby(_)) {
+            state = state.step();
+        }
+
+        // Boards are random, so only the number of cards dealt is checked.
+        let events: Vec<_> = state
+            .drain_events()
+            .into_iter()
+            .map(|event| match event {
+                GameEvent::DealtBoard(street, cards) => {
+                    format!("{street}: {}", vec!["??"; cards.len()].join(" "))
+                }
+                event => event.to_string(),
+            })
+            .collect();
+        assert_eq!(
+            events,
+            [
+                "0 joined the table",
+                "1 joined the table",
+                "2 joined the table",
+                "button moves to seat 0",
+                "1 posts a $5 blind",
+                "2 posts a $10 blind",
+                "0 folds",
+                "1 calls",
+                "2 checks",
+                "flop: ?? ?? ??",
+                "1 checks",
+                "2 checks",
+                "turn: ??",
+                "1 checks",
+                "2 checks",
+                "river: ??",
+                "1 checks",
+                "2 checks",
+                "showdown order: 1, 2",
+                "1 shows Ah Ad",
+                "2 mucks",
+                "main pot $20 (seats 1, 2): seat 1 wins with Pair of Aces",
+                "1 won $20",
+            ]
+        );
+    }
+
+    #[test]
+    fn river_bettor_reveals_first_at_showdown() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(state.take_action(&username0, Action::Fold).is_ok());
+        assert!(state.take_action(&username1, Action::Call).is_ok());
+        assert!(state.take_action(&username2, Action::Check).is_ok());
+        for _ in 0..2 {
+            state = state.step();
+            state = state.step();
+            assert!(state.take_action(&username1, Action::Check).is_ok());
+            assert!(state.take_action(&username2, Action::Check).is_ok());
+        }
+
+        // Player 1 checks the river, player 2 bets, and player 1 calls.
+        state = state.step();
+        state = state.step();
+        assert!(state.take_action(&username1, Action::Check).is_ok());
+        assert!(state.take_action(&username2, Action::Raise(None)).is_ok());
+        assert!(state.take_action(&username1, Action::Call).is_ok());
+
+        // The bettor has the worse hand, so both hands are revealed.
+        let PokerState::TakeAction(game) = &mut state else {
+            unreachable!()
+        };
+        game.data.board = vec![
+            Card(2, Suit::Spade),
+            Card(5, Suit::Diamond),
+            Card(8, Suit::Club),
+            Card(11, Suit::Heart),
+            Card(12, Suit::Spade),
+        ];
+        for player in &mut game.data.players {
+            if player.user.name == username1 {
+                player.cards = vec![Card(1, Suit::Heart), Card(1, Suit::Diamond)];
+            } else if player.user.name == username2 {
+                player.cards = vec![Card(13, Suit::Heart), Card(13, Suit::Diamond)];
+            }
+        }
+        state.drain_events();
+        while !matches!(state, PokerState::Lobby(_)) {
+            state = state.step();
+        }
+
+        let reveals: Vec<_> = state
+            .drain_events()
+            .into_iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    GameEvent::ShowdownOrder(_)
+                        | GameEvent::ShowedHand(..)
+                        | GameEvent::MuckedHand(_)
+                )
+            })
+            .collect();
+        assert_eq!(
+            reveals,
+            [
+                GameEvent::ShowdownOrder(vec![username2.clone(), username1.clone()]),
+                GameEvent::ShowedHand(
+                    username2,
+                    vec![Card(13, Suit::Heart), Card(13, Suit::Diamond)]
+                ),
+                GameEvent::ShowedHand(
+                    username1,
+                    vec![Card(1, Suit::Heart), Card(1, Suit::Diamond)]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn game_view_shows_recent_actions() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        assert!(state.get_views()[&username0].recent_actions.is_empty());
+        assert!(state.take_action(&username0, Action::Call).is_ok());
+        assert!(state.take_action(&username1, Action::Call).is_ok());
+        assert!(state.take_action(&username2, Action::Check).is_ok());
+
+        let seat_of = |state: &PokerState, username: &Username| {
+            let PokerState::TakeAction(game) = state else {
+                unreachable!()
+            };
+            game.data
+                .players
+                .iter()
+                .find(|player| &player.user.name == username)
+                .unwrap()
+                .seat_idx
+        };
+        let expected = vec![
+            (seat_of(&state, &username0), Action::Call),
+            (seat_of(&state, &username1), Action::Call),
+            (seat_of(&state, &username2), Action::Check),
+        ];
+        let view = &state.get_views()[&username1];
+        let actions: Vec<_> = view
+            .recent_actions
+            .iter()
+            .map(|record| (record.seat, record.action.clone()))
+            .collect();
+        assert_eq!(actions, expected);
+        assert!(
+            view.recent_actions
+                .windows(2)
+                .all(|pair| pair[0].pot_size <= pair[1].pot_size)
+        );
+        assert_eq!(view.recent_actions.last().unwrap().pot_size, view.pot.size);
+
+        // The history carries over to the flop, but each street is capped.
+        state = state.step();
+        state = state.step();
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        assert!(state.take_action(&username0, Action::Check).is_ok());
+        assert_eq!(state.get_views()[&username0].recent_actions.len(), 4);
+        if let PokerState::TakeAction(game) = &mut state {
+            for _ in 0..MAX_RECENT_ACTIONS_PER_STREET {
+                game.record_recent_action(0, Action::Check);
+            }
+        }
+        let recent_actions = Arc::clone(&state.get_views()[&username0].recent_actions);
+        assert_eq!(recent_actions.len(), 3 + MAX_RECENT_ACTIONS_PER_STREET);
+        assert_eq!(
+            recent_actions[..3]
+                .iter()
+                .map(|record| (record.seat, record.action.clone()))
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        // Cleared when the next hand is dealt.
+        while !matches!(state, PokerState::Lobby(_)) {
+            state = state.step();
+        }
+        assert_eq!(state.init_start(&username0), Ok(()));
+        while !matches!(state, PokerState::TakeAction(_)) {
+            state = state.step();
+        }
+        assert!(state.get_views()[&username0].recent_actions.is_empty());
+    }
+
+    #[test]
+    fn hand_history_records_scripted_hand() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        assert!(state.take_action(&username0, Action::Call).is_ok());
+        assert!(state.take_action(&username1, Action::Call).is_ok());
+        assert!(state.take_action(&username2, Action::Check).is_ok());
+        state = state.step();
+        state = state.step();
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        assert!(state.take_action(&username0, Action::Check).is_ok());
+        assert!(state.take_action(&username1, Action::Raise(None)).is_ok());
+        assert!(state.take_action(&username2, Action::Fold).is_ok());
+        assert!(state.take_action(&username0, Action::Fold).is_ok());
+        while !matches!(state, PokerState::BootPlayers(_)) {
+            state = state.step();
+        }
+        assert!(state.take_hand_history().is_none());
+        state = state.step();
+        assert!(matches!(state, PokerState::Lobby(_)));
+
+        let history = state.take_hand_history().unwrap();
+        assert!(state.take_hand_history().is_none());
+        let json = serde_json::to_value(&history).unwrap();
+        let actions: Vec<_> = json["actions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|a| {
+                (
+                    a["street"].as_str().unwrap(),
+                    a["username"].as_str().unwrap(),
+                    a["action"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("preflop", "0", "call"),
+                ("preflop", "1", "call"),
+                ("preflop", "2", "check"),
+                ("flop", "0", "check"),
+                ("flop", "1", "raise"),
+                ("flop", "2", "fold"),
+                ("flop", "0", "fold"),
+            ]
         );
+        assert_eq!(json["actions"][0]["amount"], 10);
+        assert_eq!(json["actions"][1]["amount"], 5);
+        assert_eq!(json["actions"][2]["amount"], 0);
+
+        // Nobody showed, so no hole cards are revealed.
+        let seats = json["seats"].as_array().unwrap();
+        assert_eq!(seats.len(), 3);
+        assert!(seats.iter().all(|s| s["hole_cards"].is_null()));
+
+        // The blinds were posted before the preflop calls.
+        let forced_bets: Vec<_> = json["forced_bets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|b| {
+                (
+                    b["username"].as_str().unwrap(),
+                    b["amount"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(forced_bets, vec![("1", 5), ("2", 10)]);
+
+        // Only the flop was dealt.
+        let board = json["board"].as_array().unwrap();
+        assert_eq!(board.len(), 1);
+        assert_eq!(board[0]["street"], "flop");
+        assert_eq!(board[0]["cards"].as_array().unwrap().len(), 3);
+        assert!(json.get("second_board").is_none());
+
+        // The hand didn't go to showdown and the raiser took the pot.
+        assert!(json["showdown"].as_array().unwrap().is_empty());
+        let awards = json["pot_awards"].as_array().unwrap();
+        assert_eq!(awards.len(), 1);
+        assert_eq!(awards[0]["username"], "1");
     }
 
     #[test]
@@ -2815,6 +5721,37 @@ mod state_tests {
         assert_eq!(state.init_start(&username0), Ok(()));
     }
 
+    #[test]
+    fn queued_blinds_apply_between_hands() {
+        let mut state = init_state();
+        let username0 = Username::new("0");
+        let blinds = Blinds {
+            small: 10,
+            big: 20,
+            ante: 5,
+        };
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        let old_blinds = state.get_views()[&username0].blinds.clone();
+        state.queue_blinds(blinds.clone());
+
+        // The hand in progress finishes with the old blinds.
+        while !matches!(state, PokerState::UpdateBlinds(_)) {
+            state = state.step();
+            assert_eq!(state.get_views()[&username0].blinds, old_blinds);
+        }
+        state = state.step();
+        assert_eq!(*state.get_views()[&username0].blinds, blinds);
+        assert!(
+            state
+                .drain_events()
+                .contains(&GameEvent::BlindsIncreased(blinds))
+        );
+    }
+
     #[test]
     fn early_showdown_1_winner_2_folds() {
         let mut state = init_state();
@@ -2992,4 +5929,548 @@ mod state_tests {
         state = state.step();
         assert!(matches!(state, PokerState::Deal(_)));
     }
+
+    /// Play a game to completion, force folding everyone that has to act.
+    fn play_game(mut state: PokerState) -> PokerState {
+        let username0 = Username::new("0");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        state = state.step();
+        while !matches!(state, PokerState::Lobby(_)) {
+            state = state.step();
+        }
+        state
+    }
+
+    #[test]
+    fn sitting_out_player_is_dealt_out() {
+        let mut state = play_game(init_state());
+        let username0 = Username::new("0");
+        let username2 = Username::new("2");
+        assert_eq!(state.set_sitting_out(&username2, true), Ok(()));
+        assert_eq!(
+            state.set_sitting_out(&username2, true),
+            Err(UserError::UserAlreadySittingOut)
+        );
+        assert!(
+            state
+                .drain_events()
+                .contains(&GameEvent::SatOut(username2.clone()))
+        );
+        let money = state.get_views()[&username2].players[2].user.money;
+
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        let PokerState::TakeAction(ref game) = state else {
+            unreachable!()
+        };
+        let positions = &game.data.play_positions;
+        assert_ne!(positions.small_blind_idx, 2);
+        assert_ne!(positions.big_blind_idx, 2);
+        assert_eq!(game.data.player_counts.num_active, 2);
+        assert_eq!(game.data.players[0].cards.len(), 2);
+        assert_eq!(game.data.players[1].cards.len(), 2);
+        assert!(game.data.players[2].cards.is_empty());
+        assert_eq!(game.data.players[2].user.money, money);
+        assert_ne!(state.get_next_action_username(), Some(username2.clone()));
+
+        // The player keeps their seat while sitting out.
+        let views = state.get_views();
+        assert_eq!(views[&username2].players.len(), 3);
+        assert!(views[&username2].players[2].sitting_out);
+        assert!(!views[&username2].players[0].sitting_out);
+    }
+
+    #[test]
+    fn sit_in_restores_player_next_game() {
+        let mut state = play_game(init_state());
+        let username0 = Username::new("0");
+        let username2 = Username::new("2");
+        assert_eq!(
+            state.set_sitting_out(&username2, false),
+            Err(UserError::UserNotSittingOut)
+        );
+        assert_eq!(state.set_sitting_out(&username2, true), Ok(()));
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+
+        // Sitting back in mid-game doesn't deal the player into the
+        // current game.
+        assert_eq!(state.set_sitting_out(&username2, false), Ok(()));
+        assert!(
+            state
+                .drain_events()
+                .contains(&GameEvent::SatIn(username2.clone()))
+        );
+        let PokerState::TakeAction(ref game) = state else {
+            unreachable!()
+        };
+        assert!(game.data.players[2].cards.is_empty());
+        assert!(!state.get_views()[&username2].players[2].sitting_out);
+
+        while !matches!(state, PokerState::Lobby(_)) {
+            state = state.step();
+        }
+        assert_eq!(state.init_start(&username0), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        let PokerState::TakeAction(ref game) = state else {
+            unreachable!()
+        };
+        assert_eq!(game.data.player_counts.num_active, 3);
+        assert_eq!(game.data.players[2].cards.len(), 2);
+    }
+
+    /// Start a game with the given betting structure and step to the
+    /// first preflop action.
+    fn init_take_action_with_betting_structure(betting_structure: BettingStructure) -> PokerState {
+        let settings = GameSettings {
+            betting_structure,
+            ..Default::default()
+        };
+        let mut state: PokerState = settings.into();
+        for i in 0..3 {
+            let username = i.to_string().into();
+            state.new_user(&username).unwrap();
+            state.waitlist_user(&username).unwrap();
+        }
+        assert_eq!(state.init_start(&Username::new("0")), Ok(()));
+        for _ in 0..5 {
+            state = state.step();
+        }
+        assert!(matches!(state, PokerState::TakeAction(_)));
+        state
+    }
+
+    #[test]
+    fn pot_limit_caps_raise_at_pot_size() {
+        let mut state = init_take_action_with_betting_structure(BettingStructure::PotLimit);
+        let username0 = Username::new("0");
+
+        // Calling 10 makes the pot 25, so the most player 0 can put in
+        // is the 10 call plus a 25 raise.
+        let turn = state.get_turn_info().unwrap();
+        assert_eq!(turn.max_raise, Some(35));
+        let choices = state.get_action_choices().unwrap();
+        assert!(!choices.contains(&Action::AllIn));
+        assert!(choices.contains(&Action::Raise(None)));
+
+        assert!(matches!(
+            state.take_action(&username0, Action::Raise(Some(36))),
+            Err(UserError::InvalidBet { .. })
+        ));
+        assert!(
+            state
+                .take_action(&username0, Action::Raise(Some(35)))
+                .is_ok()
+        );
+    }
+
+    /// Save and reload a state the way a table snapshot does.
+    fn reload(state: &PokerState) -> PokerState {
+        let json = serde_json::to_value(state).unwrap();
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn mid_hand_state_reloads_to_equivalent_state() {
+        let mut state = init_take_action_with_betting_structure(BettingStructure::NoLimit);
+        assert!(
+            state
+                .take_action(&Username::new("0"), Action::Raise(Some(40)))
+                .is_ok()
+        );
+
+        let mut reloaded = reload(&state);
+        assert_eq!(reloaded.to_string(), state.to_string());
+        assert_eq!(reloaded.get_views(), state.get_views());
+        assert_eq!(reloaded.get_turn_info(), state.get_turn_info());
+
+        // Reloading at every phase, including between dealing streets,
+        // plays out the same hand with the same cards.
+        while !matches!(state, PokerState::Lobby(_)) {
+            if let Some(username) = state.get_next_action_username() {
+                let action = if state.get_action_choices().unwrap().contains(&Action::Check) {
+                    Action::Check
+                } else {
+                    Action::Call
+                };
+                assert_eq!(
+                    reloaded.take_action(&username, action.clone()),
+                    state.take_action(&username, action)
+                );
+            }
+            state = state.step();
+            reloaded = reload(&reloaded.step());
+            assert_eq!(reloaded.to_string(), state.to_string());
+            assert_eq!(reloaded.get_views(), state.get_views());
+        }
+        assert_eq!(reloaded.get_settled_stacks(), state.get_settled_stacks());
+    }
+
+    #[test]
+    fn under_min_raise_rejected_with_min() {
+        let mut state = init_take_action_with_betting_structure(BettingStructure::NoLimit);
+        let username0 = Username::new("0");
+
+        assert_eq!(
+            state.take_action(&username0, Action::Raise(Some(15))),
+            Err(UserError::RaiseBelowMinimum { min_raise: 20 })
+        );
+        // The rejected raise doesn't use up the player's turn.
+        assert_eq!(
+            state.take_action(&username0, Action::Raise(Some(20))),
+            Ok(Action::Raise(Some(20)))
+        );
+    }
+
+    #[test]
+    fn all_in_below_min_raise_accepted() {
+        let mut state = init_take_action_with_betting_structure(BettingStructure::NoLimit);
+        let username0 = Username::new("0");
+        let PokerState::TakeAction(game) = &mut state else {
+            unreachable!()
+        };
+        game.data.players[0].user.money = 15;
+
+        // Shoving 15 is less than the 20 minimum raise, but it's all the
+        // player has, whether they ask for an all-in or a raise.
+        assert_eq!(
+            state.take_action(&username0, Action::Raise(Some(15))),
+            Ok(Action::AllIn)
+        );
+    }
+
+    /// Least and most the next player can raise by, if they can raise.
+    fn raise_choice_bounds(state: &PokerState) -> Option<(Usd, Usd)> {
+        state
+            .get_action_choices()?
+            .0
+            .into_iter()
+            .find_map(|choice| match choice {
+                ActionChoice::Raise { min, max } => Some((min, max)),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn raise_choice_bounds_uncapped_and_capped() {
+        // No-limit raises go up to the player's whole stack.
+        let state = init_take_action_with_betting_structure(BettingStructure::NoLimit);
+        assert_eq!(raise_choice_bounds(&state), Some((20, 600)));
+        let turn = state.get_turn_info().unwrap();
+        assert_eq!((turn.min_raise, turn.max_raise), (Some(20), Some(600)));
+
+        // Pot-limit raises are capped at the pot.
+        let state = init_take_action_with_betting_structure(BettingStructure::PotLimit);
+        assert_eq!(raise_choice_bounds(&state), Some((20, 35)));
+        let turn = state.get_turn_info().unwrap();
+        assert_eq!((turn.min_raise, turn.max_raise), (Some(20), Some(35)));
+    }
+
+    #[test]
+    fn under_raise_all_in_doesnt_reopen_raising() {
+        let mut state = init_take_action_with_betting_structure(BettingStructure::NoLimit);
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+        let PokerState::TakeAction(game) = &mut state else {
+            unreachable!()
+        };
+        game.data.players[1].user.money = 45;
+
+        // Player 0 raises to 40, and the small blind goes all-in to 50,
+        // which is short of a full raise.
+        assert!(
+            state
+                .take_action(&username0, Action::Raise(Some(40)))
+                .is_ok()
+        );
+        assert_eq!(
+            state.take_action(&username1, Action::AllIn),
+            Ok(Action::AllIn)
+        );
+
+        // The big blind hasn't acted yet, so they can still raise, but
+        // the all-in doesn't add to the size of their raise: they call
+        // 40 and raise by 40 more.
+        assert_eq!(raise_choice_bounds(&state), Some((80, 590)));
+        assert!(state.take_action(&username2, Action::Call).is_ok());
+
+        // Player 0 already acted on the full raise, so they can only
+        // call the extra 10 or fold.
+        assert_eq!(raise_choice_bounds(&state), None);
+        let choices = state.get_action_choices().unwrap();
+        assert!(!choices.contains(&Action::AllIn));
+        assert!(choices.contains(&Action::Call));
+        assert_eq!(state.get_turn_info().unwrap().min_raise, None);
+        assert_eq!(
+            state.take_action(&username0, Action::Raise(None)),
+            Err(UserError::InvalidAction)
+        );
+        assert!(state.take_action(&username0, Action::Call).is_ok());
+    }
+
+    #[test]
+    fn short_all_in_between_callers_doesnt_reopen_raising() {
+        let mut state = init_take_action_with_betting_structure(BettingStructure::NoLimit);
+        let username0 = Username::new("0");
+        let username2 = Username::new("2");
+        let PokerState::TakeAction(game) = &mut state else {
+            unreachable!()
+        };
+        game.data.players[1].user.money = 10;
+
+        // Player 0 calls the big blind, and the small blind goes all-in
+        // to 15, which is short of a full raise to 20.
+        assert_eq!(
+            state.take_action(&username0, Action::Call),
+            Ok(Action::Call)
+        );
+        assert_eq!(
+            state.take_action(&Username::new("1"), Action::AllIn),
+            Ok(Action::AllIn)
+        );
+
+        // The big blind hasn't acted yet, so they can still raise.
+        assert!(raise_choice_bounds(&state).is_some());
+        assert_eq!(
+            state.take_action(&username2, Action::Call),
+            Ok(Action::Call)
+        );
+
+        // Player 0 already called, so they can only call the extra 5 or fold.
+        assert_eq!(
+            state.get_action_choices(),
+            Some([ActionChoice::Call(5), ActionChoice::Fold].into())
+        );
+        assert_eq!(
+            state.take_action(&username0, Action::Raise(None)),
+            Err(UserError::InvalidAction)
+        );
+        assert_eq!(
+            state.take_action(&username0, Action::Call),
+            Ok(Action::Call)
+        );
+    }
+
+    #[test]
+    fn fixed_limit_street_stops_raising_at_cap() {
+        let mut state = init_take_action_with_betting_structure(BettingStructure::FixedLimit {
+            small_bet: 10,
+            big_bet: 20,
+        });
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        let username2 = Username::new("2");
+
+        // Only the fixed small bet is a legal raise.
+        assert!(matches!(
+            state.take_action(&username0, Action::Raise(Some(30))),
+            Err(UserError::InvalidBet { .. })
+        ));
+        // The big blind counts as the first bet, so three raises cap
+        // the street.
+        assert!(
+            state
+                .take_action(&username0, Action::Raise(Some(20)))
+                .is_ok()
+        );
+        assert!(state.take_action(&username1, Action::Raise(None)).is_ok());
+        assert!(state.take_action(&username2, Action::Raise(None)).is_ok());
+
+        let choices = state.get_action_choices().unwrap();
+        assert!(!choices.contains(&Action::Raise(None)));
+        assert!(!choices.contains(&Action::AllIn));
+        assert!(choices.contains(&Action::Call));
+        assert_eq!(
+            state.take_action(&username0, Action::Raise(None)),
+            Err(UserError::InvalidAction)
+        );
+        assert!(state.take_action(&username0, Action::Call).is_ok());
+    }
+
+    #[test]
+    fn sitting_out_too_long_kicks_player() {
+        let settings = GameSettings {
+            max_sit_out_hands: 1,
+            ..Default::default()
+        };
+        let mut state: PokerState = settings.into();
+        for i in 0..3 {
+            let username = i.to_string().into();
+            state.new_user(&username).unwrap();
+            state.waitlist_user(&username).unwrap();
+        }
+        state = play_game(state);
+        let username2 = Username::new("2");
+        assert_eq!(state.set_sitting_out(&username2, true), Ok(()));
+
+        // Dealt out of one game, and then kicked before the next.
+        state = play_game(state);
+        assert!(state.get_views().contains_key(&username2));
+        state.drain_events();
+        state = play_game(state);
+        assert!(!state.get_views().contains_key(&username2));
+        assert!(state.drain_events().contains(&GameEvent::Kicked(username2)));
+    }
+
+    /// Button seat, small blind (if it isn't dead), and big blind of the
+    /// hand in progress.
+    fn blind_positions(state: &PokerState) -> (Option<SeatIndex>, Option<Username>, Username) {
+        let views = state.get_views();
+        let view = views.values().next().unwrap();
+        let positions = &view.play_positions;
+        let username = |player_idx: usize| view.players[player_idx].user.name.clone();
+        let small_blind =
+            (!positions.dead_small_blind).then(|| username(positions.small_blind_idx));
+        (
+            positions.button_seat,
+            small_blind,
+            username(positions.big_blind_idx),
+        )
+    }
+
+    /// Play a hand with `num_users` players, have `leaving` leave the
+    /// table, and deal the next hand.
+    fn deal_after_leaving(num_users: usize, leaving: &str) -> PokerState {
+        let mut state = PokerState::new();
+        for i in 0..num_users {
+            let username = i.to_string().into();
+            state.new_user(&username).unwrap();
+            state.waitlist_user(&username).unwrap();
+        }
+        let username0 = Username::new("0");
+        assert_eq!(state.init_start(&username0), Ok(()));
+        while !matches!(state, PokerState::TakeAction(_)) {
+            state = state.step();
+        }
+        assert_eq!(
+            blind_positions(&state),
+            (Some(0), Some(Username::new("1")), Username::new("2"))
+        );
+        while !matches!(state, PokerState::Lobby(_)) {
+            state = state.step();
+        }
+
+        let leaving = Username::new(leaving);
+        assert_eq!(state.remove_user(&leaving), Ok(Some(true)));
+        let username = (0..num_users)
+            .map(|i| Username::new(&i.to_string()))
+            .find(|username| username != &leaving)
+            .unwrap();
+        assert_eq!(state.init_start(&username), Ok(()));
+        while !matches!(state, PokerState::TakeAction(_)) {
+            state = state.step();
+        }
+        state
+    }
+
+    #[test]
+    fn button_leaving_moves_blinds_by_seat() {
+        let state = deal_after_leaving(4, "0");
+        assert_eq!(
+            blind_positions(&state),
+            (Some(1), Some(Username::new("2")), Username::new("3"))
+        );
+    }
+
+    #[test]
+    fn small_blind_leaving_leaves_dead_button() {
+        // The button stays on the empty seat the small blind left.
+        let state = deal_after_leaving(4, "1");
+        assert_eq!(
+            blind_positions(&state),
+            (Some(1), Some(Username::new("2")), Username::new("3"))
+        );
+    }
+
+    #[test]
+    fn big_blind_leaving_leaves_dead_small_blind() {
+        // No one owes the small blind, and the big blind isn't skipped.
+        let state = deal_after_leaving(4, "2");
+        assert_eq!(blind_positions(&state), (Some(1), None, Username::new("3")));
+        let views = state.get_views();
+        let view = views.values().next().unwrap();
+        assert_eq!(view.pot.size, view.blinds.big);
+    }
+
+    #[test]
+    fn heads_up_after_big_blind_leaves() {
+        // The button posts the small blind heads-up, and the big blind
+        // still moves forward.
+        let state = deal_after_leaving(3, "2");
+        assert_eq!(
+            blind_positions(&state),
+            (Some(1), Some(Username::new("1")), Username::new("0"))
+        );
+    }
+
+    #[test]
+    fn heads_up_after_small_blind_leaves() {
+        // Last hand's big blind doesn't post it again.
+        let state = deal_after_leaving(3, "1");
+        assert_eq!(
+            blind_positions(&state),
+            (Some(2), Some(Username::new("2")), Username::new("0"))
+        );
+    }
+
+    #[test]
+    fn heads_up_button_acts_first_preflop_and_last_postflop() {
+        let mut state = PokerState::new();
+        let username0 = Username::new("0");
+        let username1 = Username::new("1");
+        for username in [&username0, &username1] {
+            state.new_user(username).unwrap();
+            state.waitlist_user(username).unwrap();
+        }
+        assert_eq!(state.init_start(&username0), Ok(()));
+        while !matches!(state, PokerState::TakeAction(_)) {
+            state = state.step();
+        }
+        // The button posts the small blind and acts first preflop.
+        assert_eq!(
+            blind_positions(&state),
+            (Some(1), Some(username1.clone()), username0.clone())
+        );
+        assert_eq!(state.get_next_action_username(), Some(username1.clone()));
+        assert_eq!(
+            state.take_action(&username1, Action::Call),
+            Ok(Action::Call)
+        );
+        assert_eq!(
+            state.take_action(&username0, Action::Check),
+            Ok(Action::Check)
+        );
+        state = state.step();
+        assert!(matches!(state, PokerState::Flop(_)));
+        state = state.step();
+        assert!(matches!(state, PokerState::TakeAction(_)));
+
+        // The big blind acts first on every street after.
+        for street in 0..3 {
+            assert_eq!(state.get_next_action_username(), Some(username0.clone()));
+            assert_eq!(
+                state.take_action(&username0, Action::Check),
+                Ok(Action::Check)
+            );
+            assert_eq!(
+                state.take_action(&username1, Action::Check),
+                Ok(Action::Check)
+            );
+            if street < 2 {
+                state = state.step();
+                state = state.step();
+                assert!(matches!(state, PokerState::TakeAction(_)));
+            }
+        }
+    }
 }
@@ -4,7 +4,6 @@
 //! It provides the state management, user management traits, and game data structures.
 
 use enum_dispatch::enum_dispatch;
-use log::error;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet, VecDeque},
@@ -13,20 +12,27 @@ use std::{
 };
 use thiserror::Error;
 
-use super::constants::{DEFAULT_MAX_USERS, MAX_PLAYERS};
+use super::constants::{DEFAULT_MAX_SIT_OUT_HANDS, DEFAULT_MAX_USERS, MAX_PLAYERS};
 use super::entities::{
-    Bet, Blinds, Card, DEFAULT_BUY_IN, DEFAULT_MIN_BIG_BLIND, DEFAULT_MIN_SMALL_BLIND, Deck,
-    GameViews, PlayPositions, Player, PlayerCounts, PlayerQueues, Pot, PotView, SeatIndex, Usd,
+    Action, Bet, BettingStructure, Blinds, Card, DEFAULT_BUY_IN, DEFAULT_MIN_BIG_BLIND,
+    DEFAULT_MIN_SMALL_BLIND, Deck, GameVariant, GameViews, HandHistory, PlayPositions, Player,
+    PlayerCounts, PlayerQueues, Pot, PotBreakdown, PotView, RecentAction, SeatIndex, Street, Usd,
     User, Username, Vote,
 };
 
 /// Errors that can occur during user operations
-#[derive(Debug, Deserialize, Eq, Error, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, Error, PartialEq, Serialize)]
 pub enum UserError {
+    #[error("can't muck hand")]
+    CannotMuckHand,
     #[error("can't show hand")]
     CannotShowHand,
     #[error("can't start unless you're waitlisted or a player")]
     CannotStartGame,
+    #[error("nothing to rabbit hunt")]
+    CannotRabbitHunt,
+    #[error("can't chop the pot by equity now")]
+    CannotOfferEquityChop,
     #[error("can't vote on yourself")]
     CannotVoteOnSelf,
     #[error("game is full")]
@@ -41,10 +47,20 @@ pub enum UserError {
     InvalidAction,
     #[error("illegal {bet}")]
     InvalidBet { bet: Bet },
+    #[error("no equity chop on offer")]
+    NoEquityChopOffer,
     #[error("need 2+ players")]
     NotEnoughPlayers,
+    #[error("raise below minimum of ${min_raise}")]
+    RaiseBelowMinimum { min_raise: Usd },
     #[error("not your turn")]
     OutOfTurnAction,
+    #[error("seat isn't available")]
+    SeatUnavailable,
+    #[error("running it twice isn't allowed")]
+    RunItTwiceNotAllowed,
+    #[error("straddles aren't allowed")]
+    StraddleNotAllowed,
     #[error("user already exists")]
     UserAlreadyExists,
     #[error("user does not exist")]
@@ -53,6 +69,12 @@ pub enum UserError {
     UserNotPlaying,
     #[error("already showing hand")]
     UserAlreadyShowingHand,
+    #[error("already mucked hand")]
+    UserAlreadyMuckedHand,
+    #[error("already sitting out")]
+    UserAlreadySittingOut,
+    #[error("not sitting out")]
+    UserNotSittingOut,
     #[error("invalid game state: waitlist index {0} out of bounds")]
     InvalidWaitlistIndex(usize),
     #[error("invalid game state: player index {0} out of bounds")]
@@ -82,6 +104,23 @@ pub enum GameEvent {
     PassedVote(Vote),
     SplitPot(Username, Usd),
     JoinedTable(Username),
+    TimedOut(Username, Action),
+    SeatChangeQueue(Username, SeatIndex),
+    ChangedSeat(Username, SeatIndex),
+    RunItTwice,
+    EquityChop,
+    SatOut(Username),
+    SatIn(Username),
+    BlindsIncreased(Blinds),
+    ButtonMoved(SeatIndex),
+    PostedBlind(Username, Usd),
+    DealtBoard(Street, Vec<Card>),
+    Acted(Username, Action),
+    ShowedHand(Username, Vec<Card>),
+    /// Players in the order they reveal their hands at showdown.
+    ShowdownOrder(Vec<Username>),
+    MuckedHand(Username),
+    PotBreakdown(PotBreakdown),
 }
 
 impl fmt::Display for GameEvent {
@@ -109,19 +148,72 @@ impl fmt::Display for GameEvent {
             Self::PassedVote(vote) => format!("vote to {vote} passed"),
             Self::SplitPot(username, amount) => format!("{username} won ${amount}"),
             Self::JoinedTable(username) => format!("{username} joined the table"),
+            Self::TimedOut(username, action) => {
+                format!("{username} ran out of time and {action}")
+            }
+            Self::SeatChangeQueue(username, seat_idx) => {
+                format!("{username} will move to seat {seat_idx} after the game")
+            }
+            Self::ChangedSeat(username, seat_idx) => {
+                format!("{username} moved to seat {seat_idx}")
+            }
+            Self::RunItTwice => "running it twice".to_string(),
+            Self::EquityChop => "chopping the pot by equity".to_string(),
+            Self::SatOut(username) => format!("{username} is sitting out"),
+            Self::SatIn(username) => format!("{username} will be dealt in next game"),
+            Self::BlindsIncreased(blinds) => format!("blinds are now {blinds}"),
+            Self::ButtonMoved(seat_idx) => format!("button moves to seat {seat_idx}"),
+            Self::PostedBlind(username, amount) => format!("{username} posts a ${amount} blind"),
+            Self::DealtBoard(street, cards) => format!("{street}: {}", fmt_cards(cards)),
+            Self::Acted(username, action) => format!("{username} {action}"),
+            Self::ShowedHand(username, cards) => format!("{username} shows {}", fmt_cards(cards)),
+            Self::ShowdownOrder(usernames) => {
+                let usernames: Vec<_> = usernames.iter().map(Username::to_string).collect();
+                format!("showdown order: {}", usernames.join(", "))
+            }
+            Self::MuckedHand(username) => format!("{username} mucks"),
+            Self::PotBreakdown(breakdown) => breakdown.to_string(),
         };
         write!(f, "{repr}")
     }
 }
 
+/// Format cards in shorthand (e.g., "As Kh 2d") for event messages.
+fn fmt_cards(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(Card::to_shorthand)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Game configuration settings
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GameSettings {
     pub buy_in: Usd,
     pub min_small_blind: Usd,
     pub min_big_blind: Usd,
+    /// Ante collected from every player before the blinds. Zero
+    /// disables antes.
+    pub min_ante: Usd,
     pub max_users: usize,
     pub max_players: usize,
+    /// Whether the player under the gun can post a straddle.
+    pub straddle_allowed: bool,
+    /// Whether all-in pots can be run twice if every player in the
+    /// pot agrees to it.
+    pub run_it_twice_allowed: bool,
+    /// Poker variant being played, deciding the deck and hand rankings.
+    pub variant: GameVariant,
+    /// Limits on how much players can bet and raise.
+    pub betting_structure: BettingStructure,
+    /// Rake taken from pots that see a flop, in basis points of the pot.
+    pub rake_bps: u32,
+    /// Most rake taken from a single hand. Zero leaves the rake uncapped.
+    pub rake_cap: Usd,
+    /// Hands a player can sit out before they're kicked from the game.
+    /// Zero lets players sit out indefinitely.
+    pub max_sit_out_hands: usize,
 }
 
 impl Default for GameSettings {
@@ -149,14 +241,49 @@ impl GameSettings {
             buy_in,
             min_small_blind,
             min_big_blind,
+            min_ante: 0,
             max_users,
             max_players,
+            straddle_allowed: false,
+            run_it_twice_allowed: false,
+            variant: GameVariant::TexasHoldem,
+            betting_structure: BettingStructure::NoLimit,
+            rake_bps: 0,
+            rake_cap: 0,
+            max_sit_out_hands: DEFAULT_MAX_SIT_OUT_HANDS,
         }
     }
 }
 
+/// Serializes the running votes as a list of pairs, since votes can't be
+/// used as keys in JSON objects.
+mod vote_pairs {
+    use super::{HashMap, HashSet, Username, Vote};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(
+        votes: &HashMap<Vote, HashSet<Username>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(votes.iter())
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<HashMap<Vote, HashSet<Username>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(Vote, HashSet<Username>)>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
+
 /// Mutable game data shared across all states
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct GameData {
     /// Deck of cards. This is instantiated once and reshuffled
     /// each deal.
@@ -168,10 +295,60 @@ pub struct GameData {
     pub players: Vec<Player>,
     /// Community cards shared amongst all players.
     pub board: Vec<Card>,
+    /// Second runout of the community cards when running it twice.
+    /// Shares the cards that were on the board before the all-in with
+    /// `board`, and the rest are dealt from the same deck.
+    pub second_board: Vec<Card>,
+    /// Whether the remaining community cards are being dealt twice.
+    pub(super) run_it_twice: bool,
+    /// Users that agree to run it twice if they're all-in.
+    pub(super) run_it_twice_consent: HashSet<Username>,
+    /// Each remaining player's share of the pot when an equity chop was
+    /// offered, computed when the offer was made. Empty if there's no
+    /// offer.
+    pub(super) equity_chop: HashMap<Username, f64>,
+    /// Players that accepted the equity chop that's on offer.
+    pub(super) equity_chop_accepted: HashSet<Username>,
+    /// Community cards that would've completed the board of the last
+    /// hand if it hadn't ended early.
+    pub(super) rabbit_hunt_cards: Vec<Card>,
+    /// Players that are sitting out, mapped to the number of hands
+    /// they've been dealt out of.
+    pub(super) sitting_out: HashMap<Username, usize>,
+    /// Record of the hand that's currently being played.
+    pub(super) hand_history: Option<HandHistory>,
+    /// Record of the last hand that was played, waiting to be taken
+    /// by the table.
+    pub(super) completed_hand_history: Option<HandHistory>,
+    /// Actions taken so far this hand, shown in game views.
+    pub(super) recent_actions: Vec<RecentAction>,
+    /// Index in `recent_actions` where the current street's actions
+    /// start.
+    pub(super) street_actions_start: usize,
+    /// Bets and raises made on the current street, counting the big
+    /// blind as the first bet preflop. Caps raises in fixed-limit games.
+    pub(super) num_street_bets: usize,
+    /// How much all-ins that were too small to be full raises added to
+    /// the bet to call since the last full bet or raise on this street.
+    /// They don't count towards the size of the next raise.
+    pub(super) under_raise: Usd,
+    /// Players that acted since the last full bet or raise on this
+    /// street. An all-in that's too small to be a full raise doesn't
+    /// reopen the betting for them, so they can only call or fold.
+    pub(super) raise_closed: HashSet<usize>,
+    /// The player that made the last bet or raise in the latest betting
+    /// round, who shows first at showdown.
+    pub(super) last_aggressor: Option<usize>,
+    /// Players still in the hand, in the order they reveal their hands
+    /// at showdown. Empty once the hands are revealed.
+    pub(super) showdown_order: Vec<usize>,
     /// Mapping of running votes to users that are for those running votes.
+    #[serde(with = "vote_pairs")]
     pub(super) votes: HashMap<Vote, HashSet<Username>>,
     pub(super) player_counts: PlayerCounts,
     pub pot: Pot,
+    /// Rake taken from the pot of the current hand.
+    pub(super) rake: Usd,
     /// Queues of players to do things with at a later point of
     /// an active game.
     pub(super) player_queues: PlayerQueues,
@@ -189,6 +366,9 @@ pub struct GameData {
     /// money, but a game was in progress, so everyone's money
     /// will be reset after the game is over.
     pub(super) reset_all_money_after_game: bool,
+    /// Minimum blinds from the table's blind schedule that take effect
+    /// once the hand in progress is over.
+    pub(super) next_blinds: Option<Blinds>,
     pub(super) settings: GameSettings,
 }
 
@@ -209,24 +389,43 @@ impl GameData {
 impl From<GameSettings> for GameData {
     fn from(value: GameSettings) -> Self {
         Self {
-            deck: Deck::default(),
+            deck: Deck::new(value.variant),
             blinds: Blinds {
                 small: value.min_small_blind,
                 big: value.min_big_blind,
+                ante: value.min_ante,
             },
             spectators: HashSet::with_capacity(value.max_users),
             waitlist: VecDeque::with_capacity(value.max_users),
             open_seats: VecDeque::from_iter(0..value.max_players),
             players: Vec::with_capacity(value.max_players),
             board: Vec::with_capacity(5),
+            second_board: Vec::with_capacity(5),
+            run_it_twice: false,
+            run_it_twice_consent: HashSet::with_capacity(value.max_users),
+            equity_chop: HashMap::new(),
+            equity_chop_accepted: HashSet::new(),
+            rabbit_hunt_cards: Vec::with_capacity(5),
+            sitting_out: HashMap::with_capacity(value.max_players),
+            hand_history: None,
+            completed_hand_history: None,
+            recent_actions: Vec::new(),
+            street_actions_start: 0,
+            num_street_bets: 0,
+            under_raise: 0,
+            raise_closed: HashSet::new(),
+            last_aggressor: None,
+            showdown_order: Vec::new(),
             votes: HashMap::with_capacity(2 * value.max_users + 1),
             player_counts: PlayerCounts::default(),
             pot: Pot::new(value.max_players),
+            rake: 0,
             player_queues: PlayerQueues::default(),
             play_positions: PlayPositions::default(),
             events: VecDeque::new(),
             ledger: HashMap::with_capacity(value.max_users),
             reset_all_money_after_game: false,
+            next_blinds: None,
             settings: value,
         }
     }
@@ -281,6 +480,8 @@ pub struct SharedViewData {
     pub(super) waitlist: Arc<VecDeque<User>>,
     pub(super) open_seats: Arc<VecDeque<usize>>,
     pub(super) board: Arc<Vec<Card>>,
+    pub(super) second_board: Arc<Vec<Card>>,
     pub(super) pot: Arc<PotView>,
     pub(super) play_positions: Arc<PlayPositions>,
+    pub(super) recent_actions: Arc<Vec<RecentAction>>,
 }
@@ -58,7 +58,7 @@ pub use wallet::{
 pub mod table;
 pub use table::{
     BotDifficulty, TableActor, TableConfig, TableHandle, TableManager, TableMessage, TableMetadata,
-    TableResponse, TableSpeed, TableStateResponse,
+    TablePage, TableResponse, TableSpeed, TableStateResponse,
 };
 
 /// Bot components for automatic bot players with difficulty presets.
@@ -68,13 +68,22 @@ pub use bot::{
     DifficultyParams,
 };
 
-/// Security components for rate limiting and anti-collusion detection.
+/// Security components for rate limiting, anti-collusion detection, and audit logging.
 pub mod security;
 pub use security::{
-    AntiCollusionDetector, CollusionFlag, FlagSeverity, FlagType, IpTableRestrictions,
-    RateLimitConfig, RateLimitResult, RateLimiter, SeatRandomizer,
+    AntiCollusionDetector, AuditAction, AuditEntry, AuditFilter, AuditLog, CollusionFlag,
+    FlagSeverity, FlagType, IpTableRestrictions, RateLimitConfig, RateLimitResult, RateLimiter,
+    SeatRandomizer,
 };
 
+/// Player statistics components for VPIP, PFR, and win-rate tracking.
+pub mod stats;
+pub use stats::{PlayerStats, PlayerStatsSummary, StatsCounters, StatsRepository};
+
+/// Player notes components for private notes on opponents.
+pub mod notes;
+pub use notes::{NotesError, NotesRepository};
+
 /// Tournament components for Sit-n-Go and scheduled tournaments.
 pub mod tournament;
 pub use tournament::{
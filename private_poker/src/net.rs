@@ -6,6 +6,9 @@
 /// TCP client for connecting to a poker server.
 pub mod client;
 
+/// Optional zstd compression for large serialized messages.
+pub mod compression;
+
 /// Network error types for serialization and protocol operations.
 pub mod errors;
 
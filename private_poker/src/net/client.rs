@@ -189,6 +189,15 @@ impl Client {
         Ok(())
     }
 
+    pub fn muck_hand(&mut self) -> Result<(), Error> {
+        let msg = ClientMessage {
+            username: self.username.clone(),
+            command: UserCommand::Muck,
+        };
+        utils::write_prefixed(&mut self.stream, &msg)?;
+        Ok(())
+    }
+
     pub fn start_game(&mut self) -> Result<(), Error> {
         let msg = ClientMessage {
             username: self.username.clone(),
@@ -0,0 +1,136 @@
+//! Optional zstd compression for serialized server messages.
+//!
+//! A compressed frame is the serialized payload prefixed with a one-byte
+//! marker saying whether the rest of the frame is zstd-compressed. Small
+//! payloads aren't worth compressing, so they're sent as-is behind the
+//! uncompressed marker.
+
+use std::io;
+
+/// Marker for a frame holding the payload as-is.
+pub const UNCOMPRESSED_MARKER: u8 = 0;
+
+/// Marker for a frame holding the zstd-compressed payload.
+pub const ZSTD_MARKER: u8 = 1;
+
+/// Payloads smaller than this many bytes aren't compressed, since the
+/// zstd frame overhead outweighs the savings.
+pub const COMPRESSION_THRESHOLD: usize = 1024;
+
+/// Maximum size of a decompressed payload (1MB), to prevent decompression
+/// bombs.
+const MAX_DECOMPRESSED_SIZE: usize = 1024 * 1024;
+
+/// zstd compression level, favoring speed since messages are sent often.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Wrap a serialized payload in a frame, compressing it if it's at least
+/// `COMPRESSION_THRESHOLD` bytes and compression actually shrinks it.
+pub fn compress(payload: &[u8]) -> io::Result<Vec<u8>> {
+    if payload.len() >= COMPRESSION_THRESHOLD {
+        let compressed = zstd::bulk::compress(payload, ZSTD_LEVEL)?;
+        if compressed.len() < payload.len() {
+            let mut frame = Vec::with_capacity(compressed.len() + 1);
+            frame.push(ZSTD_MARKER);
+            frame.extend_from_slice(&compressed);
+            return Ok(frame);
+        }
+    }
+    let mut frame = Vec::with_capacity(payload.len() + 1);
+    frame.push(UNCOMPRESSED_MARKER);
+    frame.extend_from_slice(payload);
+    Ok(frame)
+}
+
+/// Unwrap the payload from a frame made by `compress`.
+pub fn decompress(frame: &[u8]) -> io::Result<Vec<u8>> {
+    match frame.split_first() {
+        Some((&UNCOMPRESSED_MARKER, payload)) => Ok(payload.to_vec()),
+        Some((&ZSTD_MARKER, compressed)) => {
+            zstd::bulk::decompress(compressed, MAX_DECOMPRESSED_SIZE)
+        }
+        Some((marker, _)) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unknown compression marker {marker}"),
+        )),
+        None => Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "missing compression marker",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::messages::ServerMessage;
+    use crate::wallet::{EntryDirection, EntryType, WalletEntry};
+    use chrono::Utc;
+
+    #[test]
+    fn test_small_payload_is_not_compressed() {
+        let payload = serde_json::to_vec(&ServerMessage::LogoutSuccess).unwrap();
+        let frame = compress(&payload).unwrap();
+        assert_eq!(frame[0], UNCOMPRESSED_MARKER);
+        assert_eq!(frame.len(), payload.len() + 1);
+        assert_eq!(decompress(&frame).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_incompressible_payload_is_not_compressed() {
+        let payload: Vec<u8> = (0..COMPRESSION_THRESHOLD * 2)
+            .map(|_| rand::random())
+            .collect();
+        let frame = compress(&payload).unwrap();
+        assert_eq!(frame[0], UNCOMPRESSED_MARKER);
+        assert_eq!(decompress(&frame).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_invalid_frames() {
+        assert_eq!(
+            decompress(&[]).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(
+            decompress(&[2, 1, 2, 3]).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(decompress(&[ZSTD_MARKER, 1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_large_transaction_history_shrinks() {
+        let entries: Vec<WalletEntry> = (0..500)
+            .map(|id| WalletEntry {
+                id,
+                user_id: 42,
+                table_id: Some(id % 4),
+                amount: 100 + id,
+                balance_after: 10_000 - id,
+                direction: if id % 2 == 0 {
+                    EntryDirection::Debit
+                } else {
+                    EntryDirection::Credit
+                },
+                entry_type: EntryType::BuyIn,
+                idempotency_key: format!("buy_in_42_{id}"),
+                description: Some("Buy-in at table".to_string()),
+                created_at: Utc::now(),
+            })
+            .collect();
+        let message = ServerMessage::TransactionHistory { entries };
+        let payload = serde_json::to_vec(&message).unwrap();
+
+        let frame = compress(&payload).unwrap();
+        let decompressed = decompress(&frame).unwrap();
+
+        assert_eq!(frame[0], ZSTD_MARKER);
+        assert!(frame.len() * 4 < payload.len());
+        assert_eq!(decompressed, payload);
+
+        // The decompressed message is identical to the original.
+        let roundtrip: ServerMessage = serde_json::from_slice(&decompressed).unwrap();
+        assert_eq!(serde_json::to_vec(&roundtrip).unwrap(), payload);
+    }
+}
@@ -3,10 +3,11 @@ use std::fmt;
 
 use super::super::game::{
     GameEvent, UserError,
-    entities::{Action, ActionChoices, GameView, Username, Vote},
+    entities::{Action, GameView, TurnInfo, Username, Vote},
 };
 
 // Import types from other modules
+use super::protocol_version::ProtocolVersion;
 use crate::auth::{SessionTokens, User};
 use crate::table::{TableConfig, TableSpeed};
 use crate::wallet::WalletEntry;
@@ -14,7 +15,7 @@ use chrono::{DateTime, Utc};
 
 /// Errors due to the poker client's interaction with the poker server
 /// and not from the user's particular action.
-#[derive(Debug, Deserialize, Eq, thiserror::Error, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, thiserror::Error, PartialEq, Serialize)]
 pub enum ClientError {
     #[error("already associated")]
     AlreadyAssociated,
@@ -29,14 +30,15 @@ pub enum ClientError {
 /// Table ID type
 pub type TableId = i64;
 
-/// Stakes tier classification based on big blind size
+/// Stakes tier classification based on big blind size. Each tier's upper
+/// bound is inclusive.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StakesTier {
     /// BB ≤ 10
     Micro,
-    /// BB 10-100
+    /// BB 11-100
     Low,
-    /// BB 100-1000
+    /// BB 101-1000
     Mid,
     /// BB > 1000
     High,
@@ -54,16 +56,51 @@ impl StakesTier {
     }
 }
 
+/// Field to sort a table listing by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableSortBy {
+    /// Number of seated players
+    Players,
+    /// Big blind, then small blind
+    Stakes,
+    /// Table name
+    Name,
+}
+
+/// Sort direction for a table listing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
 /// Table filter criteria for discovery
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TableFilter {
     pub stakes_tier: Option<StakesTier>,
     pub min_players: Option<usize>,
     pub max_players: Option<usize>,
+    /// Only tables with an open seat
     pub has_waitlist_space: bool,
     pub speed: Option<TableSpeed>,
     pub bots_enabled: Option<bool>,
+    /// Only private tables
     pub is_private: bool,
+    /// Case-insensitive substring of the table name, or `None` (or an
+    /// empty query) for any name
+    pub name_query: Option<String>,
+    /// Field to sort by, or `None` for newest tables first
+    pub sort_by: Option<TableSortBy>,
+    /// Direction to sort `sort_by` in
+    pub order: SortOrder,
+    /// Number of matching tables to skip
+    pub offset: usize,
+    /// Maximum number of tables to return, or `None` for all of them
+    pub limit: Option<usize>,
 }
 
 /// Table information for discovery/listing
@@ -80,6 +117,33 @@ pub struct TableInfo {
     pub has_invite: bool,
 }
 
+impl TableInfo {
+    /// Build table info from a table's current config. The stakes tier is
+    /// derived from the config's big blind rather than cached, so it follows
+    /// blind changes. The passphrase hash and invite token are left out.
+    pub fn new(
+        id: TableId,
+        mut config: TableConfig,
+        player_count: usize,
+        waitlist_count: usize,
+    ) -> Self {
+        let requires_passphrase = config.passphrase_hash.take().is_some();
+        let has_invite = config.invite_token.take().is_some();
+        config.invite_expires_at = None;
+        Self {
+            id,
+            name: config.name.clone(),
+            player_count,
+            waitlist_count,
+            stakes_tier: StakesTier::from_big_blind(config.big_blind),
+            is_private: config.is_private,
+            requires_passphrase,
+            has_invite,
+            config,
+        }
+    }
+}
+
 /// Type of user state change requests.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum UserState {
@@ -113,6 +177,9 @@ pub enum UserCommand {
     /// a player and the game is in a state that allows hands to
     /// be shown.
     ShowHand,
+    /// User declines to show their hand at showdown. Can only occur
+    /// if they're a player that hasn't folded or already shown.
+    Muck,
     /// User wants to start the game. Can only start a game when
     /// there are 2+ potential players.
     StartGame,
@@ -186,8 +253,12 @@ pub enum UserCommand {
     GetBalance,
     /// Claim daily faucet
     ClaimFaucet,
+    /// Check whether the faucet can be claimed without claiming it
+    GetFaucetStatus,
     /// Get transaction history
     GetTransactionHistory { limit: usize, offset: usize },
+    /// Gift chips from own wallet to another user's wallet
+    GiftChips { to_user_id: i64, amount: i64 },
 
     // === Chat Commands (V2) ===
     /// Send chat message to table
@@ -206,6 +277,14 @@ pub enum UserCommand {
     StartGameAtTable { table_id: TableId },
     /// Show hand at specific table
     ShowHandAtTable { table_id: TableId },
+    /// Sit out of hands at specific table while keeping the seat
+    SitOut { table_id: TableId },
+    /// Sit back into hands at specific table
+    SitIn { table_id: TableId },
+    /// Offer the two players all-in at a table to chop the pot by equity
+    OfferEquityChop { table_id: TableId },
+    /// Accept or decline the equity chop on offer at a table
+    RespondToEquityChop { table_id: TableId, accept: bool },
 
     // === Tournament Commands (V2) ===
     /// Create a new tournament
@@ -224,6 +303,10 @@ pub enum UserCommand {
     GetTournamentInfo { tournament_id: i64 },
     /// Get tournament standings
     GetTournamentStandings { tournament_id: i64 },
+
+    // === Connection Commands (V2) ===
+    /// Reply to a server `Ping` with the same nonce
+    Pong { nonce: u64 },
 }
 
 impl fmt::Display for UserCommand {
@@ -234,6 +317,7 @@ impl fmt::Display for UserCommand {
             Self::Connect => "connected".to_string(),
             Self::Disconnect => "disconnected".to_string(),
             Self::ShowHand => "showed their hand".to_string(),
+            Self::Muck => "mucked their hand".to_string(),
             Self::StartGame => "started the game".to_string(),
             Self::TakeAction(action) => action.to_string(),
             Self::CastVote(vote) => format!("voted to {vote}"),
@@ -269,7 +353,11 @@ impl fmt::Display for UserCommand {
             // Wallet
             Self::GetBalance => "requested balance".to_string(),
             Self::ClaimFaucet => "claimed faucet".to_string(),
+            Self::GetFaucetStatus => "requested faucet status".to_string(),
             Self::GetTransactionHistory { .. } => "requested transaction history".to_string(),
+            Self::GiftChips { to_user_id, amount } => {
+                format!("gifted {} chips to user {}", amount, to_user_id)
+            }
 
             // Chat
             Self::SendChatMessage { table_id, .. } => format!("sent chat to table {}", table_id),
@@ -285,6 +373,15 @@ impl fmt::Display for UserCommand {
             }
             Self::StartGameAtTable { table_id } => format!("started game at table {}", table_id),
             Self::ShowHandAtTable { table_id } => format!("showed hand at table {}", table_id),
+            Self::SitOut { table_id } => format!("sat out at table {}", table_id),
+            Self::SitIn { table_id } => format!("sat in at table {}", table_id),
+            Self::OfferEquityChop { table_id } => {
+                format!("offered an equity chop at table {}", table_id)
+            }
+            Self::RespondToEquityChop { table_id, accept } => {
+                let response = if *accept { "accepted" } else { "declined" };
+                format!("{} the equity chop at table {}", response, table_id)
+            }
 
             // Tournament commands
             Self::CreateTournament { config } => format!("created tournament '{}'", config.name),
@@ -301,6 +398,9 @@ impl fmt::Display for UserCommand {
             Self::GetTournamentStandings { tournament_id } => {
                 format!("requested tournament {} standings", tournament_id)
             }
+
+            // Connection commands
+            Self::Pong { nonce } => format!("ponged {}", nonce),
         };
         write!(f, "{}", repr)
     }
@@ -323,7 +423,7 @@ impl fmt::Display for ClientMessage {
 }
 
 /// A message from the poker server to a poker client.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum ServerMessage {
     // === Legacy Messages (V1) ===
     /// An acknowledgement of a client message, signaling that the client's
@@ -338,8 +438,9 @@ pub enum ServerMessage {
     GameView(GameView),
     /// The game state represented as a string.
     Status(String),
-    /// A signal indicating that it's the user's turn.
-    TurnSignal(ActionChoices),
+    /// A signal indicating that it's the user's turn, with the amounts
+    /// they need to decide on an action.
+    TurnSignal(TurnInfo),
     /// An indication that the poker client sent a message that was read
     /// properly, but the type of action that it relayed was invalid
     /// for the game state, resulting in a user error.
@@ -402,6 +503,12 @@ pub enum ServerMessage {
         amount: i64,
         next_claim: DateTime<Utc>,
     },
+    /// Faucet availability. `next_claim` is set while the faucet is
+    /// cooling down.
+    FaucetStatus {
+        available: bool,
+        next_claim: Option<DateTime<Utc>>,
+    },
     /// Transaction history
     TransactionHistory { entries: Vec<WalletEntry> },
 
@@ -423,14 +530,24 @@ pub enum ServerMessage {
     /// Game view for a specific table
     TableGameView { table_id: TableId, view: GameView },
     /// Turn signal for a specific table
-    TableTurnSignal {
-        table_id: TableId,
-        action_choices: ActionChoices,
-    },
+    TableTurnSignal { table_id: TableId, turn: TurnInfo },
     /// Game event at a specific table
     TableGameEvent { table_id: TableId, event: GameEvent },
     /// Status message for a specific table
     TableStatus { table_id: TableId, message: String },
+    /// The players all-in at a table can chop the pot by these equities,
+    /// each a share of the pot from 0 to 1
+    EquityChopOffer {
+        table_id: TableId,
+        equities: Vec<(String, f64)>,
+    },
+    /// Game events missed while reconnecting to a table, oldest first.
+    /// `complete` is false if some events were too old to replay.
+    MissedEvents {
+        table_id: TableId,
+        events: Vec<GameEvent>,
+        complete: bool,
+    },
 
     // === Tournament Messages (V2) ===
     /// Tournament created
@@ -464,6 +581,8 @@ pub enum ServerMessage {
         user_id: i64,
         position: usize,
         prize: Option<i64>,
+        /// Bounty paid out for the elimination (knockout tournaments only)
+        bounty: Option<i64>,
     },
     /// Tournament error
     TournamentError(String),
@@ -477,6 +596,11 @@ pub enum ServerMessage {
     TableError(String),
     /// Rate limit exceeded
     RateLimitError { retry_after: u64 },
+
+    // === Connection Messages (V2) ===
+    /// Heartbeat the client must answer with a `Pong` carrying the same
+    /// nonce
+    Ping { nonce: u64 },
 }
 
 impl fmt::Display for ServerMessage {
@@ -488,7 +612,7 @@ impl fmt::Display for ServerMessage {
             Self::GameEvent(event) => event.to_string(),
             Self::GameView(_) => "game view".to_string(),
             Self::Status(status) => status.to_string(),
-            Self::TurnSignal(action_choices) => action_choices.to_string(),
+            Self::TurnSignal(turn) => turn.action_choices.to_string(),
             Self::UserError(error) => error.to_string(),
 
             // Auth responses
@@ -535,6 +659,10 @@ impl fmt::Display for ServerMessage {
             // Wallet responses
             Self::Balance { amount, currency } => format!("balance: {} {}", amount, currency),
             Self::FaucetClaimed { amount, .. } => format!("faucet claimed: {} chips", amount),
+            Self::FaucetStatus { next_claim, .. } => match next_claim {
+                Some(next_claim) => format!("faucet available at {}", next_claim),
+                None => "faucet available".to_string(),
+            },
             Self::TransactionHistory { entries } => format!("{} transactions", entries.len()),
 
             // Chat messages
@@ -546,11 +674,8 @@ impl fmt::Display for ServerMessage {
 
             // Multi-table game messages
             Self::TableGameView { table_id, .. } => format!("game view for table {}", table_id),
-            Self::TableTurnSignal {
-                table_id,
-                action_choices,
-            } => {
-                format!("your turn at table {} ({})", table_id, action_choices)
+            Self::TableTurnSignal { table_id, turn } => {
+                format!("your turn at table {} ({})", table_id, turn.action_choices)
             }
             Self::TableGameEvent { table_id, event } => {
                 format!("table {}: {}", table_id, event)
@@ -558,6 +683,26 @@ impl fmt::Display for ServerMessage {
             Self::TableStatus { table_id, message } => {
                 format!("table {}: {}", table_id, message)
             }
+            Self::EquityChopOffer { table_id, equities } => {
+                let shares = equities
+                    .iter()
+                    .map(|(username, equity)| format!("{} {:.1}%", username, 100.0 * equity))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("table {}: equity chop offered ({})", table_id, shares)
+            }
+            Self::MissedEvents {
+                table_id,
+                events,
+                complete,
+            } => {
+                format!(
+                    "table {}: {} missed events{}",
+                    table_id,
+                    events.len(),
+                    if *complete { "" } else { " (some too old)" }
+                )
+            }
 
             // Tournament messages
             Self::TournamentCreated { tournament_id } => {
@@ -611,8 +756,9 @@ impl fmt::Display for ServerMessage {
                 user_id,
                 position,
                 prize,
+                bounty,
             } => {
-                if let Some(amount) = prize {
+                let eliminated = if let Some(amount) = prize {
                     format!(
                         "tournament {}: player {} eliminated ({}{} place, won {})",
                         tournament_id,
@@ -639,6 +785,10 @@ impl fmt::Display for ServerMessage {
                             _ => "th",
                         }
                     )
+                };
+                match bounty {
+                    Some(bounty) => format!("{}, {} bounty collected", eliminated, bounty),
+                    None => eliminated,
                 }
             }
             Self::TournamentError(error) => format!("tournament error: {}", error),
@@ -650,11 +800,185 @@ impl fmt::Display for ServerMessage {
             Self::RateLimitError { retry_after } => {
                 format!("rate limited: retry after {} seconds", retry_after)
             }
+
+            // Connection messages
+            Self::Ping { nonce } => format!("ping {}", nonce),
         };
         write!(f, "{}", repr)
     }
 }
 
+impl ServerMessage {
+    /// Return the oldest protocol version that includes this message.
+    /// Messages newer than a client's negotiated version aren't sent to it.
+    #[must_use]
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        match self {
+            Self::Ack(_)
+            | Self::ClientError(_)
+            | Self::GameEvent(_)
+            | Self::GameView(_)
+            | Self::Status(_)
+            | Self::TurnSignal(_)
+            | Self::UserError(_) => ProtocolVersion::V1,
+            _ => ProtocolVersion::V2,
+        }
+    }
+}
+
+/// Why the server closed a WebSocket connection. Each reason is sent as an
+/// application-defined close code (4000-4999) with a human-readable
+/// reason, so clients can tell whether reconnecting will help.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CloseReason {
+    /// The client didn't start with a `Hello`.
+    HandshakeRequired,
+    /// The client's protocol version isn't supported.
+    UnsupportedVersion,
+    /// The client stopped answering pings.
+    HeartbeatTimeout,
+    /// The access token expired. The client should refresh it and
+    /// reconnect rather than log in again.
+    AuthExpired,
+    /// The access token isn't valid, so the user has to log in again.
+    AuthInvalid,
+    /// The user was removed from the table.
+    Kicked,
+    /// The table closed or doesn't exist.
+    TableClosed,
+}
+
+impl CloseReason {
+    /// Every close reason, in close code order.
+    pub const ALL: [Self; 7] = [
+        Self::HandshakeRequired,
+        Self::UnsupportedVersion,
+        Self::HeartbeatTimeout,
+        Self::AuthExpired,
+        Self::AuthInvalid,
+        Self::Kicked,
+        Self::TableClosed,
+    ];
+
+    /// WebSocket close code sent for this reason.
+    #[must_use]
+    pub const fn code(self) -> u16 {
+        match self {
+            Self::HandshakeRequired => 4000,
+            Self::UnsupportedVersion => 4001,
+            Self::HeartbeatTimeout => 4002,
+            Self::AuthExpired => 4003,
+            Self::AuthInvalid => 4004,
+            Self::Kicked => 4005,
+            Self::TableClosed => 4006,
+        }
+    }
+
+    /// Close reason for a close code, if it's one of ours.
+    #[must_use]
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.into_iter().find(|reason| reason.code() == code)
+    }
+
+    /// Whether reconnecting can succeed. An expired token has to be
+    /// refreshed first.
+    #[must_use]
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::HeartbeatTimeout | Self::AuthExpired)
+    }
+}
+
+impl fmt::Display for CloseReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let repr = match self {
+            Self::HandshakeRequired => "expected hello",
+            Self::UnsupportedVersion => "unsupported protocol version",
+            Self::HeartbeatTimeout => "heartbeat timeout",
+            Self::AuthExpired => "session expired",
+            Self::AuthInvalid => "invalid access token",
+            Self::Kicked => "removed from the table",
+            Self::TableClosed => "table closed",
+        };
+        write!(f, "{}", repr)
+    }
+}
+
+/// Errors from negotiating the protocol version.
+#[derive(Debug, Deserialize, Eq, thiserror::Error, PartialEq, Serialize)]
+pub enum HandshakeError {
+    #[error("protocol version {0} is not supported")]
+    UnsupportedVersion(u32),
+    #[error("server picked protocol version {0:?}, which wasn't offered")]
+    UnexpectedVersion(ProtocolVersion),
+}
+
+/// First message a client sends after connecting, before any game
+/// messages.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Hello {
+    /// Number of the newest protocol version the client understands. It's
+    /// a number rather than a `ProtocolVersion` so clients newer than the
+    /// server can still be downgraded.
+    pub protocol_version: u32,
+    /// Client software version, for logging.
+    pub client_version: String,
+    /// Whether the client can decompress large messages (see
+    /// `net::compression`).
+    #[serde(default)]
+    pub compression: bool,
+}
+
+impl Hello {
+    #[must_use]
+    pub fn new(client_version: &str) -> Self {
+        Self {
+            protocol_version: ProtocolVersion::current().number(),
+            client_version: client_version.to_string(),
+            compression: true,
+        }
+    }
+
+    /// Pick the protocol version to use with the client. Clients newer
+    /// than the server are downgraded to the server's version, and clients
+    /// asking for a version that doesn't exist are rejected.
+    pub fn negotiate(&self, server_version: &str) -> Result<HelloAck, HandshakeError> {
+        let number = self
+            .protocol_version
+            .min(ProtocolVersion::current().number());
+        let accepted_version = ProtocolVersion::from_number(number)
+            .ok_or(HandshakeError::UnsupportedVersion(self.protocol_version))?;
+        Ok(HelloAck {
+            accepted_version,
+            server_version: server_version.to_string(),
+            compression: self.compression,
+        })
+    }
+}
+
+/// Server's reply to a `Hello`, confirming the protocol version both
+/// sides will use.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HelloAck {
+    /// Protocol version the server will use for the connection.
+    pub accepted_version: ProtocolVersion,
+    /// Server software version, for logging.
+    pub server_version: String,
+    /// Whether large messages are sent compressed, as binary frames.
+    #[serde(default)]
+    pub compression: bool,
+}
+
+impl HelloAck {
+    /// Check the server didn't pick a version newer than the `Hello`
+    /// asked for.
+    pub fn verify(&self, hello: &Hello) -> Result<ProtocolVersion, HandshakeError> {
+        if self.accepted_version.number() > hello.protocol_version {
+            return Err(HandshakeError::UnexpectedVersion(self.accepted_version));
+        }
+        Ok(self.accepted_version)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -716,6 +1040,32 @@ mod tests {
         assert_eq!(error, deserialized);
     }
 
+    // === StakesTier Tests ===
+
+    #[test]
+    fn test_stakes_tier_boundaries() {
+        assert_eq!(StakesTier::from_big_blind(10), StakesTier::Micro);
+        assert_eq!(StakesTier::from_big_blind(11), StakesTier::Low);
+        assert_eq!(StakesTier::from_big_blind(100), StakesTier::Low);
+        assert_eq!(StakesTier::from_big_blind(101), StakesTier::Mid);
+        assert_eq!(StakesTier::from_big_blind(1000), StakesTier::Mid);
+        assert_eq!(StakesTier::from_big_blind(1001), StakesTier::High);
+    }
+
+    #[test]
+    fn test_table_info_stakes_tier_follows_big_blind() {
+        let mut config = TableConfig {
+            big_blind: 100,
+            ..Default::default()
+        };
+        assert_eq!(
+            TableInfo::new(1, config.clone(), 0, 0).stakes_tier,
+            StakesTier::Low
+        );
+        config.big_blind = 101;
+        assert_eq!(TableInfo::new(1, config, 0, 0).stakes_tier, StakesTier::Mid);
+    }
+
     // === UserState Tests ===
 
     #[test]
@@ -783,6 +1133,12 @@ mod tests {
         assert_eq!(format!("{}", cmd), "showed their hand");
     }
 
+    #[test]
+    fn test_user_command_muck() {
+        let cmd = UserCommand::Muck;
+        assert_eq!(format!("{}", cmd), "mucked their hand");
+    }
+
     #[test]
     fn test_user_command_start_game() {
         let cmd = UserCommand::StartGame;
@@ -964,6 +1320,20 @@ mod tests {
         let _deserialized: ServerMessage = deserialize_value(&serialized);
     }
 
+    #[test]
+    fn test_ping_pong_serialization() {
+        let ping = ServerMessage::Ping { nonce: 7 };
+        let serialized = serialize_value(&ping);
+        let deserialized: ServerMessage = deserialize_value(&serialized);
+        assert!(matches!(deserialized, ServerMessage::Ping { nonce: 7 }));
+        assert_eq!(ping.protocol_version(), ProtocolVersion::V2);
+
+        let pong = UserCommand::Pong { nonce: 7 };
+        let serialized = serialize_value(&pong);
+        let deserialized: UserCommand = deserialize_value(&serialized);
+        assert_eq!(deserialized, pong);
+    }
+
     #[test]
     fn test_all_client_errors_unique() {
         let errors = [
@@ -986,6 +1356,7 @@ mod tests {
             UserCommand::Connect,
             UserCommand::Disconnect,
             UserCommand::ShowHand,
+            UserCommand::Muck,
             UserCommand::StartGame,
             UserCommand::ChangeState(UserState::Play),
             UserCommand::ChangeState(UserState::Spectate),
@@ -1111,4 +1482,123 @@ mod tests {
             assert_eq!(ser1, ser2, "Same vote should serialize identically");
         }
     }
+
+    // === Handshake Tests ===
+
+    #[test]
+    fn test_handshake_matching_version() {
+        let hello = Hello::new("3.0.1");
+        let ack = hello.negotiate("3.0.1").unwrap();
+        assert_eq!(ack.accepted_version, ProtocolVersion::current());
+        assert_eq!(ack.server_version, "3.0.1");
+        assert!(ack.compression);
+        assert_eq!(ack.verify(&hello), Ok(ProtocolVersion::current()));
+    }
+
+    #[test]
+    fn test_handshake_old_client_is_downgraded() {
+        let hello = Hello {
+            protocol_version: 1,
+            client_version: "1.0.0".to_string(),
+            compression: false,
+        };
+        let ack = hello.negotiate("3.0.1").unwrap();
+        assert_eq!(ack.accepted_version, ProtocolVersion::V1);
+        assert!(!ack.compression);
+        assert_eq!(ack.verify(&hello), Ok(ProtocolVersion::V1));
+    }
+
+    #[test]
+    fn test_handshake_newer_client_is_downgraded() {
+        let hello = Hello {
+            protocol_version: ProtocolVersion::current().number() + 1,
+            client_version: "4.0.0".to_string(),
+            compression: true,
+        };
+        let ack = hello.negotiate("3.0.1").unwrap();
+        assert_eq!(ack.accepted_version, ProtocolVersion::current());
+        assert_eq!(ack.verify(&hello), Ok(ProtocolVersion::current()));
+    }
+
+    #[test]
+    fn test_handshake_mismatching_version() {
+        let hello = Hello {
+            protocol_version: 0,
+            client_version: "0.1.0".to_string(),
+            compression: false,
+        };
+        let error = hello.negotiate("3.0.1").unwrap_err();
+        assert_eq!(error, HandshakeError::UnsupportedVersion(0));
+        assert_eq!(error.to_string(), "protocol version 0 is not supported");
+    }
+
+    #[test]
+    fn test_handshake_client_rejects_unrequested_version() {
+        let hello = Hello {
+            protocol_version: 1,
+            client_version: "1.0.0".to_string(),
+            compression: false,
+        };
+        let ack = HelloAck {
+            accepted_version: ProtocolVersion::V2,
+            server_version: "3.0.1".to_string(),
+            compression: false,
+        };
+        assert_eq!(
+            ack.verify(&hello),
+            Err(HandshakeError::UnexpectedVersion(ProtocolVersion::V2))
+        );
+    }
+
+    #[test]
+    fn test_handshake_json_roundtrip() {
+        let hello = Hello::new("3.0.1");
+        let json = serde_json::to_string(&hello).unwrap();
+        assert_eq!(
+            json,
+            r#"{"protocol_version":2,"client_version":"3.0.1","compression":true}"#
+        );
+        let deserialized: Hello = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, hello);
+
+        // Clients that predate compression don't send the flag.
+        let hello: Hello =
+            serde_json::from_str(r#"{"protocol_version":2,"client_version":"3.0.0"}"#).unwrap();
+        assert!(!hello.compression);
+
+        let ack = hello.negotiate("3.0.1").unwrap();
+        let json = serde_json::to_string(&ack).unwrap();
+        let deserialized: HelloAck = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, ack);
+    }
+
+    #[test]
+    fn test_server_message_protocol_version() {
+        assert_eq!(
+            ServerMessage::Status("ok".to_string()).protocol_version(),
+            ProtocolVersion::V1
+        );
+        assert_eq!(
+            ServerMessage::TournamentStarted { tournament_id: 1 }.protocol_version(),
+            ProtocolVersion::V2
+        );
+    }
+
+    #[test]
+    fn test_close_reason_codes_round_trip() {
+        for reason in CloseReason::ALL {
+            assert_eq!(CloseReason::from_code(reason.code()), Some(reason));
+        }
+        assert_eq!(CloseReason::from_code(1000), None);
+    }
+
+    #[test]
+    fn test_close_reason_expired_token_is_retryable() {
+        assert!(CloseReason::AuthExpired.is_retryable());
+        assert!(!CloseReason::AuthInvalid.is_retryable());
+        assert_ne!(
+            CloseReason::AuthExpired.code(),
+            CloseReason::AuthInvalid.code()
+        );
+    }
 }
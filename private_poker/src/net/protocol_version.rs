@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Protocol version for multi-table poker
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ProtocolVersion {
     /// V1: Original single-table, no authentication
     V1,
@@ -17,6 +17,23 @@ impl ProtocolVersion {
         ProtocolVersion::V2
     }
 
+    /// Get the version number exchanged in the connection handshake
+    pub fn number(&self) -> u32 {
+        match self {
+            ProtocolVersion::V1 => 1,
+            ProtocolVersion::V2 => 2,
+        }
+    }
+
+    /// Get the version with the given handshake number, if it exists
+    pub fn from_number(number: u32) -> Option<Self> {
+        match number {
+            1 => Some(ProtocolVersion::V1),
+            2 => Some(ProtocolVersion::V2),
+            _ => None,
+        }
+    }
+
     /// Check if this version is compatible with another
     pub fn is_compatible_with(&self, other: &ProtocolVersion) -> bool {
         // V1 and V2 are compatible via legacy mode in server
@@ -68,6 +85,19 @@ mod tests {
         assert!(ProtocolVersion::V2.is_compatible_with(&ProtocolVersion::V1));
     }
 
+    #[test]
+    fn test_version_numbers() {
+        for version in [ProtocolVersion::V1, ProtocolVersion::V2] {
+            assert_eq!(
+                ProtocolVersion::from_number(version.number()),
+                Some(version)
+            );
+        }
+        assert_eq!(ProtocolVersion::from_number(0), None);
+        assert_eq!(ProtocolVersion::from_number(3), None);
+        assert!(ProtocolVersion::V1 < ProtocolVersion::V2);
+    }
+
     #[test]
     fn test_serialization() {
         let v1 = ProtocolVersion::V1;
@@ -22,7 +22,7 @@ use super::{
         game::{
             GameEvent, GameSettings, GameStateManagement, PhaseDependentUserManagement,
             PhaseIndependentUserManagement, PokerState,
-            entities::{Action, ActionChoices, GameView, Username},
+            entities::{Action, GameView, TurnInfo, Username},
         },
     },
     messages::{ClientError, ClientMessage, ServerMessage, UserCommand, UserState},
@@ -60,10 +60,7 @@ enum ServerData {
         error: UserError,
     },
     /// Signaling it's a specific user's turn.
-    TurnSignal {
-        username: Username,
-        action_choices: ActionChoices,
-    },
+    TurnSignal { username: Username, turn: TurnInfo },
     /// Game state represented as a string.
     Status(String),
     /// Mapping of usernames to their game views.
@@ -625,14 +622,11 @@ pub fn run(addr: SocketAddr, config: PokerConfig) -> Result<(), Error> {
                                 // A response goes to a single client. We can safely ignore cases where a
                                 // client no longer exists to receive a response because the response
                                 // is meant just for the client.
-                                ServerData::TurnSignal {
-                                    username,
-                                    action_choices,
-                                } => {
+                                ServerData::TurnSignal { username, turn } => {
                                     if let Ok(token) =
                                         token_manager.get_token_with_username(&username)
                                     {
-                                        let msg = ServerMessage::TurnSignal(action_choices);
+                                        let msg = ServerMessage::TurnSignal(turn);
                                         messages_to_write.entry(token).or_default().push_back(msg);
                                         tokens_to_reregister.insert(token);
                                     }
@@ -921,7 +915,7 @@ pub fn run(addr: SocketAddr, config: PokerConfig) -> Result<(), Error> {
             // decision. We also keep track of their username so we
             // can tell if they don't make a decision in time.
             if let Some(username) = state.get_next_action_username()
-                && let Some(action_choices) = state.get_action_choices()
+                && let Some(turn) = state.get_turn_info()
             {
                 // Check if the username from the last turn is the same as the
                 // username from this turn. If so, we need to check if there
@@ -947,7 +941,10 @@ pub fn run(addr: SocketAddr, config: PokerConfig) -> Result<(), Error> {
                         break 'command;
                     }
                     // Let all users know whose turn it is.
-                    let status = format!("it's {username}'s turn and they can {action_choices}");
+                    let status = format!(
+                        "it's {username}'s turn and they can {}",
+                        turn.action_choices
+                    );
                     let msg = ServerData::Status(status.clone());
                     server_data_sender.send(msg)?;
 
@@ -955,7 +952,7 @@ pub fn run(addr: SocketAddr, config: PokerConfig) -> Result<(), Error> {
                     info!("{status}");
                     let msg = ServerData::TurnSignal {
                         username: username.clone(),
-                        action_choices,
+                        turn,
                     };
                     server_data_sender.send(msg)?;
 
@@ -988,6 +985,9 @@ pub fn run(addr: SocketAddr, config: PokerConfig) -> Result<(), Error> {
                         UserCommand::Connect => state.new_user(&msg.username).map(|_| ()),
                         UserCommand::Disconnect => state.remove_user(&msg.username).map(|_| ()),
                         UserCommand::ShowHand => state.show_hand(&msg.username),
+                        UserCommand::Muck => state.muck_hand(&msg.username),
+                        UserCommand::SitOut { .. } => state.set_sitting_out(&msg.username, true),
+                        UserCommand::SitIn { .. } => state.set_sitting_out(&msg.username, false),
                         UserCommand::StartGame => state.init_start(&msg.username),
                         UserCommand::TakeAction(ref mut action) => state
                             .take_action(&msg.username, action.clone())
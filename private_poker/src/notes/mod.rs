@@ -0,0 +1,36 @@
+//! Player notes module for keeping private notes on opponents.
+//!
+//! This module provides:
+//! - One note per author and opponent, keyed by the opponent's username
+//! - A length limit on note text, with empty text clearing the note
+//! - Batched lookup of the author's notes on everyone seated at a table
+//!
+//! Notes are private: every read and write is scoped to the author.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use private_poker::notes::NotesRepository;
+//! use private_poker::db::Database;
+//! use std::sync::Arc;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let db = Database::new(&Default::default()).await?;
+//!     let notes = NotesRepository::new(Arc::new(db.pool().clone()));
+//!
+//!     notes.set_note(1, "alice", "3-bets light from the button").await?;
+//!
+//!     // Look up notes on everyone at the table in one query
+//!     let seated = vec!["alice".to_string(), "bob".to_string()];
+//!     for (username, note) in notes.get_notes(1, &seated).await? {
+//!         println!("{username}: {note}");
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+pub mod repository;
+
+pub use repository::{MAX_NOTE_LENGTH, NotesError, NotesRepository};
@@ -0,0 +1,124 @@
+//! Player notes persistence.
+
+use sqlx::{PgPool, Row};
+use std::{collections::HashMap, sync::Arc};
+use thiserror::Error;
+
+/// Maximum length of a note, in characters
+pub const MAX_NOTE_LENGTH: usize = 500;
+
+/// Player notes errors
+#[derive(Debug, Error)]
+pub enum NotesError {
+    /// Database error
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    /// Note text is longer than the limit
+    #[error("Note is {length} characters long, the limit is {MAX_NOTE_LENGTH}")]
+    TooLong { length: usize },
+}
+
+/// Repository for the notes players keep on their opponents
+#[derive(Clone)]
+pub struct NotesRepository {
+    pool: Arc<PgPool>,
+}
+
+impl NotesRepository {
+    /// Create a new notes repository
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Set the author's note on an opponent, replacing any note they
+    /// already had. Text that is empty after trimming clears the note.
+    ///
+    /// # Arguments
+    ///
+    /// * `author_id` - User ID of the player writing the note
+    /// * `target_username` - Username of the opponent the note is about
+    /// * `text` - Note text
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), NotesError>` - Success, or the note is too long or
+    ///   there was a database error
+    pub async fn set_note(
+        &self,
+        author_id: i64,
+        target_username: &str,
+        text: &str,
+    ) -> Result<(), NotesError> {
+        let text = text.trim();
+        let length = text.chars().count();
+        if length > MAX_NOTE_LENGTH {
+            return Err(NotesError::TooLong { length });
+        }
+
+        if text.is_empty() {
+            sqlx::query("DELETE FROM player_notes WHERE author_id = $1 AND target_username = $2")
+                .bind(author_id)
+                .bind(target_username)
+                .execute(self.pool.as_ref())
+                .await?;
+            return Ok(());
+        }
+
+        sqlx::query(
+            "INSERT INTO player_notes (author_id, target_username, note)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (author_id, target_username) DO UPDATE SET
+                 note = EXCLUDED.note,
+                 updated_at = NOW()",
+        )
+        .bind(author_id)
+        .bind(target_username)
+        .bind(text)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get the author's notes on the given opponents in a single query.
+    /// Opponents the author has no note on are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `author_id` - User ID of the player who wrote the notes
+    /// * `usernames` - Usernames of the opponents to look up
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HashMap<String, String>, NotesError>` - Notes keyed by
+    ///   opponent username, or database error
+    pub async fn get_notes(
+        &self,
+        author_id: i64,
+        usernames: &[String],
+    ) -> Result<HashMap<String, String>, NotesError> {
+        if usernames.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows = sqlx::query(
+            "SELECT target_username, note
+             FROM player_notes
+             WHERE author_id = $1 AND target_username = ANY($2)",
+        )
+        .bind(author_id)
+        .bind(usernames)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get("target_username"), row.get("note")))
+            .collect())
+    }
+}
@@ -3,7 +3,12 @@
 #![allow(clippy::similar_names)]
 #![allow(clippy::needless_raw_string_hashes)]
 
-use super::errors::AntiCollusionResult;
+use super::errors::{AntiCollusionError, AntiCollusionResult};
+use crate::game::{
+    entities::{Card, HandHistory, HistoryAction, Rank, Street, Usd, Username},
+    functional::{eval, prepare_hand},
+};
+use crate::table::messages::TableHandHistory;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, Row};
@@ -121,6 +126,170 @@ pub fn normalize_ip(ip_str: &str) -> String {
     }
 }
 
+/// Folds to a player's raises needed before the pattern is judged
+const MIN_FOLDS_TO_TARGET: u32 = 5;
+
+/// Most recent hands at a table that folding patterns are drawn from
+const FOLD_PATTERN_HANDS: i64 = 500;
+
+/// How a suspect folded to one player's raises compared to everyone
+/// else's, drawn from hand histories
+///
+/// A fold is unusual when the suspect had a made hand they showed, had
+/// already raised earlier in the hand, or was getting at least 4:1 on a
+/// call. Tight players fold a lot, but they fold the same way to everyone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FoldPattern {
+    /// Hands both players were dealt into
+    pub hands_together: u32,
+
+    /// Folds facing a raise from the target
+    pub folds_to_target: u32,
+
+    /// Folds facing a raise from the target that were unusual
+    pub unusual_folds_to_target: u32,
+
+    /// Folds facing a raise from anyone else
+    pub folds_to_others: u32,
+
+    /// Folds facing a raise from anyone else that were unusual
+    pub unusual_folds_to_others: u32,
+
+    /// Other folds in hands the target went on to win. These aren't
+    /// suspicious on their own and don't count toward a flag.
+    pub folds_in_target_wins: u32,
+}
+
+impl FoldPattern {
+    /// Tally how `suspect` folded against `target` over a set of hands
+    pub fn from_hands<'a>(
+        hands: impl IntoIterator<Item = &'a HandHistory>,
+        suspect: &Username,
+        target: &Username,
+    ) -> Self {
+        let mut pattern = Self::default();
+        for hand in hands {
+            pattern.record_hand(hand, suspect, target);
+        }
+        pattern
+    }
+
+    /// Whether the suspect folds to the target's raises in spots they
+    /// don't fold to anyone else's
+    pub fn is_suspicious(&self) -> bool {
+        if self.folds_to_target < MIN_FOLDS_TO_TARGET
+            || self.unusual_folds_to_target * 2 < self.folds_to_target
+        {
+            return false;
+        }
+        let target_rate = f64::from(self.unusual_folds_to_target) / f64::from(self.folds_to_target);
+        let others_rate = if self.folds_to_others == 0 {
+            0.0
+        } else {
+            f64::from(self.unusual_folds_to_others) / f64::from(self.folds_to_others)
+        };
+        target_rate >= others_rate * 2.0
+    }
+
+    fn record_hand(&mut self, hand: &HandHistory, suspect: &Username, target: &Username) {
+        let seat = |username: &Username| hand.seats.iter().find(|s| &s.username == username);
+        let (Some(suspect_seat), Some(_)) = (seat(suspect), seat(target)) else {
+            return;
+        };
+        self.hands_together += 1;
+
+        let hole_cards: Option<Vec<Card>> = suspect_seat
+            .hole_cards
+            .as_ref()
+            .and_then(|cards| cards.iter().map(|c| c.parse().ok()).collect());
+
+        // Forced bets open the preflop betting, so only putting in more
+        // than anyone has so far on a street makes a player the raiser
+        let mut street = Street::Preflop;
+        let mut street_bets: HashMap<&Username, Usd> = HashMap::new();
+        let mut pot: Usd = 0;
+        for bet in &hand.forced_bets {
+            *street_bets.entry(&bet.username).or_default() += bet.amount;
+            pot += bet.amount;
+        }
+        let mut street_max = street_bets.values().copied().max().unwrap_or(0);
+        let mut raiser: Option<&Username> = None;
+        let mut suspect_raised = false;
+
+        for record in &hand.actions {
+            if record.street != street {
+                street = record.street;
+                street_bets.clear();
+                street_max = 0;
+                raiser = None;
+            }
+
+            if &record.username == suspect && record.action == HistoryAction::Fold {
+                let to_call = street_max - street_bets.get(suspect).copied().unwrap_or(0);
+                let unusual = suspect_raised
+                    || to_call.saturating_mul(4) <= pot
+                    || hole_cards
+                        .as_ref()
+                        .is_some_and(|hole| has_made_hand(hole, &board_through(hand, street)));
+                match raiser {
+                    Some(raiser) if raiser == target => {
+                        self.folds_to_target += 1;
+                        self.unusual_folds_to_target += u32::from(unusual);
+                    }
+                    Some(_) => {
+                        self.folds_to_others += 1;
+                        self.unusual_folds_to_others += u32::from(unusual);
+                        self.count_target_win(hand, target);
+                    }
+                    None => self.count_target_win(hand, target),
+                }
+                return;
+            }
+
+            let bet = street_bets.entry(&record.username).or_default();
+            *bet += record.amount;
+            pot += record.amount;
+            if *bet > street_max {
+                street_max = *bet;
+                if matches!(record.action, HistoryAction::Raise | HistoryAction::AllIn) {
+                    raiser = Some(&record.username);
+                    suspect_raised |= &record.username == suspect;
+                }
+            }
+        }
+    }
+
+    fn count_target_win(&mut self, hand: &HandHistory, target: &Username) {
+        if hand.pot_awards.iter().any(|a| &a.username == target) {
+            self.folds_in_target_wins += 1;
+        }
+    }
+}
+
+/// Community cards dealt up to and including a street
+fn board_through(hand: &HandHistory, street: Street) -> Vec<Card> {
+    hand.board
+        .iter()
+        .filter(|record| record.street <= street)
+        .flat_map(|record| &record.cards)
+        .filter_map(|card| card.parse().ok())
+        .collect()
+}
+
+/// Whether the hole cards make at least a pair the board doesn't already
+/// have on its own
+fn has_made_hand(hole: &[Card], board: &[Card]) -> bool {
+    let best_rank = |cards: &[Card]| {
+        let mut cards = cards.to_vec();
+        prepare_hand(&mut cards);
+        eval(&cards)
+            .first()
+            .map_or(Rank::HighCard, |subhand| subhand.rank)
+    };
+    let with_hole = best_rank(&[hole, board].concat());
+    with_hole >= Rank::OnePair && (board.is_empty() || with_hole > best_rank(board))
+}
+
 /// Anti-collusion detector
 pub struct AntiCollusionDetector {
     /// Database pool
@@ -317,8 +486,8 @@ impl AntiCollusionDetector {
         user_id: i64,
         beneficiary_id: i64,
     ) -> AntiCollusionResult<()> {
-        // Check if coordinated folding (always folding to same player)
-        // This would require historical tracking - placeholder for now
+        // Folding to the same player over many hands is judged from hand
+        // histories by `check_coordinated_folding`
 
         // Check if same IP
         let ips = self.user_ips.read().await;
@@ -345,6 +514,93 @@ impl AntiCollusionDetector {
         Ok(())
     }
 
+    /// Check whether a player folds to another player's raises in spots
+    /// they don't fold to anyone else's
+    ///
+    /// Looks at the table's recent hand histories. Folding in hands the
+    /// target happens to win isn't held against the suspect, only folding
+    /// to the target's raises with a shown made hand, after raising, or
+    /// at a good price. A suspicious pattern is flagged for review.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Table ID
+    /// * `suspect_id` - User who folds
+    /// * `target_id` - User the folds would benefit
+    ///
+    /// # Returns
+    ///
+    /// * `AntiCollusionResult<FoldPattern>` - How the suspect folded against the target
+    pub async fn check_coordinated_folding(
+        &self,
+        table_id: i64,
+        suspect_id: i64,
+        target_id: i64,
+    ) -> AntiCollusionResult<FoldPattern> {
+        let suspect = self.username(suspect_id).await?;
+        let target = self.username(target_id).await?;
+
+        let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+            r#"
+            SELECT history FROM table_hand_histories
+            WHERE table_id = $1
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(table_id)
+        .bind(FOLD_PATTERN_HANDS)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let histories: Vec<TableHandHistory> = rows
+            .into_iter()
+            .filter_map(|(json,)| serde_json::from_value(json).ok())
+            .collect();
+        let pattern = FoldPattern::from_hands(histories.iter().map(|h| &h.hand), &suspect, &target);
+
+        if pattern.is_suspicious() {
+            let ips = self.user_ips.read().await;
+            let same_ip = matches!(
+                (ips.get(&suspect_id), ips.get(&target_id)),
+                (Some(ip1), Some(ip2)) if ip1 == ip2
+            );
+            drop(ips);
+
+            self.create_flag(
+                suspect_id,
+                table_id,
+                FlagType::CoordinatedFolding,
+                if same_ip {
+                    FlagSeverity::High
+                } else {
+                    FlagSeverity::Medium
+                },
+                serde_json::json!({
+                    "beneficiary_id": target_id,
+                    "same_ip": same_ip,
+                    "pattern": pattern,
+                }),
+            )
+            .await?;
+        }
+
+        Ok(pattern)
+    }
+
+    /// Look up the username a user plays under
+    async fn username(&self, user_id: i64) -> AntiCollusionResult<Username> {
+        let username: Option<String> =
+            sqlx::query_scalar("SELECT username FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(self.pool.as_ref())
+                .await?;
+
+        username
+            .map(Username::from)
+            .ok_or(AntiCollusionError::UserNotFound(user_id))
+    }
+
     /// Create a collusion flag
     async fn create_flag(
         &self,
@@ -0,0 +1,432 @@
+//! Append-only audit log for security-relevant actions.
+//!
+//! Entries are buffered in memory and written to the database by a
+//! background task, so recording one never waits on the database. The
+//! buffer is bounded; if the database falls far enough behind that it
+//! fills up, new entries are dropped and logged as errors instead of
+//! slowing down the caller.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::errors::{AuditLogError, AuditLogResult};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool, Row, postgres::PgRow};
+use std::{fmt, str::FromStr, sync::Arc};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    oneshot,
+};
+
+/// Most entries that can wait to be written before new ones are dropped
+const AUDIT_BUFFER_SIZE: usize = 1024;
+
+/// Most entries written in one insert
+const AUDIT_BATCH_SIZE: usize = 100;
+
+/// Type of action recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Login,
+    LoginFailed,
+    Logout,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+    KickUser,
+    MuteUser,
+    UnmuteUser,
+    FreezeWallet,
+    UnfreezeWallet,
+    CloseTable,
+    PauseTable,
+    ResumeTable,
+    ViewFlags,
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditAction::Login => write!(f, "login"),
+            AuditAction::LoginFailed => write!(f, "login_failed"),
+            AuditAction::Logout => write!(f, "logout"),
+            AuditAction::ApiKeyCreated => write!(f, "api_key_created"),
+            AuditAction::ApiKeyRevoked => write!(f, "api_key_revoked"),
+            AuditAction::KickUser => write!(f, "kick_user"),
+            AuditAction::MuteUser => write!(f, "mute_user"),
+            AuditAction::UnmuteUser => write!(f, "unmute_user"),
+            AuditAction::FreezeWallet => write!(f, "freeze_wallet"),
+            AuditAction::UnfreezeWallet => write!(f, "unfreeze_wallet"),
+            AuditAction::CloseTable => write!(f, "close_table"),
+            AuditAction::PauseTable => write!(f, "pause_table"),
+            AuditAction::ResumeTable => write!(f, "resume_table"),
+            AuditAction::ViewFlags => write!(f, "view_flags"),
+        }
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = AuditLogError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "login" => Ok(AuditAction::Login),
+            "login_failed" => Ok(AuditAction::LoginFailed),
+            "logout" => Ok(AuditAction::Logout),
+            "api_key_created" => Ok(AuditAction::ApiKeyCreated),
+            "api_key_revoked" => Ok(AuditAction::ApiKeyRevoked),
+            "kick_user" => Ok(AuditAction::KickUser),
+            "mute_user" => Ok(AuditAction::MuteUser),
+            "unmute_user" => Ok(AuditAction::UnmuteUser),
+            "freeze_wallet" => Ok(AuditAction::FreezeWallet),
+            "unfreeze_wallet" => Ok(AuditAction::UnfreezeWallet),
+            "close_table" => Ok(AuditAction::CloseTable),
+            "pause_table" => Ok(AuditAction::PauseTable),
+            "resume_table" => Ok(AuditAction::ResumeTable),
+            "view_flags" => Ok(AuditAction::ViewFlags),
+            _ => Err(AuditLogError::InvalidAction(s.to_string())),
+        }
+    }
+}
+
+/// Entry in the audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Database ID, or `None` until the entry is written
+    pub id: Option<i64>,
+    /// User who took the action, or `None` if it's unknown (e.g., a failed login)
+    pub actor_id: Option<i64>,
+    /// What was done
+    pub action: AuditAction,
+    /// User the action was taken on
+    pub target_user_id: Option<i64>,
+    /// Table the action was taken at
+    pub table_id: Option<i64>,
+    /// IP address the action came from
+    pub ip_address: Option<String>,
+    /// Anything else worth knowing, such as the reason for a freeze
+    pub details: Option<String>,
+    /// When the action was taken
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    /// Create an entry for an action taken now
+    ///
+    /// # Arguments
+    ///
+    /// * `actor_id` - User who took the action
+    /// * `action` - What was done
+    ///
+    /// # Returns
+    ///
+    /// * `AuditEntry` - Entry with no target, IP address, or details
+    pub fn new(actor_id: Option<i64>, action: AuditAction) -> Self {
+        Self {
+            id: None,
+            actor_id,
+            action,
+            target_user_id: None,
+            table_id: None,
+            ip_address: None,
+            details: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Set the user the action was taken on
+    pub fn with_target_user(mut self, user_id: i64) -> Self {
+        self.target_user_id = Some(user_id);
+        self
+    }
+
+    /// Set the table the action was taken at
+    pub fn with_table(mut self, table_id: i64) -> Self {
+        self.table_id = Some(table_id);
+        self
+    }
+
+    /// Set the IP address the action came from
+    pub fn with_ip(mut self, ip_address: Option<String>) -> Self {
+        self.ip_address = ip_address;
+        self
+    }
+
+    /// Set the entry's details
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+/// Filter for audit log entries
+///
+/// Dates are compared in UTC, and both ends of the range are inclusive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditFilter {
+    /// Only entries for actions taken by this user
+    pub actor_id: Option<i64>,
+    /// Only entries for actions taken on this user
+    pub target_user_id: Option<i64>,
+    /// Only entries for actions taken at this table
+    pub table_id: Option<i64>,
+    /// Only entries of this type
+    pub action: Option<AuditAction>,
+    /// Only entries created at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only entries created at or before this time
+    pub to: Option<DateTime<Utc>>,
+    /// Maximum number of entries to return
+    pub limit: i64,
+    /// Number of entries to skip
+    pub offset: i64,
+}
+
+impl Default for AuditFilter {
+    fn default() -> Self {
+        Self {
+            actor_id: None,
+            target_user_id: None,
+            table_id: None,
+            action: None,
+            from: None,
+            to: None,
+            limit: 100,
+            offset: 0,
+        }
+    }
+}
+
+/// Message to the background writer
+enum AuditCommand {
+    Record(AuditEntry),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Audit log writer
+///
+/// Cloning is cheap, and clones share the same buffer and writer.
+#[derive(Clone)]
+pub struct AuditLog {
+    /// Database pool
+    pool: Arc<PgPool>,
+
+    /// Buffer of entries waiting to be written
+    sender: mpsc::Sender<AuditCommand>,
+}
+
+impl AuditLog {
+    /// Create a new audit log and spawn its background writer
+    ///
+    /// Must be called from within a Tokio runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    ///
+    /// * `AuditLog` - New audit log instance
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        let (sender, receiver) = mpsc::channel(AUDIT_BUFFER_SIZE);
+        tokio::spawn(run_writer(pool.clone(), receiver));
+        Self { pool, sender }
+    }
+
+    /// Record an entry without waiting for it to be written
+    ///
+    /// If the buffer is full, the entry is dropped and logged as an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - Entry to record
+    pub fn record(&self, entry: AuditEntry) {
+        if let Err(e) = self.sender.try_send(AuditCommand::Record(entry)) {
+            let reason = match e {
+                TrySendError::Full(_) => "buffer is full",
+                TrySendError::Closed(_) => "writer has stopped",
+            };
+            if let AuditCommand::Record(entry) = e.into_inner() {
+                log::error!("Audit log {}, dropping entry: {:?}", reason, entry);
+            }
+        }
+    }
+
+    /// Wait until every entry recorded so far has been written
+    pub async fn flush(&self) {
+        let (tx, rx) = oneshot::channel();
+        if self.sender.send(AuditCommand::Flush(tx)).await.is_ok() {
+            let _ = rx.await;
+        }
+    }
+
+    /// Get audit log entries matching a filter
+    ///
+    /// Entries still waiting in the buffer aren't included; call
+    /// [`flush`](Self::flush) first to see them.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Actor, target, table, action, date range, and page to return
+    ///
+    /// # Returns
+    ///
+    /// * `AuditLogResult<Vec<AuditEntry>>` - Matching entries, newest first
+    pub async fn query(&self, filter: &AuditFilter) -> AuditLogResult<Vec<AuditEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, actor_id, action, target_user_id, table_id, ip_address, details, created_at
+            FROM audit_log
+            WHERE ($1::BIGINT IS NULL OR actor_id = $1)
+              AND ($2::BIGINT IS NULL OR target_user_id = $2)
+              AND ($3::BIGINT IS NULL OR table_id = $3)
+              AND ($4::TEXT IS NULL OR action = $4)
+              AND ($5::TIMESTAMP IS NULL OR created_at >= $5)
+              AND ($6::TIMESTAMP IS NULL OR created_at <= $6)
+            ORDER BY created_at DESC, id DESC
+            LIMIT $7 OFFSET $8
+            "#,
+        )
+        .bind(filter.actor_id)
+        .bind(filter.target_user_id)
+        .bind(filter.table_id)
+        .bind(filter.action.map(|action| action.to_string()))
+        .bind(filter.from.map(|from| from.naive_utc()))
+        .bind(filter.to.map(|to| to.naive_utc()))
+        .bind(filter.limit)
+        .bind(filter.offset)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        rows.iter().map(entry_from_row).collect()
+    }
+}
+
+/// Write buffered entries in batches until every sender is dropped
+async fn run_writer(pool: Arc<PgPool>, mut receiver: mpsc::Receiver<AuditCommand>) {
+    let mut batch = Vec::with_capacity(AUDIT_BATCH_SIZE);
+    let mut flushes = Vec::new();
+
+    while let Some(command) = receiver.recv().await {
+        let mut next = Some(command);
+        while let Some(command) = next {
+            match command {
+                AuditCommand::Record(entry) => batch.push(entry),
+                AuditCommand::Flush(done) => flushes.push(done),
+            }
+            next = if batch.len() < AUDIT_BATCH_SIZE {
+                receiver.try_recv().ok()
+            } else {
+                None
+            };
+        }
+
+        if !batch.is_empty() {
+            if let Err(e) = write_batch(pool.as_ref(), &batch).await {
+                log::error!("Failed to write {} audit log entries: {}", batch.len(), e);
+            }
+            batch.clear();
+        }
+        for done in flushes.drain(..) {
+            let _ = done.send(());
+        }
+    }
+}
+
+/// Insert a batch of entries in one statement
+///
+/// Callers that need an entry to commit or roll back with their own changes
+/// can pass their transaction as the executor instead of using the buffer.
+pub(crate) async fn write_batch<'e, E>(executor: E, batch: &[AuditEntry]) -> Result<(), sqlx::Error>
+where
+    E: PgExecutor<'e>,
+{
+    let actor_ids: Vec<Option<i64>> = batch.iter().map(|entry| entry.actor_id).collect();
+    let actions: Vec<String> = batch.iter().map(|entry| entry.action.to_string()).collect();
+    let target_user_ids: Vec<Option<i64>> =
+        batch.iter().map(|entry| entry.target_user_id).collect();
+    let table_ids: Vec<Option<i64>> = batch.iter().map(|entry| entry.table_id).collect();
+    let ip_addresses: Vec<Option<String>> =
+        batch.iter().map(|entry| entry.ip_address.clone()).collect();
+    let details: Vec<Option<String>> = batch.iter().map(|entry| entry.details.clone()).collect();
+    let created_ats: Vec<NaiveDateTime> = batch
+        .iter()
+        .map(|entry| entry.created_at.naive_utc())
+        .collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (actor_id, action, target_user_id, table_id, ip_address, details, created_at)
+        SELECT * FROM UNNEST($1::BIGINT[], $2::TEXT[], $3::BIGINT[], $4::BIGINT[], $5::TEXT[], $6::TEXT[], $7::TIMESTAMP[])
+        "#,
+    )
+    .bind(actor_ids)
+    .bind(actions)
+    .bind(target_user_ids)
+    .bind(table_ids)
+    .bind(ip_addresses)
+    .bind(details)
+    .bind(created_ats)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+fn entry_from_row(row: &PgRow) -> AuditLogResult<AuditEntry> {
+    Ok(AuditEntry {
+        id: Some(row.get("id")),
+        actor_id: row.get("actor_id"),
+        action: row.get::<String, _>("action").parse()?,
+        target_user_id: row.get("target_user_id"),
+        table_id: row.get("table_id"),
+        ip_address: row.get("ip_address"),
+        details: row.get("details"),
+        created_at: row.get::<NaiveDateTime, _>("created_at").and_utc(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_action_round_trips_through_strings() {
+        for action in [
+            AuditAction::Login,
+            AuditAction::LoginFailed,
+            AuditAction::Logout,
+            AuditAction::ApiKeyCreated,
+            AuditAction::ApiKeyRevoked,
+            AuditAction::KickUser,
+            AuditAction::MuteUser,
+            AuditAction::UnmuteUser,
+            AuditAction::FreezeWallet,
+            AuditAction::UnfreezeWallet,
+            AuditAction::CloseTable,
+            AuditAction::PauseTable,
+            AuditAction::ResumeTable,
+            AuditAction::ViewFlags,
+        ] {
+            assert_eq!(action.to_string().parse::<AuditAction>().unwrap(), action);
+        }
+        assert!("drop_tables".parse::<AuditAction>().is_err());
+    }
+
+    #[test]
+    fn test_audit_entry_builder() {
+        let entry = AuditEntry::new(Some(1), AuditAction::KickUser)
+            .with_target_user(2)
+            .with_table(3)
+            .with_ip(Some("10.0.0.1".to_string()))
+            .with_details("spamming chat");
+
+        assert_eq!(entry.id, None);
+        assert_eq!(entry.actor_id, Some(1));
+        assert_eq!(entry.target_user_id, Some(2));
+        assert_eq!(entry.table_id, Some(3));
+        assert_eq!(entry.ip_address.as_deref(), Some("10.0.0.1"));
+        assert_eq!(entry.details.as_deref(), Some("spamming chat"));
+    }
+}
@@ -58,9 +58,28 @@ pub enum RateLimitError {
     #[error("Configuration error: {0}")]
     Configuration(String),
 
+    /// Rate limit checked without the IP address or user ID it's keyed on
+    #[error("Rate limit key missing for endpoint: {0}")]
+    MissingKey(String),
+
     /// Database corruption detected
     #[error("Database corruption detected: {message}")]
     DatabaseCorruption {
         message: String,
     },
 }
+
+/// Result type for audit log operations
+pub type AuditLogResult<T> = Result<T, AuditLogError>;
+
+/// Audit log errors
+#[derive(Debug, Error)]
+pub enum AuditLogError {
+    /// Database error
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    /// Unknown action stored in the log
+    #[error("Invalid audit action: {0}")]
+    InvalidAction(String),
+}
@@ -5,6 +5,7 @@
 //! - Anti-collusion detection with shadow flagging
 //! - IP-based table restrictions
 //! - Seat randomization to prevent manipulation
+//! - Append-only audit log of security-relevant actions
 //!
 //! ## Rate Limiting
 //!
@@ -19,9 +20,16 @@
 //! Detects suspicious patterns without auto-banning:
 //! - **Same-IP detection**: Flags players from same IP at same table
 //! - **Win rate anomalies**: Flags >80% win rate against same-IP players
-//! - **Coordinated folding**: Detects always folding to same player
+//! - **Coordinated folding**: Flags folding to one player's raises in spots the
+//!   player doesn't fold to anyone else
 //! - **Shadow flagging**: All flags require admin review
 //!
+//! ## Audit Log
+//!
+//! Records who did what to whom, and from where:
+//! - **Append-only**: The database rejects updates and deletes
+//! - **Non-blocking**: Entries are buffered and written in the background
+//!
 //! ## Example
 //!
 //! ```no_run
@@ -65,13 +73,19 @@
 //! ```
 
 pub mod anti_collusion;
+pub mod audit_log;
 pub mod errors;
 pub mod rate_limiter;
 pub mod seat_randomizer;
 
 pub use anti_collusion::{
-    AntiCollusionDetector, CollusionFlag, FlagSeverity, FlagType, IpTableRestrictions, normalize_ip,
+    AntiCollusionDetector, CollusionFlag, FlagSeverity, FlagType, FoldPattern, IpTableRestrictions,
+    normalize_ip,
+};
+pub use audit_log::{AuditAction, AuditEntry, AuditFilter, AuditLog};
+pub use errors::{
+    AntiCollusionError, AntiCollusionResult, AuditLogError, AuditLogResult, RateLimitError,
+    RateLimiterResult,
 };
-pub use errors::{AntiCollusionError, AntiCollusionResult, RateLimitError, RateLimiterResult};
-pub use rate_limiter::{RateLimitConfig, RateLimitResult, RateLimiter};
-pub use seat_randomizer::SeatRandomizer;
+pub use rate_limiter::{RateLimitConfig, RateLimitKeyStrategy, RateLimitResult, RateLimiter};
+pub use seat_randomizer::{SeatRandomizer, shuffle_seats};
@@ -8,6 +8,42 @@ use sqlx::{PgPool, Row};
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::RwLock;
 
+/// What an endpoint's rate limit is keyed on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitKeyStrategy {
+    /// Key on the client's IP address, for endpoints used before
+    /// authenticating (e.g., login and register)
+    #[default]
+    Ip,
+
+    /// Key on the authenticated user, so the limit follows them across
+    /// IPs and users sharing an IP don't share a limit
+    User,
+}
+
+impl RateLimitKeyStrategy {
+    /// Build the identifier a rate limit is tracked under
+    ///
+    /// Identifiers are prefixed with the strategy so IP and user
+    /// buckets never collide.
+    ///
+    /// # Arguments
+    ///
+    /// * `ip_address` - Client IP address, if known
+    /// * `user_id` - Authenticated user ID, if any
+    ///
+    /// # Returns
+    ///
+    /// * `Option<String>` - Identifier, or None if the strategy needs an IP
+    ///   address or user ID and there isn't one
+    pub fn identifier(self, ip_address: Option<&str>, user_id: Option<i64>) -> Option<String> {
+        match self {
+            Self::Ip => ip_address.map(|ip_address| format!("ip:{}", ip_address)),
+            Self::User => user_id.map(|user_id| format!("user:{}", user_id)),
+        }
+    }
+}
+
 /// Rate limit configuration for an endpoint
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
@@ -22,6 +58,9 @@ pub struct RateLimitConfig {
 
     /// Whether to use exponential backoff
     pub exponential_backoff: bool,
+
+    /// What the limit is keyed on
+    pub key_strategy: RateLimitKeyStrategy,
 }
 
 impl RateLimitConfig {
@@ -41,6 +80,7 @@ impl RateLimitConfig {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(900),
             exponential_backoff: true,
+            key_strategy: RateLimitKeyStrategy::Ip,
         }
     }
 
@@ -60,6 +100,7 @@ impl RateLimitConfig {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(3600),
             exponential_backoff: false,
+            key_strategy: RateLimitKeyStrategy::Ip,
         }
     }
 
@@ -79,6 +120,7 @@ impl RateLimitConfig {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(7200),
             exponential_backoff: true,
+            key_strategy: RateLimitKeyStrategy::Ip,
         }
     }
 
@@ -98,6 +140,27 @@ impl RateLimitConfig {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(300),
             exponential_backoff: false,
+            key_strategy: RateLimitKeyStrategy::User,
+        }
+    }
+
+    /// Configuration for sending verification emails
+    pub fn email_verification() -> Self {
+        Self {
+            max_attempts: std::env::var("RATE_LIMIT_EMAIL_VERIFICATION_ATTEMPTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3),
+            window_secs: std::env::var("RATE_LIMIT_EMAIL_VERIFICATION_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            lockout_secs: std::env::var("RATE_LIMIT_EMAIL_VERIFICATION_LOCKOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            exponential_backoff: false,
+            key_strategy: RateLimitKeyStrategy::User,
         }
     }
 }
@@ -142,6 +205,10 @@ impl RateLimiter {
             RateLimitConfig::password_reset(),
         );
         configs.insert("chat".to_string(), RateLimitConfig::chat());
+        configs.insert(
+            "email_verification".to_string(),
+            RateLimitConfig::email_verification(),
+        );
 
         Self {
             pool,
@@ -292,6 +359,62 @@ impl RateLimiter {
         Ok(RateLimitResult::Allowed { remaining })
     }
 
+    /// Check and record an attempt, keyed by the endpoint's strategy
+    ///
+    /// Pre-auth endpoints are keyed on the IP address, and authenticated
+    /// endpoints are keyed on the user so their limit follows them across
+    /// IPs. The two strategies never share a bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Endpoint name (e.g., "login", "chat")
+    /// * `ip_address` - Client IP address, if known
+    /// * `user_id` - Authenticated user ID, if any
+    ///
+    /// # Returns
+    ///
+    /// * `RateLimiterResult<RateLimitResult>` - Whether action is allowed, locked, or error
+    pub async fn check_rate_limit(
+        &self,
+        endpoint: &str,
+        ip_address: Option<&str>,
+        user_id: Option<i64>,
+    ) -> RateLimiterResult<RateLimitResult> {
+        let config = self
+            .configs
+            .get(endpoint)
+            .ok_or_else(|| RateLimitError::InvalidEndpoint(endpoint.to_string()))?;
+        let identifier = config
+            .key_strategy
+            .identifier(ip_address, user_id)
+            .ok_or_else(|| RateLimitError::MissingKey(endpoint.to_string()))?;
+        self.check_and_record(endpoint, &identifier).await
+    }
+
+    /// Reset the rate limit for a key built by the endpoint's strategy
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Endpoint name
+    /// * `ip_address` - Client IP address, if known
+    /// * `user_id` - Authenticated user ID, if any
+    pub async fn reset_rate_limit(
+        &self,
+        endpoint: &str,
+        ip_address: Option<&str>,
+        user_id: Option<i64>,
+    ) -> RateLimiterResult<()> {
+        let config = self
+            .configs
+            .get(endpoint)
+            .ok_or_else(|| RateLimitError::InvalidEndpoint(endpoint.to_string()))?;
+        let identifier = config
+            .key_strategy
+            .identifier(ip_address, user_id)
+            .ok_or_else(|| RateLimitError::MissingKey(endpoint.to_string()))?;
+        self.reset(endpoint, &identifier).await
+    }
+
     /// Record an attempt
     ///
     /// # Arguments
@@ -495,6 +618,7 @@ mod tests {
                 window_secs: 60,
                 lockout_secs: 300,
                 exponential_backoff: false,
+                key_strategy: RateLimitKeyStrategy::Ip,
             },
         );
 
@@ -602,6 +726,7 @@ mod tests {
                 window_secs: 1, // 1 second window for fast testing
                 lockout_secs: 5,
                 exponential_backoff: false,
+                key_strategy: RateLimitKeyStrategy::Ip,
             },
         );
 
@@ -670,4 +795,91 @@ mod tests {
             "Different users should have independent rate limits"
         );
     }
+
+    #[test]
+    fn test_user_identifier_ignores_ip() {
+        let strategy = RateLimitKeyStrategy::User;
+        assert_eq!(
+            strategy.identifier(Some("10.0.0.1"), Some(7)),
+            strategy.identifier(Some("10.0.0.2"), Some(7))
+        );
+        assert_ne!(
+            strategy.identifier(Some("10.0.0.1"), Some(7)),
+            strategy.identifier(Some("10.0.0.1"), Some(8))
+        );
+        assert_eq!(strategy.identifier(Some("10.0.0.1"), None), None);
+    }
+
+    #[test]
+    fn test_ip_and_user_identifiers_never_collide() {
+        let ip = RateLimitKeyStrategy::Ip.identifier(Some("7"), Some(7));
+        let user = RateLimitKeyStrategy::User.identifier(Some("7"), Some(7));
+        assert_ne!(ip, user);
+    }
+
+    #[tokio::test]
+    async fn test_same_user_from_two_ips_shares_bucket() {
+        let mut limiter = create_test_limiter().await;
+        limiter.configs.insert(
+            "test_user_endpoint".to_string(),
+            RateLimitConfig {
+                max_attempts: 2,
+                window_secs: 60,
+                lockout_secs: 300,
+                exponential_backoff: false,
+                key_strategy: RateLimitKeyStrategy::User,
+            },
+        );
+        let user_id = Utc::now().timestamp_nanos_opt().unwrap();
+
+        for ip_address in ["10.0.0.1", "10.0.0.2"] {
+            let result = limiter
+                .check_rate_limit("test_user_endpoint", Some(ip_address), Some(user_id))
+                .await
+                .unwrap();
+            assert!(result.is_allowed());
+        }
+
+        // The limit follows the user to a third IP
+        let result = limiter
+            .check_rate_limit("test_user_endpoint", Some("10.0.0.3"), Some(user_id))
+            .await
+            .unwrap();
+        assert!(matches!(result, RateLimitResult::Locked { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_two_users_behind_one_ip_get_own_buckets() {
+        let mut limiter = create_test_limiter().await;
+        limiter.configs.insert(
+            "test_user_endpoint".to_string(),
+            RateLimitConfig {
+                max_attempts: 1,
+                window_secs: 60,
+                lockout_secs: 300,
+                exponential_backoff: false,
+                key_strategy: RateLimitKeyStrategy::User,
+            },
+        );
+        let user_id = Utc::now().timestamp_nanos_opt().unwrap();
+
+        for user_id in [user_id, user_id + 1] {
+            let result = limiter
+                .check_rate_limit("test_user_endpoint", Some("10.0.0.1"), Some(user_id))
+                .await
+                .unwrap();
+            assert!(
+                matches!(result, RateLimitResult::Allowed { remaining: 0 }),
+                "Users behind the same IP should have independent rate limits"
+            );
+        }
+
+        // Without a user ID, a per-user endpoint can't be checked
+        assert!(matches!(
+            limiter
+                .check_rate_limit("test_user_endpoint", Some("10.0.0.1"), None)
+                .await,
+            Err(RateLimitError::MissingKey(_))
+        ));
+    }
 }
@@ -1,18 +1,35 @@
 //! Seat randomization to prevent seat selection manipulation.
 
-use rand::seq::SliceRandom;
+use crate::game::entities::SeatIndex;
+use rand::{Rng, rngs::ThreadRng, seq::SliceRandom};
 use std::collections::HashMap;
 
+/// Shuffle seats in place with the given RNG.
+///
+/// This is a Fisher-Yates shuffle, so every ordering is equally likely and
+/// there's no bias toward keeping the original order.
+pub fn shuffle_seats<R: Rng + ?Sized>(seats: &mut [SeatIndex], rng: &mut R) {
+    seats.shuffle(rng);
+}
+
 /// Seat randomizer for anti-collusion
-pub struct SeatRandomizer {
+pub struct SeatRandomizer<R = ThreadRng> {
     /// Random number generator
-    rng: rand::rngs::ThreadRng,
+    rng: R,
 }
 
 impl SeatRandomizer {
-    /// Create a new seat randomizer
+    /// Create a new seat randomizer seeded from entropy
     pub fn new() -> Self {
-        Self { rng: rand::rng() }
+        Self::with_rng(rand::rng())
+    }
+}
+
+impl<R: Rng> SeatRandomizer<R> {
+    /// Create a seat randomizer with a specific RNG, e.g., a seeded one
+    /// for reproducible tests or fairness audits
+    pub fn with_rng(rng: R) -> Self {
+        Self { rng }
     }
 
     /// Assign random seats to players
@@ -32,7 +49,7 @@ impl SeatRandomizer {
 
         // Create list of available seat indices
         let mut available_seats: Vec<usize> = (0..max_seats).collect();
-        available_seats.shuffle(&mut self.rng);
+        shuffle_seats(&mut available_seats, &mut self.rng);
 
         // Assign random seat to each user
         let mut assignments = HashMap::new();
@@ -68,7 +85,7 @@ impl SeatRandomizer {
             return None;
         }
 
-        available_seats.shuffle(&mut self.rng);
+        shuffle_seats(&mut available_seats, &mut self.rng);
         Some(available_seats[0])
     }
 
@@ -105,6 +122,7 @@ impl Default for SeatRandomizer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
 
     #[test]
     fn test_assign_seats_randomizes() {
@@ -161,4 +179,47 @@ mod tests {
         let assignments = randomizer.assign_seats(&[], 10);
         assert!(assignments.is_empty());
     }
+
+    #[test]
+    fn test_seeded_assignments_are_reproducible() {
+        let user_ids = vec![1, 2, 3, 4, 5];
+        let mut randomizer1 = SeatRandomizer::with_rng(StdRng::seed_from_u64(7));
+        let mut randomizer2 = SeatRandomizer::with_rng(StdRng::seed_from_u64(7));
+        assert_eq!(
+            randomizer1.assign_seats(&user_ids, 10),
+            randomizer2.assign_seats(&user_ids, 10)
+        );
+    }
+
+    #[test]
+    fn test_shuffle_seats_is_uniform() {
+        const NUM_SEATS: usize = 6;
+        const NUM_SHUFFLES: usize = 60_000;
+        // Chi-square critical value for 5 degrees of freedom at p = 0.001.
+        const CRITICAL_VALUE: f64 = 20.515;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        // counts[position][seat] is how often the seat at `position` in the
+        // join order ended up holding `seat`.
+        let mut counts = [[0usize; NUM_SEATS]; NUM_SEATS];
+        for _ in 0..NUM_SHUFFLES {
+            let mut seats: Vec<SeatIndex> = (0..NUM_SEATS).collect();
+            shuffle_seats(&mut seats, &mut rng);
+            for (position, &seat) in seats.iter().enumerate() {
+                counts[position][seat] += 1;
+            }
+        }
+
+        let expected = (NUM_SHUFFLES / NUM_SEATS) as f64;
+        for (position, row) in counts.iter().enumerate() {
+            let chi_square: f64 = row
+                .iter()
+                .map(|&count| (count as f64 - expected).powi(2) / expected)
+                .sum();
+            assert!(
+                chi_square < CRITICAL_VALUE,
+                "Position {position} isn't uniform: chi-square {chi_square:.2}, counts {row:?}"
+            );
+        }
+    }
 }
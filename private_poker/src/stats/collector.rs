@@ -0,0 +1,120 @@
+//! Collector counting what players did in completed hands.
+
+use super::models::StatsCounters;
+use crate::game::entities::{HandHistory, HistoryAction, Street, Username};
+use std::collections::HashMap;
+
+/// Stats of the humans playing at a table, counted from each completed
+/// hand's history
+#[derive(Debug, Default)]
+pub struct PlayerStats {
+    /// Counters for each user since the table opened
+    counters: HashMap<i64, StatsCounters>,
+}
+
+impl PlayerStats {
+    /// Create an empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a completed hand toward the stats of the humans that played
+    /// it. Players without a user ID (i.e., bots) aren't counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - History of the completed hand
+    /// * `user_ids` - User IDs of the humans at the table
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(i64, StatsCounters)>` - What each human did in the hand
+    pub fn record_hand(
+        &mut self,
+        hand: &HandHistory,
+        user_ids: &HashMap<Username, i64>,
+    ) -> Vec<(i64, StatsCounters)> {
+        let mut hand_stats = Vec::new();
+        for (username, counters) in count_hand(hand) {
+            if let Some(&user_id) = user_ids.get(&username) {
+                *self.counters.entry(user_id).or_default() += counters;
+                hand_stats.push((user_id, counters));
+            }
+        }
+        hand_stats.sort_by_key(|(user_id, _)| *user_id);
+        hand_stats
+    }
+
+    /// Get a user's counters since the table opened
+    pub fn get(&self, user_id: i64) -> Option<&StatsCounters> {
+        self.counters.get(&user_id)
+    }
+}
+
+/// Count what every player dealt into a hand did in it
+fn count_hand(hand: &HandHistory) -> HashMap<Username, StatsCounters> {
+    let mut stats: HashMap<Username, StatsCounters> = hand
+        .seats
+        .iter()
+        .map(|seat| {
+            let counters = StatsCounters {
+                hands_played: 1,
+                ..Default::default()
+            };
+            (seat.username.clone(), counters)
+        })
+        .collect();
+
+    // Forced bets are what players start the preflop betting with, so a
+    // bet is only a raise if it puts in more than anyone has so far
+    let mut invested: HashMap<&Username, u64> = HashMap::new();
+    for bet in &hand.forced_bets {
+        *invested.entry(&bet.username).or_default() += u64::from(bet.amount);
+    }
+    let mut preflop_max = invested.values().copied().max().unwrap_or(0);
+    for record in &hand.actions {
+        let total = invested.entry(&record.username).or_default();
+        *total += u64::from(record.amount);
+        if record.street != Street::Preflop {
+            continue;
+        }
+        let Some(counters) = stats.get_mut(&record.username) else {
+            continue;
+        };
+        let voluntary = matches!(
+            record.action,
+            HistoryAction::Call | HistoryAction::Raise | HistoryAction::AllIn
+        ) && record.amount > 0;
+        if voluntary {
+            counters.vpip_hands = 1;
+        }
+        if voluntary && *total > preflop_max {
+            counters.pfr_hands = 1;
+        }
+        preflop_max = preflop_max.max(*total);
+    }
+
+    for record in &hand.showdown {
+        if let Some(counters) = stats.get_mut(&record.username) {
+            counters.showdown_hands = 1;
+        }
+    }
+
+    let mut won: HashMap<&Username, u64> = HashMap::new();
+    for award in &hand.pot_awards {
+        *won.entry(&award.username).or_default() += u64::from(award.amount);
+    }
+    let big_blind = i64::from(hand.blinds.big.max(1));
+    for (username, counters) in &mut stats {
+        let won = won.get(username).copied().unwrap_or(0);
+        if won > 0 {
+            counters.hands_won = 1;
+        }
+        let invested = invested.get(username).copied().unwrap_or(0);
+        let net_chips = won as i64 - invested as i64;
+        counters.net_centi_bb = net_chips * 100 / big_blind;
+        counters.net_chips = net_chips;
+        counters.biggest_pot = won as i64;
+    }
+    stats
+}
@@ -0,0 +1,45 @@
+//! Player statistics module tracking how players play their hands.
+//!
+//! This module provides:
+//! - A per-table collector counting what each human did in completed hands
+//! - VPIP (voluntarily put money in pot), PFR (preflop raise), showdown,
+//!   and win counters
+//! - Per-user, per-day counters persisted to the database
+//! - Summaries over a window of days, with win-rate in big blinds per 100
+//!   hands (bb/100)
+//! - Leaderboards ranking players by net chips won, biggest pot, or hands
+//!   played over a period
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use private_poker::stats::StatsRepository;
+//! use private_poker::db::Database;
+//! use std::sync::Arc;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let db = Database::new(&Default::default()).await?;
+//!     let stats = StatsRepository::new(Arc::new(db.pool().clone()));
+//!
+//!     // Summarize the last 30 days of hands
+//!     let summary = stats.get_stats(1, 30).await?;
+//!     println!(
+//!         "VPIP {:.1}%, PFR {:.1}%, {:.2} bb/100",
+//!         summary.vpip_pct, summary.pfr_pct, summary.bb_per_100
+//!     );
+//!
+//!     Ok(())
+//! }
+//! ```
+
+pub mod collector;
+pub mod models;
+pub mod repository;
+
+pub use collector::PlayerStats;
+pub use models::{
+    LeaderboardEntry, LeaderboardMetric, LeaderboardPage, LeaderboardPeriod, LeaderboardQuery,
+    PlayerStatsSummary, StatsCounters,
+};
+pub use repository::StatsRepository;
@@ -0,0 +1,198 @@
+//! Player statistics data models.
+
+use serde::{Deserialize, Serialize};
+
+/// Counters of what a player did over some number of hands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsCounters {
+    pub hands_played: i64,
+    /// Hands the player voluntarily put money in preflop (blinds and
+    /// antes don't count)
+    pub vpip_hands: i64,
+    /// Hands the player raised preflop
+    pub pfr_hands: i64,
+    pub showdown_hands: i64,
+    /// Hands the player won at least part of a pot in
+    pub hands_won: i64,
+    /// Chips won minus chips put in, in hundredths of a big blind
+    pub net_centi_bb: i64,
+    /// Chips won minus chips put in
+    pub net_chips: i64,
+    /// Most chips won in a single hand
+    pub biggest_pot: i64,
+}
+
+impl std::ops::AddAssign for StatsCounters {
+    fn add_assign(&mut self, other: Self) {
+        self.hands_played += other.hands_played;
+        self.vpip_hands += other.vpip_hands;
+        self.pfr_hands += other.pfr_hands;
+        self.showdown_hands += other.showdown_hands;
+        self.hands_won += other.hands_won;
+        self.net_centi_bb += other.net_centi_bb;
+        self.net_chips += other.net_chips;
+        self.biggest_pot = self.biggest_pot.max(other.biggest_pot);
+    }
+}
+
+/// A player's stats over a window of days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerStatsSummary {
+    pub user_id: i64,
+    /// Number of days the stats cover, ending today
+    pub window_days: u32,
+    pub hands_played: i64,
+    /// Percentage of hands the player voluntarily put money in preflop
+    pub vpip_pct: f64,
+    /// Percentage of hands the player raised preflop
+    pub pfr_pct: f64,
+    /// Percentage of hands the player went to showdown
+    pub showdown_pct: f64,
+    /// Percentage of hands the player won at least part of a pot in
+    pub won_pct: f64,
+    /// Big blinds won per 100 hands
+    pub bb_per_100: f64,
+}
+
+impl PlayerStatsSummary {
+    /// Summarize counters from the window of days
+    #[must_use]
+    pub fn new(user_id: i64, window_days: u32, counters: &StatsCounters) -> Self {
+        let pct = |count: i64| {
+            if counters.hands_played > 0 {
+                100.0 * count as f64 / counters.hands_played as f64
+            } else {
+                0.0
+            }
+        };
+        Self {
+            user_id,
+            window_days,
+            hands_played: counters.hands_played,
+            vpip_pct: pct(counters.vpip_hands),
+            pfr_pct: pct(counters.pfr_hands),
+            showdown_pct: pct(counters.showdown_hands),
+            won_pct: pct(counters.hands_won),
+            // Hundredths of a big blind per hand is big blinds per 100 hands
+            bb_per_100: if counters.hands_played > 0 {
+                counters.net_centi_bb as f64 / counters.hands_played as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Time window a leaderboard ranks players over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardPeriod {
+    /// Today
+    Day,
+    /// The last 7 days, including today
+    #[default]
+    Week,
+    /// The last 30 days, including today
+    Month,
+    /// Every day with stats
+    AllTime,
+}
+
+impl LeaderboardPeriod {
+    /// Number of days the period covers, ending today, or `None` for all
+    /// time
+    #[must_use]
+    pub fn days(self) -> Option<u32> {
+        match self {
+            Self::Day => Some(1),
+            Self::Week => Some(7),
+            Self::Month => Some(30),
+            Self::AllTime => None,
+        }
+    }
+}
+
+/// What a leaderboard ranks players by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    /// Chips won minus chips put in
+    #[default]
+    NetChips,
+    /// Most chips won in a single hand
+    BiggestPot,
+    HandsPlayed,
+}
+
+/// Which page of which leaderboard to get
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeaderboardQuery {
+    pub period: LeaderboardPeriod,
+    pub metric: LeaderboardMetric,
+    /// Maximum number of players to return
+    pub limit: i64,
+    /// Number of players to skip
+    pub offset: i64,
+}
+
+impl Default for LeaderboardQuery {
+    fn default() -> Self {
+        Self {
+            period: LeaderboardPeriod::default(),
+            metric: LeaderboardMetric::default(),
+            limit: 25,
+            offset: 0,
+        }
+    }
+}
+
+/// A player's place on a leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// 1-based rank, shared by players with the same value
+    pub rank: i64,
+    pub user_id: i64,
+    pub username: String,
+    /// The player's value of the leaderboard's metric
+    pub value: i64,
+}
+
+/// A page of a leaderboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardPage {
+    pub entries: Vec<LeaderboardEntry>,
+    /// Number of ranked players across all pages
+    pub total: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_percentages() {
+        let counters = StatsCounters {
+            hands_played: 200,
+            vpip_hands: 50,
+            pfr_hands: 30,
+            showdown_hands: 20,
+            hands_won: 40,
+            net_centi_bb: 1_000,
+            ..Default::default()
+        };
+        let summary = PlayerStatsSummary::new(1, 30, &counters);
+        assert_eq!(summary.hands_played, 200);
+        assert!((summary.vpip_pct - 25.0).abs() < f64::EPSILON);
+        assert!((summary.pfr_pct - 15.0).abs() < f64::EPSILON);
+        assert!((summary.showdown_pct - 10.0).abs() < f64::EPSILON);
+        assert!((summary.won_pct - 20.0).abs() < f64::EPSILON);
+        // 10 big blinds won over 200 hands
+        assert!((summary.bb_per_100 - 5.0).abs() < f64::EPSILON);
+
+        let empty = PlayerStatsSummary::new(1, 30, &StatsCounters::default());
+        assert_eq!(empty.hands_played, 0);
+        assert_eq!(empty.vpip_pct, 0.0);
+        assert_eq!(empty.bb_per_100, 0.0);
+    }
+}
@@ -0,0 +1,199 @@
+//! Player statistics persistence.
+
+use super::models::{
+    LeaderboardEntry, LeaderboardMetric, LeaderboardPage, LeaderboardQuery, PlayerStatsSummary,
+    StatsCounters,
+};
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+
+/// Repository for the per-user, per-day stats counters
+#[derive(Clone)]
+pub struct StatsRepository {
+    pool: Arc<PgPool>,
+}
+
+impl StatsRepository {
+    /// Create a new stats repository
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Add what each user did in a completed hand to their counters for
+    /// today. Every user's counters are updated together, or not at all.
+    ///
+    /// # Arguments
+    ///
+    /// * `hand_stats` - User IDs and what they did in the hand
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), sqlx::Error>` - Success or database error
+    pub async fn record_hand(
+        &self,
+        hand_stats: &[(i64, StatsCounters)],
+    ) -> Result<(), sqlx::Error> {
+        if hand_stats.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for (user_id, counters) in hand_stats {
+            sqlx::query(
+                "INSERT INTO player_stats (
+                     user_id, hands_played, vpip_hands, pfr_hands, showdown_hands, hands_won,
+                     net_centi_bb, net_chips, biggest_pot
+                 ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                 ON CONFLICT (user_id, stat_date) DO UPDATE SET
+                     hands_played = player_stats.hands_played + EXCLUDED.hands_played,
+                     vpip_hands = player_stats.vpip_hands + EXCLUDED.vpip_hands,
+                     pfr_hands = player_stats.pfr_hands + EXCLUDED.pfr_hands,
+                     showdown_hands = player_stats.showdown_hands + EXCLUDED.showdown_hands,
+                     hands_won = player_stats.hands_won + EXCLUDED.hands_won,
+                     net_centi_bb = player_stats.net_centi_bb + EXCLUDED.net_centi_bb,
+                     net_chips = player_stats.net_chips + EXCLUDED.net_chips,
+                     biggest_pot = GREATEST(player_stats.biggest_pot, EXCLUDED.biggest_pot),
+                     updated_at = NOW()",
+            )
+            .bind(user_id)
+            .bind(counters.hands_played)
+            .bind(counters.vpip_hands)
+            .bind(counters.pfr_hands)
+            .bind(counters.showdown_hands)
+            .bind(counters.hands_won)
+            .bind(counters.net_centi_bb)
+            .bind(counters.net_chips)
+            .bind(counters.biggest_pot)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Summarize a user's stats over the last `window_days` days,
+    /// including today
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User ID
+    /// * `window_days` - Number of days to summarize
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PlayerStatsSummary, sqlx::Error>` - Stats summary or database error
+    pub async fn get_stats(
+        &self,
+        user_id: i64,
+        window_days: u32,
+    ) -> Result<PlayerStatsSummary, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT COALESCE(SUM(hands_played), 0)::BIGINT AS hands_played,
+                    COALESCE(SUM(vpip_hands), 0)::BIGINT AS vpip_hands,
+                    COALESCE(SUM(pfr_hands), 0)::BIGINT AS pfr_hands,
+                    COALESCE(SUM(showdown_hands), 0)::BIGINT AS showdown_hands,
+                    COALESCE(SUM(hands_won), 0)::BIGINT AS hands_won,
+                    COALESCE(SUM(net_centi_bb), 0)::BIGINT AS net_centi_bb,
+                    COALESCE(SUM(net_chips), 0)::BIGINT AS net_chips,
+                    COALESCE(MAX(biggest_pot), 0)::BIGINT AS biggest_pot
+             FROM player_stats
+             WHERE user_id = $1 AND stat_date > CURRENT_DATE - $2::INT",
+        )
+        .bind(user_id)
+        .bind(i32::try_from(window_days).unwrap_or(i32::MAX))
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        let counters = StatsCounters {
+            hands_played: row.get("hands_played"),
+            vpip_hands: row.get("vpip_hands"),
+            pfr_hands: row.get("pfr_hands"),
+            showdown_hands: row.get("showdown_hands"),
+            hands_won: row.get("hands_won"),
+            net_centi_bb: row.get("net_centi_bb"),
+            net_chips: row.get("net_chips"),
+            biggest_pot: row.get("biggest_pot"),
+        };
+        Ok(PlayerStatsSummary::new(user_id, window_days, &counters))
+    }
+
+    /// Get a page of a leaderboard. Players are ranked in the database, so
+    /// only the requested page is loaded. Players with frozen wallets and
+    /// bots (users with an unrevoked API key) aren't ranked.
+    ///
+    /// Players with the same value share a rank, and are ordered by user ID
+    /// so pages don't overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Period, metric, and page to get
+    ///
+    /// # Returns
+    ///
+    /// * `Result<LeaderboardPage, sqlx::Error>` - Leaderboard page or database error
+    pub async fn get_leaderboard(
+        &self,
+        query: &LeaderboardQuery,
+    ) -> Result<LeaderboardPage, sqlx::Error> {
+        // Only ever one of these fixed expressions is formatted into the query
+        let value = match query.metric {
+            LeaderboardMetric::NetChips => "SUM(ps.net_chips)",
+            LeaderboardMetric::BiggestPot => "MAX(ps.biggest_pot)",
+            LeaderboardMetric::HandsPlayed => "SUM(ps.hands_played)",
+        };
+        let ranked = format!(
+            "SELECT ps.user_id, {value}::BIGINT AS value
+             FROM player_stats ps
+             JOIN users u ON u.id = ps.user_id
+             LEFT JOIN wallets w ON w.user_id = ps.user_id
+             WHERE ($1::INT IS NULL OR ps.stat_date > CURRENT_DATE - $1::INT)
+               AND u.is_active
+               AND NOT COALESCE(w.frozen, FALSE)
+               AND NOT EXISTS (
+                   SELECT 1 FROM api_keys k
+                   WHERE k.user_id = ps.user_id AND k.revoked_at IS NULL
+               )
+             GROUP BY ps.user_id
+             HAVING SUM(ps.hands_played) > 0"
+        );
+        let days = query
+            .period
+            .days()
+            .map(|days| i32::try_from(days).unwrap_or(i32::MAX));
+
+        let rows = sqlx::query(&format!(
+            "SELECT r.user_id, u.username, r.value,
+                    RANK() OVER (ORDER BY r.value DESC) AS rank
+             FROM ({ranked}) r
+             JOIN users u ON u.id = r.user_id
+             ORDER BY r.value DESC, r.user_id
+             LIMIT $2 OFFSET $3"
+        ))
+        .bind(days)
+        .bind(query.limit)
+        .bind(query.offset)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM ({ranked}) r"))
+            .bind(days)
+            .fetch_one(self.pool.as_ref())
+            .await?;
+
+        let entries = rows
+            .iter()
+            .map(|row| LeaderboardEntry {
+                rank: row.get("rank"),
+                user_id: row.get("user_id"),
+                username: row.get("username"),
+                value: row.get("value"),
+            })
+            .collect();
+        Ok(LeaderboardPage { entries, total })
+    }
+}
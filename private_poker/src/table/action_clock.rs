@@ -0,0 +1,194 @@
+//! Per-turn action clock with an optional time bank.
+
+use crate::game::entities::Username;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Turn that's currently being timed.
+#[derive(Debug)]
+struct Turn {
+    username: Username,
+    started_at: Instant,
+}
+
+/// Action clock tracking how long the player to act has been thinking.
+///
+/// Each turn gets `timeout` to act. Once that elapses, the player's time
+/// bank is consumed before the turn is considered expired. Time used from
+/// the bank is only deducted once the turn ends, so a player acting in
+/// overtime keeps whatever's left of their bank for later turns.
+///
+/// The clock never reads the system time itself. Callers pass in `now`
+/// so the clock can be driven by a controlled clock in tests.
+#[derive(Debug)]
+pub struct ActionClock {
+    timeout: Duration,
+    time_bank: Duration,
+    time_banks: HashMap<Username, Duration>,
+    turn: Option<Turn>,
+}
+
+impl ActionClock {
+    /// Create a new action clock
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Time each player has to act on their turn
+    /// * `time_bank` - Extra time each player can use across all their turns
+    pub fn new(timeout: Duration, time_bank: Duration) -> Self {
+        Self {
+            timeout,
+            time_bank,
+            time_banks: HashMap::new(),
+            turn: None,
+        }
+    }
+
+    /// Update the clock with the player whose turn it is. The clock
+    /// restarts when the turn passes to another player and is cancelled
+    /// when it's no one's turn (e.g., the hand ended early).
+    pub fn update(&mut self, username: Option<&Username>, now: Instant) {
+        let is_same_turn = match (&self.turn, username) {
+            (Some(turn), Some(username)) => &turn.username == username,
+            _ => false,
+        };
+        if is_same_turn {
+            return;
+        }
+        self.finish_turn(now);
+        self.turn = username.map(|username| Turn {
+            username: username.clone(),
+            started_at: now,
+        });
+    }
+
+    /// Return whether the current turn has used up its timeout and the
+    /// player's time bank.
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.remaining(now)
+            .is_some_and(|remaining| remaining.is_zero())
+    }
+
+    /// Return how much time the player to act has left, including their
+    /// time bank, or nothing if no turn is being timed.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.turn.as_ref().map(|turn| {
+            let elapsed = now.saturating_duration_since(turn.started_at);
+            (self.timeout + self.get_time_bank(&turn.username)).saturating_sub(elapsed)
+        })
+    }
+
+    /// Return how much of the user's time bank is left.
+    pub fn get_time_bank(&self, username: &Username) -> Duration {
+        self.time_banks
+            .get(username)
+            .copied()
+            .unwrap_or(self.time_bank)
+    }
+
+    /// Forget a user's time bank (e.g., when they leave the table).
+    pub fn remove_user(&mut self, username: &Username) {
+        self.time_banks.remove(username);
+    }
+
+    /// End the current turn, deducting any overtime from the player's
+    /// time bank.
+    fn finish_turn(&mut self, now: Instant) {
+        if let Some(turn) = self.turn.take() {
+            let elapsed = now.saturating_duration_since(turn.started_at);
+            let overtime = elapsed.saturating_sub(self.timeout);
+            if !overtime.is_zero() {
+                let time_bank = self.get_time_bank(&turn.username);
+                self.time_banks
+                    .insert(turn.username, time_bank.saturating_sub(overtime));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        GameStateManagement, PhaseIndependentUserManagement, PokerState, entities::Action,
+    };
+
+    fn init_state_at_take_action() -> PokerState {
+        let mut state = PokerState::new();
+        for i in 0..2 {
+            let username = i.to_string().into();
+            state.new_user(&username).unwrap();
+            state.waitlist_user(&username).unwrap();
+        }
+        state.init_start(&Username::new("0")).unwrap();
+        while !matches!(state, PokerState::TakeAction(_)) {
+            state = state.step();
+        }
+        state
+    }
+
+    #[test]
+    fn test_timeout_folds_player() {
+        let mut state = init_state_at_take_action();
+        let mut clock = ActionClock::new(Duration::from_secs(30), Duration::ZERO);
+        let start = Instant::now();
+
+        let username = state.get_next_action_username().unwrap();
+        clock.update(Some(&username), start);
+        assert!(!clock.is_expired(start + Duration::from_secs(29)));
+
+        let now = start + Duration::from_secs(31);
+        assert!(clock.is_expired(now));
+        assert_eq!(state.take_timeout_action(&username), Ok(Action::Fold));
+        assert!(
+            state
+                .drain_events()
+                .iter()
+                .any(|event| event.to_string() == format!("{username} ran out of time and folds"))
+        );
+
+        // Heads-up, so the fold ends the hand and the clock is cancelled.
+        clock.update(state.get_next_action_username().as_ref(), now);
+        assert_eq!(clock.remaining(now), None);
+        assert!(!clock.is_expired(now + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_time_bank_consumed_before_timeout() {
+        let username = Username::new("alice");
+        let mut clock = ActionClock::new(Duration::from_secs(30), Duration::from_secs(10));
+        let start = Instant::now();
+
+        clock.update(Some(&username), start);
+        assert!(!clock.is_expired(start + Duration::from_secs(35)));
+        assert!(clock.is_expired(start + Duration::from_secs(40)));
+
+        // Act 5 seconds into the time bank, leaving 5 seconds for later.
+        let now = start + Duration::from_secs(35);
+        clock.update(None, now);
+        assert_eq!(clock.get_time_bank(&username), Duration::from_secs(5));
+
+        clock.update(Some(&username), now);
+        assert_eq!(
+            clock.remaining(now + Duration::from_secs(30)),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn test_clock_resets_when_turn_passes() {
+        let alice = Username::new("alice");
+        let bob = Username::new("bob");
+        let mut clock = ActionClock::new(Duration::from_secs(30), Duration::ZERO);
+        let start = Instant::now();
+
+        clock.update(Some(&alice), start);
+        let now = start + Duration::from_secs(20);
+        clock.update(Some(&alice), now);
+        assert_eq!(clock.remaining(now), Some(Duration::from_secs(10)));
+
+        clock.update(Some(&bob), now);
+        assert_eq!(clock.remaining(now), Some(Duration::from_secs(30)));
+        assert!(!clock.is_expired(now + Duration::from_secs(29)));
+    }
+}
@@ -1,6 +1,41 @@
 //! Table configuration models.
 
+use crate::{
+    game::entities::{BettingStructure, GameVariant},
+    tournament::BlindLevel,
+};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Most hands a table can make players wait between top-ups
+pub const MAX_TOP_UP_COOLDOWN_HANDS: u8 = 100;
+
+/// Invalid table configuration
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConfigError {
+    /// A field has a value tables can't be created with
+    #[error("Invalid {field}: {reason}")]
+    InvalidField { field: &'static str, reason: String },
+}
+
+impl ConfigError {
+    fn invalid(field: &'static str, reason: impl Into<String>) -> Self {
+        Self::InvalidField {
+            field,
+            reason: reason.into(),
+        }
+    }
+
+    /// Name of the offending field
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::InvalidField { field, .. } => field,
+        }
+    }
+}
+
+/// Card back styles clients know how to render
+pub const DECK_STYLES: &[&str] = &["classic", "four_color", "midnight", "royal"];
 
 /// Table speed variants
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -28,6 +63,7 @@ pub enum BotDifficulty {
     Easy,     // Loose-passive, high VPIP (45%), low aggression
     Standard, // Balanced, moderate VPIP (30%), TAG-style
     Tag,      // Tight-aggressive, low VPIP (20%), high aggression
+    Gto,      // Mixed strategies from equity vs. pot odds, randomized sizing
 }
 
 impl std::fmt::Display for BotDifficulty {
@@ -36,6 +72,7 @@ impl std::fmt::Display for BotDifficulty {
             BotDifficulty::Easy => write!(f, "easy"),
             BotDifficulty::Standard => write!(f, "standard"),
             BotDifficulty::Tag => write!(f, "tag"),
+            BotDifficulty::Gto => write!(f, "gto"),
         }
     }
 }
@@ -49,12 +86,20 @@ pub struct TableConfig {
     /// Maximum number of players (default: 10)
     pub max_players: usize,
 
+    /// Maximum number of spectators (0 disables spectating)
+    #[serde(default = "default_max_spectators")]
+    pub max_spectators: usize,
+
     /// Small blind amount
     pub small_blind: i64,
 
     /// Big blind amount
     pub big_blind: i64,
 
+    /// Ante collected from every player before the blinds (0 disables antes)
+    #[serde(default)]
+    pub ante: i64,
+
     /// Minimum buy-in in big blinds (e.g., 20 BB)
     pub min_buy_in_bb: u8,
 
@@ -67,9 +112,35 @@ pub struct TableConfig {
     /// Top-up cooldown in hands (e.g., 20 hands between top-ups)
     pub top_up_cooldown_hands: u8,
 
+    /// Whether buy-ins and top-ups are capped at the deepest stack at the
+    /// table (but never below the minimum buy-in)
+    #[serde(default)]
+    pub match_biggest_stack: bool,
+
+    /// Hands a player can sit out before they're removed from the table
+    /// (0 lets players sit out indefinitely)
+    #[serde(default = "default_max_sit_out_hands")]
+    pub max_sit_out_hands: u8,
+
+    /// Seated players needed to start a hand automatically, after a short
+    /// countdown (0 waits for a player to start it)
+    #[serde(default)]
+    pub auto_start_min_players: usize,
+
     /// Table speed
     pub speed: TableSpeed,
 
+    /// Per-player time bank in seconds, used once the action timeout
+    /// runs out (0 disables the time bank)
+    #[serde(default)]
+    pub time_bank_secs: u32,
+
+    /// Delay between streets when the rest of the board is dealt with
+    /// every player all-in, so clients can reveal it street by street
+    /// (0 deals a street every tick)
+    #[serde(default)]
+    pub allin_runout_delay_ms: u32,
+
     /// Whether bots are enabled
     pub bots_enabled: bool,
 
@@ -79,6 +150,53 @@ pub struct TableConfig {
     /// Bot difficulty preset
     pub bot_difficulty: BotDifficulty,
 
+    /// Whether the under-the-gun player can post a straddle (2x big blind)
+    #[serde(default)]
+    pub straddle_allowed: bool,
+
+    /// Whether all-in pots can be run twice when every player in the pot agrees
+    #[serde(default)]
+    pub run_it_twice_enabled: bool,
+
+    /// Whether players can see the rest of the board after a hand ends early
+    #[serde(default)]
+    pub rabbit_hunt_enabled: bool,
+
+    /// Poker variant played at the table
+    #[serde(default)]
+    pub variant: GameVariant,
+
+    /// Limits on bet and raise sizes (no-limit, pot-limit, or fixed-limit)
+    #[serde(default)]
+    pub betting_structure: BettingStructure,
+
+    /// Rake taken from pots that see a flop, in basis points (0 disables rake)
+    #[serde(default)]
+    pub rake_bps: u32,
+
+    /// Most rake taken from a single hand in chips (0 leaves the rake uncapped)
+    #[serde(default)]
+    pub rake_cap_chips: i64,
+
+    /// Whether players need a verified email to join
+    #[serde(default)]
+    pub require_verified_email: bool,
+
+    /// Blind levels the table moves through over time, starting at the
+    /// first level (`None` keeps the blinds fixed)
+    #[serde(default)]
+    pub blind_schedule: Option<Vec<BlindLevel>>,
+
+    /// Card back style clients render at the table (`None` for the
+    /// default). Styles not in `DECK_STYLES` fall back to the default.
+    #[serde(default)]
+    pub deck_style: Option<String>,
+
+    /// Keep the table open while it's empty instead of closing it once
+    /// it has been idle for a while
+    #[serde(default)]
+    pub persistent: bool,
+
     /// Whether table is private (requires passphrase or invite)
     pub is_private: bool,
 
@@ -92,21 +210,47 @@ pub struct TableConfig {
     pub invite_expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+fn default_max_spectators() -> usize {
+    50
+}
+
+fn default_max_sit_out_hands() -> u8 {
+    10
+}
+
 impl Default for TableConfig {
     fn default() -> Self {
         Self {
             name: "Default Table".to_string(),
             max_players: 10,
+            max_spectators: default_max_spectators(),
             small_blind: 50,
             big_blind: 100,
+            ante: 0,
             min_buy_in_bb: 20,
             max_buy_in_bb: 100,
             absolute_chip_cap: 100_000,
             top_up_cooldown_hands: 20,
+            match_biggest_stack: false,
+            max_sit_out_hands: default_max_sit_out_hands(),
+            auto_start_min_players: 0,
             speed: TableSpeed::Normal,
+            time_bank_secs: 0,
+            allin_runout_delay_ms: 0,
             bots_enabled: true,
             target_bot_count: 5,
             bot_difficulty: BotDifficulty::Standard,
+            straddle_allowed: false,
+            run_it_twice_enabled: false,
+            rabbit_hunt_enabled: false,
+            variant: GameVariant::TexasHoldem,
+            betting_structure: BettingStructure::NoLimit,
+            rake_bps: 0,
+            rake_cap_chips: 0,
+            require_verified_email: false,
+            blind_schedule: None,
+            deck_style: None,
+            persistent: false,
             is_private: false,
             passphrase_hash: None,
             invite_token: None,
@@ -117,21 +261,140 @@ impl Default for TableConfig {
 
 impl TableConfig {
     /// Validate configuration
-    pub fn validate(&self) -> Result<(), String> {
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), ConfigError>` - Success, or the first invalid field
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.small_blind <= 0 {
+            return Err(ConfigError::invalid("small_blind", "must be positive"));
+        }
+
         if self.big_blind <= self.small_blind {
-            return Err("Big blind must be greater than small blind".to_string());
+            return Err(ConfigError::invalid(
+                "big_blind",
+                "must be greater than the small blind",
+            ));
+        }
+
+        if self.ante < 0 || self.ante >= self.big_blind {
+            return Err(ConfigError::invalid(
+                "ante",
+                "must be non-negative and less than the big blind",
+            ));
+        }
+
+        if self.min_buy_in_bb == 0 {
+            return Err(ConfigError::invalid("min_buy_in_bb", "must be positive"));
+        }
+
+        if self.max_buy_in_bb < self.min_buy_in_bb {
+            return Err(ConfigError::invalid(
+                "max_buy_in_bb",
+                "must be at least the min buy-in",
+            ));
         }
 
-        if self.max_buy_in_bb <= self.min_buy_in_bb {
-            return Err("Max buy-in must be greater than min buy-in".to_string());
+        if self.top_up_cooldown_hands > MAX_TOP_UP_COOLDOWN_HANDS {
+            return Err(ConfigError::invalid(
+                "top_up_cooldown_hands",
+                format!("can't be more than {MAX_TOP_UP_COOLDOWN_HANDS} hands"),
+            ));
         }
 
         if self.max_players == 0 || self.max_players > 23 {
-            return Err("Max players must be between 1 and 23".to_string());
+            return Err(ConfigError::invalid(
+                "max_players",
+                "must be between 1 and 23",
+            ));
+        }
+
+        if self.auto_start_min_players == 1 || self.auto_start_min_players > self.max_players {
+            return Err(ConfigError::invalid(
+                "auto_start_min_players",
+                format!(
+                    "must be between 2 and {} players (or 0 to disable auto-start)",
+                    self.max_players
+                ),
+            ));
+        }
+
+        // Every player needs hole cards with enough left for the board,
+        // and for a second board if all-in pots can be run twice.
+        let board_cards = if self.run_it_twice_enabled { 10 } else { 5 };
+        if self.variant.num_hole_cards() * self.max_players + board_cards > self.variant.deck_size()
+        {
+            return Err(ConfigError::invalid(
+                "max_players",
+                format!("is too high for a {} deck", self.variant),
+            ));
+        }
+
+        if let BettingStructure::FixedLimit { small_bet, big_bet } = self.betting_structure
+            && (small_bet == 0 || big_bet < small_bet)
+        {
+            return Err(ConfigError::invalid(
+                "betting_structure",
+                "fixed-limit bets need a positive small bet and a big bet at least as large",
+            ));
+        }
+
+        if self.allin_runout_delay_ms > 10_000 {
+            return Err(ConfigError::invalid(
+                "allin_runout_delay_ms",
+                "can't be more than 10 seconds",
+            ));
+        }
+
+        if self.rake_bps > 10_000 {
+            return Err(ConfigError::invalid(
+                "rake_bps",
+                "can't be more than 10,000 basis points",
+            ));
+        }
+
+        if self.rake_cap_chips < 0 {
+            return Err(ConfigError::invalid(
+                "rake_cap_chips",
+                "must be non-negative",
+            ));
+        }
+
+        if let Some(schedule) = &self.blind_schedule {
+            if schedule.is_empty() {
+                return Err(ConfigError::invalid(
+                    "blind_schedule",
+                    "must have at least one level",
+                ));
+            }
+            for level in schedule {
+                if level.small_blind <= 0 || level.big_blind <= level.small_blind {
+                    return Err(ConfigError::invalid(
+                        "blind_schedule",
+                        format!(
+                            "level {} needs a positive small blind and a bigger big blind",
+                            level.level
+                        ),
+                    ));
+                }
+                let ante = level.ante.unwrap_or(0);
+                if ante < 0 || ante >= level.big_blind {
+                    return Err(ConfigError::invalid(
+                        "blind_schedule",
+                        format!(
+                            "level {} ante must be non-negative and less than the big blind",
+                            level.level
+                        ),
+                    ));
+                }
+            }
         }
 
         if self.absolute_chip_cap <= 0 || self.absolute_chip_cap > 100_000 {
-            return Err("Absolute chip cap must be between 1 and 100,000".to_string());
+            return Err(ConfigError::invalid(
+                "absolute_chip_cap",
+                "must be between 1 and 100,000",
+            ));
         }
 
         Ok(())
@@ -148,6 +411,13 @@ impl TableConfig {
         bb_max.min(self.absolute_chip_cap)
     }
 
+    /// Get the deck style clients render, if it's one they know
+    pub fn deck_style(&self) -> Option<&str> {
+        self.deck_style
+            .as_deref()
+            .filter(|style| DECK_STYLES.contains(style))
+    }
+
     /// Get action timeout based on table speed
     pub fn action_timeout_secs(&self) -> u64 {
         match self.speed {
@@ -157,3 +427,119 @@ impl TableConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_field(config: TableConfig) -> &'static str {
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains(err.field()));
+        err.field()
+    }
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert_eq!(TableConfig::default().validate(), Ok(()));
+
+        // A fixed buy-in is fine
+        let config = TableConfig {
+            min_buy_in_bb: 50,
+            max_buy_in_bb: 50,
+            ..Default::default()
+        };
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_zero_blinds_are_invalid() {
+        let config = TableConfig {
+            small_blind: 0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(config), "small_blind");
+
+        let config = TableConfig {
+            small_blind: 0,
+            big_blind: 0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(config), "small_blind");
+    }
+
+    #[test]
+    fn test_big_blind_must_exceed_small_blind() {
+        for big_blind in [50, 25] {
+            let config = TableConfig {
+                small_blind: 50,
+                big_blind,
+                ..Default::default()
+            };
+            assert_eq!(invalid_field(config), "big_blind");
+        }
+    }
+
+    #[test]
+    fn test_inverted_buy_in_range_is_invalid() {
+        let config = TableConfig {
+            min_buy_in_bb: 100,
+            max_buy_in_bb: 20,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(config), "max_buy_in_bb");
+
+        let config = TableConfig {
+            min_buy_in_bb: 0,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(config), "min_buy_in_bb");
+    }
+
+    #[test]
+    fn test_top_up_cooldown_must_be_sane() {
+        let config = TableConfig {
+            top_up_cooldown_hands: MAX_TOP_UP_COOLDOWN_HANDS + 1,
+            ..Default::default()
+        };
+        assert_eq!(invalid_field(config), "top_up_cooldown_hands");
+
+        let config = TableConfig {
+            top_up_cooldown_hands: MAX_TOP_UP_COOLDOWN_HANDS,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_max_players_must_fit_the_deck() {
+        for (variant, max_players) in [
+            (GameVariant::TexasHoldem, 23),
+            (GameVariant::ShortDeck, 15),
+            (GameVariant::Omaha, 11),
+        ] {
+            let config = TableConfig {
+                variant,
+                max_players,
+                ..Default::default()
+            };
+            assert!(config.validate().is_ok());
+
+            // Running it twice deals a second board from the same deck
+            let config = TableConfig {
+                variant,
+                max_players,
+                run_it_twice_enabled: true,
+                ..Default::default()
+            };
+            assert_eq!(invalid_field(config), "max_players");
+        }
+
+        let config = TableConfig {
+            variant: GameVariant::Omaha,
+            max_players: 10,
+            run_it_twice_enabled: true,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+}
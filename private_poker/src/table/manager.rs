@@ -6,10 +6,22 @@ use super::{
     config::TableConfig,
     messages::{TableMessage, TableResponse, TableStateResponse},
 };
-use crate::wallet::{TableId, WalletManager};
-use sqlx::{PgPool, Row};
-use std::{collections::HashMap, sync::Arc};
-use tokio::sync::{RwLock, oneshot};
+use crate::game::entities::Blinds;
+use crate::net::messages::{SortOrder, StakesTier, TableFilter, TableInfo, TableSortBy};
+use crate::wallet::{ReconcileReport, TableId, WalletManager};
+use sqlx::{PgPool, Row, types::Json};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::{RwLock, oneshot},
+    time::{Instant, timeout_at},
+};
 
 /// Table metadata for discovery
 #[derive(Debug, Clone)]
@@ -22,9 +34,90 @@ pub struct TableMetadata {
     pub big_blind: i64,
     pub is_private: bool,
     pub speed: String,
+    pub bots_enabled: bool,
     pub is_active: bool,
 }
 
+/// One page of a filtered table listing
+#[derive(Debug, Clone)]
+pub struct TablePage {
+    /// Tables on this page
+    pub tables: Vec<TableMetadata>,
+    /// Number of tables matching the filter across all pages
+    pub total: usize,
+}
+
+/// Check whether a table meets a filter's criteria
+fn matches_filter(table: &TableMetadata, filter: &TableFilter) -> bool {
+    filter
+        .stakes_tier
+        .is_none_or(|tier| StakesTier::from_big_blind(table.big_blind) == tier)
+        && filter
+            .min_players
+            .is_none_or(|min| table.player_count >= min)
+        && filter
+            .max_players
+            .is_none_or(|max| table.player_count <= max)
+        && (!filter.has_waitlist_space || table.player_count < table.max_players)
+        && filter
+            .speed
+            .is_none_or(|speed| table.speed == speed.to_string())
+        && filter
+            .bots_enabled
+            .is_none_or(|bots_enabled| table.bots_enabled == bots_enabled)
+        && (!filter.is_private || table.is_private)
+        && filter
+            .name_query
+            .as_deref()
+            .is_none_or(|query| table.name.to_lowercase().contains(&query.to_lowercase()))
+}
+
+/// Apply a filter's criteria, sort order, and pagination to a table listing.
+///
+/// Ties are broken by table ID so the order is stable and consecutive pages
+/// never overlap. Without a sort field, tables keep their listing order.
+pub fn filter_tables(tables: Vec<TableMetadata>, filter: &TableFilter) -> TablePage {
+    let mut tables: Vec<TableMetadata> = tables
+        .into_iter()
+        .filter(|table| matches_filter(table, filter))
+        .collect();
+
+    if let Some(sort_by) = filter.sort_by {
+        tables.sort_by(|a, b| {
+            let ordering = match sort_by {
+                TableSortBy::Players => a.player_count.cmp(&b.player_count),
+                TableSortBy::Stakes => {
+                    (a.big_blind, a.small_blind).cmp(&(b.big_blind, b.small_blind))
+                }
+                TableSortBy::Name => a.name.cmp(&b.name),
+            };
+            let ordering = match filter.order {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            };
+            ordering.then_with(|| a.id.cmp(&b.id))
+        });
+    }
+
+    let total = tables.len();
+    let tables = tables
+        .into_iter()
+        .skip(filter.offset)
+        .take(filter.limit.unwrap_or(usize::MAX))
+        .collect();
+    TablePage { tables, total }
+}
+
+/// When a table last had anyone at it, for closing idle tables
+#[derive(Debug, Clone, Copy)]
+struct TableActivity {
+    /// Last time the table had a player, waitlister, or spectator
+    last_occupied: Instant,
+
+    /// Persistent tables are never closed for being idle
+    persistent: bool,
+}
+
 /// Table manager for managing multiple table instances
 pub struct TableManager {
     /// Database connection pool
@@ -36,11 +129,20 @@ pub struct TableManager {
     /// Active table handles
     tables: Arc<RwLock<HashMap<TableId, TableHandle>>>,
 
-    /// Next table ID (for in-memory tables)
-    next_table_id: Arc<RwLock<TableId>>,
-
     /// Cached player counts (avoids N+1 query on list_tables)
     player_count_cache: Arc<RwLock<HashMap<TableId, usize>>>,
+
+    /// Last time each table was occupied
+    activity: Arc<RwLock<HashMap<TableId, TableActivity>>>,
+
+    /// Whether tables are being drained for shutdown
+    draining: Arc<AtomicBool>,
+
+    /// Most tables users can have open at once (`None` for no limit)
+    max_tables: Option<usize>,
+
+    /// Tables that are open or being created, counted against `max_tables`
+    table_slots: Arc<AtomicUsize>,
 }
 
 impl TableManager {
@@ -59,29 +161,77 @@ impl TableManager {
             pool,
             wallet_manager,
             tables: Arc::new(RwLock::new(HashMap::new())),
-            next_table_id: Arc::new(RwLock::new(1)),
             player_count_cache: Arc::new(RwLock::new(HashMap::new())),
+            activity: Arc::new(RwLock::new(HashMap::new())),
+            draining: Arc::new(AtomicBool::new(false)),
+            max_tables: None,
+            table_slots: Arc::new(AtomicUsize::new(0)),
         }
     }
 
+    /// Limit how many tables can be open at once
+    ///
+    /// Only tables created by users are rejected once the limit is hit.
+    /// Tables created by the server itself (no creator) and tables loaded
+    /// from the database are always opened, but they still take up slots.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_tables` - Most tables that can be open at once
+    ///
+    /// # Returns
+    ///
+    /// * `TableManager` - Table manager enforcing the limit
+    pub fn with_max_tables(mut self, max_tables: usize) -> Self {
+        self.max_tables = Some(max_tables);
+        self
+    }
+
+    /// Take a slot for a new table, failing if a user-created table would
+    /// go over the limit
+    fn reserve_table_slot(&self, user_created: bool) -> Result<(), String> {
+        let limit = self.max_tables.filter(|_| user_created);
+        self.table_slots
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |slots| match limit {
+                Some(max) if slots >= max => None,
+                _ => Some(slots + 1),
+            })
+            .map(|_| ())
+            .map_err(|slots| format!("Table limit reached ({} tables open)", slots))
+    }
+
+    /// Free a table's slot once it's closed or discarded
+    fn release_table_slot(&self) {
+        let _ = self
+            .table_slots
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |slots| {
+                slots.checked_sub(1)
+            });
+    }
+
     /// Load existing tables from database and spawn actors
     ///
     /// Queries the database for all active tables and spawns table actors for them.
-    /// Updates next_table_id to be one more than the highest existing table ID.
     ///
     /// # Returns
     ///
     /// * `Result<usize, String>` - Number of tables loaded, or error
     pub async fn load_existing_tables(&self) -> Result<usize, String> {
+        use crate::game::entities::{BettingStructure, GameVariant};
         use crate::table::config::{BotDifficulty, TableSpeed};
+        use crate::tournament::BlindLevel;
 
         // Query all active tables from database
         let rows = sqlx::query(
             r#"
-            SELECT id, name, max_players, small_blind, big_blind,
+            SELECT id, name, max_players, max_spectators, small_blind, big_blind, ante,
                    min_buy_in_bb, max_buy_in_bb, absolute_chip_cap, top_up_cooldown_hands,
-                   speed, bots_enabled, target_bot_count, bot_difficulty,
-                   is_private, passphrase_hash, invite_token, invite_expires_at
+                   match_biggest_stack, max_sit_out_hands, auto_start_min_players, speed,
+                   time_bank_secs, allin_runout_delay_ms, bots_enabled, target_bot_count,
+                   bot_difficulty, straddle_allowed, run_it_twice_enabled, rabbit_hunt_enabled, variant,
+                   betting_structure, rake_bps, rake_cap_chips, require_verified_email, blind_schedule,
+                   deck_style, persistent, is_private, passphrase_hash, invite_token,
+                   invite_expires_at
             FROM tables
             WHERE is_active = true
             ORDER BY id ASC
@@ -95,12 +245,10 @@ impl TableManager {
             return Ok(0);
         }
 
-        let mut max_id = 0i64;
         let mut loaded_count = 0;
 
         for row in rows {
             let table_id: i64 = row.get("id");
-            max_id = max_id.max(table_id);
 
             // Parse table configuration from database row
             let speed_str: String = row.get("speed");
@@ -114,22 +262,50 @@ impl TableManager {
             let bot_difficulty = match difficulty_str.as_str() {
                 "easy" => BotDifficulty::Easy,
                 "tag" => BotDifficulty::Tag,
+                "gto" => BotDifficulty::Gto,
                 _ => BotDifficulty::Standard,
             };
 
+            let variant_str: String = row.get("variant");
+            let variant = match variant_str.as_str() {
+                "short_deck" => GameVariant::ShortDeck,
+                "omaha" => GameVariant::Omaha,
+                _ => GameVariant::TexasHoldem,
+            };
+
             let config = TableConfig {
                 name: row.get("name"),
                 max_players: row.get::<i32, _>("max_players") as usize,
+                max_spectators: row.get::<i32, _>("max_spectators") as usize,
                 small_blind: row.get("small_blind"),
                 big_blind: row.get("big_blind"),
+                ante: row.get("ante"),
                 min_buy_in_bb: row.get::<i16, _>("min_buy_in_bb") as u8,
                 max_buy_in_bb: row.get::<i16, _>("max_buy_in_bb") as u8,
                 absolute_chip_cap: row.get("absolute_chip_cap"),
                 top_up_cooldown_hands: row.get::<i16, _>("top_up_cooldown_hands") as u8,
+                match_biggest_stack: row.get("match_biggest_stack"),
+                max_sit_out_hands: row.get::<i16, _>("max_sit_out_hands") as u8,
+                auto_start_min_players: row.get::<i16, _>("auto_start_min_players") as usize,
                 speed,
+                time_bank_secs: row.get::<i32, _>("time_bank_secs") as u32,
+                allin_runout_delay_ms: row.get::<i32, _>("allin_runout_delay_ms") as u32,
                 bots_enabled: row.get("bots_enabled"),
                 target_bot_count: row.get::<i16, _>("target_bot_count") as u8,
                 bot_difficulty,
+                straddle_allowed: row.get("straddle_allowed"),
+                run_it_twice_enabled: row.get("run_it_twice_enabled"),
+                rabbit_hunt_enabled: row.get("rabbit_hunt_enabled"),
+                variant,
+                betting_structure: row.get::<Json<BettingStructure>, _>("betting_structure").0,
+                rake_bps: row.get::<i32, _>("rake_bps") as u32,
+                rake_cap_chips: row.get("rake_cap_chips"),
+                require_verified_email: row.get("require_verified_email"),
+                blind_schedule: row
+                    .get::<Option<Json<Vec<BlindLevel>>>, _>("blind_schedule")
+                    .map(|schedule| schedule.0),
+                deck_style: row.get("deck_style"),
+                persistent: row.get("persistent"),
                 is_private: row.get("is_private"),
                 passphrase_hash: row.get("passphrase_hash"),
                 invite_token: row.get("invite_token"),
@@ -138,23 +314,19 @@ impl TableManager {
                     .map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc)),
             };
 
-            // Create and spawn table actor
-            let (actor, handle) = TableActor::new(
+            // Create the table actor and resume any hand it was playing
+            let persistent = config.persistent;
+            let (mut actor, handle) = TableActor::new(
                 table_id,
                 config,
                 self.wallet_manager.clone(),
                 self.pool.clone(),
             );
+            actor.restore_snapshot().await;
 
-            // Store handle
-            let mut tables = self.tables.write().await;
-            tables.insert(table_id, handle.clone());
-            drop(tables);
-
-            // Initialize player count cache to 0 (will be updated by table state queries)
-            let mut cache = self.player_count_cache.write().await;
-            cache.insert(table_id, 0);
-            drop(cache);
+            // Player count cache starts at 0 (updated by table state queries)
+            let _ = self.reserve_table_slot(false);
+            self.publish_table(table_id, handle, 0, persistent).await;
 
             // Spawn actor task
             tokio::spawn(async move {
@@ -165,11 +337,6 @@ impl TableManager {
             loaded_count += 1;
         }
 
-        // Update next_table_id to be one more than the highest existing ID
-        let mut next_id = self.next_table_id.write().await;
-        *next_id = max_id + 1;
-        drop(next_id);
-
         Ok(loaded_count)
     }
 
@@ -188,45 +355,191 @@ impl TableManager {
         config: TableConfig,
         creator_user_id: Option<i64>,
     ) -> Result<TableId, String> {
+        let persistent = config.persistent;
+        let (table_id, actor, handle) = self.insert_table(config, creator_user_id).await?;
+        self.publish_table(table_id, handle, 0, persistent).await;
+
+        // Spawn actor task
+        tokio::spawn(async move {
+            actor.run().await;
+        });
+
+        log::info!("Created and spawned table {}", table_id);
+
+        Ok(table_id)
+    }
+
+    /// Create a table and seat its creator in one step
+    ///
+    /// The table is only listed once the creator is seated. If the join
+    /// fails for any reason (insufficient funds, a failed escrow transfer)
+    /// the actor is shut down and the table and escrow rows are removed, so
+    /// no empty table is left behind.
+    ///
+    /// A passphrase makes the table private; it's hashed into the config and
+    /// used for the creator's own join.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Table configuration
+    /// * `user_id` - User creating and joining the table
+    /// * `username` - Creator's username
+    /// * `buy_in_amount` - Creator's buy-in
+    /// * `passphrase` - Optional passphrase for a private table
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TableId, String>` - Table ID or the reason creation failed
+    pub async fn create_and_join(
+        &self,
+        mut config: TableConfig,
+        user_id: i64,
+        username: String,
+        buy_in_amount: i64,
+        passphrase: Option<String>,
+    ) -> Result<TableId, String> {
+        if let Some(pass) = &passphrase {
+            use argon2::{
+                Argon2, PasswordHasher,
+                password_hash::{SaltString, rand_core::OsRng},
+            };
+
+            let salt = SaltString::generate(&mut OsRng);
+            let hash = Argon2::default()
+                .hash_password(pass.as_bytes(), &salt)
+                .map_err(|_| "Failed to hash passphrase".to_string())?;
+            config.is_private = true;
+            config.passphrase_hash = Some(hash.to_string());
+        }
+
+        let persistent = config.persistent;
+        let (table_id, actor, handle) = self.insert_table(config, Some(user_id)).await?;
+
+        // Run the actor unpublished so no one else can see or join the table
+        // before its creator is seated
+        tokio::spawn(async move {
+            actor.run().await;
+        });
+
+        let (tx, rx) = oneshot::channel();
+        let joined = match handle
+            .send(TableMessage::JoinTable {
+                user_id,
+                username,
+                buy_in_amount,
+                passphrase,
+                idempotency_key: None,
+                response: tx,
+            })
+            .await
+        {
+            Ok(()) => match rx.await {
+                Ok(response) if response.is_success() => Ok(()),
+                Ok(response) => Err(response
+                    .error_message()
+                    .unwrap_or_else(|| "Failed to join table".to_string())),
+                Err(_) => Err("Failed to receive response".to_string()),
+            },
+            Err(e) => Err(format!("Failed to send message: {}", e)),
+        };
+
+        if let Err(e) = joined {
+            self.discard_table(table_id, &handle).await;
+            return Err(e);
+        }
+
+        self.publish_table(table_id, handle, 1, persistent).await;
+
+        log::info!("Created table {} and seated creator {}", table_id, user_id);
+
+        Ok(table_id)
+    }
+
+    /// Validate a config, take a table slot, and write the table and escrow
+    /// rows, returning the actor for the caller to spawn
+    async fn insert_table(
+        &self,
+        config: TableConfig,
+        creator_user_id: Option<i64>,
+    ) -> Result<(TableId, TableActor, TableHandle), String> {
+        if self.draining.load(Ordering::SeqCst) {
+            return Err("Server is shutting down".to_string());
+        }
+
         // Validate configuration
-        config.validate()?;
+        config.validate().map_err(|e| e.to_string())?;
 
-        // Get next table ID
-        let mut next_id = self.next_table_id.write().await;
-        let table_id = *next_id;
-        *next_id += 1;
-        drop(next_id);
+        self.reserve_table_slot(creator_user_id.is_some())?;
+        let result = self.write_table(config, creator_user_id).await;
+        if result.is_err() {
+            self.release_table_slot();
+        }
+        result
+    }
 
-        // Insert table into database
-        sqlx::query(
+    /// Write the table and escrow rows for a config that's already been
+    /// validated, returning the actor for the caller to spawn
+    async fn write_table(
+        &self,
+        config: TableConfig,
+        creator_user_id: Option<i64>,
+    ) -> Result<(TableId, TableActor, TableHandle), String> {
+        // Insert table into database, letting it assign the table ID
+        let table_id: TableId = sqlx::query_scalar(
             r#"
             INSERT INTO tables (
-                id, name, max_players, small_blind, big_blind,
+                name, max_players, max_spectators, small_blind, big_blind, ante,
                 min_buy_in_bb, max_buy_in_bb, absolute_chip_cap, top_up_cooldown_hands,
-                speed, bots_enabled, target_bot_count, bot_difficulty,
-                is_private, passphrase_hash, invite_token, invite_expires_at, creator_user_id
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
+                match_biggest_stack, max_sit_out_hands, auto_start_min_players, speed,
+                time_bank_secs, allin_runout_delay_ms, bots_enabled, target_bot_count,
+                bot_difficulty, straddle_allowed, run_it_twice_enabled, rabbit_hunt_enabled, variant,
+                betting_structure, rake_bps, rake_cap_chips, require_verified_email,
+                blind_schedule, deck_style, persistent, is_private, passphrase_hash,
+                invite_token, invite_expires_at, creator_user_id
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18,
+                $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34,
+                $35
+            )
+            RETURNING id
             "#,
         )
-        .bind(table_id)
         .bind(&config.name)
         .bind(config.max_players as i32)
+        .bind(config.max_spectators as i32)
         .bind(config.small_blind)
         .bind(config.big_blind)
+        .bind(config.ante)
         .bind(config.min_buy_in_bb as i16)
         .bind(config.max_buy_in_bb as i16)
         .bind(config.absolute_chip_cap)
         .bind(config.top_up_cooldown_hands as i16)
+        .bind(config.match_biggest_stack)
+        .bind(config.max_sit_out_hands as i16)
+        .bind(config.auto_start_min_players as i16)
         .bind(config.speed.to_string())
+        .bind(config.time_bank_secs as i32)
+        .bind(config.allin_runout_delay_ms as i32)
         .bind(config.bots_enabled)
         .bind(config.target_bot_count as i16)
         .bind(config.bot_difficulty.to_string())
+        .bind(config.straddle_allowed)
+        .bind(config.run_it_twice_enabled)
+        .bind(config.rabbit_hunt_enabled)
+        .bind(config.variant.to_string())
+        .bind(Json(config.betting_structure))
+        .bind(config.rake_bps as i32)
+        .bind(config.rake_cap_chips)
+        .bind(config.require_verified_email)
+        .bind(config.blind_schedule.as_ref().map(Json))
+        .bind(&config.deck_style)
+        .bind(config.persistent)
         .bind(config.is_private)
         .bind(&config.passphrase_hash)
         .bind(&config.invite_token)
         .bind(config.invite_expires_at.map(|dt| dt.naive_utc()))
         .bind(creator_user_id)
-        .execute(self.pool.as_ref())
+        .fetch_one(self.pool.as_ref())
         .await
         .map_err(|e| format!("Database error: {}", e))?;
 
@@ -237,7 +550,7 @@ impl TableManager {
             .await
             .map_err(|e| format!("Failed to create escrow: {}", e))?;
 
-        // Create and spawn table actor
+        // Create table actor
         let (actor, handle) = TableActor::new(
             table_id,
             config,
@@ -245,24 +558,90 @@ impl TableManager {
             self.pool.clone(),
         );
 
-        // Store handle
+        Ok((table_id, actor, handle))
+    }
+
+    /// Make a table's handle visible to lookups, seed its player count,
+    /// and start its idle clock
+    async fn publish_table(
+        &self,
+        table_id: TableId,
+        handle: TableHandle,
+        player_count: usize,
+        persistent: bool,
+    ) {
         let mut tables = self.tables.write().await;
-        tables.insert(table_id, handle.clone());
+        tables.insert(table_id, handle);
         drop(tables);
 
-        // Initialize player count cache to 0
         let mut cache = self.player_count_cache.write().await;
-        cache.insert(table_id, 0);
+        cache.insert(table_id, player_count);
         drop(cache);
 
-        // Spawn actor task
-        tokio::spawn(async move {
-            actor.run().await;
-        });
+        let activity = TableActivity {
+            last_occupied: Instant::now(),
+            persistent,
+        };
+        self.activity.write().await.insert(table_id, activity);
+    }
 
-        log::info!("Created and spawned table {}", table_id);
+    /// Undo a table that was never published
+    ///
+    /// The actor refunds a failed buy-in itself, so the escrow should be
+    /// empty. If it isn't, the rows are kept (inactive) rather than deleting
+    /// chips along with them.
+    async fn discard_table(&self, table_id: TableId, handle: &TableHandle) {
+        let (tx, rx) = oneshot::channel();
+        if handle
+            .send(TableMessage::Close { response: tx })
+            .await
+            .is_ok()
+        {
+            let _ = rx.await;
+        }
 
-        Ok(table_id)
+        let deleted = sqlx::query("DELETE FROM table_escrows WHERE table_id = $1 AND balance = 0")
+            .bind(table_id)
+            .execute(self.pool.as_ref())
+            .await;
+
+        let removed = match deleted {
+            Ok(result) if result.rows_affected() == 1 => {
+                // Closing doesn't drop the snapshot, so the table's rows go
+                // together
+                let _ = sqlx::query("DELETE FROM table_snapshots WHERE table_id = $1")
+                    .bind(table_id)
+                    .execute(self.pool.as_ref())
+                    .await;
+                sqlx::query("DELETE FROM tables WHERE id = $1")
+                    .bind(table_id)
+                    .execute(self.pool.as_ref())
+                    .await
+                    .is_ok()
+            }
+            Ok(_) => {
+                log::error!(
+                    "Table {} escrow not empty after failed creator join, keeping it",
+                    table_id
+                );
+                false
+            }
+            Err(e) => {
+                log::error!("Failed to delete escrow for table {}: {}", table_id, e);
+                false
+            }
+        };
+
+        self.release_table_slot();
+
+        if !removed
+            && let Err(e) = sqlx::query("UPDATE tables SET is_active = false WHERE id = $1")
+                .bind(table_id)
+                .execute(self.pool.as_ref())
+                .await
+        {
+            log::error!("Failed to deactivate table {}: {}", table_id, e);
+        }
     }
 
     /// Get a table handle
@@ -287,10 +666,11 @@ impl TableManager {
     pub async fn list_tables(&self) -> Result<Vec<TableMetadata>, String> {
         let rows = sqlx::query(
             r#"
-            SELECT id, name, max_players, small_blind, big_blind, is_private, speed, is_active
+            SELECT id, name, max_players, small_blind, big_blind, is_private, speed,
+                   bots_enabled, is_active
             FROM tables
             WHERE is_active = true
-            ORDER BY created_at DESC
+            ORDER BY created_at DESC, id DESC
             "#,
         )
         .fetch_all(self.pool.as_ref())
@@ -317,6 +697,7 @@ impl TableManager {
                 big_blind: row.get("big_blind"),
                 is_private: row.get("is_private"),
                 speed: row.get("speed"),
+                bots_enabled: row.get("bots_enabled"),
                 is_active: row.get("is_active"),
             });
         }
@@ -324,8 +705,25 @@ impl TableManager {
         Ok(metadata_list)
     }
 
+    /// List one page of the active tables matching a filter
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Criteria, sort order, and page to list
+    ///
+    /// # Returns
+    ///
+    /// * `TablePage` - Matching tables on the page and the total match count
+    pub async fn list_tables_page(&self, filter: &TableFilter) -> Result<TablePage, String> {
+        let tables = self.list_tables().await?;
+        Ok(filter_tables(tables, filter))
+    }
+
     /// Close a table
     ///
+    /// A hand in progress is called off, and everyone's chips go back from
+    /// escrow to their wallets before the table is marked inactive.
+    ///
     /// # Arguments
     ///
     /// * `table_id` - Table ID
@@ -334,11 +732,11 @@ impl TableManager {
     ///
     /// * `Result<(), String>` - Success or error
     pub async fn close_table(&self, table_id: TableId) -> Result<(), String> {
-        // Send close message to table
+        // Settle the table so no chips are left behind in its escrow
         if let Some(handle) = self.get_table(table_id).await {
             let (tx, rx) = oneshot::channel();
             handle
-                .send(TableMessage::Close { response: tx })
+                .send(TableMessage::ForceSettle { response: tx })
                 .await
                 .map_err(|e| format!("Failed to send close message: {}", e))?;
 
@@ -347,9 +745,11 @@ impl TableManager {
                 .map_err(|_| "Failed to receive response".to_string())?;
         }
 
-        // Remove from active tables
+        // Remove from active tables, freeing its slot
         let mut tables = self.tables.write().await;
-        tables.remove(&table_id);
+        if tables.remove(&table_id).is_some() {
+            self.release_table_slot();
+        }
         drop(tables);
 
         // Remove from player count cache
@@ -357,6 +757,8 @@ impl TableManager {
         cache.remove(&table_id);
         drop(cache);
 
+        self.activity.write().await.remove(&table_id);
+
         // Mark as inactive in database
         sqlx::query("UPDATE tables SET is_active = false WHERE id = $1")
             .bind(table_id)
@@ -369,6 +771,297 @@ impl TableManager {
         Ok(())
     }
 
+    /// Pause or resume a table
+    ///
+    /// A paused table doesn't start new hands or take actions until it's
+    /// resumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Table ID
+    /// * `paused` - Whether to pause (`true`) or resume (`false`) the table
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TableResponse, String>` - Response or error
+    pub async fn set_paused(
+        &self,
+        table_id: TableId,
+        paused: bool,
+    ) -> Result<TableResponse, String> {
+        let handle = self
+            .get_table(table_id)
+            .await
+            .ok_or_else(|| "Table not found".to_string())?;
+
+        let (tx, rx) = oneshot::channel();
+        let message = if paused {
+            TableMessage::Pause { response: tx }
+        } else {
+            TableMessage::Resume { response: tx }
+        };
+        handle
+            .send(message)
+            .await
+            .map_err(|e| format!("Failed to send message: {}", e))?;
+
+        rx.await
+            .map_err(|_| "Failed to receive response".to_string())
+    }
+
+    /// Raise a table's minimum blinds once its hand in progress is over
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Table ID
+    /// * `blinds` - New minimum small blind, big blind, and ante
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TableResponse, String>` - Response or error
+    pub async fn update_blinds(
+        &self,
+        table_id: TableId,
+        blinds: Blinds,
+    ) -> Result<TableResponse, String> {
+        let handle = self
+            .get_table(table_id)
+            .await
+            .ok_or_else(|| "Table not found".to_string())?;
+
+        let (tx, rx) = oneshot::channel();
+        handle
+            .send(TableMessage::UpdateBlinds {
+                blinds,
+                response: tx,
+            })
+            .await
+            .map_err(|e| format!("Failed to send message: {}", e))?;
+
+        rx.await
+            .map_err(|_| "Failed to receive response".to_string())
+    }
+
+    /// Put a table on a break, or end its break
+    ///
+    /// A table on a break plays out its hand in progress, but doesn't deal
+    /// another until the break is over.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Table ID
+    /// * `on_break` - Whether to start (`true`) or end (`false`) the break
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TableResponse, String>` - Response or error
+    pub async fn set_on_break(
+        &self,
+        table_id: TableId,
+        on_break: bool,
+    ) -> Result<TableResponse, String> {
+        let handle = self
+            .get_table(table_id)
+            .await
+            .ok_or_else(|| "Table not found".to_string())?;
+
+        let (tx, rx) = oneshot::channel();
+        handle
+            .send(TableMessage::SetBreak {
+                on_break,
+                response: tx,
+            })
+            .await
+            .map_err(|e| format!("Failed to send message: {}", e))?;
+
+        rx.await
+            .map_err(|_| "Failed to receive response".to_string())
+    }
+
+    /// Mute or unmute a user in a table's chat
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Table ID
+    /// * `user_id` - User to mute or unmute
+    /// * `muted` - Whether to mute (`true`) or unmute (`false`) the user
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TableResponse, String>` - Response or error
+    pub async fn set_muted(
+        &self,
+        table_id: TableId,
+        user_id: i64,
+        muted: bool,
+    ) -> Result<TableResponse, String> {
+        let handle = self
+            .get_table(table_id)
+            .await
+            .ok_or_else(|| "Table not found".to_string())?;
+
+        let (tx, rx) = oneshot::channel();
+        let message = if muted {
+            TableMessage::MuteUser {
+                user_id,
+                response: tx,
+            }
+        } else {
+            TableMessage::UnmuteUser {
+                user_id,
+                response: tx,
+            }
+        };
+        handle
+            .send(message)
+            .await
+            .map_err(|e| format!("Failed to send message: {}", e))?;
+
+        rx.await
+            .map_err(|_| "Failed to receive response".to_string())
+    }
+
+    /// Drain every table for shutdown
+    ///
+    /// New tables and joins are refused right away. Tables close as soon
+    /// as their hand in progress is over, and tables still mid-hand once
+    /// `timeout` has passed are force-settled. Either way, every player's
+    /// chips go back from escrow to their wallet before the table closes.
+    /// Draining again once the tables are closed does nothing.
+    ///
+    /// Tables stay active in the database, so they're loaded again when
+    /// the server restarts.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait for hands in progress to finish
+    pub async fn drain_all(&self, timeout: Duration) {
+        self.draining.store(true, Ordering::SeqCst);
+        let deadline = Instant::now() + timeout;
+
+        let handles: Vec<TableHandle> = self.tables.read().await.values().cloned().collect();
+        let mut pending = Vec::new();
+        for handle in handles {
+            let (tx, rx) = oneshot::channel();
+            match handle.send(TableMessage::Drain { response: tx }).await {
+                Ok(()) => pending.push((handle, rx)),
+                Err(e) => log::warn!("Table {}: Failed to drain: {}", handle.table_id(), e),
+            }
+        }
+
+        for (handle, rx) in pending {
+            let table_id = handle.table_id();
+            if timeout_at(deadline, rx).await.is_err() {
+                log::warn!(
+                    "Table {}: Hand still in progress after {:?}, force-settling",
+                    table_id,
+                    timeout
+                );
+                let (tx, rx) = oneshot::channel();
+                if handle
+                    .send(TableMessage::ForceSettle { response: tx })
+                    .await
+                    .is_ok()
+                {
+                    let _ = rx.await;
+                }
+            }
+
+            if self.tables.write().await.remove(&table_id).is_some() {
+                self.release_table_slot();
+            }
+            self.player_count_cache.write().await.remove(&table_id);
+            log::info!("Drained table {}", table_id);
+        }
+    }
+
+    /// Close tables that have been empty for at least `idle_timeout`
+    ///
+    /// A table counts as occupied while anyone is seated, waitlisted, or
+    /// spectating, bots included. Persistent tables are never closed. An
+    /// idle table's escrow has to be empty before it's closed; one still
+    /// holding chips is left open and logged so reconciliation can return
+    /// them.
+    ///
+    /// # Arguments
+    ///
+    /// * `idle_timeout` - How long a table has to be empty before closing
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<TableId>` - Tables that were closed
+    pub async fn close_idle_tables(&self, idle_timeout: Duration) -> Vec<TableId> {
+        let now = Instant::now();
+        let activity: Vec<(TableId, TableActivity)> = self
+            .activity
+            .read()
+            .await
+            .iter()
+            .filter(|(_, activity)| !activity.persistent)
+            .map(|(table_id, activity)| (*table_id, *activity))
+            .collect();
+
+        let mut closed = Vec::new();
+        for (table_id, activity) in activity {
+            let Ok(state) = self.get_table_state(table_id, None).await else {
+                continue;
+            };
+            if state.player_count + state.waitlist_count + state.spectator_count > 0 {
+                if let Some(activity) = self.activity.write().await.get_mut(&table_id) {
+                    activity.last_occupied = now;
+                }
+                continue;
+            }
+            if now.duration_since(activity.last_occupied) < idle_timeout
+                || !self.escrow_is_empty(table_id).await
+            {
+                continue;
+            }
+
+            match self.close_table(table_id).await {
+                Ok(()) => {
+                    log::info!("Closed table {} after being idle", table_id);
+                    closed.push(table_id);
+                }
+                Err(e) => log::warn!("Table {}: Failed to close idle table: {}", table_id, e),
+            }
+        }
+        closed
+    }
+
+    /// Whether a table has no chips seated and none left in escrow
+    async fn escrow_is_empty(&self, table_id: TableId) -> bool {
+        let Some(handle) = self.get_table(table_id).await else {
+            return false;
+        };
+        let (tx, rx) = oneshot::channel();
+        if handle
+            .send(TableMessage::GetEscrowedChips { response: tx })
+            .await
+            .is_err()
+        {
+            return false;
+        }
+        let seated = rx.await.ok().flatten();
+
+        match self.wallet_manager.get_escrow(table_id).await {
+            Ok(escrow) if seated == Some(0) && escrow.balance == 0 => true,
+            Ok(escrow) => {
+                log::warn!(
+                    "Table {}: idle but escrow holds {} chips ({:?} seated), leaving it open",
+                    table_id,
+                    escrow.balance,
+                    seated
+                );
+                false
+            }
+            Err(e) => {
+                log::warn!("Table {}: Failed to check escrow: {}", table_id, e);
+                false
+            }
+        }
+    }
+
     /// Join a table
     ///
     /// # Arguments
@@ -378,6 +1071,8 @@ impl TableManager {
     /// * `username` - Username
     /// * `buy_in_amount` - Buy-in amount in chips
     /// * `passphrase` - Optional passphrase for private tables
+    /// * `idempotency_key` - Optional client key; retries with the same key
+    ///   return the original result instead of charging again
     ///
     /// # Returns
     ///
@@ -389,6 +1084,7 @@ impl TableManager {
         username: String,
         buy_in_amount: i64,
         passphrase: Option<String>,
+        idempotency_key: Option<String>,
     ) -> Result<TableResponse, String> {
         let handle = self
             .get_table(table_id)
@@ -402,6 +1098,7 @@ impl TableManager {
                 username,
                 buy_in_amount,
                 passphrase,
+                idempotency_key,
                 response: tx,
             })
             .await
@@ -466,6 +1163,51 @@ impl TableManager {
         Ok(response)
     }
 
+    /// Kick a user from a table, cashing out their chips and disconnecting
+    /// them
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Table ID
+    /// * `user_id` - User ID
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TableResponse, String>` - Response or error
+    pub async fn kick_user(
+        &self,
+        table_id: TableId,
+        user_id: i64,
+    ) -> Result<TableResponse, String> {
+        let handle = self
+            .get_table(table_id)
+            .await
+            .ok_or_else(|| "Table not found".to_string())?;
+
+        let (tx, rx) = oneshot::channel();
+        handle
+            .send(TableMessage::KickUser {
+                user_id,
+                response: tx,
+            })
+            .await
+            .map_err(|e| format!("Failed to send message: {}", e))?;
+
+        let response = rx
+            .await
+            .map_err(|_| "Failed to receive response".to_string())?;
+
+        // Update cache on successful kick
+        if response.is_success()
+            && let Ok(state) = self.get_table_state(table_id, None).await
+        {
+            self.update_player_count_cache(table_id, state.player_count)
+                .await;
+        }
+
+        Ok(response)
+    }
+
     /// Get table state
     ///
     /// # Arguments
@@ -499,6 +1241,69 @@ impl TableManager {
             .map_err(|_| "Failed to receive response".to_string())
     }
 
+    /// Get table info for listings
+    ///
+    /// The info is built from the table's current config, so its stakes
+    /// tier reflects blind changes since the table was created.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Table ID
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TableInfo, String>` - Table info or error
+    pub async fn get_table_info(&self, table_id: TableId) -> Result<TableInfo, String> {
+        let handle = self
+            .get_table(table_id)
+            .await
+            .ok_or_else(|| "Table not found".to_string())?;
+
+        let (tx, rx) = oneshot::channel();
+        handle
+            .send(TableMessage::GetInfo { response: tx })
+            .await
+            .map_err(|e| format!("Failed to send message: {}", e))?;
+
+        rx.await
+            .map_err(|_| "Failed to receive response".to_string())
+    }
+
+    /// Compare every table escrow against the chips seated at its table
+    ///
+    /// Tables whose actor is gone count as having no active actor, so
+    /// their escrow is orphaned. Read-only unless `return_orphaned` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `return_orphaned` - Refund orphaned escrows to their players
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ReconcileReport, String>` - Reconciliation report or error
+    pub async fn reconcile_escrow(&self, return_orphaned: bool) -> Result<ReconcileReport, String> {
+        let handles: Vec<TableHandle> = self.tables.read().await.values().cloned().collect();
+        let mut table_chips = HashMap::new();
+        for handle in handles {
+            let (tx, rx) = oneshot::channel();
+            if handle
+                .send(TableMessage::GetEscrowedChips { response: tx })
+                .await
+                .is_err()
+            {
+                continue;
+            }
+            if let Ok(chips) = rx.await {
+                table_chips.insert(handle.table_id(), chips);
+            }
+        }
+
+        self.wallet_manager
+            .reconcile_escrow(&table_chips, return_orphaned)
+            .await
+            .map_err(|e| format!("Failed to reconcile escrow: {}", e))
+    }
+
     /// Get active table count
     pub async fn active_table_count(&self) -> usize {
         let tables = self.tables.read().await;
@@ -535,3 +1340,162 @@ impl TableManager {
             .unwrap_or(-1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::TableSpeed;
+
+    fn init_table(id: TableId, name: &str, player_count: usize, big_blind: i64) -> TableMetadata {
+        TableMetadata {
+            id,
+            name: name.to_string(),
+            player_count,
+            max_players: 6,
+            small_blind: big_blind / 2,
+            big_blind,
+            is_private: false,
+            speed: "normal".to_string(),
+            bots_enabled: false,
+            is_active: true,
+        }
+    }
+
+    fn init_tables() -> Vec<TableMetadata> {
+        vec![
+            init_table(1, "Aces", 2, 20),
+            init_table(2, "Kings", 5, 200),
+            init_table(3, "Queens", 6, 20),
+            init_table(4, "Jacks", 2, 2000),
+            init_table(5, "Tens", 4, 20),
+        ]
+    }
+
+    fn table_ids(page: &TablePage) -> Vec<TableId> {
+        page.tables.iter().map(|table| table.id).collect()
+    }
+
+    #[test]
+    fn test_sort_by_player_count_descending() {
+        let filter = TableFilter {
+            sort_by: Some(TableSortBy::Players),
+            order: SortOrder::Desc,
+            ..Default::default()
+        };
+        let page = filter_tables(init_tables(), &filter);
+        // Tables 1 and 4 both have 2 players, so they're ordered by ID.
+        assert_eq!(table_ids(&page), vec![3, 2, 5, 1, 4]);
+        assert_eq!(page.total, 5);
+    }
+
+    #[test]
+    fn test_sort_by_stakes_and_name() {
+        let filter = TableFilter {
+            sort_by: Some(TableSortBy::Stakes),
+            ..Default::default()
+        };
+        let page = filter_tables(init_tables(), &filter);
+        assert_eq!(table_ids(&page), vec![1, 3, 5, 2, 4]);
+
+        let filter = TableFilter {
+            sort_by: Some(TableSortBy::Name),
+            ..Default::default()
+        };
+        let page = filter_tables(init_tables(), &filter);
+        assert_eq!(table_ids(&page), vec![1, 4, 2, 3, 5]);
+    }
+
+    #[test]
+    fn test_offset_limit_slicing() {
+        let mut filter = TableFilter {
+            sort_by: Some(TableSortBy::Players),
+            order: SortOrder::Desc,
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let mut seen = Vec::new();
+        for expected in [vec![3, 2], vec![5, 1], vec![4]] {
+            let page = filter_tables(init_tables(), &filter);
+            assert_eq!(table_ids(&page), expected);
+            assert_eq!(page.total, 5);
+            seen.extend(table_ids(&page));
+            filter.offset += 2;
+        }
+        // Pages don't overlap and cover every table.
+        seen.sort_unstable();
+        assert_eq!(seen, vec![1, 2, 3, 4, 5]);
+
+        // Past the end is an empty page.
+        filter.offset = 10;
+        let page = filter_tables(init_tables(), &filter);
+        assert!(page.tables.is_empty());
+        assert_eq!(page.total, 5);
+    }
+
+    #[test]
+    fn test_filter_composes_with_pagination() {
+        let filter = TableFilter {
+            stakes_tier: Some(StakesTier::Low),
+            has_waitlist_space: true,
+            speed: Some(TableSpeed::Normal),
+            sort_by: Some(TableSortBy::Players),
+            order: SortOrder::Desc,
+            limit: Some(1),
+            ..Default::default()
+        };
+        // Tables 1, 3, and 5 are low stakes, but table 3 is full.
+        let page = filter_tables(init_tables(), &filter);
+        assert_eq!(table_ids(&page), vec![5]);
+        assert_eq!(page.total, 2);
+
+        let filter = TableFilter {
+            speed: Some(TableSpeed::Turbo),
+            ..Default::default()
+        };
+        let page = filter_tables(init_tables(), &filter);
+        assert!(page.tables.is_empty());
+        assert_eq!(page.total, 0);
+    }
+
+    #[test]
+    fn test_name_query_matches_substring() {
+        let filter = TableFilter {
+            name_query: Some("EEN".to_string()),
+            ..Default::default()
+        };
+        let page = filter_tables(init_tables(), &filter);
+        assert_eq!(table_ids(&page), vec![3]);
+
+        // Case folding isn't limited to ASCII.
+        let mut tables = init_tables();
+        tables.push(init_table(6, "Soirée Été", 3, 20));
+        let filter = TableFilter {
+            name_query: Some("ÉTÉ".to_string()),
+            ..Default::default()
+        };
+        let page = filter_tables(tables, &filter);
+        assert_eq!(table_ids(&page), vec![6]);
+
+        // An empty query matches every table.
+        let filter = TableFilter {
+            name_query: Some(String::new()),
+            ..Default::default()
+        };
+        let page = filter_tables(init_tables(), &filter);
+        assert_eq!(page.total, 5);
+    }
+
+    #[test]
+    fn test_name_query_composes_with_stakes_tier() {
+        let filter = TableFilter {
+            name_query: Some("n".to_string()),
+            stakes_tier: Some(StakesTier::Low),
+            ..Default::default()
+        };
+        // Kings also matches the name, but isn't low stakes.
+        let page = filter_tables(init_tables(), &filter);
+        assert_eq!(table_ids(&page), vec![3, 5]);
+        assert_eq!(page.total, 2);
+    }
+}
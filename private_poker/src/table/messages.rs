@@ -1,7 +1,12 @@
 //! Table actor message types.
 
-use crate::game::entities::{Action, GameView};
+use crate::game::{
+    GameEvent,
+    entities::{Action, Blinds, Card, GameView, HandHistory, TurnInfo},
+};
+use crate::net::messages::TableInfo;
 use crate::wallet::TableId;
+use chrono::{DateTime, Utc};
 use tokio::sync::oneshot;
 
 /// Messages that can be sent to a TableActor
@@ -13,6 +18,8 @@ pub enum TableMessage {
         username: String,
         buy_in_amount: i64,
         passphrase: Option<String>,
+        /// Client-chosen key that makes retries of this join idempotent
+        idempotency_key: Option<String>,
         response: oneshot::Sender<TableResponse>,
     },
 
@@ -22,6 +29,13 @@ pub enum TableMessage {
         response: oneshot::Sender<TableResponse>,
     },
 
+    /// Kick a user from the table (admin only), cashing out their chips
+    /// like a leave and disconnecting them
+    KickUser {
+        user_id: i64,
+        response: oneshot::Sender<TableResponse>,
+    },
+
     /// Player action (fold, check, call, raise, all-in)
     TakeAction {
         user_id: i64,
@@ -35,12 +49,30 @@ pub enum TableMessage {
         response: oneshot::Sender<TableStateResponse>,
     },
 
+    /// Get table info for listings, built from the current config
+    GetInfo {
+        response: oneshot::Sender<TableInfo>,
+    },
+
+    /// Get the chips seated players hold against the table's escrow, or
+    /// `None` while a hand is in progress
+    GetEscrowedChips {
+        response: oneshot::Sender<Option<i64>>,
+    },
+
     /// Get game view for a specific user
     GetGameView {
         user_id: i64,
         response: oneshot::Sender<Option<GameView>>,
     },
 
+    /// Get the actions the user could take and the amounts they'd face,
+    /// without acting. Only answers on the user's own turn.
+    GetLegalActions {
+        user_id: i64,
+        response: oneshot::Sender<Result<TurnInfo, TableResponse>>,
+    },
+
     /// Spectate table (read-only)
     Spectate {
         user_id: i64,
@@ -58,6 +90,7 @@ pub enum TableMessage {
     JoinWaitlist {
         user_id: i64,
         username: String,
+        buy_in_amount: i64,
         response: oneshot::Sender<TableResponse>,
     },
 
@@ -67,6 +100,60 @@ pub enum TableMessage {
         response: oneshot::Sender<TableResponse>,
     },
 
+    /// Straddle the next time the user is under the gun
+    Straddle {
+        user_id: i64,
+        response: oneshot::Sender<TableResponse>,
+    },
+
+    /// Agree (or stop agreeing) to run it twice when all-in
+    SetRunItTwice {
+        user_id: i64,
+        enabled: bool,
+        response: oneshot::Sender<TableResponse>,
+    },
+
+    /// Offer the two players all-in to chop the pot by their current
+    /// equity instead of dealing the rest of the board. Responds with
+    /// each player's share of the pot, from 0 to 1.
+    OfferEquityChop {
+        response: oneshot::Sender<Result<Vec<(String, f64)>, TableResponse>>,
+    },
+
+    /// Accept or decline the equity chop on offer
+    RespondToEquityChop {
+        user_id: i64,
+        accept: bool,
+        response: oneshot::Sender<TableResponse>,
+    },
+
+    /// Start the next hand without waiting for the table to auto-start
+    StartGame {
+        user_id: i64,
+        response: oneshot::Sender<TableResponse>,
+    },
+
+    /// Sit out of (or back into) hands while keeping the seat
+    SetSittingOut {
+        user_id: i64,
+        sitting_out: bool,
+        response: oneshot::Sender<TableResponse>,
+    },
+
+    /// See the community cards that would've completed the board of a
+    /// hand that ended early
+    RabbitHunt {
+        user_id: i64,
+        response: oneshot::Sender<Result<Vec<Card>, TableResponse>>,
+    },
+
+    /// Move to an open seat at the start of the next hand
+    RequestSeat {
+        user_id: i64,
+        desired_seat: usize,
+        response: oneshot::Sender<TableResponse>,
+    },
+
     /// Send chat message
     SendChat {
         user_id: i64,
@@ -74,6 +161,29 @@ pub enum TableMessage {
         response: oneshot::Sender<TableResponse>,
     },
 
+    /// Get recent chat messages
+    GetChatHistory {
+        response: oneshot::Sender<Vec<ChatMessage>>,
+    },
+
+    /// Get the history of a completed hand
+    GetHandHistory {
+        hand_number: u32,
+        response: oneshot::Sender<Option<TableHandHistory>>,
+    },
+
+    /// Mute user in table chat (admin only)
+    MuteUser {
+        user_id: i64,
+        response: oneshot::Sender<TableResponse>,
+    },
+
+    /// Unmute user in table chat (admin only)
+    UnmuteUser {
+        user_id: i64,
+        response: oneshot::Sender<TableResponse>,
+    },
+
     /// Top-up chips from wallet to table
     TopUp {
         user_id: i64,
@@ -81,6 +191,15 @@ pub enum TableMessage {
         response: oneshot::Sender<TableResponse>,
     },
 
+    /// Refill the stack to a target after any hand it ends below a
+    /// threshold (a threshold of 0 turns auto top-up off)
+    SetAutoTopUp {
+        user_id: i64,
+        threshold_chips: i64,
+        target_chips: i64,
+        response: oneshot::Sender<TableResponse>,
+    },
+
     /// Pause table (admin only)
     Pause {
         response: oneshot::Sender<TableResponse>,
@@ -91,18 +210,52 @@ pub enum TableMessage {
         response: oneshot::Sender<TableResponse>,
     },
 
+    /// Raise the minimum blinds once the hand in progress is over, e.g.,
+    /// when a tournament's clock moves to its next level
+    UpdateBlinds {
+        blinds: Blinds,
+        response: oneshot::Sender<TableResponse>,
+    },
+
+    /// Stop dealing new hands (or start again), e.g., for a tournament
+    /// break. The hand in progress is played out.
+    SetBreak {
+        on_break: bool,
+        response: oneshot::Sender<TableResponse>,
+    },
+
     /// Close table (admin only)
     Close {
         response: oneshot::Sender<TableResponse>,
     },
 
+    /// Stop taking joins and close the table once the hand in progress
+    /// is over, returning everyone's chips from escrow. Answered once the
+    /// table is closed.
+    Drain {
+        response: oneshot::Sender<TableResponse>,
+    },
+
+    /// Call off the hand in progress and close the table right away,
+    /// returning everyone's chips (including what they put into the
+    /// pot) from escrow
+    ForceSettle {
+        response: oneshot::Sender<TableResponse>,
+    },
+
     /// Internal: Advance game state (called by timer)
     Tick,
 
     /// Subscribe to state change notifications
+    ///
+    /// With `resume`, the subscriber is sent the game events it missed
+    /// since its last subscription dropped. With `last_seq`, it's sent the
+    /// game views since the one with that sequence number.
     Subscribe {
         user_id: i64,
         sender: tokio::sync::mpsc::Sender<StateChangeNotification>,
+        resume: bool,
+        last_seq: Option<u64>,
     },
 
     /// Unsubscribe from state change notifications
@@ -118,6 +271,77 @@ pub enum StateChangeNotification {
     PlayerListChanged,
     /// Pot size changed
     PotChanged,
+    /// A waitlisted user took an open seat, with their buy-in moved
+    /// into escrow
+    SeatedFromWaitlist { user_id: i64 },
+    /// The table stopped dealing because fewer than two players are left
+    /// (`waiting` is true), or started again once a second player sat
+    WaitingForPlayers { waiting: bool },
+    /// Enough players are seated for the table to start a hand on its
+    /// own in `starts_in_secs`, or the countdown was cancelled (`None`)
+    /// because players left
+    AutoStartCountdown { starts_in_secs: Option<u64> },
+    /// The players all-in can chop the pot by these equities, snapshotted
+    /// when the offer was made
+    EquityChopOffered { equities: Vec<(String, f64)> },
+    /// Something happened in the game (e.g., a player acted or the flop
+    /// was dealt). Events are sent before the state change that shows them.
+    GameEvent(GameEvent),
+    /// Chat message sent to the table
+    ChatMessage(ChatMessage),
+    /// Game events that occurred while a resuming subscriber was
+    /// disconnected, oldest first. `complete` is false if older events
+    /// were no longer buffered.
+    MissedEvents {
+        events: Vec<GameEvent>,
+        complete: bool,
+    },
+    /// Game views a resuming subscriber missed, oldest first, starting
+    /// with the last view it has. Empty if that view is no longer
+    /// buffered, so the subscriber needs a full snapshot.
+    MissedViews { views: Vec<GameView> },
+    /// Notifications were dropped because the subscriber fell behind, so
+    /// its view may be stale and it needs a full snapshot
+    Resync,
+    /// The subscriber was kicked from the table. It's the last
+    /// notification the subscriber gets.
+    Kicked,
+}
+
+/// Chat message sent to a table
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ChatMessage {
+    /// Table ID
+    pub table_id: TableId,
+
+    /// Sender user ID
+    pub user_id: i64,
+
+    /// Sender username
+    pub username: String,
+
+    /// Message text
+    pub message: String,
+
+    /// When the message was sent
+    pub timestamp: DateTime<Utc>,
+}
+
+/// History of a hand played at a table
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct TableHandHistory {
+    /// Table ID
+    pub table_id: TableId,
+
+    /// Number of the hand at the table
+    pub hand_number: u32,
+
+    /// When the hand ended
+    pub completed_at: DateTime<Utc>,
+
+    /// What happened during the hand
+    #[serde(flatten)]
+    pub hand: HandHistory,
 }
 
 /// Response from table operations
@@ -144,6 +368,9 @@ pub enum TableResponse {
     /// Invalid action for current game state
     InvalidAction(String),
 
+    /// Raise was smaller than the minimum and wasn't an all-in
+    RaiseBelowMinimum { min_raise: i64 },
+
     /// Access denied (private table, wrong passphrase)
     AccessDenied,
 
@@ -152,6 +379,12 @@ pub enum TableResponse {
 
     /// Rate limited (chat, top-up cooldown)
     RateLimited { retry_after_secs: u64 },
+
+    /// Table already has as many spectators as it allows
+    SpectatorLimitReached,
+
+    /// Table requires a verified email to join
+    EmailNotVerified,
 }
 
 /// Table state response
@@ -181,6 +414,9 @@ pub struct TableStateResponse {
     /// Big blind
     pub big_blind: i64,
 
+    /// Ante
+    pub ante: i64,
+
     /// Current pot size
     pub pot_size: i64,
 
@@ -223,12 +459,19 @@ impl TableResponse {
             )),
             TableResponse::NotYourTurn => Some("Not your turn".to_string()),
             TableResponse::InvalidAction(msg) => Some(format!("Invalid action: {}", msg)),
+            TableResponse::RaiseBelowMinimum { min_raise } => {
+                Some(format!("Raise below minimum of ${}", min_raise))
+            }
             TableResponse::AccessDenied => Some("Access denied".to_string()),
             TableResponse::NotAtTable => Some("Not at table".to_string()),
             TableResponse::RateLimited { retry_after_secs } => Some(format!(
                 "Rate limited, retry after {} seconds",
                 retry_after_secs
             )),
+            TableResponse::SpectatorLimitReached => Some("Spectator limit reached".to_string()),
+            TableResponse::EmailNotVerified => {
+                Some("Verify your email before joining this table".to_string())
+            }
             _ => None,
         }
     }
@@ -46,12 +46,14 @@
 //! }
 //! ```
 
+pub mod action_clock;
 pub mod actor;
 pub mod config;
 pub mod manager;
 pub mod messages;
 
+pub use action_clock::ActionClock;
 pub use actor::{TableActor, TableHandle};
-pub use config::{BotDifficulty, TableConfig, TableSpeed};
-pub use manager::{TableManager, TableMetadata};
+pub use config::{BotDifficulty, ConfigError, TableConfig, TableSpeed};
+pub use manager::{TableManager, TableMetadata, TablePage, filter_tables};
 pub use messages::{TableMessage, TableResponse, TableStateResponse};
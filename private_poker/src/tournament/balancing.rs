@@ -0,0 +1,310 @@
+//! Seat balancing for multi-table tournaments.
+//!
+//! Tables are kept within one player of each other by moving players from
+//! the largest table to the shortest one as players bust, and a table is
+//! broken up entirely once its players fit in the open seats elsewhere.
+
+use crate::wallet::TableId;
+use serde::{Deserialize, Serialize};
+
+/// Player seated at a tournament table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeatedPlayer {
+    /// User ID
+    pub user_id: i64,
+    /// Seat index at the table
+    pub seat: usize,
+    /// Current chip count, carried along when the player is moved
+    pub chip_count: i64,
+}
+
+/// Table in a multi-table tournament
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TournamentTable {
+    /// Table ID
+    pub table_id: TableId,
+    /// Number of seats at the table
+    pub max_players: usize,
+    /// Seat of the dealer button for the current hand
+    pub button_seat: usize,
+    /// Players seated at the table
+    pub players: Vec<SeatedPlayer>,
+}
+
+impl TournamentTable {
+    /// Return the occupied seats in the order they'll act after the
+    /// button, starting with the seat that'll be the next button.
+    fn seats_after_button(&self) -> Vec<usize> {
+        let mut seats: Vec<usize> = self.players.iter().map(|p| p.seat).collect();
+        seats.sort_unstable();
+        let split = seats.partition_point(|seat| *seat <= self.button_seat);
+        seats.rotate_left(split);
+        seats
+    }
+
+    /// Return the index of the player that'll post the big blind next hand.
+    fn next_big_blind_idx(&self) -> Option<usize> {
+        let seats = self.seats_after_button();
+        // The button moves to the first seat, so the small blind is the
+        // second seat and the big blind is the third.
+        let seat = match seats.len() {
+            0 => return None,
+            // Heads-up, the button posts the small blind.
+            1 | 2 => seats[seats.len() - 1],
+            _ => seats[2],
+        };
+        self.players.iter().position(|p| p.seat == seat)
+    }
+
+    /// Return the open seat a moved player should take so they're the
+    /// next player to post the big blind, or as close to it as possible.
+    /// The first open seat after the next small blind is the big blind
+    /// position for the next hand.
+    fn seat_for_incoming_player(&self) -> Option<usize> {
+        let seats = self.seats_after_button();
+        let small_blind = match seats.len() {
+            0 => self.button_seat,
+            1 | 2 => seats[0],
+            _ => seats[1],
+        };
+        (1..=self.max_players)
+            .map(|offset| (small_blind + offset) % self.max_players)
+            .find(|seat| !self.players.iter().any(|p| p.seat == *seat))
+    }
+
+    fn open_seats(&self) -> usize {
+        self.max_players.saturating_sub(self.players.len())
+    }
+}
+
+/// Player moved from one table to another
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableMove {
+    /// User ID
+    pub user_id: i64,
+    /// Chips the player brings to the new table
+    pub chip_count: i64,
+    /// Table the player is moved from
+    pub from_table: TableId,
+    /// Table the player is moved to
+    pub to_table: TableId,
+    /// Seat the player takes at the new table
+    pub to_seat: usize,
+}
+
+/// Moves needed to balance a tournament's tables
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalancePlan {
+    /// Players to move, in order
+    pub moves: Vec<TableMove>,
+    /// Tables that were broken up and can be closed
+    pub broken_tables: Vec<TableId>,
+}
+
+/// Move a player to the shortest table other than `from_table`.
+fn move_player(
+    tables: &mut [TournamentTable],
+    from_table_idx: usize,
+    player_idx: usize,
+) -> Option<TableMove> {
+    let to_table_idx = tables
+        .iter()
+        .enumerate()
+        .filter(|(idx, table)| *idx != from_table_idx && table.open_seats() > 0)
+        .min_by_key(|(_, table)| (table.players.len(), table.table_id))
+        .map(|(idx, _)| idx)?;
+    let to_seat = tables[to_table_idx].seat_for_incoming_player()?;
+
+    let mut player = tables[from_table_idx].players.remove(player_idx);
+    player.seat = to_seat;
+    let table_move = TableMove {
+        user_id: player.user_id,
+        chip_count: player.chip_count,
+        from_table: tables[from_table_idx].table_id,
+        to_table: tables[to_table_idx].table_id,
+        to_seat,
+    };
+    tables[to_table_idx].players.push(player);
+    Some(table_move)
+}
+
+/// Work out which players to move so every table is within one player
+/// of the others, updating `tables` to reflect the moves.
+///
+/// The shortest table is broken up whenever its players fit in the open
+/// seats at the other tables. Otherwise, while the largest table has two
+/// or more players more than the shortest one, the player due to post the
+/// big blind next at the largest table is moved to the shortest table. They
+/// take the seat that makes them the next big blind there, so nobody skips
+/// paying a big blind by changing tables.
+pub fn plan_balance(tables: &mut Vec<TournamentTable>) -> BalancePlan {
+    let mut plan = BalancePlan::default();
+    tables.retain(|table| {
+        if table.players.is_empty() {
+            plan.broken_tables.push(table.table_id);
+        }
+        !table.players.is_empty()
+    });
+
+    // Break the shortest table if everyone at it fits elsewhere.
+    while tables.len() > 1 {
+        let Some(shortest_idx) =
+            (0..tables.len()).min_by_key(|idx| (tables[*idx].players.len(), tables[*idx].table_id))
+        else {
+            break;
+        };
+        let open_seats_elsewhere: usize = tables
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != shortest_idx)
+            .map(|(_, table)| table.open_seats())
+            .sum();
+        if tables[shortest_idx].players.len() > open_seats_elsewhere {
+            break;
+        }
+        while let Some(player_idx) = tables[shortest_idx].next_big_blind_idx() {
+            match move_player(tables, shortest_idx, player_idx) {
+                Some(table_move) => plan.moves.push(table_move),
+                None => break,
+            }
+        }
+        if !tables[shortest_idx].players.is_empty() {
+            break;
+        }
+        plan.broken_tables.push(tables[shortest_idx].table_id);
+        tables.remove(shortest_idx);
+    }
+
+    // Even out the remaining tables.
+    while let Some(largest_idx) = (0..tables.len()).max_by_key(|idx| {
+        (
+            tables[*idx].players.len(),
+            std::cmp::Reverse(tables[*idx].table_id),
+        )
+    }) {
+        let Some(shortest_len) = tables.iter().map(|table| table.players.len()).min() else {
+            break;
+        };
+        if tables[largest_idx].players.len() < shortest_len + 2 {
+            break;
+        }
+        let Some(player_idx) = tables[largest_idx].next_big_blind_idx() else {
+            break;
+        };
+        match move_player(tables, largest_idx, player_idx) {
+            Some(table_move) => plan.moves.push(table_move),
+            None => break,
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_table(table_id: TableId, user_ids: std::ops::Range<i64>) -> TournamentTable {
+        TournamentTable {
+            table_id,
+            max_players: 9,
+            button_seat: 0,
+            players: user_ids
+                .enumerate()
+                .map(|(seat, user_id)| SeatedPlayer {
+                    user_id,
+                    seat,
+                    chip_count: 1500,
+                })
+                .collect(),
+        }
+    }
+
+    fn bust(tables: &mut [TournamentTable], user_id: i64) {
+        for table in tables.iter_mut() {
+            table.players.retain(|p| p.user_id != user_id);
+        }
+    }
+
+    fn table_sizes(tables: &[TournamentTable]) -> Vec<usize> {
+        tables.iter().map(|table| table.players.len()).collect()
+    }
+
+    #[test]
+    fn test_balanced_tables_are_left_alone() {
+        let mut tables = vec![init_table(1, 0..9), init_table(2, 9..17)];
+        let plan = plan_balance(&mut tables);
+        assert_eq!(plan, BalancePlan::default());
+    }
+
+    #[test]
+    fn test_moves_next_big_blind_to_short_table() {
+        let mut tables = vec![init_table(1, 0..9), init_table(2, 9..16)];
+        let plan = plan_balance(&mut tables);
+        assert_eq!(plan.moves.len(), 1);
+        let table_move = &plan.moves[0];
+        // With the button on seat 0, seat 3 posts the next big blind.
+        assert_eq!(table_move.user_id, 3);
+        assert_eq!(table_move.chip_count, 1500);
+        assert_eq!(table_move.from_table, 1);
+        assert_eq!(table_move.to_table, 2);
+        // Seat 7 is the first open seat after the next small blind (seat 2)
+        // once seats 3-6 are taken, so it's the next big blind there.
+        assert_eq!(table_move.to_seat, 7);
+        assert_eq!(table_sizes(&tables), vec![8, 8]);
+    }
+
+    #[test]
+    fn test_incoming_player_takes_next_big_blind_seat() {
+        let mut table = init_table(1, 0..6);
+        table.players.retain(|p| p.seat != 3);
+        // The next small blind is seat 2 and seat 3 is open.
+        assert_eq!(table.seat_for_incoming_player(), Some(3));
+    }
+
+    #[test]
+    fn test_three_tables_stay_within_one() {
+        let mut tables = vec![
+            init_table(1, 0..9),
+            init_table(2, 9..18),
+            init_table(3, 18..27),
+        ];
+
+        // Bust players from the first table only, rebalancing after each
+        // elimination.
+        for user_id in 0..9 {
+            bust(&mut tables, user_id);
+            let plan = plan_balance(&mut tables);
+            for table_move in &plan.moves {
+                assert_ne!(table_move.from_table, table_move.to_table);
+                assert_eq!(table_move.chip_count, 1500);
+            }
+            let sizes = table_sizes(&tables);
+            let max = *sizes.iter().max().unwrap();
+            let min = *sizes.iter().min().unwrap();
+            assert!(max - min <= 1, "unbalanced tables: {sizes:?}");
+            for table in &tables {
+                let mut seats: Vec<_> = table.players.iter().map(|p| p.seat).collect();
+                seats.sort_unstable();
+                seats.dedup();
+                assert_eq!(seats.len(), table.players.len(), "seat taken twice");
+            }
+        }
+        assert_eq!(tables.iter().map(|t| t.players.len()).sum::<usize>(), 18);
+    }
+
+    #[test]
+    fn test_breaks_table_when_players_fit_elsewhere() {
+        let mut tables = vec![
+            init_table(1, 0..5),
+            init_table(2, 5..9),
+            init_table(3, 9..13),
+        ];
+        // The 4 players at table 2 fit in the open seats at tables 1 and 3.
+        let plan = plan_balance(&mut tables);
+        assert_eq!(plan.broken_tables, vec![2]);
+        assert_eq!(plan.moves.len(), 4);
+        assert!(plan.moves.iter().all(|m| m.from_table == 2));
+        assert_eq!(table_sizes(&tables), vec![7, 6]);
+    }
+}
@@ -2,14 +2,31 @@
 
 #![allow(clippy::needless_raw_string_hashes)]
 
+use super::balancing::{self, BalancePlan, SeatedPlayer, TournamentTable};
 use super::models::{
-    PrizeStructure, TournamentConfig, TournamentId, TournamentInfo, TournamentRegistration,
-    TournamentState, TournamentType,
+    self, BlindLevel, BountyAward, TournamentConfig, TournamentId, TournamentInfo,
+    TournamentRegistration, TournamentState, TournamentType,
 };
-use chrono::Utc;
-use sqlx::{PgPool, Row};
+use crate::net::messages::ServerMessage;
+use crate::table::TableManager;
+use crate::wallet::{TableId, WalletError, WalletManager};
+use sqlx::{PgPool, Row, postgres::PgRow};
 use std::sync::Arc;
 use thiserror::Error;
+use tokio::sync::broadcast;
+
+/// Number of tournament events buffered for each subscriber
+const EVENT_CAPACITY: usize = 64;
+
+/// Columns of a tournament's info, including how many milliseconds its
+/// clock has run (not counting pauses), or NULL before it starts
+const INFO_COLUMNS: &str = r#"
+    id, name, tournament_type, config, state, buy_in, registered_count,
+    current_level, created_at, started_at, finished_at,
+    paused_at IS NOT NULL AS paused,
+    FLOOR(EXTRACT(EPOCH FROM COALESCE(paused_at, NOW()) - started_at) * 1000)::BIGINT
+        - paused_ms AS elapsed_ms
+"#;
 
 /// Tournament errors
 #[derive(Debug, Error)]
@@ -35,11 +52,20 @@ pub enum TournamentError {
     #[error("Insufficient players: need {needed}, have {current}")]
     InsufficientPlayers { needed: usize, current: usize },
 
+    #[error("Not enough seats: need {needed}, have {available}")]
+    InsufficientSeats { needed: usize, available: usize },
+
+    #[error("Invalid payouts: {0}")]
+    InvalidPayouts(String),
+
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    #[error("Wallet error: {0}")]
+    Wallet(#[from] WalletError),
 }
 
 pub type TournamentResult<T> = Result<T, TournamentError>;
@@ -48,12 +74,27 @@ pub type TournamentResult<T> = Result<T, TournamentError>;
 #[derive(Clone)]
 pub struct TournamentManager {
     pool: Arc<PgPool>,
+    wallet_manager: WalletManager,
+    /// Sends tournament events (e.g., blinds going up) to subscribers
+    events: broadcast::Sender<ServerMessage>,
 }
 
 impl TournamentManager {
     /// Create a new tournament manager
     pub fn new(pool: Arc<PgPool>) -> Self {
-        Self { pool }
+        let wallet_manager = WalletManager::new(pool.clone());
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            pool,
+            wallet_manager,
+            events,
+        }
+    }
+
+    /// Subscribe to tournament events, such as
+    /// `ServerMessage::BlindLevelIncreased`
+    pub fn subscribe(&self) -> broadcast::Receiver<ServerMessage> {
+        self.events.subscribe()
     }
 
     /// Create a new tournament
@@ -61,6 +102,10 @@ impl TournamentManager {
         &self,
         config: TournamentConfig,
     ) -> TournamentResult<TournamentId> {
+        config
+            .payout_structure
+            .validate()
+            .map_err(TournamentError::InvalidPayouts)?;
         let config_json = serde_json::to_value(&config)?;
 
         let row = sqlx::query(
@@ -71,10 +116,7 @@ impl TournamentManager {
             "#,
         )
         .bind(&config.name)
-        .bind(match config.tournament_type {
-            TournamentType::SitAndGo => "sit_and_go",
-            TournamentType::Scheduled => "scheduled",
-        })
+        .bind(config.tournament_type.to_string())
         .bind(config_json)
         .bind("registering")
         .bind(config.buy_in)
@@ -124,14 +166,15 @@ impl TournamentManager {
         // Insert registration
         sqlx::query(
             r#"
-            INSERT INTO tournament_registrations (tournament_id, user_id, username, chip_count)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO tournament_registrations (tournament_id, user_id, username, chip_count, bounty)
+            VALUES ($1, $2, $3, $4, $5)
             "#,
         )
         .bind(tournament_id)
         .bind(user_id)
         .bind(&username)
         .bind(tournament.config.starting_stack)
+        .bind(tournament.config.tournament_type.bounty_per_player())
         .execute(self.pool.as_ref())
         .await?;
 
@@ -141,8 +184,11 @@ impl TournamentManager {
             .execute(self.pool.as_ref())
             .await?;
 
-        // Check if we should start (for Sit-n-Go)
-        if tournament.config.tournament_type == TournamentType::SitAndGo {
+        // Check if we should start (for Sit-n-Go and knockouts)
+        if matches!(
+            tournament.config.tournament_type,
+            TournamentType::SitAndGo | TournamentType::Knockout { .. }
+        ) {
             let new_count = tournament.registered_count + 1;
             if new_count >= tournament.config.max_players {
                 self.start_tournament(tournament_id).await?;
@@ -243,21 +289,325 @@ impl TournamentManager {
         Ok(next_level)
     }
 
+    /// Pause a running tournament's clock and stop dealing at its tables
+    ///
+    /// The time a tournament spends paused doesn't count toward its blind
+    /// levels. Pausing a paused tournament does nothing.
+    pub async fn pause_tournament(&self, tournament_id: TournamentId) -> TournamentResult<()> {
+        let tournament = self.get_tournament_info(tournament_id).await?;
+
+        if tournament.state != TournamentState::Running {
+            return Err(TournamentError::InvalidState {
+                expected: TournamentState::Running,
+                actual: tournament.state,
+            });
+        }
+
+        sqlx::query("UPDATE tournaments SET paused_at = NOW() WHERE id = $1 AND paused_at IS NULL")
+            .bind(tournament_id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Start a paused tournament's clock again. Resuming a tournament that
+    /// isn't paused does nothing.
+    pub async fn resume_tournament(&self, tournament_id: TournamentId) -> TournamentResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE tournaments
+            SET paused_ms = paused_ms
+                    + FLOOR(EXTRACT(EPOCH FROM NOW() - paused_at) * 1000)::BIGINT,
+                paused_at = NULL
+            WHERE id = $1 AND paused_at IS NOT NULL
+            "#,
+        )
+        .bind(tournament_id)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Move every running tournament along its blind structure (see
+    /// [`Self::update_clock`]). Meant to be called every second or so.
+    ///
+    /// A tournament whose clock can't be updated is logged and skipped, so
+    /// it doesn't hold up the others.
+    pub async fn update_clocks(&self, table_manager: &TableManager) -> TournamentResult<()> {
+        let tournament_ids: Vec<TournamentId> =
+            sqlx::query_scalar("SELECT id FROM tournaments WHERE state = 'running' ORDER BY id")
+                .fetch_all(self.pool.as_ref())
+                .await?;
+
+        for tournament_id in tournament_ids {
+            if let Err(e) = self.update_clock(tournament_id, table_manager).await {
+                log::warn!(
+                    "Failed to update clock of tournament {}: {}",
+                    tournament_id,
+                    e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move a running tournament along its blind structure
+    ///
+    /// Levels are timed by how long the tournament has been running, not
+    /// counting pauses, so every one of its tables moves up together. When
+    /// the clock reaches a new level, its blinds are pushed to the tables
+    /// and `ServerMessage::BlindLevelIncreased` is sent to subscribers.
+    /// During breaks and pauses, the tables finish their hands in progress
+    /// but don't deal new ones. The blinds of the level after a break are
+    /// pushed when the break starts, so the first hand after it is dealt
+    /// at them.
+    ///
+    /// A tournament that isn't running is left alone.
+    pub async fn update_clock(
+        &self,
+        tournament_id: TournamentId,
+        table_manager: &TableManager,
+    ) -> TournamentResult<()> {
+        let row = sqlx::query(
+            r#"
+            SELECT config, current_level, tables_on_break, paused_at IS NOT NULL AS paused,
+                   FLOOR(EXTRACT(EPOCH FROM COALESCE(paused_at, NOW()) - started_at) * 1000)::BIGINT
+                       - paused_ms AS elapsed_ms
+            FROM tournaments
+            WHERE id = $1 AND state = 'running' AND started_at IS NOT NULL
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        let Some(row) = row else {
+            return Ok(());
+        };
+
+        let config: TournamentConfig = serde_json::from_value(row.get("config"))?;
+        let current_level = row.get::<i32, _>("current_level") as u32;
+        let tables_on_break: bool = row.get("tables_on_break");
+        let paused: bool = row.get("paused");
+        let elapsed_ms: i64 = row.get("elapsed_ms");
+        let position = config.clock_position(elapsed_ms.max(0) as u64 / 1000);
+
+        let table_ids: Vec<TableId> = sqlx::query_scalar(
+            "SELECT table_id FROM tournament_tables WHERE tournament_id = $1 ORDER BY table_id",
+        )
+        .bind(tournament_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        if position.level > current_level {
+            // Only one caller gets to announce the new level
+            let result = sqlx::query(
+                "UPDATE tournaments SET current_level = $1, level_started_at = NOW() WHERE id = $2 AND current_level < $1",
+            )
+            .bind(position.level as i32)
+            .bind(tournament_id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+            if result.rows_affected() > 0 {
+                if let Some(level) = config.get_blind_level(position.level) {
+                    self.push_blinds(tournament_id, table_manager, &table_ids, level)
+                        .await;
+                }
+                log::info!(
+                    "Tournament {}: Blinds up to level {}",
+                    tournament_id,
+                    position.level
+                );
+                // No one listening isn't an error
+                let _ = self.events.send(ServerMessage::BlindLevelIncreased {
+                    tournament_id,
+                    new_level: position.level,
+                });
+            }
+        }
+
+        let on_break = paused || position.on_break;
+        if on_break != tables_on_break {
+            sqlx::query("UPDATE tournaments SET tables_on_break = $1 WHERE id = $2")
+                .bind(on_break)
+                .bind(tournament_id)
+                .execute(self.pool.as_ref())
+                .await?;
+
+            if on_break
+                && position.on_break
+                && let Some(next_level) = config.get_blind_level(position.level + 1)
+            {
+                self.push_blinds(tournament_id, table_manager, &table_ids, next_level)
+                    .await;
+            }
+            for table_id in &table_ids {
+                if let Err(e) = table_manager.set_on_break(*table_id, on_break).await {
+                    log::warn!(
+                        "Tournament {}: Failed to set break at table {}: {}",
+                        tournament_id,
+                        table_id,
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Push a level's blinds to a tournament's tables
+    async fn push_blinds(
+        &self,
+        tournament_id: TournamentId,
+        table_manager: &TableManager,
+        table_ids: &[TableId],
+        level: &BlindLevel,
+    ) {
+        for table_id in table_ids {
+            if let Err(e) = table_manager.update_blinds(*table_id, level.blinds()).await {
+                log::warn!(
+                    "Tournament {}: Failed to update blinds at table {}: {}",
+                    tournament_id,
+                    table_id,
+                    e
+                );
+            }
+        }
+    }
+
     /// Record player elimination
+    ///
+    /// In knockout tournaments, the eliminated player's bounty is split
+    /// between `eliminated_by` (see [`super::models::find_eliminators`]).
+    /// The cash part of each share is paid to the eliminator's wallet right
+    /// away, and the rest is added to their own bounty.
     pub async fn eliminate_player(
         &self,
         tournament_id: TournamentId,
         user_id: i64,
         position: usize,
-    ) -> TournamentResult<()> {
+        eliminated_by: &[i64],
+    ) -> TournamentResult<Vec<BountyAward>> {
         let tournament = self.get_tournament_info(tournament_id).await?;
 
         // Calculate prize (if in the money)
-        let prize_structure =
-            PrizeStructure::standard(tournament.registered_count, tournament.config.buy_in);
+        let prize_amount = tournament.prize_structure.payout_for_position(position);
 
-        let prize_amount = prize_structure.payout_for_position(position);
+        self.record_elimination(&tournament, user_id, position, prize_amount, eliminated_by)
+            .await
+    }
 
+    /// Record players eliminated in the same hand that tie for a position
+    ///
+    /// The players all finish in `position`, and share the prizes of the
+    /// positions from there down (see
+    /// [`super::models::PrizeStructure::split_tied`]), so bursting the
+    /// bubble together splits the last paid place. Odd chips go to the
+    /// first players in `user_ids`. Bounties are paid as in
+    /// [`Self::eliminate_player`].
+    pub async fn eliminate_tied_players(
+        &self,
+        tournament_id: TournamentId,
+        user_ids: &[i64],
+        position: usize,
+        eliminated_by: &[i64],
+    ) -> TournamentResult<Vec<BountyAward>> {
+        let tournament = self.get_tournament_info(tournament_id).await?;
+        let shares = tournament
+            .prize_structure
+            .split_tied(position, user_ids.len());
+
+        let mut awards = Vec::new();
+        for (user_id, share) in user_ids.iter().zip(shares) {
+            let prize_amount = (share > 0).then_some(share);
+            awards.extend(
+                self.record_elimination(
+                    &tournament,
+                    *user_id,
+                    position,
+                    prize_amount,
+                    eliminated_by,
+                )
+                .await?,
+            );
+        }
+
+        Ok(awards)
+    }
+
+    /// Record the players knocked out in the same hand
+    ///
+    /// Players are ranked by the stacks they started the hand with (see
+    /// [`super::models::rank_busts`]), so when a double bust bursts the
+    /// bubble, the deeper stack takes the last paid place and the shorter
+    /// stack misses the money. Players that started with the same stack tie
+    /// as in [`Self::eliminate_tied_players`]. In knockout tournaments, each
+    /// player's bounty goes to the winners that covered them.
+    ///
+    /// # Arguments
+    ///
+    /// * `busted` - Players knocked out, with the stacks they started the hand with
+    /// * `winners` - Players that won chips in the hand, with the stacks they started the hand with
+    pub async fn eliminate_busted_players(
+        &self,
+        tournament_id: TournamentId,
+        busted: &[(i64, i64)],
+        winners: &[(i64, i64)],
+    ) -> TournamentResult<Vec<BountyAward>> {
+        let tournament = self.get_tournament_info(tournament_id).await?;
+
+        let remaining: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM tournament_registrations WHERE tournament_id = $1 AND finish_position IS NULL",
+        )
+        .bind(tournament_id)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        let mut awards = Vec::new();
+        for (position, user_ids) in models::rank_busts(busted, remaining as usize) {
+            let shares = tournament
+                .prize_structure
+                .split_tied(position, user_ids.len());
+            for (user_id, share) in user_ids.iter().zip(shares) {
+                // Tied players started with the same stack, so they share eliminators
+                let stack = busted
+                    .iter()
+                    .find(|(id, _)| id == user_id)
+                    .map_or(0, |(_, stack)| *stack);
+                let eliminated_by = models::find_eliminators(stack, winners);
+                awards.extend(
+                    self.record_elimination(
+                        &tournament,
+                        *user_id,
+                        position,
+                        (share > 0).then_some(share),
+                        &eliminated_by,
+                    )
+                    .await?,
+                );
+            }
+        }
+
+        Ok(awards)
+    }
+
+    /// Record a player's finish and pay out their bounty
+    ///
+    /// Sends a [`ServerMessage::PlayerEliminated`] to subscribers once the
+    /// finish is recorded.
+    async fn record_elimination(
+        &self,
+        tournament: &TournamentInfo,
+        user_id: i64,
+        position: usize,
+        prize_amount: Option<i64>,
+        eliminated_by: &[i64],
+    ) -> TournamentResult<Vec<BountyAward>> {
+        let tournament_id = tournament.id;
         sqlx::query(
             r#"
             UPDATE tournament_registrations
@@ -272,7 +622,98 @@ impl TournamentManager {
         .execute(self.pool.as_ref())
         .await?;
 
-        Ok(())
+        let awards = if matches!(
+            tournament.config.tournament_type,
+            TournamentType::Knockout { .. }
+        ) {
+            Some(self.pay_bounty(tournament, user_id, eliminated_by).await?)
+        } else {
+            None
+        };
+
+        // No one listening isn't an error
+        let _ = self.events.send(ServerMessage::PlayerEliminated {
+            tournament_id,
+            user_id,
+            position,
+            prize: prize_amount,
+            bounty: awards.as_ref().map(|awards| {
+                awards
+                    .iter()
+                    .map(|award| award.cash + award.bounty_increase)
+                    .sum()
+            }),
+        });
+
+        Ok(awards.unwrap_or_default())
+    }
+
+    /// Collect an eliminated player's bounty and pay it to their eliminators
+    ///
+    /// Taking the bounty, raising the eliminators' bounties and crediting
+    /// their wallets happen in one transaction, so the bounty is paid
+    /// exactly once or not at all.
+    async fn pay_bounty(
+        &self,
+        tournament: &TournamentInfo,
+        user_id: i64,
+        eliminated_by: &[i64],
+    ) -> TournamentResult<Vec<BountyAward>> {
+        let tournament_id = tournament.id;
+        let mut tx = self.pool.begin().await?;
+
+        // Collect the eliminated player's bounty, zeroing it in the same
+        // statement so it can't be read twice
+        let row = sqlx::query(
+            r#"
+            UPDATE tournament_registrations AS r
+            SET bounty = 0
+            FROM (
+                SELECT bounty FROM tournament_registrations
+                WHERE tournament_id = $1 AND user_id = $2
+                FOR UPDATE
+            ) AS old
+            WHERE r.tournament_id = $1 AND r.user_id = $2
+            RETURNING old.bounty
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let bounty: i64 = row.map_or(0, |row| row.get("bounty"));
+
+        let awards = tournament.config.bounty_awards(bounty, eliminated_by);
+        for award in &awards {
+            if award.bounty_increase > 0 {
+                sqlx::query(
+                    "UPDATE tournament_registrations SET bounty = bounty + $1 WHERE tournament_id = $2 AND user_id = $3",
+                )
+                .bind(award.bounty_increase)
+                .bind(tournament_id)
+                .bind(award.user_id)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            if award.cash > 0 {
+                let idempotency_key =
+                    format!("bounty_{}_{}_{}", tournament_id, user_id, award.user_id);
+                self.wallet_manager
+                    .pay_bounty(
+                        &mut tx,
+                        award.user_id,
+                        award.cash,
+                        idempotency_key,
+                        format!("Bounty from tournament {tournament_id}"),
+                    )
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(awards)
     }
 
     /// Finish tournament
@@ -311,70 +752,15 @@ impl TournamentManager {
         &self,
         tournament_id: TournamentId,
     ) -> TournamentResult<TournamentInfo> {
-        let row = sqlx::query(
-            r#"
-            SELECT id, name, tournament_type, config, state, buy_in, registered_count,
-                   current_level, level_started_at, created_at, started_at, finished_at
-            FROM tournaments
-            WHERE id = $1
-            "#,
-        )
+        let row = sqlx::query(&format!(
+            "SELECT {INFO_COLUMNS} FROM tournaments WHERE id = $1"
+        ))
         .bind(tournament_id)
         .fetch_optional(self.pool.as_ref())
         .await?
         .ok_or(TournamentError::NotFound(tournament_id))?;
 
-        let config: TournamentConfig = serde_json::from_value(row.get("config"))?;
-        let state_str: String = row.get("state");
-        let state = match state_str.as_str() {
-            "registering" => TournamentState::Registering,
-            "running" => TournamentState::Running,
-            "finished" => TournamentState::Finished,
-            "cancelled" => TournamentState::Cancelled,
-            _ => TournamentState::Registering,
-        };
-
-        let registered_count: i32 = row.get("registered_count");
-        let current_level: i32 = row.get("current_level");
-
-        // Calculate time to next level
-        let time_to_next_level = if state == TournamentState::Running {
-            if let Some(level_started_at) =
-                row.get::<Option<chrono::NaiveDateTime>, _>("level_started_at")
-            {
-                let level_started_at = level_started_at.and_utc();
-                if let Some(current_blind) = config.get_blind_level(current_level as u32) {
-                    let elapsed = (Utc::now() - level_started_at).num_seconds();
-                    let remaining = current_blind.duration_secs as i64 - elapsed;
-                    Some(remaining.max(0) as u32)
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        let prize_structure = PrizeStructure::standard(registered_count as usize, config.buy_in);
-
-        Ok(TournamentInfo {
-            id: row.get("id"),
-            config,
-            state,
-            registered_count: registered_count as usize,
-            current_level: current_level as u32,
-            time_to_next_level,
-            prize_structure,
-            created_at: row.get::<chrono::NaiveDateTime, _>("created_at").and_utc(),
-            started_at: row
-                .get::<Option<chrono::NaiveDateTime>, _>("started_at")
-                .map(|dt| dt.and_utc()),
-            finished_at: row
-                .get::<Option<chrono::NaiveDateTime>, _>("finished_at")
-                .map(|dt| dt.and_utc()),
-        })
+        tournament_info_from_row(&row)
     }
 
     /// List all tournaments
@@ -389,85 +775,21 @@ impl TournamentManager {
                 TournamentState::Finished => "finished",
                 TournamentState::Cancelled => "cancelled",
             };
-            sqlx::query(
-                r#"
-                SELECT id, name, tournament_type, config, state, buy_in, registered_count,
-                       current_level, level_started_at, created_at, started_at, finished_at
-                FROM tournaments
-                WHERE state = $1
-                ORDER BY created_at DESC
-                "#,
-            )
+            sqlx::query(&format!(
+                "SELECT {INFO_COLUMNS} FROM tournaments WHERE state = $1 ORDER BY created_at DESC"
+            ))
             .bind(state_str)
+            .fetch_all(self.pool.as_ref())
+            .await?
         } else {
-            sqlx::query(
-                r#"
-                SELECT id, name, tournament_type, config, state, buy_in, registered_count,
-                       current_level, level_started_at, created_at, started_at, finished_at
-                FROM tournaments
-                ORDER BY created_at DESC
-                "#,
-            )
+            sqlx::query(&format!(
+                "SELECT {INFO_COLUMNS} FROM tournaments ORDER BY created_at DESC"
+            ))
+            .fetch_all(self.pool.as_ref())
+            .await?
         };
 
-        let rows = query.fetch_all(self.pool.as_ref()).await?;
-
-        let mut tournaments = Vec::new();
-        for row in rows {
-            let config: TournamentConfig = serde_json::from_value(row.get("config"))?;
-            let state_str: String = row.get("state");
-            let state = match state_str.as_str() {
-                "registering" => TournamentState::Registering,
-                "running" => TournamentState::Running,
-                "finished" => TournamentState::Finished,
-                "cancelled" => TournamentState::Cancelled,
-                _ => TournamentState::Registering,
-            };
-
-            let registered_count: i32 = row.get("registered_count");
-            let current_level: i32 = row.get("current_level");
-
-            let time_to_next_level = if state == TournamentState::Running {
-                if let Some(level_started_at) =
-                    row.get::<Option<chrono::NaiveDateTime>, _>("level_started_at")
-                {
-                    let level_started_at = level_started_at.and_utc();
-                    if let Some(current_blind) = config.get_blind_level(current_level as u32) {
-                        let elapsed = (Utc::now() - level_started_at).num_seconds();
-                        let remaining = current_blind.duration_secs as i64 - elapsed;
-                        Some(remaining.max(0) as u32)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-
-            let prize_structure =
-                PrizeStructure::standard(registered_count as usize, config.buy_in);
-
-            tournaments.push(TournamentInfo {
-                id: row.get("id"),
-                config,
-                state,
-                registered_count: registered_count as usize,
-                current_level: current_level as u32,
-                time_to_next_level,
-                prize_structure,
-                created_at: row.get::<chrono::NaiveDateTime, _>("created_at").and_utc(),
-                started_at: row
-                    .get::<Option<chrono::NaiveDateTime>, _>("started_at")
-                    .map(|dt| dt.and_utc()),
-                finished_at: row
-                    .get::<Option<chrono::NaiveDateTime>, _>("finished_at")
-                    .map(|dt| dt.and_utc()),
-            });
-        }
-
-        Ok(tournaments)
+        query.iter().map(tournament_info_from_row).collect()
     }
 
     /// Get tournament registrations
@@ -477,7 +799,8 @@ impl TournamentManager {
     ) -> TournamentResult<Vec<TournamentRegistration>> {
         let rows = sqlx::query(
             r#"
-            SELECT user_id, username, registered_at, chip_count, finish_position, prize_amount
+            SELECT user_id, username, registered_at, chip_count, finish_position, prize_amount,
+                   bounty
             FROM tournament_registrations
             WHERE tournament_id = $1
             ORDER BY registered_at
@@ -500,12 +823,191 @@ impl TournamentManager {
                     .get::<Option<i32>, _>("finish_position")
                     .map(|p| p as usize),
                 prize_amount: row.get("prize_amount"),
+                bounty: row.get("bounty"),
             })
             .collect();
 
         Ok(registrations)
     }
 
+    /// Spread a tournament's players across its tables
+    ///
+    /// Players are dealt out to the tables one at a time so table sizes
+    /// differ by at most one. Each table starts with the button on seat 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `tournament_id` - Tournament ID
+    /// * `table_ids` - Tables created for the tournament
+    /// * `max_players_per_table` - Number of seats at each table
+    ///
+    /// # Returns
+    ///
+    /// * `TournamentResult<Vec<TournamentTable>>` - Seating at each table
+    pub async fn assign_tables(
+        &self,
+        tournament_id: TournamentId,
+        table_ids: &[TableId],
+        max_players_per_table: usize,
+    ) -> TournamentResult<Vec<TournamentTable>> {
+        let registrations = self.get_registrations(tournament_id).await?;
+        let available = table_ids.len() * max_players_per_table;
+        if registrations.len() > available {
+            return Err(TournamentError::InsufficientSeats {
+                needed: registrations.len(),
+                available,
+            });
+        }
+
+        let mut tables: Vec<TournamentTable> = table_ids
+            .iter()
+            .map(|table_id| TournamentTable {
+                table_id: *table_id,
+                max_players: max_players_per_table,
+                button_seat: 0,
+                players: Vec::new(),
+            })
+            .collect();
+        for (i, registration) in registrations.iter().enumerate() {
+            let table = &mut tables[i % table_ids.len()];
+            table.players.push(SeatedPlayer {
+                user_id: registration.user_id,
+                seat: i / table_ids.len(),
+                chip_count: registration.chip_count,
+            });
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for table in &tables {
+            sqlx::query(
+                r#"
+                INSERT INTO tournament_tables (tournament_id, table_id, max_players, button_seat)
+                VALUES ($1, $2, $3, 0)
+                "#,
+            )
+            .bind(tournament_id)
+            .bind(table.table_id)
+            .bind(table.max_players as i32)
+            .execute(&mut *tx)
+            .await?;
+
+            for player in &table.players {
+                sqlx::query(
+                    "UPDATE tournament_registrations SET table_id = $1, seat = $2 WHERE tournament_id = $3 AND user_id = $4",
+                )
+                .bind(table.table_id)
+                .bind(player.seat as i32)
+                .bind(tournament_id)
+                .bind(player.user_id)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(tables)
+    }
+
+    /// Record where the dealer button is at a tournament table
+    pub async fn update_button(
+        &self,
+        tournament_id: TournamentId,
+        table_id: TableId,
+        button_seat: usize,
+    ) -> TournamentResult<()> {
+        sqlx::query(
+            "UPDATE tournament_tables SET button_seat = $1 WHERE tournament_id = $2 AND table_id = $3",
+        )
+        .bind(button_seat as i32)
+        .bind(tournament_id)
+        .bind(table_id)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Rebalance a tournament's tables after an elimination
+    ///
+    /// Moves players so every table is within one player of the others and
+    /// breaks up tables whose players fit elsewhere (see
+    /// [`balancing::plan_balance`]). The new seating is recorded, and the
+    /// returned plan lists the moves, with each player's stack, for the
+    /// caller to carry out at the table actors.
+    pub async fn balance_tables(
+        &self,
+        tournament_id: TournamentId,
+    ) -> TournamentResult<BalancePlan> {
+        let table_rows = sqlx::query(
+            r#"
+            SELECT table_id, max_players, button_seat
+            FROM tournament_tables
+            WHERE tournament_id = $1
+            ORDER BY table_id
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let mut tables: Vec<TournamentTable> = table_rows
+            .into_iter()
+            .map(|row| TournamentTable {
+                table_id: row.get("table_id"),
+                max_players: row.get::<i32, _>("max_players") as usize,
+                button_seat: row.get::<i32, _>("button_seat") as usize,
+                players: Vec::new(),
+            })
+            .collect();
+
+        let player_rows = sqlx::query(
+            r#"
+            SELECT user_id, table_id, seat, chip_count
+            FROM tournament_registrations
+            WHERE tournament_id = $1 AND finish_position IS NULL AND table_id IS NOT NULL
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        for row in player_rows {
+            let table_id: TableId = row.get("table_id");
+            if let Some(table) = tables.iter_mut().find(|t| t.table_id == table_id) {
+                table.players.push(SeatedPlayer {
+                    user_id: row.get("user_id"),
+                    seat: row.get::<Option<i32>, _>("seat").unwrap_or(0) as usize,
+                    chip_count: row.get("chip_count"),
+                });
+            }
+        }
+
+        let plan = balancing::plan_balance(&mut tables);
+
+        let mut tx = self.pool.begin().await?;
+        for table_move in &plan.moves {
+            sqlx::query(
+                "UPDATE tournament_registrations SET table_id = $1, seat = $2 WHERE tournament_id = $3 AND user_id = $4",
+            )
+            .bind(table_move.to_table)
+            .bind(table_move.to_seat as i32)
+            .bind(tournament_id)
+            .bind(table_move.user_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        for table_id in &plan.broken_tables {
+            sqlx::query("DELETE FROM tournament_tables WHERE tournament_id = $1 AND table_id = $2")
+                .bind(tournament_id)
+                .bind(table_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(plan)
+    }
+
     /// Update player chip count
     pub async fn update_chip_count(
         &self,
@@ -525,3 +1027,52 @@ impl TournamentManager {
         Ok(())
     }
 }
+
+/// Build a tournament's info from a row of [`INFO_COLUMNS`]
+fn tournament_info_from_row(row: &PgRow) -> TournamentResult<TournamentInfo> {
+    let config: TournamentConfig = serde_json::from_value(row.get("config"))?;
+    let state_str: String = row.get("state");
+    let state = match state_str.as_str() {
+        "registering" => TournamentState::Registering,
+        "running" => TournamentState::Running,
+        "finished" => TournamentState::Finished,
+        "cancelled" => TournamentState::Cancelled,
+        _ => TournamentState::Registering,
+    };
+
+    let registered_count: i32 = row.get("registered_count");
+    let current_level: i32 = row.get("current_level");
+
+    // Time to the next level comes from the tournament's clock, which
+    // stops while the tournament is paused
+    let clock = match row.get::<Option<i64>, _>("elapsed_ms") {
+        Some(elapsed_ms) if state == TournamentState::Running => {
+            Some(config.clock_position(elapsed_ms.max(0) as u64 / 1000))
+        }
+        _ => None,
+    };
+    let time_to_next_level = clock
+        .and_then(|clock| clock.secs_left)
+        .map(|secs| u32::try_from(secs).unwrap_or(u32::MAX));
+
+    let prize_structure = config.prize_structure(registered_count as usize);
+
+    Ok(TournamentInfo {
+        id: row.get("id"),
+        config,
+        state,
+        registered_count: registered_count as usize,
+        current_level: current_level as u32,
+        time_to_next_level,
+        paused: row.get("paused"),
+        on_break: clock.is_some_and(|clock| clock.on_break),
+        prize_structure,
+        created_at: row.get::<chrono::NaiveDateTime, _>("created_at").and_utc(),
+        started_at: row
+            .get::<Option<chrono::NaiveDateTime>, _>("started_at")
+            .map(|dt| dt.and_utc()),
+        finished_at: row
+            .get::<Option<chrono::NaiveDateTime>, _>("finished_at")
+            .map(|dt| dt.and_utc()),
+    })
+}
@@ -3,9 +3,13 @@
 //! This module provides tournament management functionality including:
 //! - Tournament creation and configuration
 //! - Player registration and buy-ins
-//! - Blind level progression
-//! - Prize pool calculation and distribution
+//! - Blind level progression on a clock shared by all of a tournament's
+//!   tables, with breaks and pauses
+//! - Prize pool calculation and distribution, with custom and satellite
+//!   payouts
 //! - Player elimination tracking
+//! - Knockout bounties
+//! - Seat balancing across multiple tables
 //!
 //! ## Example
 //!
@@ -33,11 +37,13 @@
 //! }
 //! ```
 
+pub mod balancing;
 pub mod manager;
 pub mod models;
 
+pub use balancing::{BalancePlan, SeatedPlayer, TableMove, TournamentTable};
 pub use manager::{TournamentError, TournamentManager, TournamentResult};
 pub use models::{
-    BlindLevel, PrizeStructure, TournamentConfig, TournamentId, TournamentInfo,
-    TournamentRegistration, TournamentState, TournamentType,
+    BlindLevel, BountyAward, ClockPosition, PayoutStructure, PrizeStructure, TournamentConfig,
+    TournamentId, TournamentInfo, TournamentRegistration, TournamentState, TournamentType,
 };
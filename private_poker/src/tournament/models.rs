@@ -1,5 +1,6 @@
 //! Tournament data models for Sit-n-Go tournaments.
 
+use crate::game::entities::{Blinds, Usd};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +27,31 @@ pub enum TournamentType {
     SitAndGo,
     /// Scheduled tournament (starts at specific time)
     Scheduled,
+    /// Knockout tournament (eliminating a player pays their bounty)
+    Knockout {
+        /// Bounty each player starts with, paid on top of the buy-in
+        bounty_per_player: i64,
+    },
+}
+
+impl TournamentType {
+    /// Bounty each player starts with (zero unless it's a knockout)
+    pub fn bounty_per_player(&self) -> i64 {
+        match self {
+            Self::Knockout { bounty_per_player } => *bounty_per_player,
+            _ => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for TournamentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SitAndGo => write!(f, "sit_and_go"),
+            Self::Scheduled => write!(f, "scheduled"),
+            Self::Knockout { .. } => write!(f, "knockout"),
+        }
+    }
 }
 
 /// Blind structure for tournament
@@ -41,6 +67,10 @@ pub struct BlindLevel {
     pub ante: Option<i64>,
     /// Duration of this level in seconds
     pub duration_secs: u32,
+    /// Length of the break after this level in seconds, during which no
+    /// hands are dealt (only used by tournament clocks)
+    #[serde(default)]
+    pub break_secs: u32,
 }
 
 impl BlindLevel {
@@ -52,6 +82,7 @@ impl BlindLevel {
             big_blind,
             ante: None,
             duration_secs,
+            break_secs: 0,
         }
     }
 
@@ -60,6 +91,33 @@ impl BlindLevel {
         self.ante = Some(ante);
         self
     }
+
+    /// Create a blind level followed by a break
+    pub fn with_break(mut self, break_secs: u32) -> Self {
+        self.break_secs = break_secs;
+        self
+    }
+
+    /// Minimum blinds a table plays at during this level
+    pub fn blinds(&self) -> Blinds {
+        Blinds {
+            small: self.small_blind.max(0) as Usd,
+            big: self.big_blind.max(0) as Usd,
+            ante: self.ante.unwrap_or(0).max(0) as Usd,
+        }
+    }
+}
+
+/// Where a tournament's clock is in its blind structure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockPosition {
+    /// Current blind level, or the level just finished during a break
+    pub level: u32,
+    /// Whether the clock is on the break after `level`
+    pub on_break: bool,
+    /// Seconds until the level or break is over, or `None` for the last
+    /// level, which lasts until the tournament ends
+    pub secs_left: Option<u64>,
 }
 
 /// Prize structure for tournament
@@ -130,6 +188,66 @@ impl PrizeStructure {
         }
     }
 
+    /// Create a prize structure paying shares of the pool given in basis
+    /// points (10,000 = 100%)
+    ///
+    /// Each payout is rounded down, and the chips left over go one at a
+    /// time to the payouts that lost the most to rounding (the better
+    /// finish on ties), so the payouts always add up to the pool.
+    pub fn from_basis_points(total_pool: i64, basis_points: &[u32]) -> Self {
+        let total_pool = total_pool.max(0);
+        let total_bps: i128 = basis_points.iter().map(|bps| i128::from(*bps)).sum();
+        if total_bps == 0 {
+            return Self {
+                total_pool,
+                payouts: vec![total_pool],
+            };
+        }
+
+        let exact: Vec<i128> = basis_points
+            .iter()
+            .map(|bps| i128::from(total_pool) * i128::from(*bps))
+            .collect();
+        let mut payouts: Vec<i64> = exact.iter().map(|e| (e / total_bps) as i64).collect();
+
+        let mut by_remainder: Vec<usize> = (0..payouts.len()).collect();
+        by_remainder.sort_by_key(|i| std::cmp::Reverse(exact[*i] % total_bps));
+        let leftover = total_pool - payouts.iter().sum::<i64>();
+        for i in by_remainder.into_iter().take(leftover as usize) {
+            payouts[i] += 1;
+        }
+
+        Self {
+            total_pool,
+            payouts,
+        }
+    }
+
+    /// Create a satellite prize structure where the top finishers each win
+    /// a seat worth `seat_value`, and the next finisher gets what's left
+    /// of the pool in cash
+    pub fn satellite(total_pool: i64, seat_value: i64) -> Self {
+        let total_pool = total_pool.max(0);
+        if seat_value <= 0 {
+            return Self {
+                total_pool,
+                payouts: vec![total_pool],
+            };
+        }
+
+        let seats = (total_pool / seat_value) as usize;
+        let mut payouts = vec![seat_value; seats];
+        let leftover = total_pool % seat_value;
+        if leftover > 0 {
+            payouts.push(leftover);
+        }
+
+        Self {
+            total_pool,
+            payouts,
+        }
+    }
+
     /// Get payout for a specific position (1-indexed)
     pub fn payout_for_position(&self, position: usize) -> Option<i64> {
         if position == 0 || position > self.payouts.len() {
@@ -138,6 +256,83 @@ impl PrizeStructure {
             Some(self.payouts[position - 1])
         }
     }
+
+    /// Split the payouts of the positions players tied for
+    ///
+    /// Players knocked out in the same hand with the same starting stack
+    /// tie for the positions from `position` down, and share those
+    /// positions' payouts equally. Odd chips go to the first players, in
+    /// the order the caller lists them.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Best position the players tied for (1-indexed)
+    /// * `num_players` - Number of players that tied
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<i64>` - Each tied player's share
+    pub fn split_tied(&self, position: usize, num_players: usize) -> Vec<i64> {
+        if num_players == 0 {
+            return Vec::new();
+        }
+
+        let combined: i64 = (position..position + num_players)
+            .filter_map(|p| self.payout_for_position(p))
+            .sum();
+        let share = combined / num_players as i64;
+        let odd_chips = (combined % num_players as i64) as usize;
+        (0..num_players)
+            .map(|i| if i < odd_chips { share + 1 } else { share })
+            .collect()
+    }
+}
+
+/// How a tournament's prize pool is paid out
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PayoutStructure {
+    /// Payouts depend on the number of players (see
+    /// [`PrizeStructure::standard`])
+    #[default]
+    Standard,
+    /// Shares of the pool for 1st, 2nd, and so on, in basis points that
+    /// add up to 10,000 (100%)
+    Custom { basis_points: Vec<u32> },
+    /// The top finishers each win a seat worth `seat_value`, and the next
+    /// finisher gets what's left in cash
+    Satellite { seat_value: i64 },
+}
+
+impl PayoutStructure {
+    /// Check the structure pays out the whole pool
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if custom shares are empty or
+    /// don't add up to 100%, or if satellite seats aren't worth anything.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Standard => Ok(()),
+            Self::Custom { basis_points } => {
+                let total: u64 = basis_points.iter().map(|bps| u64::from(*bps)).sum();
+                if basis_points.is_empty() {
+                    Err("Custom payouts need at least one share".to_string())
+                } else if total != 10_000 {
+                    Err(format!(
+                        "Custom payouts must add up to 100%, not {}.{:02}%",
+                        total / 100,
+                        total % 100
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            Self::Satellite { seat_value } if *seat_value <= 0 => {
+                Err("Satellite seats must be worth more than 0 chips".to_string())
+            }
+            Self::Satellite { .. } => Ok(()),
+        }
+    }
 }
 
 /// Tournament configuration
@@ -163,6 +358,17 @@ pub struct TournamentConfig {
     pub scheduled_start: Option<DateTime<Utc>>,
     /// Late registration period in seconds
     pub late_registration_secs: Option<u32>,
+    /// Percentage of a collected bounty paid straight to the eliminator
+    /// in knockout tournaments; the rest is added to their own bounty
+    #[serde(default = "default_bounty_cash_percent")]
+    pub bounty_cash_percent: u8,
+    /// How the prize pool is paid out
+    #[serde(default)]
+    pub payout_structure: PayoutStructure,
+}
+
+fn default_bounty_cash_percent() -> u8 {
+    50
 }
 
 impl TournamentConfig {
@@ -195,9 +401,18 @@ impl TournamentConfig {
             starting_level: 1,
             scheduled_start: None,
             late_registration_secs: None,
+            bounty_cash_percent: default_bounty_cash_percent(),
+            payout_structure: PayoutStructure::Standard,
         }
     }
 
+    /// Create a knockout Sit-n-Go where each player carries a bounty
+    pub fn knockout(name: String, max_players: usize, buy_in: i64, bounty_per_player: i64) -> Self {
+        let mut config = Self::sit_and_go(name, max_players, buy_in);
+        config.tournament_type = TournamentType::Knockout { bounty_per_player };
+        config
+    }
+
     /// Create a turbo Sit-n-Go (faster blind increases)
     pub fn turbo_sit_and_go(name: String, max_players: usize, buy_in: i64) -> Self {
         let mut config = Self::sit_and_go(name, max_players, buy_in);
@@ -208,10 +423,160 @@ impl TournamentConfig {
         config
     }
 
+    /// Work out the payouts for a tournament with `total_players` buy-ins
+    pub fn prize_structure(&self, total_players: usize) -> PrizeStructure {
+        match &self.payout_structure {
+            PayoutStructure::Standard => PrizeStructure::standard(total_players, self.buy_in),
+            PayoutStructure::Custom { basis_points } => {
+                let total_pool = total_players as i64 * self.buy_in;
+                PrizeStructure::from_basis_points(total_pool, basis_points)
+            }
+            PayoutStructure::Satellite { seat_value } => {
+                let total_pool = total_players as i64 * self.buy_in;
+                PrizeStructure::satellite(total_pool, *seat_value)
+            }
+        }
+    }
+
     /// Get blind level by number
     pub fn get_blind_level(&self, level: u32) -> Option<&BlindLevel> {
         self.blind_levels.iter().find(|bl| bl.level == level)
     }
+
+    /// Find where the clock is after `elapsed_secs` of tournament time
+    ///
+    /// Levels run in order from `starting_level`, each followed by its
+    /// break. Time the tournament spends paused shouldn't be counted in
+    /// `elapsed_secs`.
+    pub fn clock_position(&self, elapsed_secs: u64) -> ClockPosition {
+        let mut levels: Vec<&BlindLevel> = self
+            .blind_levels
+            .iter()
+            .filter(|bl| bl.level >= self.starting_level)
+            .collect();
+        levels.sort_by_key(|bl| bl.level);
+
+        let mut remaining = elapsed_secs;
+        for (i, level) in levels.iter().enumerate() {
+            if i + 1 == levels.len() {
+                break;
+            }
+            let duration = u64::from(level.duration_secs);
+            if remaining < duration {
+                return ClockPosition {
+                    level: level.level,
+                    on_break: false,
+                    secs_left: Some(duration - remaining),
+                };
+            }
+            remaining -= duration;
+            let break_secs = u64::from(level.break_secs);
+            if remaining < break_secs {
+                return ClockPosition {
+                    level: level.level,
+                    on_break: true,
+                    secs_left: Some(break_secs - remaining),
+                };
+            }
+            remaining -= break_secs;
+        }
+
+        ClockPosition {
+            level: levels.last().map_or(self.starting_level, |bl| bl.level),
+            on_break: false,
+            secs_left: None,
+        }
+    }
+
+    /// Split an eliminated player's bounty between the players that
+    /// eliminated them
+    ///
+    /// Each eliminator gets an equal share, with any odd chips going to the
+    /// first eliminator. `bounty_cash_percent` of each share is paid out
+    /// immediately and the rest is added to the eliminator's own bounty.
+    pub fn bounty_awards(&self, bounty: i64, eliminators: &[i64]) -> Vec<BountyAward> {
+        if bounty <= 0 || eliminators.is_empty() {
+            return Vec::new();
+        }
+
+        let num_eliminators = eliminators.len() as i64;
+        let share = bounty / num_eliminators;
+        let remainder = bounty % num_eliminators;
+        let cash_percent = i64::from(self.bounty_cash_percent.min(100));
+
+        eliminators
+            .iter()
+            .enumerate()
+            .map(|(i, user_id)| {
+                let share = if i == 0 { share + remainder } else { share };
+                let cash = share * cash_percent / 100;
+                BountyAward {
+                    user_id: *user_id,
+                    cash,
+                    bounty_increase: share - cash,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Bounty collected for knocking a player out of a knockout tournament
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BountyAward {
+    /// Eliminator's user ID
+    pub user_id: i64,
+    /// Paid straight to the eliminator's wallet
+    pub cash: i64,
+    /// Added to the eliminator's own bounty
+    pub bounty_increase: i64,
+}
+
+/// Find the players that eliminated a busted player
+///
+/// `winners` are the players that won chips in the hand along with the
+/// stacks they started the hand with. In multi-way all-ins, only winners
+/// that covered the busted stack could've won the busted player's last
+/// chips, so players with shorter stacks don't get a share of the bounty.
+pub fn find_eliminators(busted_stack: i64, winners: &[(i64, i64)]) -> Vec<i64> {
+    winners
+        .iter()
+        .filter(|(_, stack)| *stack >= busted_stack)
+        .map(|(user_id, _)| *user_id)
+        .collect()
+}
+
+/// Rank the players knocked out in the same hand
+///
+/// `busted` are the players knocked out along with the stacks they started
+/// the hand with, and `remaining` is how many players were left in the
+/// tournament going into the hand (busted players included). The deeper
+/// stack finishes ahead, and players that started with the same stack tie.
+///
+/// # Returns
+///
+/// * `Vec<(usize, Vec<i64>)>` - Each finishing position, best first, with
+///   the players that finished there
+pub fn rank_busts(busted: &[(i64, i64)], remaining: usize) -> Vec<(usize, Vec<i64>)> {
+    let mut busted = busted.to_vec();
+    busted.sort_by_key(|(_, stack)| std::cmp::Reverse(*stack));
+
+    let mut position = (remaining + 1).saturating_sub(busted.len()).max(1);
+    let mut ranks: Vec<(usize, Vec<i64>)> = Vec::new();
+    let mut last_stack = None;
+    for (user_id, stack) in busted {
+        match ranks.last_mut() {
+            Some((_, user_ids)) if last_stack == Some(stack) => user_ids.push(user_id),
+            _ => {
+                if let Some((_, user_ids)) = ranks.last() {
+                    position += user_ids.len();
+                }
+                ranks.push((position, vec![user_id]));
+            }
+        }
+        last_stack = Some(stack);
+    }
+
+    ranks
 }
 
 /// Tournament registration entry
@@ -229,6 +594,9 @@ pub struct TournamentRegistration {
     pub finish_position: Option<usize>,
     /// Prize amount (None if not in the money)
     pub prize_amount: Option<i64>,
+    /// Bounty on the player's head (knockout tournaments only)
+    #[serde(default)]
+    pub bounty: i64,
 }
 
 /// Tournament information
@@ -246,6 +614,12 @@ pub struct TournamentInfo {
     pub current_level: u32,
     /// Time until next level (seconds)
     pub time_to_next_level: Option<u32>,
+    /// Whether the clock is paused
+    #[serde(default)]
+    pub paused: bool,
+    /// Whether the clock is on a break between levels
+    #[serde(default)]
+    pub on_break: bool,
     /// Prize structure
     pub prize_structure: PrizeStructure,
     /// Created at timestamp
@@ -294,6 +668,47 @@ mod tests {
         assert_eq!(prize.payouts, vec![500, 300, 200]);
     }
 
+    #[test]
+    fn test_satellite_prize_structure() {
+        // 3 seats, with what's left going to 4th
+        let prize = PrizeStructure::satellite(1050, 300);
+        assert_eq!(prize.payouts, vec![300, 300, 300, 150]);
+
+        // No leftover, so no cash prize
+        let prize = PrizeStructure::satellite(900, 300);
+        assert_eq!(prize.payouts, vec![300, 300, 300]);
+    }
+
+    #[test]
+    fn test_split_tied_bubble() {
+        let prize = PrizeStructure::from_basis_points(1001, &[5000, 3000, 2000]);
+        assert_eq!(prize.payouts, vec![501, 300, 200]);
+
+        // Two players bust together in 3rd and 4th and share 3rd's prize
+        assert_eq!(prize.split_tied(3, 2), vec![100, 100]);
+        // The odd chip goes to the first player listed
+        assert_eq!(prize.split_tied(1, 2), vec![401, 400]);
+        assert_eq!(prize.split_tied(4, 2), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_payout_structure_validation() {
+        assert!(PayoutStructure::Standard.validate().is_ok());
+        let custom = |basis_points: Vec<u32>| PayoutStructure::Custom { basis_points };
+        assert!(custom(vec![5000, 3000, 2000]).validate().is_ok());
+        assert!(custom(vec![5000, 3000, 1950]).validate().is_err());
+        assert!(custom(vec![]).validate().is_err());
+        assert!(
+            PayoutStructure::Satellite { seat_value: 0 }
+                .validate()
+                .is_err()
+        );
+
+        let mut config = TournamentConfig::sit_and_go("Sat".to_string(), 9, 100);
+        config.payout_structure = PayoutStructure::Satellite { seat_value: 300 };
+        assert_eq!(config.prize_structure(7).payouts, vec![300, 300, 100]);
+    }
+
     #[test]
     fn test_payout_for_position() {
         let prize = PrizeStructure::standard(10, 100);
@@ -334,6 +749,111 @@ mod tests {
         assert!(level_99.is_none());
     }
 
+    #[test]
+    fn test_knockout_pays_bounty() {
+        let config = TournamentConfig::knockout("KO".to_string(), 9, 100, 50);
+        assert_eq!(
+            config.tournament_type,
+            TournamentType::Knockout {
+                bounty_per_player: 50
+            }
+        );
+        assert_eq!(config.tournament_type.bounty_per_player(), 50);
+
+        // The covering player is the only one that eliminated the busted player.
+        let eliminators = find_eliminators(1000, &[(1, 3000), (2, 500)]);
+        assert_eq!(eliminators, vec![1]);
+
+        let awards = config.bounty_awards(50, &eliminators);
+        assert_eq!(
+            awards,
+            vec![BountyAward {
+                user_id: 1,
+                cash: 25,
+                bounty_increase: 25,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_rank_busts_puts_deeper_stack_ahead() {
+        // Four left, and two bust on the bubble of a three-place payout.
+        let ranks = rank_busts(&[(1, 800), (2, 1200)], 4);
+        assert_eq!(ranks, vec![(3, vec![2]), (4, vec![1])]);
+
+        // Equal stacks tie, and the next player down skips the tied places.
+        let ranks = rank_busts(&[(1, 500), (2, 900), (3, 500), (4, 200)], 6);
+        assert_eq!(ranks, vec![(3, vec![2]), (4, vec![1, 3]), (6, vec![4])]);
+    }
+
+    #[test]
+    fn test_knockout_chop_shares_bounty() {
+        let mut config = TournamentConfig::knockout("KO".to_string(), 9, 100, 50);
+        config.bounty_cash_percent = 100;
+
+        let eliminators = find_eliminators(1000, &[(1, 1000), (2, 2000)]);
+        assert_eq!(eliminators, vec![1, 2]);
+
+        // The odd chip goes to the first eliminator.
+        let awards = config.bounty_awards(75, &eliminators);
+        assert_eq!(
+            awards,
+            vec![
+                BountyAward {
+                    user_id: 1,
+                    cash: 38,
+                    bounty_increase: 0,
+                },
+                BountyAward {
+                    user_id: 2,
+                    cash: 37,
+                    bounty_increase: 0,
+                },
+            ]
+        );
+        assert_eq!(awards.iter().map(|a| a.cash).sum::<i64>(), 75);
+    }
+
+    #[test]
+    fn test_clock_position_counts_levels_and_breaks() {
+        let mut config = TournamentConfig::sit_and_go("Clock".to_string(), 9, 100);
+        config.blind_levels = vec![
+            BlindLevel::new(1, 10, 20, 60).with_break(30),
+            BlindLevel::new(2, 20, 40, 60),
+            BlindLevel::new(3, 40, 80, 60),
+        ];
+
+        let at = |secs| config.clock_position(secs);
+        assert_eq!(
+            at(0),
+            ClockPosition {
+                level: 1,
+                on_break: false,
+                secs_left: Some(60),
+            }
+        );
+        assert_eq!(
+            at(70),
+            ClockPosition {
+                level: 1,
+                on_break: true,
+                secs_left: Some(20),
+            }
+        );
+        assert_eq!(at(90).level, 2);
+        assert!(!at(90).on_break);
+        assert_eq!(at(150).level, 3);
+
+        // The last level lasts until the tournament ends
+        assert_eq!(at(10_000).level, 3);
+        assert_eq!(at(10_000).secs_left, None);
+
+        // Tournaments starting at a later level skip the earlier ones
+        config.starting_level = 2;
+        assert_eq!(config.clock_position(0).level, 2);
+        assert_eq!(config.clock_position(60).level, 3);
+    }
+
     #[test]
     fn test_blind_level_with_ante() {
         let level = BlindLevel::new(5, 100, 200, 300).with_ante(25);
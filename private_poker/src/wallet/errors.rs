@@ -1,5 +1,6 @@
 //! Wallet error types.
 
+use crate::db::retry::{RetryableError, is_transient_db_error};
 use thiserror::Error;
 
 /// Wallet errors
@@ -37,6 +38,14 @@ pub enum WalletError {
     #[error("Faucet claim not available until {0}")]
     FaucetNotAvailable(chrono::DateTime<chrono::Utc>),
 
+    /// Wallet is frozen and can't move chips
+    #[error("Wallet is frozen for user {0}")]
+    Frozen(i64),
+
+    /// Chips can't be transferred to the same user
+    #[error("Cannot transfer chips to yourself")]
+    SelfTransfer,
+
     /// Balance overflow (arithmetic overflow prevented)
     #[error("Balance overflow: operation would exceed maximum balance")]
     BalanceOverflow,
@@ -48,6 +57,20 @@ pub enum WalletError {
     /// Transaction failed
     #[error("Transaction failed: {0}")]
     TransactionFailed(String),
+
+    /// Database is failing and the circuit breaker is open
+    #[error("Database temporarily unavailable")]
+    Unavailable,
+}
+
+impl RetryableError for WalletError {
+    fn is_transient(&self) -> bool {
+        matches!(self, WalletError::Database(e) if is_transient_db_error(e))
+    }
+
+    fn circuit_open() -> Self {
+        WalletError::Unavailable
+    }
 }
 
 impl WalletError {
@@ -61,6 +84,8 @@ impl WalletError {
             WalletError::Database(_) => "Internal server error".to_string(),
             // Sanitize wallet not found - don't expose user IDs
             WalletError::WalletNotFound(_) => "Wallet not found".to_string(),
+            // Sanitize frozen wallet - don't expose user IDs
+            WalletError::Frozen(_) => "Wallet is frozen".to_string(),
             // Sanitize escrow not found - don't expose table IDs
             WalletError::EscrowNotFound(_) => "Escrow not found".to_string(),
             // All other errors are safe to expose
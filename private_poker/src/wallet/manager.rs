@@ -3,11 +3,16 @@
 
 use super::{
     errors::{WalletError, WalletResult},
-    models::{EntryDirection, EntryType, FaucetClaim, TableEscrow, TableId, Wallet, WalletEntry},
+    models::{
+        EntryDirection, EntryFilter, EntryType, EscrowDiscrepancy, FaucetClaim, FaucetStatus,
+        ReconcileReport, SummaryPeriod, TableEscrow, TableId, Wallet, WalletEntry, WalletSummary,
+    },
 };
-use chrono::{Duration, Utc};
-use sqlx::{PgPool, Postgres, Row, Transaction};
-use std::sync::Arc;
+use crate::db::retry::{CircuitBreaker, DbRetry};
+use crate::security::{AuditAction, AuditEntry, audit_log::write_batch};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction, postgres::PgRow};
+use std::{collections::HashMap, sync::Arc};
 
 /// Wallet manager
 #[derive(Clone)]
@@ -17,6 +22,7 @@ pub struct WalletManager {
     default_balance: i64,
     faucet_amount: i64,
     faucet_cooldown: Duration,
+    retry: DbRetry,
 }
 
 impl WalletManager {
@@ -50,6 +56,7 @@ impl WalletManager {
             default_balance,
             faucet_amount,
             faucet_cooldown: Duration::hours(faucet_cooldown_hours),
+            retry: DbRetry::default(),
         }
     }
 
@@ -65,7 +72,7 @@ impl WalletManager {
     pub async fn get_wallet(&self, user_id: i64) -> WalletResult<Wallet> {
         let row = sqlx::query(
             r#"
-            SELECT user_id, balance, currency, created_at, updated_at
+            SELECT user_id, balance, currency, frozen, frozen_reason, created_at, updated_at
             FROM wallets
             WHERE user_id = $1
             "#,
@@ -79,6 +86,8 @@ impl WalletManager {
             user_id: row.get("user_id"),
             balance: row.get("balance"),
             currency: row.get("currency"),
+            frozen: row.get("frozen"),
+            frozen_reason: row.get("frozen_reason"),
             created_at: row.get::<chrono::NaiveDateTime, _>("created_at").and_utc(),
             updated_at: row.get::<chrono::NaiveDateTime, _>("updated_at").and_utc(),
         })
@@ -129,6 +138,7 @@ impl WalletManager {
     ///
     /// # Errors
     ///
+    /// * `WalletError::Frozen` - Wallet is frozen
     /// * `WalletError::InsufficientBalance` - Not enough chips
     /// * `WalletError::DuplicateTransaction` - Idempotency key already used
     pub async fn transfer_to_escrow(
@@ -155,6 +165,9 @@ impl WalletManager {
             return Err(WalletError::DuplicateTransaction(idempotency_key));
         }
 
+        // Frozen players can't buy in to tables
+        self.lock_unfrozen_wallet(&mut tx, user_id).await?;
+
         // Atomically debit wallet with balance check
         // This prevents race conditions by checking and updating in a single atomic operation
         let wallet_result = sqlx::query(
@@ -347,6 +360,488 @@ impl WalletManager {
         Ok(new_balance)
     }
 
+    /// Transfer chips to table escrow, retrying transient database errors
+    ///
+    /// Retries are bounded by the manager's retry policy and reuse the same
+    /// idempotency key, so an attempt that committed but lost its reply is
+    /// seen as a duplicate on the next try and treated as applied. Fails fast
+    /// with `WalletError::Unavailable` while the circuit breaker is open.
+    pub async fn transfer_to_escrow_with_retry(
+        &self,
+        user_id: i64,
+        table_id: TableId,
+        amount: i64,
+        idempotency_key: String,
+    ) -> WalletResult<i64> {
+        self.retry
+            .run(|attempt| {
+                let key = idempotency_key.clone();
+                async move {
+                    let result = self
+                        .transfer_to_escrow(user_id, table_id, amount, key)
+                        .await;
+                    self.settle_retried_transfer(user_id, attempt, result).await
+                }
+            })
+            .await
+    }
+
+    /// Transfer chips out of table escrow, retrying transient database errors
+    ///
+    /// See [`WalletManager::transfer_to_escrow_with_retry`].
+    pub async fn transfer_from_escrow_with_retry(
+        &self,
+        user_id: i64,
+        table_id: TableId,
+        amount: i64,
+        idempotency_key: String,
+    ) -> WalletResult<i64> {
+        self.retry
+            .run(|attempt| {
+                let key = idempotency_key.clone();
+                async move {
+                    let result = self
+                        .transfer_from_escrow(user_id, table_id, amount, key)
+                        .await;
+                    self.settle_retried_transfer(user_id, attempt, result).await
+                }
+            })
+            .await
+    }
+
+    /// Treat a duplicate on a retry as the earlier attempt having committed
+    async fn settle_retried_transfer(
+        &self,
+        user_id: i64,
+        attempt: u32,
+        result: WalletResult<i64>,
+    ) -> WalletResult<i64> {
+        match result {
+            Err(WalletError::DuplicateTransaction(_)) if attempt > 1 => {
+                Ok(self.get_wallet(user_id).await?.balance)
+            }
+            other => other,
+        }
+    }
+
+    /// Circuit breaker guarding retried wallet calls
+    pub fn db_breaker(&self) -> &CircuitBreaker {
+        self.retry.breaker()
+    }
+
+    /// Transfer rake from a table escrow into the table's rake account
+    ///
+    /// The ledger entry is recorded against the rake account, which uses the
+    /// negated table ID as its account ID so it can't collide with a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_id` - Table ID
+    /// * `amount` - Amount of rake to collect
+    /// * `idempotency_key` - Unique key to prevent duplicate transactions
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<i64>` - New rake account balance or error
+    pub async fn collect_rake(
+        &self,
+        table_id: TableId,
+        amount: i64,
+        idempotency_key: String,
+    ) -> WalletResult<i64> {
+        if amount <= 0 {
+            return Err(WalletError::InvalidAmount(amount));
+        }
+
+        // Start transaction
+        let mut tx = self.pool.begin().await?;
+
+        // Check for duplicate transaction
+        let existing = sqlx::query("SELECT id FROM wallet_entries WHERE idempotency_key = $1")
+            .bind(&idempotency_key)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if existing.is_some() {
+            return Err(WalletError::DuplicateTransaction(idempotency_key));
+        }
+
+        // Atomically debit escrow with balance check
+        let escrow_result = sqlx::query(
+            "UPDATE table_escrows
+             SET balance = balance - $1, updated_at = NOW()
+             WHERE table_id = $2 AND balance >= $1
+             RETURNING balance",
+        )
+        .bind(amount)
+        .bind(table_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if escrow_result.is_none() {
+            let check_escrow = sqlx::query("SELECT balance FROM table_escrows WHERE table_id = $1")
+                .bind(table_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+            return match check_escrow {
+                Some(row) => Err(WalletError::InsufficientBalance {
+                    user_id: -table_id,
+                    available: row.get("balance"),
+                    required: amount,
+                }),
+                None => Err(WalletError::EscrowNotFound(table_id)),
+            };
+        }
+
+        // Credit rake account (create if doesn't exist)
+        let rake_row = sqlx::query(
+            "INSERT INTO rake_accounts (table_id, balance, updated_at)
+             VALUES ($1, $2, NOW())
+             ON CONFLICT (table_id)
+             DO UPDATE SET
+                balance = rake_accounts.balance + EXCLUDED.balance,
+                updated_at = NOW()
+             RETURNING balance",
+        )
+        .bind(table_id)
+        .bind(amount)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let new_rake_balance: i64 = rake_row.get("balance");
+
+        // Create credit entry for the rake account
+        self.create_entry(
+            &mut tx,
+            -table_id,
+            Some(table_id),
+            amount,
+            new_rake_balance,
+            EntryDirection::Credit,
+            EntryType::Rake,
+            idempotency_key,
+            Some(format!("Rake from table {table_id}")),
+        )
+        .await?;
+
+        // Commit transaction
+        tx.commit().await?;
+
+        Ok(new_rake_balance)
+    }
+
+    /// Pay a knockout bounty to a user's wallet
+    ///
+    /// The credit is made in the caller's transaction, so it only lands if
+    /// the bounty is also taken off the eliminated player.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - Transaction to credit the wallet in
+    /// * `user_id` - User ID
+    /// * `amount` - Amount of the bounty
+    /// * `idempotency_key` - Unique key to prevent duplicate transactions
+    /// * `description` - Description of the ledger entry
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<i64>` - New wallet balance or error
+    pub async fn pay_bounty(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: i64,
+        amount: i64,
+        idempotency_key: String,
+        description: String,
+    ) -> WalletResult<i64> {
+        if amount <= 0 {
+            return Err(WalletError::InvalidAmount(amount));
+        }
+
+        // Check for duplicate transaction
+        let existing = sqlx::query("SELECT id FROM wallet_entries WHERE idempotency_key = $1")
+            .bind(&idempotency_key)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        if existing.is_some() {
+            return Err(WalletError::DuplicateTransaction(idempotency_key));
+        }
+
+        // Get current wallet balance (with row lock)
+        let current_balance = self.lock_unfrozen_wallet(tx, user_id).await?;
+
+        // Credit wallet with the bounty (with overflow protection)
+        let new_balance = current_balance
+            .checked_add(amount)
+            .ok_or(WalletError::BalanceOverflow)?;
+        sqlx::query("UPDATE wallets SET balance = $1, updated_at = NOW() WHERE user_id = $2")
+            .bind(new_balance)
+            .bind(user_id)
+            .execute(&mut **tx)
+            .await?;
+
+        // Create credit entry
+        self.create_entry(
+            tx,
+            user_id,
+            None,
+            amount,
+            new_balance,
+            EntryDirection::Credit,
+            EntryType::Bounty,
+            idempotency_key,
+            Some(description),
+        )
+        .await?;
+
+        Ok(new_balance)
+    }
+
+    /// Gift chips from one user's wallet to another's
+    ///
+    /// Both wallets are debited and credited in a single transaction, so the
+    /// transfer either fully applies or not at all.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_user_id` - User sending the chips
+    /// * `to_user_id` - User receiving the chips
+    /// * `amount` - Amount to transfer
+    /// * `idempotency_key` - Unique key to prevent duplicate transactions
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<i64>` - Sender's new wallet balance or error
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::SelfTransfer` - Sender and recipient are the same user
+    /// * `WalletError::Frozen` - Either wallet is frozen
+    /// * `WalletError::InsufficientBalance` - Sender doesn't have enough chips
+    /// * `WalletError::BalanceOverflow` - Recipient's balance would overflow
+    /// * `WalletError::DuplicateTransaction` - Idempotency key already used
+    pub async fn transfer_between_users(
+        &self,
+        from_user_id: i64,
+        to_user_id: i64,
+        amount: i64,
+        idempotency_key: String,
+    ) -> WalletResult<i64> {
+        if amount <= 0 {
+            return Err(WalletError::InvalidAmount(amount));
+        }
+        if from_user_id == to_user_id {
+            return Err(WalletError::SelfTransfer);
+        }
+
+        // Both sides of the gift get their own ledger entry
+        let debit_key = format!("{idempotency_key}:debit");
+        let credit_key = format!("{idempotency_key}:credit");
+
+        // Start transaction
+        let mut tx = self.pool.begin().await?;
+
+        // Check for duplicate transaction
+        let existing = sqlx::query("SELECT id FROM wallet_entries WHERE idempotency_key = $1")
+            .bind(&debit_key)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if existing.is_some() {
+            return Err(WalletError::DuplicateTransaction(idempotency_key));
+        }
+
+        // Lock both wallets in user ID order so concurrent gifts can't deadlock
+        let wallet_rows = sqlx::query(
+            "SELECT user_id, balance, frozen FROM wallets
+             WHERE user_id = ANY($1)
+             ORDER BY user_id
+             FOR UPDATE",
+        )
+        .bind(vec![from_user_id, to_user_id])
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let find_wallet = |user_id: i64| {
+            wallet_rows
+                .iter()
+                .find(|row| row.get::<i64, _>("user_id") == user_id)
+                .ok_or(WalletError::WalletNotFound(user_id))
+        };
+        let from_wallet = find_wallet(from_user_id)?;
+        let to_wallet = find_wallet(to_user_id)?;
+
+        for (user_id, wallet) in [(from_user_id, from_wallet), (to_user_id, to_wallet)] {
+            if wallet.get::<bool, _>("frozen") {
+                return Err(WalletError::Frozen(user_id));
+            }
+        }
+
+        // Debit sender (with underflow protection) and credit recipient (with overflow protection)
+        let from_balance: i64 = from_wallet.get("balance");
+        if from_balance < amount {
+            return Err(WalletError::InsufficientBalance {
+                user_id: from_user_id,
+                available: from_balance,
+                required: amount,
+            });
+        }
+        let new_from_balance = from_balance
+            .checked_sub(amount)
+            .ok_or(WalletError::BalanceOverflow)?;
+        let new_to_balance = to_wallet
+            .get::<i64, _>("balance")
+            .checked_add(amount)
+            .ok_or(WalletError::BalanceOverflow)?;
+
+        for (user_id, balance) in [
+            (from_user_id, new_from_balance),
+            (to_user_id, new_to_balance),
+        ] {
+            sqlx::query("UPDATE wallets SET balance = $1, updated_at = NOW() WHERE user_id = $2")
+                .bind(balance)
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        // Create debit and credit entries
+        self.create_entry(
+            &mut tx,
+            from_user_id,
+            None,
+            -amount,
+            new_from_balance,
+            EntryDirection::Debit,
+            EntryType::Gift,
+            debit_key,
+            Some(format!("Gift to user {to_user_id}")),
+        )
+        .await?;
+
+        self.create_entry(
+            &mut tx,
+            to_user_id,
+            None,
+            amount,
+            new_to_balance,
+            EntryDirection::Credit,
+            EntryType::Gift,
+            credit_key,
+            Some(format!("Gift from user {from_user_id}")),
+        )
+        .await?;
+
+        // Commit transaction
+        tx.commit().await?;
+
+        Ok(new_from_balance)
+    }
+
+    /// Freeze a user's wallet for suspected fraud
+    ///
+    /// While frozen, the wallet can't buy in to tables, claim the faucet, or
+    /// send or receive gifts. Cashing out of a table is still allowed so the
+    /// player can leave the tables they're at. The audit log entry is written
+    /// in the same transaction, so a freeze is never left unrecorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User whose wallet to freeze
+    /// * `reason` - Why the wallet is being frozen
+    /// * `actor_id` - Admin freezing the wallet, or `None` for the system
+    /// * `ip_address` - Where the request came from, if known
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<()>` - Success or error
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::WalletNotFound` - User has no wallet
+    pub async fn freeze(
+        &self,
+        user_id: i64,
+        reason: &str,
+        actor_id: Option<i64>,
+        ip_address: Option<String>,
+    ) -> WalletResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            "UPDATE wallets
+             SET frozen = TRUE, frozen_reason = $1, frozen_at = NOW(), updated_at = NOW()
+             WHERE user_id = $2",
+        )
+        .bind(reason)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(WalletError::WalletNotFound(user_id));
+        }
+
+        let entry = AuditEntry::new(actor_id, AuditAction::FreezeWallet)
+            .with_target_user(user_id)
+            .with_ip(ip_address)
+            .with_details(reason);
+        write_batch(&mut *tx, std::slice::from_ref(&entry)).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Unfreeze a user's wallet
+    ///
+    /// Like [`freeze`](Self::freeze), the audit log entry is written in the
+    /// same transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User whose wallet to unfreeze
+    /// * `actor_id` - Admin unfreezing the wallet, or `None` for the system
+    /// * `ip_address` - Where the request came from, if known
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<()>` - Success or error
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::WalletNotFound` - User has no wallet
+    pub async fn unfreeze(
+        &self,
+        user_id: i64,
+        actor_id: Option<i64>,
+        ip_address: Option<String>,
+    ) -> WalletResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            "UPDATE wallets
+             SET frozen = FALSE, frozen_reason = NULL, frozen_at = NULL, updated_at = NOW()
+             WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(WalletError::WalletNotFound(user_id));
+        }
+
+        let entry = AuditEntry::new(actor_id, AuditAction::UnfreezeWallet)
+            .with_target_user(user_id)
+            .with_ip(ip_address);
+        write_batch(&mut *tx, std::slice::from_ref(&entry)).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
     /// Claim daily faucet
     ///
     /// # Arguments
@@ -359,6 +854,7 @@ impl WalletManager {
     ///
     /// # Errors
     ///
+    /// * `WalletError::Frozen` - Wallet is frozen
     /// * `WalletError::FaucetNotAvailable` - Cooldown period not elapsed
     pub async fn claim_faucet(&self, user_id: i64) -> WalletResult<FaucetClaim> {
         // Start transaction
@@ -387,13 +883,7 @@ impl WalletManager {
         }
 
         // Get current wallet balance (with row lock)
-        let wallet_row = sqlx::query("SELECT balance FROM wallets WHERE user_id = $1 FOR UPDATE")
-            .bind(user_id)
-            .fetch_optional(&mut *tx)
-            .await?
-            .ok_or(WalletError::WalletNotFound(user_id))?;
-
-        let current_balance: i64 = wallet_row.get("balance");
+        let current_balance = self.lock_unfrozen_wallet(&mut tx, user_id).await?;
 
         // Credit wallet with faucet amount (with overflow protection)
         let new_balance = current_balance
@@ -455,6 +945,64 @@ impl WalletManager {
         })
     }
 
+    /// Get faucet availability without attempting a claim
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User ID
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<FaucetStatus>` - Whether the faucet can be claimed and,
+    ///   if not, the same next claim time a claim would report
+    pub async fn faucet_status(&self, user_id: i64) -> WalletResult<FaucetStatus> {
+        let last_claim = sqlx::query(
+            "SELECT next_claim_at FROM faucet_claims
+             WHERE user_id = $1
+             ORDER BY claimed_at DESC
+             LIMIT 1",
+        )
+        .bind(user_id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        let next_claim = last_claim
+            .map(|row| {
+                row.get::<chrono::NaiveDateTime, _>("next_claim_at")
+                    .and_utc()
+            })
+            .filter(|&next_claim_at| Utc::now() < next_claim_at);
+
+        Ok(FaucetStatus {
+            available: next_claim.is_none(),
+            next_claim,
+        })
+    }
+
+    /// Lock a wallet row for the rest of the transaction and return its balance
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::WalletNotFound` - User has no wallet
+    /// * `WalletError::Frozen` - Wallet is frozen
+    async fn lock_unfrozen_wallet(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: i64,
+    ) -> WalletResult<i64> {
+        let wallet_row =
+            sqlx::query("SELECT balance, frozen FROM wallets WHERE user_id = $1 FOR UPDATE")
+                .bind(user_id)
+                .fetch_optional(&mut **tx)
+                .await?
+                .ok_or(WalletError::WalletNotFound(user_id))?;
+
+        if wallet_row.get::<bool, _>("frozen") {
+            return Err(WalletError::Frozen(user_id));
+        }
+        Ok(wallet_row.get("balance"))
+    }
+
     /// Create a wallet entry (double-entry ledger)
     #[allow(clippy::too_many_arguments)]
     async fn create_entry(
@@ -515,34 +1063,311 @@ impl WalletManager {
         .fetch_all(self.pool.as_ref())
         .await?;
 
-        let entries = rows
-            .into_iter()
-            .map(|row| WalletEntry {
-                id: row.get("id"),
-                user_id: row.get("user_id"),
-                table_id: row.get("table_id"),
-                amount: row.get("amount"),
-                balance_after: row.get("balance_after"),
-                direction: match row.get::<String, _>("direction").as_str() {
-                    "debit" => EntryDirection::Debit,
-                    "credit" => EntryDirection::Credit,
-                    _ => EntryDirection::Credit,
-                },
-                entry_type: match row.get::<String, _>("entry_type").as_str() {
-                    "buy_in" => EntryType::BuyIn,
-                    "cash_out" => EntryType::CashOut,
-                    "rake" => EntryType::Rake,
-                    "bonus" => EntryType::Bonus,
-                    "admin_adjust" => EntryType::AdminAdjust,
-                    "transfer" => EntryType::Transfer,
-                    _ => EntryType::Transfer,
+        Ok(rows.iter().map(entry_from_row).collect())
+    }
+
+    /// Get wallet entries for a user matching a filter
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User ID
+    /// * `filter` - Entry type, direction, date range, and page to return
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<Vec<WalletEntry>>` - Matching wallet entries, newest first
+    pub async fn get_entries_filtered(
+        &self,
+        user_id: i64,
+        filter: &EntryFilter,
+    ) -> WalletResult<Vec<WalletEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, user_id, table_id, amount, balance_after, direction, entry_type, idempotency_key, description, created_at
+            FROM wallet_entries
+            WHERE user_id = $1
+              AND ($2::TEXT IS NULL OR entry_type = $2)
+              AND ($3::TEXT IS NULL OR direction = $3)
+              AND ($4::TIMESTAMP IS NULL OR created_at >= $4)
+              AND ($5::TIMESTAMP IS NULL OR created_at <= $5)
+            ORDER BY created_at DESC, id DESC
+            LIMIT $6 OFFSET $7
+            "#,
+        )
+        .bind(user_id)
+        .bind(filter.entry_type.map(|entry_type| entry_type.to_string()))
+        .bind(filter.direction.map(|direction| direction.to_string()))
+        .bind(filter.from.map(|from| from.naive_utc()))
+        .bind(filter.to.map(|to| to.naive_utc()))
+        .bind(filter.limit)
+        .bind(filter.offset)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows.iter().map(entry_from_row).collect())
+    }
+
+    /// Export wallet entries for a user matching a filter as CSV
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User ID
+    /// * `filter` - Entry type, direction, date range, and page to export
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<String>` - CSV with a header row and one row per entry
+    pub async fn export_entries_csv(
+        &self,
+        user_id: i64,
+        filter: &EntryFilter,
+    ) -> WalletResult<String> {
+        let entries = self.get_entries_filtered(user_id, filter).await?;
+        Ok(entries_to_csv(&entries))
+    }
+
+    /// Summarize a user's wallet activity over a period ending now
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User ID
+    /// * `period` - Day, week, or month to summarize
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<WalletSummary>` - Totals per bucket and the balance change
+    pub async fn summarize(
+        &self,
+        user_id: i64,
+        period: SummaryPeriod,
+    ) -> WalletResult<WalletSummary> {
+        let to = Utc::now();
+        let from = to - period.duration();
+
+        // Aggregate in the database; the balance change comes from the
+        // first entry's opening balance and the last entry's closing balance
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COALESCE(SUM(-amount) FILTER (WHERE entry_type = 'buy_in'), 0)::BIGINT AS buy_ins,
+                COALESCE(SUM(amount) FILTER (WHERE entry_type = 'cash_out'), 0)::BIGINT AS cash_outs,
+                COALESCE(SUM(-amount) FILTER (WHERE entry_type = 'rake'), 0)::BIGINT AS rake_paid,
+                COALESCE(SUM(amount) FILTER (WHERE entry_type = 'bonus'), 0)::BIGINT AS faucet_claimed,
+                COALESCE(SUM(amount) FILTER (
+                    WHERE entry_type NOT IN ('buy_in', 'cash_out', 'rake', 'bonus')
+                ), 0)::BIGINT AS other,
+                (ARRAY_AGG(balance_after - amount ORDER BY created_at, id))[1] AS opening_balance,
+                (ARRAY_AGG(balance_after ORDER BY created_at DESC, id DESC))[1] AS closing_balance
+            FROM wallet_entries
+            WHERE user_id = $1 AND created_at >= $2 AND created_at <= $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(from.naive_utc())
+        .bind(to.naive_utc())
+        .fetch_one(self.pool.as_ref())
+        .await?;
+
+        let buy_ins: i64 = row.get("buy_ins");
+        let cash_outs: i64 = row.get("cash_outs");
+        let opening_balance: Option<i64> = row.get("opening_balance");
+        let closing_balance: Option<i64> = row.get("closing_balance");
+
+        Ok(WalletSummary {
+            user_id,
+            period,
+            from,
+            to,
+            net_win_loss: cash_outs - buy_ins,
+            buy_ins,
+            cash_outs,
+            rake_paid: row.get("rake_paid"),
+            faucet_claimed: row.get("faucet_claimed"),
+            other: row.get("other"),
+            balance_change: closing_balance
+                .zip(opening_balance)
+                .map_or(0, |(closing, opening)| closing - opening),
+        })
+    }
+
+    /// Compare every table escrow against the chips seated at its table
+    ///
+    /// `table_chips` holds one entry per table with a running actor: the
+    /// chips seated there, or `None` while a hand is in progress and the
+    /// count isn't settled. An escrow with a balance but no running table
+    /// is orphaned. Nothing is moved unless `return_orphaned` is set, in
+    /// which case orphaned chips are refunded to the players who bought in,
+    /// but only when their net buy-ins account for the whole balance.
+    /// Refunds are recorded as cash-outs, so running this again finds
+    /// nothing left to return.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_chips` - Seated chips for each running table
+    /// * `return_orphaned` - Refund orphaned escrows to their players
+    ///
+    /// # Returns
+    ///
+    /// * `WalletResult<ReconcileReport>` - Escrows checked and discrepancies found
+    pub async fn reconcile_escrow(
+        &self,
+        table_chips: &HashMap<TableId, Option<i64>>,
+        return_orphaned: bool,
+    ) -> WalletResult<ReconcileReport> {
+        let escrows = sqlx::query(
+            r#"
+            SELECT table_id, balance, updated_at
+            FROM table_escrows
+            ORDER BY table_id
+            "#,
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let mut report = ReconcileReport {
+            escrows_checked: escrows.len(),
+            discrepancies: Vec::new(),
+        };
+
+        for row in escrows {
+            let table_id: TableId = row.get("table_id");
+            let escrow_balance: i64 = row.get("balance");
+            let table = table_chips.get(&table_id).copied();
+
+            let mut discrepancy = match table {
+                // Mid-hand; chips are in the pot rather than the stacks
+                Some(None) => continue,
+                Some(Some(chips)) if chips == escrow_balance => continue,
+                None if escrow_balance == 0 => continue,
+                _ => EscrowDiscrepancy {
+                    table_id,
+                    escrow_balance,
+                    table_chips: table.flatten(),
+                    chips_returned: 0,
                 },
-                idempotency_key: row.get("idempotency_key"),
-                description: row.get("description"),
-                created_at: row.get::<chrono::NaiveDateTime, _>("created_at").and_utc(),
-            })
+            };
+
+            if return_orphaned && table.is_none() {
+                let updated_at = row.get::<chrono::NaiveDateTime, _>("updated_at");
+                discrepancy.chips_returned = self
+                    .return_orphaned_escrow(table_id, escrow_balance, updated_at.and_utc())
+                    .await?;
+            }
+
+            report.discrepancies.push(discrepancy);
+        }
+
+        Ok(report)
+    }
+
+    /// Refund an orphaned escrow to the players with outstanding buy-ins
+    ///
+    /// Returns the chips refunded, which is zero when the buy-ins don't
+    /// add up to the escrow balance.
+    async fn return_orphaned_escrow(
+        &self,
+        table_id: TableId,
+        escrow_balance: i64,
+        updated_at: DateTime<Utc>,
+    ) -> WalletResult<i64> {
+        let rows = sqlx::query(
+            r#"
+            SELECT user_id, (-SUM(amount))::BIGINT AS net_buy_in
+            FROM wallet_entries
+            WHERE table_id = $1 AND entry_type IN ('buy_in', 'cash_out')
+            GROUP BY user_id
+            HAVING SUM(amount) < 0
+            ORDER BY user_id
+            "#,
+        )
+        .bind(table_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let refunds: Vec<(i64, i64)> = rows
+            .iter()
+            .map(|row| (row.get("user_id"), row.get("net_buy_in")))
             .collect();
+        if refunds.iter().map(|(_, amount)| amount).sum::<i64>() != escrow_balance {
+            return Ok(0);
+        }
+
+        // Keyed on the escrow's last update so two overlapping runs that
+        // read the same balance can't both refund a player
+        let mut returned = 0;
+        for (user_id, amount) in refunds {
+            let idempotency_key = format!(
+                "reconcile_{table_id}_{user_id}_{}",
+                updated_at.timestamp_micros()
+            );
+            self.transfer_from_escrow(user_id, table_id, amount, idempotency_key)
+                .await?;
+            returned += amount;
+        }
+
+        Ok(returned)
+    }
+}
+
+/// Convert a `wallet_entries` row into a wallet entry
+fn entry_from_row(row: &PgRow) -> WalletEntry {
+    WalletEntry {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        table_id: row.get("table_id"),
+        amount: row.get("amount"),
+        balance_after: row.get("balance_after"),
+        direction: match row.get::<String, _>("direction").as_str() {
+            "debit" => EntryDirection::Debit,
+            "credit" => EntryDirection::Credit,
+            _ => EntryDirection::Credit,
+        },
+        entry_type: match row.get::<String, _>("entry_type").as_str() {
+            "buy_in" => EntryType::BuyIn,
+            "cash_out" => EntryType::CashOut,
+            "rake" => EntryType::Rake,
+            "bonus" => EntryType::Bonus,
+            "admin_adjust" => EntryType::AdminAdjust,
+            "transfer" => EntryType::Transfer,
+            "bounty" => EntryType::Bounty,
+            "gift" => EntryType::Gift,
+            _ => EntryType::Transfer,
+        },
+        idempotency_key: row.get("idempotency_key"),
+        description: row.get("description"),
+        created_at: row.get::<chrono::NaiveDateTime, _>("created_at").and_utc(),
+    }
+}
+
+/// Header row of exported wallet entries
+const ENTRIES_CSV_HEADER: &str = "timestamp,type,direction,amount,balance_after,reference";
+
+/// Format wallet entries as CSV
+///
+/// Timestamps are UTC in `YYYY-MM-DD HH:MM:SS` so spreadsheets parse them
+/// as dates. The reference is the entry's description.
+fn entries_to_csv(entries: &[WalletEntry]) -> String {
+    let mut csv = String::from(ENTRIES_CSV_HEADER);
+    csv.push('\n');
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            entry.created_at.format("%Y-%m-%d %H:%M:%S"),
+            entry.entry_type,
+            entry.direction,
+            entry.amount,
+            entry.balance_after,
+            escape_csv_field(entry.description.as_deref().unwrap_or("")),
+        ));
+    }
+    csv
+}
 
-        Ok(entries)
+/// Quote a CSV field if it contains a delimiter, quote, or line break
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
     }
 }
@@ -6,6 +6,10 @@
 //! - Idempotency keys to prevent duplicate transactions
 //! - ACID-compliant atomic transfers
 //! - Daily faucet for demo/testing
+//! - Chip gifting between players
+//! - Filtered transaction history with CSV export
+//! - Daily, weekly, and monthly statements
+//! - Escrow reconciliation against seated chips
 //!
 //! ## Example
 //!
@@ -40,5 +44,6 @@ pub mod models;
 pub use errors::{WalletError, WalletResult};
 pub use manager::WalletManager;
 pub use models::{
-    EntryDirection, EntryType, FaucetClaim, TableEscrow, TableId, Wallet, WalletEntry,
+    EntryDirection, EntryFilter, EntryType, EscrowDiscrepancy, FaucetClaim, FaucetStatus,
+    ReconcileReport, SummaryPeriod, TableEscrow, TableId, Wallet, WalletEntry, WalletSummary,
 };
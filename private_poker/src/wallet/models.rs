@@ -12,6 +12,10 @@ pub struct Wallet {
     pub user_id: i64,
     pub balance: i64,
     pub currency: String,
+    /// Whether the wallet is frozen for suspected fraud
+    pub frozen: bool,
+    /// Why the wallet was frozen, if it is
+    pub frozen_reason: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -67,6 +71,8 @@ pub enum EntryType {
     Bonus,
     AdminAdjust,
     Transfer,
+    Bounty,
+    Gift,
 }
 
 impl std::fmt::Display for EntryType {
@@ -78,10 +84,126 @@ impl std::fmt::Display for EntryType {
             EntryType::Bonus => write!(f, "bonus"),
             EntryType::AdminAdjust => write!(f, "admin_adjust"),
             EntryType::Transfer => write!(f, "transfer"),
+            EntryType::Bounty => write!(f, "bounty"),
+            EntryType::Gift => write!(f, "gift"),
         }
     }
 }
 
+/// Filter for a user's wallet entries
+///
+/// Dates are compared in UTC, the timezone entries are stored in, and both
+/// ends of the range are inclusive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryFilter {
+    /// Only entries of this type
+    pub entry_type: Option<EntryType>,
+    /// Only entries in this direction
+    pub direction: Option<EntryDirection>,
+    /// Only entries created at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only entries created at or before this time
+    pub to: Option<DateTime<Utc>>,
+    /// Maximum number of entries to return
+    pub limit: i64,
+    /// Number of entries to skip
+    pub offset: i64,
+}
+
+impl Default for EntryFilter {
+    fn default() -> Self {
+        Self {
+            entry_type: None,
+            direction: None,
+            from: None,
+            to: None,
+            limit: 100,
+            offset: 0,
+        }
+    }
+}
+
+/// Window a wallet summary covers, ending now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl SummaryPeriod {
+    /// Length of the window (a month is 30 days)
+    pub fn duration(&self) -> chrono::Duration {
+        match self {
+            SummaryPeriod::Day => chrono::Duration::days(1),
+            SummaryPeriod::Week => chrono::Duration::weeks(1),
+            SummaryPeriod::Month => chrono::Duration::days(30),
+        }
+    }
+}
+
+/// Wallet statement for a period, aggregated from the ledger
+///
+/// Every entry in the window falls into exactly one bucket, so
+/// `net_win_loss - rake_paid + faucet_claimed + other` equals
+/// `balance_change`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletSummary {
+    pub user_id: i64,
+    pub period: SummaryPeriod,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// Chips cashed out of tables minus chips bought in
+    pub net_win_loss: i64,
+    /// Chips bought in to tables
+    pub buy_ins: i64,
+    /// Chips cashed out of tables
+    pub cash_outs: i64,
+    /// Rake charged to the wallet
+    pub rake_paid: i64,
+    /// Chips claimed from the faucet
+    pub faucet_claimed: i64,
+    /// Net of all other entries (gifts, bounties, adjustments)
+    pub other: i64,
+    /// Change in wallet balance over the window
+    pub balance_change: i64,
+}
+
+impl WalletSummary {
+    /// Whether the buckets add up to the balance change
+    pub fn is_consistent(&self) -> bool {
+        self.net_win_loss - self.rake_paid + self.faucet_claimed + self.other == self.balance_change
+    }
+}
+
+/// A table escrow whose balance does not match the chips seated at its table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscrowDiscrepancy {
+    pub table_id: TableId,
+    /// Balance held in escrow
+    pub escrow_balance: i64,
+    /// Chips seated at the table, or `None` if no actor is running it
+    pub table_chips: Option<i64>,
+    /// Chips refunded to players by this run
+    pub chips_returned: i64,
+}
+
+/// Result of comparing every table escrow against its table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReconcileReport {
+    /// Number of escrows compared
+    pub escrows_checked: usize,
+    pub discrepancies: Vec<EscrowDiscrepancy>,
+}
+
+impl ReconcileReport {
+    /// Whether every escrow matched its table
+    pub fn is_clean(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
 /// Faucet claim model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FaucetClaim {
@@ -92,6 +214,15 @@ pub struct FaucetClaim {
     pub next_claim_at: DateTime<Utc>,
 }
 
+/// Faucet availability, without attempting a claim
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaucetStatus {
+    /// Whether a claim would succeed now
+    pub available: bool,
+    /// When the next claim is allowed, if the faucet is cooling down
+    pub next_claim: Option<DateTime<Utc>>,
+}
+
 /// Transfer request (chips from wallet to escrow or vice versa)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransferRequest {
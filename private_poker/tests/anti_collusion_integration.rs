@@ -7,7 +7,11 @@
 
 use private_poker::auth::{AuthManager, RegisterRequest};
 use private_poker::db::{Database, DatabaseConfig};
+use private_poker::entities::{
+    ActionRecord, Blinds, ForcedBetRecord, HandHistory, HistoryAction, PotAward, SeatRecord, Street,
+};
 use private_poker::security::AntiCollusionDetector;
+use private_poker::table::messages::TableHandHistory;
 use sqlx::PgPool;
 use std::sync::Arc;
 
@@ -68,6 +72,109 @@ async fn cleanup_flags(pool: &PgPool, user_id: i64) {
         .await;
 }
 
+/// Helper to register a user and return their ID
+async fn register_user(auth_mgr: &AuthManager, pool: &PgPool, username: &str) -> i64 {
+    cleanup_user(pool, username).await;
+    let user = auth_mgr
+        .register(RegisterRequest {
+            username: username.to_string(),
+            password: "SecurePass123!".to_string(),
+            display_name: username.to_string(),
+            email: None,
+        })
+        .await
+        .expect("Registration should succeed");
+    cleanup_flags(pool, user.id).await;
+    user.id
+}
+
+/// A preflop hand where `raiser` raises and `folder` folds to it, with the
+/// folder's cards known only if they showed them
+fn raise_and_fold(
+    players: [&str; 3],
+    raiser: &str,
+    folder: &str,
+    shown: Option<[&str; 2]>,
+) -> HandHistory {
+    let mut hand = HandHistory::new(
+        Blinds {
+            small: 5,
+            big: 10,
+            ante: 0,
+        },
+        players
+            .iter()
+            .enumerate()
+            .map(|(seat, username)| SeatRecord {
+                seat,
+                username: username.to_string().into(),
+                starting_stack: 1000,
+                hole_cards: (*username == folder)
+                    .then(|| shown.map(|cards| cards.map(String::from).to_vec()))
+                    .flatten(),
+            })
+            .collect(),
+    );
+    // Whoever isn't in the pot posts the big blind
+    let blind = players
+        .iter()
+        .position(|p| *p != raiser && *p != folder)
+        .unwrap();
+    hand.forced_bets.push(ForcedBetRecord {
+        seat: blind,
+        username: players[blind].to_string().into(),
+        amount: 10,
+    });
+    for (username, action, amount) in [
+        (raiser, HistoryAction::Raise, 30),
+        (folder, HistoryAction::Fold, 0),
+    ] {
+        hand.actions.push(ActionRecord {
+            street: Street::Preflop,
+            seat: players.iter().position(|p| *p == username).unwrap(),
+            username: username.to_string().into(),
+            action,
+            amount,
+        });
+    }
+    hand.pot_awards.push(PotAward {
+        seat: players.iter().position(|p| *p == raiser).unwrap(),
+        username: raiser.to_string().into(),
+        amount: 40,
+    });
+    hand
+}
+
+/// Helper to store hand histories for a table, replacing any it had
+async fn store_hand_histories(pool: &PgPool, table_id: i64, hands: Vec<HandHistory>) {
+    cleanup_hand_histories(pool, table_id).await;
+    for (hand_number, hand) in (1..).zip(hands) {
+        let history = TableHandHistory {
+            table_id,
+            hand_number,
+            completed_at: chrono::Utc::now(),
+            hand,
+        };
+        sqlx::query(
+            "INSERT INTO table_hand_histories (table_id, hand_number, history) VALUES ($1, $2, $3)",
+        )
+        .bind(table_id)
+        .bind(i64::from(hand_number))
+        .bind(serde_json::to_value(&history).unwrap())
+        .execute(pool)
+        .await
+        .expect("Should store hand history");
+    }
+}
+
+/// Helper to cleanup hand histories for a table
+async fn cleanup_hand_histories(pool: &PgPool, table_id: i64) {
+    let _ = sqlx::query("DELETE FROM table_hand_histories WHERE table_id = $1")
+        .bind(table_id)
+        .execute(pool)
+        .await;
+}
+
 #[tokio::test]
 async fn test_same_ip_detection() {
     // Test: Two users with same IP join a table -> flag created
@@ -539,6 +646,126 @@ async fn test_coordinated_folding_pattern() {
     cleanup_user(&pool, &username_beneficiary).await;
 }
 
+#[tokio::test]
+async fn test_folding_strong_hands_to_one_player_is_flagged() {
+    // Test: Suspect folds shown pocket pairs to one player's raises but
+    // only junk to everyone else's -> flagged
+    let (detector, auth_mgr, pool) = setup_managers().await;
+    let suspect_name = unique_username("fsuspect");
+    let target_name = unique_username("ftarget");
+    let other_name = unique_username("fother");
+    let table_id = 1012;
+
+    let suspect = register_user(&auth_mgr, &pool, &suspect_name).await;
+    let target = register_user(&auth_mgr, &pool, &target_name).await;
+    register_user(&auth_mgr, &pool, &other_name).await;
+
+    let players = [suspect_name.as_str(), &target_name, &other_name];
+    let mut hands = Vec::new();
+    for pair in [
+        ["Ah", "Ad"],
+        ["Kh", "Kd"],
+        ["Qh", "Qd"],
+        ["Jh", "Jd"],
+        ["Th", "Td"],
+        ["9h", "9d"],
+    ] {
+        hands.push(raise_and_fold(
+            players,
+            &target_name,
+            &suspect_name,
+            Some(pair),
+        ));
+        hands.push(raise_and_fold(players, &other_name, &suspect_name, None));
+    }
+    store_hand_histories(&pool, table_id, hands).await;
+
+    let pattern = detector
+        .check_coordinated_folding(table_id, suspect, target)
+        .await
+        .expect("Folding analysis should complete");
+
+    assert_eq!(pattern.folds_to_target, 6);
+    assert_eq!(pattern.unusual_folds_to_target, 6);
+    assert_eq!(pattern.folds_to_others, 6);
+    assert_eq!(pattern.unusual_folds_to_others, 0);
+    assert!(pattern.is_suspicious());
+
+    let fold_flags: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM collusion_flags
+         WHERE user_id = $1 AND table_id = $2 AND flag_type = 'coordinated_folding'",
+    )
+    .bind(suspect)
+    .bind(table_id)
+    .fetch_one(pool.as_ref())
+    .await
+    .expect("Should query folding flags");
+    assert_eq!(fold_flags, 1, "Pattern should be flagged for review");
+
+    cleanup_hand_histories(&pool, table_id).await;
+    cleanup_flags(&pool, suspect).await;
+    cleanup_user(&pool, &suspect_name).await;
+    cleanup_user(&pool, &target_name).await;
+    cleanup_user(&pool, &other_name).await;
+}
+
+#[tokio::test]
+async fn test_tight_folding_is_not_flagged() {
+    // Test: Suspect folds unshown hands to raises from everyone, including
+    // hands the target wins -> not flagged
+    let (detector, auth_mgr, pool) = setup_managers().await;
+    let suspect_name = unique_username("tsuspect");
+    let target_name = unique_username("ttarget");
+    let other_name = unique_username("tother");
+    let table_id = 1013;
+
+    let suspect = register_user(&auth_mgr, &pool, &suspect_name).await;
+    let target = register_user(&auth_mgr, &pool, &target_name).await;
+    register_user(&auth_mgr, &pool, &other_name).await;
+
+    let players = [suspect_name.as_str(), &target_name, &other_name];
+    let mut hands = Vec::new();
+    for _ in 0..8 {
+        hands.push(raise_and_fold(players, &target_name, &suspect_name, None));
+        hands.push(raise_and_fold(players, &other_name, &suspect_name, None));
+    }
+    // The target also wins some pots the suspect folded to someone else
+    for _ in 0..4 {
+        let mut hand = raise_and_fold(players, &other_name, &suspect_name, None);
+        hand.pot_awards[0].seat = 1;
+        hand.pot_awards[0].username = target_name.clone().into();
+        hands.push(hand);
+    }
+    store_hand_histories(&pool, table_id, hands).await;
+
+    let pattern = detector
+        .check_coordinated_folding(table_id, suspect, target)
+        .await
+        .expect("Folding analysis should complete");
+
+    assert_eq!(pattern.hands_together, 20);
+    assert_eq!(pattern.folds_to_target, 8);
+    assert_eq!(pattern.folds_in_target_wins, 4);
+    assert_eq!(pattern.unusual_folds_to_target, 0);
+    assert!(!pattern.is_suspicious());
+
+    let fold_flags: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM collusion_flags
+         WHERE user_id = $1 AND table_id = $2",
+    )
+    .bind(suspect)
+    .bind(table_id)
+    .fetch_one(pool.as_ref())
+    .await
+    .expect("Should query flags");
+    assert_eq!(fold_flags, 0, "Tight play shouldn't be flagged");
+
+    cleanup_hand_histories(&pool, table_id).await;
+    cleanup_user(&pool, &suspect_name).await;
+    cleanup_user(&pool, &target_name).await;
+    cleanup_user(&pool, &other_name).await;
+}
+
 #[tokio::test]
 async fn test_ip_change_detection() {
     // Test: User changes IP during session
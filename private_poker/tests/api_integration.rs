@@ -6,11 +6,19 @@
 
 use private_poker::auth::{AuthManager, LoginRequest, RegisterRequest};
 use private_poker::db::{Database, DatabaseConfig};
-use private_poker::table::{TableConfig, TableManager};
+use private_poker::entities::Action;
+use private_poker::net::messages::ServerMessage;
+use private_poker::notes::{MAX_NOTE_LENGTH, NotesError, NotesRepository};
+use private_poker::stats::{StatsCounters, StatsRepository};
+use private_poker::table::{TableActor, TableConfig, TableManager, TableMessage};
+use private_poker::tournament::{
+    BlindLevel, PayoutStructure, TournamentConfig, TournamentError, TournamentManager,
+};
 use private_poker::wallet::WalletManager;
 use serial_test::serial;
 use sqlx::PgPool;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Generate a unique short username (3-20 chars)
 fn unique_username(prefix: &str) -> String {
@@ -56,6 +64,9 @@ async fn setup_auth_manager() -> (Arc<AuthManager>, Arc<PgPool>) {
 async fn cleanup_test_tables(pool: &PgPool) {
     // Delete table escrows first due to foreign key
     let _ = sqlx::query("DELETE FROM table_escrows").execute(pool).await;
+    let _ = sqlx::query("DELETE FROM table_snapshots")
+        .execute(pool)
+        .await;
 
     // Delete all tables
     let _ = sqlx::query("DELETE FROM tables").execute(pool).await;
@@ -226,6 +237,22 @@ async fn test_table_manager_create_and_list() {
     assert!(tables.iter().any(|t| t.id == table_id));
 }
 
+#[tokio::test]
+async fn test_table_manager_rejects_invalid_config() {
+    let pool = setup_test_db().await;
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool, wallet_manager);
+
+    // The error names the field, so it can be shown to whoever made the table
+    let config = TableConfig {
+        small_blind: 10,
+        big_blind: 10,
+        ..Default::default()
+    };
+    let err = table_manager.create_table(config, None).await.unwrap_err();
+    assert!(err.contains("big_blind"), "unexpected error: {err}");
+}
+
 #[tokio::test]
 #[serial]
 async fn test_table_join_and_leave() {
@@ -259,7 +286,7 @@ async fn test_table_join_and_leave() {
 
     // Join table
     let result = table_manager
-        .join_table(table_id, user_id, username, 500, None)
+        .join_table(table_id, user_id, username, 500, None, None)
         .await;
 
     assert!(result.is_ok(), "Join table should succeed");
@@ -270,6 +297,624 @@ async fn test_table_join_and_leave() {
     assert!(result.is_ok(), "Leave table should succeed");
 }
 
+#[tokio::test]
+#[serial]
+async fn test_create_table_rejected_past_limit_until_one_closes() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    cleanup_test_tables(&pool).await;
+    let (user_id, _, _) = create_test_user(&auth_manager, &unique_username("limit")).await;
+
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool.clone(), wallet_manager).with_max_tables(2);
+
+    let config = |name: &str| TableConfig {
+        name: name.to_string(),
+        small_blind: 5,
+        big_blind: 10,
+        ..Default::default()
+    };
+
+    let first = table_manager
+        .create_table(config("Limit Table 1"), Some(user_id))
+        .await
+        .expect("First table should fit");
+    table_manager
+        .create_table(config("Limit Table 2"), Some(user_id))
+        .await
+        .expect("Second table should fit");
+
+    let err = table_manager
+        .create_table(config("Limit Table 3"), Some(user_id))
+        .await
+        .unwrap_err();
+    assert!(
+        err.contains("Table limit reached"),
+        "unexpected error: {err}"
+    );
+
+    // Closing a table frees its slot for the next one
+    table_manager.close_table(first).await.unwrap();
+    table_manager
+        .create_table(config("Limit Table 3"), Some(user_id))
+        .await
+        .expect("Closing a table should free a slot");
+    assert_eq!(table_manager.active_table_count().await, 2);
+
+    // Tables the server creates itself aren't held to the limit
+    table_manager
+        .create_table(config("Server Table"), None)
+        .await
+        .expect("Server-created tables ignore the limit");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_close_table_returns_seated_chips() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    cleanup_test_tables(&pool).await;
+
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool.clone(), wallet_manager.clone());
+
+    let config = TableConfig {
+        name: "Force Closed Table".to_string(),
+        small_blind: 5,
+        big_blind: 10,
+        bots_enabled: false,
+        ..Default::default()
+    };
+    let table_id = table_manager
+        .create_table(config, None)
+        .await
+        .expect("Table creation should succeed");
+    let handle = table_manager.get_table(table_id).await.unwrap();
+
+    // Two players buy in from the waitlist and start a hand
+    let mut players = Vec::new();
+    for prefix in ["closed_a", "closed_b"] {
+        let username = unique_username(prefix);
+        let (user_id, _, _) = create_test_user(&auth_manager, &username).await;
+        wallet_manager
+            .claim_faucet(user_id)
+            .await
+            .expect("Faucet claim should succeed");
+        let balance = wallet_manager.get_wallet(user_id).await.unwrap().balance;
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        handle
+            .send(TableMessage::JoinWaitlist {
+                user_id,
+                username,
+                buy_in_amount: 500,
+                response: tx,
+            })
+            .await
+            .unwrap();
+        assert!(rx.await.unwrap().is_success());
+        players.push((user_id, balance));
+    }
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    handle
+        .send(TableMessage::StartGame {
+            user_id: players[0].0,
+            response: tx,
+        })
+        .await
+        .unwrap();
+    assert!(rx.await.unwrap().is_success());
+
+    // Wait for both buy-ins to reach escrow as the players are seated
+    let mut escrow = 0;
+    for _ in 0..50 {
+        escrow = wallet_manager.get_escrow(table_id).await.unwrap().balance;
+        if escrow == 1000 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(escrow, 1000);
+
+    table_manager
+        .close_table(table_id)
+        .await
+        .expect("Close should succeed");
+
+    assert!(table_manager.get_table(table_id).await.is_none());
+    assert_eq!(
+        wallet_manager.get_escrow(table_id).await.unwrap().balance,
+        0
+    );
+    for (user_id, balance) in players {
+        assert_eq!(
+            wallet_manager.get_wallet(user_id).await.unwrap().balance,
+            balance
+        );
+    }
+
+    // Nobody is left to resume, so the snapshot goes too
+    let snapshots: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM table_snapshots WHERE table_id = $1")
+            .bind(table_id)
+            .fetch_one(pool.as_ref())
+            .await
+            .unwrap();
+    assert_eq!(snapshots, 0);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_auto_top_up_refills_stack_after_hand() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    cleanup_test_tables(&pool).await;
+
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool.clone(), wallet_manager.clone());
+
+    let config = TableConfig {
+        name: "Auto Top-Up Table".to_string(),
+        small_blind: 5,
+        big_blind: 10,
+        bots_enabled: false,
+        ..Default::default()
+    };
+    let table_id = table_manager
+        .create_table(config, None)
+        .await
+        .expect("Table creation should succeed");
+    let handle = table_manager.get_table(table_id).await.unwrap();
+
+    // Two players buy in for 500 and want their stacks refilled to 600
+    // whenever they end a hand below 500
+    let mut players = Vec::new();
+    for prefix in ["topup_a", "topup_b"] {
+        let username = unique_username(prefix);
+        let (user_id, _, _) = create_test_user(&auth_manager, &username).await;
+        wallet_manager
+            .claim_faucet(user_id)
+            .await
+            .expect("Faucet claim should succeed");
+        let balance = wallet_manager.get_wallet(user_id).await.unwrap().balance;
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        handle
+            .send(TableMessage::JoinWaitlist {
+                user_id,
+                username: username.clone(),
+                buy_in_amount: 500,
+                response: tx,
+            })
+            .await
+            .unwrap();
+        assert!(rx.await.unwrap().is_success());
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        handle
+            .send(TableMessage::SetAutoTopUp {
+                user_id,
+                threshold_chips: 500,
+                target_chips: 600,
+                response: tx,
+            })
+            .await
+            .unwrap();
+        assert!(rx.await.unwrap().is_success());
+        players.push((user_id, username, balance));
+    }
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    handle
+        .send(TableMessage::StartGame {
+            user_id: players[0].0,
+            response: tx,
+        })
+        .await
+        .unwrap();
+    assert!(rx.await.unwrap().is_success());
+
+    // Whoever acts first folds, dropping their stack below the threshold
+    let mut folder = None;
+    for _ in 0..100 {
+        for (index, (user_id, _, _)) in players.iter().enumerate() {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            handle
+                .send(TableMessage::GetLegalActions {
+                    user_id: *user_id,
+                    response: tx,
+                })
+                .await
+                .unwrap();
+            if rx.await.unwrap().is_err() {
+                continue;
+            }
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            handle
+                .send(TableMessage::TakeAction {
+                    user_id: *user_id,
+                    action: Action::Fold,
+                    response: tx,
+                })
+                .await
+                .unwrap();
+            assert!(rx.await.unwrap().is_success());
+            folder = Some(index);
+        }
+        if folder.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let folder = folder.expect("One of the players should get to act");
+    let (folder_id, folder_name, folder_balance) = &players[folder];
+    let (winner_id, winner_name, winner_balance) = &players[1 - folder];
+
+    // Once the hand completes, the folder's stack is refilled to the target
+    let mut stacks = None;
+    for _ in 0..100 {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        handle
+            .send(TableMessage::GetGameView {
+                user_id: *folder_id,
+                response: tx,
+            })
+            .await
+            .unwrap();
+        let view = rx.await.unwrap().expect("The folder should get a view");
+        let stack = |name: &str| {
+            view.players
+                .iter()
+                .find(|p| p.user.name.as_str() == name)
+                .map(|p| i64::from(p.user.money))
+                .unwrap()
+        };
+        let (folder_stack, winner_stack) = (stack(folder_name), stack(winner_name));
+        if folder_stack == 600 {
+            stacks = Some((folder_stack, winner_stack));
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let (folder_stack, winner_stack) = stacks.expect("The folder's stack should be topped up");
+    assert_eq!(folder_stack, 600);
+
+    // The folder lost their blind to the winner, whose stack is left alone
+    let lost = winner_stack - 500;
+    assert!(lost > 0);
+    let top_up = 600 - (500 - lost);
+    assert_eq!(
+        wallet_manager.get_wallet(*folder_id).await.unwrap().balance,
+        folder_balance - 500 - top_up
+    );
+    assert_eq!(
+        wallet_manager.get_wallet(*winner_id).await.unwrap().balance,
+        winner_balance - 500
+    );
+    assert_eq!(
+        wallet_manager.get_escrow(table_id).await.unwrap().balance,
+        1000 + top_up
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_retried_join_with_same_key_charges_once() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    cleanup_test_tables(&pool).await;
+    let username = unique_username("retry");
+    let (user_id, _access_token, _refresh_token) = create_test_user(&auth_manager, &username).await;
+
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool.clone(), wallet_manager.clone());
+
+    wallet_manager
+        .claim_faucet(user_id)
+        .await
+        .expect("Faucet claim should succeed");
+    let starting_balance = wallet_manager.get_wallet(user_id).await.unwrap().balance;
+
+    let config = TableConfig {
+        name: "Retried Join Table".to_string(),
+        max_players: 6,
+        small_blind: 5,
+        big_blind: 10,
+        ..Default::default()
+    };
+    let table_id = table_manager
+        .create_table(config, Some(user_id))
+        .await
+        .expect("Table creation should succeed");
+
+    // The client lost the first response and retries with the same key
+    let key = Some("join-retry-key".to_string());
+    for _ in 0..2 {
+        let response = table_manager
+            .join_table(table_id, user_id, username.clone(), 500, None, key.clone())
+            .await
+            .expect("Join should reach the table");
+        assert!(response.is_success(), "unexpected response: {response:?}");
+    }
+
+    let balance = wallet_manager.get_wallet(user_id).await.unwrap().balance;
+    assert_eq!(balance, starting_balance - 500);
+    let escrow = wallet_manager.get_escrow(table_id).await.unwrap();
+    assert_eq!(escrow.balance, 500);
+
+    // Only one seat was taken, so the second leave finds nobody
+    let first = table_manager.leave_table(table_id, user_id).await.unwrap();
+    assert!(first.is_success());
+    let second = table_manager.leave_table(table_id, user_id).await;
+    assert!(!second.is_ok_and(|r| r.is_success()));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_snapshot_restored_only_while_escrow_matches() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    cleanup_test_tables(&pool).await;
+    let username = unique_username("snapshot");
+    let (user_id, _access_token, _refresh_token) = create_test_user(&auth_manager, &username).await;
+
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool.clone(), wallet_manager.clone());
+    wallet_manager
+        .claim_faucet(user_id)
+        .await
+        .expect("Faucet claim should succeed");
+
+    let config = TableConfig {
+        name: "Snapshot Table".to_string(),
+        small_blind: 5,
+        big_blind: 10,
+        ..Default::default()
+    };
+    let table_id = table_manager
+        .create_table(config.clone(), Some(user_id))
+        .await
+        .expect("Table creation should succeed");
+    let response = table_manager
+        .join_table(table_id, user_id, username.clone(), 500, None, None)
+        .await
+        .expect("Join should reach the table");
+    assert!(response.is_success(), "unexpected response: {response:?}");
+
+    // The table saves a snapshot with the user seated on its next tick
+    let mut saved_escrow = None;
+    for _ in 0..30 {
+        saved_escrow = sqlx::query_scalar::<_, i64>(
+            "SELECT escrow_balance FROM table_snapshots WHERE table_id = $1 AND state::text LIKE $2",
+        )
+        .bind(table_id)
+        .bind(format!("%{}%", username))
+        .fetch_optional(pool.as_ref())
+        .await
+        .unwrap();
+        if saved_escrow.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(saved_escrow, Some(500));
+
+    // A restarted table picks the seated user back up
+    let (mut actor, handle) = TableActor::new(
+        table_id,
+        config.clone(),
+        wallet_manager.clone(),
+        pool.clone(),
+    );
+    assert!(actor.restore_snapshot().await);
+    tokio::spawn(actor.run());
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    handle
+        .send(TableMessage::GetGameView {
+            user_id,
+            response: tx,
+        })
+        .await
+        .unwrap();
+    assert!(rx.await.unwrap().is_some());
+
+    // Once the escrow moved past the snapshot, the snapshot is dropped
+    sqlx::query("UPDATE table_escrows SET balance = balance + 1 WHERE table_id = $1")
+        .bind(table_id)
+        .execute(pool.as_ref())
+        .await
+        .unwrap();
+    let (mut actor, _handle) = TableActor::new(table_id, config, wallet_manager, pool.clone());
+    assert!(!actor.restore_snapshot().await);
+    let snapshots: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM table_snapshots WHERE table_id = $1")
+            .bind(table_id)
+            .fetch_one(pool.as_ref())
+            .await
+            .unwrap();
+    assert_eq!(snapshots, 0);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_close_without_settling_keeps_snapshot() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    cleanup_test_tables(&pool).await;
+    let username = unique_username("unsettled");
+    let (user_id, _access_token, _refresh_token) = create_test_user(&auth_manager, &username).await;
+
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool.clone(), wallet_manager.clone());
+    wallet_manager
+        .claim_faucet(user_id)
+        .await
+        .expect("Faucet claim should succeed");
+
+    let config = TableConfig {
+        name: "Unsettled Table".to_string(),
+        small_blind: 5,
+        big_blind: 10,
+        ..Default::default()
+    };
+    let table_id = table_manager
+        .create_table(config, Some(user_id))
+        .await
+        .expect("Table creation should succeed");
+    let response = table_manager
+        .join_table(table_id, user_id, username, 500, None, None)
+        .await
+        .expect("Join should reach the table");
+    assert!(response.is_success(), "unexpected response: {response:?}");
+
+    let count_snapshots = || async {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM table_snapshots WHERE table_id = $1")
+            .bind(table_id)
+            .fetch_one(pool.as_ref())
+            .await
+            .unwrap()
+    };
+    for _ in 0..30 {
+        if count_snapshots().await == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(count_snapshots().await, 1);
+
+    // The chips are still in escrow, so the snapshot is their only record
+    let handle = table_manager.get_table(table_id).await.unwrap();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    handle
+        .send(TableMessage::Close { response: tx })
+        .await
+        .unwrap();
+    assert!(rx.await.unwrap().is_success());
+    // The actor drops its inbox once its run loop has exited
+    for _ in 0..30 {
+        if handle.send(TableMessage::Tick).await.is_err() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(handle.send(TableMessage::Tick).await.is_err());
+    assert_eq!(count_snapshots().await, 1);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_create_and_join_leaves_no_table_when_buy_in_fails() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    cleanup_test_tables(&pool).await;
+    let username = unique_username("creator");
+    let (user_id, _access_token, _refresh_token) = create_test_user(&auth_manager, &username).await;
+
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool.clone(), wallet_manager.clone());
+
+    let config = TableConfig {
+        name: "Create And Join Table".to_string(),
+        max_players: 6,
+        small_blind: 100,
+        big_blind: 200,
+        ..Default::default()
+    };
+
+    // A legal buy-in, but more than the new wallet's starting balance
+    let result = table_manager
+        .create_and_join(config.clone(), user_id, username.clone(), 15_000, None)
+        .await;
+    assert!(result.is_err(), "Join without funds should fail");
+
+    let tables = table_manager.list_tables().await.unwrap();
+    assert!(tables.is_empty(), "No table should be listed");
+    let table_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tables")
+        .fetch_one(pool.as_ref())
+        .await
+        .unwrap();
+    let escrow_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM table_escrows")
+        .fetch_one(pool.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(table_rows, 0);
+    assert_eq!(escrow_rows, 0);
+
+    // With an affordable buy-in the table is created with its creator seated
+    let table_id = table_manager
+        .create_and_join(config, user_id, username, 4_000, None)
+        .await
+        .expect("Create and join should succeed");
+
+    let tables = table_manager.list_tables().await.unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].id, table_id);
+    assert_eq!(tables[0].player_count, 1);
+    assert_eq!(
+        wallet_manager.get_escrow(table_id).await.unwrap().balance,
+        4_000
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_idle_tables_close_unless_persistent_or_watched() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    cleanup_test_tables(&pool).await;
+    let username = unique_username("watcher");
+    let (user_id, _access_token, _refresh_token) = create_test_user(&auth_manager, &username).await;
+
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool.clone(), wallet_manager);
+
+    let table_config = |name: &str, persistent: bool| TableConfig {
+        name: name.to_string(),
+        bots_enabled: false,
+        persistent,
+        ..Default::default()
+    };
+    let idle_id = table_manager
+        .create_table(table_config("Idle Table", false), None)
+        .await
+        .unwrap();
+    let persistent_id = table_manager
+        .create_table(table_config("Persistent Table", true), None)
+        .await
+        .unwrap();
+    let watched_id = table_manager
+        .create_table(table_config("Watched Table", false), None)
+        .await
+        .unwrap();
+
+    // A spectator alone keeps a table occupied
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    table_manager
+        .get_table(watched_id)
+        .await
+        .unwrap()
+        .send(TableMessage::Spectate {
+            user_id,
+            username,
+            response: tx,
+        })
+        .await
+        .unwrap();
+    assert!(rx.await.unwrap().is_success());
+
+    let idle_timeout = Duration::from_millis(200);
+    let closed = table_manager.close_idle_tables(idle_timeout).await;
+    assert!(closed.is_empty(), "Nothing has been idle long enough yet");
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let closed = table_manager.close_idle_tables(idle_timeout).await;
+    assert_eq!(closed, vec![idle_id]);
+
+    assert!(table_manager.get_table(idle_id).await.is_none());
+    assert!(table_manager.get_table(persistent_id).await.is_some());
+    assert!(table_manager.get_table(watched_id).await.is_some());
+    let listed: Vec<i64> = table_manager
+        .list_tables()
+        .await
+        .unwrap()
+        .iter()
+        .map(|t| t.id)
+        .collect();
+    assert!(!listed.contains(&idle_id));
+    assert!(listed.contains(&persistent_id));
+}
+
 #[tokio::test]
 #[serial]
 async fn test_table_get_state() {
@@ -373,7 +1018,7 @@ async fn test_api_error_handling() {
 
     // Test joining non-existent table
     let result = table_manager
-        .join_table(99999, 1, "test_user".to_string(), 500, None)
+        .join_table(99999, 1, "test_user".to_string(), 500, None, None)
         .await;
     assert!(result.is_err(), "Joining non-existent table should fail");
 
@@ -440,10 +1085,10 @@ async fn test_concurrent_table_joins() {
     let mut handles = Vec::new();
     for (user_id, username) in user_ids {
         let tm = table_manager.clone();
-        let handle =
-            tokio::spawn(
-                async move { tm.join_table(table_id, user_id, username, 500, None).await },
-            );
+        let handle = tokio::spawn(async move {
+            tm.join_table(table_id, user_id, username, 500, None, None)
+                .await
+        });
         handles.push(handle);
     }
 
@@ -460,3 +1105,300 @@ async fn test_concurrent_table_joins() {
         "Multiple users should successfully join"
     );
 }
+
+#[tokio::test]
+async fn test_player_stats_summed_over_window() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    let (user_id, _, _) = create_test_user(&auth_manager, &unique_username("stats")).await;
+    let stats = StatsRepository::new(pool);
+
+    let empty = stats.get_stats(user_id, 30).await.unwrap();
+    assert_eq!(empty.hands_played, 0);
+
+    // A raised hand that won 3 big blinds, then a folded hand
+    let raised = StatsCounters {
+        hands_played: 1,
+        vpip_hands: 1,
+        pfr_hands: 1,
+        showdown_hands: 0,
+        hands_won: 1,
+        net_centi_bb: 300,
+        ..Default::default()
+    };
+    let folded = StatsCounters {
+        hands_played: 1,
+        ..Default::default()
+    };
+    stats.record_hand(&[(user_id, raised)]).await.unwrap();
+    stats.record_hand(&[(user_id, folded)]).await.unwrap();
+
+    let summary = stats.get_stats(user_id, 30).await.unwrap();
+    assert_eq!(summary.hands_played, 2);
+    assert!((summary.vpip_pct - 50.0).abs() < f64::EPSILON);
+    assert!((summary.pfr_pct - 50.0).abs() < f64::EPSILON);
+    assert!((summary.bb_per_100 - 150.0).abs() < f64::EPSILON);
+}
+
+#[tokio::test]
+async fn test_player_note_set_overwritten_and_cleared() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    let (author_id, _, _) = create_test_user(&auth_manager, &unique_username("author")).await;
+    let (other_id, _, _) = create_test_user(&auth_manager, &unique_username("other")).await;
+    let notes = NotesRepository::new(pool);
+    let target = vec!["villain".to_string()];
+
+    notes
+        .set_note(author_id, "villain", "limps a lot")
+        .await
+        .unwrap();
+    notes
+        .set_note(author_id, "villain", "  limp-reraises aces  ")
+        .await
+        .unwrap();
+    let found = notes.get_notes(author_id, &target).await.unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found["villain"], "limp-reraises aces");
+
+    // Notes are private to their author
+    assert!(notes.get_notes(other_id, &target).await.unwrap().is_empty());
+
+    // Too long is rejected and keeps the old note
+    let too_long = "x".repeat(MAX_NOTE_LENGTH + 1);
+    assert!(matches!(
+        notes.set_note(author_id, "villain", &too_long).await,
+        Err(NotesError::TooLong { .. })
+    ));
+    let kept = notes.get_notes(author_id, &target).await.unwrap();
+    assert_eq!(kept["villain"], "limp-reraises aces");
+
+    // Empty text clears the note
+    notes.set_note(author_id, "villain", " ").await.unwrap();
+    assert!(
+        notes
+            .get_notes(author_id, &target)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_player_notes_batch_lookup() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    let (author_id, _, _) = create_test_user(&auth_manager, &unique_username("author")).await;
+    let notes = NotesRepository::new(pool);
+
+    notes.set_note(author_id, "alice", "tight").await.unwrap();
+    notes
+        .set_note(author_id, "bob", "bluffs rivers")
+        .await
+        .unwrap();
+    notes
+        .set_note(author_id, "carol", "not seated")
+        .await
+        .unwrap();
+
+    // Only the seated players with a note come back
+    let seated = vec!["alice".to_string(), "bob".to_string(), "dave".to_string()];
+    let found = notes.get_notes(author_id, &seated).await.unwrap();
+    assert_eq!(found.len(), 2);
+    assert_eq!(found["alice"], "tight");
+    assert_eq!(found["bob"], "bluffs rivers");
+
+    assert!(notes.get_notes(author_id, &[]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_tournament_clock_advances_blind_level() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    cleanup_test_tables(&pool).await;
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let table_manager = TableManager::new(pool.clone(), wallet_manager);
+    let tournaments = TournamentManager::new(pool.clone());
+
+    // A 1-second first level, so the test doesn't wait long
+    let mut config = TournamentConfig::sit_and_go(unique_username("clock"), 2, 100);
+    config.blind_levels = vec![
+        BlindLevel::new(1, 10, 20, 1),
+        BlindLevel::new(2, 20, 40, 60),
+    ];
+    let tournament_id = tournaments.create_tournament(config).await.unwrap();
+    for _ in 0..2 {
+        let username = unique_username("clock");
+        let (user_id, _, _) = create_test_user(&auth_manager, &username).await;
+        tournaments
+            .register_player(tournament_id, user_id, username)
+            .await
+            .unwrap();
+    }
+    let table_config = TableConfig {
+        name: unique_username("Clock Table"),
+        ..Default::default()
+    };
+    let table_id = table_manager
+        .create_table(table_config, None)
+        .await
+        .unwrap();
+    tournaments
+        .assign_tables(tournament_id, &[table_id], 9)
+        .await
+        .unwrap();
+    let mut events = tournaments.subscribe();
+
+    // Time spent paused doesn't count toward the level
+    tournaments.pause_tournament(tournament_id).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1200)).await;
+    tournaments
+        .update_clock(tournament_id, &table_manager)
+        .await
+        .unwrap();
+    let info = tournaments
+        .get_tournament_info(tournament_id)
+        .await
+        .unwrap();
+    assert!(info.paused);
+    assert_eq!(info.current_level, 1);
+    assert!(events.try_recv().is_err());
+
+    tournaments.resume_tournament(tournament_id).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1200)).await;
+    tournaments
+        .update_clock(tournament_id, &table_manager)
+        .await
+        .unwrap();
+    let info = tournaments
+        .get_tournament_info(tournament_id)
+        .await
+        .unwrap();
+    assert!(!info.paused);
+    assert_eq!(info.current_level, 2);
+    assert!(matches!(
+        events.try_recv(),
+        Ok(ServerMessage::BlindLevelIncreased {
+            tournament_id: id,
+            new_level: 2,
+        }) if id == tournament_id
+    ));
+
+    // The level is only announced once
+    tournaments
+        .update_clock(tournament_id, &table_manager)
+        .await
+        .unwrap();
+    assert!(events.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_tied_bubble_players_split_custom_payout() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    let tournaments = TournamentManager::new(pool);
+
+    let mut config = TournamentConfig::sit_and_go(unique_username("payout"), 4, 101);
+    config.payout_structure = PayoutStructure::Custom {
+        basis_points: vec![6000, 4000, 1000],
+    };
+    assert!(matches!(
+        tournaments.create_tournament(config.clone()).await,
+        Err(TournamentError::InvalidPayouts(_))
+    ));
+
+    // 60/25/15 of 404 chips is 242.4/101/60.6, so 3rd gets the odd chip
+    config.payout_structure = PayoutStructure::Custom {
+        basis_points: vec![6000, 2500, 1500],
+    };
+    let tournament_id = tournaments.create_tournament(config).await.unwrap();
+    let mut user_ids = Vec::new();
+    for _ in 0..4 {
+        let username = unique_username("payout");
+        let (user_id, _, _) = create_test_user(&auth_manager, &username).await;
+        tournaments
+            .register_player(tournament_id, user_id, username)
+            .await
+            .unwrap();
+        user_ids.push(user_id);
+    }
+    let info = tournaments
+        .get_tournament_info(tournament_id)
+        .await
+        .unwrap();
+    assert_eq!(info.prize_structure.payouts, vec![242, 101, 61]);
+
+    // Two players bust together on the bubble and split 3rd
+    tournaments
+        .eliminate_tied_players(tournament_id, &user_ids[2..], 3, &[user_ids[0]])
+        .await
+        .unwrap();
+    let registrations = tournaments.get_registrations(tournament_id).await.unwrap();
+    let mut tied: Vec<_> = registrations
+        .iter()
+        .filter(|r| r.finish_position == Some(3))
+        .map(|r| r.prize_amount)
+        .collect();
+    tied.sort();
+    assert_eq!(tied, vec![Some(30), Some(31)]);
+}
+
+#[tokio::test]
+async fn test_double_bust_on_bubble_ranks_by_starting_stack() {
+    let (auth_manager, pool) = setup_auth_manager().await;
+    let tournaments = TournamentManager::new(pool);
+
+    // 50/30/20 of 500 chips, so 4th is the bubble
+    let mut config = TournamentConfig::sit_and_go(unique_username("bubble"), 5, 100);
+    config.payout_structure = PayoutStructure::Custom {
+        basis_points: vec![5000, 3000, 2000],
+    };
+    let tournament_id = tournaments.create_tournament(config).await.unwrap();
+    let mut user_ids = Vec::new();
+    for _ in 0..5 {
+        let username = unique_username("bubble");
+        let (user_id, _, _) = create_test_user(&auth_manager, &username).await;
+        tournaments
+            .register_player(tournament_id, user_id, username)
+            .await
+            .unwrap();
+        user_ids.push(user_id);
+    }
+    tournaments
+        .eliminate_player(tournament_id, user_ids[4], 5, &[user_ids[0]])
+        .await
+        .unwrap();
+
+    // Both bust in the same hand, and the deeper stack min-cashes
+    let mut events = tournaments.subscribe();
+    tournaments
+        .eliminate_busted_players(
+            tournament_id,
+            &[(user_ids[2], 800), (user_ids[3], 1200)],
+            &[(user_ids[0], 3000)],
+        )
+        .await
+        .unwrap();
+
+    let mut eliminated = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let ServerMessage::PlayerEliminated {
+            user_id,
+            position,
+            prize,
+            ..
+        } = event
+        {
+            eliminated.push((user_id, position, prize));
+        }
+    }
+    assert_eq!(
+        eliminated,
+        vec![(user_ids[3], 3, Some(100)), (user_ids[2], 4, None)]
+    );
+
+    let registrations = tournaments.get_registrations(tournament_id).await.unwrap();
+    let finish = |user_id: i64| {
+        let r = registrations.iter().find(|r| r.user_id == user_id).unwrap();
+        (r.finish_position, r.prize_amount)
+    };
+    assert_eq!(finish(user_ids[3]), (Some(3), Some(100)));
+    assert_eq!(finish(user_ids[2]), (Some(4), None));
+    assert_eq!(finish(user_ids[0]), (None, None));
+}
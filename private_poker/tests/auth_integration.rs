@@ -2,10 +2,14 @@
 //!
 //! Tests registration, login, 2FA, session management, and password reset flows.
 
-use private_poker::auth::{AuthError, AuthManager, LoginRequest, RegisterRequest};
+use async_trait::async_trait;
+use private_poker::auth::{
+    AuthError, AuthManager, AuthResult, LoginRequest, Mailer, RegisterRequest, UserId,
+};
 use private_poker::db::{Database, DatabaseConfig};
+use private_poker::security::RateLimitConfig;
 use sqlx::PgPool;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 /// Helper to create a test database pool
 async fn setup_test_db() -> Arc<PgPool> {
@@ -39,6 +43,69 @@ async fn setup_auth_manager() -> (AuthManager, Arc<PgPool>) {
     (auth, pool)
 }
 
+/// Mailer that keeps sent emails so tests can read them
+#[derive(Default)]
+struct CapturingMailer {
+    sent: Mutex<Vec<(String, String)>>,
+}
+
+impl CapturingMailer {
+    /// Verification code from the last email sent
+    fn last_code(&self) -> String {
+        let sent = self.sent.lock().unwrap();
+        let (_, body) = sent.last().expect("An email should have been sent");
+        body.split_whitespace()
+            .find_map(|word| {
+                let word = word.trim_end_matches('.');
+                (word.len() == 6 && word.chars().all(|c| c.is_ascii_digit()))
+                    .then(|| word.to_string())
+            })
+            .expect("Email should contain a code")
+    }
+}
+
+#[async_trait]
+impl Mailer for CapturingMailer {
+    async fn send(&self, to: &str, _subject: &str, body: &str) -> AuthResult<()> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push((to.to_string(), body.to_string()));
+        Ok(())
+    }
+}
+
+/// Helper to register a user with an email, using a capturing mailer
+async fn setup_email_user(
+    username: &str,
+) -> (AuthManager, Arc<CapturingMailer>, Arc<PgPool>, UserId) {
+    let (auth, pool) = setup_auth_manager().await;
+    let mailer = Arc::new(CapturingMailer::default());
+    let auth = auth.with_mailer(mailer.clone());
+    cleanup_user(pool.as_ref(), username).await;
+
+    let user = auth
+        .register(RegisterRequest {
+            username: username.to_string(),
+            password: "SecurePass123!".to_string(),
+            display_name: username.to_string(),
+            email: Some(format!("{}@example.com", username)),
+        })
+        .await
+        .expect("Registration should succeed");
+    assert!(!user.email_verified);
+
+    (auth, mailer, pool, user.id)
+}
+
+async fn is_email_verified(pool: &PgPool, user_id: UserId) -> bool {
+    sqlx::query_scalar("SELECT email_verified FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .expect("User should exist")
+}
+
 /// Helper to clean up test user
 async fn cleanup_user(pool: &PgPool, username: &str) {
     let _ = sqlx::query("DELETE FROM users WHERE username = $1")
@@ -474,3 +541,263 @@ async fn test_multiple_sessions_same_user() {
 
     cleanup_user(pool.as_ref(), username).await;
 }
+
+#[tokio::test]
+async fn test_login_lockout_returns_retry_info() {
+    let (auth, pool) = setup_auth_manager().await;
+    let username = "test_login_lockout";
+    cleanup_user(pool.as_ref(), username).await;
+    let _ = sqlx::query("DELETE FROM rate_limit_attempts WHERE identifier = $1")
+        .bind(username)
+        .execute(pool.as_ref())
+        .await;
+
+    auth.register(RegisterRequest {
+        username: username.to_string(),
+        password: "SecurePass123!".to_string(),
+        display_name: username.to_string(),
+        email: None,
+    })
+    .await
+    .expect("Registration should succeed");
+
+    let login = |password: &str| {
+        auth.login(
+            LoginRequest {
+                username: username.to_string(),
+                password: password.to_string(),
+                totp_code: None,
+            },
+            "test_device".to_string(),
+        )
+    };
+
+    // A successful login resets the count, so failures before it don't
+    // add up to a lockout
+    let max_attempts = RateLimitConfig::login().max_attempts;
+    for _ in 1..max_attempts {
+        assert!(matches!(
+            login("WrongPass123!").await,
+            Err(AuthError::InvalidPassword)
+        ));
+    }
+    login("SecurePass123!")
+        .await
+        .expect("Login should succeed before the limit is reached");
+
+    for _ in 0..max_attempts {
+        assert!(matches!(
+            login("WrongPass123!").await,
+            Err(AuthError::InvalidPassword)
+        ));
+    }
+
+    // Even the right password is refused once the limit is tripped
+    match login("SecurePass123!").await {
+        Err(AuthError::RateLimited {
+            retry_after_secs,
+            attempts_remaining,
+        }) => {
+            assert!(retry_after_secs > 0);
+            assert_eq!(attempts_remaining, 0);
+        }
+        other => panic!("Expected a lockout, got {:?}", other.map(|(user, _)| user)),
+    }
+
+    let _ = sqlx::query("DELETE FROM rate_limit_attempts WHERE identifier = $1")
+        .bind(username)
+        .execute(pool.as_ref())
+        .await;
+    cleanup_user(pool.as_ref(), username).await;
+}
+
+#[tokio::test]
+async fn test_login_limited_by_ip_across_accounts() {
+    let (auth, pool) = setup_auth_manager().await;
+    let (ip_address, other_ip_address) = ("198.51.100.77", "198.51.100.78");
+    let cleanup = || async {
+        let _ = sqlx::query(
+            "DELETE FROM rate_limit_attempts WHERE identifier LIKE 'test_ip_limit_%' OR identifier = ANY($1)",
+        )
+        .bind(vec![
+            format!("ip:{}", ip_address),
+            format!("ip:{}", other_ip_address),
+        ])
+        .execute(pool.as_ref())
+        .await;
+    };
+    cleanup().await;
+
+    let login = |username: String, ip_address: &'static str| {
+        auth.login_from(
+            LoginRequest {
+                username,
+                password: "WrongPass123!".to_string(),
+                totp_code: None,
+            },
+            "test_device".to_string(),
+            Some(ip_address),
+        )
+    };
+
+    // Each attempt targets a different account, so only the IP limit adds up
+    let max_attempts = RateLimitConfig::login().max_attempts;
+    for i in 0..max_attempts {
+        assert!(matches!(
+            login(format!("test_ip_limit_{}", i), ip_address).await,
+            Err(AuthError::UserNotFound)
+        ));
+    }
+    assert!(matches!(
+        login(format!("test_ip_limit_{}", max_attempts), ip_address).await,
+        Err(AuthError::RateLimited { .. })
+    ));
+
+    // Another IP has its own bucket
+    assert!(matches!(
+        login(
+            format!("test_ip_limit_{}", max_attempts + 1),
+            other_ip_address
+        )
+        .await,
+        Err(AuthError::UserNotFound)
+    ));
+
+    cleanup().await;
+}
+
+#[tokio::test]
+async fn test_verify_email() {
+    let username = "test_verify_email";
+    let (auth, mailer, pool, user_id) = setup_email_user(username).await;
+
+    auth.send_verification(user_id)
+        .await
+        .expect("Verification email should be sent");
+    assert_eq!(
+        mailer.sent.lock().unwrap()[0].0,
+        format!("{}@example.com", username)
+    );
+
+    let code = mailer.last_code();
+    auth.verify_email(user_id, &code)
+        .await
+        .expect("Verification should succeed");
+    assert!(is_email_verified(pool.as_ref(), user_id).await);
+
+    // Codes are single-use
+    assert!(matches!(
+        auth.verify_email(user_id, &code).await,
+        Err(AuthError::InvalidVerificationCode)
+    ));
+
+    cleanup_user(pool.as_ref(), username).await;
+}
+
+#[tokio::test]
+async fn test_verify_email_rejects_expired_code() {
+    let username = "test_verify_expired";
+    let (auth, mailer, pool, user_id) = setup_email_user(username).await;
+
+    auth.send_verification(user_id)
+        .await
+        .expect("Verification email should be sent");
+    let code = mailer.last_code();
+
+    sqlx::query(
+        "UPDATE email_verifications
+         SET created_at = NOW() - INTERVAL '2 hours', expires_at = NOW() - INTERVAL '1 hour'
+         WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .execute(pool.as_ref())
+    .await
+    .expect("Failed to expire code");
+
+    assert!(matches!(
+        auth.verify_email(user_id, &code).await,
+        Err(AuthError::InvalidVerificationCode)
+    ));
+    assert!(!is_email_verified(pool.as_ref(), user_id).await);
+
+    cleanup_user(pool.as_ref(), username).await;
+}
+
+#[tokio::test]
+async fn test_verify_email_locks_code_after_failed_attempts() {
+    let username = "test_verify_lockout";
+    let (auth, mailer, pool, user_id) = setup_email_user(username).await;
+
+    auth.send_verification(user_id)
+        .await
+        .expect("Verification email should be sent");
+    let code = mailer.last_code();
+    let wrong_code = if code == "000000" { "000001" } else { "000000" };
+
+    for _ in 0..5 {
+        assert!(matches!(
+            auth.verify_email(user_id, wrong_code).await,
+            Err(AuthError::InvalidVerificationCode)
+        ));
+    }
+
+    // The right code no longer works once the code is locked
+    assert!(matches!(
+        auth.verify_email(user_id, &code).await,
+        Err(AuthError::VerificationAttemptsExceeded)
+    ));
+    assert!(!is_email_verified(pool.as_ref(), user_id).await);
+
+    cleanup_user(pool.as_ref(), username).await;
+}
+
+#[tokio::test]
+async fn test_send_verification_invalidates_earlier_codes() {
+    let username = "test_verify_resend";
+    let (auth, mailer, pool, user_id) = setup_email_user(username).await;
+
+    auth.send_verification(user_id)
+        .await
+        .expect("Verification email should be sent");
+    let first_code = mailer.last_code();
+    auth.send_verification(user_id)
+        .await
+        .expect("Verification email should be sent");
+    let second_code = mailer.last_code();
+
+    if first_code != second_code {
+        assert!(matches!(
+            auth.verify_email(user_id, &first_code).await,
+            Err(AuthError::InvalidVerificationCode)
+        ));
+    }
+    auth.verify_email(user_id, &second_code)
+        .await
+        .expect("Latest code should verify");
+    assert!(is_email_verified(pool.as_ref(), user_id).await);
+
+    cleanup_user(pool.as_ref(), username).await;
+}
+
+#[tokio::test]
+async fn test_send_verification_is_rate_limited() {
+    let username = "test_verify_limited";
+    let (auth, _mailer, pool, user_id) = setup_email_user(username).await;
+
+    let max_attempts = RateLimitConfig::email_verification().max_attempts;
+    for _ in 0..max_attempts {
+        auth.send_verification(user_id)
+            .await
+            .expect("Verification email should be sent");
+    }
+    assert!(matches!(
+        auth.send_verification(user_id).await,
+        Err(AuthError::RateLimited { .. })
+    ));
+
+    let _ = sqlx::query("DELETE FROM rate_limit_attempts WHERE identifier = $1")
+        .bind(format!("user:{}", user_id))
+        .execute(pool.as_ref())
+        .await;
+    cleanup_user(pool.as_ref(), username).await;
+}
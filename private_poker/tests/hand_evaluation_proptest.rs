@@ -4,7 +4,7 @@
 /// across a wide range of randomly generated card combinations.
 use private_poker::game::{
     entities::{Card, Suit},
-    functional::{argmax, eval, prepare_hand},
+    functional::{argmax, eval, eval_fast, prepare_hand},
 };
 use proptest::prelude::*;
 use std::collections::BTreeSet;
@@ -459,6 +459,17 @@ proptest! {
         prop_assert_eq!(hand1[0].rank, hand2[0].rank, "Rank should be consistent");
     }
 
+    /// Test that the lookup table evaluator orders hands like `eval`
+    #[test]
+    fn test_eval_fast_agrees_with_eval(
+        cards1 in seven_card_hand_strategy(),
+        cards2 in seven_card_hand_strategy(),
+    ) {
+        let ordering = eval_hand(&cards1).cmp(&eval_hand(&cards2));
+        let fast_ordering = eval_fast(&cards1).cmp(&eval_fast(&cards2));
+        prop_assert_eq!(ordering, fast_ordering, "eval_fast should order hands like eval");
+    }
+
     /// Test that no invalid card values appear in results
     #[test]
     fn test_no_invalid_card_values(cards in seven_card_hand_strategy()) {
@@ -163,6 +163,60 @@ fn test_custom_prize_structure_conservation() {
     }
 }
 
+#[test]
+fn test_fifty_thirty_twenty_indivisible_pool() {
+    // 50/30/20 of 1,001 chips is 500.5/300.3/200.2, so 1st gets the chip
+    // lost to rounding
+    let structure = PrizeStructure::from_basis_points(1001, &[5000, 3000, 2000]);
+    assert_eq!(structure.payouts, vec![501, 300, 200]);
+    assert_eq!(structure.payouts.iter().sum::<i64>(), 1001);
+
+    // 50/30/20 of 7 chips is 3.5/2.1/1.4
+    let structure = PrizeStructure::from_basis_points(7, &[5000, 3000, 2000]);
+    assert_eq!(structure.payouts, vec![4, 2, 1]);
+}
+
+#[test]
+fn test_largest_remainder_allocation() {
+    // 33.33/33.33/33.34 of 100 chips: 3rd lost the most to rounding
+    let structure = PrizeStructure::from_basis_points(100, &[3333, 3333, 3334]);
+    assert_eq!(structure.payouts, vec![33, 33, 34]);
+
+    // Even shares of 10 chips lose the same to rounding, so the better
+    // finishes get the leftover chips
+    let structure = PrizeStructure::from_basis_points(10, &[2500, 2500, 2500, 2500]);
+    assert_eq!(structure.payouts, vec![3, 3, 2, 2]);
+
+    // Every split of every pool adds up exactly
+    let splits: [&[u32]; 4] = [
+        &[5000, 3000, 2000],
+        &[4000, 2500, 1500, 1000, 1000],
+        &[10000],
+        &[3334, 3333, 3333],
+    ];
+    for pool in 0..500 {
+        for basis_points in splits {
+            let structure = PrizeStructure::from_basis_points(pool, basis_points);
+            assert_eq!(
+                structure.payouts.iter().sum::<i64>(),
+                pool,
+                "{} pool with {:?} basis points",
+                pool,
+                basis_points
+            );
+        }
+    }
+}
+
+#[test]
+fn test_satellite_conservation() {
+    for pool in [0, 1, 299, 300, 1050, 12_345] {
+        let structure = PrizeStructure::satellite(pool, 300);
+        assert_eq!(structure.payouts.iter().sum::<i64>(), pool);
+        assert!(structure.payouts.iter().all(|payout| *payout <= 300));
+    }
+}
+
 #[test]
 fn test_edge_case_small_pools() {
     // Test very small pools where rounding matters most
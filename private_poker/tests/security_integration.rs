@@ -2,10 +2,12 @@
 
 //! Integration tests for security features.
 //!
-//! Tests rate limiting, anti-collusion detection, and seat randomization.
+//! Tests rate limiting, anti-collusion detection, seat randomization, and table chat.
 
 use private_poker::db::{Database, DatabaseConfig};
 use private_poker::security::{AntiCollusionDetector, RateLimiter, SeatRandomizer};
+use private_poker::table::{TableActor, TableConfig, TableHandle, TableMessage, TableResponse};
+use private_poker::wallet::WalletManager;
 use sqlx::PgPool;
 use std::sync::Arc;
 
@@ -410,3 +412,91 @@ async fn test_rate_limit_cleanup() {
     let result = limiter.cleanup_expired().await;
     assert!(result.is_ok(), "Cleanup should succeed");
 }
+
+// === Table Chat Tests ===
+
+/// Send a chat message to a table and wait for the response
+async fn send_chat(handle: &TableHandle, user_id: i64, message: &str) -> TableResponse {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    handle
+        .send(TableMessage::SendChat {
+            user_id,
+            message: message.to_string(),
+            response: tx,
+        })
+        .await
+        .expect("Table should accept message");
+    rx.await.expect("Table should respond")
+}
+
+#[tokio::test]
+async fn test_table_chat_rate_limited() {
+    let pool = setup_test_db().await;
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let (actor, handle) = TableActor::new(1, TableConfig::default(), wallet_manager, pool.clone());
+    tokio::spawn(actor.run());
+
+    let user_id = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    handle
+        .send(TableMessage::Spectate {
+            user_id,
+            username: unique_id("chatter"),
+            response: tx,
+        })
+        .await
+        .expect("Table should accept message");
+    assert!(rx.await.unwrap().is_success(), "Spectate should succeed");
+
+    // Chat limit is 10 messages per minute
+    for i in 0..10 {
+        let response = send_chat(&handle, user_id, "hello").await;
+        assert!(
+            response.is_success(),
+            "Message #{} should be allowed",
+            i + 1
+        );
+    }
+
+    // Later messages should be throttled
+    for _ in 0..3 {
+        let response = send_chat(&handle, user_id, "spam").await;
+        assert!(
+            matches!(response, TableResponse::RateLimited { .. }),
+            "Message should be rate limited, got {:?}",
+            response
+        );
+    }
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    handle
+        .send(TableMessage::GetChatHistory { response: tx })
+        .await
+        .expect("Table should accept message");
+    assert_eq!(
+        rx.await.unwrap().len(),
+        10,
+        "Only allowed messages are stored"
+    );
+
+    // Clean up
+    RateLimiter::new(pool)
+        .reset("chat", &user_id.to_string())
+        .await
+        .expect("Reset should succeed");
+}
+
+#[tokio::test]
+async fn test_table_chat_rejects_users_not_at_table() {
+    let pool = setup_test_db().await;
+    let wallet_manager = Arc::new(WalletManager::new(pool.clone()));
+    let (actor, handle) = TableActor::new(1, TableConfig::default(), wallet_manager, pool);
+    tokio::spawn(actor.run());
+
+    let user_id = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    let response = send_chat(&handle, user_id, "hello").await;
+    assert!(
+        matches!(response, TableResponse::NotAtTable),
+        "Users not at the table should not be able to chat"
+    );
+}
@@ -302,6 +302,7 @@ fn test_pot_distribution_integration() {
 
     let mut pot = Pot {
         investments: HashMap::new(),
+        street_start_investments: HashMap::new(),
     };
 
     // Player 0: $50
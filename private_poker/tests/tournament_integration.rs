@@ -6,7 +6,8 @@
 #[cfg(test)]
 mod tournament_tests {
     use private_poker::tournament::models::{
-        BlindLevel, PrizeStructure, TournamentConfig, TournamentState, TournamentType,
+        BlindLevel, PayoutStructure, PrizeStructure, TournamentConfig, TournamentState,
+        TournamentType,
     };
 
     #[test]
@@ -23,6 +24,8 @@ mod tournament_tests {
             starting_level: 1,
             scheduled_start: None,
             late_registration_secs: None,
+            bounty_cash_percent: 50,
+            payout_structure: PayoutStructure::Standard,
         };
 
         assert_eq!(config.min_players, 2);
@@ -180,6 +183,8 @@ mod tournament_tests {
             starting_level: 1,
             scheduled_start: None,
             late_registration_secs: None,
+            bounty_cash_percent: 50,
+            payout_structure: PayoutStructure::Standard,
         };
 
         let initial_bb = config.blind_levels[0].big_blind;
@@ -223,6 +228,8 @@ mod tournament_tests {
             starting_level: 1,
             scheduled_start: None,
             late_registration_secs: None,
+            bounty_cash_percent: 50,
+            payout_structure: PayoutStructure::Standard,
         };
 
         assert!(config.min_players >= 2, "Need at least 2 players");
@@ -5,9 +5,12 @@
 
 use private_poker::auth::{AuthManager, RegisterRequest};
 use private_poker::db::{Database, DatabaseConfig};
-use private_poker::wallet::WalletManager;
+use private_poker::wallet::{
+    EntryDirection, EntryFilter, EntryType, EscrowDiscrepancy, SummaryPeriod, WalletError,
+    WalletManager,
+};
 use sqlx::PgPool;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 /// Generate unique idempotency key
 fn unique_key(prefix: &str) -> String {
@@ -170,6 +173,54 @@ async fn test_faucet_cooldown() {
     cleanup_user(&pool, username).await;
 }
 
+#[tokio::test]
+async fn test_faucet_status() {
+    let (wallet_mgr, auth_mgr, pool) = setup_managers().await;
+    let username = "test_faucet_status";
+    cleanup_user(&pool, username).await;
+
+    // Register user
+    let user = auth_mgr
+        .register(RegisterRequest {
+            username: username.to_string(),
+            password: "SecurePass123!".to_string(),
+            display_name: username.to_string(),
+            email: None,
+        })
+        .await
+        .expect("Registration should succeed");
+
+    // Never claimed, so available immediately
+    let status = wallet_mgr
+        .faucet_status(user.id)
+        .await
+        .expect("Should get faucet status");
+    assert!(status.available);
+    assert_eq!(status.next_claim, None);
+
+    // Cooling down after a claim
+    let claim = wallet_mgr
+        .claim_faucet(user.id)
+        .await
+        .expect("Faucet claim should succeed");
+    let status = wallet_mgr
+        .faucet_status(user.id)
+        .await
+        .expect("Should get faucet status");
+    assert!(!status.available);
+    assert_eq!(status.next_claim, Some(claim.next_claim_at));
+
+    // Matches what the next claim reports
+    match wallet_mgr.claim_faucet(user.id).await {
+        Err(WalletError::FaucetNotAvailable(next_claim_at)) => {
+            assert_eq!(status.next_claim, Some(next_claim_at));
+        }
+        other => panic!("Expected faucet cooldown, got {:?}", other),
+    }
+
+    cleanup_user(&pool, username).await;
+}
+
 #[tokio::test]
 async fn test_transfer_to_escrow() {
     let (wallet_mgr, auth_mgr, pool) = setup_managers().await;
@@ -321,6 +372,60 @@ async fn test_transfer_from_escrow() {
     cleanup_user(&pool, username).await;
 }
 
+#[tokio::test]
+async fn test_collect_rake() {
+    let (wallet_mgr, _auth_mgr, pool) = setup_managers().await;
+    let table_id = 989;
+    cleanup_table_escrow(&pool, table_id).await;
+    let _ = sqlx::query("DELETE FROM rake_accounts WHERE table_id = $1")
+        .bind(table_id)
+        .execute(pool.as_ref())
+        .await;
+
+    sqlx::query("INSERT INTO table_escrows (table_id, balance) VALUES ($1, 500) ON CONFLICT (table_id) DO UPDATE SET balance = 500")
+        .bind(table_id)
+        .execute(pool.as_ref())
+        .await
+        .expect("Should create table escrow");
+
+    let rake_balance = wallet_mgr
+        .collect_rake(table_id, 25, unique_key("test_rake"))
+        .await
+        .expect("Rake collection should succeed");
+    assert_eq!(rake_balance, 25);
+
+    let escrow = wallet_mgr
+        .get_escrow(table_id)
+        .await
+        .expect("Should get escrow");
+    assert_eq!(escrow.balance, 475, "Rake should come out of the escrow");
+
+    // The rake account's ledger uses the negated table ID.
+    let entries = wallet_mgr
+        .get_entries(-table_id, 10)
+        .await
+        .expect("Should get entries");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].entry_type, EntryType::Rake);
+    assert_eq!(entries[0].amount, 25);
+
+    // Can't take more rake than is in the escrow.
+    let result = wallet_mgr
+        .collect_rake(table_id, 1000, unique_key("test_rake_too_much"))
+        .await;
+    assert!(result.is_err(), "Rake larger than the escrow should fail");
+
+    let _ = sqlx::query("DELETE FROM wallet_entries WHERE user_id = $1")
+        .bind(-table_id)
+        .execute(pool.as_ref())
+        .await;
+    let _ = sqlx::query("DELETE FROM rake_accounts WHERE table_id = $1")
+        .bind(table_id)
+        .execute(pool.as_ref())
+        .await;
+    cleanup_table_escrow(&pool, table_id).await;
+}
+
 #[tokio::test]
 async fn test_escrow_idempotency() {
     let (wallet_mgr, auth_mgr, pool) = setup_managers().await;
@@ -1023,3 +1128,365 @@ async fn test_transaction_idempotency() {
     cleanup_table_escrow(&pool, table_id).await;
     cleanup_user(&pool, username).await;
 }
+
+#[tokio::test]
+async fn test_gift_chips_between_users() {
+    let (wallet_mgr, auth_mgr, pool) = setup_managers().await;
+    let sender_name = "test_gift_sender";
+    let recipient_name = "test_gift_recipient";
+    cleanup_user(&pool, sender_name).await;
+    cleanup_user(&pool, recipient_name).await;
+
+    let mut user_ids = Vec::new();
+    for username in [sender_name, recipient_name] {
+        let user = auth_mgr
+            .register(RegisterRequest {
+                username: username.to_string(),
+                password: "SecurePass123!".to_string(),
+                display_name: username.to_string(),
+                email: None,
+            })
+            .await
+            .expect("Registration should succeed");
+        user_ids.push(user.id);
+    }
+    let (sender_id, recipient_id) = (user_ids[0], user_ids[1]);
+
+    let sender_before = wallet_mgr.get_wallet(sender_id).await.unwrap().balance;
+    let recipient_before = wallet_mgr.get_wallet(recipient_id).await.unwrap().balance;
+
+    let gift_key = unique_key("gift");
+    let new_balance = wallet_mgr
+        .transfer_between_users(sender_id, recipient_id, 250, gift_key.clone())
+        .await
+        .expect("Gift should succeed");
+    assert_eq!(new_balance, sender_before - 250);
+
+    // Both balances move together
+    let sender_after = wallet_mgr.get_wallet(sender_id).await.unwrap().balance;
+    let recipient_after = wallet_mgr.get_wallet(recipient_id).await.unwrap().balance;
+    assert_eq!(sender_after, sender_before - 250);
+    assert_eq!(recipient_after, recipient_before + 250);
+
+    // Each side has a gift entry
+    let sender_entries = wallet_mgr.get_entries(sender_id, 10).await.unwrap();
+    assert!(sender_entries.iter().any(|entry| {
+        entry.entry_type == EntryType::Gift
+            && entry.direction == EntryDirection::Debit
+            && entry.amount == -250
+    }));
+    let recipient_entries = wallet_mgr.get_entries(recipient_id, 10).await.unwrap();
+    assert!(recipient_entries.iter().any(|entry| {
+        entry.entry_type == EntryType::Gift
+            && entry.direction == EntryDirection::Credit
+            && entry.amount == 250
+    }));
+
+    // Retrying with the same key is a no-op
+    let retry = wallet_mgr
+        .transfer_between_users(sender_id, recipient_id, 250, gift_key)
+        .await;
+    assert!(matches!(retry, Err(WalletError::DuplicateTransaction(_))));
+    assert_eq!(
+        wallet_mgr.get_wallet(sender_id).await.unwrap().balance,
+        sender_after
+    );
+    assert_eq!(
+        wallet_mgr.get_wallet(recipient_id).await.unwrap().balance,
+        recipient_after
+    );
+
+    // A gift larger than the sender's balance doesn't apply at all
+    let too_much = wallet_mgr
+        .transfer_between_users(
+            sender_id,
+            recipient_id,
+            sender_after + 1,
+            unique_key("gift_too_much"),
+        )
+        .await;
+    assert!(matches!(
+        too_much,
+        Err(WalletError::InsufficientBalance { .. })
+    ));
+    assert_eq!(
+        wallet_mgr.get_wallet(recipient_id).await.unwrap().balance,
+        recipient_after
+    );
+
+    cleanup_user(&pool, sender_name).await;
+    cleanup_user(&pool, recipient_name).await;
+}
+
+#[tokio::test]
+async fn test_filter_entries_and_export_csv() {
+    let (wallet_mgr, auth_mgr, pool) = setup_managers().await;
+    let username = "test_entry_filter";
+    let table_id = 1004;
+    cleanup_user(&pool, username).await;
+    cleanup_table_escrow(&pool, table_id).await;
+
+    let user = auth_mgr
+        .register(RegisterRequest {
+            username: username.to_string(),
+            password: "SecurePass123!".to_string(),
+            display_name: username.to_string(),
+            email: None,
+        })
+        .await
+        .expect("Registration should succeed");
+
+    let before_claim = chrono::Utc::now() - chrono::Duration::seconds(1);
+    let claim = wallet_mgr
+        .claim_faucet(user.id)
+        .await
+        .expect("Faucet claim should succeed");
+    wallet_mgr
+        .transfer_to_escrow(user.id, table_id, 100, unique_key("filter_buy_in"))
+        .await
+        .expect("Transfer should succeed");
+
+    // Only faucet entries
+    let faucet_filter = EntryFilter {
+        entry_type: Some(EntryType::Bonus),
+        ..Default::default()
+    };
+    let entries = wallet_mgr
+        .get_entries_filtered(user.id, &faucet_filter)
+        .await
+        .expect("Should get entries");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].amount, claim.amount);
+
+    // Direction and date range narrow the results too
+    let debit_filter = EntryFilter {
+        direction: Some(EntryDirection::Debit),
+        from: Some(before_claim),
+        to: Some(chrono::Utc::now() + chrono::Duration::seconds(1)),
+        ..Default::default()
+    };
+    let entries = wallet_mgr
+        .get_entries_filtered(user.id, &debit_filter)
+        .await
+        .expect("Should get entries");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].entry_type, EntryType::BuyIn);
+
+    let future_filter = EntryFilter {
+        from: Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+        ..Default::default()
+    };
+    let entries = wallet_mgr
+        .get_entries_filtered(user.id, &future_filter)
+        .await
+        .expect("Should get entries");
+    assert!(entries.is_empty());
+
+    // CSV has a header and one row per entry
+    let csv = wallet_mgr
+        .export_entries_csv(user.id, &faucet_filter)
+        .await
+        .expect("Should export entries");
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "timestamp,type,direction,amount,balance_after,reference"
+    );
+    assert_eq!(lines.len(), 2);
+    let fields: Vec<&str> = lines[1].split(',').collect();
+    assert!(
+        chrono::NaiveDateTime::parse_from_str(fields[0], "%Y-%m-%d %H:%M:%S").is_ok(),
+        "Timestamp should be spreadsheet-friendly: {}",
+        fields[0]
+    );
+    assert_eq!(fields[1], "bonus");
+    assert_eq!(fields[2], "credit");
+    assert_eq!(fields[3], claim.amount.to_string());
+    assert_eq!(fields[5], "Daily faucet claim");
+
+    cleanup_table_escrow(&pool, table_id).await;
+    cleanup_user(&pool, username).await;
+}
+
+#[tokio::test]
+async fn test_summarize_matches_balance_change() {
+    let (wallet_mgr, auth_mgr, pool) = setup_managers().await;
+    let username = "test_wallet_summary";
+    let table_id = 1005;
+    cleanup_user(&pool, username).await;
+    cleanup_table_escrow(&pool, table_id).await;
+
+    let user = auth_mgr
+        .register(RegisterRequest {
+            username: username.to_string(),
+            password: "SecurePass123!".to_string(),
+            display_name: username.to_string(),
+            email: None,
+        })
+        .await
+        .expect("Registration should succeed");
+    let starting_balance = wallet_mgr.get_wallet(user.id).await.unwrap().balance;
+
+    // Faucet, buy in for 500, and cash out 200
+    let claim = wallet_mgr
+        .claim_faucet(user.id)
+        .await
+        .expect("Faucet claim should succeed");
+    wallet_mgr
+        .transfer_to_escrow(user.id, table_id, 500, unique_key("summary_buy_in"))
+        .await
+        .expect("Buy-in should succeed");
+    wallet_mgr
+        .transfer_from_escrow(user.id, table_id, 200, unique_key("summary_cash_out"))
+        .await
+        .expect("Cash-out should succeed");
+
+    let summary = wallet_mgr
+        .summarize(user.id, SummaryPeriod::Day)
+        .await
+        .expect("Should summarize wallet");
+
+    assert_eq!(summary.faucet_claimed, claim.amount);
+    assert_eq!(summary.buy_ins, 500);
+    assert_eq!(summary.cash_outs, 200);
+    assert_eq!(summary.net_win_loss, -300);
+    assert_eq!(summary.rake_paid, 0);
+    assert_eq!(summary.other, 0);
+    assert!(
+        summary.is_consistent(),
+        "Buckets should sum to balance change"
+    );
+
+    let ending_balance = wallet_mgr.get_wallet(user.id).await.unwrap().balance;
+    assert_eq!(summary.balance_change, ending_balance - starting_balance);
+
+    cleanup_table_escrow(&pool, table_id).await;
+    cleanup_user(&pool, username).await;
+}
+
+#[tokio::test]
+async fn test_frozen_wallet_rejects_buy_in_but_allows_cash_out() {
+    let (wallet_mgr, auth_mgr, pool) = setup_managers().await;
+    let username = "test_frozen_wallet";
+    let table_id = 1006;
+    cleanup_user(&pool, username).await;
+    cleanup_table_escrow(&pool, table_id).await;
+
+    let user = auth_mgr
+        .register(RegisterRequest {
+            username: username.to_string(),
+            password: "SecurePass123!".to_string(),
+            display_name: username.to_string(),
+            email: None,
+        })
+        .await
+        .expect("Registration should succeed");
+
+    // Already seated with chips in escrow when the wallet gets frozen
+    wallet_mgr
+        .transfer_to_escrow(user.id, table_id, 500, unique_key("frozen_setup"))
+        .await
+        .expect("Setup transfer should succeed");
+
+    wallet_mgr
+        .freeze(user.id, "Chip dumping", Some(1), None)
+        .await
+        .expect("Freeze should succeed");
+
+    let wallet = wallet_mgr.get_wallet(user.id).await.unwrap();
+    assert!(wallet.frozen);
+    assert_eq!(wallet.frozen_reason.as_deref(), Some("Chip dumping"));
+
+    // The freeze was audited in the same transaction
+    let audited = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM audit_log
+         WHERE action = 'freeze_wallet' AND target_user_id = $1 AND details = 'Chip dumping'",
+    )
+    .bind(user.id)
+    .fetch_one(pool.as_ref())
+    .await
+    .unwrap();
+    assert_eq!(audited, 1);
+
+    // Buy-ins and faucet claims are rejected without moving any chips
+    let buy_in = wallet_mgr
+        .transfer_to_escrow(user.id, table_id, 500, unique_key("frozen_buy_in"))
+        .await;
+    assert!(matches!(buy_in, Err(WalletError::Frozen(id)) if id == user.id));
+    let faucet = wallet_mgr.claim_faucet(user.id).await;
+    assert!(matches!(faucet, Err(WalletError::Frozen(_))));
+    assert_eq!(
+        wallet_mgr.get_wallet(user.id).await.unwrap().balance,
+        wallet.balance
+    );
+
+    // Cashing out still works so they can leave the table
+    let balance = wallet_mgr
+        .transfer_from_escrow(user.id, table_id, 500, unique_key("frozen_cash_out"))
+        .await
+        .expect("Cash-out should succeed while frozen");
+    assert_eq!(balance, wallet.balance + 500);
+
+    // Unfreezing allows buy-ins again
+    wallet_mgr
+        .unfreeze(user.id, Some(1), None)
+        .await
+        .expect("Unfreeze should succeed");
+    assert!(!wallet_mgr.get_wallet(user.id).await.unwrap().frozen);
+    wallet_mgr
+        .transfer_to_escrow(user.id, table_id, 500, unique_key("unfrozen_buy_in"))
+        .await
+        .expect("Buy-in should succeed after unfreezing");
+
+    cleanup_table_escrow(&pool, table_id).await;
+    cleanup_user(&pool, username).await;
+}
+
+#[tokio::test]
+async fn test_reconcile_reports_stranded_escrow() {
+    let (wallet_mgr, _auth_mgr, pool) = setup_managers().await;
+    let table_id = 1007;
+
+    // Chips left in escrow for a table that isn't running
+    sqlx::query("INSERT INTO table_escrows (table_id, balance) VALUES ($1, 750) ON CONFLICT (table_id) DO UPDATE SET balance = 750")
+        .bind(table_id)
+        .execute(pool.as_ref())
+        .await
+        .expect("Should create table escrow");
+
+    let expected = EscrowDiscrepancy {
+        table_id,
+        escrow_balance: 750,
+        table_chips: None,
+        chips_returned: 0,
+    };
+
+    // Reporting is read-only, so running it twice finds the same thing
+    for _ in 0..2 {
+        let report = wallet_mgr
+            .reconcile_escrow(&HashMap::new(), false)
+            .await
+            .expect("Reconciliation should succeed");
+        assert!(report.escrows_checked >= 1);
+        assert!(report.discrepancies.contains(&expected));
+        assert_eq!(wallet_mgr.get_escrow(table_id).await.unwrap().balance, 750);
+    }
+
+    // No buy-ins account for the chips, so nothing can be returned
+    let report = wallet_mgr
+        .reconcile_escrow(&HashMap::new(), true)
+        .await
+        .expect("Reconciliation should succeed");
+    assert!(report.discrepancies.contains(&expected));
+    assert_eq!(wallet_mgr.get_escrow(table_id).await.unwrap().balance, 750);
+
+    // A running table whose seated chips match its escrow is clean
+    let table_chips = HashMap::from([(table_id, Some(750))]);
+    let report = wallet_mgr
+        .reconcile_escrow(&table_chips, false)
+        .await
+        .expect("Reconciliation should succeed");
+    assert!(report.discrepancies.iter().all(|d| d.table_id != table_id));
+
+    cleanup_table_escrow(&pool, table_id).await;
+}
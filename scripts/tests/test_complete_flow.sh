@@ -41,7 +41,7 @@ echo "-------------------------------------"
 TABLES=$(curl -s http://localhost:8080/api/tables)
 echo "$TABLES" | jq .
 
-TABLE_COUNT=$(echo "$TABLES" | jq 'length')
+TABLE_COUNT=$(echo "$TABLES" | jq '.tables | length')
 if [ "$TABLE_COUNT" -gt 0 ]; then
     echo "✅ Found $TABLE_COUNT table(s)"
 else
@@ -49,8 +49,8 @@ else
     exit 1
 fi
 
-TABLE_ID=$(echo "$TABLES" | jq -r '.[0].id')
-TABLE_NAME=$(echo "$TABLES" | jq -r '.[0].name')
+TABLE_ID=$(echo "$TABLES" | jq -r '.tables[0].id')
+TABLE_NAME=$(echo "$TABLES" | jq -r '.tables[0].name')
 echo "Using table: $TABLE_NAME (ID: $TABLE_ID)"
 echo
 